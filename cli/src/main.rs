@@ -8,27 +8,37 @@
 
 mod debug;
 mod helper;
+mod repl;
 
 use boa_engine::{
     builtins::promise::PromiseState,
     context::ContextBuilder,
-    job::{FutureJob, JobQueue, NativeJob},
     js_string,
-    module::{Module, SimpleModuleLoader},
+    module::SimpleModuleLoader,
     optimizer::OptimizerOptions,
     property::Attribute,
     script::Script,
     vm::flowgraph::{Direction, Graph},
-    Context, JsError, JsNativeError, JsResult, Source,
+    Context, JsError, JsResult, Source,
+};
+use boa_runtime::{
+    CommonJs, CommonJsModuleLoader, Console, Crypto, EventLoop, Events, ImportMap,
+    NodeModuleLoader, OsRandom, Permissions, Streams, StructuredClone, TextDecoder, TextEncoder,
+    Timers, Url, UrlSearchParams, WorkerConfig, Workers,
 };
-use boa_runtime::Console;
 use clap::{Parser, ValueEnum, ValueHint};
 use colored::Colorize;
 use debug::init_boa_debug_object;
 use rustyline::{config::Config, error::ReadlineError, EditMode, Editor};
 use std::{
-    cell::RefCell, collections::VecDeque, eprintln, fs::read, fs::OpenOptions, io, path::PathBuf,
-    println, rc::Rc,
+    eprintln,
+    fs::read,
+    fs::OpenOptions,
+    io,
+    path::{Path, PathBuf},
+    println,
+    rc::Rc,
+    time::Duration,
 };
 
 #[cfg(all(
@@ -128,15 +138,92 @@ struct Opt {
     /// Root path from where the module resolver will try to load the modules.
     #[arg(long, short = 'r', default_value_os_t = PathBuf::from("."), requires = "mod")]
     root: PathBuf,
+
+    /// Import map remapping the specifiers of the imported modules.
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    import_map: Option<PathBuf>,
+
+    /// Allow reading the given paths, or any path if none is given. The module root and the
+    /// directories of the evaluated files can always be read.
+    #[arg(long, value_name = "PATH", num_args = 0.., require_equals = true, value_delimiter = ',')]
+    allow_read: Option<Vec<PathBuf>>,
+
+    /// Allow writing the given paths, or any path if none is given.
+    #[arg(long, value_name = "PATH", num_args = 0.., require_equals = true, value_delimiter = ',')]
+    allow_write: Option<Vec<PathBuf>>,
+
+    /// Allow connecting to the given hosts, or any host if none is given.
+    #[arg(long, value_name = "HOST", num_args = 0.., require_equals = true, value_delimiter = ',')]
+    allow_net: Option<Vec<String>>,
+
+    /// Allow accessing the given environment variables, or any variable if none is given.
+    #[arg(long, value_name = "NAME", num_args = 0.., require_equals = true, value_delimiter = ',')]
+    allow_env: Option<Vec<String>>,
+
+    /// Allow all the permissions.
+    #[arg(long, short = 'A')]
+    allow_all: bool,
 }
 
 impl Opt {
+    /// Returns the permissions granted to the scripts by the `--allow-*` flags.
+    ///
+    /// Reading the module root and the directories of the evaluated files is always granted,
+    /// which allows the scripts to import their modules and to `require` their dependencies.
+    fn permissions(&self) -> Permissions {
+        if self.allow_all {
+            return Permissions::all();
+        }
+        let permissions = self.files.iter().filter_map(|file| file.parent()).fold(
+            Permissions::none().allow_read(&self.root),
+            Permissions::allow_read,
+        );
+        let permissions = grant(
+            permissions,
+            self.allow_read.as_deref(),
+            Permissions::allow_all_read,
+            |permissions, path| permissions.allow_read(path),
+        );
+        let permissions = grant(
+            permissions,
+            self.allow_write.as_deref(),
+            Permissions::allow_all_write,
+            |permissions, path| permissions.allow_write(path),
+        );
+        let permissions = grant(
+            permissions,
+            self.allow_net.as_deref(),
+            Permissions::allow_all_net,
+            |permissions, host| permissions.allow_net(host),
+        );
+        grant(
+            permissions,
+            self.allow_env.as_deref(),
+            Permissions::allow_all_env,
+            |permissions, name| permissions.allow_env(name),
+        )
+    }
+
     /// Returns whether a dump flag has been used.
     const fn has_dump_flag(&self) -> bool {
         self.dump_ast.is_some()
     }
 }
 
+/// Grants the values of an `--allow-*` flag, or every value if the flag is given without any.
+fn grant<T>(
+    permissions: Permissions,
+    values: Option<&[T]>,
+    all: fn(Permissions) -> Permissions,
+    one: fn(Permissions, &T) -> Permissions,
+) -> Permissions {
+    match values {
+        None => permissions,
+        Some([]) => all(permissions),
+        Some(values) => values.iter().fold(permissions, one),
+    }
+}
+
 #[derive(Debug, Copy, Clone, Default, ValueEnum)]
 enum DumpFormat {
     /// The different types of format available for dumping.
@@ -252,10 +339,11 @@ fn generate_flowgraph(
 fn evaluate_files(
     args: &Opt,
     context: &mut Context,
-    loader: &SimpleModuleLoader,
+    loader: &NodeModuleLoader<CommonJsModuleLoader<SimpleModuleLoader>>,
 ) -> Result<(), io::Error> {
     for file in &args.files {
         let buffer = read(file)?;
+        let path = file.canonicalize()?;
 
         if args.has_dump_flag() {
             if let Err(e) = dump(&buffer, args, context) {
@@ -273,16 +361,14 @@ fn evaluate_files(
             }
         } else if args.module {
             let result: JsResult<PromiseState> = (|| {
-                let module = Module::parse(Source::from_bytes(&buffer), None, context)?;
-
-                loader.insert(
-                    file.canonicalize()
-                        .map_err(|e| JsNativeError::typ().with_message(e.to_string()))?,
-                    module.clone(),
-                );
+                // Parses the static imports of the module in parallel, resolving and checking
+                // them like the imports of the loaded modules.
+                let module = loader.preload(&path, context)?;
 
                 let promise = module.load_link_evaluate(context);
 
+                // Like a script, a module runs to completion, so this blocks until all of its
+                // timers and workers are done.
                 context.run_jobs();
                 Ok(promise.state())
             })();
@@ -312,10 +398,11 @@ fn evaluate_files(
                 }
             }
         } else {
-            match context.eval(Source::from_bytes(&buffer)) {
+            match context.eval(Source::from_reader(buffer.as_slice(), Some(&path))) {
                 Ok(v) => println!("{}", v.display()),
                 Err(v) => eprintln!("Uncaught {v}"),
             }
+            // Blocks until all the timers and workers started by the script are done.
             context.run_jobs();
         }
     }
@@ -329,13 +416,19 @@ fn main() -> Result<(), io::Error> {
 
     let args = Opt::parse();
 
-    let queue = Rc::new(Jobs::default());
+    let event_loop = Rc::new(EventLoop::new());
     let loader = Rc::new(
         SimpleModuleLoader::new(&args.root)
+            .and_then(|loader| CommonJsModuleLoader::new(loader, &args.root))
+            .and_then(|loader| NodeModuleLoader::new(loader, &args.root))
+            .and_then(|loader| match &args.import_map {
+                Some(path) => Ok(loader.with_import_map(ImportMap::load(path)?)),
+                None => Ok(loader),
+            })
             .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?,
     );
     let mut context = ContextBuilder::new()
-        .job_queue(queue)
+        .job_queue(event_loop.clone())
         .module_loader(loader.clone())
         .build()
         .expect("cannot fail with default global object");
@@ -343,8 +436,11 @@ fn main() -> Result<(), io::Error> {
     // Strict mode
     context.strict(args.strict);
 
-    // Add `console`.
-    add_runtime(&mut context);
+    // Restrict the runtime APIs to the permissions granted by the flags.
+    args.permissions().register(&mut context);
+
+    // Add `console` and the timer functions.
+    add_runtime(&mut context, &event_loop, &args.root);
 
     // Trace Output
     context.set_trace(args.trace);
@@ -409,7 +505,7 @@ fn main() -> Result<(), io::Error> {
                             Err(v) => eprintln!("Uncaught {v}"),
                         }
                     } else {
-                        match context.eval(Source::from_bytes(line.trim_end())) {
+                        match repl::eval(line.trim_end(), args.strict, &event_loop, &mut context) {
                             Ok(v) => {
                                 println!("{}", v.display());
                             }
@@ -417,7 +513,10 @@ fn main() -> Result<(), io::Error> {
                                 eprintln!("{}: {}", "Uncaught".red(), v.to_string().red());
                             }
                         }
-                        context.run_jobs();
+                        // Only run the tasks that are ready, since waiting for all the timers and
+                        // workers would block the prompt (forever with `setInterval`). The
+                        // remaining tasks run after the next lines.
+                        event_loop.advance(Duration::ZERO, &mut context);
                     }
                 }
 
@@ -438,38 +537,41 @@ fn main() -> Result<(), io::Error> {
     Ok(())
 }
 
-/// Adds the CLI runtime to the context.
-fn add_runtime(context: &mut Context) {
+/// Adds the CLI runtime to the context, resolving the specifiers passed to `require` against the
+/// module `root`.
+fn add_runtime(context: &mut Context, event_loop: &Rc<EventLoop>, root: &Path) {
+    Events::register(event_loop, context).expect("the event classes shouldn't exist");
+    add_globals(context, event_loop);
+    Workers::register(
+        event_loop,
+        WorkerConfig::new().with_init(add_globals),
+        context,
+    )
+    .expect("the Worker class shouldn't exist");
+    CommonJs::register(root, context).expect("the require function shouldn't exist");
+}
+
+/// Adds the globals shared by the main context and the contexts of the workers, which register
+/// the event classes themselves.
+fn add_globals(context: &mut Context, event_loop: &Rc<EventLoop>) {
     let console = Console::init(context);
     context
         .register_global_property(js_string!(Console::NAME), console, Attribute::all())
         .expect("the console object shouldn't exist");
-}
-
-#[derive(Default)]
-struct Jobs(RefCell<VecDeque<NativeJob>>);
-
-impl JobQueue for Jobs {
-    fn enqueue_promise_job(&self, job: NativeJob, _: &mut Context) {
-        self.0.borrow_mut().push_back(job);
-    }
-
-    fn run_jobs(&self, context: &mut Context) {
-        loop {
-            let jobs = std::mem::take(&mut *self.0.borrow_mut());
-            if jobs.is_empty() {
-                return;
-            }
-            for job in jobs {
-                if let Err(e) = job.call(context) {
-                    eprintln!("Uncaught {e}");
-                }
-            }
-        }
-    }
-
-    fn enqueue_future_job(&self, future: FutureJob, _: &mut Context) {
-        let job = pollster::block_on(future);
-        self.0.borrow_mut().push_back(job);
-    }
+    Timers::register(event_loop, context).expect("the timer functions shouldn't exist");
+    Streams::register(context).expect("the stream classes shouldn't exist");
+    StructuredClone::register(context).expect("the structuredClone function shouldn't exist");
+    Crypto::register(OsRandom, context).expect("the crypto object shouldn't exist");
+    context
+        .register_global_class::<TextEncoder>()
+        .expect("the TextEncoder class shouldn't exist");
+    context
+        .register_global_class::<TextDecoder>()
+        .expect("the TextDecoder class shouldn't exist");
+    context
+        .register_global_class::<Url>()
+        .expect("the URL class shouldn't exist");
+    context
+        .register_global_class::<UrlSearchParams>()
+        .expect("the URLSearchParams class shouldn't exist");
 }
@@ -0,0 +1,267 @@
+//! The evaluation of the lines of the REPL, with support for top-level `await`.
+//!
+//! Lines are evaluated as scripts, whose bindings are kept across the lines of the REPL. Scripts
+//! cannot use `await` outside of async functions, so a line using top-level `await` is rewritten
+//! into the body of an async arrow function, which is run until the promise it returns is settled.
+//! Its declarations are hoisted out of the function, in order to keep them defined for the next
+//! lines:
+//!
+//! ```js
+//! const data = await fetchData();
+//! data.length;
+//! ```
+//!
+//! is evaluated as the equivalent of:
+//!
+//! ```js
+//! let data;
+//! (async () => {
+//!     data = await fetchData();
+//!     return data.length;
+//! })()
+//! ```
+//!
+//! Hoisted `const` declarations become `let` declarations, since they are initialized by the
+//! function. The rewriting is done on the AST of the line, which is then evaluated without being
+//! printed back to source.
+
+use boa_ast::{
+    declaration::{Binding, LexicalDeclaration, VarDeclaration, Variable, VariableList},
+    expression::{
+        operator::{
+            assign::{AssignOp, AssignTarget},
+            Assign,
+        },
+        Await, Call,
+    },
+    function::{
+        ArrowFunction, AsyncArrowFunction, AsyncFunction, AsyncGenerator, Class,
+        FormalParameterList, Function, FunctionBody, Generator,
+    },
+    operations::bound_names,
+    property::MethodDefinition,
+    statement::{iteration::ForOfLoop, Return},
+    visitor::{VisitWith, Visitor},
+    Declaration, Expression, ModuleItem, Statement, StatementList, StatementListItem,
+};
+use boa_engine::{
+    builtins::promise::PromiseState, object::builtins::JsPromise, script::Script, Context, JsError,
+    JsNativeError, JsResult, JsValue, Source,
+};
+use boa_parser::Parser;
+use boa_runtime::EventLoop;
+use std::{ops::ControlFlow, path::Path};
+
+/// Evaluates a line of the REPL, waiting for the result of its top-level `await` expressions,
+/// if any.
+///
+/// Dynamic imports of the line are resolved relative to the current directory.
+pub(crate) fn eval(
+    line: &str,
+    strict: bool,
+    event_loop: &EventLoop,
+    context: &mut Context,
+) -> JsResult<JsValue> {
+    let path = std::env::current_dir().ok().map(|dir| dir.join("[repl]"));
+
+    let script = match Script::parse(
+        Source::from_reader(line.as_bytes(), path.as_deref()),
+        None,
+        context,
+    ) {
+        Ok(script) => script,
+        Err(err) => {
+            // A line that isn't a valid script may still be valid with top-level `await`.
+            let Some(wrapped) = wrap_top_level_await(line, path.as_deref(), strict, context) else {
+                return Err(err);
+            };
+            let script = Script::from_ast(wrapped?, None, path, context);
+            return eval_async(&script, event_loop, context);
+        }
+    };
+    script.evaluate(context)
+}
+
+/// Evaluates a script built by [`wrap_top_level_await`], running the event loop until the
+/// promise of its async function is settled.
+fn eval_async(script: &Script, event_loop: &EventLoop, context: &mut Context) -> JsResult<JsValue> {
+    let value = script.evaluate(context)?;
+    let Some(promise) = value
+        .as_object()
+        .and_then(|object| JsPromise::from_object(object.clone()).ok())
+    else {
+        return Ok(value);
+    };
+
+    event_loop.run_until_settled(&promise, context);
+    match promise.state() {
+        PromiseState::Fulfilled(value) => Ok(value),
+        PromiseState::Rejected(err) => Err(JsError::from_opaque(err)),
+        PromiseState::Pending => Ok(value),
+    }
+}
+
+/// Wraps `line` into an async function called by a script if it is a module using top-level
+/// `await`, hoisting its declarations out of the function.
+///
+/// Returns `None` if `line` isn't a module, or if it doesn't use top-level `await`.
+fn wrap_top_level_await(
+    line: &str,
+    path: Option<&Path>,
+    strict: bool,
+    context: &mut Context,
+) -> Option<JsResult<boa_ast::Script>> {
+    let mut parser = Parser::new(Source::from_reader(line.as_bytes(), path));
+    parser.set_identifier(context.next_parser_identifier());
+    let module = parser.parse_module(context.interner_mut()).ok()?;
+    let items = module.items().items();
+
+    if items
+        .iter()
+        .any(|item| !matches!(item, ModuleItem::StatementListItem(_)))
+    {
+        return Some(Err(JsNativeError::syntax()
+            .with_message(
+                "import and export declarations cannot be used in the REPL, use `import()` instead",
+            )
+            .into()));
+    }
+    if !TopLevelAwaitFinder.visit_module(&module).is_break() {
+        return None;
+    }
+
+    let mut hoisted = Vec::new();
+    let mut body = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        let ModuleItem::StatementListItem(item) = item else {
+            unreachable!("import and export declarations were rejected");
+        };
+        match item {
+            StatementListItem::Declaration(Declaration::Lexical(declaration)) => {
+                if let Some(variables) = hoist_variables(declaration.variable_list(), &mut body) {
+                    hoisted.push(Declaration::from(LexicalDeclaration::Let(variables)).into());
+                }
+            }
+            StatementListItem::Declaration(Declaration::Class(class)) => {
+                if let Some(name) = class.name() {
+                    let variables =
+                        VariableList::new(Box::new([Variable::from_identifier(name, None)]))
+                            .expect("the list has a variable");
+                    hoisted.push(Declaration::from(LexicalDeclaration::Let(variables)).into());
+                    body.push(assign(name.into(), class.clone().into()));
+                } else {
+                    body.push(Statement::Expression(class.clone().into()).into());
+                }
+            }
+            StatementListItem::Declaration(_) => hoisted.push(item.clone()),
+            StatementListItem::Statement(Statement::Var(declaration)) => {
+                if let Some(variables) = hoist_variables(&declaration.0, &mut body) {
+                    hoisted.push(Statement::from(VarDeclaration(variables)).into());
+                }
+            }
+            StatementListItem::Statement(Statement::Expression(expression))
+                if i + 1 == items.len() =>
+            {
+                body.push(Statement::from(Return::new(Some(expression.clone()))).into());
+            }
+            StatementListItem::Statement(_) => body.push(item.clone()),
+        }
+    }
+
+    let function = AsyncArrowFunction::new(
+        None,
+        FormalParameterList::default(),
+        FunctionBody::new(StatementList::new(body, strict)),
+    );
+    let call = Call::new(function.into(), Box::default());
+    hoisted.push(Statement::Expression(call.into()).into());
+
+    Some(Ok(boa_ast::Script::new(StatementList::new(
+        hoisted, strict,
+    ))))
+}
+
+/// Returns the declaration of `variables` without their initializers, which are added to `body`
+/// as assignments.
+///
+/// Returns `None` if `variables` doesn't bind any name.
+fn hoist_variables(
+    variables: &VariableList,
+    body: &mut Vec<StatementListItem>,
+) -> Option<VariableList> {
+    for variable in variables.as_ref() {
+        let Some(init) = variable.init() else {
+            continue;
+        };
+        let target = match variable.binding() {
+            Binding::Identifier(name) => AssignTarget::Identifier(*name),
+            Binding::Pattern(pattern) => AssignTarget::Pattern(pattern.clone()),
+        };
+        body.push(assign(target, init.clone()));
+    }
+
+    let names = bound_names(variables)
+        .into_iter()
+        .map(|name| Variable::from_identifier(name, None))
+        .collect::<Box<[_]>>();
+    VariableList::new(names)
+}
+
+/// Creates the statement assigning `value` to `target`.
+fn assign(target: AssignTarget, value: Expression) -> StatementListItem {
+    let assign = Assign::new(AssignOp::Assign, target, value);
+    Statement::Expression(assign.into()).into()
+}
+
+/// Finds the `await` expressions and `for await` loops outside of functions and classes.
+struct TopLevelAwaitFinder;
+
+impl<'ast> Visitor<'ast> for TopLevelAwaitFinder {
+    type BreakTy = ();
+
+    fn visit_await(&mut self, _: &'ast Await) -> ControlFlow<Self::BreakTy> {
+        ControlFlow::Break(())
+    }
+
+    fn visit_for_of_loop(&mut self, node: &'ast ForOfLoop) -> ControlFlow<Self::BreakTy> {
+        if node.r#await() {
+            return ControlFlow::Break(());
+        }
+        node.visit_with(self)
+    }
+
+    fn visit_function(&mut self, _: &'ast Function) -> ControlFlow<Self::BreakTy> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_generator(&mut self, _: &'ast Generator) -> ControlFlow<Self::BreakTy> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_async_function(&mut self, _: &'ast AsyncFunction) -> ControlFlow<Self::BreakTy> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_async_generator(&mut self, _: &'ast AsyncGenerator) -> ControlFlow<Self::BreakTy> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_arrow_function(&mut self, _: &'ast ArrowFunction) -> ControlFlow<Self::BreakTy> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_async_arrow_function(
+        &mut self,
+        _: &'ast AsyncArrowFunction,
+    ) -> ControlFlow<Self::BreakTy> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_method_definition(&mut self, _: &'ast MethodDefinition) -> ControlFlow<Self::BreakTy> {
+        ControlFlow::Continue(())
+    }
+
+    fn visit_class(&mut self, _: &'ast Class) -> ControlFlow<Self::BreakTy> {
+        ControlFlow::Continue(())
+    }
+}
@@ -21,7 +21,7 @@ use crate::{
     string::{common::StaticJsStrings, utf16},
     symbol::JsSymbol,
     value::{IntegerOrNan, JsValue, PreferredType},
-    Context, JsArgs, JsData, JsError, JsResult, JsString,
+    Context, JsArgs, JsData, JsResult, JsString,
 };
 use boa_gc::{Finalize, Trace};
 use boa_profiler::Profiler;
@@ -30,6 +30,9 @@ use utils::{
     make_date, make_day, make_time, parse_date, replace_params, time_clip, DateParameters,
 };
 
+#[cfg(feature = "intl")]
+use crate::builtins::intl::date_time_format::{DateTimeFormat, DateTimeReqs};
+
 pub(crate) mod utils;
 
 #[cfg(test)]
@@ -1328,58 +1331,140 @@ impl Date {
     /// The `toLocaleDateString()` method returns the date portion of the given Date instance according
     /// to language-specific conventions.
     ///
+    /// Note that without the `intl` feature this returns the same string as `toDateString()`.
+    ///
     /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [ECMA-402 reference][spec-402]
     ///  - [MDN documentation][mdn]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.tolocaledatestring
+    /// [spec-402]: https://tc39.es/ecma402/#sup-date.prototype.tolocaledatestring
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/toLocaleDateString
     pub(crate) fn to_locale_date_string(
-        _this: &JsValue,
-        _args: &[JsValue],
-        _context: &mut Context,
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
     ) -> JsResult<JsValue> {
-        Err(JsError::from_opaque(JsValue::new(js_string!(
-            "Function Unimplemented"
-        ))))
+        #[cfg(feature = "intl")]
+        {
+            // 3. Let dateFormat be ? CreateDateTimeFormat(%DateTimeFormat%, locales, options, date, date).
+            Self::format_locale_string(
+                this,
+                args,
+                &DateTimeReqs::Date,
+                &DateTimeReqs::Date,
+                context,
+            )
+        }
+
+        #[cfg(not(feature = "intl"))]
+        {
+            Self::to_date_string(this, args, context)
+        }
     }
 
     /// [`Date.prototype.toLocaleString()`][spec].
     ///
     /// The `toLocaleString()` method returns a string representing the specified Date object.
     ///
+    /// Note that without the `intl` feature this returns the same string as `toString()`.
+    ///
     /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [ECMA-402 reference][spec-402]
     ///  - [MDN documentation][mdn]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.tolocalestring
+    /// [spec-402]: https://tc39.es/ecma402/#sup-date.prototype.tolocalestring
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/toLocaleString
     pub(crate) fn to_locale_string(
-        _this: &JsValue,
-        _: &[JsValue],
-        _context: &mut Context,
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
     ) -> JsResult<JsValue> {
-        Err(JsError::from_opaque(JsValue::new(js_string!(
-            "Function Unimplemented]"
-        ))))
+        #[cfg(feature = "intl")]
+        {
+            // 3. Let dateFormat be ? CreateDateTimeFormat(%DateTimeFormat%, locales, options, any, all).
+            Self::format_locale_string(
+                this,
+                args,
+                &DateTimeReqs::AnyAll,
+                &DateTimeReqs::AnyAll,
+                context,
+            )
+        }
+
+        #[cfg(not(feature = "intl"))]
+        {
+            Self::to_string(this, args, context)
+        }
     }
 
     /// [`Date.prototype.toLocaleTimeString()`][spec].
     ///
     /// The `toLocaleTimeString()` method returns the time portion of a Date object in human readable
-    /// form in American English.
+    /// form according to language-specific conventions.
+    ///
+    /// Note that without the `intl` feature this returns the same string as `toTimeString()`.
     ///
     /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [ECMA-402 reference][spec-402]
     ///  - [MDN documentation][mdn]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-date.prototype.tolocaletimestring
+    /// [spec-402]: https://tc39.es/ecma402/#sup-date.prototype.tolocaletimestring
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/toLocaleTimeString
     pub(crate) fn to_locale_time_string(
-        _this: &JsValue,
-        _args: &[JsValue],
-        _context: &mut Context,
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        #[cfg(feature = "intl")]
+        {
+            // 3. Let timeFormat be ? CreateDateTimeFormat(%DateTimeFormat%, locales, options, time, time).
+            Self::format_locale_string(
+                this,
+                args,
+                &DateTimeReqs::Time,
+                &DateTimeReqs::Time,
+                context,
+            )
+        }
+
+        #[cfg(not(feature = "intl"))]
+        {
+            Self::to_time_string(this, args, context)
+        }
+    }
+
+    /// Shared steps of the `toLocaleString`, `toLocaleDateString` and `toLocaleTimeString`
+    /// methods.
+    #[cfg(feature = "intl")]
+    fn format_locale_string(
+        this: &JsValue,
+        args: &[JsValue],
+        required: &DateTimeReqs,
+        defaults: &DateTimeReqs,
+        context: &mut Context,
     ) -> JsResult<JsValue> {
-        Err(JsError::from_opaque(JsValue::new(js_string!(
-            "Function Unimplemented]"
-        ))))
+        // 1. Let x be ? thisTimeValue(this value).
+        // 2. If x is NaN, return "Invalid Date".
+        let Some(x) = this_time_value(this)? else {
+            return Ok(js_string!("Invalid Date").into());
+        };
+
+        let format = DateTimeFormat::new(
+            args.get_or_undefined(0),
+            args.get_or_undefined(1),
+            required,
+            defaults,
+            context,
+        )?;
+
+        // 4. Return ? FormatDateTime(dateFormat, x).
+        format.format_date_time(x, context).map(JsValue::from)
     }
 
     /// [`Date.prototype.toString()`][spec].
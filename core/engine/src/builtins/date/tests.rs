@@ -766,6 +766,18 @@ fn date_proto_to_date_string() {
     )]);
 }
 
+#[cfg(not(feature = "intl"))]
+#[test]
+fn date_proto_to_locale_string() {
+    run_test_actions([
+        TestAction::run("let date = new Date(2020, 6, 8, 9, 16, 15, 779)"),
+        TestAction::assert("date.toLocaleString() === date.toString()"),
+        TestAction::assert("date.toLocaleDateString() === date.toDateString()"),
+        TestAction::assert("date.toLocaleTimeString() === date.toTimeString()"),
+        TestAction::assert_eq("new Date(NaN).toLocaleString()", js_string!("Invalid Date")),
+    ]);
+}
+
 #[test]
 fn date_proto_to_gmt_string() {
     run_test_actions([TestAction::assert_eq(
@@ -114,7 +114,7 @@ pub(crate) fn make_date(day: i64, time: i64) -> Option<i64> {
 /// Otherwise, returns `None`.
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-timeclip
-pub(super) fn time_clip(time: i64) -> Option<i64> {
+pub(crate) fn time_clip(time: i64) -> Option<i64> {
     // 1. If time is not finite, return NaN.
     // 2. If abs(ℝ(time)) > 8.64 × 10^15, return NaN.
     // 3. Return 𝔽(! ToIntegerOrInfinity(time)).
@@ -0,0 +1,1654 @@
+//! This module implements the global `Intl.DateTimeFormat` object.
+//!
+//! `Intl.DateTimeFormat` is a built-in object that has properties and methods for date and time i18n.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma402/#datetimeformat-objects
+
+use std::cell::OnceCell;
+
+use boa_gc::{custom_trace, Finalize, Trace};
+use boa_profiler::Profiler;
+use chrono::{
+    Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, Offset, TimeZone as _, Timelike,
+};
+use chrono_tz::Tz;
+use icu_calendar::{types::Time, AnyCalendar, AnyCalendarKind, Date, DateTime};
+use icu_datetime::{
+    fields::{Field, FieldSymbol, Hour},
+    options::{components, length, preferences},
+    pattern::{runtime::Pattern, PatternItem},
+    provider::calendar::TimeLengthsV1Marker,
+    DateTimeFormatter, DateTimeFormatterOptions,
+};
+use icu_decimal::provider::DecimalSymbolsV1Marker;
+use icu_locid::{extensions::unicode::Value, extensions_unicode_key as key, Locale};
+use icu_provider::{DataLocale, DataProvider, DataRequest, DataRequestMetadata, DataResponse};
+use icu_timezone::{
+    provider::names::{Bcp47ToIanaMapV1Marker, IanaToBcp47MapV1Marker},
+    IanaBcp47RoundTripMapper, TimeZoneBcp47Id,
+};
+
+use crate::{
+    builtins::{
+        date::utils::time_clip, options::get_option, Array, BuiltInBuilder, BuiltInConstructor,
+        BuiltInObject, IntrinsicObject, OrdinaryObject,
+    },
+    context::{
+        icu::IntlProvider,
+        intrinsics::{Intrinsics, StandardConstructor, StandardConstructors},
+    },
+    js_string,
+    native_function::NativeFunction,
+    object::{
+        internal_methods::get_prototype_from_constructor, FunctionObjectBuilder, JsFunction,
+        JsObject,
+    },
+    property::Attribute,
+    realm::Realm,
+    string::{common::StaticJsStrings, utf16},
+    symbol::JsSymbol,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsString, JsValue,
+};
+
+use super::{
+    locale::{canonicalize_locale_list, resolve_locale, supported_locales, validate_extension},
+    options::{get_number_option, IntlOptions},
+    Service,
+};
+
+#[cfg(all(test, feature = "intl_bundled"))]
+mod tests;
+
+mod options;
+pub(crate) use options::*;
+
+/// JavaScript `Intl.DateTimeFormat` object.
+#[derive(Debug, Finalize, JsData)]
+pub(crate) struct DateTimeFormat {
+    locale: Locale,
+    calendar: AnyCalendarKind,
+    numbering_system: Option<Value>,
+    time_zone: TimeZone,
+    hour_cycle: Option<preferences::HourCycle>,
+    date_style: Option<length::Date>,
+    time_style: Option<length::Time>,
+    day_period: Option<components::Text>,
+    fractional_second_digits: Option<u8>,
+    time_zone_name: Option<TimeZoneName>,
+    day_period_only: bool,
+    formatter: DateTimeFormatter,
+    data_locale: DataLocale,
+    field_formatters: OnceCell<Vec<(PartKind, DateTimeFormatter)>>,
+    bound_format: Option<JsFunction>,
+}
+
+// SAFETY: only `bound_format` is a traceable object.
+unsafe impl Trace for DateTimeFormat {
+    custom_trace!(this, mark, mark(&this.bound_format));
+}
+
+#[derive(Debug, Clone, Default)]
+pub(in crate::builtins::intl) struct DateTimeFormatLocaleOptions {
+    calendar: Option<Value>,
+    numbering_system: Option<Value>,
+    hour_cycle: Option<preferences::HourCycle>,
+    hour12: Option<bool>,
+}
+
+impl Service for DateTimeFormat {
+    type LangMarker = TimeLengthsV1Marker;
+
+    type LocaleOptions = DateTimeFormatLocaleOptions;
+
+    fn resolve(locale: &mut Locale, options: &mut Self::LocaleOptions, provider: &IntlProvider) {
+        let keywords = &locale.extensions.unicode.keywords;
+
+        let is_valid_calendar = |ca: &Value| AnyCalendarKind::get_for_bcp47_value(ca).is_some();
+        let locale_ca = keywords.get(&key!("ca")).cloned().filter(is_valid_calendar);
+        let option_ca = options.calendar.take().filter(is_valid_calendar);
+
+        let is_valid_nu = |nu: &Value| {
+            validate_extension::<DecimalSymbolsV1Marker>(
+                locale.id.clone(),
+                key!("nu"),
+                nu,
+                provider,
+            )
+        };
+        let locale_nu = keywords.get(&key!("nu")).cloned().filter(is_valid_nu);
+        let option_nu = options.numbering_system.take().filter(is_valid_nu);
+
+        let locale_hc = keywords
+            .get(&key!("hc"))
+            .and_then(|hc| hour_cycle_from_value(hc).map(|cycle| (hc.clone(), cycle)));
+
+        // If `hour12` is provided, the `hc` keyword is ignored and removed from the locale.
+        let option_hc = if options.hour12.is_some() {
+            None
+        } else {
+            options.hour_cycle
+        };
+
+        locale.extensions.unicode.clear();
+
+        // The extensions are only kept in the locale if the options didn't override them
+        // with a different value.
+        if let Some(ca) = &locale_ca {
+            if option_ca.as_ref().map_or(true, |opt| opt == ca) {
+                locale
+                    .extensions
+                    .unicode
+                    .keywords
+                    .set(key!("ca"), ca.clone());
+            }
+        }
+        if let Some(nu) = &locale_nu {
+            if option_nu.as_ref().map_or(true, |opt| opt == nu) {
+                locale
+                    .extensions
+                    .unicode
+                    .keywords
+                    .set(key!("nu"), nu.clone());
+            }
+        }
+        if let Some((hc, cycle)) = &locale_hc {
+            if options.hour12.is_none() && option_hc.map_or(true, |opt| opt == *cycle) {
+                locale
+                    .extensions
+                    .unicode
+                    .keywords
+                    .set(key!("hc"), hc.clone());
+            }
+        }
+
+        options.calendar = option_ca.or(locale_ca);
+        options.numbering_system = option_nu.or(locale_nu);
+        options.hour_cycle = if options.hour12.is_some() {
+            None
+        } else {
+            option_hc.or(locale_hc.map(|(_, cycle)| cycle))
+        };
+    }
+}
+
+impl IntrinsicObject for DateTimeFormat {
+    fn init(realm: &Realm) {
+        let _timer = Profiler::global().start_event(std::any::type_name::<Self>(), "init");
+
+        let format = BuiltInBuilder::callable(realm, Self::format)
+            .name(js_string!("get format"))
+            .build();
+
+        BuiltInBuilder::from_standard_constructor::<Self>(realm)
+            .static_method(
+                Self::supported_locales_of,
+                js_string!("supportedLocalesOf"),
+                1,
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("Intl.DateTimeFormat"),
+                Attribute::CONFIGURABLE,
+            )
+            .accessor(
+                js_string!("format"),
+                Some(format),
+                None,
+                Attribute::CONFIGURABLE,
+            )
+            .method(Self::format_to_parts, js_string!("formatToParts"), 1)
+            .method(Self::format_range, js_string!("formatRange"), 2)
+            .method(
+                Self::format_range_to_parts,
+                js_string!("formatRangeToParts"),
+                2,
+            )
+            .method(Self::resolved_options, js_string!("resolvedOptions"), 0)
+            .build();
+    }
+
+    fn get(intrinsics: &Intrinsics) -> JsObject {
+        Self::STANDARD_CONSTRUCTOR(intrinsics.constructors()).constructor()
+    }
+}
+
+impl BuiltInObject for DateTimeFormat {
+    const NAME: JsString = StaticJsStrings::DATE_TIME_FORMAT;
+}
+
+impl BuiltInConstructor for DateTimeFormat {
+    const LENGTH: usize = 0;
+
+    const STANDARD_CONSTRUCTOR: fn(&StandardConstructors) -> &StandardConstructor =
+        StandardConstructors::date_time_format;
+
+    /// The `Intl.DateTimeFormat` constructor is the `%DateTimeFormat%` intrinsic object and a standard built-in property of the `Intl` object.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#datetimeformat-objects
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat
+    fn constructor(
+        new_target: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. If NewTarget is undefined, let newTarget be the active function object, else let newTarget be NewTarget.
+        let new_target = &if new_target.is_undefined() {
+            context
+                .active_function_object()
+                .unwrap_or_else(|| {
+                    context
+                        .intrinsics()
+                        .constructors()
+                        .date_time_format()
+                        .constructor()
+                })
+                .into()
+        } else {
+            new_target.clone()
+        };
+
+        let locales = args.get_or_undefined(0);
+        let options = args.get_or_undefined(1);
+
+        // 2. Let dateTimeFormat be ? CreateDateTimeFormat(newTarget, locales, options, any, date).
+        let prototype = get_prototype_from_constructor(
+            new_target,
+            StandardConstructors::date_time_format,
+            context,
+        )?;
+        let date_time_format = Self::new(
+            locales,
+            options,
+            &DateTimeReqs::AnyAll,
+            &DateTimeReqs::Date,
+            context,
+        )?;
+
+        // 3. If the implementation supports the normative optional constructor mode of 4.3 Note 1, then
+        //     a. Let this be the this value.
+        //     b. Return ? ChainDateTimeFormat(dateTimeFormat, NewTarget, this).
+        // 4. Return dateTimeFormat.
+        Ok(JsObject::from_proto_and_data_with_shared_shape(
+            context.root_shape(),
+            prototype,
+            date_time_format,
+        )
+        .into())
+    }
+}
+
+impl DateTimeFormat {
+    /// Abstract operation [`CreateDateTimeFormat ( newTarget, locales, options, required, defaults )`][spec].
+    ///
+    /// Creates a new `DateTimeFormat` from the provided `locales` and `options`. This is also
+    /// used by the `toLocaleString` family of methods of `Date` to avoid allocating an object
+    /// for every call.
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-createdatetimeformat
+    pub(crate) fn new(
+        locales: &JsValue,
+        options: &JsValue,
+        required: &DateTimeReqs,
+        defaults: &DateTimeReqs,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        // 2. Let requestedLocales be ? CanonicalizeLocaleList(locales).
+        let requested_locales = canonicalize_locale_list(locales, context)?;
+
+        // 3. Set options to ? ToDateTimeOptions(options, required, defaults).
+        let options = to_date_time_options(options, required, defaults, context)?;
+
+        // 4. Let opt be a new Record.
+        // 5. Let matcher be ? GetOption(options, "localeMatcher", string, « "lookup", "best fit" », "best fit").
+        // 6. Set opt.[[localeMatcher]] to matcher.
+        let matcher = get_option(&options, utf16!("localeMatcher"), context)?.unwrap_or_default();
+
+        // 7. Let calendar be ? GetOption(options, "calendar", string, empty, undefined).
+        // 8. If calendar is not undefined, then
+        //     a. If calendar cannot be matched by the type Unicode locale nonterminal, throw a RangeError exception.
+        // 9. Set opt.[[ca]] to calendar.
+        let calendar = get_option::<Value>(&options, utf16!("calendar"), context)?;
+
+        // 10. Let numberingSystem be ? GetOption(options, "numberingSystem", string, empty, undefined).
+        // 11. If numberingSystem is not undefined, then
+        //     a. If numberingSystem cannot be matched by the type Unicode locale nonterminal, throw a RangeError exception.
+        // 12. Set opt.[[nu]] to numberingSystem.
+        let numbering_system = get_option::<Value>(&options, utf16!("numberingSystem"), context)?;
+
+        // 13. Let hour12 be ? GetOption(options, "hour12", boolean, empty, undefined).
+        let hour12 = get_option::<bool>(&options, utf16!("hour12"), context)?;
+
+        // 14. Let hourCycle be ? GetOption(options, "hourCycle", string, « "h11", "h12", "h23", "h24" », undefined).
+        // 15. If hour12 is not undefined, then
+        //     a. Set hourCycle to null.
+        // 16. Set opt.[[hc]] to hourCycle.
+        let hour_cycle =
+            get_option::<preferences::HourCycle>(&options, utf16!("hourCycle"), context)?;
+
+        let mut intl_options = IntlOptions {
+            matcher,
+            service_options: DateTimeFormatLocaleOptions {
+                calendar,
+                numbering_system,
+                hour_cycle,
+                hour12,
+            },
+        };
+
+        // 17. Let localeData be %DateTimeFormat%.[[LocaleData]].
+        // 18. Let r be ResolveLocale(%DateTimeFormat%.[[AvailableLocales]], requestedLocales, opt, %DateTimeFormat%.[[RelevantExtensionKeys]], localeData).
+        // 19. Set dateTimeFormat.[[Locale]] to r.[[locale]].
+        // 20. Let resolvedCalendar be r.[[ca]].
+        // 21. Set dateTimeFormat.[[Calendar]] to resolvedCalendar.
+        // 22. Set dateTimeFormat.[[NumberingSystem]] to r.[[nu]].
+        let locale = resolve_locale::<Self>(
+            &requested_locales,
+            &mut intl_options,
+            context.intl_provider(),
+        );
+        let DateTimeFormatLocaleOptions {
+            calendar,
+            numbering_system,
+            hour_cycle,
+            ..
+        } = intl_options.service_options;
+
+        // 23. Let dataLocale be r.[[dataLocale]].
+        // 24. Let dataLocaleData be localeData.[[<dataLocale>]].
+        let mut data_locale = DataLocale::from(&locale.id);
+        if let Some(ca) = &calendar {
+            data_locale.set_unicode_ext(key!("ca"), ca.clone());
+        }
+        if let Some(nu) = &numbering_system {
+            data_locale.set_unicode_ext(key!("nu"), nu.clone());
+        }
+
+        // 25. If hour12 is true, then
+        //     a. Let hc be dataLocaleData.[[hourCycle12]].
+        // 26. Else if hour12 is false, then
+        //     a. Let hc be dataLocaleData.[[hourCycle24]].
+        // 27. Else,
+        //     a. Assert: hour12 is undefined.
+        //     b. Let hc be r.[[hc]].
+        //     c. If hc is null, set hc to dataLocaleData.[[hourCycle]].
+        let hour_cycle = match hour12 {
+            Some(true) => Some(locale_hour_cycles(&data_locale, context.intl_provider()).0),
+            Some(false) => Some(locale_hour_cycles(&data_locale, context.intl_provider()).1),
+            None => hour_cycle,
+        };
+        if let Some(hc) = hour_cycle {
+            data_locale.set_unicode_ext(
+                key!("hc"),
+                Value::try_from_bytes(hour_cycle_to_str(hc).as_bytes())
+                    .expect("hour cycles must be valid unicode extension values"),
+            );
+        }
+
+        let calendar =
+            AnyCalendar::try_new_for_locale_unstable(context.intl_provider(), &data_locale)
+                .map_err(|err| JsNativeError::range().with_message(err.to_string()))?
+                .kind();
+
+        // 28. Let timeZone be ? Get(options, "timeZone").
+        let time_zone = options.get(utf16!("timeZone"), context)?;
+
+        // 29. If timeZone is undefined, then
+        //     a. Set timeZone to DefaultTimeZone().
+        // 30. Else,
+        //     a. Set timeZone to ? ToString(timeZone).
+        //     b. If IsTimeZoneOffsetString(timeZone) is true, then ...
+        //     c. Else if IsValidTimeZoneName(timeZone) is false, then
+        //         i. Throw a RangeError exception.
+        //     d. Set timeZone to CanonicalizeTimeZoneName(timeZone).
+        // 31. Set dateTimeFormat.[[TimeZone]] to timeZone.
+        let time_zone = if time_zone.is_undefined() {
+            TimeZone::Host
+        } else {
+            let time_zone = time_zone.to_string(context)?.to_std_string_escaped();
+            TimeZone::from_identifier(&time_zone, context)?.ok_or_else(|| {
+                JsNativeError::range().with_message(format!("unsupported time zone `{time_zone}`"))
+            })?
+        };
+
+        // 32. Let formatOptions be a new Record.
+        // 33. Set formatOptions.[[hourCycle]] to hc.
+        let mut bag = components::Bag::empty();
+
+        // 34. Let hasExplicitFormatComponents be false.
+        // 35. For each row of Table 6, except the header row, in table order, do
+        //     a. Let prop be the name given in the Property column of the current row.
+        //     b. If prop is "fractionalSecondDigits", then
+        //         i. Let value be ? GetNumberOption(options, "fractionalSecondDigits", 1, 3, undefined).
+        //     c. Else,
+        //         i. Let values be a List whose elements are the strings given in the Values column of the current row.
+        //         ii. Let value be ? GetOption(options, prop, string, values, undefined).
+        //     d. Set formatOptions.[[<prop>]] to value.
+        //     e. If value is not undefined, then
+        //         i. Set hasExplicitFormatComponents to true.
+        bag.weekday = get_option(&options, utf16!("weekday"), context)?;
+        bag.era = get_option(&options, utf16!("era"), context)?;
+        bag.year = get_option(&options, utf16!("year"), context)?;
+        bag.month = get_option(&options, utf16!("month"), context)?;
+        bag.day = get_option(&options, utf16!("day"), context)?;
+        let day_period = get_option::<components::Text>(&options, utf16!("dayPeriod"), context)?;
+        bag.hour = get_option(&options, utf16!("hour"), context)?;
+        bag.minute = get_option(&options, utf16!("minute"), context)?;
+        bag.second = get_option(&options, utf16!("second"), context)?;
+        let fractional_second_digits =
+            get_number_option(&options, utf16!("fractionalSecondDigits"), 1, 3, context)?;
+        let time_zone_name = get_option::<TimeZoneName>(&options, utf16!("timeZoneName"), context)?;
+
+        let has_explicit_format_components = bag != components::Bag::empty()
+            || day_period.is_some()
+            || fractional_second_digits.is_some()
+            || time_zone_name.is_some();
+
+        // 36. Let formatMatcher be ? GetOption(options, "formatMatcher", string, « "basic", "best fit" », "best fit").
+        let _format_matcher =
+            get_option::<FormatMatcher>(&options, utf16!("formatMatcher"), context)?;
+
+        // 37. Let dateStyle be ? GetOption(options, "dateStyle", string, « "full", "long", "medium", "short" », undefined).
+        // 38. Set dateTimeFormat.[[DateStyle]] to dateStyle.
+        let date_style = get_option::<length::Date>(&options, utf16!("dateStyle"), context)?;
+
+        // 39. Let timeStyle be ? GetOption(options, "timeStyle", string, « "full", "long", "medium", "short" », undefined).
+        // 40. Set dateTimeFormat.[[TimeStyle]] to timeStyle.
+        let time_style = get_option::<length::Time>(&options, utf16!("timeStyle"), context)?;
+
+        let day_period_only = day_period.is_some()
+            && bag == components::Bag::empty()
+            && fractional_second_digits.is_none();
+
+        // 41. If dateStyle is not undefined or timeStyle is not undefined, then
+        let format_options = if date_style.is_some() || time_style.is_some() {
+            // a. If hasExplicitFormatComponents is true, then
+            if has_explicit_format_components {
+                // i. Throw a TypeError exception.
+                return Err(JsNativeError::typ()
+                    .with_message(
+                        "`dateStyle` and `timeStyle` cannot be combined with explicit format components",
+                    )
+                    .into());
+            }
+
+            // b. Let styles be dataLocaleData.[[styles]].[[<resolvedCalendar>]].
+            // c. Let bestFormat be DateTimeStyleFormat(dateStyle, timeStyle, styles).
+            // NOTE: ICU4X cannot format time zones with a plain `DateTimeFormatter`, so the
+            // long time styles are formatted with the medium style, and the time zone is
+            // appended afterwards.
+            let time = time_style.map(|style| match style {
+                length::Time::Full | length::Time::Long => length::Time::Medium,
+                style => style,
+            });
+            let bag = match (date_style, time) {
+                (Some(date), Some(time)) => length::Bag::from_date_time_style(date, time),
+                (Some(date), None) => length::Bag::from_date_style(date),
+                (None, Some(time)) => length::Bag::from_time_style(time),
+                (None, None) => unreachable!("checked that at least one style was defined"),
+            };
+            DateTimeFormatterOptions::Length(bag)
+        } else {
+            // 42. Else,
+            //     a. Let needDefaults be true.
+            //     b-d. ...
+            // NOTE: The defaults were already added by `ToDateTimeOptions`.
+            //     e. Let formats be dataLocaleData.[[formats]].[[<resolvedCalendar>]].
+            //     f-h. Let bestFormat be BestFitFormatMatcher(formatOptions, formats).
+            bag.fractional_second = fractional_second_digits;
+            if day_period_only {
+                // ICU4X doesn't support formatting standalone day periods, so we format the
+                // hour with a 12-hour clock and extract the day period from the result.
+                bag.hour = Some(components::Numeric::Numeric);
+                bag.preferences = Some(preferences::Bag::from_hour_cycle(
+                    preferences::HourCycle::H12,
+                ));
+            } else {
+                bag.preferences = hour_cycle.map(preferences::Bag::from_hour_cycle);
+            }
+            DateTimeFormatterOptions::Components(bag)
+        };
+
+        let formatter = DateTimeFormatter::try_new_experimental_unstable(
+            context.intl_provider(),
+            &data_locale,
+            format_options,
+        )
+        .map_err(|err| JsNativeError::range().with_message(err.to_string()))?;
+
+        // 43. Set dateTimeFormat.[[DateTimeFormat]] to bestFormat.
+        // 44. If bestFormat has a field [[hour]], then
+        //     a. Set dateTimeFormat.[[HourCycle]] to hc.
+        // 45. Return dateTimeFormat.
+        Ok(Self {
+            locale,
+            calendar,
+            numbering_system,
+            time_zone,
+            hour_cycle,
+            date_style,
+            time_style,
+            day_period,
+            fractional_second_digits,
+            time_zone_name,
+            day_period_only,
+            formatter,
+            data_locale,
+            field_formatters: OnceCell::new(),
+            bound_format: None,
+        })
+    }
+
+    /// [`Intl.DateTimeFormat.supportedLocalesOf ( locales [ , options ] )`][spec].
+    ///
+    /// Returns an array containing those of the provided locales that are supported in date and
+    /// time formatting without having to fall back to the runtime's default locale.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.datetimeformat.supportedlocalesof
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/supportedLocalesOf
+    fn supported_locales_of(
+        _: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let locales = args.get_or_undefined(0);
+        let options = args.get_or_undefined(1);
+
+        // 1. Let availableLocales be %DateTimeFormat%.[[AvailableLocales]].
+        // 2. Let requestedLocales be ? CanonicalizeLocaleList(locales).
+        let requested_locales = canonicalize_locale_list(locales, context)?;
+
+        // 3. Return ? SupportedLocales(availableLocales, requestedLocales, options).
+        supported_locales::<<Self as Service>::LangMarker>(&requested_locales, options, context)
+            .map(JsValue::from)
+    }
+
+    /// [`get Intl.DateTimeFormat.prototype.format`][spec].
+    ///
+    /// Formats a date according to the locale and formatting options of this
+    /// `Intl.DateTimeFormat` object.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.datetimeformat.prototype.format
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/format
+    fn format(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let dtf be the this value.
+        // 2. If the implementation supports the normative optional constructor mode of 4.3 Note 1, then
+        //     a. Set dtf to ? UnwrapDateTimeFormat(dtf).
+        // 3. Perform ? RequireInternalSlot(dtf, [[InitializedDateTimeFormat]]).
+        let this = this.as_object().ok_or_else(|| {
+            JsNativeError::typ()
+                .with_message("`format` can only be called on a `DateTimeFormat` object")
+        })?;
+        let dtf_obj = this.clone();
+        let mut dtf = this.downcast_mut::<Self>().ok_or_else(|| {
+            JsNativeError::typ()
+                .with_message("`format` can only be called on a `DateTimeFormat` object")
+        })?;
+
+        // 4. If dtf.[[BoundFormat]] is undefined, then
+        //     a. Let F be a new built-in function object as defined in DateTime Format Functions (11.5.4).
+        //     b. Set F.[[DateTimeFormat]] to dtf.
+        //     c. Set dtf.[[BoundFormat]] to F.
+        let bound_format = if let Some(f) = dtf.bound_format.clone() {
+            f
+        } else {
+            let bound_format = FunctionObjectBuilder::new(
+                context.realm(),
+                // 11.5.4 DateTime Format Functions
+                // https://tc39.es/ecma402/#sec-datetime-format-functions
+                NativeFunction::from_copy_closure_with_captures(
+                    |_, args, dtf, context| {
+                        // 1. Let dtf be F.[[DateTimeFormat]].
+                        // 2. Assert: Type(dtf) is Object and dtf has an [[InitializedDateTimeFormat]] internal slot.
+                        // 3. If date is not provided or is undefined, then
+                        //     a. Let x be ! Call(%Date.now%, undefined).
+                        // 4. Else,
+                        //     a. Let x be ? ToNumber(date).
+                        let x = to_date_value(args.get_or_undefined(0), context)?;
+
+                        let dtf = dtf
+                            .downcast_ref::<Self>()
+                            .expect("checked above that the object was a date time format object");
+
+                        // 5. Return ? FormatDateTime(dtf, x).
+                        dtf.format_date_time(x, context).map(JsValue::from)
+                    },
+                    dtf_obj,
+                ),
+            )
+            .length(1)
+            .build();
+
+            dtf.bound_format = Some(bound_format.clone());
+            bound_format
+        };
+
+        // 5. Return dtf.[[BoundFormat]].
+        Ok(bound_format.into())
+    }
+
+    /// [`Intl.DateTimeFormat.prototype.formatToParts ( date )`][spec].
+    ///
+    /// Returns an array of objects containing the locale-specific tokens from which it is possible
+    /// to build custom strings while preserving the locale-specific parts.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-Intl.DateTimeFormat.prototype.formatToParts
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/formatToParts
+    fn format_to_parts(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let dtf be the this value.
+        // 2. Perform ? RequireInternalSlot(dtf, [[InitializedDateTimeFormat]]).
+        let dtf = this
+            .as_object()
+            .filter(|o| o.is::<Self>())
+            .cloned()
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("`formatToParts` can only be called on a `DateTimeFormat` object")
+            })?;
+
+        // 3. If date is undefined, then
+        //     a. Let x be ! Call(%Date.now%, undefined).
+        // 4. Else,
+        //     a. Let x be ? ToNumber(date).
+        let x = to_date_value(args.get_or_undefined(0), context)?;
+
+        // 5. Return ? FormatDateTimeToParts(dtf, x).
+        let parts = dtf
+            .downcast_ref::<Self>()
+            .expect("checked above that the object was a date time format object")
+            .partition_date_time_pattern(x, context)?;
+
+        Ok(parts_to_array(parts.into_iter().map(|part| (part, None)), context).into())
+    }
+
+    /// [`Intl.DateTimeFormat.prototype.formatRange ( startDate, endDate )`][spec].
+    ///
+    /// Formats a date range according to the locale and formatting options of this
+    /// `Intl.DateTimeFormat` object.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.datetimeformat.prototype.formatRange
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/formatRange
+    fn format_range(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let parts = Self::partition_range_from_args(this, args, "formatRange", context)?;
+
+        // 7. Return ? FormatDateTimeRange(dtf, x, y).
+        let result: String = parts.iter().map(|(part, _)| part.value.as_str()).collect();
+        Ok(js_string!(result).into())
+    }
+
+    /// [`Intl.DateTimeFormat.prototype.formatRangeToParts ( startDate, endDate )`][spec].
+    ///
+    /// Returns an array of objects containing the locale-specific tokens of a formatted date
+    /// range.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-Intl.DateTimeFormat.prototype.formatRangeToParts
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/formatRangeToParts
+    fn format_range_to_parts(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let parts = Self::partition_range_from_args(this, args, "formatRangeToParts", context)?;
+
+        // 7. Return ? FormatDateTimeRangeToParts(dtf, x, y).
+        Ok(parts_to_array(
+            parts.into_iter().map(|(part, source)| (part, Some(source))),
+            context,
+        )
+        .into())
+    }
+
+    /// Shared steps of `formatRange` and `formatRangeToParts`.
+    fn partition_range_from_args(
+        this: &JsValue,
+        args: &[JsValue],
+        method: &str,
+        context: &mut Context,
+    ) -> JsResult<Vec<(Part, RangeSource)>> {
+        // 1. Let dtf be this value.
+        // 2. Perform ? RequireInternalSlot(dtf, [[InitializedDateTimeFormat]]).
+        let dtf = this
+            .as_object()
+            .filter(|o| o.is::<Self>())
+            .cloned()
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message(format!(
+                    "`{method}` can only be called on a `DateTimeFormat` object"
+                ))
+            })?;
+
+        // 3. If startDate is undefined or endDate is undefined, throw a TypeError exception.
+        let start = args.get_or_undefined(0);
+        let end = args.get_or_undefined(1);
+        if start.is_undefined() || end.is_undefined() {
+            return Err(JsNativeError::typ()
+                .with_message("extremum of range cannot be `undefined`")
+                .into());
+        }
+
+        // 4. Let x be ? ToNumber(startDate).
+        let x = start.to_integer_or_nan(context)?;
+        // 5. Let y be ? ToNumber(endDate).
+        let y = end.to_integer_or_nan(context)?;
+
+        // PartitionDateTimeRangePattern ( dateTimeFormat, x, y )
+        // 1. Let x be TimeClip(x).
+        // 2. If x is NaN, throw a RangeError exception.
+        // 3. Let y be TimeClip(y).
+        // 4. If y is NaN, throw a RangeError exception.
+        let (Some(x), Some(y)) = (
+            x.as_integer().and_then(time_clip),
+            y.as_integer().and_then(time_clip),
+        ) else {
+            return Err(JsNativeError::range()
+                .with_message("invalid time value")
+                .into());
+        };
+
+        let dtf = dtf
+            .downcast_ref::<Self>()
+            .expect("checked above that the object was a date time format object");
+
+        let start = dtf.partition_date_time_pattern(x, context)?;
+        let end = dtf.partition_date_time_pattern(y, context)?;
+
+        // If both dates are practically equal for the fields of this format, the range is
+        // formatted as a single date.
+        if start == end {
+            return Ok(start
+                .into_iter()
+                .map(|part| (part, RangeSource::Shared))
+                .collect());
+        }
+
+        Ok(start
+            .into_iter()
+            .map(|part| (part, RangeSource::StartRange))
+            .chain(std::iter::once((
+                Part::new(PartKind::Literal, " – "),
+                RangeSource::Shared,
+            )))
+            .chain(end.into_iter().map(|part| (part, RangeSource::EndRange)))
+            .collect())
+    }
+
+    /// [`Intl.DateTimeFormat.prototype.resolvedOptions ( )`][spec].
+    ///
+    /// Returns a new object with properties reflecting the locale and date and time formatting
+    /// options computed during initialization of this `Intl.DateTimeFormat` object.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.datetimeformat.prototype.resolvedoptions
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/resolvedOptions
+    fn resolved_options(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let dtf be the this value.
+        // 2. If the implementation supports the normative optional constructor mode of 4.3 Note 1, then
+        //     a. Set dtf to ? UnwrapDateTimeFormat(dtf).
+        // 3. Perform ? RequireInternalSlot(dtf, [[InitializedDateTimeFormat]]).
+        let dtf = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message(
+                    "`resolvedOptions` can only be called on a `DateTimeFormat` object",
+                )
+            })?;
+
+        let time_zone = dtf.time_zone.identifier(context);
+
+        // 4. Let options be OrdinaryObjectCreate(%Object.prototype%).
+        let options = context
+            .intrinsics()
+            .templates()
+            .ordinary_object()
+            .create(OrdinaryObject, vec![]);
+
+        // 5. For each row of Table 7, except the header row, in table order, do
+        //     a. Let p be the Property value of the current row.
+        //     b. If there is an Internal Slot value in the current row, then
+        //         i. Let v be the value of dtf's internal slot whose name is the Internal Slot value of the current row.
+        //     c. Else,
+        //         i. Let format be dtf.[[DateTimeFormat]].
+        //         ii. If format has a field [[<p>]] and dtf.[[DateStyle]] is undefined and dtf.[[TimeStyle]] is undefined, then
+        //             1. Let v be format.[[<p>]].
+        //         iii. Else,
+        //             1. Let v be undefined.
+        //     d. If v is not undefined, then
+        //         i. If there is a Conversion value in the current row, then
+        //             1. Let conversion be the Conversion value of the current row.
+        //             2. If conversion is hour12, then
+        //                 a. If v is "h11" or "h12", set v to true. Otherwise, set v to false.
+        //             3. Else,
+        //                 a. Assert: conversion is number.
+        //                 b. Set v to 𝔽(v).
+        //         ii. Perform ! CreateDataPropertyOrThrow(options, p, v).
+        let mut push = |key: &str, value: JsValue| {
+            options
+                .create_data_property_or_throw(js_string!(key), value, context)
+                .expect("operation must not fail per the spec");
+        };
+
+        let resolved = dtf.formatter.resolve_components();
+        let has_styles = dtf.date_style.is_some() || dtf.time_style.is_some();
+        let day_period_only = dtf.day_period_only;
+
+        push("locale", js_string!(dtf.locale.to_string()).into());
+        push(
+            "calendar",
+            js_string!(dtf.calendar.as_bcp47_string()).into(),
+        );
+        push(
+            "numberingSystem",
+            js_string!(dtf
+                .numbering_system
+                .as_ref()
+                .map_or_else(|| String::from("latn"), ToString::to_string))
+            .into(),
+        );
+        push("timeZone", js_string!(time_zone).into());
+
+        if resolved.hour.is_some() && !day_period_only {
+            let hour_cycle = dtf
+                .hour_cycle
+                .or_else(|| resolved.preferences.and_then(|prefs| prefs.hour_cycle));
+            if let Some(hour_cycle) = hour_cycle {
+                push(
+                    "hourCycle",
+                    js_string!(hour_cycle_to_str(hour_cycle)).into(),
+                );
+                push(
+                    "hour12",
+                    matches!(
+                        hour_cycle,
+                        preferences::HourCycle::H11 | preferences::HourCycle::H12
+                    )
+                    .into(),
+                );
+            }
+        }
+
+        if !has_styles {
+            if let Some(weekday) = resolved.weekday {
+                push("weekday", js_string!(text_to_str(weekday)).into());
+            }
+            if let Some(era) = resolved.era {
+                push("era", js_string!(text_to_str(era)).into());
+            }
+            if let Some(year) = resolved.year {
+                push("year", js_string!(year_to_str(year)).into());
+            }
+            if let Some(month) = resolved.month {
+                push("month", js_string!(month_to_str(month)).into());
+            }
+            if let Some(day) = resolved.day {
+                push("day", js_string!(day_to_str(day)).into());
+            }
+            if let Some(day_period) = dtf.day_period {
+                push("dayPeriod", js_string!(text_to_str(day_period)).into());
+            }
+            if !day_period_only {
+                if let Some(hour) = resolved.hour {
+                    push("hour", js_string!(numeric_to_str(hour)).into());
+                }
+                if let Some(minute) = resolved.minute {
+                    push("minute", js_string!(numeric_to_str(minute)).into());
+                }
+                if let Some(second) = resolved.second {
+                    push("second", js_string!(numeric_to_str(second)).into());
+                }
+            }
+            if let Some(digits) = dtf.fractional_second_digits {
+                push("fractionalSecondDigits", digits.into());
+            }
+            if let Some(time_zone_name) = dtf.time_zone_name {
+                push(
+                    "timeZoneName",
+                    js_string!(time_zone_name.to_string()).into(),
+                );
+            }
+        }
+
+        if let Some(date_style) = dtf.date_style {
+            push(
+                "dateStyle",
+                js_string!(date_style_to_str(date_style)).into(),
+            );
+        }
+        if let Some(time_style) = dtf.time_style {
+            push(
+                "timeStyle",
+                js_string!(time_style_to_str(time_style)).into(),
+            );
+        }
+
+        // 6. Return options.
+        Ok(options.into())
+    }
+
+    /// Abstract operation [`FormatDateTime ( dateTimeFormat, x )`][spec].
+    ///
+    /// Formats the time value `x` into a string using the options of this date time format.
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-formatdatetime
+    pub(crate) fn format_date_time(&self, x: i64, context: &mut Context) -> JsResult<JsString> {
+        // 1. Let parts be ? PartitionDateTimePattern(dateTimeFormat, x).
+        // 2. Let result be the empty String.
+        // 3. For each Record { [[Type]], [[Value]] } part in parts, do
+        //     a. Set result to the string-concatenation of result and part.[[Value]].
+        // 4. Return result.
+        let result: String = self
+            .partition_date_time_pattern(x, context)?
+            .into_iter()
+            .map(|part| part.value)
+            .collect();
+        Ok(js_string!(result))
+    }
+
+    /// Formats a date and time that is already in local time, without applying any time zone
+    /// conversion nor appending any time zone name.
+    ///
+    /// This is used to format Temporal objects that don't have an associated time zone.
+    #[cfg(feature = "temporal")]
+    pub(crate) fn format_local_date_time(
+        &self,
+        local: &NaiveDateTime,
+        context: &Context,
+    ) -> JsResult<JsString> {
+        let result: String = self
+            .partition_local(local, None, context.intl_provider())?
+            .into_iter()
+            .map(|part| part.value)
+            .collect();
+        Ok(js_string!(result))
+    }
+
+    /// Abstract operation [`PartitionDateTimePattern ( dateTimeFormat, x )`][spec].
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-partitiondatetimepattern
+    fn partition_date_time_pattern(&self, x: i64, context: &Context) -> JsResult<Vec<Part>> {
+        // 1. Let x be TimeClip(x).
+        // 2. If x is NaN, throw a RangeError exception.
+        // NOTE: Callers already pass a clipped time value.
+
+        // 3. Let epochNanoseconds be ℤ(ℝ(x) × 10^6).
+        // 4. Let timeZone be dateTimeFormat.[[TimeZone]].
+        // 5. Let offsetNs be GetOffsetNanosecondsFor(timeZone, epochNanoseconds).
+        // 6. Let tm be ToLocalTime(epochNanoseconds, dateTimeFormat.[[Calendar]], timeZone).
+        let (local, offset) = NaiveDateTime::from_timestamp_millis(x)
+            .and_then(|utc| self.time_zone.to_local(utc, context))
+            .ok_or_else(|| JsNativeError::range().with_message("invalid time value"))?;
+
+        // 7-17. ...
+        self.partition_local(&local, Some(offset), context.intl_provider())
+    }
+
+    /// Splits a formatted local date time into its constituent parts.
+    ///
+    /// If `offset` is provided, the time zone name is also appended if the format requires it.
+    fn partition_local(
+        &self,
+        local: &NaiveDateTime,
+        offset: Option<i32>,
+        provider: &IntlProvider,
+    ) -> JsResult<Vec<Part>> {
+        let datetime = to_icu_date_time(local)
+            .ok_or_else(|| JsNativeError::range().with_message("invalid time value"))?;
+        let format = |formatter: &DateTimeFormatter| {
+            formatter
+                .format_to_string(&datetime)
+                .map_err(|err| JsNativeError::range().with_message(err.to_string()))
+        };
+
+        let formatted = format(&self.formatter)?;
+
+        let mut parts = Vec::new();
+        if self.day_period_only {
+            let day_period = extract_field(PartKind::DayPeriod, &formatted).unwrap_or_default();
+            parts.push(Part::new(PartKind::DayPeriod, day_period));
+        } else {
+            let mut cursor = 0;
+            for (kind, formatter) in self.field_formatters(provider) {
+                let field = format(formatter)?;
+                let Some(field) = extract_field(*kind, &field) else {
+                    continue;
+                };
+                let Some(index) = formatted[cursor..].find(field).map(|i| i + cursor) else {
+                    continue;
+                };
+                if index > cursor {
+                    parts.push(Part::new(PartKind::Literal, &formatted[cursor..index]));
+                }
+                parts.push(Part::new(*kind, field));
+                cursor = index + field.len();
+            }
+            if cursor < formatted.len() {
+                parts.push(Part::new(PartKind::Literal, &formatted[cursor..]));
+            }
+        }
+
+        let time_zone_name = self.time_zone_name.or(match self.time_style {
+            Some(length::Time::Full) => Some(TimeZoneName::Long),
+            Some(length::Time::Long) => Some(TimeZoneName::Short),
+            _ => None,
+        });
+        if let (Some(style), Some(offset)) = (time_zone_name, offset) {
+            parts.push(Part::new(PartKind::Literal, " "));
+            parts.push(Part::new(
+                PartKind::TimeZoneName,
+                &self.time_zone.display_name(style, offset),
+            ));
+        }
+
+        Ok(parts)
+    }
+
+    /// Gets the list of formatters used to extract every field of the resolved pattern, in the
+    /// order in which they appear in the pattern.
+    fn field_formatters(&self, provider: &IntlProvider) -> &[(PartKind, DateTimeFormatter)] {
+        if let Some(formatters) = self.field_formatters.get() {
+            return formatters;
+        }
+
+        let resolved = self.formatter.resolve_components();
+        let hour_cycle = self
+            .hour_cycle
+            .or_else(|| resolved.preferences.and_then(|prefs| prefs.hour_cycle));
+        let bag = |init: &dyn Fn(&mut components::Bag)| {
+            let mut bag = components::Bag::empty();
+            init(&mut bag);
+            bag
+        };
+
+        let mut candidates = Vec::new();
+        if let Some(era) = resolved.era {
+            candidates.push((PartKind::Era, bag(&|bag| bag.era = Some(era))));
+        }
+        if let Some(year) = resolved.year {
+            candidates.push((PartKind::Year, bag(&|bag| bag.year = Some(year))));
+        }
+        if let Some(month) = resolved.month {
+            candidates.push((PartKind::Month, bag(&|bag| bag.month = Some(month))));
+        }
+        if let Some(day) = resolved.day {
+            candidates.push((PartKind::Day, bag(&|bag| bag.day = Some(day))));
+        }
+        if let Some(weekday) = resolved.weekday {
+            candidates.push((PartKind::Weekday, bag(&|bag| bag.weekday = Some(weekday))));
+        }
+        if let Some(hour) = resolved.hour {
+            let hour_bag = bag(&|bag| {
+                bag.hour = Some(hour);
+                bag.preferences = hour_cycle.map(preferences::Bag::from_hour_cycle);
+            });
+            candidates.push((PartKind::Hour, hour_bag));
+            if matches!(
+                hour_cycle,
+                Some(preferences::HourCycle::H11 | preferences::HourCycle::H12)
+            ) {
+                candidates.push((PartKind::DayPeriod, hour_bag));
+            }
+        }
+        if let Some(minute) = resolved.minute {
+            // Minutes and seconds are formatted alongside their preceding field, since their
+            // width may depend on it.
+            candidates.push((
+                PartKind::Minute,
+                bag(&|bag| {
+                    bag.hour = resolved.hour;
+                    bag.minute = Some(minute);
+                    bag.preferences = hour_cycle.map(preferences::Bag::from_hour_cycle);
+                }),
+            ));
+        }
+        if let Some(second) = resolved.second {
+            candidates.push((
+                PartKind::Second,
+                bag(&|bag| {
+                    bag.minute = resolved.minute;
+                    bag.second = Some(second);
+                }),
+            ));
+            if let Some(digits) = resolved.fractional_second.filter(|&digits| digits > 0) {
+                candidates.push((
+                    PartKind::FractionalSecond,
+                    bag(&|bag| {
+                        bag.second = Some(second);
+                        bag.fractional_second = Some(digits);
+                    }),
+                ));
+            }
+        }
+
+        // Sort the fields by their position in the formatted probe date.
+        let probe = NaiveDate::from_ymd_opt(2001, 11, 22)
+            .and_then(|date| date.and_hms_milli_opt(15, 47, 58, 678))
+            .and_then(|probe| to_icu_date_time(&probe))
+            .expect("probe date must be valid");
+        let formatted = self.formatter.format_to_string(&probe).unwrap_or_default();
+        let mut formatters = candidates
+            .into_iter()
+            .filter_map(|(kind, bag)| {
+                let field_formatter = DateTimeFormatter::try_new_experimental_unstable(
+                    provider,
+                    &self.data_locale,
+                    DateTimeFormatterOptions::Components(bag),
+                )
+                .ok()?;
+                let field = field_formatter.format_to_string(&probe).ok()?;
+                let index = formatted.find(extract_field(kind, &field)?)?;
+                Some((index, kind, field_formatter))
+            })
+            .collect::<Vec<_>>();
+        formatters.sort_by_key(|(index, _, _)| *index);
+
+        let formatters = formatters
+            .into_iter()
+            .map(|(_, kind, formatter)| (kind, formatter))
+            .collect();
+        self.field_formatters.get_or_init(|| formatters)
+    }
+}
+
+/// Converts a value into a time value for the format functions, defaulting to the current time
+/// if `value` is undefined.
+pub(crate) fn to_date_value(value: &JsValue, context: &mut Context) -> JsResult<i64> {
+    if value.is_undefined() {
+        return Ok(context.host_hooks().utc_now().timestamp_millis());
+    }
+
+    value
+        .to_integer_or_nan(context)?
+        .as_integer()
+        .and_then(time_clip)
+        .ok_or_else(|| {
+            JsNativeError::range()
+                .with_message("invalid time value")
+                .into()
+        })
+}
+
+/// Converts a local date time into an ISO date time that can be formatted by ICU4X.
+fn to_icu_date_time(local: &NaiveDateTime) -> Option<DateTime<AnyCalendar>> {
+    let date = Date::try_new_iso_date(
+        local.year(),
+        u8::try_from(local.month()).ok()?,
+        u8::try_from(local.day()).ok()?,
+    )
+    .ok()?;
+    let time = Time::try_new(
+        u8::try_from(local.hour()).ok()?,
+        u8::try_from(local.minute()).ok()?,
+        u8::try_from(local.second()).ok()?,
+        local.nanosecond(),
+    )
+    .ok()?;
+    Some(DateTime::new(date, time).to_any())
+}
+
+/// Extracts the text of a field from a string formatted with only that field.
+fn extract_field(kind: PartKind, formatted: &str) -> Option<&str> {
+    let numeric = |c: char| c.is_numeric();
+    let text = |c: char| c.is_alphabetic() || c == '.' || c == ' ' || c == '\u{a0}';
+
+    match kind {
+        PartKind::Hour => runs(formatted, numeric).next(),
+        PartKind::Minute | PartKind::Second | PartKind::FractionalSecond => {
+            runs(formatted, numeric).last()
+        }
+        PartKind::Era | PartKind::Weekday | PartKind::DayPeriod => {
+            runs(formatted, text).max_by_key(|run| run.len())
+        }
+        _ => runs(formatted, numeric)
+            .max_by_key(|run| run.len())
+            .or_else(|| runs(formatted, text).max_by_key(|run| run.len())),
+    }
+}
+
+/// Returns an iterator over the maximal runs of characters of `string` that satisfy `predicate`,
+/// with surrounding whitespace trimmed.
+fn runs(string: &str, predicate: impl Fn(char) -> bool) -> impl Iterator<Item = &str> {
+    string
+        .split(move |c| !predicate(c))
+        .map(str::trim)
+        .filter(|run| !run.is_empty())
+}
+
+/// Gets the hour cycles used by `locale` for 12-hour and 24-hour clocks, which correspond to
+/// `dataLocaleData.[[hourCycle12]]` and `dataLocaleData.[[hourCycle24]]` respectively.
+///
+/// The hour cycles are extracted from the time patterns of the locale, defaulting to `h12` and
+/// `h23` if the locale doesn't have time patterns.
+fn locale_hour_cycles(
+    locale: &DataLocale,
+    provider: &IntlProvider,
+) -> (preferences::HourCycle, preferences::HourCycle) {
+    let lengths = DataProvider::<TimeLengthsV1Marker>::load(
+        provider,
+        DataRequest {
+            locale,
+            metadata: DataRequestMetadata::default(),
+        },
+    )
+    .and_then(DataResponse::take_payload)
+    .ok();
+
+    let hour_cycle_of = |pattern: &Pattern<'_>| {
+        pattern.items.iter().find_map(|item| match item {
+            PatternItem::Field(Field {
+                symbol: FieldSymbol::Hour(hour),
+                ..
+            }) => Some(match hour {
+                Hour::H11 => preferences::HourCycle::H11,
+                Hour::H12 => preferences::HourCycle::H12,
+                Hour::H23 => preferences::HourCycle::H23,
+                Hour::H24 => preferences::HourCycle::H24,
+            }),
+            _ => None,
+        })
+    };
+
+    let hour_cycle12 = lengths
+        .as_ref()
+        .and_then(|lengths| hour_cycle_of(&lengths.get().time_h11_h12.short))
+        .unwrap_or(preferences::HourCycle::H12);
+    let hour_cycle24 = lengths
+        .as_ref()
+        .and_then(|lengths| hour_cycle_of(&lengths.get().time_h23_h24.short))
+        .unwrap_or(preferences::HourCycle::H23);
+
+    (hour_cycle12, hour_cycle24)
+}
+
+/// Converts a [`preferences::HourCycle`] from its unicode extension representation.
+fn hour_cycle_from_value(value: &Value) -> Option<preferences::HourCycle> {
+    match value.to_string().as_str() {
+        "h11" => Some(preferences::HourCycle::H11),
+        "h12" => Some(preferences::HourCycle::H12),
+        "h23" => Some(preferences::HourCycle::H23),
+        "h24" => Some(preferences::HourCycle::H24),
+        _ => None,
+    }
+}
+
+/// The time zone used by a `DateTimeFormat`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TimeZone {
+    /// The time zone of the host, as returned by [`HostHooks::local_from_utc`].
+    ///
+    /// [`HostHooks::local_from_utc`]: crate::context::HostHooks::local_from_utc
+    Host,
+    /// A time zone with a fixed offset (in minutes) from UTC.
+    Fixed { name: String, offset: i32 },
+    /// A time zone of the IANA Time Zone Database.
+    Named(Tz),
+}
+
+impl TimeZone {
+    /// Parses and canonicalizes a time zone identifier, returning `None` if it is not supported.
+    fn from_identifier(identifier: &str, context: &Context) -> JsResult<Option<Self>> {
+        if let Some(offset) = parse_offset_time_zone(identifier) {
+            return Ok(Some(Self::Fixed {
+                name: offset_string(offset, true),
+                offset,
+            }));
+        }
+
+        let Some(name) = canonicalize_time_zone_name(identifier, context.intl_provider())? else {
+            return Ok(None);
+        };
+        Ok(Self::from_canonical_name(name))
+    }
+
+    /// Creates a time zone from its canonical identifier, returning `None` if the time zone
+    /// database doesn't have it.
+    fn from_canonical_name(name: String) -> Option<Self> {
+        if name == "UTC" {
+            return Some(Self::Fixed { name, offset: 0 });
+        }
+
+        // `Etc/GMT±N` zones have a fixed offset with an inverted sign.
+        if let Some(hours) = name.strip_prefix("Etc/GMT") {
+            let offset = -hours.parse::<i32>().ok()? * 60;
+            return Some(Self::Fixed { name, offset });
+        }
+
+        name.parse().ok().map(Self::Named)
+    }
+
+    /// Gets the canonical identifier of this time zone.
+    fn identifier(&self, context: &Context) -> String {
+        match self {
+            Self::Host => {
+                let name = host_time_zone().and_then(|name| {
+                    canonicalize_time_zone_name(&name, context.intl_provider())
+                        .ok()
+                        .flatten()
+                });
+                if let Some(name) = name {
+                    return name;
+                }
+
+                let offset = context
+                    .host_hooks()
+                    .local_from_utc(context.host_hooks().utc_now())
+                    .offset()
+                    .local_minus_utc()
+                    / 60;
+                if offset == 0 {
+                    String::from("UTC")
+                } else {
+                    offset_string(offset, true)
+                }
+            }
+            Self::Fixed { name, .. } => name.clone(),
+            Self::Named(tz) => String::from(tz.name()),
+        }
+    }
+
+    /// Converts an UTC date time into the local date time of this time zone, also returning
+    /// the offset from UTC in minutes.
+    fn to_local(&self, utc: NaiveDateTime, context: &Context) -> Option<(NaiveDateTime, i32)> {
+        let offset = match self {
+            Self::Host => context.host_hooks().local_from_utc(utc).offset().fix(),
+            Self::Fixed { offset, .. } => FixedOffset::east_opt(offset * 60)?,
+            Self::Named(tz) => tz.offset_from_utc_datetime(&utc).fix(),
+        };
+        let seconds = offset.local_minus_utc();
+        Some((
+            utc.checked_add_signed(Duration::seconds(i64::from(seconds)))?,
+            seconds / 60,
+        ))
+    }
+
+    /// Gets the name of this time zone to display in a formatted date.
+    fn display_name(&self, style: TimeZoneName, offset: i32) -> String {
+        let long = matches!(
+            style,
+            TimeZoneName::Long | TimeZoneName::LongOffset | TimeZoneName::LongGeneric
+        );
+
+        if matches!(self, Self::Fixed { name, .. } if name == "UTC")
+            && !matches!(style, TimeZoneName::ShortOffset | TimeZoneName::LongOffset)
+        {
+            return String::from(if long {
+                "Coordinated Universal Time"
+            } else {
+                "UTC"
+            });
+        }
+
+        if offset == 0 {
+            return String::from("GMT");
+        }
+
+        format!("GMT{}", offset_string(offset, long))
+    }
+}
+
+/// Gets the identifier of the time zone of the host in the IANA Time Zone Database.
+///
+/// Uses the time zone in the `TZ` environment variable or, if it isn't defined, the time zone of
+/// the operating system.
+fn host_time_zone() -> Option<String> {
+    match std::env::var("TZ") {
+        Ok(tz) => {
+            let tz = tz.strip_prefix(':').unwrap_or(&tz);
+            (!tz.is_empty() && !tz.starts_with('/')).then(|| tz.to_owned())
+        }
+        Err(_) => iana_time_zone::get_timezone().ok(),
+    }
+}
+
+/// Parses an offset time zone of the form `±HH`, `±HHMM` or `±HH:MM`, returning its offset
+/// in minutes.
+fn parse_offset_time_zone(identifier: &str) -> Option<i32> {
+    let sign = match identifier.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let rest = &identifier[1..];
+    let (hours, minutes) = match rest.len() {
+        2 => (rest, "00"),
+        4 => rest.split_at(2),
+        5 if rest.as_bytes()[2] == b':' => (&rest[..2], &rest[3..]),
+        _ => return None,
+    };
+    if !hours
+        .bytes()
+        .chain(minutes.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let (hours, minutes): (i32, i32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    Some(sign * (hours * 60 + minutes))
+}
+
+/// Abstract operations [`IsValidTimeZoneName ( timeZone )`][valid] and
+/// [`CanonicalizeTimeZoneName ( timeZone )`][canon].
+///
+/// Returns the canonical identifier of the time zone `identifier` of the IANA Time Zone Database,
+/// or `None` if the provider doesn't have a time zone with that identifier.
+///
+/// [valid]: https://tc39.es/ecma402/#sec-isvalidtimezonename
+/// [canon]: https://tc39.es/ecma402/#sec-canonicalizetimezonename
+fn canonicalize_time_zone_name(
+    identifier: &str,
+    provider: &IntlProvider,
+) -> JsResult<Option<String>> {
+    let mapper = IanaBcp47RoundTripMapper::try_new_unstable(provider)
+        .map_err(|err| JsNativeError::typ().with_message(err.to_string()))?;
+    let mapper = mapper.as_borrowed();
+
+    Ok(mapper.iana_to_bcp47(identifier).and_then(|id| {
+        mapper
+            .bcp47_to_iana(id)
+            .and_then(|name| primary_time_zone_name(id, name))
+    }))
+}
+
+/// Gets the primary identifier of the time zone with the BCP-47 identifier `id` and the
+/// canonical IANA identifier `name`.
+///
+/// ECMA-402 uses `UTC` as the primary identifier of all the UTC time zones, and doesn't support
+/// the `Etc/Unknown` time zone.
+fn primary_time_zone_name(id: TimeZoneBcp47Id, name: &str) -> Option<String> {
+    match id.0.as_str() {
+        "unk" => None,
+        "utc" | "gmt" => Some(String::from("UTC")),
+        _ => Some(String::from(name)),
+    }
+}
+
+/// Checks if the time zone with the canonical identifier `name` can be used by
+/// `Intl.DateTimeFormat`.
+pub(in crate::builtins::intl) fn is_available_time_zone(name: &str) -> bool {
+    TimeZone::from_canonical_name(String::from(name)).is_some()
+}
+
+/// Abstract operation [`AvailablePrimaryTimeZoneIdentifiers ( )`][spec].
+///
+/// Gets the primary identifiers of the time zones of the provider that are supported by
+/// `Intl.DateTimeFormat`, sorted by code units.
+///
+/// [spec]: https://tc39.es/ecma402/#sec-availableprimarytimezoneidentifiers
+pub(in crate::builtins::intl) fn available_time_zones(context: &Context) -> JsResult<Vec<String>> {
+    let provider = context.intl_provider();
+    let request = DataRequest::default();
+    let ids = DataProvider::<IanaToBcp47MapV1Marker>::load(provider, request)
+        .and_then(DataResponse::take_payload)
+        .map_err(|err| JsNativeError::typ().with_message(err.to_string()))?;
+    let names = DataProvider::<Bcp47ToIanaMapV1Marker>::load(provider, request)
+        .and_then(DataResponse::take_payload)
+        .map_err(|err| JsNativeError::typ().with_message(err.to_string()))?;
+
+    let mut zones = ids
+        .get()
+        .bcp47_ids
+        .iter()
+        .zip(names.get().canonical_iana_ids.iter())
+        .filter_map(|(id, name)| primary_time_zone_name(id, name))
+        .filter(|name| is_available_time_zone(name))
+        .collect::<Vec<_>>();
+    zones.sort_unstable();
+    zones.dedup();
+    Ok(zones)
+}
+
+/// Formats an offset in minutes as `±HH:MM`, or as `±H[:MM]` if `padded` is false.
+fn offset_string(offset: i32, padded: bool) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let (hours, minutes) = (offset.abs() / 60, offset.abs() % 60);
+    if padded {
+        format!("{sign}{hours:02}:{minutes:02}")
+    } else if minutes == 0 {
+        format!("{sign}{hours}")
+    } else {
+        format!("{sign}{hours}:{minutes:02}")
+    }
+}
+
+/// The type of a part of a formatted date, as returned by `formatToParts`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum PartKind {
+    Literal,
+    Era,
+    Year,
+    Month,
+    Day,
+    Weekday,
+    DayPeriod,
+    Hour,
+    Minute,
+    Second,
+    FractionalSecond,
+    TimeZoneName,
+}
+
+impl PartKind {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Literal => "literal",
+            Self::Era => "era",
+            Self::Year => "year",
+            Self::Month => "month",
+            Self::Day => "day",
+            Self::Weekday => "weekday",
+            Self::DayPeriod => "dayPeriod",
+            Self::Hour => "hour",
+            Self::Minute => "minute",
+            Self::Second => "second",
+            Self::FractionalSecond => "fractionalSecond",
+            Self::TimeZoneName => "timeZoneName",
+        }
+    }
+}
+
+/// A typed part of a formatted date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Part {
+    kind: PartKind,
+    value: String,
+}
+
+impl Part {
+    fn new(kind: PartKind, value: &str) -> Self {
+        Self {
+            kind,
+            value: value.to_owned(),
+        }
+    }
+}
+
+/// The source of a part in a formatted range.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum RangeSource {
+    StartRange,
+    EndRange,
+    Shared,
+}
+
+impl RangeSource {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::StartRange => "startRange",
+            Self::EndRange => "endRange",
+            Self::Shared => "shared",
+        }
+    }
+}
+
+/// Converts a list of parts into an array of `{ type, value [, source] }` objects.
+fn parts_to_array(
+    parts: impl IntoIterator<Item = (Part, Option<RangeSource>)>,
+    context: &mut Context,
+) -> JsObject {
+    // 2. Let result be ! ArrayCreate(0).
+    let result = Array::array_create(0, None, context)
+        .expect("creating an empty array with default proto must not fail");
+
+    // 3. Let n be 0.
+    // 4. For each Record { [[Type]], [[Value]] } part in parts, do
+    for (n, (part, source)) in parts.into_iter().enumerate() {
+        // a. Let O be OrdinaryObjectCreate(%Object.prototype%).
+        let o = context
+            .intrinsics()
+            .templates()
+            .ordinary_object()
+            .create(OrdinaryObject, vec![]);
+
+        // b. Perform ! CreateDataPropertyOrThrow(O, "type", part.[[Type]]).
+        o.create_data_property_or_throw(utf16!("type"), js_string!(part.kind.as_str()), context)
+            .expect("operation must not fail per the spec");
+
+        // c. Perform ! CreateDataPropertyOrThrow(O, "value", part.[[Value]]).
+        o.create_data_property_or_throw(utf16!("value"), js_string!(part.value.as_str()), context)
+            .expect("operation must not fail per the spec");
+
+        if let Some(source) = source {
+            o.create_data_property_or_throw(utf16!("source"), js_string!(source.as_str()), context)
+                .expect("operation must not fail per the spec");
+        }
+
+        // d. Perform ! CreateDataPropertyOrThrow(result, ! ToString(n), O).
+        result
+            .create_data_property_or_throw(n, o, context)
+            .expect("operation must not fail per the spec");
+
+        // e. Increment n by 1.
+    }
+
+    // 5. Return result.
+    result
+}
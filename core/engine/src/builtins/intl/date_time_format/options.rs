@@ -0,0 +1,409 @@
+use std::{fmt, str::FromStr};
+
+use icu_datetime::options::{components, length, preferences::HourCycle};
+
+use crate::{
+    builtins::{
+        options::{OptionType, ParsableOptionType},
+        OrdinaryObject,
+    },
+    js_string,
+    object::JsObject,
+    string::utf16,
+    Context, JsNativeError, JsResult, JsValue,
+};
+
+impl OptionType for HourCycle {
+    fn from_value(value: JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.to_string(context)?.to_std_string_escaped().as_str() {
+            "h11" => Ok(Self::H11),
+            "h12" => Ok(Self::H12),
+            "h23" => Ok(Self::H23),
+            "h24" => Ok(Self::H24),
+            _ => Err(JsNativeError::range()
+                .with_message("provided string was not `h11`, `h12`, `h23` or `h24`")
+                .into()),
+        }
+    }
+}
+
+impl OptionType for components::Text {
+    fn from_value(value: JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.to_string(context)?.to_std_string_escaped().as_str() {
+            "narrow" => Ok(Self::Narrow),
+            "short" => Ok(Self::Short),
+            "long" => Ok(Self::Long),
+            _ => Err(JsNativeError::range()
+                .with_message("provided string was not `narrow`, `short` or `long`")
+                .into()),
+        }
+    }
+}
+
+impl OptionType for components::Numeric {
+    fn from_value(value: JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.to_string(context)?.to_std_string_escaped().as_str() {
+            "numeric" => Ok(Self::Numeric),
+            "2-digit" => Ok(Self::TwoDigit),
+            _ => Err(JsNativeError::range()
+                .with_message("provided string was not `numeric` or `2-digit`")
+                .into()),
+        }
+    }
+}
+
+impl OptionType for components::Year {
+    fn from_value(value: JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.to_string(context)?.to_std_string_escaped().as_str() {
+            "numeric" => Ok(Self::Numeric),
+            "2-digit" => Ok(Self::TwoDigit),
+            _ => Err(JsNativeError::range()
+                .with_message("provided string was not `numeric` or `2-digit`")
+                .into()),
+        }
+    }
+}
+
+impl OptionType for components::Month {
+    fn from_value(value: JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.to_string(context)?.to_std_string_escaped().as_str() {
+            "numeric" => Ok(Self::Numeric),
+            "2-digit" => Ok(Self::TwoDigit),
+            "narrow" => Ok(Self::Narrow),
+            "short" => Ok(Self::Short),
+            "long" => Ok(Self::Long),
+            _ => Err(JsNativeError::range()
+                .with_message(
+                    "provided string was not `numeric`, `2-digit`, `narrow`, `short` or `long`",
+                )
+                .into()),
+        }
+    }
+}
+
+impl OptionType for components::Day {
+    fn from_value(value: JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.to_string(context)?.to_std_string_escaped().as_str() {
+            "numeric" => Ok(Self::NumericDayOfMonth),
+            "2-digit" => Ok(Self::TwoDigitDayOfMonth),
+            _ => Err(JsNativeError::range()
+                .with_message("provided string was not `numeric` or `2-digit`")
+                .into()),
+        }
+    }
+}
+
+impl OptionType for length::Date {
+    fn from_value(value: JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.to_string(context)?.to_std_string_escaped().as_str() {
+            "full" => Ok(Self::Full),
+            "long" => Ok(Self::Long),
+            "medium" => Ok(Self::Medium),
+            "short" => Ok(Self::Short),
+            _ => Err(JsNativeError::range()
+                .with_message("provided string was not `full`, `long`, `medium` or `short`")
+                .into()),
+        }
+    }
+}
+
+impl OptionType for length::Time {
+    fn from_value(value: JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.to_string(context)?.to_std_string_escaped().as_str() {
+            "full" => Ok(Self::Full),
+            "long" => Ok(Self::Long),
+            "medium" => Ok(Self::Medium),
+            "short" => Ok(Self::Short),
+            _ => Err(JsNativeError::range()
+                .with_message("provided string was not `full`, `long`, `medium` or `short`")
+                .into()),
+        }
+    }
+}
+
+/// Gets the string representation of an hour cycle, as used by `resolvedOptions`.
+pub(crate) const fn hour_cycle_to_str(hour_cycle: HourCycle) -> &'static str {
+    match hour_cycle {
+        HourCycle::H11 => "h11",
+        HourCycle::H12 => "h12",
+        HourCycle::H23 => "h23",
+        HourCycle::H24 => "h24",
+    }
+}
+
+/// Gets the string representation of a textual component, as used by `resolvedOptions`.
+pub(crate) const fn text_to_str(text: components::Text) -> &'static str {
+    match text {
+        components::Text::Narrow => "narrow",
+        components::Text::Short => "short",
+        _ => "long",
+    }
+}
+
+/// Gets the string representation of a numeric component, as used by `resolvedOptions`.
+pub(crate) const fn numeric_to_str(numeric: components::Numeric) -> &'static str {
+    match numeric {
+        components::Numeric::TwoDigit => "2-digit",
+        _ => "numeric",
+    }
+}
+
+/// Gets the string representation of a year component, as used by `resolvedOptions`.
+pub(crate) const fn year_to_str(year: components::Year) -> &'static str {
+    match year {
+        components::Year::TwoDigit | components::Year::TwoDigitWeekOf => "2-digit",
+        _ => "numeric",
+    }
+}
+
+/// Gets the string representation of a month component, as used by `resolvedOptions`.
+pub(crate) const fn month_to_str(month: components::Month) -> &'static str {
+    match month {
+        components::Month::TwoDigit => "2-digit",
+        components::Month::Narrow => "narrow",
+        components::Month::Short => "short",
+        components::Month::Long => "long",
+        _ => "numeric",
+    }
+}
+
+/// Gets the string representation of a day component, as used by `resolvedOptions`.
+pub(crate) const fn day_to_str(day: components::Day) -> &'static str {
+    match day {
+        components::Day::TwoDigitDayOfMonth => "2-digit",
+        _ => "numeric",
+    }
+}
+
+/// Gets the string representation of a date style, as used by `resolvedOptions`.
+pub(crate) const fn date_style_to_str(style: length::Date) -> &'static str {
+    match style {
+        length::Date::Full => "full",
+        length::Date::Long => "long",
+        length::Date::Medium => "medium",
+        _ => "short",
+    }
+}
+
+/// Gets the string representation of a time style, as used by `resolvedOptions`.
+pub(crate) const fn time_style_to_str(style: length::Time) -> &'static str {
+    match style {
+        length::Time::Full => "full",
+        length::Time::Long => "long",
+        length::Time::Medium => "medium",
+        _ => "short",
+    }
+}
+
+/// The `timeZoneName` option of `Intl.DateTimeFormat`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TimeZoneName {
+    Short,
+    Long,
+    ShortOffset,
+    LongOffset,
+    ShortGeneric,
+    LongGeneric,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseTimeZoneNameError;
+
+impl fmt::Display for ParseTimeZoneNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("provided string was not `short`, `long`, `shortOffset`, `longOffset`, `shortGeneric` or `longGeneric`")
+    }
+}
+
+impl FromStr for TimeZoneName {
+    type Err = ParseTimeZoneNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "short" => Ok(Self::Short),
+            "long" => Ok(Self::Long),
+            "shortOffset" => Ok(Self::ShortOffset),
+            "longOffset" => Ok(Self::LongOffset),
+            "shortGeneric" => Ok(Self::ShortGeneric),
+            "longGeneric" => Ok(Self::LongGeneric),
+            _ => Err(ParseTimeZoneNameError),
+        }
+    }
+}
+
+impl ParsableOptionType for TimeZoneName {}
+
+impl fmt::Display for TimeZoneName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Short => "short",
+            Self::Long => "long",
+            Self::ShortOffset => "shortOffset",
+            Self::LongOffset => "longOffset",
+            Self::ShortGeneric => "shortGeneric",
+            Self::LongGeneric => "longGeneric",
+        })
+    }
+}
+
+/// The `formatMatcher` option of `Intl.DateTimeFormat`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum FormatMatcher {
+    Basic,
+    #[default]
+    BestFit,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseFormatMatcherError;
+
+impl fmt::Display for ParseFormatMatcherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("provided string was not `basic` or `best fit`")
+    }
+}
+
+impl FromStr for FormatMatcher {
+    type Err = ParseFormatMatcherError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "basic" => Ok(Self::Basic),
+            "best fit" => Ok(Self::BestFit),
+            _ => Err(ParseFormatMatcherError),
+        }
+    }
+}
+
+impl ParsableOptionType for FormatMatcher {}
+
+/// Represents the `required` and `defaults` arguments in the abstract operation
+/// `toDateTimeOptions`.
+///
+/// Since `required` and `defaults` differ only in the `any` and `all` variants,
+/// we combine both in a single variant `AnyAll`.
+#[derive(Debug, PartialEq)]
+pub(crate) enum DateTimeReqs {
+    Date,
+    Time,
+    AnyAll,
+}
+
+/// The abstract operation `toDateTimeOptions` is called with arguments `options`, `required` and
+/// `defaults`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma402/#sec-todatetimeoptions
+pub(crate) fn to_date_time_options(
+    options: &JsValue,
+    required: &DateTimeReqs,
+    defaults: &DateTimeReqs,
+    context: &mut Context,
+) -> JsResult<JsObject> {
+    // 1. If options is undefined, let options be null;
+    // otherwise let options be ? ToObject(options).
+    // 2. Let options be ! OrdinaryObjectCreate(options).
+    let options = if options.is_undefined() {
+        None
+    } else {
+        Some(options.to_object(context)?)
+    };
+    let options = JsObject::from_proto_and_data_with_shared_shape(
+        context.root_shape(),
+        options,
+        OrdinaryObject,
+    );
+
+    // 3. Let needDefaults be true.
+    let mut need_defaults = true;
+
+    // 4. If required is "date" or "any", then
+    if [DateTimeReqs::Date, DateTimeReqs::AnyAll].contains(required) {
+        // a. For each property name prop of « "weekday", "year", "month", "day" », do
+        for property in [
+            utf16!("weekday"),
+            utf16!("year"),
+            utf16!("month"),
+            utf16!("day"),
+        ] {
+            // i. Let value be ? Get(options, prop).
+            let value = options.get(property, context)?;
+
+            // ii. If value is not undefined, let needDefaults be false.
+            if !value.is_undefined() {
+                need_defaults = false;
+            }
+        }
+    }
+
+    // 5. If required is "time" or "any", then
+    if [DateTimeReqs::Time, DateTimeReqs::AnyAll].contains(required) {
+        // a. For each property name prop of « "dayPeriod", "hour", "minute", "second",
+        // "fractionalSecondDigits" », do
+        for property in [
+            utf16!("dayPeriod"),
+            utf16!("hour"),
+            utf16!("minute"),
+            utf16!("second"),
+            utf16!("fractionalSecondDigits"),
+        ] {
+            // i. Let value be ? Get(options, prop).
+            let value = options.get(property, context)?;
+
+            // ii. If value is not undefined, let needDefaults be false.
+            if !value.is_undefined() {
+                need_defaults = false;
+            }
+        }
+    }
+
+    // 6. Let dateStyle be ? Get(options, "dateStyle").
+    let date_style = options.get(utf16!("dateStyle"), context)?;
+
+    // 7. Let timeStyle be ? Get(options, "timeStyle").
+    let time_style = options.get(utf16!("timeStyle"), context)?;
+
+    // 8. If dateStyle is not undefined or timeStyle is not undefined, let needDefaults be false.
+    if !date_style.is_undefined() || !time_style.is_undefined() {
+        need_defaults = false;
+    }
+
+    // 9. If required is "date" and timeStyle is not undefined, then
+    if required == &DateTimeReqs::Date && !time_style.is_undefined() {
+        // a. Throw a TypeError exception.
+        return Err(JsNativeError::typ()
+            .with_message("'date' is required, but timeStyle was defined")
+            .into());
+    }
+
+    // 10. If required is "time" and dateStyle is not undefined, then
+    if required == &DateTimeReqs::Time && !date_style.is_undefined() {
+        // a. Throw a TypeError exception.
+        return Err(JsNativeError::typ()
+            .with_message("'time' is required, but dateStyle was defined")
+            .into());
+    }
+
+    // 11. If needDefaults is true and defaults is either "date" or "all", then
+    if need_defaults && [DateTimeReqs::Date, DateTimeReqs::AnyAll].contains(defaults) {
+        // a. For each property name prop of « "year", "month", "day" », do
+        for property in [utf16!("year"), utf16!("month"), utf16!("day")] {
+            // i. Perform ? CreateDataPropertyOrThrow(options, prop, "numeric").
+            options.create_data_property_or_throw(property, js_string!("numeric"), context)?;
+        }
+    }
+
+    // 12. If needDefaults is true and defaults is either "time" or "all", then
+    if need_defaults && [DateTimeReqs::Time, DateTimeReqs::AnyAll].contains(defaults) {
+        // a. For each property name prop of « "hour", "minute", "second" », do
+        for property in [utf16!("hour"), utf16!("minute"), utf16!("second")] {
+            // i. Perform ? CreateDataPropertyOrThrow(options, prop, "numeric").
+            options.create_data_property_or_throw(property, js_string!("numeric"), context)?;
+        }
+    }
+
+    // 13. Return options.
+    Ok(options)
+}
@@ -0,0 +1,28 @@
+use crate::{js_string, run_test_actions, TestAction};
+
+#[test]
+fn format_with_time_zone() {
+    run_test_actions([
+        TestAction::run("const date = new Date(Date.UTC(2024, 0, 15, 3, 30))"),
+        TestAction::assert_eq(
+            "new Intl.DateTimeFormat('de-DE', { timeZone: 'Asia/Tokyo', hour: 'numeric', minute: 'numeric' }).format(date)",
+            js_string!("12:30"),
+        ),
+        TestAction::assert_eq(
+            "new Intl.DateTimeFormat('de-DE', { timeZone: 'America/New_York', year: 'numeric', month: '2-digit', day: '2-digit' }).format(date)",
+            js_string!("14.01.2024"),
+        ),
+        TestAction::assert_eq(
+            "new Intl.DateTimeFormat('de-DE', { timeZone: 'Europe/Berlin' }).resolvedOptions().timeZone",
+            js_string!("Europe/Berlin"),
+        ),
+    ]);
+}
+
+#[test]
+fn to_locale_string() {
+    run_test_actions([TestAction::assert_eq(
+        "new Date(Date.UTC(2024, 0, 15, 3, 30)).toLocaleDateString('de-DE', { timeZone: 'America/New_York' })",
+        js_string!("14.1.2024"),
+    )]);
+}
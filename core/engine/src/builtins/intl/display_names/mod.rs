@@ -0,0 +1,664 @@
+//! This module implements the global `Intl.DisplayNames` object.
+//!
+//! `Intl.DisplayNames` is a built-in object that enables the consistent translation of language,
+//! region and script display names.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/ecma402/#intl-displaynames-objects
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DisplayNames
+
+use boa_gc::{Finalize, Trace};
+use boa_icu_provider::provider::CurrencyNamesV1Marker;
+use boa_profiler::Profiler;
+use icu_displaynames::provider::{
+    LanguageDisplayNamesV1Marker, LocaleDisplayNamesV1Marker, RegionDisplayNamesV1Marker,
+    ScriptDisplayNamesV1Marker, VariantDisplayNamesV1Marker,
+};
+use icu_locid::{
+    extensions::unicode::Value,
+    subtags::{Region, Script},
+    LanguageIdentifier, Locale,
+};
+use icu_provider::{
+    DataLocale, DataPayload, DataProvider, DataRequest, DataRequestMetadata, DataResponse,
+    KeyedDataMarker,
+};
+use zerovec::{maps::ZeroMapKV, ule::UnvalidatedStr, ZeroMap};
+
+use crate::{
+    builtins::{
+        options::{get_option, get_options_object},
+        BuiltInBuilder, BuiltInConstructor, BuiltInObject, IntrinsicObject, OrdinaryObject,
+    },
+    context::{
+        icu::IntlProvider,
+        intrinsics::{Intrinsics, StandardConstructor, StandardConstructors},
+    },
+    js_string,
+    object::{internal_methods::get_prototype_from_constructor, JsObject},
+    property::Attribute,
+    realm::Realm,
+    string::{common::StaticJsStrings, utf16},
+    symbol::JsSymbol,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsString, JsValue,
+};
+
+use super::{
+    locale::{canonicalize_locale_list, resolve_locale, supported_locales},
+    number_format::Currency,
+    options::IntlOptions,
+    Service,
+};
+
+#[cfg(all(test, feature = "intl_bundled"))]
+mod tests;
+
+mod options;
+
+use options::{DisplayNamesType, Fallback, LanguageDisplay, Style};
+
+/// JavaScript `Intl.DisplayNames` object.
+#[derive(Debug, Trace, Finalize, JsData)]
+// SAFETY: `DisplayNames` doesn't contain any traceable data.
+#[boa_gc(unsafe_empty_trace)]
+pub(crate) struct DisplayNames {
+    locale: Locale,
+    style: Style,
+    typ: DisplayNamesType,
+    fallback: Fallback,
+    language_display: Option<LanguageDisplay>,
+}
+
+impl Service for DisplayNames {
+    type LangMarker = LanguageDisplayNamesV1Marker;
+
+    type LocaleOptions = ();
+}
+
+impl IntrinsicObject for DisplayNames {
+    fn init(realm: &Realm) {
+        let _timer = Profiler::global().start_event(std::any::type_name::<Self>(), "init");
+
+        BuiltInBuilder::from_standard_constructor::<Self>(realm)
+            .static_method(
+                Self::supported_locales_of,
+                js_string!("supportedLocalesOf"),
+                1,
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("Intl.DisplayNames"),
+                Attribute::CONFIGURABLE,
+            )
+            .method(Self::of, js_string!("of"), 1)
+            .method(Self::resolved_options, js_string!("resolvedOptions"), 0)
+            .build();
+    }
+
+    fn get(intrinsics: &Intrinsics) -> JsObject {
+        Self::STANDARD_CONSTRUCTOR(intrinsics.constructors()).constructor()
+    }
+}
+
+impl BuiltInObject for DisplayNames {
+    const NAME: JsString = StaticJsStrings::DISPLAY_NAMES;
+}
+
+impl BuiltInConstructor for DisplayNames {
+    const LENGTH: usize = 2;
+
+    const STANDARD_CONSTRUCTOR: fn(&StandardConstructors) -> &StandardConstructor =
+        StandardConstructors::display_names;
+
+    /// Constructor [`Intl.DisplayNames ( locales, options )`][spec].
+    ///
+    /// Constructor for `DisplayNames` objects.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-Intl.DisplayNames
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DisplayNames/DisplayNames
+    fn constructor(
+        new_target: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. If NewTarget is undefined, throw a TypeError exception.
+        if new_target.is_undefined() {
+            return Err(JsNativeError::typ()
+                .with_message("cannot call `Intl.DisplayNames` constructor without `new`")
+                .into());
+        }
+
+        // 2. Let displayNames be ? OrdinaryCreateFromConstructor(NewTarget, "%DisplayNames.prototype%", « ... »).
+        let prototype = get_prototype_from_constructor(
+            new_target,
+            StandardConstructors::display_names,
+            context,
+        )?;
+
+        let locales = args.get_or_undefined(0);
+        let options = args.get_or_undefined(1);
+
+        // 3. Let requestedLocales be ? CanonicalizeLocaleList(locales).
+        let requested_locales = canonicalize_locale_list(locales, context)?;
+
+        // 4. If options is undefined, throw a TypeError exception.
+        if options.is_undefined() {
+            return Err(JsNativeError::typ()
+                .with_message("`Intl.DisplayNames` requires an options object")
+                .into());
+        }
+
+        // 5. Set options to ? GetOptionsObject(options).
+        let options = get_options_object(options)?;
+
+        // 6. Let opt be a new Record.
+        // 7. Let matcher be ? GetOption(options, "localeMatcher", string, « "lookup", "best fit" », "best fit").
+        // 8. Set opt.[[localeMatcher]] to matcher.
+        let matcher = get_option(&options, utf16!("localeMatcher"), context)?.unwrap_or_default();
+
+        // 9. Let r be ResolveLocale(%DisplayNames%.[[AvailableLocales]], requestedLocales, opt, %DisplayNames%.[[RelevantExtensionKeys]], %DisplayNames%.[[LocaleData]]).
+        let locale = resolve_locale::<Self>(
+            &requested_locales,
+            &mut IntlOptions {
+                matcher,
+                ..Default::default()
+            },
+            context.intl_provider(),
+        );
+
+        // 10. Let style be ? GetOption(options, "style", string, « "narrow", "short", "long" », "long").
+        // 11. Set displayNames.[[Style]] to style.
+        let style = get_option(&options, utf16!("style"), context)?.unwrap_or_default();
+
+        // 12. Let type be ? GetOption(options, "type", string, « "language", "region", "script", "currency", "calendar", "dateTimeField" », undefined).
+        // 13. If type is undefined, throw a TypeError exception.
+        // 14. Set displayNames.[[Type]] to type.
+        let typ = get_option::<DisplayNamesType>(&options, utf16!("type"), context)?.ok_or_else(
+            || JsNativeError::typ().with_message("`Intl.DisplayNames` requires a `type` option"),
+        )?;
+
+        // 15. Let fallback be ? GetOption(options, "fallback", string, « "code", "none" », "code").
+        // 16. Set displayNames.[[Fallback]] to fallback.
+        let fallback = get_option(&options, utf16!("fallback"), context)?.unwrap_or_default();
+
+        // 17. Set displayNames.[[Locale]] to r.[[locale]].
+        // 18. Let resolvedLocaleData be r.[[LocaleData]].
+        // 19. Let types be resolvedLocaleData.[[types]].
+        // 20. Assert: types is a Record (see 12.2.3).
+        // 21. Let languageDisplay be ? GetOption(options, "languageDisplay", string, « "dialect", "standard" », "dialect").
+        let language_display =
+            get_option::<LanguageDisplay>(&options, utf16!("languageDisplay"), context)?
+                .unwrap_or_default();
+
+        // 22. Let typeFields be types.[[<type>]].
+        // 23. Assert: typeFields is a Record (see 12.2.3).
+        // 24. If type is "language", then
+        //     a. Set displayNames.[[LanguageDisplay]] to languageDisplay.
+        //     b. Let typeFields be typeFields.[[<languageDisplay>]].
+        //     c. Assert: typeFields is a Record (see 12.2.3).
+        let language_display = (typ == DisplayNamesType::Language).then_some(language_display);
+
+        // 25. Let styleFields be typeFields.[[<style>]].
+        // 26. Assert: styleFields is a Record (see 12.2.3).
+        // 27. Set displayNames.[[Fields]] to styleFields.
+        // 28. Return displayNames.
+        Ok(JsObject::from_proto_and_data_with_shared_shape(
+            context.root_shape(),
+            prototype,
+            Self {
+                locale,
+                style,
+                typ,
+                fallback,
+                language_display,
+            },
+        )
+        .into())
+    }
+}
+
+impl DisplayNames {
+    /// [`Intl.DisplayNames.supportedLocalesOf ( locales [ , options ] )`][spec].
+    ///
+    /// Returns an array containing those of the provided locales that are supported in display
+    /// names without having to fall back to the runtime's default locale.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-Intl.DisplayNames.supportedLocalesOf
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DisplayNames/supportedLocalesOf
+    fn supported_locales_of(
+        _: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let locales = args.get_or_undefined(0);
+        let options = args.get_or_undefined(1);
+
+        // 1. Let availableLocales be %DisplayNames%.[[AvailableLocales]].
+        // 2. Let requestedLocales be ? CanonicalizeLocaleList(locales).
+        let requested_locales = canonicalize_locale_list(locales, context)?;
+
+        // 3. Return ? SupportedLocales(availableLocales, requestedLocales, options).
+        supported_locales::<<Self as Service>::LangMarker>(&requested_locales, options, context)
+            .map(JsValue::from)
+    }
+
+    /// [`Intl.DisplayNames.prototype.of ( code )`][spec].
+    ///
+    /// Returns the display name of the provided code.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-Intl.DisplayNames.prototype.of
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DisplayNames/of
+    fn of(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let displayNames be this value.
+        // 2. Perform ? RequireInternalSlot(displayNames, [[InitializedDisplayNames]]).
+        let dn = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("`of` can only be called on an `Intl.DisplayNames` object")
+            })?;
+
+        // 3. Let code be ? ToString(code).
+        let code = args
+            .get_or_undefined(0)
+            .to_string(context)?
+            .to_std_string_escaped();
+
+        // 4. Set code to ? CanonicalCodeForDisplayNames(displayNames.[[Type]], code).
+        // 5. Let fields be displayNames.[[Fields]].
+        // 6. If fields has a field [[<code>]], return fields.[[<code>]].
+        let (code, name) = match dn.typ {
+            DisplayNamesType::Language => {
+                // a. If code cannot be matched by the unicode_language_id Unicode locale nonterminal, throw a RangeError exception.
+                // b. If IsStructurallyValidLanguageTag(code) is false, throw a RangeError exception.
+                let mut locale = LanguageIdentifier::try_from_bytes(code.as_bytes())
+                    .map(Locale::from)
+                    .map_err(|_| {
+                        JsNativeError::range()
+                            .with_message("provided code was not a valid language identifier")
+                    })?;
+
+                // c. Return CanonicalizeUnicodeLocaleId(code).
+                context
+                    .intl_provider()
+                    .locale_canonicalizer()
+                    .canonicalize(&mut locale);
+
+                let name = dn.language_name(&locale.id, context)?;
+                (locale.id.to_string(), name)
+            }
+            DisplayNamesType::Region => {
+                // a. If code cannot be matched by the unicode_region_subtag Unicode locale nonterminal, throw a RangeError exception.
+                // b. Return the ASCII-uppercase of code.
+                let region = Region::try_from_bytes(code.as_bytes()).map_err(|_| {
+                    JsNativeError::range().with_message("provided code was not a valid region")
+                })?;
+
+                let names = load::<RegionDisplayNamesV1Marker>(&dn.locale, context)?;
+                let names = names.get();
+                let name = get_name(
+                    dn.style,
+                    &names.names,
+                    &names.short_names,
+                    &region.into_tinystr().to_unvalidated(),
+                )
+                .map(ToOwned::to_owned);
+                (region.to_string(), name)
+            }
+            DisplayNamesType::Script => {
+                // a. If code cannot be matched by the unicode_script_subtag Unicode locale nonterminal, throw a RangeError exception.
+                // b. Assert: The length of code is 4, and every code unit of code represents an ASCII letter (0x0041 through 0x005A and 0x0061 through 0x007A, both inclusive).
+                // c. Let first be the ASCII-uppercase of the substring of code from 0 to 1.
+                // d. Let rest be the ASCII-lowercase of the substring of code from 1.
+                // e. Return the string-concatenation of first and rest.
+                let script = Script::try_from_bytes(code.as_bytes()).map_err(|_| {
+                    JsNativeError::range().with_message("provided code was not a valid script")
+                })?;
+
+                let names = load::<ScriptDisplayNamesV1Marker>(&dn.locale, context)?;
+                let names = names.get();
+                let name = get_name(
+                    dn.style,
+                    &names.names,
+                    &names.short_names,
+                    &script.into_tinystr().to_unvalidated(),
+                )
+                .map(ToOwned::to_owned);
+                (script.to_string(), name)
+            }
+            DisplayNamesType::Currency => {
+                // a. If ! IsWellFormedCurrencyCode(code) is false, throw a RangeError exception.
+                // b. Return the ASCII-uppercase of code.
+                let currency = code.parse::<Currency>().map_err(|_| {
+                    JsNativeError::range()
+                        .with_message("provided code was not a well-formed currency code")
+                })?;
+
+                let names = load::<CurrencyNamesV1Marker>(&dn.locale, context)?;
+                let name = names
+                    .get()
+                    .names
+                    .get(&currency.to_data_key())
+                    .map(ToOwned::to_owned);
+                (currency.to_string(), name)
+            }
+            DisplayNamesType::Calendar => {
+                // a. If code cannot be matched by the type Unicode locale nonterminal, throw a RangeError exception.
+                // b. If code uses any of the backwards compatibility syntax described in Unicode Technical Standard #35 LDML § 3.3 BCP 47 Conformance, throw a RangeError exception.
+                // c. Return the ASCII-lowercase of code.
+                let calendar = Value::try_from_bytes(code.as_bytes())
+                    .ok()
+                    .filter(|_| !code.is_empty())
+                    .ok_or_else(|| {
+                        JsNativeError::range()
+                            .with_message("provided code was not a valid calendar type")
+                    })?
+                    .to_string();
+
+                let name = dn
+                    .is_english()
+                    .then(|| calendar_name(&calendar))
+                    .flatten()
+                    .map(ToOwned::to_owned);
+                (calendar, name)
+            }
+            DisplayNamesType::DateTimeField => {
+                // a. If the result of IsValidDateTimeFieldCode(code) is false, throw a RangeError exception.
+                // b. Return code.
+                let name = date_time_field_name(&code).ok_or_else(|| {
+                    JsNativeError::range()
+                        .with_message("provided code was not a valid date time field")
+                })?;
+
+                let name = dn.is_english().then(|| name.to_owned());
+                (code, name)
+            }
+        };
+
+        if let Some(name) = name {
+            return Ok(js_string!(name).into());
+        }
+
+        // 7. If displayNames.[[Fallback]] is "code", return code.
+        // 8. Return undefined.
+        match dn.fallback {
+            Fallback::Code => Ok(js_string!(code).into()),
+            Fallback::None => Ok(JsValue::undefined()),
+        }
+    }
+
+    /// [`Intl.DisplayNames.prototype.resolvedOptions ( )`][spec].
+    ///
+    /// Returns a new object with properties reflecting the locale and options computed during the
+    /// construction of the current `Intl.DisplayNames` object.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-Intl.DisplayNames.prototype.resolvedOptions
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DisplayNames/resolvedOptions
+    fn resolved_options(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let displayNames be this value.
+        // 2. Perform ? RequireInternalSlot(displayNames, [[InitializedDisplayNames]]).
+        let dn = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message(
+                    "`resolvedOptions` can only be called on an `Intl.DisplayNames` object",
+                )
+            })?;
+
+        // 3. Let options be OrdinaryObjectCreate(%Object.prototype%).
+        let options = context
+            .intrinsics()
+            .templates()
+            .ordinary_object()
+            .create(OrdinaryObject, vec![]);
+
+        // 4. For each row of Table 16, except the header row, in table order, do
+        //     a. Let p be the Property value of the current row.
+        //     b. Let v be the value of displayNames's internal slot whose name is the Internal Slot value of the current row.
+        //     c. If v is not undefined, then
+        //         i. Perform ! CreateDataPropertyOrThrow(options, p, v).
+        let mut push = |key: &str, value: &str| {
+            options
+                .create_data_property_or_throw(js_string!(key), js_string!(value), context)
+                .expect("operation must not fail per the spec");
+        };
+
+        push("locale", &dn.locale.to_string());
+        push("style", dn.style.as_str());
+        push("type", dn.typ.as_str());
+        push("fallback", dn.fallback.as_str());
+        if let Some(language_display) = dn.language_display {
+            push("languageDisplay", language_display.as_str());
+        }
+
+        // 5. Return options.
+        Ok(options.into())
+    }
+
+    /// Returns `true` if the resolved locale of this `DisplayNames` is an English locale.
+    ///
+    /// ICU4X doesn't provide display names for calendars or date time fields yet, so only
+    /// English names are available for those types.
+    fn is_english(&self) -> bool {
+        self.locale.id.language.as_str() == "en"
+    }
+
+    /// Gets the display name of a language identifier, following the `languageDisplay` option.
+    ///
+    /// With the "dialect" display, the longest prefix of the identifier that has a name of its own
+    /// (e.g. "British English" for `en-GB`) is used. The subtags that aren't covered by the name
+    /// are appended between parentheses, e.g. "English (Latin, United Kingdom)".
+    fn language_name(
+        &self,
+        id: &LanguageIdentifier,
+        context: &Context,
+    ) -> JsResult<Option<String>> {
+        let languages = load::<LanguageDisplayNamesV1Marker>(&self.locale, context)?;
+        let languages = languages.get();
+
+        let mut script = id.script;
+        let mut region = id.region;
+        let mut name = None;
+
+        if self.language_display == Some(LanguageDisplay::Dialect) {
+            let locales = load::<LocaleDisplayNamesV1Marker>(&self.locale, context)?;
+            let locales = locales.get();
+
+            let candidates = [
+                (script.is_some() && region.is_some()).then_some((script, region)),
+                region.is_some().then_some((None, region)),
+                script.is_some().then_some((script, None)),
+            ];
+
+            for (candidate_script, candidate_region) in candidates.into_iter().flatten() {
+                let key = LanguageIdentifier {
+                    language: id.language,
+                    script: candidate_script,
+                    region: candidate_region,
+                    variants: icu_locid::subtags::Variants::new(),
+                }
+                .to_string();
+
+                if let Some(found) = get_name(
+                    self.style,
+                    &locales.names,
+                    &locales.short_names,
+                    UnvalidatedStr::from_str(&key),
+                ) {
+                    name = Some(found.to_owned());
+                    if candidate_script.is_some() {
+                        script = None;
+                    }
+                    if candidate_region.is_some() {
+                        region = None;
+                    }
+                    break;
+                }
+            }
+        }
+
+        let Some(name) = name.or_else(|| {
+            get_name(
+                self.style,
+                &languages.names,
+                &languages.short_names,
+                &id.language.into_tinystr().to_unvalidated(),
+            )
+            .map(ToOwned::to_owned)
+        }) else {
+            return Ok(None);
+        };
+
+        let mut qualifiers = Vec::new();
+
+        if let Some(script) = script {
+            let names = load::<ScriptDisplayNamesV1Marker>(&self.locale, context)?;
+            let names = names.get();
+            qualifiers.push(
+                get_name(
+                    self.style,
+                    &names.names,
+                    &names.short_names,
+                    &script.into_tinystr().to_unvalidated(),
+                )
+                .map_or_else(|| script.to_string(), ToOwned::to_owned),
+            );
+        }
+
+        if let Some(region) = region {
+            let names = load::<RegionDisplayNamesV1Marker>(&self.locale, context)?;
+            let names = names.get();
+            qualifiers.push(
+                get_name(
+                    self.style,
+                    &names.names,
+                    &names.short_names,
+                    &region.into_tinystr().to_unvalidated(),
+                )
+                .map_or_else(|| region.to_string(), ToOwned::to_owned),
+            );
+        }
+
+        if !id.variants.is_empty() {
+            let names = load::<VariantDisplayNamesV1Marker>(&self.locale, context)?;
+            let names = names.get();
+            for variant in id.variants.iter() {
+                qualifiers.push(
+                    names
+                        .names
+                        .get(&variant.into_tinystr().to_unvalidated())
+                        .map_or_else(|| variant.to_string(), ToOwned::to_owned),
+                );
+            }
+        }
+
+        if qualifiers.is_empty() {
+            return Ok(Some(name));
+        }
+
+        Ok(Some(format!("{name} ({})", qualifiers.join(", "))))
+    }
+}
+
+/// Gets the name of `key` from `names`, preferring `short_names` for the short and narrow styles.
+fn get_name<'a, K>(
+    style: Style,
+    names: &'a ZeroMap<'a, K, str>,
+    short_names: &'a ZeroMap<'a, K, str>,
+    key: &K,
+) -> Option<&'a str>
+where
+    K: ZeroMapKV<'a> + Ord + ?Sized,
+{
+    if style != Style::Long {
+        if let Some(name) = short_names.get(key) {
+            return Some(name);
+        }
+    }
+    names.get(key)
+}
+
+/// Loads the display names data of the marker `M` for the provided locale.
+fn load<M>(locale: &Locale, context: &Context) -> JsResult<DataPayload<M>>
+where
+    M: KeyedDataMarker,
+    IntlProvider: DataProvider<M>,
+{
+    DataProvider::<M>::load(
+        context.intl_provider(),
+        DataRequest {
+            locale: &DataLocale::from(locale),
+            metadata: DataRequestMetadata::default(),
+        },
+    )
+    .and_then(DataResponse::take_payload)
+    .map_err(|e| JsNativeError::typ().with_message(e.to_string()).into())
+}
+
+/// Gets the English display name of a calendar type.
+fn calendar_name(calendar: &str) -> Option<&'static str> {
+    let name = match calendar {
+        "buddhist" => "Buddhist Calendar",
+        "chinese" => "Chinese Calendar",
+        "coptic" => "Coptic Calendar",
+        "dangi" => "Dangi Calendar",
+        "ethioaa" => "Ethiopic Amete Alem Calendar",
+        "ethiopic" => "Ethiopic Calendar",
+        "gregory" => "Gregorian Calendar",
+        "hebrew" => "Hebrew Calendar",
+        "indian" => "Indian National Calendar",
+        "islamic" => "Hijri Calendar",
+        "islamic-civil" => "Hijri Calendar (tabular, civil epoch)",
+        "islamic-rgsa" => "Hijri Calendar (Saudi Arabia, sighting)",
+        "islamic-tbla" => "Hijri Calendar (tabular, astronomical epoch)",
+        "islamic-umalqura" => "Hijri Calendar (Umm al-Qura)",
+        "iso8601" => "ISO-8601 Calendar",
+        "japanese" => "Japanese Calendar",
+        "persian" => "Persian Calendar",
+        "roc" => "Minguo Calendar",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Abstract operation [`IsValidDateTimeFieldCode ( field )`][spec], returning the English
+/// display name of the field if it is valid.
+///
+/// [spec]: https://tc39.es/ecma402/#sec-isvaliddatetimefieldcode
+fn date_time_field_name(field: &str) -> Option<&'static str> {
+    // 1. If field is listed in the Code column of Table 9, return true.
+    // 2. Return false.
+    let name = match field {
+        "era" => "era",
+        "year" => "year",
+        "quarter" => "quarter",
+        "month" => "month",
+        "weekOfYear" => "week",
+        "weekday" => "day of the week",
+        "day" => "day",
+        "dayPeriod" => "AM/PM",
+        "hour" => "hour",
+        "minute" => "minute",
+        "second" => "second",
+        "timeZoneName" => "time zone",
+        _ => return None,
+    };
+    Some(name)
+}
@@ -0,0 +1,181 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::builtins::options::ParsableOptionType;
+
+/// The formatting style to use, as set by the `style` option.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Style {
+    Narrow,
+    Short,
+    #[default]
+    Long,
+}
+
+impl Style {
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Narrow => "narrow",
+            Self::Short => "short",
+            Self::Long => "long",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseStyleError;
+
+impl Display for ParseStyleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("provided string was not `narrow`, `short` or `long`")
+    }
+}
+
+impl FromStr for Style {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "narrow" => Ok(Self::Narrow),
+            "short" => Ok(Self::Short),
+            "long" => Ok(Self::Long),
+            _ => Err(ParseStyleError),
+        }
+    }
+}
+
+impl ParsableOptionType for Style {}
+
+/// The type of the codes that will be passed to `of`, as set by the `type` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DisplayNamesType {
+    Language,
+    Region,
+    Script,
+    Currency,
+    Calendar,
+    DateTimeField,
+}
+
+impl DisplayNamesType {
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Language => "language",
+            Self::Region => "region",
+            Self::Script => "script",
+            Self::Currency => "currency",
+            Self::Calendar => "calendar",
+            Self::DateTimeField => "dateTimeField",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseDisplayNamesTypeError;
+
+impl Display for ParseDisplayNamesTypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "provided string was not `language`, `region`, `script`, `currency`, \
+            `calendar` or `dateTimeField`",
+        )
+    }
+}
+
+impl FromStr for DisplayNamesType {
+    type Err = ParseDisplayNamesTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "language" => Ok(Self::Language),
+            "region" => Ok(Self::Region),
+            "script" => Ok(Self::Script),
+            "currency" => Ok(Self::Currency),
+            "calendar" => Ok(Self::Calendar),
+            "dateTimeField" => Ok(Self::DateTimeField),
+            _ => Err(ParseDisplayNamesTypeError),
+        }
+    }
+}
+
+impl ParsableOptionType for DisplayNamesType {}
+
+/// What to return from `of` when there's no display name for a code, as set by the `fallback`
+/// option.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Fallback {
+    #[default]
+    Code,
+    None,
+}
+
+impl Fallback {
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Code => "code",
+            Self::None => "none",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseFallbackError;
+
+impl Display for ParseFallbackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("provided string was not `code` or `none`")
+    }
+}
+
+impl FromStr for Fallback {
+    type Err = ParseFallbackError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "code" => Ok(Self::Code),
+            "none" => Ok(Self::None),
+            _ => Err(ParseFallbackError),
+        }
+    }
+}
+
+impl ParsableOptionType for Fallback {}
+
+/// How to display language codes, as set by the `languageDisplay` option.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum LanguageDisplay {
+    #[default]
+    Dialect,
+    Standard,
+}
+
+impl LanguageDisplay {
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dialect => "dialect",
+            Self::Standard => "standard",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseLanguageDisplayError;
+
+impl Display for ParseLanguageDisplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("provided string was not `dialect` or `standard`")
+    }
+}
+
+impl FromStr for LanguageDisplay {
+    type Err = ParseLanguageDisplayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dialect" => Ok(Self::Dialect),
+            "standard" => Ok(Self::Standard),
+            _ => Err(ParseLanguageDisplayError),
+        }
+    }
+}
+
+impl ParsableOptionType for LanguageDisplay {}
@@ -0,0 +1,23 @@
+use crate::{js_string, run_test_actions, TestAction};
+
+#[test]
+fn of() {
+    run_test_actions([
+        TestAction::assert_eq(
+            "new Intl.DisplayNames(['de'], { type: 'region' }).of('US')",
+            js_string!("Vereinigte Staaten"),
+        ),
+        TestAction::assert_eq(
+            "new Intl.DisplayNames(['de'], { type: 'language' }).of('fr')",
+            js_string!("Französisch"),
+        ),
+        TestAction::assert_eq(
+            "new Intl.DisplayNames(['de'], { type: 'currency' }).of('USD')",
+            js_string!("US-Dollar"),
+        ),
+        TestAction::assert_eq(
+            "new Intl.DisplayNames(['de'], { type: 'region', fallback: 'code' }).of('AA')",
+            js_string!("AA"),
+        ),
+    ]);
+}
@@ -0,0 +1,867 @@
+//! This module implements the global `Intl.DurationFormat` object.
+//!
+//! `Intl.DurationFormat` is a built-in object that enables language-sensitive duration formatting.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/proposal-intl-duration-format/#durationformat-objects
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DurationFormat
+
+use boa_gc::{Finalize, Trace};
+use boa_profiler::Profiler;
+use fixed_decimal::{FixedDecimal, Sign};
+use icu_list::{ListFormatter, ListLength};
+use icu_locid::{extensions::unicode::Value, Locale};
+use icu_provider::DataLocale;
+
+use crate::{
+    builtins::{
+        options::{get_option, get_options_object},
+        Array, BuiltInBuilder, BuiltInConstructor, BuiltInObject, IntrinsicObject, OrdinaryObject,
+    },
+    context::intrinsics::{Intrinsics, StandardConstructor, StandardConstructors},
+    js_string,
+    object::{internal_methods::get_prototype_from_constructor, JsObject},
+    property::Attribute,
+    realm::Realm,
+    string::{common::StaticJsStrings, utf16},
+    symbol::JsSymbol,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsString, JsValue,
+};
+
+use super::{
+    list_format::{create_parts_from_list, ListPart},
+    locale::{canonicalize_locale_list, resolve_locale, supported_locales},
+    number_format::{
+        IntlMathematicalValue, NumberFormat, NumberFormatLocaleOptions, Part, PartKind,
+    },
+    options::{get_number_option, IntlOptions},
+    Service,
+};
+
+#[cfg(all(test, feature = "intl_bundled"))]
+mod tests;
+
+mod options;
+
+use options::{DurationUnit, Style, UnitDisplay, UnitOptions, UnitStyle};
+
+/// JavaScript `Intl.DurationFormat` object.
+#[derive(Debug, Trace, Finalize, JsData)]
+// SAFETY: `DurationFormat` doesn't contain any traceable data.
+#[boa_gc(unsafe_empty_trace)]
+pub(crate) struct DurationFormat {
+    locale: Locale,
+    numbering_system: Option<Value>,
+    style: Style,
+    units: [UnitOptions; 10],
+    fractional_digits: Option<u8>,
+}
+
+impl Service for DurationFormat {
+    type LangMarker = <NumberFormat as Service>::LangMarker;
+
+    type LocaleOptions = NumberFormatLocaleOptions;
+
+    fn resolve(
+        locale: &mut Locale,
+        options: &mut Self::LocaleOptions,
+        provider: &crate::context::icu::IntlProvider,
+    ) {
+        // The only relevant extension key is "nu", which is resolved in the same way as
+        // `Intl.NumberFormat`.
+        <NumberFormat as Service>::resolve(locale, options, provider);
+    }
+}
+
+impl IntrinsicObject for DurationFormat {
+    fn init(realm: &Realm) {
+        let _timer = Profiler::global().start_event(std::any::type_name::<Self>(), "init");
+
+        BuiltInBuilder::from_standard_constructor::<Self>(realm)
+            .static_method(
+                Self::supported_locales_of,
+                js_string!("supportedLocalesOf"),
+                1,
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("Intl.DurationFormat"),
+                Attribute::CONFIGURABLE,
+            )
+            .method(Self::format, js_string!("format"), 1)
+            .method(Self::format_to_parts, js_string!("formatToParts"), 1)
+            .method(Self::resolved_options, js_string!("resolvedOptions"), 0)
+            .build();
+    }
+
+    fn get(intrinsics: &Intrinsics) -> JsObject {
+        Self::STANDARD_CONSTRUCTOR(intrinsics.constructors()).constructor()
+    }
+}
+
+impl BuiltInObject for DurationFormat {
+    const NAME: JsString = StaticJsStrings::DURATION_FORMAT;
+}
+
+impl BuiltInConstructor for DurationFormat {
+    const LENGTH: usize = 0;
+
+    const STANDARD_CONSTRUCTOR: fn(&StandardConstructors) -> &StandardConstructor =
+        StandardConstructors::duration_format;
+
+    /// Constructor [`Intl.DurationFormat ( [ locales [ , options ] ] )`][spec].
+    ///
+    /// Constructor for `DurationFormat` objects.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-duration-format/#sec-intl-durationformat-constructor
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DurationFormat/DurationFormat
+    fn constructor(
+        new_target: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. If NewTarget is undefined, throw a TypeError exception.
+        if new_target.is_undefined() {
+            return Err(JsNativeError::typ()
+                .with_message("cannot call `Intl.DurationFormat` constructor without `new`")
+                .into());
+        }
+
+        // 2. Let durationFormat be ? OrdinaryCreateFromConstructor(NewTarget, "%DurationFormatPrototype%", « ... »).
+        let prototype = get_prototype_from_constructor(
+            new_target,
+            StandardConstructors::duration_format,
+            context,
+        )?;
+
+        let locales = args.get_or_undefined(0);
+        let options = args.get_or_undefined(1);
+
+        // 3. Let requestedLocales be ? CanonicalizeLocaleList(locales).
+        let requested_locales = canonicalize_locale_list(locales, context)?;
+
+        // 4. Let options be ? GetOptionsObject(options).
+        let options = get_options_object(options)?;
+
+        // 5. Let matcher be ? GetOption(options, "localeMatcher", string, « "lookup", "best fit" », "best fit").
+        let matcher = get_option(&options, utf16!("localeMatcher"), context)?.unwrap_or_default();
+
+        // 6. Let numberingSystem be ? GetOption(options, "numberingSystem", string, empty, undefined).
+        // 7. If numberingSystem is not undefined, then
+        //     a. If numberingSystem does not match the Unicode Locale Identifier type nonterminal, throw a RangeError exception.
+        let numbering_system = get_option(&options, utf16!("numberingSystem"), context)?;
+
+        // 8. Let opt be the Record { [[localeMatcher]]: matcher, [[nu]]: numberingSystem }.
+        let mut intl_options = IntlOptions {
+            matcher,
+            service_options: NumberFormatLocaleOptions { numbering_system },
+        };
+
+        // 9. Let r be ResolveLocale(%DurationFormat%.[[AvailableLocales]], requestedLocales, opt, %DurationFormat%.[[RelevantExtensionKeys]], %DurationFormat%.[[LocaleData]]).
+        // 10. Let locale be r.[[locale]].
+        // 11. Set durationFormat.[[Locale]] to locale.
+        // 12. Set durationFormat.[[NumberingSystem]] to r.[[nu]].
+        let locale = resolve_locale::<Self>(
+            &requested_locales,
+            &mut intl_options,
+            context.intl_provider(),
+        );
+        let numbering_system = intl_options.service_options.numbering_system;
+
+        // 13. Let style be ? GetOption(options, "style", string, « "long", "short", "narrow", "digital" », "short").
+        // 14. Set durationFormat.[[Style]] to style.
+        let style = get_option(&options, utf16!("style"), context)?.unwrap_or_default();
+
+        // 15. Let prevStyle be the empty String.
+        let mut prev_style = None;
+
+        // 16. For each row of Table 1, except the header row, in table order, do
+        let mut units = [UnitOptions {
+            style: UnitStyle::Short,
+            display: UnitDisplay::Auto,
+        }; 10];
+        for (unit, slot) in DurationUnit::ALL.into_iter().zip(&mut units) {
+            // a. Let styleSlot be the Style Slot value of the current row.
+            // b. Let displaySlot be the Display Slot value of the current row.
+            // c. Let unit be the Unit value of the current row.
+            // d. Let valueList be the Values value of the current row.
+            // e. Let digitalBase be the Digital Default value of the current row.
+            // f. Let unitOptions be ? GetDurationUnitOptions(unit, options, style, valueList, digitalBase, prevStyle).
+            let unit_options =
+                get_duration_unit_options(unit, &options, style, prev_style, context)?;
+
+            // g. Set the value of the styleSlot slot of durationFormat to unitOptions.[[Style]].
+            // h. Set the value of the displaySlot slot of durationFormat to unitOptions.[[Display]].
+            *slot = unit_options;
+
+            // i. If unit is one of "hours", "minutes", "seconds", "milliseconds", or "microseconds", then
+            //     i. Set prevStyle to unitOptions.[[Style]].
+            if matches!(
+                unit,
+                DurationUnit::Hours
+                    | DurationUnit::Minutes
+                    | DurationUnit::Seconds
+                    | DurationUnit::Milliseconds
+                    | DurationUnit::Microseconds
+            ) {
+                prev_style = Some(unit_options.style);
+            }
+        }
+
+        // 17. Set durationFormat.[[FractionalDigits]] to ? GetNumberOption(options, "fractionalDigits", 0, 9, undefined).
+        let fractional_digits =
+            get_number_option(&options, utf16!("fractionalDigits"), 0, 9, context)?;
+
+        // 18. Return durationFormat.
+        Ok(JsObject::from_proto_and_data_with_shared_shape(
+            context.root_shape(),
+            prototype,
+            Self {
+                locale,
+                numbering_system,
+                style,
+                units,
+                fractional_digits,
+            },
+        )
+        .into())
+    }
+}
+
+impl DurationFormat {
+    /// [`Intl.DurationFormat.supportedLocalesOf ( locales [ , options ] )`][spec].
+    ///
+    /// Returns an array containing those of the provided locales that are supported in duration
+    /// formatting without having to fall back to the runtime's default locale.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-duration-format/#sec-Intl.DurationFormat.supportedLocalesOf
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DurationFormat/supportedLocalesOf
+    fn supported_locales_of(
+        _: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let locales = args.get_or_undefined(0);
+        let options = args.get_or_undefined(1);
+
+        // 1. Let availableLocales be %DurationFormat%.[[AvailableLocales]].
+        // 2. Let requestedLocales be ? CanonicalizeLocaleList(locales).
+        let requested_locales = canonicalize_locale_list(locales, context)?;
+
+        // 3. Return ? SupportedLocales(availableLocales, requestedLocales, options).
+        supported_locales::<<Self as Service>::LangMarker>(&requested_locales, options, context)
+            .map(JsValue::from)
+    }
+
+    /// [`Intl.DurationFormat.prototype.format ( duration )`][spec].
+    ///
+    /// Formats a duration according to the locale and formatting options of this
+    /// `Intl.DurationFormat` object.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-duration-format/#sec-Intl.DurationFormat.prototype.format
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DurationFormat/format
+    fn format(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let df be this value.
+        // 2. Perform ? RequireInternalSlot(df, [[InitializedDurationFormat]]).
+        let df = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("`format` can only be called on an `Intl.DurationFormat` object")
+            })?;
+
+        // 3. Let record be ? ToDurationRecord(duration).
+        let record = to_duration_record(args.get_or_undefined(0), context)?;
+
+        // 4. Let parts be PartitionDurationFormatPattern(df, record).
+        let parts = df.partition_duration_format_pattern(&record, context)?;
+
+        // 5. Let result be the empty String.
+        // 6. For each Record { [[Type]], [[Value]], [[Unit]] } part in parts, do
+        //     a. Set result to the string-concatenation of result and part.[[Value]].
+        // 7. Return result.
+        let result: String = parts.iter().map(|(part, _)| part.value.as_str()).collect();
+
+        Ok(js_string!(result).into())
+    }
+
+    /// [`Intl.DurationFormat.prototype.formatToParts ( duration )`][spec].
+    ///
+    /// Returns an array of objects representing the formatted duration in parts that can be used
+    /// for custom locale-aware formatting.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-duration-format/#sec-Intl.DurationFormat.prototype.formatToParts
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DurationFormat/formatToParts
+    fn format_to_parts(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let df be this value.
+        // 2. Perform ? RequireInternalSlot(df, [[InitializedDurationFormat]]).
+        let df = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message(
+                    "`formatToParts` can only be called on an `Intl.DurationFormat` object",
+                )
+            })?;
+
+        // 3. Let record be ? ToDurationRecord(duration).
+        let record = to_duration_record(args.get_or_undefined(0), context)?;
+
+        // 4. Let parts be PartitionDurationFormatPattern(df, record).
+        let parts = df.partition_duration_format_pattern(&record, context)?;
+
+        // 5. Let result be ! ArrayCreate(0).
+        let result = Array::array_create(0, None, context)
+            .expect("creating an empty array with default proto must not fail");
+
+        // 6. Let n be 0.
+        // 7. For each Record { [[Type]], [[Value]], [[Unit]] } part in parts, do
+        for (n, (part, unit)) in parts.into_iter().enumerate() {
+            // a. Let obj be OrdinaryObjectCreate(%ObjectPrototype%).
+            let o = context
+                .intrinsics()
+                .templates()
+                .ordinary_object()
+                .create(OrdinaryObject, vec![]);
+
+            // b. Perform ! CreateDataPropertyOrThrow(obj, "type", part.[[Type]]).
+            o.create_data_property_or_throw(
+                utf16!("type"),
+                js_string!(part.kind.as_str()),
+                context,
+            )
+            .expect("operation must not fail per the spec");
+
+            // c. Perform ! CreateDataPropertyOrThrow(obj, "value", part.[[Value]]).
+            o.create_data_property_or_throw(
+                utf16!("value"),
+                js_string!(part.value.as_str()),
+                context,
+            )
+            .expect("operation must not fail per the spec");
+
+            // d. If part.[[Unit]] is not empty, perform ! CreateDataPropertyOrThrow(obj, "unit", part.[[Unit]]).
+            if let Some(unit) = unit {
+                o.create_data_property_or_throw(
+                    utf16!("unit"),
+                    js_string!(unit.singular()),
+                    context,
+                )
+                .expect("operation must not fail per the spec");
+            }
+
+            // e. Perform ! CreateDataPropertyOrThrow(result, ! ToString(n), obj).
+            // f. Increment n by 1.
+            result
+                .create_data_property_or_throw(n, o, context)
+                .expect("operation must not fail per the spec");
+        }
+
+        // 8. Return result.
+        Ok(result.into())
+    }
+
+    /// [`Intl.DurationFormat.prototype.resolvedOptions ( )`][spec].
+    ///
+    /// Returns a new object with properties reflecting the locale and options computed during the
+    /// construction of the current `Intl.DurationFormat` object.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-duration-format/#sec-Intl.DurationFormat.prototype.resolvedOptions
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DurationFormat/resolvedOptions
+    fn resolved_options(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let df be the this value.
+        // 2. Perform ? RequireInternalSlot(df, [[InitializedDurationFormat]]).
+        let df = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message(
+                    "`resolvedOptions` can only be called on an `Intl.DurationFormat` object",
+                )
+            })?;
+
+        // 3. Let options be OrdinaryObjectCreate(%Object.prototype%).
+        let options = context
+            .intrinsics()
+            .templates()
+            .ordinary_object()
+            .create(OrdinaryObject, vec![]);
+
+        // 4. For each row of Table 2, except the header row, in table order, do
+        //     a. Let p be the Property value of the current row.
+        //     b. Let v be the value of df's internal slot whose name is the Internal Slot value of the current row.
+        //     c. If p is "fractionalDigits", then
+        //         i. If v is not undefined, set v to 𝔽(v).
+        //     d. Else,
+        //         i. Assert: v is not undefined.
+        //     e. If v is "fractional", then
+        //         i. Assert: The Internal Slot value of the current row is [[MillisecondsStyle]], [[MicrosecondsStyle]], or [[NanosecondsStyle]] .
+        //         ii. Set v to "numeric".
+        //     f. If v is not undefined, then
+        //         i. Perform ! CreateDataPropertyOrThrow(options, p, v).
+        let mut push = |key: &str, value: JsValue| {
+            options
+                .create_data_property_or_throw(js_string!(key), value, context)
+                .expect("operation must not fail per the spec");
+        };
+
+        push("locale", js_string!(df.locale.to_string()).into());
+        push(
+            "numberingSystem",
+            js_string!(df
+                .numbering_system
+                .as_ref()
+                .map_or_else(|| String::from("latn"), ToString::to_string))
+            .into(),
+        );
+        push("style", js_string!(df.style.as_str()).into());
+
+        for (unit, options) in DurationUnit::ALL.into_iter().zip(df.units) {
+            push(unit.as_str(), js_string!(options.style.as_str()).into());
+            push(
+                unit.display_option(),
+                js_string!(options.display.as_str()).into(),
+            );
+        }
+
+        if let Some(fractional_digits) = df.fractional_digits {
+            push("fractionalDigits", fractional_digits.into());
+        }
+
+        // 5. Return options.
+        Ok(options.into())
+    }
+
+    /// Abstract operation [`PartitionDurationFormatPattern ( durationFormat, duration )`][spec].
+    ///
+    /// Splits the formatted duration into a list of parts, tagging the parts of each formatted
+    /// number with the unit it represents.
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-duration-format/#sec-partitiondurationformatpattern
+    fn partition_duration_format_pattern(
+        &self,
+        duration: &[f64; 10],
+        context: &mut Context,
+    ) -> JsResult<Vec<(Part, Option<DurationUnit>)>> {
+        // 1. Let result be a new empty List.
+        let mut elements: Vec<Vec<(Part, Option<DurationUnit>)>> = Vec::new();
+
+        // 2. Let signDisplayed be true.
+        let mut sign_displayed = true;
+        let negative = duration.iter().any(|value| *value < 0.0);
+
+        // 3. Let needSeparator be false.
+        let mut need_separator = false;
+
+        // 4. For each row of Table 1, except the header row, in table order, do
+        for (i, unit) in DurationUnit::ALL.into_iter().enumerate() {
+            // a. Let styleSlot be the Style Slot value of the current row.
+            // b. Let displaySlot be the Display Slot value of the current row.
+            // c. Let valueSlot be the Value Field value of the current row.
+            // d. Let style be durationFormat.[[<styleSlot>]].
+            // e. Let display be durationFormat.[[<displaySlot>]].
+            let UnitOptions { style, display } = self.units[i];
+
+            // f. Let value be duration.[[<valueSlot>]].
+            // g. If style is "fractional", the value was already formatted as part of a larger unit.
+            if style == UnitStyle::Fractional {
+                break;
+            }
+
+            // h. Let nfOpts be OrdinaryObjectCreate(null).
+            let nf_options = JsObject::with_null_proto();
+            let mut set = |key: &str, value: JsValue| {
+                nf_options
+                    .create_data_property_or_throw(js_string!(key), value, context)
+                    .expect("operation must not fail per the spec");
+            };
+
+            // i. If the next unit is fractional, then
+            let next_fractional = self
+                .units
+                .get(i + 1)
+                .is_some_and(|next| next.style == UnitStyle::Fractional);
+            let mut value = if next_fractional {
+                // i. Set value to value + the sum of the values of all smaller units, each
+                //    scaled down to a fraction of this unit.
+                let smaller = &duration[i..];
+                let exponent = 3 * (smaller.len() - 1);
+                #[allow(clippy::cast_possible_truncation)]
+                let total = smaller
+                    .iter()
+                    .fold(0i128, |acc, value| acc * 1000 + (value.abs() as i128));
+                #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                let decimal = FixedDecimal::from(total).multiplied_pow10(-(exponent as i16));
+
+                // ii. Let fractionalDigits be durationFormat.[[FractionalDigits]].
+                // iii. If fractionalDigits is undefined, then
+                //     1. Let maximumFractionDigits be 9𝔽.
+                //     2. Let minimumFractionDigits be +0𝔽.
+                // iv. Else,
+                //     1. Let maximumFractionDigits be fractionalDigits.
+                //     2. Let minimumFractionDigits be fractionalDigits.
+                // v. Perform ! CreateDataPropertyOrThrow(nfOpts, "maximumFractionDigits", maximumFractionDigits).
+                // vi. Perform ! CreateDataPropertyOrThrow(nfOpts, "minimumFractionDigits", minimumFractionDigits).
+                // vii. Perform ! CreateDataPropertyOrThrow(nfOpts, "roundingMode", "trunc").
+                set(
+                    "maximumFractionDigits",
+                    self.fractional_digits.unwrap_or(9).into(),
+                );
+                set(
+                    "minimumFractionDigits",
+                    self.fractional_digits.unwrap_or(0).into(),
+                );
+                set("roundingMode", js_string!("trunc").into());
+                decimal
+            } else {
+                #[allow(clippy::cast_possible_truncation)]
+                FixedDecimal::from(duration[i].abs() as i128)
+            };
+            let is_zero = value.is_zero();
+
+            // j. If style is "numeric" or "2-digit", the minutes are displayed whenever both the
+            //    hours and seconds are displayed, to avoid leaving a gap in the time.
+            let display_required = unit == DurationUnit::Minutes
+                && need_separator
+                && (self.units[i + 1].display == UnitDisplay::Always
+                    || duration[i + 1..].iter().any(|value| *value != 0.0));
+
+            // k. If value is not 0 or display is not "auto", then
+            if is_zero && display == UnitDisplay::Auto && !display_required {
+                if !style.is_numeric() {
+                    need_separator = false;
+                }
+                continue;
+            }
+
+            // i. If signDisplayed is true, display the sign of the duration on this unit.
+            // ii. Else, the sign is never displayed.
+            if negative && sign_displayed {
+                value.set_sign(Sign::Negative);
+                sign_displayed = false;
+            }
+
+            // iii. Perform ! CreateDataPropertyOrThrow(nfOpts, "numberingSystem", durationFormat.[[NumberingSystem]]).
+            if let Some(nu) = &self.numbering_system {
+                set("numberingSystem", js_string!(nu.to_string()).into());
+            }
+
+            let parts = if style.is_numeric() {
+                // iv. If style is "2-digit", then
+                //     1. Perform ! CreateDataPropertyOrThrow(nfOpts, "minimumIntegerDigits", 2𝔽).
+                if style == UnitStyle::TwoDigit {
+                    set("minimumIntegerDigits", 2.into());
+                }
+                set("useGrouping", false.into());
+
+                // v. Let nf be ! Construct(%NumberFormat%, « durationFormat.[[Locale]], nfOpts »).
+                // vi. Let parts be ! PartitionNumberPattern(nf, value).
+                let nf = NumberFormat::new(
+                    &js_string!(self.locale.to_string()).into(),
+                    &nf_options.into(),
+                    context,
+                )?;
+                nf.partition_number_pattern(IntlMathematicalValue::Decimal(value))
+            } else {
+                // iv. Perform ! CreateDataPropertyOrThrow(nfOpts, "style", "unit").
+                // v. Perform ! CreateDataPropertyOrThrow(nfOpts, "unit", numberFormatUnit).
+                // vi. Perform ! CreateDataPropertyOrThrow(nfOpts, "unitDisplay", style).
+                set("style", js_string!("unit").into());
+                set("unit", js_string!(unit.singular()).into());
+                set("unitDisplay", js_string!(style.as_str()).into());
+
+                // vii. Let nf be ! Construct(%NumberFormat%, « durationFormat.[[Locale]], nfOpts »).
+                // viii. Let parts be ! PartitionNumberPattern(nf, value).
+                let nf = NumberFormat::new(
+                    &js_string!(self.locale.to_string()).into(),
+                    &nf_options.into(),
+                    context,
+                )?;
+                nf.partition_number_pattern(IntlMathematicalValue::Decimal(value))
+            };
+
+            let parts = parts.into_iter().map(|part| (part, Some(unit)));
+
+            // ix. If needSeparator is true, append the time separator to the last element of
+            //     result, followed by the parts of this unit.
+            // x. Else, append the parts as a new element of result.
+            match elements.last_mut() {
+                Some(last) if need_separator && style.is_numeric() => {
+                    last.push((Part::new(PartKind::Literal, ":"), None));
+                    last.extend(parts);
+                }
+                _ => elements.push(parts.collect()),
+            }
+
+            // xi. Set needSeparator to true if this unit was formatted numerically.
+            need_separator = style.is_numeric();
+        }
+
+        // 5. Let lfOpts be OrdinaryObjectCreate(null).
+        // 6. Perform ! CreateDataPropertyOrThrow(lfOpts, "type", "unit").
+        // 7. Let listStyle be durationFormat.[[Style]].
+        // 8. If listStyle is "digital", then
+        //     a. Set listStyle to "short".
+        // 9. Perform ! CreateDataPropertyOrThrow(lfOpts, "style", listStyle).
+        // 10. Let lf be ! Construct(%ListFormat%, « durationFormat.[[Locale]], lfOpts »).
+        let length = match self.style {
+            Style::Long => ListLength::Wide,
+            Style::Short | Style::Digital => ListLength::Short,
+            Style::Narrow => ListLength::Narrow,
+        };
+        let lf = ListFormatter::try_new_unit_with_length_unstable(
+            context.intl_provider(),
+            &DataLocale::from(&self.locale),
+            length,
+        )
+        .map_err(|e| JsNativeError::typ().with_message(e.to_string()))?;
+
+        // 11. Let strings be a new empty List.
+        // 12. For each element parts of result, do
+        //     a. Let string be the empty String.
+        //     b. For each Record { [[Type]], [[Value]], [[Unit]] } part in parts, do
+        //         i. Set string to the string-concatenation of string and part.[[Value]].
+        //     c. Append string to strings.
+        let strings: Vec<String> = elements
+            .iter()
+            .map(|parts| parts.iter().map(|(part, _)| part.value.as_str()).collect())
+            .collect();
+
+        // 13. Let formattedPartsList be CreatePartsFromList(lf, strings).
+        let formatted = create_parts_from_list(&lf, strings.into_iter())?;
+
+        // 14. Let partitionedPartsList be a new empty List.
+        // 15. Let resultIndex be 0.
+        // 16. For each Record { [[Type]], [[Value]] } listPart in formattedPartsList, do
+        //     a. If listPart.[[Type]] is "element", then
+        //         i. Append all elements of result[resultIndex] to partitionedPartsList.
+        //         ii. Set resultIndex to resultIndex + 1.
+        //     b. Else,
+        //         i. Append the Record { [[Type]]: "literal", [[Value]]: listPart.[[Value]], [[Unit]]: empty } to partitionedPartsList.
+        let mut elements = elements.into_iter();
+        let mut result = Vec::new();
+        for part in formatted {
+            match part {
+                ListPart::Element(_) => result.extend(elements.next().into_iter().flatten()),
+                ListPart::Literal(literal) => {
+                    result.push((Part::new(PartKind::Literal, &literal), None));
+                }
+            }
+        }
+
+        // 17. Return partitionedPartsList.
+        Ok(result)
+    }
+}
+
+/// Abstract operation [`GetDurationUnitOptions ( unit, options, baseStyle, stylesList, digitalBase, prevStyle )`][spec].
+///
+/// Gets the style and display options of a single duration unit.
+///
+/// [spec]: https://tc39.es/proposal-intl-duration-format/#sec-getdurationunitoptions
+fn get_duration_unit_options(
+    unit: DurationUnit,
+    options: &JsObject,
+    base_style: Style,
+    prev_style: Option<UnitStyle>,
+    context: &mut Context,
+) -> JsResult<UnitOptions> {
+    // 1. Let style be ? GetOption(options, unit, string, stylesList, undefined).
+    let style = get_option::<UnitStyle>(options, &js_string!(unit.as_str()), context)?;
+    if let Some(style) = style {
+        if !unit.accepts(style) {
+            return Err(JsNativeError::range()
+                .with_message(format!("invalid style for the `{}` option", unit.as_str()))
+                .into());
+        }
+    }
+
+    // 2. Let displayDefault be "always".
+    let mut display_default = UnitDisplay::Always;
+
+    let prev_numeric = prev_style.is_some_and(UnitStyle::is_numeric);
+
+    // 3. If style is undefined, then
+    let mut style = if let Some(style) = style {
+        style
+    } else if base_style == Style::Digital {
+        // a. If baseStyle is "digital", then
+        //     i. If unit is not one of "hours", "minutes", or "seconds", then
+        //         1. Set displayDefault to "auto".
+        //     ii. Set style to digitalBase.
+        if !matches!(
+            unit,
+            DurationUnit::Hours | DurationUnit::Minutes | DurationUnit::Seconds
+        ) {
+            display_default = UnitDisplay::Auto;
+        }
+        unit.digital_default()
+    } else if prev_numeric {
+        // b. Else if prevStyle is "fractional", "numeric" or "2-digit", then
+        //     i. If unit is not one of "minutes" or "seconds", then
+        //         1. Set displayDefault to "auto".
+        //     ii. Set style to "numeric".
+        if !matches!(unit, DurationUnit::Minutes | DurationUnit::Seconds) {
+            display_default = UnitDisplay::Auto;
+        }
+        UnitStyle::Numeric
+    } else {
+        // c. Else,
+        //     i. Set displayDefault to "auto".
+        //     ii. Set style to baseStyle.
+        display_default = UnitDisplay::Auto;
+        match base_style {
+            Style::Long => UnitStyle::Long,
+            Style::Short | Style::Digital => UnitStyle::Short,
+            Style::Narrow => UnitStyle::Narrow,
+        }
+    };
+
+    // 4. If style is "numeric", then
+    //     a. If unit is one of "milliseconds", "microseconds", or "nanoseconds", then
+    //         i. Set style to "fractional".
+    //         ii. Set displayDefault to "auto".
+    if style == UnitStyle::Numeric && unit.is_subsecond() {
+        style = UnitStyle::Fractional;
+        display_default = UnitDisplay::Auto;
+    }
+
+    // 5. Let displayField be the string-concatenation of unit and "Display".
+    // 6. Let display be ? GetOption(options, displayField, string, « "auto", "always" », displayDefault).
+    let display = get_option(options, &js_string!(unit.display_option()), context)?
+        .unwrap_or(display_default);
+
+    // 7. If display is "always" and style is "fractional", then
+    //     a. Throw a RangeError exception.
+    if display == UnitDisplay::Always && style == UnitStyle::Fractional {
+        return Err(JsNativeError::range()
+            .with_message(format!(
+                "`{}` cannot be always displayed as a fraction",
+                unit.as_str()
+            ))
+            .into());
+    }
+
+    // 8. If prevStyle is "fractional", then
+    //     a. If style is not "fractional", then
+    //         i. Throw a RangeError exception.
+    if prev_style == Some(UnitStyle::Fractional) && style != UnitStyle::Fractional {
+        return Err(JsNativeError::range()
+            .with_message(format!(
+                "`{}` must be fractional after a fractional unit",
+                unit.as_str()
+            ))
+            .into());
+    }
+
+    // 9. If prevStyle is "numeric" or "2-digit", then
+    if matches!(prev_style, Some(UnitStyle::Numeric | UnitStyle::TwoDigit)) {
+        // a. If style is not "fractional", "numeric" and "2-digit", then
+        //     i. Throw a RangeError exception.
+        if !style.is_numeric() {
+            return Err(JsNativeError::range()
+                .with_message(format!(
+                    "`{}` must be numeric after a numeric unit",
+                    unit.as_str()
+                ))
+                .into());
+        }
+
+        // b. If unit is "minutes" or "seconds", then
+        //     i. Set style to "2-digit".
+        if matches!(unit, DurationUnit::Minutes | DurationUnit::Seconds) {
+            style = UnitStyle::TwoDigit;
+        }
+    }
+
+    // 10. Return the Record { [[Style]]: style, [[Display]]: display }.
+    Ok(UnitOptions { style, display })
+}
+
+/// Abstract operation [`ToDurationRecord ( input )`][spec].
+///
+/// Converts a `Temporal.Duration`-like object into a list of the values of its units, from the
+/// largest to the smallest.
+///
+/// [spec]: https://tc39.es/proposal-intl-duration-format/#sec-todurationrecord
+fn to_duration_record(input: &JsValue, context: &mut Context) -> JsResult<[f64; 10]> {
+    // 1. If Type(input) is not Object, then
+    let Some(input) = input.as_object() else {
+        // a. If Type(input) is String, throw a RangeError exception.
+        if input.is_string() {
+            return Err(JsNativeError::range()
+                .with_message("cannot format a duration string")
+                .into());
+        }
+        // b. Throw a TypeError exception.
+        return Err(JsNativeError::typ()
+            .with_message("the duration to format must be an object")
+            .into());
+    };
+
+    // 2. Let result be a new Duration Record with each field set to 0.
+    let mut result = [0.0; 10];
+    let mut any_defined = false;
+
+    // 3. For each field of the Duration Record, in alphabetical order, do
+    let mut fields = DurationUnit::ALL;
+    fields.sort_unstable_by_key(|unit| unit.as_str());
+    for unit in fields {
+        // a. Let value be ? Get(input, field).
+        let value = input.get(js_string!(unit.as_str()), context)?;
+
+        // b. If value is not undefined, then
+        if !value.is_undefined() {
+            any_defined = true;
+
+            // i. Set the field of result to ? ToIntegerIfIntegral(value).
+            let number = value.to_number(context)?;
+            if !number.is_finite() || number.fract() != 0.0 {
+                return Err(JsNativeError::range()
+                    .with_message(format!("`{}` must be an integral number", unit.as_str()))
+                    .into());
+            }
+            result[unit as usize] = number;
+        }
+    }
+
+    // 4. If all fields of the input were undefined, throw a TypeError exception.
+    if !any_defined {
+        return Err(JsNativeError::typ()
+            .with_message("the duration to format must have at least one unit")
+            .into());
+    }
+
+    // 5. If IsValidDurationRecord(result) is false, throw a RangeError exception.
+    if result.iter().any(|value| *value < 0.0) && result.iter().any(|value| *value > 0.0) {
+        return Err(JsNativeError::range()
+            .with_message("all the units of a duration must have the same sign")
+            .into());
+    }
+
+    // 6. Return result.
+    Ok(result)
+}
@@ -0,0 +1,265 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::builtins::options::ParsableOptionType;
+
+/// The base style of a `DurationFormat`, as set by the `style` option.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Style {
+    Long,
+    #[default]
+    Short,
+    Narrow,
+    Digital,
+}
+
+impl Style {
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Long => "long",
+            Self::Short => "short",
+            Self::Narrow => "narrow",
+            Self::Digital => "digital",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseStyleError;
+
+impl Display for ParseStyleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("provided string was not `long`, `short`, `narrow` or `digital`")
+    }
+}
+
+impl FromStr for Style {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "long" => Ok(Self::Long),
+            "short" => Ok(Self::Short),
+            "narrow" => Ok(Self::Narrow),
+            "digital" => Ok(Self::Digital),
+            _ => Err(ParseStyleError),
+        }
+    }
+}
+
+impl ParsableOptionType for Style {}
+
+/// The style of a single duration unit, as set by the `<unit>` options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnitStyle {
+    Long,
+    Short,
+    Narrow,
+    Numeric,
+    TwoDigit,
+    /// Internal style of the subsecond units that are displayed as the fraction of a larger unit.
+    Fractional,
+}
+
+impl UnitStyle {
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Long => "long",
+            Self::Short => "short",
+            Self::Narrow => "narrow",
+            // `resolvedOptions` reports fractional units as numeric.
+            Self::Numeric | Self::Fractional => "numeric",
+            Self::TwoDigit => "2-digit",
+        }
+    }
+
+    /// Returns `true` if this is one of the numeric styles, which are formatted without
+    /// unit labels.
+    pub(crate) const fn is_numeric(self) -> bool {
+        matches!(self, Self::Numeric | Self::TwoDigit | Self::Fractional)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseUnitStyleError;
+
+impl Display for ParseUnitStyleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("provided string was not `long`, `short`, `narrow`, `numeric` or `2-digit`")
+    }
+}
+
+impl FromStr for UnitStyle {
+    type Err = ParseUnitStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "long" => Ok(Self::Long),
+            "short" => Ok(Self::Short),
+            "narrow" => Ok(Self::Narrow),
+            "numeric" => Ok(Self::Numeric),
+            "2-digit" => Ok(Self::TwoDigit),
+            _ => Err(ParseUnitStyleError),
+        }
+    }
+}
+
+impl ParsableOptionType for UnitStyle {}
+
+/// Whether to always display a unit or only when it is nonzero, as set by the `<unit>Display`
+/// options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnitDisplay {
+    Auto,
+    Always,
+}
+
+impl UnitDisplay {
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Always => "always",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseUnitDisplayError;
+
+impl Display for ParseUnitDisplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("provided string was not `auto` or `always`")
+    }
+}
+
+impl FromStr for UnitDisplay {
+    type Err = ParseUnitDisplayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            _ => Err(ParseUnitDisplayError),
+        }
+    }
+}
+
+impl ParsableOptionType for UnitDisplay {}
+
+/// The resolved style and display of a duration unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct UnitOptions {
+    pub(crate) style: UnitStyle,
+    pub(crate) display: UnitDisplay,
+}
+
+/// A unit of a duration, in order from the largest to the smallest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum DurationUnit {
+    Years,
+    Months,
+    Weeks,
+    Days,
+    Hours,
+    Minutes,
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+
+impl DurationUnit {
+    /// All duration units, from the largest to the smallest.
+    pub(crate) const ALL: [Self; 10] = [
+        Self::Years,
+        Self::Months,
+        Self::Weeks,
+        Self::Days,
+        Self::Hours,
+        Self::Minutes,
+        Self::Seconds,
+        Self::Milliseconds,
+        Self::Microseconds,
+        Self::Nanoseconds,
+    ];
+
+    /// The name of the option and duration record field of this unit.
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Years => "years",
+            Self::Months => "months",
+            Self::Weeks => "weeks",
+            Self::Days => "days",
+            Self::Hours => "hours",
+            Self::Minutes => "minutes",
+            Self::Seconds => "seconds",
+            Self::Milliseconds => "milliseconds",
+            Self::Microseconds => "microseconds",
+            Self::Nanoseconds => "nanoseconds",
+        }
+    }
+
+    /// The name of the `<unit>Display` option of this unit.
+    pub(crate) const fn display_option(self) -> &'static str {
+        match self {
+            Self::Years => "yearsDisplay",
+            Self::Months => "monthsDisplay",
+            Self::Weeks => "weeksDisplay",
+            Self::Days => "daysDisplay",
+            Self::Hours => "hoursDisplay",
+            Self::Minutes => "minutesDisplay",
+            Self::Seconds => "secondsDisplay",
+            Self::Milliseconds => "millisecondsDisplay",
+            Self::Microseconds => "microsecondsDisplay",
+            Self::Nanoseconds => "nanosecondsDisplay",
+        }
+    }
+
+    /// The singular name of this unit, as used by `Intl.NumberFormat` and `formatToParts`.
+    pub(crate) const fn singular(self) -> &'static str {
+        match self {
+            Self::Years => "year",
+            Self::Months => "month",
+            Self::Weeks => "week",
+            Self::Days => "day",
+            Self::Hours => "hour",
+            Self::Minutes => "minute",
+            Self::Seconds => "second",
+            Self::Milliseconds => "millisecond",
+            Self::Microseconds => "microsecond",
+            Self::Nanoseconds => "nanosecond",
+        }
+    }
+
+    /// Returns `true` if the style is one of the valid values of the `<unit>` option.
+    pub(crate) const fn accepts(self, style: UnitStyle) -> bool {
+        match self {
+            Self::Years | Self::Months | Self::Weeks | Self::Days => {
+                matches!(
+                    style,
+                    UnitStyle::Long | UnitStyle::Short | UnitStyle::Narrow
+                )
+            }
+            Self::Hours | Self::Minutes | Self::Seconds => !matches!(style, UnitStyle::Fractional),
+            Self::Milliseconds | Self::Microseconds | Self::Nanoseconds => {
+                !matches!(style, UnitStyle::TwoDigit | UnitStyle::Fractional)
+            }
+        }
+    }
+
+    /// The style of this unit when the base style is `"digital"` and there's no explicit
+    /// `<unit>` option.
+    pub(crate) const fn digital_default(self) -> UnitStyle {
+        match self {
+            Self::Years | Self::Months | Self::Weeks | Self::Days => UnitStyle::Short,
+            _ => UnitStyle::Numeric,
+        }
+    }
+
+    /// Returns `true` if this is one of the subsecond units.
+    pub(crate) const fn is_subsecond(self) -> bool {
+        matches!(
+            self,
+            Self::Milliseconds | Self::Microseconds | Self::Nanoseconds
+        )
+    }
+}
@@ -0,0 +1,15 @@
+use crate::{js_string, run_test_actions, TestAction};
+
+#[test]
+fn format_list() {
+    run_test_actions([
+        TestAction::assert_eq(
+            "new Intl.DurationFormat('de', { style: 'long' }).format({ hours: 1, minutes: 2, seconds: 3 })",
+            js_string!("1 Stunde, 2 Minuten und 3 Sekunden"),
+        ),
+        TestAction::assert_eq(
+            "new Intl.DurationFormat('de', { style: 'digital' }).format({ hours: 1, minutes: 2, seconds: 3 })",
+            js_string!("1:02:03"),
+        ),
+    ]);
+}
@@ -0,0 +1,381 @@
+//! Locale information used by `Intl.supportedValuesOf` and the `Intl.Locale` info getters.
+//!
+//! Most of the values are computed by probing the data provider of the context, so that the
+//! reported values always match what the rest of the `Intl` services are able to use.
+
+use icu_calendar::{week::WeekCalculator, AnyCalendar, AnyCalendarKind};
+use icu_collator::provider::CollationMetadataV1Marker;
+use icu_datetime::{pattern::CoarseHourCycle, provider::calendar::TimeLengthsV1Marker};
+use icu_decimal::provider::DecimalSymbolsV1Marker;
+use icu_locid::{
+    extensions::unicode::Value, extensions_unicode_key as key, langid, subtags::Region,
+    LanguageIdentifier, Locale,
+};
+use icu_locid_transform::{Direction, LocaleDirectionality};
+use icu_provider::{DataLocale, DataProvider, DataRequest, DataRequestMetadata, DataResponse};
+use icu_timezone::provider::names::{Bcp47ToIanaMapV1Marker, IanaToBcp47MapV1Marker};
+
+use crate::{
+    builtins::intl::date_time_format::is_available_time_zone, context::icu::IntlProvider, Context,
+    JsNativeError, JsResult,
+};
+
+use super::validate_extension;
+
+/// Calendars that can be constructed by `AnyCalendar`.
+///
+/// `iso` is left out because it is not a valid ECMA-402 calendar identifier.
+const CALENDARS: [AnyCalendarKind; 17] = [
+    AnyCalendarKind::Buddhist,
+    AnyCalendarKind::Chinese,
+    AnyCalendarKind::Coptic,
+    AnyCalendarKind::Dangi,
+    AnyCalendarKind::Ethiopian,
+    AnyCalendarKind::EthiopianAmeteAlem,
+    AnyCalendarKind::Gregorian,
+    AnyCalendarKind::Hebrew,
+    AnyCalendarKind::Indian,
+    AnyCalendarKind::IslamicCivil,
+    AnyCalendarKind::IslamicObservational,
+    AnyCalendarKind::IslamicTabular,
+    AnyCalendarKind::IslamicUmmAlQura,
+    AnyCalendarKind::Japanese,
+    AnyCalendarKind::JapaneseExtended,
+    AnyCalendarKind::Persian,
+    AnyCalendarKind::Roc,
+];
+
+/// Collation types defined by CLDR, along with the language that tailors them.
+///
+/// `standard` and `search` are left out since ECMA-402 forbids them.
+const COLLATIONS: [(&str, LanguageIdentifier); 14] = [
+    ("big5han", langid!("zh")),
+    ("compat", langid!("ar")),
+    ("dict", langid!("si")),
+    ("emoji", LanguageIdentifier::UND),
+    ("eor", LanguageIdentifier::UND),
+    ("gb2312", langid!("zh")),
+    ("phonebk", langid!("de")),
+    ("phonetic", langid!("ln")),
+    ("pinyin", langid!("zh")),
+    ("searchjl", langid!("ko")),
+    ("stroke", langid!("zh")),
+    ("trad", langid!("es")),
+    ("unihan", langid!("ko")),
+    ("zhuyin", langid!("zh")),
+];
+
+/// [Numbering systems with simple digit mappings][spec], along with their zero digit and a
+/// language that uses them.
+///
+/// [spec]: https://tc39.es/ecma402/#table-numbering-system-digits
+const NUMBERING_SYSTEMS: [(&str, char, LanguageIdentifier); 24] = [
+    ("adlm", '\u{1E950}', langid!("ff-Adlm")),
+    ("arab", '\u{0660}', langid!("ar")),
+    ("arabext", '\u{06F0}', langid!("fa")),
+    ("beng", '\u{09E6}', langid!("bn")),
+    ("cakm", '\u{11136}', langid!("ccp")),
+    ("deva", '\u{0966}', langid!("mr")),
+    ("gujr", '\u{0AE6}', langid!("gu")),
+    ("guru", '\u{0A66}', langid!("pa")),
+    ("hanidec", '\u{3007}', langid!("zh")),
+    ("java", '\u{A9D0}', langid!("jv")),
+    ("khmr", '\u{17E0}', langid!("km")),
+    ("knda", '\u{0CE6}', langid!("kn")),
+    ("laoo", '\u{0ED0}', langid!("lo")),
+    ("latn", '0', LanguageIdentifier::UND),
+    ("mlym", '\u{0D66}', langid!("ml")),
+    ("mtei", '\u{ABF0}', langid!("mni")),
+    ("mymr", '\u{1040}', langid!("my")),
+    ("olck", '\u{1C50}', langid!("sat")),
+    ("orya", '\u{0B66}', langid!("or")),
+    ("sund", '\u{1BB0}', langid!("su")),
+    ("tamldec", '\u{0BE6}', langid!("ta")),
+    ("telu", '\u{0C66}', langid!("te")),
+    ("thai", '\u{0E50}', langid!("th")),
+    ("tibt", '\u{0F20}', langid!("dz")),
+];
+
+/// Gets the calendars supported by the provider, sorted by identifier.
+pub(in crate::builtins::intl) fn available_calendars(provider: &IntlProvider) -> Vec<&'static str> {
+    let mut calendars = CALENDARS
+        .into_iter()
+        .filter(|&kind| AnyCalendar::try_new_unstable(provider, kind).is_ok())
+        .map(AnyCalendarKind::as_bcp47_string)
+        .collect::<Vec<_>>();
+    calendars.sort_unstable();
+    calendars
+}
+
+/// Gets the collation types supported by the provider, sorted by identifier.
+pub(in crate::builtins::intl) fn available_collations(
+    provider: &IntlProvider,
+) -> Vec<&'static str> {
+    COLLATIONS
+        .iter()
+        .filter(|(co, lang)| is_valid_collation(lang, co, provider))
+        .map(|(co, _)| *co)
+        .collect()
+}
+
+/// Gets the numbering systems supported by the provider, sorted by identifier.
+pub(in crate::builtins::intl) fn available_numbering_systems(
+    provider: &IntlProvider,
+) -> Vec<&'static str> {
+    NUMBERING_SYSTEMS
+        .iter()
+        .filter(|(nu, zero, lang)| {
+            let mut locale = DataLocale::from(lang);
+            locale.set_unicode_ext(
+                key!("nu"),
+                Value::try_from_bytes(nu.as_bytes())
+                    .expect("numbering systems must be valid unicode extension values"),
+            );
+            zero_digit(&locale, provider) == Some(*zero)
+        })
+        .map(|(nu, ..)| *nu)
+        .collect()
+}
+
+/// Abstract operation [`CalendarsOfLocale ( loc )`][spec].
+///
+/// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-calendars-of-locale
+pub(in crate::builtins::intl) fn calendars_of_locale(
+    locale: &Locale,
+    provider: &IntlProvider,
+) -> Vec<String> {
+    // 1. If loc.[[Calendar]] is not undefined, then
+    //     a. Return CreateArrayFromList( « loc.[[Calendar]] » ).
+    if let Some(ca) = locale.extensions.unicode.keywords.get(&key!("ca")) {
+        return vec![ca.to_string()];
+    }
+
+    // 2-5. Return the preferred calendar of the locale.
+    let kind = AnyCalendar::try_new_for_locale_unstable(provider, &DataLocale::from(&locale.id))
+        .map_or(AnyCalendarKind::Gregorian, |calendar| calendar.kind());
+    vec![String::from(kind.as_bcp47_string())]
+}
+
+/// Abstract operation [`CollationsOfLocale ( loc )`][spec].
+///
+/// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-collations-of-locale
+pub(in crate::builtins::intl) fn collations_of_locale(
+    locale: &Locale,
+    provider: &IntlProvider,
+) -> Vec<String> {
+    // 1. If loc.[[Collation]] is not undefined, then
+    //     a. Return CreateArrayFromList( « loc.[[Collation]] » ).
+    if let Some(co) = locale.extensions.unicode.keywords.get(&key!("co")) {
+        return vec![co.to_string()];
+    }
+
+    // 2-5. Return the collation types tailored for the language of the locale, or for the
+    //      root locale.
+    COLLATIONS
+        .iter()
+        .filter(|(co, lang)| {
+            (lang.language.is_empty() || lang.language == locale.id.language)
+                && is_valid_collation(lang, co, provider)
+        })
+        .map(|(co, _)| String::from(*co))
+        .collect()
+}
+
+/// Abstract operation [`HourCyclesOfLocale ( loc )`][spec].
+///
+/// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-hour-cycles-of-locale
+pub(in crate::builtins::intl) fn hour_cycles_of_locale(
+    locale: &Locale,
+    provider: &IntlProvider,
+) -> JsResult<Vec<String>> {
+    // 1. If loc.[[HourCycle]] is not undefined, then
+    //     a. Return CreateArrayFromList( « loc.[[HourCycle]] » ).
+    if let Some(hc) = locale.extensions.unicode.keywords.get(&key!("hc")) {
+        return Ok(vec![hc.to_string()]);
+    }
+
+    // 2-5. Return the preferred hour cycle of the locale.
+    let lengths = DataProvider::<TimeLengthsV1Marker>::load(
+        provider,
+        DataRequest {
+            locale: &DataLocale::from(&locale.id),
+            metadata: DataRequestMetadata::default(),
+        },
+    )
+    .and_then(DataResponse::take_payload)
+    .map_err(|err| JsNativeError::typ().with_message(err.to_string()))?;
+
+    let hc = match lengths.get().preferred_hour_cycle {
+        CoarseHourCycle::H11H12 => "h12",
+        CoarseHourCycle::H23H24 => "h23",
+    };
+    Ok(vec![String::from(hc)])
+}
+
+/// Abstract operation [`NumberingSystemsOfLocale ( loc )`][spec].
+///
+/// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-numbering-systems-of-locale
+pub(in crate::builtins::intl) fn numbering_systems_of_locale(
+    locale: &Locale,
+    provider: &IntlProvider,
+) -> Vec<String> {
+    // 1. If loc.[[NumberingSystem]] is not undefined, then
+    //     a. Return CreateArrayFromList( « loc.[[NumberingSystem]] » ).
+    if let Some(nu) = locale.extensions.unicode.keywords.get(&key!("nu")) {
+        return vec![nu.to_string()];
+    }
+
+    // 2-5. Return the default numbering system of the locale, identified by its zero digit.
+    let zero = zero_digit(&DataLocale::from(&locale.id), provider);
+    let nu = NUMBERING_SYSTEMS
+        .iter()
+        .find(|(_, digit, _)| Some(*digit) == zero)
+        .map_or("latn", |(nu, ..)| nu);
+    vec![String::from(nu)]
+}
+
+/// Abstract operation [`TimeZonesOfLocale ( loc )`][spec].
+///
+/// Returns `None` if the locale doesn't have a region subtag.
+///
+/// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-time-zones-of-locale
+pub(in crate::builtins::intl) fn time_zones_of_locale(
+    locale: &Locale,
+    context: &Context,
+) -> JsResult<Option<Vec<String>>> {
+    // 1. Let locale be loc.[[Locale]].
+    // 2. Assert: locale matches the unicode_locale_id production.
+    // 3. Let region be the substring of locale corresponding to the unicode_region_subtag production.
+    let Some(region) = locale.id.region else {
+        return Ok(None);
+    };
+
+    let provider = context.intl_provider();
+    let request = DataRequest::default();
+    let ids = DataProvider::<IanaToBcp47MapV1Marker>::load(provider, request)
+        .and_then(DataResponse::take_payload)
+        .map_err(|err| JsNativeError::typ().with_message(err.to_string()))?;
+    let names = DataProvider::<Bcp47ToIanaMapV1Marker>::load(provider, request)
+        .and_then(DataResponse::take_payload)
+        .map_err(|err| JsNativeError::typ().with_message(err.to_string()))?;
+
+    // 4. Let list be a List of unique canonical time zone identifiers, which must be String
+    //    values indicating a canonical Zone name of the IANA Time Zone Database, ordered as if an
+    //    Array of the same values had been sorted using %Array.prototype.sort% using undefined as
+    //    comparefn, of those in common use in region.
+    // NOTE: Only the time zones supported by `Intl.DateTimeFormat` are returned.
+    let mut zones = ids
+        .get()
+        .bcp47_ids
+        .iter()
+        .zip(names.get().canonical_iana_ids.iter())
+        .filter(|(id, name)| {
+            is_time_zone_of_region(id.0.as_str(), region) && is_available_time_zone(name)
+        })
+        .map(|(_, name)| String::from(name))
+        .collect::<Vec<_>>();
+    zones.sort_unstable();
+
+    // 5. Return CreateArrayFromList( list ).
+    Ok(Some(zones))
+}
+
+/// Abstract operation [`TextInfoOfLocale ( loc )`][spec].
+///
+/// Returns `true` if the locale is written from right to left.
+///
+/// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-text-info-of-locale
+pub(in crate::builtins::intl) fn is_right_to_left(
+    locale: &Locale,
+    provider: &IntlProvider,
+) -> JsResult<bool> {
+    let directionality = LocaleDirectionality::try_new_unstable(provider)
+        .map_err(|err| JsNativeError::typ().with_message(err.to_string()))?;
+
+    Ok(directionality.get(&locale.id) == Some(Direction::RightToLeft))
+}
+
+/// The week information of a locale, as returned by [`week_info_of_locale`].
+#[derive(Debug, Clone, Copy)]
+pub(in crate::builtins::intl) struct WeekInfo {
+    /// The first day of the week, from 1 (Monday) to 7 (Sunday).
+    pub(in crate::builtins::intl) first_day: u8,
+    /// The days of the weekend, from 1 (Monday) to 7 (Sunday).
+    pub(in crate::builtins::intl) weekend: &'static [u8],
+    /// The minimal number of days required in the first week of a month or year.
+    pub(in crate::builtins::intl) minimal_days: u8,
+}
+
+/// Abstract operation [`WeekInfoOfLocale ( loc )`][spec].
+///
+/// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-week-info-of-locale
+pub(in crate::builtins::intl) fn week_info_of_locale(
+    locale: &Locale,
+    provider: &IntlProvider,
+) -> JsResult<WeekInfo> {
+    // Week data is keyed by region, so the likely region is needed for locales without one.
+    let mut id = locale.id.clone();
+    provider.locale_expander().maximize(&mut id);
+
+    let calculator = WeekCalculator::try_new_unstable(provider, &DataLocale::from(&id))
+        .map_err(|err| JsNativeError::typ().with_message(err.to_string()))?;
+
+    Ok(WeekInfo {
+        first_day: calculator.first_weekday as u8,
+        weekend: weekend_of_region(id.region),
+        minimal_days: calculator.min_week_days,
+    })
+}
+
+/// Gets the days of the weekend of a region.
+///
+/// ICU4X doesn't provide weekend data yet, so this encodes the regions that don't use a
+/// Saturday-Sunday weekend according to CLDR.
+fn weekend_of_region(region: Option<Region>) -> &'static [u8] {
+    let Some(region) = region else {
+        return &[6, 7];
+    };
+
+    match region.as_str() {
+        "AE" | "BH" | "DZ" | "EG" | "IL" | "IQ" | "JO" | "KW" | "LY" | "OM" | "QA" | "SA"
+        | "SD" | "SY" | "YE" => &[5, 6],
+        "AF" => &[4, 5],
+        "IR" => &[5],
+        "IN" | "UG" => &[7],
+        _ => &[6, 7],
+    }
+}
+
+/// Checks if `co` is a collation type tailored for `language` in the provider.
+fn is_valid_collation(language: &LanguageIdentifier, co: &str, provider: &IntlProvider) -> bool {
+    let co = Value::try_from_bytes(co.as_bytes())
+        .expect("collation types must be valid unicode extension values");
+    validate_extension::<CollationMetadataV1Marker>(language.clone(), key!("co"), &co, provider)
+}
+
+/// Gets the zero digit of the decimal symbols of a locale.
+fn zero_digit(locale: &DataLocale, provider: &IntlProvider) -> Option<char> {
+    DataProvider::<DecimalSymbolsV1Marker>::load(
+        provider,
+        DataRequest {
+            locale,
+            metadata: DataRequestMetadata::default(),
+        },
+    )
+    .and_then(DataResponse::take_payload)
+    .ok()
+    .map(|symbols| symbols.get().digits[0])
+}
+
+/// Checks if the BCP-47 time zone identifier `id` belongs to `region`.
+///
+/// Geographic BCP-47 time zone identifiers start with the lowercase region code of the zone,
+/// while the rest of identifiers (`utc`, `est5edt`, `utcw05`, ...) contain digits or have
+/// less than 5 characters.
+fn is_time_zone_of_region(id: &str, region: Region) -> bool {
+    id.len() >= 5
+        && !id.bytes().any(|b| b.is_ascii_digit())
+        && id
+            .get(..2)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(region.as_str()))
+}
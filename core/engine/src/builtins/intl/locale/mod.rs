@@ -10,16 +10,21 @@ use icu_locid::{
     extensions::unicode::Value, extensions_unicode_key as key, extensions_unicode_value as value,
 };
 
-#[cfg(test)]
+#[cfg(all(test, feature = "intl_bundled"))]
 mod tests;
 
 mod utils;
 pub(crate) use utils::*;
 
+mod info;
+pub(in crate::builtins::intl) use info::*;
+
 mod options;
 
 use crate::{
-    builtins::{BuiltInBuilder, BuiltInConstructor, BuiltInObject, IntrinsicObject},
+    builtins::{
+        Array, BuiltInBuilder, BuiltInConstructor, BuiltInObject, IntrinsicObject, OrdinaryObject,
+    },
     context::intrinsics::{Intrinsics, StandardConstructor, StandardConstructors},
     js_string,
     object::{internal_methods::get_prototype_from_constructor, JsObject},
@@ -86,6 +91,17 @@ impl IntrinsicObject for Locale {
             .method(Self::maximize, js_string!("maximize"), 0)
             .method(Self::minimize, js_string!("minimize"), 0)
             .method(Self::to_string, js_string!("toString"), 0)
+            .method(Self::get_calendars, js_string!("getCalendars"), 0)
+            .method(Self::get_collations, js_string!("getCollations"), 0)
+            .method(Self::get_hour_cycles, js_string!("getHourCycles"), 0)
+            .method(
+                Self::get_numbering_systems,
+                js_string!("getNumberingSystems"),
+                0,
+            )
+            .method(Self::get_time_zones, js_string!("getTimeZones"), 0)
+            .method(Self::get_text_info, js_string!("getTextInfo"), 0)
+            .method(Self::get_week_info, js_string!("getWeekInfo"), 0)
             .accessor(
                 js_string!("baseName"),
                 Some(base_name),
@@ -769,4 +785,250 @@ impl Locale {
             .map(|sc| js_string!(sc.to_string()).into())
             .unwrap_or_default())
     }
+
+    /// [`Intl.Locale.prototype.getCalendars ( )`][spec].
+    ///
+    /// Returns the calendars commonly used by the locale.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-Intl.Locale.prototype.getCalendars
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/Locale/getCalendars
+    pub(crate) fn get_calendars(
+        this: &JsValue,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let loc be the this value.
+        // 2. Perform ? RequireInternalSlot(loc, [[InitializedLocale]]).
+        let loc = Self::this_locale(this, "getCalendars")?;
+
+        // 3. Return ! CalendarsOfLocale(loc).
+        let list = calendars_of_locale(&loc, context.intl_provider());
+        Ok(Array::create_array_from_list(
+            list.into_iter().map(|value| js_string!(value).into()),
+            context,
+        )
+        .into())
+    }
+
+    /// [`Intl.Locale.prototype.getCollations ( )`][spec].
+    ///
+    /// Returns the collation types commonly used by the locale.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-Intl.Locale.prototype.getCollations
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/Locale/getCollations
+    pub(crate) fn get_collations(
+        this: &JsValue,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let loc be the this value.
+        // 2. Perform ? RequireInternalSlot(loc, [[InitializedLocale]]).
+        let loc = Self::this_locale(this, "getCollations")?;
+
+        // 3. Return ! CollationsOfLocale(loc).
+        let list = collations_of_locale(&loc, context.intl_provider());
+        Ok(Array::create_array_from_list(
+            list.into_iter().map(|value| js_string!(value).into()),
+            context,
+        )
+        .into())
+    }
+
+    /// [`Intl.Locale.prototype.getHourCycles ( )`][spec].
+    ///
+    /// Returns the hour cycles commonly used by the locale.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-Intl.Locale.prototype.getHourCycles
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/Locale/getHourCycles
+    pub(crate) fn get_hour_cycles(
+        this: &JsValue,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let loc be the this value.
+        // 2. Perform ? RequireInternalSlot(loc, [[InitializedLocale]]).
+        let loc = Self::this_locale(this, "getHourCycles")?;
+
+        // 3. Return ! HourCyclesOfLocale(loc).
+        let list = hour_cycles_of_locale(&loc, context.intl_provider())?;
+        Ok(Array::create_array_from_list(
+            list.into_iter().map(|value| js_string!(value).into()),
+            context,
+        )
+        .into())
+    }
+
+    /// [`Intl.Locale.prototype.getNumberingSystems ( )`][spec].
+    ///
+    /// Returns the numbering systems commonly used by the locale.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-Intl.Locale.prototype.getNumberingSystems
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/Locale/getNumberingSystems
+    pub(crate) fn get_numbering_systems(
+        this: &JsValue,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let loc be the this value.
+        // 2. Perform ? RequireInternalSlot(loc, [[InitializedLocale]]).
+        let loc = Self::this_locale(this, "getNumberingSystems")?;
+
+        // 3. Return ! NumberingSystemsOfLocale(loc).
+        let list = numbering_systems_of_locale(&loc, context.intl_provider());
+        Ok(Array::create_array_from_list(
+            list.into_iter().map(|value| js_string!(value).into()),
+            context,
+        )
+        .into())
+    }
+
+    /// [`Intl.Locale.prototype.getTimeZones ( )`][spec].
+    ///
+    /// Returns the time zones commonly used in the region of the locale, or `undefined` if the
+    /// locale doesn't have a region.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-Intl.Locale.prototype.getTimeZones
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/Locale/getTimeZones
+    pub(crate) fn get_time_zones(
+        this: &JsValue,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let loc be the this value.
+        // 2. Perform ? RequireInternalSlot(loc, [[InitializedLocale]]).
+        let loc = Self::this_locale(this, "getTimeZones")?;
+
+        // 3. Let locale be loc.[[Locale]].
+        // 4. If the unicode_language_id production of locale does not contain the
+        //    ["-" unicode_region_subtag] sequence, return undefined.
+        // 5. Return ! TimeZonesOfLocale(loc).
+        let Some(zones) = time_zones_of_locale(&loc, context)? else {
+            return Ok(JsValue::undefined());
+        };
+        Ok(Array::create_array_from_list(
+            zones.into_iter().map(|zone| js_string!(zone).into()),
+            context,
+        )
+        .into())
+    }
+
+    /// [`Intl.Locale.prototype.getTextInfo ( )`][spec].
+    ///
+    /// Returns the text layout information of the locale.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-Intl.Locale.prototype.getTextInfo
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/Locale/getTextInfo
+    pub(crate) fn get_text_info(
+        this: &JsValue,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let loc be the this value.
+        // 2. Perform ? RequireInternalSlot(loc, [[InitializedLocale]]).
+        let loc = Self::this_locale(this, "getTextInfo")?;
+
+        // 3. Let info be ! ObjectCreate(%Object.prototype%).
+        let info = context
+            .intrinsics()
+            .templates()
+            .ordinary_object()
+            .create(OrdinaryObject, vec![]);
+
+        // 4. Let dir be ! CharacterDirectionOfLocale(loc).
+        let dir = if is_right_to_left(&loc, context.intl_provider())? {
+            js_string!("rtl")
+        } else {
+            js_string!("ltr")
+        };
+
+        // 5. Perform ! CreateDataPropertyOrThrow(info, "direction", dir).
+        info.create_data_property_or_throw(utf16!("direction"), dir, context)
+            .expect("operation must not fail per the spec");
+
+        // 6. Return info.
+        Ok(info.into())
+    }
+
+    /// [`Intl.Locale.prototype.getWeekInfo ( )`][spec].
+    ///
+    /// Returns the week information of the locale.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-locale-info/#sec-Intl.Locale.prototype.getWeekInfo
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/Locale/getWeekInfo
+    pub(crate) fn get_week_info(
+        this: &JsValue,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let loc be the this value.
+        // 2. Perform ? RequireInternalSlot(loc, [[InitializedLocale]]).
+        let loc = Self::this_locale(this, "getWeekInfo")?;
+
+        // 3. Let info be ! ObjectCreate(%Object.prototype%).
+        let info = context
+            .intrinsics()
+            .templates()
+            .ordinary_object()
+            .create(OrdinaryObject, vec![]);
+
+        // 4. Let wi be ! WeekInfoOfLocale(loc).
+        let wi = week_info_of_locale(&loc, context.intl_provider())?;
+
+        // 5. Let we be ! CreateArrayFromList( wi.[[Weekend]] ).
+        let we = Array::create_array_from_list(
+            wi.weekend.iter().map(|&day| JsValue::from(day)),
+            context,
+        );
+
+        // 6. Perform ! CreateDataPropertyOrThrow(info, "firstDay", wi.[[FirstDay]]).
+        // 7. Perform ! CreateDataPropertyOrThrow(info, "weekend", we).
+        // 8. Perform ! CreateDataPropertyOrThrow(info, "minimalDays", wi.[[MinimalDays]]).
+        info.create_data_property_or_throw(utf16!("firstDay"), wi.first_day, context)
+            .expect("operation must not fail per the spec");
+        info.create_data_property_or_throw(utf16!("weekend"), we, context)
+            .expect("operation must not fail per the spec");
+        info.create_data_property_or_throw(utf16!("minimalDays"), wi.minimal_days, context)
+            .expect("operation must not fail per the spec");
+
+        // 9. Return info.
+        Ok(info.into())
+    }
+
+    /// Gets a copy of the locale of a `Locale` object, throwing a `TypeError` if `this` isn't
+    /// a `Locale` object.
+    fn this_locale(this: &JsValue, method: &str) -> JsResult<icu_locid::Locale> {
+        this.as_object()
+            .and_then(|obj| {
+                obj.downcast_ref::<icu_locid::Locale>()
+                    .map(|loc| loc.clone())
+            })
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message(format!(
+                        "`{method}` can only be called on a `Locale` object"
+                    ))
+                    .into()
+            })
+    }
 }
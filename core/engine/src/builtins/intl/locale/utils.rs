@@ -597,7 +597,7 @@ pub(in crate::builtins::intl) fn validate_extension<M: KeyedDataMarker>(
         .is_some()
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "intl_bundled"))]
 mod tests {
     use icu_locid::{langid, locale, Locale};
     use icu_plurals::provider::CardinalV1Marker;
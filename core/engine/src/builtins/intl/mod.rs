@@ -22,7 +22,7 @@ use crate::{
     realm::Realm,
     string::common::StaticJsStrings,
     symbol::JsSymbol,
-    Context, JsArgs, JsResult, JsString, JsValue,
+    Context, JsArgs, JsNativeError, JsResult, JsString, JsValue,
 };
 
 use boa_profiler::Profiler;
@@ -30,17 +30,25 @@ use icu_provider::KeyedDataMarker;
 
 pub(crate) mod collator;
 pub(crate) mod date_time_format;
+pub(crate) mod display_names;
+pub(crate) mod duration_format;
 pub(crate) mod list_format;
 pub(crate) mod locale;
 pub(crate) mod number_format;
 pub(crate) mod plural_rules;
+pub(crate) mod relative_time_format;
 pub(crate) mod segmenter;
 
 pub(crate) use self::{
-    collator::Collator, date_time_format::DateTimeFormat, list_format::ListFormat, locale::Locale,
-    plural_rules::PluralRules, segmenter::Segmenter,
+    collator::Collator, date_time_format::DateTimeFormat, display_names::DisplayNames,
+    duration_format::DurationFormat, list_format::ListFormat, locale::Locale,
+    number_format::NumberFormat, plural_rules::PluralRules,
+    relative_time_format::RelativeTimeFormat, segmenter::Segmenter,
 };
 
+#[cfg(all(test, feature = "intl_bundled"))]
+mod tests;
+
 mod options;
 
 /// JavaScript `Intl` object.
@@ -90,6 +98,15 @@ impl IntrinsicObject for Intl {
                     .constructor(),
                 PluralRules::ATTRIBUTE,
             )
+            .static_property(
+                NumberFormat::NAME,
+                realm
+                    .intrinsics()
+                    .constructors()
+                    .number_format()
+                    .constructor(),
+                NumberFormat::ATTRIBUTE,
+            )
             .static_property(
                 DateTimeFormat::NAME,
                 realm
@@ -99,11 +116,43 @@ impl IntrinsicObject for Intl {
                     .constructor(),
                 DateTimeFormat::ATTRIBUTE,
             )
+            .static_property(
+                RelativeTimeFormat::NAME,
+                realm
+                    .intrinsics()
+                    .constructors()
+                    .relative_time_format()
+                    .constructor(),
+                RelativeTimeFormat::ATTRIBUTE,
+            )
+            .static_property(
+                DisplayNames::NAME,
+                realm
+                    .intrinsics()
+                    .constructors()
+                    .display_names()
+                    .constructor(),
+                DisplayNames::ATTRIBUTE,
+            )
+            .static_property(
+                DurationFormat::NAME,
+                realm
+                    .intrinsics()
+                    .constructors()
+                    .duration_format()
+                    .constructor(),
+                DurationFormat::ATTRIBUTE,
+            )
             .static_method(
                 Self::get_canonical_locales,
                 js_string!("getCanonicalLocales"),
                 1,
             )
+            .static_method(
+                Self::supported_values_of,
+                js_string!("supportedValuesOf"),
+                1,
+            )
             .build();
     }
 
@@ -143,6 +192,78 @@ impl Intl {
             context,
         )))
     }
+
+    /// `Intl.supportedValuesOf ( key )`
+    ///
+    /// Returns an array containing the supported calendar, collation, currency, numbering system,
+    /// time zone or unit identifiers.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN docs][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.supportedvaluesof
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/supportedValuesOf
+    pub(crate) fn supported_values_of(
+        _: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let key be ? ToString(key).
+        let key = args.get_or_undefined(0).to_string(context)?;
+
+        let provider = context.intl_provider();
+        let list: Vec<JsValue> = match key.to_std_string_escaped().as_str() {
+            // 2. If key is "calendar", then
+            //     a. Let list be ! AvailableCanonicalCalendars( ).
+            "calendar" => locale::available_calendars(provider)
+                .into_iter()
+                .map(|ca| js_string!(ca).into())
+                .collect(),
+            // 3. Else if key is "collation", then
+            //     a. Let list be ! AvailableCanonicalCollations( ).
+            "collation" => locale::available_collations(provider)
+                .into_iter()
+                .map(|co| js_string!(co).into())
+                .collect(),
+            // 4. Else if key is "currency", then
+            //     a. Let list be ! AvailableCanonicalCurrencies( ).
+            "currency" => number_format::Currency::available(provider)?
+                .into_iter()
+                .map(|currency| js_string!(currency).into())
+                .collect(),
+            // 5. Else if key is "numberingSystem", then
+            //     a. Let list be ! AvailableCanonicalNumberingSystems( ).
+            "numberingSystem" => locale::available_numbering_systems(provider)
+                .into_iter()
+                .map(|nu| js_string!(nu).into())
+                .collect(),
+            // 6. Else if key is "timeZone", then
+            //     a. Let list be ! AvailablePrimaryTimeZoneIdentifiers( ).
+            "timeZone" => date_time_format::available_time_zones(context)?
+                .into_iter()
+                .map(|tz| js_string!(tz).into())
+                .collect(),
+            // 7. Else if key is "unit", then
+            //     a. Let list be ! AvailableCanonicalUnits( ).
+            "unit" => number_format::Unit::sanctioned_single_units()
+                .map(|unit| js_string!(unit).into())
+                .collect(),
+            // 8. Else,
+            //     a. Throw a RangeError exception.
+            _ => {
+                return Err(JsNativeError::range()
+                    .with_message(format!(
+                        "`{}` is not a supported key for `Intl.supportedValuesOf`",
+                        key.to_std_string_escaped()
+                    ))
+                    .into())
+            }
+        };
+
+        // 9. Return CreateArrayFromList( list ).
+        Ok(Array::create_array_from_list(list, context).into())
+    }
 }
 
 /// A service component that is part of the `Intl` API.
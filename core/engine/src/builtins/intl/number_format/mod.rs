@@ -1,4 +1,1841 @@
+use boa_gc::{custom_trace, Finalize, Trace};
+use boa_icu_provider::provider::{
+    CurrencyNamesV1Marker, LongUnitsV1Marker, NarrowUnitsV1Marker, NumberPatternsV1Marker,
+    ShortUnitsV1Marker, UnitsV1,
+};
+use boa_profiler::Profiler;
+use fixed_decimal::{FixedDecimal, FloatPrecision, Sign, SignDisplay};
+use icu_compactdecimal::provider::{
+    CompactDecimalPatternDataV1, Count, LongCompactDecimalFormatDataV1Marker, PatternULE,
+    ShortCompactDecimalFormatDataV1Marker,
+};
+use icu_decimal::{
+    options::GroupingStrategy,
+    provider::{DecimalSymbolsV1Marker, GroupingSizesV1},
+};
+use icu_locid::{extensions::unicode::Value, extensions_unicode_key as key, Locale};
+use icu_plurals::{PluralCategory, PluralRules as NativePluralRules};
+use icu_provider::{
+    DataLocale, DataMarker, DataPayload, DataProvider, DataRequest, DataRequestMetadata,
+    DataResponse, KeyedDataMarker,
+};
+use zerovec::maps::ZeroMap2dCursor;
+
+use crate::{
+    builtins::{
+        options::get_option, Array, BuiltInBuilder, BuiltInConstructor, BuiltInObject,
+        IntrinsicObject, OrdinaryObject,
+    },
+    context::{
+        icu::IntlProvider,
+        intrinsics::{Intrinsics, StandardConstructor, StandardConstructors},
+    },
+    js_string,
+    native_function::NativeFunction,
+    object::{
+        internal_methods::get_prototype_from_constructor, FunctionObjectBuilder, JsFunction,
+        JsObject,
+    },
+    property::Attribute,
+    realm::Realm,
+    string::{common::StaticJsStrings, utf16},
+    symbol::JsSymbol,
+    value::PreferredType,
+    Context, JsArgs, JsBigInt, JsData, JsNativeError, JsResult, JsString, JsValue,
+};
+
+use super::{
+    locale::{canonicalize_locale_list, resolve_locale, supported_locales, validate_extension},
+    options::{coerce_options_to_object, IntlOptions},
+    Service,
+};
+
+#[cfg(all(test, feature = "intl_bundled"))]
+mod tests;
+
 mod options;
 mod utils;
 pub(crate) use options::*;
 pub(crate) use utils::*;
+
+/// JavaScript `Intl.NumberFormat` object.
+#[derive(Debug, Finalize, JsData)]
+pub(crate) struct NumberFormat {
+    locale: Locale,
+    numbering_system: Option<Value>,
+    symbols: DataPayload<DecimalSymbolsV1Marker>,
+    patterns: DataPayload<NumberPatternsV1Marker>,
+    currency_names: Option<DataPayload<CurrencyNamesV1Marker>>,
+    unit_patterns: Option<DataPayload<UnitPatternsMarker>>,
+    compact_patterns: Option<DataPayload<CompactPatternsMarker>>,
+    plural_rules: Option<NativePluralRules>,
+    unit_options: UnitFormatOptions,
+    digit_options: DigitFormatOptions,
+    notation: Notation,
+    compact_display: CompactDisplay,
+    use_grouping: GroupingStrategy,
+    sign_display: SignDisplay,
+    bound_format: Option<JsFunction>,
+}
+
+// SAFETY: only `bound_format` is a traceable object.
+unsafe impl Trace for NumberFormat {
+    custom_trace!(this, mark, mark(&this.bound_format));
+}
+
+/// Marker of the display patterns of the units, which are loaded using the marker of the
+/// unit display of the `NumberFormat`.
+#[derive(Debug)]
+struct UnitPatternsMarker;
+
+impl DataMarker for UnitPatternsMarker {
+    type Yokeable = UnitsV1<'static>;
+}
+
+/// Marker of the compact decimal patterns, which are loaded using the marker of the compact
+/// display of the `NumberFormat`.
+#[derive(Debug)]
+struct CompactPatternsMarker;
+
+impl DataMarker for CompactPatternsMarker {
+    type Yokeable = CompactDecimalPatternDataV1<'static>;
+}
+
+/// The unit options of a `NumberFormat`, as set by [`SetNumberFormatUnitOptions`][spec].
+///
+/// [spec]: https://tc39.es/ecma402/#sec-setnumberformatunitoptions
+#[derive(Debug, Copy, Clone)]
+#[allow(variant_size_differences)]
+pub(crate) enum UnitFormatOptions {
+    Decimal,
+    Percent,
+    Currency {
+        currency: Currency,
+        display: CurrencyDisplay,
+        sign: CurrencySign,
+    },
+    Unit {
+        unit: Unit,
+        display: UnitDisplay,
+    },
+}
+
+impl UnitFormatOptions {
+    /// Abstract operation [`SetNumberFormatUnitOptions ( intlObj, options )`][spec].
+    ///
+    /// Gets the style, currency and unit options of the number formatter from the options object.
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-setnumberformatunitoptions
+    fn from_options(options: &JsObject, context: &mut Context) -> JsResult<Self> {
+        // 1. Let style be ? GetOption(options, "style", string, « "decimal", "percent", "currency", "unit" », "decimal").
+        // 2. Set intlObj.[[Style]] to style.
+        let style: Style = get_option(options, utf16!("style"), context)?.unwrap_or_default();
+
+        // 3. Let currency be ? GetOption(options, "currency", string, empty, undefined).
+        // 5. Else,
+        //     a. If IsWellFormedCurrencyCode(currency) is false, throw a RangeError exception.
+        let currency: Option<Currency> = get_option(options, utf16!("currency"), context)?;
+
+        // 4. If currency is undefined, then
+        //     a. If style is "currency", throw a TypeError exception.
+        if style == Style::Currency && currency.is_none() {
+            return Err(JsNativeError::typ()
+                .with_message("cannot format with a `currency` style without a `currency` option")
+                .into());
+        }
+
+        // 6. Let currencyDisplay be ? GetOption(options, "currencyDisplay", string, « "code", "symbol", "narrowSymbol", "name" », "symbol").
+        let currency_display =
+            get_option(options, utf16!("currencyDisplay"), context)?.unwrap_or_default();
+
+        // 7. Let currencySign be ? GetOption(options, "currencySign", string, « "standard", "accounting" », "standard").
+        let currency_sign =
+            get_option(options, utf16!("currencySign"), context)?.unwrap_or_default();
+
+        // 8. Let unit be ? GetOption(options, "unit", string, empty, undefined).
+        // 10. Else,
+        //     a. If IsWellFormedUnitIdentifier(unit) is false, throw a RangeError exception.
+        let unit: Option<Unit> = get_option(options, utf16!("unit"), context)?;
+
+        // 9. If unit is undefined, then
+        //     a. If style is "unit", throw a TypeError exception.
+        if style == Style::Unit && unit.is_none() {
+            return Err(JsNativeError::typ()
+                .with_message("cannot format with a `unit` style without a `unit` option")
+                .into());
+        }
+
+        // 11. Let unitDisplay be ? GetOption(options, "unitDisplay", string, « "short", "narrow", "long" », "short").
+        let unit_display = get_option(options, utf16!("unitDisplay"), context)?.unwrap_or_default();
+
+        Ok(match (style, currency, unit) {
+            // 12. If style is "currency", then
+            //     a. Set intlObj.[[Currency]] to the ASCII-uppercase of currency.
+            //     b. Set intlObj.[[CurrencyDisplay]] to currencyDisplay.
+            //     c. Set intlObj.[[CurrencySign]] to currencySign.
+            (Style::Currency, Some(currency), _) => Self::Currency {
+                currency,
+                display: currency_display,
+                sign: currency_sign,
+            },
+            // 13. If style is "unit", then
+            //     a. Set intlObj.[[Unit]] to unit.
+            //     b. Set intlObj.[[UnitDisplay]] to unitDisplay.
+            (Style::Unit, _, Some(unit)) => Self::Unit {
+                unit,
+                display: unit_display,
+            },
+            (Style::Percent, _, _) => Self::Percent,
+            _ => Self::Decimal,
+        })
+    }
+
+    /// Gets the style of these unit options.
+    const fn style(self) -> Style {
+        match self {
+            Self::Decimal => Style::Decimal,
+            Self::Percent => Style::Percent,
+            Self::Currency { .. } => Style::Currency,
+            Self::Unit { .. } => Style::Unit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(in crate::builtins::intl) struct NumberFormatLocaleOptions {
+    pub(in crate::builtins::intl) numbering_system: Option<Value>,
+}
+
+impl Service for NumberFormat {
+    type LangMarker = DecimalSymbolsV1Marker;
+
+    type LocaleOptions = NumberFormatLocaleOptions;
+
+    fn resolve(locale: &mut Locale, options: &mut Self::LocaleOptions, provider: &IntlProvider) {
+        let locale_nu = locale
+            .extensions
+            .unicode
+            .keywords
+            .get(&key!("nu"))
+            .cloned()
+            .filter(|nu| {
+                validate_extension::<Self::LangMarker>(locale.id.clone(), key!("nu"), nu, provider)
+            });
+
+        let option_nu = options.numbering_system.take().filter(|nu| {
+            validate_extension::<Self::LangMarker>(locale.id.clone(), key!("nu"), nu, provider)
+        });
+
+        locale.extensions.unicode.clear();
+
+        // The extension is only kept in the locale if the options didn't override it
+        // with a different value.
+        if let Some(nu) = &locale_nu {
+            if option_nu.as_ref().map_or(true, |opt| opt == nu) {
+                locale
+                    .extensions
+                    .unicode
+                    .keywords
+                    .set(key!("nu"), nu.clone());
+            }
+        }
+
+        options.numbering_system = option_nu.or(locale_nu);
+    }
+}
+
+impl IntrinsicObject for NumberFormat {
+    fn init(realm: &Realm) {
+        let _timer = Profiler::global().start_event(std::any::type_name::<Self>(), "init");
+
+        let format = BuiltInBuilder::callable(realm, Self::format)
+            .name(js_string!("get format"))
+            .build();
+
+        BuiltInBuilder::from_standard_constructor::<Self>(realm)
+            .static_method(
+                Self::supported_locales_of,
+                js_string!("supportedLocalesOf"),
+                1,
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("Intl.NumberFormat"),
+                Attribute::CONFIGURABLE,
+            )
+            .accessor(
+                js_string!("format"),
+                Some(format),
+                None,
+                Attribute::CONFIGURABLE,
+            )
+            .method(Self::format_to_parts, js_string!("formatToParts"), 1)
+            .method(Self::format_range, js_string!("formatRange"), 2)
+            .method(
+                Self::format_range_to_parts,
+                js_string!("formatRangeToParts"),
+                2,
+            )
+            .method(Self::resolved_options, js_string!("resolvedOptions"), 0)
+            .build();
+    }
+
+    fn get(intrinsics: &Intrinsics) -> JsObject {
+        Self::STANDARD_CONSTRUCTOR(intrinsics.constructors()).constructor()
+    }
+}
+
+impl BuiltInObject for NumberFormat {
+    const NAME: JsString = StaticJsStrings::NUMBER_FORMAT;
+}
+
+impl BuiltInConstructor for NumberFormat {
+    const LENGTH: usize = 0;
+
+    const STANDARD_CONSTRUCTOR: fn(&StandardConstructors) -> &StandardConstructor =
+        StandardConstructors::number_format;
+
+    /// Constructor [`Intl.NumberFormat ( [ locales [ , options ] ] )`][spec].
+    ///
+    /// Constructor for `NumberFormat` objects.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.numberformat
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/NumberFormat/NumberFormat
+    fn constructor(
+        new_target: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. If NewTarget is undefined, let newTarget be the active function object, else let newTarget be NewTarget.
+        let new_target = &if new_target.is_undefined() {
+            context
+                .active_function_object()
+                .unwrap_or_else(|| {
+                    context
+                        .intrinsics()
+                        .constructors()
+                        .number_format()
+                        .constructor()
+                })
+                .into()
+        } else {
+            new_target.clone()
+        };
+
+        let locales = args.get_or_undefined(0);
+        let options = args.get_or_undefined(1);
+
+        // 2. Let numberFormat be ? OrdinaryCreateFromConstructor(newTarget, "%NumberFormat.prototype%", « ... »).
+        let prototype = get_prototype_from_constructor(
+            new_target,
+            StandardConstructors::number_format,
+            context,
+        )?;
+
+        // 3. Perform ? InitializeNumberFormat(numberFormat, locales, options).
+        let number_format = Self::new(locales, options, context)?;
+
+        // 4. If the implementation supports the normative optional constructor mode of 4.3 Note 1, then
+        //     a. Let this be the this value.
+        //     b. Return ? ChainNumberFormat(numberFormat, NewTarget, this).
+        // 5. Return numberFormat.
+        Ok(JsObject::from_proto_and_data_with_shared_shape(
+            context.root_shape(),
+            prototype,
+            number_format,
+        )
+        .into())
+    }
+}
+
+impl NumberFormat {
+    /// Abstract operation [`InitializeNumberFormat ( numberFormat, locales, options )`][spec].
+    ///
+    /// Creates a new `NumberFormat` from the provided `locales` and `options`. This is also used
+    /// by `Number.prototype.toLocaleString` and `BigInt.prototype.toLocaleString` to avoid
+    /// allocating an object for every call.
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-initializenumberformat
+    pub(crate) fn new(
+        locales: &JsValue,
+        options: &JsValue,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        // 1. Let requestedLocales be ? CanonicalizeLocaleList(locales).
+        let requested_locales = canonicalize_locale_list(locales, context)?;
+
+        // 2. Set options to ? CoerceOptionsToObject(options).
+        let options = coerce_options_to_object(options, context)?;
+
+        // 3. Let opt be a new Record.
+        // 4. Let matcher be ? GetOption(options, "localeMatcher", string, « "lookup", "best fit" », "best fit").
+        // 5. Set opt.[[localeMatcher]] to matcher.
+        let matcher = get_option(&options, utf16!("localeMatcher"), context)?.unwrap_or_default();
+
+        // 6. Let numberingSystem be ? GetOption(options, "numberingSystem", string, empty, undefined).
+        // 7. If numberingSystem is not undefined, then
+        //     a. If numberingSystem cannot be matched by the type Unicode locale nonterminal, throw a RangeError exception.
+        // 8. Set opt.[[nu]] to numberingSystem.
+        let numbering_system = get_option(&options, utf16!("numberingSystem"), context)?;
+
+        let mut intl_options = IntlOptions {
+            matcher,
+            service_options: NumberFormatLocaleOptions { numbering_system },
+        };
+
+        // 9. Let localeData be %NumberFormat%.[[LocaleData]].
+        // 10. Let r be ResolveLocale(%NumberFormat%.[[AvailableLocales]], requestedLocales, opt, %NumberFormat%.[[RelevantExtensionKeys]], localeData).
+        // 11. Set numberFormat.[[Locale]] to r.[[locale]].
+        // 12. Set numberFormat.[[DataLocale]] to r.[[dataLocale]].
+        // 13. Set numberFormat.[[NumberingSystem]] to r.[[nu]].
+        let locale = resolve_locale::<Self>(
+            &requested_locales,
+            &mut intl_options,
+            context.intl_provider(),
+        );
+        let numbering_system = intl_options.service_options.numbering_system;
+
+        // 14. Perform ? SetNumberFormatUnitOptions(numberFormat, options).
+        let unit_options = UnitFormatOptions::from_options(&options, context)?;
+
+        // 15. Let style be numberFormat.[[Style]].
+        // 16. If style is "currency", then
+        //     a. Let currency be numberFormat.[[Currency]].
+        //     b. Let cDigits be CurrencyDigits(currency).
+        //     c. Let mnfdDefault be cDigits.
+        //     d. Let mxfdDefault be cDigits.
+        // 17. Else,
+        //     a. Let mnfdDefault be 0.
+        //     b. If style is "percent", then
+        //         i. Let mxfdDefault be 0.
+        //     c. Else,
+        //         i. Let mxfdDefault be 3.
+        let (min_fraction_default, max_fraction_default) = match unit_options {
+            UnitFormatOptions::Currency { currency, .. } => {
+                let digits = currency.digits();
+                (digits, digits)
+            }
+            UnitFormatOptions::Percent => (0, 0),
+            _ => (0, 3),
+        };
+
+        // 18. Let notation be ? GetOption(options, "notation", string, « "standard", "scientific", "engineering", "compact" », "standard").
+        // 19. Set numberFormat.[[Notation]] to notation.
+        let notation = get_option(&options, utf16!("notation"), context)?.unwrap_or_default();
+
+        // 20. Perform ? SetNumberFormatDigitOptions(numberFormat, options, mnfdDefault, mxfdDefault, notation).
+        let digit_options = get_digit_format_options(
+            &options,
+            min_fraction_default,
+            max_fraction_default,
+            notation,
+            context,
+        )?;
+
+        // 21. Let compactDisplay be ? GetOption(options, "compactDisplay", string, « "short", "long" », "short").
+        // 22. Let defaultUseGrouping be "auto".
+        // 23. If notation is "compact", then
+        //     a. Set numberFormat.[[CompactDisplay]] to compactDisplay.
+        //     b. Set defaultUseGrouping to "min2".
+        let compact_display =
+            get_option(&options, utf16!("compactDisplay"), context)?.unwrap_or_default();
+        let default_use_grouping = if notation == Notation::Compact {
+            GroupingStrategy::Min2
+        } else {
+            GroupingStrategy::Auto
+        };
+
+        // 24. NOTE: For historical reasons, the strings "true" and "false" are accepted and replaced with the default value.
+        // 25. Let useGrouping be ? GetBooleanOrStringNumberFormatOption(options, "useGrouping", « "min2", "auto", "always", "true", "false" », defaultUseGrouping).
+        // 26. If useGrouping is "true" or useGrouping is "false", set useGrouping to defaultUseGrouping.
+        // 27. If useGrouping is true, set useGrouping to "always".
+        // 28. Set numberFormat.[[UseGrouping]] to useGrouping.
+        let use_grouping = get_use_grouping_option(&options, default_use_grouping, context)?;
+
+        // 29. Let signDisplay be ? GetOption(options, "signDisplay", string, « "auto", "never", "always", "exceptZero", "negative" », "auto").
+        // 30. Set numberFormat.[[SignDisplay]] to signDisplay.
+        let sign_display =
+            get_option(&options, utf16!("signDisplay"), context)?.unwrap_or(SignDisplay::Auto);
+
+        let nu = numbering_system.as_ref();
+        let symbols = load(&locale, nu, context)?;
+        let patterns = load(&locale, nu, context)?;
+        let currency_names = match unit_options {
+            UnitFormatOptions::Currency { display, .. } if display != CurrencyDisplay::Code => {
+                Some(load(&locale, nu, context)?)
+            }
+            _ => None,
+        };
+        let unit_patterns = match unit_options {
+            UnitFormatOptions::Unit { display, .. } => Some(match display {
+                UnitDisplay::Short => load::<ShortUnitsV1Marker>(&locale, nu, context)?.cast(),
+                UnitDisplay::Narrow => load::<NarrowUnitsV1Marker>(&locale, nu, context)?.cast(),
+                UnitDisplay::Long => load::<LongUnitsV1Marker>(&locale, nu, context)?.cast(),
+            }),
+            _ => None,
+        };
+        let compact_patterns = if notation == Notation::Compact {
+            Some(match compact_display {
+                CompactDisplay::Short => {
+                    load::<ShortCompactDecimalFormatDataV1Marker>(&locale, nu, context)?.cast()
+                }
+                CompactDisplay::Long => {
+                    load::<LongCompactDecimalFormatDataV1Marker>(&locale, nu, context)?.cast()
+                }
+            })
+        } else {
+            None
+        };
+
+        // The plural category of the formatted number selects the display names of currencies
+        // and units, and the compact patterns.
+        let plural_rules = if matches!(
+            unit_options,
+            UnitFormatOptions::Currency {
+                display: CurrencyDisplay::Name,
+                ..
+            } | UnitFormatOptions::Unit { .. }
+        ) || compact_patterns.is_some()
+        {
+            Some(
+                NativePluralRules::try_new_cardinal_unstable(
+                    context.intl_provider(),
+                    &DataLocale::from(&locale),
+                )
+                .map_err(|e| JsNativeError::typ().with_message(e.to_string()))?,
+            )
+        } else {
+            None
+        };
+
+        // 31. Return numberFormat.
+        Ok(Self {
+            locale,
+            numbering_system,
+            symbols,
+            patterns,
+            currency_names,
+            unit_patterns,
+            compact_patterns,
+            plural_rules,
+            unit_options,
+            digit_options,
+            notation,
+            compact_display,
+            use_grouping,
+            sign_display,
+            bound_format: None,
+        })
+    }
+
+    /// [`Intl.NumberFormat.supportedLocalesOf ( locales [ , options ] )`][spec].
+    ///
+    /// Returns an array containing those of the provided locales that are supported in number
+    /// formatting without having to fall back to the runtime's default locale.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.numberformat.supportedlocalesof
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/NumberFormat/supportedLocalesOf
+    fn supported_locales_of(
+        _: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let locales = args.get_or_undefined(0);
+        let options = args.get_or_undefined(1);
+
+        // 1. Let availableLocales be %NumberFormat%.[[AvailableLocales]].
+        // 2. Let requestedLocales be ? CanonicalizeLocaleList(locales).
+        let requested_locales = canonicalize_locale_list(locales, context)?;
+
+        // 3. Return ? SupportedLocales(availableLocales, requestedLocales, options).
+        supported_locales::<<Self as Service>::LangMarker>(&requested_locales, options, context)
+            .map(JsValue::from)
+    }
+
+    /// [`get Intl.NumberFormat.prototype.format`][spec].
+    ///
+    /// Formats a number according to the locale and formatting options of this
+    /// `Intl.NumberFormat` object.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.numberformat.prototype.format
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/NumberFormat/format
+    fn format(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let nf be the this value.
+        // 2. If the implementation supports the normative optional constructor mode of 4.3 Note 1, then
+        //     a. Set nf to ? UnwrapNumberFormat(nf).
+        // 3. Perform ? RequireInternalSlot(nf, [[InitializedNumberFormat]]).
+        let this = this.as_object().ok_or_else(|| {
+            JsNativeError::typ()
+                .with_message("`format` can only be called on a `NumberFormat` object")
+        })?;
+        let nf_obj = this.clone();
+        let mut nf = this.downcast_mut::<Self>().ok_or_else(|| {
+            JsNativeError::typ()
+                .with_message("`format` can only be called on a `NumberFormat` object")
+        })?;
+
+        // 4. If nf.[[BoundFormat]] is undefined, then
+        //     a. Let F be a new built-in function object as defined in Number Format Functions (15.5.2).
+        //     b. Set F.[[NumberFormat]] to nf.
+        //     c. Set nf.[[BoundFormat]] to F.
+        let bound_format = if let Some(f) = nf.bound_format.clone() {
+            f
+        } else {
+            let bound_format = FunctionObjectBuilder::new(
+                context.realm(),
+                // 15.5.2 Number Format Functions
+                // https://tc39.es/ecma402/#sec-number-format-functions
+                NativeFunction::from_copy_closure_with_captures(
+                    |_, args, nf, context| {
+                        // 1. Let nf be F.[[NumberFormat]].
+                        // 2. Assert: Type(nf) is Object and nf has an [[InitializedNumberFormat]] internal slot.
+                        // 3. If value is not provided, let value be undefined.
+                        // 4. Let x be ? ToIntlMathematicalValue(value).
+                        let x = IntlMathematicalValue::from_js_value(
+                            args.get_or_undefined(0),
+                            context,
+                        )?;
+
+                        let nf = nf
+                            .downcast_ref::<Self>()
+                            .expect("checked above that the object was a number format object");
+
+                        // 5. Return FormatNumeric(nf, x).
+                        Ok(js_string!(nf.format_to_string(x)).into())
+                    },
+                    nf_obj,
+                ),
+            )
+            .length(1)
+            .build();
+
+            nf.bound_format = Some(bound_format.clone());
+            bound_format
+        };
+
+        // 5. Return nf.[[BoundFormat]].
+        Ok(bound_format.into())
+    }
+
+    /// [`Intl.NumberFormat.prototype.formatToParts ( value )`][spec].
+    ///
+    /// Returns an array of objects containing the locale-specific tokens from which it is possible
+    /// to build custom strings while preserving the locale-specific parts.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.numberformat.prototype.formattoparts
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/NumberFormat/formatToParts
+    fn format_to_parts(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let nf be the this value.
+        // 2. Perform ? RequireInternalSlot(nf, [[InitializedNumberFormat]]).
+        let nf = this
+            .as_object()
+            .filter(|o| o.is::<Self>())
+            .cloned()
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("`formatToParts` can only be called on a `NumberFormat` object")
+            })?;
+
+        // 3. Let x be ? ToIntlMathematicalValue(value).
+        let x = IntlMathematicalValue::from_js_value(args.get_or_undefined(0), context)?;
+
+        // 4. Return FormatNumericToParts(nf, x).
+        let parts = nf
+            .downcast_ref::<Self>()
+            .expect("checked above that the object was a number format object")
+            .partition_number_pattern(x);
+
+        Ok(parts_to_array(parts.into_iter().map(|part| (part, None)), context).into())
+    }
+
+    /// [`Intl.NumberFormat.prototype.formatRange ( start, end )`][spec].
+    ///
+    /// Formats a range of numbers according to the locale and formatting options of this
+    /// `Intl.NumberFormat` object.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.numberformat.prototype.formatrange
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/NumberFormat/formatRange
+    fn format_range(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let parts = Self::partition_range_from_args(this, args, "formatRange", context)?;
+
+        // 6. Return ! FormatNumericRange(nf, x, y).
+        let result: String = parts.iter().map(|(part, _)| part.value.as_str()).collect();
+        Ok(js_string!(result).into())
+    }
+
+    /// [`Intl.NumberFormat.prototype.formatRangeToParts ( start, end )`][spec].
+    ///
+    /// Returns an array of objects containing the locale-specific tokens of a formatted range.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.numberformat.prototype.formatrangetoparts
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/NumberFormat/formatRangeToParts
+    fn format_range_to_parts(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let parts = Self::partition_range_from_args(this, args, "formatRangeToParts", context)?;
+
+        // 6. Return ! FormatNumericRangeToParts(nf, x, y).
+        Ok(parts_to_array(
+            parts.into_iter().map(|(part, source)| (part, Some(source))),
+            context,
+        )
+        .into())
+    }
+
+    /// Shared steps of `formatRange` and `formatRangeToParts`.
+    fn partition_range_from_args(
+        this: &JsValue,
+        args: &[JsValue],
+        method: &str,
+        context: &mut Context,
+    ) -> JsResult<Vec<(Part, RangeSource)>> {
+        // 1. Let nf be the this value.
+        // 2. Perform ? RequireInternalSlot(nf, [[InitializedNumberFormat]]).
+        let nf = this
+            .as_object()
+            .filter(|o| o.is::<Self>())
+            .cloned()
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message(format!(
+                    "`{method}` can only be called on a `NumberFormat` object"
+                ))
+            })?;
+
+        // 3. If start is undefined or end is undefined, throw a TypeError exception.
+        let start = args.get_or_undefined(0);
+        let end = args.get_or_undefined(1);
+        if start.is_undefined() || end.is_undefined() {
+            return Err(JsNativeError::typ()
+                .with_message("extremum of range cannot be `undefined`")
+                .into());
+        }
+
+        // 4. Let x be ? ToIntlMathematicalValue(start).
+        let x = IntlMathematicalValue::from_js_value(start, context)?;
+        // 5. Let y be ? ToIntlMathematicalValue(end).
+        let y = IntlMathematicalValue::from_js_value(end, context)?;
+
+        let nf = nf
+            .downcast_ref::<Self>()
+            .expect("checked above that the object was a number format object");
+
+        nf.partition_number_range_pattern(x, y)
+    }
+
+    /// [`Intl.NumberFormat.prototype.resolvedOptions ( )`][spec].
+    ///
+    /// Returns a new object with properties reflecting the locale and number formatting options
+    /// computed during initialization of this `Intl.NumberFormat` object.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.numberformat.prototype.resolvedoptions
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/NumberFormat/resolvedOptions
+    fn resolved_options(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let nf be the this value.
+        // 2. If the implementation supports the normative optional constructor mode of 4.3 Note 1, then
+        //     a. Set nf to ? UnwrapNumberFormat(nf).
+        // 3. Perform ? RequireInternalSlot(nf, [[InitializedNumberFormat]]).
+        let nf = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("`resolvedOptions` can only be called on a `NumberFormat` object")
+            })?;
+
+        // 4. Let options be OrdinaryObjectCreate(%Object.prototype%).
+        let options = context
+            .intrinsics()
+            .templates()
+            .ordinary_object()
+            .create(OrdinaryObject, vec![]);
+
+        // 5. For each row of Table 12, except the header row, in table order, do
+        //     a. Let p be the Property value of the current row.
+        //     b. Let v be the value of nf's internal slot whose name is the Internal Slot value of the current row.
+        //     c. If v is not undefined, then
+        //         i. If there is a Conversion value in the current row, then
+        //             1. Assert: The Conversion value of the current row is number.
+        //             2. Set v to 𝔽(v).
+        //         ii. Perform ! CreateDataPropertyOrThrow(options, p, v).
+        let mut push = |key: &str, value: JsValue| {
+            options
+                .create_data_property_or_throw(js_string!(key), value, context)
+                .expect("operation must not fail per the spec");
+        };
+
+        push("locale", js_string!(nf.locale.to_string()).into());
+        push(
+            "numberingSystem",
+            js_string!(nf
+                .numbering_system
+                .as_ref()
+                .map_or_else(|| String::from("latn"), ToString::to_string))
+            .into(),
+        );
+        push(
+            "style",
+            js_string!(nf.unit_options.style().to_string()).into(),
+        );
+
+        match nf.unit_options {
+            UnitFormatOptions::Currency {
+                currency,
+                display,
+                sign,
+            } => {
+                push("currency", js_string!(currency.as_str()).into());
+                push("currencyDisplay", js_string!(display.to_string()).into());
+                push("currencySign", js_string!(sign.to_string()).into());
+            }
+            UnitFormatOptions::Unit { unit, display } => {
+                push("unit", js_string!(unit.to_string()).into());
+                push("unitDisplay", js_string!(display.to_string()).into());
+            }
+            UnitFormatOptions::Decimal | UnitFormatOptions::Percent => {}
+        }
+
+        push(
+            "minimumIntegerDigits",
+            nf.digit_options.minimum_integer_digits.into(),
+        );
+
+        if let Some(Extrema { minimum, maximum }) = nf.digit_options.rounding_type.fraction_digits()
+        {
+            push("minimumFractionDigits", minimum.into());
+            push("maximumFractionDigits", maximum.into());
+        }
+
+        if let Some(Extrema { minimum, maximum }) =
+            nf.digit_options.rounding_type.significant_digits()
+        {
+            push("minimumSignificantDigits", minimum.into());
+            push("maximumSignificantDigits", maximum.into());
+        }
+
+        push("useGrouping", grouping_strategy_to_value(nf.use_grouping));
+        push("notation", js_string!(nf.notation.to_string()).into());
+
+        if nf.notation == Notation::Compact {
+            push(
+                "compactDisplay",
+                js_string!(nf.compact_display.to_string()).into(),
+            );
+        }
+
+        push(
+            "signDisplay",
+            js_string!(sign_display_to_str(nf.sign_display)).into(),
+        );
+        push(
+            "roundingIncrement",
+            nf.digit_options.rounding_increment.to_u16().into(),
+        );
+        push(
+            "roundingMode",
+            js_string!(nf.digit_options.rounding_mode.to_string()).into(),
+        );
+        push(
+            "roundingPriority",
+            js_string!(nf.digit_options.rounding_priority.to_string()).into(),
+        );
+        push(
+            "trailingZeroDisplay",
+            js_string!(nf.digit_options.trailing_zero_display.to_string()).into(),
+        );
+
+        // 6. Return options.
+        Ok(options.into())
+    }
+
+    /// Gets the digit formatting options of this number format.
+    pub(crate) const fn digit_options(&self) -> &DigitFormatOptions {
+        &self.digit_options
+    }
+
+    /// Abstract operation [`FormatNumeric ( numberFormat, x )`][spec].
+    ///
+    /// Formats the mathematical value `x` into a string using the options of this number format.
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-formatnumber
+    pub(crate) fn format_to_string(&self, x: IntlMathematicalValue) -> String {
+        // 1. Let parts be ? PartitionNumberPattern(numberFormat, x).
+        // 2. Let result be the empty String.
+        // 3. For each Record { [[Type]], [[Value]] } part in parts, do
+        //     a. Set result to the string-concatenation of result and part.[[Value]].
+        // 4. Return result.
+        self.partition_number_pattern(x)
+            .into_iter()
+            .map(|part| part.value)
+            .collect()
+    }
+
+    /// Abstract operation [`PartitionNumberPattern ( numberFormat, x )`][spec].
+    ///
+    /// Splits the formatted representation of `x` into a list of typed parts.
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-partitionnumberpattern
+    pub(crate) fn partition_number_pattern(&self, x: IntlMathematicalValue) -> Vec<Part> {
+        let patterns = self.patterns.get();
+
+        let is_nan = matches!(x, IntlMathematicalValue::NotANumber);
+
+        // 1. Let exponent be 0.
+        let (mut number_parts, negative, zero, category) = match x {
+            // 2. If x is not-a-number, then
+            //     a. Let n be an implementation- and locale-dependent (ILD) String value indicating the NaN value.
+            IntlMathematicalValue::NotANumber => (
+                vec![Part::new(PartKind::Nan, &patterns.nan)],
+                false,
+                false,
+                PluralCategory::Other,
+            ),
+            // 3. Else if x is positive-infinity, then
+            //     a. Let n be an ILD String value indicating positive infinity.
+            // 4. Else if x is negative-infinity, then
+            //     a. Let n be an ILD String value indicating negative infinity.
+            IntlMathematicalValue::Infinity | IntlMathematicalValue::NegativeInfinity => (
+                vec![Part::new(PartKind::Infinity, &patterns.infinity)],
+                matches!(x, IntlMathematicalValue::NegativeInfinity),
+                false,
+                PluralCategory::Other,
+            ),
+            // 5. Else,
+            IntlMathematicalValue::Decimal(mut x) => {
+                // a. If x is not negative-zero, then
+                //     i. Assert: x is a mathematical value.
+                //     ii. If numberFormat.[[Style]] is "percent", let x be 100 × x.
+                if matches!(self.unit_options, UnitFormatOptions::Percent) {
+                    x.multiply_pow10(2);
+                }
+
+                //     iii. Set exponent to ComputeExponent(numberFormat, x).
+                let exponent = self.compute_exponent(&x);
+
+                //     iv. Let x be x × 10^-exponent.
+                x.multiply_pow10(-exponent);
+
+                // b. Let formatNumberResult be FormatNumericToString(numberFormat, x).
+                // c. Let n be formatNumberResult.[[FormattedString]].
+                // d. Let x be formatNumberResult.[[RoundedNumber]].
+                let x = format_fixed_decimal(x, &self.digit_options);
+                let negative = x.sign() == Sign::Negative;
+                let zero = x.is_zero();
+                let category = self
+                    .plural_rules
+                    .as_ref()
+                    .map_or(PluralCategory::Other, |rules| rules.category_for(&x));
+
+                let mut parts = self.partition_digits(&x);
+                self.partition_notation(exponent, &x, category, &mut parts);
+
+                (parts, negative, zero, category)
+            }
+        };
+
+        // 6. Let pattern be GetNumberFormatPattern(numberFormat, x).
+        // 7. Let result be a new empty List.
+        // 8. Let patternParts be PartitionPattern(pattern).
+        // 9. For each Record { [[Type]], [[Value]] } patternPart of patternParts, do
+        //     ...
+        let sign = match self.sign_display {
+            _ if is_nan => Sign::None,
+            SignDisplay::ExceptZero | SignDisplay::Negative if zero => Sign::None,
+            SignDisplay::Never => Sign::None,
+            _ if negative => Sign::Negative,
+            SignDisplay::Always | SignDisplay::ExceptZero => Sign::Positive,
+            _ => Sign::None,
+        };
+
+        let mut result = Vec::new();
+        match self.unit_options {
+            UnitFormatOptions::Decimal => {
+                self.push_sign(sign, true, &mut result);
+                result.append(&mut number_parts);
+                self.push_sign(sign, false, &mut result);
+            }
+            UnitFormatOptions::Percent => {
+                self.partition_affixes(
+                    &patterns.percent_pattern,
+                    sign,
+                    "",
+                    number_parts,
+                    &mut result,
+                );
+            }
+            UnitFormatOptions::Currency {
+                currency,
+                display: CurrencyDisplay::Name,
+                ..
+            } => {
+                let key = currency.to_data_key();
+                let count = Count::from(category);
+                let name = self
+                    .currency_names
+                    .as_ref()
+                    .map(DataPayload::get)
+                    .and_then(|names| {
+                        names
+                            .plural_names
+                            .get_2d(&key, &count)
+                            .or_else(|| names.plural_names.get_2d(&key, &Count::Other))
+                            .or_else(|| names.names.get(&key))
+                    })
+                    .unwrap_or(currency.as_str());
+                let pattern = patterns
+                    .currency_name_patterns
+                    .get(&count)
+                    .or_else(|| patterns.currency_name_patterns.get(&Count::Other))
+                    .unwrap_or("{0} {1}");
+
+                partition_placeholders(pattern, PartKind::Literal, &mut result, |index, result| {
+                    if index == 0 {
+                        self.push_sign(sign, true, result);
+                        result.append(&mut number_parts);
+                        self.push_sign(sign, false, result);
+                    } else {
+                        result.push(Part::new(PartKind::Currency, name));
+                    }
+                });
+            }
+            UnitFormatOptions::Currency {
+                currency,
+                display,
+                sign: currency_sign,
+            } => {
+                let key = currency.to_data_key();
+                let names = self.currency_names.as_ref().map(DataPayload::get);
+                let symbol = match display {
+                    CurrencyDisplay::Symbol => names.and_then(|names| names.symbols.get(&key)),
+                    CurrencyDisplay::NarrowSymbol => names.and_then(|names| {
+                        names
+                            .narrow_symbols
+                            .get(&key)
+                            .or_else(|| names.symbols.get(&key))
+                    }),
+                    CurrencyDisplay::Code | CurrencyDisplay::Name => None,
+                }
+                .unwrap_or(currency.as_str());
+                let pattern = if currency_sign == CurrencySign::Accounting {
+                    &patterns.accounting_pattern
+                } else {
+                    &patterns.currency_pattern
+                };
+
+                self.partition_affixes(pattern, sign, symbol, number_parts, &mut result);
+            }
+            UnitFormatOptions::Unit { unit, .. } => {
+                let pattern = self.unit_pattern(unit, category);
+                partition_placeholders(&pattern, PartKind::Unit, &mut result, |_, result| {
+                    self.push_sign(sign, true, result);
+                    result.append(&mut number_parts);
+                    self.push_sign(sign, false, result);
+                });
+            }
+        }
+
+        // 10. Return result.
+        result
+    }
+
+    /// Pushes the part of the prefix or the suffix of the sign of a number to `result`.
+    fn push_sign(&self, sign: Sign, prefix: bool, result: &mut Vec<Part>) {
+        let symbols = self.symbols.get();
+        let (affixes, kind) = match sign {
+            Sign::None => return,
+            Sign::Negative => (&symbols.minus_sign_affixes, PartKind::MinusSign),
+            Sign::Positive => (&symbols.plus_sign_affixes, PartKind::PlusSign),
+        };
+        let affix = if prefix {
+            &affixes.prefix
+        } else {
+            &affixes.suffix
+        };
+        if !affix.is_empty() {
+            result.push(Part::new(kind, affix));
+        }
+    }
+
+    /// Pushes the parts of a number displayed with a [CLDR number pattern][pattern] to `result`,
+    /// where `¤` is replaced by `currency`.
+    ///
+    /// The negative subpattern is used for negative numbers if the pattern has one. Otherwise,
+    /// the sign of the number is displayed before the positive subpattern.
+    ///
+    /// [pattern]: https://www.unicode.org/reports/tr35/tr35-numbers.html#Number_Format_Patterns
+    fn partition_affixes(
+        &self,
+        pattern: &str,
+        sign: Sign,
+        currency: &str,
+        mut number_parts: Vec<Part>,
+        result: &mut Vec<Part>,
+    ) {
+        let (positive, negative) = pattern
+            .split_once(';')
+            .map_or((pattern, None), |(positive, negative)| {
+                (positive, Some(negative))
+            });
+        let subpattern = negative.filter(|negative| {
+            sign == Sign::Negative || (sign == Sign::Positive && negative.contains('-'))
+        });
+
+        let pattern = subpattern.unwrap_or(positive);
+        let start = pattern.find(['#', '0', '@']).unwrap_or(pattern.len());
+        let end = pattern.rfind(['#', '0', '@']).map_or(start, |end| end + 1);
+        let affix_sign = if subpattern.is_some() {
+            sign
+        } else {
+            Sign::None
+        };
+        let prefix = self.affix_parts(&pattern[..start], affix_sign, currency);
+        let suffix = self.affix_parts(&pattern[end..], affix_sign, currency);
+
+        // Currency symbols that end with a letter, like currency codes, are separated from the
+        // number by a space, as defined by the `currencySpacing` data of CLDR.
+        let spaced = |part: Option<&Part>, letter: fn(&str) -> Option<char>| {
+            part.is_some_and(|part| {
+                part.kind == PartKind::Currency
+                    && letter(&part.value).is_some_and(char::is_alphanumeric)
+            })
+        };
+
+        if subpattern.is_none() {
+            self.push_sign(sign, true, result);
+        }
+        let prefix_spaced = spaced(prefix.last(), |value| value.chars().next_back());
+        result.extend(prefix);
+        if prefix_spaced {
+            result.push(Part::new(PartKind::Literal, "\u{A0}"));
+        }
+        result.append(&mut number_parts);
+        if spaced(suffix.first(), |value| value.chars().next()) {
+            result.push(Part::new(PartKind::Literal, "\u{A0}"));
+        }
+        result.extend(suffix);
+        if subpattern.is_none() {
+            self.push_sign(sign, false, result);
+        }
+    }
+
+    /// Splits the affix of a CLDR number pattern into its literal, sign, percent sign and
+    /// currency parts.
+    fn affix_parts(&self, affix: &str, sign: Sign, currency: &str) -> Vec<Part> {
+        let symbols = self.symbols.get();
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut quoted = false;
+        for c in affix.chars() {
+            let part = match c {
+                '\'' => {
+                    quoted = !quoted;
+                    continue;
+                }
+                _ if quoted => None,
+                '¤' => Some(Part::new(PartKind::Currency, currency)),
+                '%' => Some(Part::new(
+                    PartKind::PercentSign,
+                    &self.patterns.get().percent_sign,
+                )),
+                '-' => match sign {
+                    Sign::None => continue,
+                    Sign::Negative => Some(Part::new(
+                        PartKind::MinusSign,
+                        &symbols.minus_sign_affixes.prefix,
+                    )),
+                    Sign::Positive => Some(Part::new(
+                        PartKind::PlusSign,
+                        &symbols.plus_sign_affixes.prefix,
+                    )),
+                },
+                _ => None,
+            };
+            if let Some(part) = part {
+                if !literal.is_empty() {
+                    parts.push(Part::new(PartKind::Literal, &literal));
+                    literal.clear();
+                }
+                parts.push(part);
+            } else {
+                literal.push(c);
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::new(PartKind::Literal, &literal));
+        }
+        parts
+    }
+
+    /// Gets the pattern used to display the unit of a number of the provided plural category,
+    /// where `{0}` is replaced by the formatted number.
+    ///
+    /// Units without display data are displayed using their identifier.
+    fn unit_pattern(&self, unit: Unit, category: PluralCategory) -> String {
+        let units = self.unit_patterns.as_ref().map(DataPayload::get);
+        let pattern = |id: &str, count: Count| {
+            units.and_then(|units| {
+                units
+                    .patterns
+                    .get_2d(id, &count)
+                    .or_else(|| units.patterns.get_2d(id, &Count::Other))
+            })
+        };
+
+        let numerator = pattern(unit.numerator, Count::from(category))
+            .map_or_else(|| format!("{{0}} {}", unit.numerator), String::from);
+        let Some(denominator) = unit.denominator else {
+            return numerator;
+        };
+
+        if let Some(per_unit) = units.and_then(|units| units.per_unit_patterns.get(denominator)) {
+            return per_unit.replace("{0}", &numerator);
+        }
+
+        // Denominators without a specific pattern are displayed with the singular form of the
+        // unit, removing the placeholder of the number.
+        let denominator = pattern(denominator, Count::One).map_or(denominator, |pattern| {
+            pattern
+                .trim_start_matches("{0}")
+                .trim_end_matches("{0}")
+                .trim()
+        });
+        units
+            .map_or("{0}/{1}", |units| &units.compound_pattern)
+            .replace("{1}", denominator)
+            .replace("{0}", &numerator)
+    }
+
+    /// Splits the digits of a rounded number into integer, group, decimal and fraction parts.
+    fn partition_digits(&self, x: &FixedDecimal) -> Vec<Part> {
+        let symbols = self.symbols.get();
+        let digit = |m: i16| symbols.digits[usize::from(x.digit_at(m))];
+
+        let mut parts = Vec::new();
+        let range = x.magnitude_range();
+        let upper_magnitude = *range.end();
+
+        let mut integer = String::new();
+        for m in (0..=upper_magnitude).rev() {
+            integer.push(digit(m));
+            if m > 0
+                && check_grouping(
+                    upper_magnitude,
+                    m,
+                    self.use_grouping,
+                    symbols.grouping_sizes,
+                )
+            {
+                parts.push(Part::new(PartKind::Integer, &integer));
+                parts.push(Part::new(PartKind::Group, &symbols.grouping_separator));
+                integer.clear();
+            }
+        }
+        parts.push(Part::new(PartKind::Integer, &integer));
+
+        if *range.start() < 0 {
+            parts.push(Part::new(PartKind::Decimal, &symbols.decimal_separator));
+            let fraction: String = (*range.start()..0).rev().map(digit).collect();
+            parts.push(Part::new(PartKind::Fraction, &fraction));
+        }
+
+        parts
+    }
+
+    /// Abstract operation [`PartitionNotationSubPattern ( numberFormat, x, n, exponent )`][spec].
+    ///
+    /// Adds the compact or exponent parts of the notation to the parts of the rounded number `x`.
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-partitionnotationsubpattern
+    fn partition_notation(
+        &self,
+        exponent: i16,
+        x: &FixedDecimal,
+        category: PluralCategory,
+        parts: &mut Vec<Part>,
+    ) {
+        let symbols = self.symbols.get();
+        match self.notation {
+            Notation::Standard => {}
+            Notation::Scientific | Notation::Engineering => {
+                parts.push(Part::new(
+                    PartKind::ExponentSeparator,
+                    &self.patterns.get().exponential,
+                ));
+                if exponent < 0 {
+                    parts.push(Part::new(
+                        PartKind::ExponentMinusSign,
+                        &symbols.minus_sign_affixes.prefix,
+                    ));
+                }
+                let exponent: String = exponent
+                    .unsigned_abs()
+                    .to_string()
+                    .bytes()
+                    .map(|b| symbols.digits[usize::from(b - b'0')])
+                    .collect();
+                parts.push(Part::new(PartKind::ExponentInteger, &exponent));
+            }
+            Notation::Compact => {
+                let Some(patterns) =
+                    self.compact_patterns_for_magnitude(x.nonzero_magnitude_start() + exponent)
+                else {
+                    return;
+                };
+                let pattern = (*x == FixedDecimal::from(1))
+                    .then(|| patterns.get1(&Count::Explicit1))
+                    .flatten()
+                    .or_else(|| patterns.get1(&Count::from(category)))
+                    .or_else(|| patterns.get1(&Count::Other));
+                let Some(pattern) = pattern.filter(|pattern| pattern.exponent != 0) else {
+                    return;
+                };
+
+                let text = &pattern.literal_text;
+                let number = std::mem::take(parts);
+                if pattern.index == u8::MAX {
+                    // The pattern doesn't contain the number, like the French `mille`.
+                    push_text(text, PartKind::Compact, parts);
+                } else {
+                    let (before, after) = text.split_at(usize::from(pattern.index));
+                    push_text(before, PartKind::Compact, parts);
+                    parts.extend(number);
+                    push_text(after, PartKind::Compact, parts);
+                }
+            }
+        }
+    }
+
+    /// Gets the compact patterns used to display numbers of the provided magnitude, keyed by
+    /// plural category.
+    fn compact_patterns_for_magnitude(
+        &self,
+        magnitude: i16,
+    ) -> Option<ZeroMap2dCursor<'_, '_, i8, Count, PatternULE>> {
+        self.compact_patterns
+            .as_ref()?
+            .get()
+            .patterns
+            .iter0()
+            .filter(|patterns| i16::from(*patterns.key0()) <= magnitude)
+            .last()
+    }
+
+    /// Abstract operation [`ComputeExponent ( numberFormat, x )`][spec].
+    ///
+    /// Computes the base-10 exponent by which to scale `x` according to the number formatting
+    /// settings.
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-computeexponent
+    fn compute_exponent(&self, x: &FixedDecimal) -> i16 {
+        // 1. If x = 0, then
+        if x.is_zero() {
+            // a. Return 0.
+            return 0;
+        }
+
+        // 2. If x < 0, then
+        //     a. Let x = -x.
+        // 3. Let magnitude be the base 10 logarithm of x rounded down to the nearest integer.
+        let magnitude = x.nonzero_magnitude_start();
+
+        // 4. Let exponent be ComputeExponentForMagnitude(numberFormat, magnitude).
+        let exponent = self.compute_exponent_for_magnitude(magnitude);
+
+        // 5. Let x be x × 10^(-exponent).
+        // 6. Let formatNumberResult be FormatNumericToString(numberFormat, x).
+        let rounded =
+            format_fixed_decimal(x.clone().multiplied_pow10(-exponent), &self.digit_options);
+
+        // 7. If formatNumberResult.[[RoundedNumber]] = 0, then
+        if rounded.is_zero() {
+            // a. Return exponent.
+            return exponent;
+        }
+
+        // 8. Let newMagnitude be the base 10 logarithm of formatNumberResult.[[RoundedNumber]] rounded down to the nearest integer.
+        // 9. If newMagnitude is magnitude - exponent, then
+        if rounded.nonzero_magnitude_start() == magnitude - exponent {
+            // a. Return exponent.
+            return exponent;
+        }
+
+        // 10. Return ComputeExponentForMagnitude(numberFormat, magnitude + 1).
+        self.compute_exponent_for_magnitude(magnitude + 1)
+    }
+
+    /// Abstract operation [`ComputeExponentForMagnitude ( numberFormat, magnitude )`][spec].
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-computeexponentformagnitude
+    fn compute_exponent_for_magnitude(&self, magnitude: i16) -> i16 {
+        match self.notation {
+            // 4. Else,
+            //     a. Assert: notation is "standard".
+            //     b. Return 0.
+            Notation::Standard => 0,
+            // 2. If notation is "scientific", then
+            //     a. Return magnitude.
+            Notation::Scientific => magnitude,
+            // 3. Else if notation is "engineering", then
+            //     a. Let thousands be the greatest integer that is not greater than magnitude / 3.
+            //     b. Return thousands × 3.
+            Notation::Engineering => magnitude.div_euclid(3) * 3,
+            // 4. Else if notation is "compact", then
+            //     a. Let exponent be an implementation- and locale-dependent (ILD) integer by which to scale a number of the given magnitude in compact notation for the current locale.
+            //     b. Return exponent.
+            Notation::Compact => self
+                .compact_patterns_for_magnitude(magnitude)
+                .and_then(|patterns| patterns.get1(&Count::Other))
+                .map_or(0, |pattern| i16::from(pattern.exponent)),
+        }
+    }
+
+    /// Abstract operation [`PartitionNumberRangePattern ( numberFormat, x, y )`][spec].
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-partitionnumberrangepattern
+    fn partition_number_range_pattern(
+        &self,
+        x: IntlMathematicalValue,
+        y: IntlMathematicalValue,
+    ) -> JsResult<Vec<(Part, RangeSource)>> {
+        // 1. If x is not-a-number or y is not-a-number, throw a RangeError exception.
+        if matches!(x, IntlMathematicalValue::NotANumber)
+            || matches!(y, IntlMathematicalValue::NotANumber)
+        {
+            return Err(JsNativeError::range()
+                .with_message("extremum of range cannot be NaN")
+                .into());
+        }
+
+        // 2. Let xResult be ? PartitionNumberPattern(numberFormat, x).
+        let x_result = self.partition_number_pattern(x);
+        // 3. Let yResult be ? PartitionNumberPattern(numberFormat, y).
+        let y_result = self.partition_number_pattern(y);
+
+        // 4. If ! FormatNumeric(numberFormat, x) is equal to ! FormatNumeric(numberFormat, y), then
+        if x_result == y_result {
+            // a. Let appxResult be ? FormatApproximately(numberFormat, xResult).
+            // b. For each element r in appxResult, do
+            //     i. Set r.[[Source]] to "shared".
+            // c. Return appxResult.
+            let position = x_result
+                .iter()
+                .position(|part| {
+                    !matches!(
+                        part.kind,
+                        PartKind::MinusSign | PartKind::PlusSign | PartKind::Literal
+                    )
+                })
+                .unwrap_or_default();
+            let mut result = x_result;
+            result.insert(
+                position,
+                Part::new(
+                    PartKind::ApproximatelySign,
+                    &self.patterns.get().approximately_sign,
+                ),
+            );
+            return Ok(result
+                .into_iter()
+                .map(|part| (part, RangeSource::Shared))
+                .collect());
+        }
+
+        // 5. Let result be a new empty List.
+        // 6. For each element r in xResult, do
+        //     a. Append a new Record { [[Type]]: r.[[Type]], [[Value]]: r.[[Value]], [[Source]]: "startRange" } as the last element of result.
+        // 7. Let rangeSeparator be an ILND String value used to separate two numbers.
+        // 8. Append a new Record { [[Type]]: "literal", [[Value]]: rangeSeparator, [[Source]]: "shared" } element to result.
+        // 9. For each element r in yResult, do
+        //     a. Append a new Record { [[Type]]: r.[[Type]], [[Value]]: r.[[Value]], [[Source]]: "endRange" } as the last element of result.
+        // 10. Return ! CollapseNumberRange(result).
+        Ok(x_result
+            .into_iter()
+            .map(|part| (part, RangeSource::StartRange))
+            .chain(std::iter::once((
+                Part::new(PartKind::Literal, "–"),
+                RangeSource::Shared,
+            )))
+            .chain(
+                y_result
+                    .into_iter()
+                    .map(|part| (part, RangeSource::EndRange)),
+            )
+            .collect())
+    }
+}
+
+/// Loads the data used to format numbers for the provided locale and numbering system.
+fn load<M>(
+    locale: &Locale,
+    numbering_system: Option<&Value>,
+    context: &Context,
+) -> JsResult<DataPayload<M>>
+where
+    M: KeyedDataMarker,
+    IntlProvider: DataProvider<M>,
+{
+    let mut data_locale = DataLocale::from(locale);
+    if let Some(nu) = numbering_system {
+        data_locale.set_unicode_ext(key!("nu"), nu.clone());
+    }
+
+    DataProvider::<M>::load(
+        context.intl_provider(),
+        DataRequest {
+            locale: &data_locale,
+            metadata: DataRequestMetadata::default(),
+        },
+    )
+    .and_then(DataResponse::take_payload)
+    .map_err(|e| JsNativeError::typ().with_message(e.to_string()).into())
+}
+
+/// Abstract operation [`GetBooleanOrStringNumberFormatOption ( options, property, stringValues, fallback )`][spec]
+/// specialized for the `useGrouping` option.
+///
+/// [spec]: https://tc39.es/ecma402/#sec-getbooleanorstringnumberformatoption
+fn get_use_grouping_option(
+    options: &JsObject,
+    fallback: GroupingStrategy,
+    context: &mut Context,
+) -> JsResult<GroupingStrategy> {
+    // 1. Let value be ? Get(options, property).
+    let value = options.get(utf16!("useGrouping"), context)?;
+
+    // 2. If value is undefined, return fallback.
+    if value.is_undefined() {
+        return Ok(fallback);
+    }
+
+    // 3. If value is true, return true.
+    if value == JsValue::Boolean(true) {
+        return Ok(GroupingStrategy::Always);
+    }
+
+    // 4. If ToBoolean(value) is false, return false.
+    if !value.to_boolean() {
+        return Ok(GroupingStrategy::Never);
+    }
+
+    // 5. Let value be ? ToString(value).
+    // 6. If stringValues does not contain value, throw a RangeError exception.
+    // 7. Return value.
+    match value.to_string(context)?.to_std_string_escaped().as_str() {
+        "min2" => Ok(GroupingStrategy::Min2),
+        "auto" => Ok(GroupingStrategy::Auto),
+        "always" => Ok(GroupingStrategy::Always),
+        "true" | "false" => Ok(fallback),
+        _ => Err(JsNativeError::range()
+            .with_message("invalid value for option `useGrouping`")
+            .into()),
+    }
+}
+
+/// Checks if a grouping separator must be inserted after the digit at `magnitude`.
+fn check_grouping(
+    upper_magnitude: i16,
+    magnitude: i16,
+    strategy: GroupingStrategy,
+    sizes: GroupingSizesV1,
+) -> bool {
+    if sizes.primary == 0 {
+        return false;
+    }
+    let primary = i16::from(sizes.primary);
+    if magnitude < primary {
+        return false;
+    }
+    let min_grouping = match strategy {
+        GroupingStrategy::Never => return false,
+        GroupingStrategy::Always => 1,
+        GroupingStrategy::Min2 => i16::from(sizes.min_grouping).max(2),
+        _ => i16::from(sizes.min_grouping).max(1),
+    };
+    if upper_magnitude < primary + min_grouping - 1 {
+        return false;
+    }
+    let secondary = if sizes.secondary == 0 {
+        primary
+    } else {
+        i16::from(sizes.secondary)
+    };
+    (magnitude - primary) % secondary == 0
+}
+
+/// Splits a pattern with `{n}` placeholders into the parts of its text, calling `placeholder`
+/// with the index of each placeholder to push its parts.
+fn partition_placeholders(
+    pattern: &str,
+    kind: PartKind,
+    result: &mut Vec<Part>,
+    mut placeholder: impl FnMut(usize, &mut Vec<Part>),
+) {
+    let mut rest = pattern;
+    while let Some((text, index, tail)) = rest.split_once('{').and_then(|(text, tail)| {
+        let (index, tail) = tail.split_once('}')?;
+        Some((text, index.parse().ok()?, tail))
+    }) {
+        push_text(text, kind, result);
+        placeholder(index, result);
+        rest = tail;
+    }
+    push_text(rest, kind, result);
+}
+
+/// Pushes a text of a pattern as a part of the provided kind, splitting its leading and trailing
+/// whitespace into literal parts.
+fn push_text(text: &str, kind: PartKind, result: &mut Vec<Part>) {
+    let trimmed = text.trim_start();
+    let content = trimmed.trim_end();
+    for (kind, text) in [
+        (PartKind::Literal, &text[..text.len() - trimmed.len()]),
+        (kind, content),
+        (PartKind::Literal, &trimmed[content.len()..]),
+    ] {
+        if !text.is_empty() {
+            result.push(Part::new(kind, text));
+        }
+    }
+}
+
+/// A mathematical value, as defined by [`ToIntlMathematicalValue`][spec].
+///
+/// [spec]: https://tc39.es/ecma402/#sec-tointlmathematicalvalue
+#[derive(Debug, Clone)]
+pub(crate) enum IntlMathematicalValue {
+    NegativeInfinity,
+    Infinity,
+    NotANumber,
+    Decimal(FixedDecimal),
+}
+
+impl From<f64> for IntlMathematicalValue {
+    fn from(value: f64) -> Self {
+        if value.is_nan() {
+            Self::NotANumber
+        } else if value == f64::INFINITY {
+            Self::Infinity
+        } else if value == f64::NEG_INFINITY {
+            Self::NegativeInfinity
+        } else {
+            Self::Decimal(
+                FixedDecimal::try_from_f64(value, FloatPrecision::Floating)
+                    .expect("`value` must be finite"),
+            )
+        }
+    }
+}
+
+impl From<&JsBigInt> for IntlMathematicalValue {
+    fn from(value: &JsBigInt) -> Self {
+        value
+            .to_string()
+            .parse()
+            .map_or_else(|_| Self::from(value.to_f64()), Self::Decimal)
+    }
+}
+
+impl IntlMathematicalValue {
+    /// Abstract operation [`ToIntlMathematicalValue ( value )`][spec].
+    ///
+    /// Converts `value` into a mathematical value, preserving the precision of `BigInt`s and
+    /// decimal strings.
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-tointlmathematicalvalue
+    pub(crate) fn from_js_value(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        // 1. If value is a Number, return ℝ(value).
+        if let Some(number) = value.as_number() {
+            return Ok(Self::from(number));
+        }
+
+        // 2. Let primValue be ? ToPrimitive(value, number).
+        let prim_value = value.to_primitive(context, PreferredType::Number)?;
+
+        // 3. If primValue is a BigInt, return ℝ(primValue).
+        if let Some(bigint) = prim_value.as_bigint() {
+            return Ok(Self::from(bigint));
+        }
+
+        // 4. If primValue is a String, then
+        //     a. Let str be primValue.
+        // 5. Else,
+        //     a. Let x be ? ToNumber(primValue).
+        //     b. If x is -0𝔽, return negative-zero.
+        //     c. Let str be Number::toString(x, 10).
+        // 6. Let text be StringToCodePoints(str).
+        // 7. Let literal be ParseText(text, StringIntlMVLiteral).
+        // 8. If literal is a List of errors, return not-a-number.
+        // 9. Let intlMV be the StringIntlMV of literal.
+        // 10. If intlMV is a mathematical value, then
+        //     a. Let rounded be RoundMVResult(abs(intlMV)).
+        //     b. If rounded is +∞𝔽 and intlMV < 0, return negative-infinity.
+        //     c. If rounded is +∞𝔽, return positive-infinity.
+        //     d. If rounded is +0𝔽 and intlMV < 0, return negative-zero.
+        //     e. If rounded is +0𝔽, return 0.
+        // 11. Return intlMV.
+        if let Some(string) = prim_value.as_string() {
+            let string = string.to_std_string_escaped();
+            let trimmed = string.trim();
+            if trimmed
+                .bytes()
+                .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'-' | b'+' | b'e' | b'E'))
+            {
+                if let Ok(decimal) = trimmed.trim_start_matches('+').parse::<FixedDecimal>() {
+                    return Ok(Self::Decimal(decimal));
+                }
+            }
+        }
+
+        prim_value.to_number(context).map(Self::from)
+    }
+}
+
+/// The type of a part of a formatted number, as returned by `formatToParts`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum PartKind {
+    Literal,
+    Nan,
+    Infinity,
+    Integer,
+    Group,
+    Decimal,
+    Fraction,
+    MinusSign,
+    PlusSign,
+    PercentSign,
+    Currency,
+    Unit,
+    Compact,
+    ExponentSeparator,
+    ExponentMinusSign,
+    ExponentInteger,
+    ApproximatelySign,
+}
+
+impl PartKind {
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Literal => "literal",
+            Self::Nan => "nan",
+            Self::Infinity => "infinity",
+            Self::Integer => "integer",
+            Self::Group => "group",
+            Self::Decimal => "decimal",
+            Self::Fraction => "fraction",
+            Self::MinusSign => "minusSign",
+            Self::PlusSign => "plusSign",
+            Self::PercentSign => "percentSign",
+            Self::Currency => "currency",
+            Self::Unit => "unit",
+            Self::Compact => "compact",
+            Self::ExponentSeparator => "exponentSeparator",
+            Self::ExponentMinusSign => "exponentMinusSign",
+            Self::ExponentInteger => "exponentInteger",
+            Self::ApproximatelySign => "approximatelySign",
+        }
+    }
+}
+
+/// A typed part of a formatted number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Part {
+    pub(crate) kind: PartKind,
+    pub(crate) value: String,
+}
+
+impl Part {
+    pub(crate) fn new(kind: PartKind, value: &str) -> Self {
+        Self {
+            kind,
+            value: value.to_owned(),
+        }
+    }
+}
+
+/// The source of a part in a formatted range.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum RangeSource {
+    StartRange,
+    EndRange,
+    Shared,
+}
+
+impl RangeSource {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::StartRange => "startRange",
+            Self::EndRange => "endRange",
+            Self::Shared => "shared",
+        }
+    }
+}
+
+/// Converts a list of parts into an array of `{ type, value [, source] }` objects.
+fn parts_to_array(
+    parts: impl IntoIterator<Item = (Part, Option<RangeSource>)>,
+    context: &mut Context,
+) -> JsObject {
+    // 2. Let result be ! ArrayCreate(0).
+    let result = Array::array_create(0, None, context)
+        .expect("creating an empty array with default proto must not fail");
+
+    // 3. Let n be 0.
+    // 4. For each Record { [[Type]], [[Value]] } part in parts, do
+    for (n, (part, source)) in parts.into_iter().enumerate() {
+        // a. Let O be OrdinaryObjectCreate(%Object.prototype%).
+        let o = context
+            .intrinsics()
+            .templates()
+            .ordinary_object()
+            .create(OrdinaryObject, vec![]);
+
+        // b. Perform ! CreateDataPropertyOrThrow(O, "type", part.[[Type]]).
+        o.create_data_property_or_throw(utf16!("type"), js_string!(part.kind.as_str()), context)
+            .expect("operation must not fail per the spec");
+
+        // c. Perform ! CreateDataPropertyOrThrow(O, "value", part.[[Value]]).
+        o.create_data_property_or_throw(utf16!("value"), js_string!(part.value.as_str()), context)
+            .expect("operation must not fail per the spec");
+
+        if let Some(source) = source {
+            o.create_data_property_or_throw(utf16!("source"), js_string!(source.as_str()), context)
+                .expect("operation must not fail per the spec");
+        }
+
+        // d. Perform ! CreateDataPropertyOrThrow(result, ! ToString(n), O).
+        result
+            .create_data_property_or_throw(n, o, context)
+            .expect("operation must not fail per the spec");
+
+        // e. Increment n by 1.
+    }
+
+    // 5. Return result.
+    result
+}
@@ -1,6 +1,16 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
-use crate::builtins::options::{ParsableOptionType, RoundingMode};
+use boa_icu_provider::provider::SupportedCurrenciesV1Marker;
+use fixed_decimal::SignDisplay;
+use icu_decimal::options::GroupingStrategy;
+use icu_provider::{DataProvider, DataRequest, DataResponse};
+use tinystr::{TinyAsciiStr, UnvalidatedTinyAsciiStr};
+
+use crate::{
+    builtins::options::{OptionType, ParsableOptionType, RoundingMode},
+    context::icu::IntlProvider,
+    js_string, Context, JsNativeError, JsResult, JsValue,
+};
 
 use super::RoundingIncrement;
 
@@ -32,7 +42,7 @@ impl fmt::Display for ParseNotationError {
     }
 }
 
-impl std::str::FromStr for Notation {
+impl FromStr for Notation {
     type Err = ParseNotationError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -65,7 +75,7 @@ impl fmt::Display for ParseRoundingPriorityError {
     }
 }
 
-impl std::str::FromStr for RoundingPriority {
+impl FromStr for RoundingPriority {
     type Err = ParseRoundingPriorityError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -107,7 +117,7 @@ impl fmt::Display for ParseTrailingZeroDisplayError {
     }
 }
 
-impl std::str::FromStr for TrailingZeroDisplay {
+impl FromStr for TrailingZeroDisplay {
     type Err = ParseTrailingZeroDisplayError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -180,3 +190,486 @@ impl RoundingType {
         }
     }
 }
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub(crate) enum Style {
+    #[default]
+    Decimal,
+    Percent,
+    Currency,
+    Unit,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseStyleError;
+
+impl fmt::Display for ParseStyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("provided string was not a valid style option")
+    }
+}
+
+impl FromStr for Style {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "decimal" => Ok(Self::Decimal),
+            "percent" => Ok(Self::Percent),
+            "currency" => Ok(Self::Currency),
+            "unit" => Ok(Self::Unit),
+            _ => Err(ParseStyleError),
+        }
+    }
+}
+
+impl ParsableOptionType for Style {}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decimal => "decimal",
+            Self::Percent => "percent",
+            Self::Currency => "currency",
+            Self::Unit => "unit",
+        }
+        .fmt(f)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub(crate) enum CurrencyDisplay {
+    Code,
+    #[default]
+    Symbol,
+    NarrowSymbol,
+    Name,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseCurrencyDisplayError;
+
+impl fmt::Display for ParseCurrencyDisplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("provided string was not a valid currency display option")
+    }
+}
+
+impl FromStr for CurrencyDisplay {
+    type Err = ParseCurrencyDisplayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "code" => Ok(Self::Code),
+            "symbol" => Ok(Self::Symbol),
+            "narrowSymbol" => Ok(Self::NarrowSymbol),
+            "name" => Ok(Self::Name),
+            _ => Err(ParseCurrencyDisplayError),
+        }
+    }
+}
+
+impl ParsableOptionType for CurrencyDisplay {}
+
+impl fmt::Display for CurrencyDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Code => "code",
+            Self::Symbol => "symbol",
+            Self::NarrowSymbol => "narrowSymbol",
+            Self::Name => "name",
+        }
+        .fmt(f)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub(crate) enum CurrencySign {
+    #[default]
+    Standard,
+    Accounting,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseCurrencySignError;
+
+impl fmt::Display for ParseCurrencySignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("provided string was not a valid currency sign option")
+    }
+}
+
+impl FromStr for CurrencySign {
+    type Err = ParseCurrencySignError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "standard" => Ok(Self::Standard),
+            "accounting" => Ok(Self::Accounting),
+            _ => Err(ParseCurrencySignError),
+        }
+    }
+}
+
+impl ParsableOptionType for CurrencySign {}
+
+impl fmt::Display for CurrencySign {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Standard => "standard",
+            Self::Accounting => "accounting",
+        }
+        .fmt(f)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub(crate) enum UnitDisplay {
+    #[default]
+    Short,
+    Narrow,
+    Long,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseUnitDisplayError;
+
+impl fmt::Display for ParseUnitDisplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("provided string was not a valid unit display option")
+    }
+}
+
+impl FromStr for UnitDisplay {
+    type Err = ParseUnitDisplayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "short" => Ok(Self::Short),
+            "narrow" => Ok(Self::Narrow),
+            "long" => Ok(Self::Long),
+            _ => Err(ParseUnitDisplayError),
+        }
+    }
+}
+
+impl ParsableOptionType for UnitDisplay {}
+
+impl fmt::Display for UnitDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Short => "short",
+            Self::Narrow => "narrow",
+            Self::Long => "long",
+        }
+        .fmt(f)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub(crate) enum CompactDisplay {
+    #[default]
+    Short,
+    Long,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseCompactDisplayError;
+
+impl fmt::Display for ParseCompactDisplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("provided string was not a valid compact display option")
+    }
+}
+
+impl FromStr for CompactDisplay {
+    type Err = ParseCompactDisplayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "short" => Ok(Self::Short),
+            "long" => Ok(Self::Long),
+            _ => Err(ParseCompactDisplayError),
+        }
+    }
+}
+
+impl ParsableOptionType for CompactDisplay {}
+
+impl fmt::Display for CompactDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Short => "short",
+            Self::Long => "long",
+        }
+        .fmt(f)
+    }
+}
+
+impl fmt::Display for Notation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Standard => "standard",
+            Self::Scientific => "scientific",
+            Self::Engineering => "engineering",
+            Self::Compact => "compact",
+        }
+        .fmt(f)
+    }
+}
+
+impl OptionType for SignDisplay {
+    fn from_value(value: JsValue, context: &mut Context) -> JsResult<Self> {
+        match value.to_string(context)?.to_std_string_escaped().as_str() {
+            "auto" => Ok(Self::Auto),
+            "never" => Ok(Self::Never),
+            "always" => Ok(Self::Always),
+            "exceptZero" => Ok(Self::ExceptZero),
+            "negative" => Ok(Self::Negative),
+            _ => Err(JsNativeError::range()
+                .with_message("provided string was not a valid sign display option")
+                .into()),
+        }
+    }
+}
+
+/// Gets the string representation of a [`SignDisplay`] option.
+pub(crate) const fn sign_display_to_str(sign_display: SignDisplay) -> &'static str {
+    match sign_display {
+        SignDisplay::Never => "never",
+        SignDisplay::Always => "always",
+        SignDisplay::ExceptZero => "exceptZero",
+        SignDisplay::Negative => "negative",
+        _ => "auto",
+    }
+}
+
+/// Gets the value of the `useGrouping` option as exposed by `resolvedOptions`.
+pub(crate) fn grouping_strategy_to_value(strategy: GroupingStrategy) -> JsValue {
+    match strategy {
+        GroupingStrategy::Always => js_string!("always").into(),
+        GroupingStrategy::Min2 => js_string!("min2").into(),
+        GroupingStrategy::Never => false.into(),
+        _ => js_string!("auto").into(),
+    }
+}
+
+/// A well-formed ISO 4217 currency code, stored in upper case.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Currency([u8; 3]);
+
+#[derive(Debug)]
+pub(crate) struct ParseCurrencyError;
+
+impl fmt::Display for ParseCurrencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("provided string was not a well-formed currency code")
+    }
+}
+
+impl FromStr for Currency {
+    type Err = ParseCurrencyError;
+
+    /// Abstract operation [`IsWellFormedCurrencyCode ( currency )`][spec].
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-iswellformedcurrencycode
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // 1. If the length of currency is not 3, return false.
+        // 2. Let normalized be the ASCII-uppercase of currency.
+        // 3. If normalized contains any code unit outside of 0x0041 through 0x005A (corresponding
+        //    to Unicode characters LATIN CAPITAL LETTER A through LATIN CAPITAL LETTER Z), return false.
+        // 4. Return true.
+        let bytes: [u8; 3] = s.as_bytes().try_into().map_err(|_| ParseCurrencyError)?;
+        if !bytes.iter().all(u8::is_ascii_alphabetic) {
+            return Err(ParseCurrencyError);
+        }
+        Ok(Self(bytes.map(|b| b.to_ascii_uppercase())))
+    }
+}
+
+impl ParsableOptionType for Currency {}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl Currency {
+    /// Gets the currency code as a string slice.
+    pub(crate) fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("currency codes can only contain ASCII letters")
+    }
+
+    /// Abstract operation [`CurrencyDigits ( currency )`][spec].
+    ///
+    /// Gets the number of minor unit digits of the currency, as defined by ISO 4217.
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-currencydigits
+    pub(crate) fn digits(self) -> u8 {
+        match &self.0 {
+            b"BIF" | b"CLP" | b"DJF" | b"GNF" | b"ISK" | b"JPY" | b"KMF" | b"KRW" | b"PYG"
+            | b"RWF" | b"UGX" | b"UYI" | b"VND" | b"VUV" | b"XAF" | b"XOF" | b"XPF" => 0,
+            b"BHD" | b"IQD" | b"JOD" | b"KWD" | b"LYD" | b"OMR" | b"TND" => 3,
+            b"CLF" | b"UYW" => 4,
+            _ => 2,
+        }
+    }
+
+    /// Gets the codes of the currencies supported by the provider, sorted by code.
+    pub(crate) fn available(provider: &IntlProvider) -> JsResult<Vec<String>> {
+        let currencies =
+            DataProvider::<SupportedCurrenciesV1Marker>::load(provider, DataRequest::default())
+                .and_then(DataResponse::take_payload)
+                .map_err(|err| JsNativeError::typ().with_message(err.to_string()))?;
+
+        Ok(currencies
+            .get()
+            .codes
+            .iter()
+            .filter_map(|code| code.try_into_tinystr().ok())
+            .map(|code| code.to_string())
+            .collect())
+    }
+
+    /// Gets the currency code as the key of the currency in the locale data.
+    pub(crate) fn to_data_key(self) -> UnvalidatedTinyAsciiStr<3> {
+        TinyAsciiStr::from_bytes(&self.0)
+            .expect("currency codes can only contain ASCII letters")
+            .to_unvalidated()
+    }
+}
+
+/// Table of [sanctioned single unit identifiers][spec], sorted by identifier.
+///
+/// [spec]: https://tc39.es/ecma402/#table-sanctioned-single-unit-identifiers
+const SANCTIONED_SINGLE_UNIT_IDENTIFIERS: &[&str] = &[
+    "acre",
+    "bit",
+    "byte",
+    "celsius",
+    "centimeter",
+    "day",
+    "degree",
+    "fahrenheit",
+    "fluid-ounce",
+    "foot",
+    "gallon",
+    "gigabit",
+    "gigabyte",
+    "gram",
+    "hectare",
+    "hour",
+    "inch",
+    "kilobit",
+    "kilobyte",
+    "kilogram",
+    "kilometer",
+    "liter",
+    "megabit",
+    "megabyte",
+    "meter",
+    "microsecond",
+    "mile",
+    "mile-scandinavian",
+    "milliliter",
+    "millimeter",
+    "millisecond",
+    "minute",
+    "month",
+    "nanosecond",
+    "ounce",
+    "percent",
+    "petabyte",
+    "pound",
+    "second",
+    "stone",
+    "terabit",
+    "terabyte",
+    "week",
+    "yard",
+    "year",
+];
+
+/// Abstract operation [`IsSanctionedSingleUnitIdentifier ( unitIdentifier )`][spec].
+///
+/// [spec]: https://tc39.es/ecma402/#sec-issanctionedsingleunitidentifier
+fn sanctioned_single_unit(id: &str) -> Option<&'static str> {
+    SANCTIONED_SINGLE_UNIT_IDENTIFIERS
+        .binary_search(&id)
+        .ok()
+        .map(|index| SANCTIONED_SINGLE_UNIT_IDENTIFIERS[index])
+}
+
+/// A well-formed unit identifier, which can be either a single unit or a compound
+/// `<numerator>-per-<denominator>` unit.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Unit {
+    pub(crate) numerator: &'static str,
+    pub(crate) denominator: Option<&'static str>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseUnitError;
+
+impl fmt::Display for ParseUnitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("provided string was not a well-formed unit identifier")
+    }
+}
+
+impl FromStr for Unit {
+    type Err = ParseUnitError;
+
+    /// Abstract operation [`IsWellFormedUnitIdentifier ( unitIdentifier )`][spec].
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-iswellformedunitidentifier
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // 1. If IsSanctionedSingleUnitIdentifier(unitIdentifier) is true, then
+        if let Some(numerator) = sanctioned_single_unit(s) {
+            // a. Return true.
+            return Ok(Self {
+                numerator,
+                denominator: None,
+            });
+        }
+
+        // 2. Let i be StringIndexOf(unitIdentifier, "-per-", 0).
+        // 3. If i is -1 or StringIndexOf(unitIdentifier, "-per-", i + 1) is not -1, then
+        //     a. Return false.
+        let (numerator, denominator) = s.split_once("-per-").ok_or(ParseUnitError)?;
+        if denominator.contains("-per-") {
+            return Err(ParseUnitError);
+        }
+
+        // 4. Assert: The five-character substring "-per-" occurs exactly once in unitIdentifier, at index i.
+        // 5. Let numerator be the substring of unitIdentifier from 0 to i.
+        // 6. Let denominator be the substring of unitIdentifier from i + 5.
+        // 7. If IsSanctionedSingleUnitIdentifier(numerator) and IsSanctionedSingleUnitIdentifier(denominator) are both true, then
+        //     a. Return true.
+        // 8. Return false.
+        Ok(Self {
+            numerator: sanctioned_single_unit(numerator).ok_or(ParseUnitError)?,
+            denominator: Some(sanctioned_single_unit(denominator).ok_or(ParseUnitError)?),
+        })
+    }
+}
+
+impl ParsableOptionType for Unit {}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.numerator)?;
+        if let Some(denominator) = self.denominator {
+            write!(f, "-per-{denominator}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Unit {
+    /// Gets the identifiers of all the sanctioned single units, sorted by identifier.
+    pub(crate) fn sanctioned_single_units() -> impl Iterator<Item = &'static str> {
+        SANCTIONED_SINGLE_UNIT_IDENTIFIERS.iter().copied()
+    }
+}
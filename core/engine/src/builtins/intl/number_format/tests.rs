@@ -0,0 +1,75 @@
+use crate::{js_string, run_test_actions, TestAction};
+
+#[test]
+fn decimal() {
+    run_test_actions([
+        TestAction::assert_eq(
+            "new Intl.NumberFormat('de-DE').format(1234567.891)",
+            js_string!("1.234.567,891"),
+        ),
+        TestAction::assert_eq(
+            "new Intl.NumberFormat('de-DE').format(-Infinity)",
+            js_string!("-∞"),
+        ),
+        TestAction::assert_eq(
+            "new Intl.NumberFormat('de-DE').format(NaN)",
+            js_string!("NaN"),
+        ),
+    ]);
+}
+
+#[test]
+fn percent() {
+    run_test_actions([
+        TestAction::assert_eq(
+            "new Intl.NumberFormat('de-DE', { style: 'percent' }).format(0.25)",
+            js_string!("25\u{a0}%"),
+        ),
+        TestAction::assert_eq(
+            "new Intl.NumberFormat('en-US', { style: 'percent' }).format(0.25)",
+            js_string!("25%"),
+        ),
+    ]);
+}
+
+#[test]
+fn currency() {
+    run_test_actions([
+        TestAction::run(
+            "const eur = new Intl.NumberFormat('de-DE', { style: 'currency', currency: 'EUR' })",
+        ),
+        TestAction::assert_eq("eur.format(1234.5)", js_string!("1.234,50\u{a0}€")),
+        TestAction::assert_eq("eur.format(-1234.5)", js_string!("-1.234,50\u{a0}€")),
+        TestAction::assert_eq(
+            "new Intl.NumberFormat('ja-JP', { style: 'currency', currency: 'JPY' }).format(1234.5)",
+            js_string!("￥1,235"),
+        ),
+        TestAction::assert_eq(
+            "new Intl.NumberFormat('de-DE', { style: 'currency', currency: 'EUR', currencyDisplay: 'name' }).format(2)",
+            js_string!("2,00 Euro"),
+        ),
+        TestAction::assert_eq(
+            "new Intl.NumberFormat('de-DE', { style: 'currency', currency: 'EUR', currencyDisplay: 'code' }).format(1)",
+            js_string!("1,00\u{a0}EUR"),
+        ),
+    ]);
+}
+
+#[test]
+fn unit() {
+    run_test_actions([
+        TestAction::run(
+            "const km = new Intl.NumberFormat('de-DE', { style: 'unit', unit: 'kilometer', unitDisplay: 'long' })",
+        ),
+        TestAction::assert_eq("km.format(1)", js_string!("1 Kilometer")),
+        TestAction::assert_eq("km.format(2.5)", js_string!("2,5 Kilometer")),
+    ]);
+}
+
+#[test]
+fn compact() {
+    run_test_actions([TestAction::assert_eq(
+        "new Intl.NumberFormat('ja-JP', { notation: 'compact' }).format(12345)",
+        js_string!("1.2万"),
+    )]);
+}
@@ -320,6 +320,33 @@ pub(crate) fn get_digit_format_options(
 pub(crate) fn f64_to_formatted_fixed_decimal(
     number: f64,
     options: &DigitFormatOptions,
+) -> FixedDecimal {
+    // 1. If x is negative-zero, then
+    //     a. Let isNegative be true.
+    //     b. Set x to 0.
+    // 2. Else,
+    //     a. Assert: x is a mathematical value.
+    //     b. If x < 0, let isNegative be true; else let isNegative be false.
+    //     c. If isNegative is true, then
+    //         i. Set x to -x.
+    // We can skip these steps, because `FixedDecimal` already provides support for
+    // negative zeroes.
+    let number = FixedDecimal::try_from_f64(number, FloatPrecision::Floating)
+        .expect("`number` must be finite");
+
+    format_fixed_decimal(number, options)
+}
+
+/// Abstract operation [`FormatNumericToString ( intlObject, x )`][spec], starting from an
+/// exact decimal value.
+///
+/// This is used to format mathematical values that cannot be represented as a finite `f64`,
+/// such as `BigInt`s or decimal strings.
+///
+/// [spec]: https://tc39.es/ecma402/#sec-formatnumberstring
+pub(crate) fn format_fixed_decimal(
+    mut number: FixedDecimal,
+    options: &DigitFormatOptions,
 ) -> FixedDecimal {
     fn round(number: &mut FixedDecimal, position: i16, mode: RoundingMode, multiple: BaseMultiple) {
         match mode {
@@ -342,7 +369,7 @@ pub(crate) fn f64_to_formatted_fixed_decimal(
         max_precision: u8,
         rounding_mode: RoundingMode,
     ) -> i16 {
-        let msb = *number.magnitude_range().end();
+        let msb = number.nonzero_magnitude_start();
         let min_msb = msb - i16::from(min_precision) + 1;
         let max_msb = msb - i16::from(max_precision) + 1;
         number.pad_end(min_msb);
@@ -373,19 +400,6 @@ pub(crate) fn f64_to_formatted_fixed_decimal(
         -i16::from(max_fraction)
     }
 
-    // 1. If x is negative-zero, then
-    //     a. Let isNegative be true.
-    //     b. Set x to 0.
-    // 2. Else,
-    //     a. Assert: x is a mathematical value.
-    //     b. If x < 0, let isNegative be true; else let isNegative be false.
-    //     c. If isNegative is true, then
-    //         i. Set x to -x.
-    // We can skip these steps, because `FixedDecimal` already provides support for
-    // negative zeroes.
-    let mut number = FixedDecimal::try_from_f64(number, FloatPrecision::Floating)
-        .expect("`number` must be finite");
-
     // 3. Let unsignedRoundingMode be GetUnsignedRoundingMode(intlObject.[[RoundingMode]], isNegative).
     // Skipping because `FixedDecimal`'s API already provides methods equivalent to `RoundingMode`s.
 
@@ -421,15 +435,15 @@ pub(crate) fn f64_to_formatted_fixed_decimal(
             let mut fixed = number.clone();
             let s_magnitude = to_raw_precision(
                 &mut number,
-                significant_digits.maximum,
                 significant_digits.minimum,
+                significant_digits.maximum,
                 options.rounding_mode,
             );
             // b. Let fResult be ToRawFixed(x, intlObject.[[MinimumFractionDigits]], intlObject.[[MaximumFractionDigits]], intlObject.[[RoundingIncrement]], unsignedRoundingMode).
             let f_magnitude = to_raw_fixed(
                 &mut fixed,
-                fraction_digits.maximum,
                 fraction_digits.minimum,
+                fraction_digits.maximum,
                 options.rounding_increment,
                 options.rounding_mode,
             );
@@ -0,0 +1,654 @@
+//! This module implements the global `Intl.RelativeTimeFormat` object.
+//!
+//! `Intl.RelativeTimeFormat` is a built-in object that enables language-sensitive relative time
+//! formatting.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/ecma402/#relativetimeformat-objects
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/RelativeTimeFormat
+
+use boa_gc::{Finalize, Trace};
+use boa_profiler::Profiler;
+use icu_locid::{extensions::unicode::Value, Locale};
+use icu_plurals::{PluralCategory, PluralRules as NativePluralRules};
+use icu_provider::{
+    DataLocale, DataMarker, DataPayload, DataProvider, DataRequest, DataRequestMetadata,
+    DataResponse, KeyedDataMarker,
+};
+use icu_relativetime::provider::{
+    LongDayRelativeTimeFormatDataV1Marker, LongHourRelativeTimeFormatDataV1Marker,
+    LongMinuteRelativeTimeFormatDataV1Marker, LongMonthRelativeTimeFormatDataV1Marker,
+    LongQuarterRelativeTimeFormatDataV1Marker, LongSecondRelativeTimeFormatDataV1Marker,
+    LongWeekRelativeTimeFormatDataV1Marker, LongYearRelativeTimeFormatDataV1Marker,
+    NarrowDayRelativeTimeFormatDataV1Marker, NarrowHourRelativeTimeFormatDataV1Marker,
+    NarrowMinuteRelativeTimeFormatDataV1Marker, NarrowMonthRelativeTimeFormatDataV1Marker,
+    NarrowQuarterRelativeTimeFormatDataV1Marker, NarrowSecondRelativeTimeFormatDataV1Marker,
+    NarrowWeekRelativeTimeFormatDataV1Marker, NarrowYearRelativeTimeFormatDataV1Marker,
+    PluralRulesCategoryMapping, RelativeTimePatternDataV1, ShortDayRelativeTimeFormatDataV1Marker,
+    ShortHourRelativeTimeFormatDataV1Marker, ShortMinuteRelativeTimeFormatDataV1Marker,
+    ShortMonthRelativeTimeFormatDataV1Marker, ShortQuarterRelativeTimeFormatDataV1Marker,
+    ShortSecondRelativeTimeFormatDataV1Marker, ShortWeekRelativeTimeFormatDataV1Marker,
+    ShortYearRelativeTimeFormatDataV1Marker, SingularSubPattern,
+};
+
+use crate::{
+    builtins::{
+        options::get_option, Array, BuiltInBuilder, BuiltInConstructor, BuiltInObject,
+        IntrinsicObject, OrdinaryObject,
+    },
+    context::{
+        icu::IntlProvider,
+        intrinsics::{Intrinsics, StandardConstructor, StandardConstructors},
+    },
+    js_string,
+    object::{internal_methods::get_prototype_from_constructor, JsObject},
+    property::Attribute,
+    realm::Realm,
+    string::{common::StaticJsStrings, utf16},
+    symbol::JsSymbol,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsString, JsValue,
+};
+
+use super::{
+    locale::{canonicalize_locale_list, resolve_locale, supported_locales},
+    number_format::{
+        f64_to_formatted_fixed_decimal, IntlMathematicalValue, NumberFormat,
+        NumberFormatLocaleOptions, Part, PartKind,
+    },
+    options::{coerce_options_to_object, IntlOptions},
+    Service,
+};
+
+#[cfg(all(test, feature = "intl_bundled"))]
+mod tests;
+
+mod options;
+
+use options::{Numeric, Style, Unit};
+
+/// JavaScript `Intl.RelativeTimeFormat` object.
+#[derive(Debug, Trace, Finalize, JsData)]
+// SAFETY: `RelativeTimeFormat` doesn't contain any traceable data. The inner `NumberFormat` is
+// never exposed to JS, so it cannot have a bound `format` function.
+#[boa_gc(unsafe_empty_trace)]
+pub(crate) struct RelativeTimeFormat {
+    locale: Locale,
+    numbering_system: Option<Value>,
+    style: Style,
+    numeric: Numeric,
+    number_format: NumberFormat,
+    plural_rules: NativePluralRules,
+}
+
+impl Service for RelativeTimeFormat {
+    type LangMarker = LongDayRelativeTimeFormatDataV1Marker;
+
+    type LocaleOptions = NumberFormatLocaleOptions;
+
+    fn resolve(locale: &mut Locale, options: &mut Self::LocaleOptions, provider: &IntlProvider) {
+        // The only relevant extension key is "nu", which is resolved in the same way as
+        // `Intl.NumberFormat`.
+        <NumberFormat as Service>::resolve(locale, options, provider);
+    }
+}
+
+impl IntrinsicObject for RelativeTimeFormat {
+    fn init(realm: &Realm) {
+        let _timer = Profiler::global().start_event(std::any::type_name::<Self>(), "init");
+
+        BuiltInBuilder::from_standard_constructor::<Self>(realm)
+            .static_method(
+                Self::supported_locales_of,
+                js_string!("supportedLocalesOf"),
+                1,
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("Intl.RelativeTimeFormat"),
+                Attribute::CONFIGURABLE,
+            )
+            .method(Self::format, js_string!("format"), 2)
+            .method(Self::format_to_parts, js_string!("formatToParts"), 2)
+            .method(Self::resolved_options, js_string!("resolvedOptions"), 0)
+            .build();
+    }
+
+    fn get(intrinsics: &Intrinsics) -> JsObject {
+        Self::STANDARD_CONSTRUCTOR(intrinsics.constructors()).constructor()
+    }
+}
+
+impl BuiltInObject for RelativeTimeFormat {
+    const NAME: JsString = StaticJsStrings::RELATIVE_TIME_FORMAT;
+}
+
+impl BuiltInConstructor for RelativeTimeFormat {
+    const LENGTH: usize = 0;
+
+    const STANDARD_CONSTRUCTOR: fn(&StandardConstructors) -> &StandardConstructor =
+        StandardConstructors::relative_time_format;
+
+    /// Constructor [`Intl.RelativeTimeFormat ( [ locales [ , options ] ] )`][spec].
+    ///
+    /// Constructor for `RelativeTimeFormat` objects.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.relativetimeformat
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/RelativeTimeFormat/RelativeTimeFormat
+    fn constructor(
+        new_target: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. If NewTarget is undefined, throw a TypeError exception.
+        if new_target.is_undefined() {
+            return Err(JsNativeError::typ()
+                .with_message("cannot call `Intl.RelativeTimeFormat` constructor without `new`")
+                .into());
+        }
+
+        // 2. Let relativeTimeFormat be ? OrdinaryCreateFromConstructor(NewTarget, "%RelativeTimeFormat.prototype%", « ... »).
+        let prototype = get_prototype_from_constructor(
+            new_target,
+            StandardConstructors::relative_time_format,
+            context,
+        )?;
+
+        // 3. Return ? InitializeRelativeTimeFormat(relativeTimeFormat, locales, options).
+        // <https://tc39.es/ecma402/#sec-InitializeRelativeTimeFormat>
+
+        let locales = args.get_or_undefined(0);
+        let options = args.get_or_undefined(1);
+
+        // 1. Let requestedLocales be ? CanonicalizeLocaleList(locales).
+        let requested_locales = canonicalize_locale_list(locales, context)?;
+
+        // 2. Set options to ? CoerceOptionsToObject(options).
+        let options = coerce_options_to_object(options, context)?;
+
+        // 3. Let opt be a new Record.
+        // 4. Let matcher be ? GetOption(options, "localeMatcher", string, « "lookup", "best fit" », "best fit").
+        // 5. Set opt.[[LocaleMatcher]] to matcher.
+        let matcher = get_option(&options, utf16!("localeMatcher"), context)?.unwrap_or_default();
+
+        // 6. Let numberingSystem be ? GetOption(options, "numberingSystem", string, empty, undefined).
+        // 7. If numberingSystem is not undefined, then
+        //     a. If numberingSystem cannot be matched by the type Unicode locale nonterminal, throw a RangeError exception.
+        // 8. Set opt.[[nu]] to numberingSystem.
+        let numbering_system = get_option(&options, utf16!("numberingSystem"), context)?;
+
+        let mut intl_options = IntlOptions {
+            matcher,
+            service_options: NumberFormatLocaleOptions { numbering_system },
+        };
+
+        // 9. Let localeData be %RelativeTimeFormat%.[[LocaleData]].
+        // 10. Let r be ResolveLocale(%RelativeTimeFormat%.[[AvailableLocales]], requestedLocales, opt, %RelativeTimeFormat%.[[RelevantExtensionKeys]], localeData).
+        // 11. Let locale be r.[[locale]].
+        // 12. Set relativeTimeFormat.[[Locale]] to locale.
+        // 13. Set relativeTimeFormat.[[DataLocale]] to r.[[dataLocale]].
+        // 14. Set relativeTimeFormat.[[NumberingSystem]] to r.[[nu]].
+        let locale = resolve_locale::<Self>(
+            &requested_locales,
+            &mut intl_options,
+            context.intl_provider(),
+        );
+        let numbering_system = intl_options.service_options.numbering_system;
+
+        // 15. Let style be ? GetOption(options, "style", string, « "long", "short", "narrow" », "long").
+        // 16. Set relativeTimeFormat.[[Style]] to style.
+        let style = get_option(&options, utf16!("style"), context)?.unwrap_or_default();
+
+        // 17. Let numeric be ? GetOption(options, "numeric", string, « "always", "auto" », "always").
+        // 18. Set relativeTimeFormat.[[Numeric]] to numeric.
+        let numeric = get_option(&options, utf16!("numeric"), context)?.unwrap_or_default();
+
+        // 19. Let relativeTimeFormat.[[NumberFormat]] be ! Construct(%NumberFormat%, « locale »).
+        let nf_options = JsObject::with_null_proto();
+        if let Some(nu) = &numbering_system {
+            nf_options
+                .create_data_property_or_throw(
+                    utf16!("numberingSystem"),
+                    js_string!(nu.to_string()),
+                    context,
+                )
+                .expect("operation must not fail per the spec");
+        }
+        let number_format = NumberFormat::new(
+            &js_string!(locale.to_string()).into(),
+            &nf_options.into(),
+            context,
+        )?;
+
+        // 20. Let relativeTimeFormat.[[PluralRules]] be ! Construct(%PluralRules%, « locale »).
+        let plural_rules = NativePluralRules::try_new_cardinal_unstable(
+            context.intl_provider(),
+            &DataLocale::from(&locale),
+        )
+        .map_err(|e| JsNativeError::typ().with_message(e.to_string()))?;
+
+        // 21. Return relativeTimeFormat.
+        Ok(JsObject::from_proto_and_data_with_shared_shape(
+            context.root_shape(),
+            prototype,
+            Self {
+                locale,
+                numbering_system,
+                style,
+                numeric,
+                number_format,
+                plural_rules,
+            },
+        )
+        .into())
+    }
+}
+
+impl RelativeTimeFormat {
+    /// [`Intl.RelativeTimeFormat.supportedLocalesOf ( locales [ , options ] )`][spec].
+    ///
+    /// Returns an array containing those of the provided locales that are supported in relative
+    /// time formatting without having to fall back to the runtime's default locale.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.relativetimeformat.supportedLocalesOf
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/RelativeTimeFormat/supportedLocalesOf
+    fn supported_locales_of(
+        _: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let locales = args.get_or_undefined(0);
+        let options = args.get_or_undefined(1);
+
+        // 1. Let availableLocales be %RelativeTimeFormat%.[[AvailableLocales]].
+        // 2. Let requestedLocales be ? CanonicalizeLocaleList(locales).
+        let requested_locales = canonicalize_locale_list(locales, context)?;
+
+        // 3. Return ? SupportedLocales(availableLocales, requestedLocales, options).
+        supported_locales::<<Self as Service>::LangMarker>(&requested_locales, options, context)
+            .map(JsValue::from)
+    }
+
+    /// [`Intl.RelativeTimeFormat.prototype.format ( value, unit )`][spec].
+    ///
+    /// Formats a value and a unit according to the locale and formatting options of this
+    /// `Intl.RelativeTimeFormat` object.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-Intl.RelativeTimeFormat.prototype.format
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/RelativeTimeFormat/format
+    fn format(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let relativeTimeFormat be the this value.
+        // 2. Perform ? RequireInternalSlot(relativeTimeFormat, [[InitializedRelativeTimeFormat]]).
+        let rtf = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message(
+                    "`format` can only be called on an `Intl.RelativeTimeFormat` object",
+                )
+            })?;
+
+        // 3. Let value be ? ToNumber(value).
+        let value = args.get_or_undefined(0).to_number(context)?;
+
+        // 4. Let unit be ? ToString(unit).
+        let unit = args.get_or_undefined(1).to_string(context)?;
+
+        // 5. Return ? FormatRelativeTime(relativeTimeFormat, value, unit).
+        // <https://tc39.es/ecma402/#sec-FormatRelativeTime>
+
+        // 1. Let parts be ? PartitionRelativeTimePattern(relativeTimeFormat, value, unit).
+        let parts = rtf.partition_relative_time_pattern(value, &unit, context)?;
+
+        // 2. Let result be an empty String.
+        // 3. For each Record { [[Type]], [[Value]], [[Unit]] } part in parts, do
+        //     a. Set result to the string-concatenation of result and part.[[Value]].
+        // 4. Return result.
+        let result: String = parts.iter().map(|(part, _)| part.value.as_str()).collect();
+
+        Ok(js_string!(result).into())
+    }
+
+    /// [`Intl.RelativeTimeFormat.prototype.formatToParts ( value, unit )`][spec].
+    ///
+    /// Returns an array of objects representing the relative time format in parts that can be
+    /// used for custom locale-aware formatting.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-Intl.RelativeTimeFormat.prototype.formatToParts
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/RelativeTimeFormat/formatToParts
+    fn format_to_parts(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let relativeTimeFormat be the this value.
+        // 2. Perform ? RequireInternalSlot(relativeTimeFormat, [[InitializedRelativeTimeFormat]]).
+        let rtf = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message(
+                    "`formatToParts` can only be called on an `Intl.RelativeTimeFormat` object",
+                )
+            })?;
+
+        // 3. Let value be ? ToNumber(value).
+        let value = args.get_or_undefined(0).to_number(context)?;
+
+        // 4. Let unit be ? ToString(unit).
+        let unit = args.get_or_undefined(1).to_string(context)?;
+
+        // 5. Return ? FormatRelativeTimeToParts(relativeTimeFormat, value, unit).
+        // <https://tc39.es/ecma402/#sec-FormatRelativeTimeToParts>
+
+        // 1. Let parts be ? PartitionRelativeTimePattern(relativeTimeFormat, value, unit).
+        let parts = rtf.partition_relative_time_pattern(value, &unit, context)?;
+
+        // 2. Let result be ! ArrayCreate(0).
+        let result = Array::array_create(0, None, context)
+            .expect("creating an empty array with default proto must not fail");
+
+        // 3. Let n be 0.
+        // 4. For each Record { [[Type]], [[Value]], [[Unit]] } part in parts, do
+        for (n, (part, unit)) in parts.into_iter().enumerate() {
+            // a. Let O be OrdinaryObjectCreate(%Object.prototype%).
+            let o = context
+                .intrinsics()
+                .templates()
+                .ordinary_object()
+                .create(OrdinaryObject, vec![]);
+
+            // b. Perform ! CreateDataPropertyOrThrow(O, "type", part.[[Type]]).
+            o.create_data_property_or_throw(
+                utf16!("type"),
+                js_string!(part.kind.as_str()),
+                context,
+            )
+            .expect("operation must not fail per the spec");
+
+            // c. Perform ! CreateDataPropertyOrThrow(O, "value", part.[[Value]]).
+            o.create_data_property_or_throw(
+                utf16!("value"),
+                js_string!(part.value.as_str()),
+                context,
+            )
+            .expect("operation must not fail per the spec");
+
+            // d. If part.[[Unit]] is not empty, then
+            if let Some(unit) = unit {
+                // i. Perform ! CreateDataPropertyOrThrow(O, "unit", part.[[Unit]]).
+                o.create_data_property_or_throw(utf16!("unit"), js_string!(unit.as_str()), context)
+                    .expect("operation must not fail per the spec");
+            }
+
+            // e. Perform ! CreateDataPropertyOrThrow(result, ! ToString(𝔽(n)), O).
+            // f. Increment n by 1.
+            result
+                .create_data_property_or_throw(n, o, context)
+                .expect("operation must not fail per the spec");
+        }
+
+        // 5. Return result.
+        Ok(result.into())
+    }
+
+    /// [`Intl.RelativeTimeFormat.prototype.resolvedOptions ( )`][spec].
+    ///
+    /// Returns a new object with properties reflecting the locale and options computed during the
+    /// construction of the current `Intl.RelativeTimeFormat` object.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.relativetimeformat.prototype.resolvedoptions
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/RelativeTimeFormat/resolvedOptions
+    fn resolved_options(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        // 1. Let relativeTimeFormat be the this value.
+        // 2. Perform ? RequireInternalSlot(relativeTimeFormat, [[InitializedRelativeTimeFormat]]).
+        let rtf = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message(
+                    "`resolvedOptions` can only be called on an `Intl.RelativeTimeFormat` object",
+                )
+            })?;
+
+        // 3. Let options be OrdinaryObjectCreate(%Object.prototype%).
+        let options = context
+            .intrinsics()
+            .templates()
+            .ordinary_object()
+            .create(OrdinaryObject, vec![]);
+
+        // 4. For each row of Table 15, except the header row, in table order, do
+        //     a. Let p be the Property value of the current row.
+        //     b. Let v be the value of relativeTimeFormat's internal slot whose name is the Internal Slot value of the current row.
+        //     c. Assert: v is not undefined.
+        //     d. Perform ! CreateDataPropertyOrThrow(options, p, v).
+        let mut push = |key: &str, value: JsValue| {
+            options
+                .create_data_property_or_throw(js_string!(key), value, context)
+                .expect("operation must not fail per the spec");
+        };
+
+        push("locale", js_string!(rtf.locale.to_string()).into());
+        push("style", js_string!(rtf.style.as_str()).into());
+        push("numeric", js_string!(rtf.numeric.as_str()).into());
+        push(
+            "numberingSystem",
+            js_string!(rtf
+                .numbering_system
+                .as_ref()
+                .map_or_else(|| String::from("latn"), ToString::to_string))
+            .into(),
+        );
+
+        // 5. Return options.
+        Ok(options.into())
+    }
+
+    /// Abstract operation [`PartitionRelativeTimePattern ( relativeTimeFormat, value, unit )`][spec].
+    ///
+    /// Splits the formatted relative time into a list of parts, tagging the parts of the
+    /// formatted number with the unit of the relative time.
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-PartitionRelativeTimePattern
+    fn partition_relative_time_pattern(
+        &self,
+        value: f64,
+        unit: &JsString,
+        context: &Context,
+    ) -> JsResult<Vec<(Part, Option<Unit>)>> {
+        // 1. If value is NaN, +∞𝔽, or -∞𝔽, throw a RangeError exception.
+        if !value.is_finite() {
+            return Err(JsNativeError::range()
+                .with_message("cannot format a non-finite relative time")
+                .into());
+        }
+
+        // 2. Let unit be ? SingularRelativeTimeUnit(unit).
+        let unit =
+            Unit::from_singular_or_plural(&unit.to_std_string_escaped()).ok_or_else(|| {
+                JsNativeError::range().with_message("provided string was not a relative time unit")
+            })?;
+
+        // 3. Let localeData be %RelativeTimeFormat%.[[LocaleData]].
+        // 4. Let dataLocale be relativeTimeFormat.[[DataLocale]].
+        // 5. Let fields be localeData.[[<dataLocale>]].
+        // 6. Let style be relativeTimeFormat.[[Style]].
+        // 7. If style is equal to "short", then
+        //     a. Let entry be the string-concatenation of unit and "-short".
+        // 8. Else if style is equal to "narrow", then
+        //     a. Let entry be the string-concatenation of unit and "-narrow".
+        // 9. Else,
+        //     a. Let entry be unit.
+        // 10. If fields doesn't have a field [[<entry>]], then
+        //     a. Let entry be unit.
+        // 11. Let patterns be fields.[[<entry>]].
+        let patterns = load_patterns(&self.locale, self.style, unit, context)?;
+        let patterns = patterns.get();
+
+        // 12. Let numeric be relativeTimeFormat.[[Numeric]].
+        // 13. If numeric is equal to "auto", then
+        if self.numeric == Numeric::Auto {
+            // a. Let valueString be ToString(value).
+            // b. If patterns has a field [[<valueString>]], then
+            if value.fract() == 0.0 && (f64::from(i8::MIN)..=f64::from(i8::MAX)).contains(&value) {
+                #[allow(clippy::cast_possible_truncation)]
+                if let Some(result) = patterns.relatives.get(&(value as i8)) {
+                    // i. Let result be patterns.[[<valueString>]].
+                    // ii. Return a List containing the Record { [[Type]]: "literal", [[Value]]: result }.
+                    return Ok(vec![(Part::new(PartKind::Literal, result), None)]);
+                }
+            }
+        }
+
+        // 14. If value is -0𝔽 or if value is less than 0, then
+        //     a. Let tl be "past".
+        //     b. Set value to -value.
+        // 15. Else,
+        //     a. Let tl be "future".
+        let (value, tl) = if value.is_sign_negative() {
+            (-value, &patterns.past)
+        } else {
+            (value, &patterns.future)
+        };
+
+        // 16. Let po be patterns.[[<tl>]].
+        // 17. Let fv be ! PartitionNumberPattern(relativeTimeFormat.[[NumberFormat]], value).
+        let fv = self
+            .number_format
+            .partition_number_pattern(IntlMathematicalValue::from(value));
+
+        // 18. Let pr be ! ResolvePlural(relativeTimeFormat.[[PluralRules]], value).[[PluralCategory]].
+        let fixed = f64_to_formatted_fixed_decimal(value, self.number_format.digit_options());
+        let pr = self.plural_rules.category_for(&fixed);
+
+        // 19. Let pattern be po.[[<pr>]].
+        let pattern = plural_pattern(tl, pr);
+
+        // 20. Return MakePartsList(pattern, unit, fv).
+        // <https://tc39.es/ecma402/#sec-makepartslist>
+        if pattern.index == u8::MAX {
+            // The pattern doesn't have a placeholder for the number.
+            return Ok(vec![(Part::new(PartKind::Literal, &pattern.pattern), None)]);
+        }
+        let index = usize::from(pattern.index);
+        if !pattern.pattern.is_char_boundary(index) {
+            return Err(JsNativeError::typ()
+                .with_message("invalid relative time pattern in locale data")
+                .into());
+        }
+        let (before, after) = pattern.pattern.split_at(index);
+
+        let mut result = Vec::with_capacity(fv.len() + 2);
+        if !before.is_empty() {
+            result.push((Part::new(PartKind::Literal, before), None));
+        }
+        result.extend(fv.into_iter().map(|part| (part, Some(unit))));
+        if !after.is_empty() {
+            result.push((Part::new(PartKind::Literal, after), None));
+        }
+
+        Ok(result)
+    }
+}
+
+/// Marker for the relative time patterns of any style and unit, which all share the same data
+/// struct.
+struct RelativeTimePatternsMarker;
+
+impl DataMarker for RelativeTimePatternsMarker {
+    type Yokeable = RelativeTimePatternDataV1<'static>;
+}
+
+/// Gets the pattern of `patterns` for the plural `category`, falling back to the pattern of the
+/// `other` category.
+fn plural_pattern<'a>(
+    patterns: &'a PluralRulesCategoryMapping<'a>,
+    category: PluralCategory,
+) -> &'a SingularSubPattern<'a> {
+    match category {
+        PluralCategory::Zero => patterns.zero.as_ref(),
+        PluralCategory::One => patterns.one.as_ref(),
+        PluralCategory::Two => patterns.two.as_ref(),
+        PluralCategory::Few => patterns.few.as_ref(),
+        PluralCategory::Many => patterns.many.as_ref(),
+        PluralCategory::Other => None,
+    }
+    .unwrap_or(&patterns.other)
+}
+
+/// Loads the relative time patterns for the provided locale, style and unit.
+fn load_patterns(
+    locale: &Locale,
+    style: Style,
+    unit: Unit,
+    context: &Context,
+) -> JsResult<DataPayload<RelativeTimePatternsMarker>> {
+    fn load<M>(
+        locale: &DataLocale,
+        context: &Context,
+    ) -> JsResult<DataPayload<RelativeTimePatternsMarker>>
+    where
+        M: KeyedDataMarker<Yokeable = RelativeTimePatternDataV1<'static>>,
+        IntlProvider: DataProvider<M>,
+    {
+        DataProvider::<M>::load(
+            context.intl_provider(),
+            DataRequest {
+                locale,
+                metadata: DataRequestMetadata::default(),
+            },
+        )
+        .and_then(DataResponse::take_payload)
+        .map(DataPayload::cast)
+        .map_err(|e| JsNativeError::typ().with_message(e.to_string()).into())
+    }
+
+    type LoadFn = fn(&DataLocale, &Context) -> JsResult<DataPayload<RelativeTimePatternsMarker>>;
+
+    let load: LoadFn = match (style, unit) {
+        (Style::Long, Unit::Second) => load::<LongSecondRelativeTimeFormatDataV1Marker>,
+        (Style::Long, Unit::Minute) => load::<LongMinuteRelativeTimeFormatDataV1Marker>,
+        (Style::Long, Unit::Hour) => load::<LongHourRelativeTimeFormatDataV1Marker>,
+        (Style::Long, Unit::Day) => load::<LongDayRelativeTimeFormatDataV1Marker>,
+        (Style::Long, Unit::Week) => load::<LongWeekRelativeTimeFormatDataV1Marker>,
+        (Style::Long, Unit::Month) => load::<LongMonthRelativeTimeFormatDataV1Marker>,
+        (Style::Long, Unit::Quarter) => load::<LongQuarterRelativeTimeFormatDataV1Marker>,
+        (Style::Long, Unit::Year) => load::<LongYearRelativeTimeFormatDataV1Marker>,
+        (Style::Short, Unit::Second) => load::<ShortSecondRelativeTimeFormatDataV1Marker>,
+        (Style::Short, Unit::Minute) => load::<ShortMinuteRelativeTimeFormatDataV1Marker>,
+        (Style::Short, Unit::Hour) => load::<ShortHourRelativeTimeFormatDataV1Marker>,
+        (Style::Short, Unit::Day) => load::<ShortDayRelativeTimeFormatDataV1Marker>,
+        (Style::Short, Unit::Week) => load::<ShortWeekRelativeTimeFormatDataV1Marker>,
+        (Style::Short, Unit::Month) => load::<ShortMonthRelativeTimeFormatDataV1Marker>,
+        (Style::Short, Unit::Quarter) => load::<ShortQuarterRelativeTimeFormatDataV1Marker>,
+        (Style::Short, Unit::Year) => load::<ShortYearRelativeTimeFormatDataV1Marker>,
+        (Style::Narrow, Unit::Second) => load::<NarrowSecondRelativeTimeFormatDataV1Marker>,
+        (Style::Narrow, Unit::Minute) => load::<NarrowMinuteRelativeTimeFormatDataV1Marker>,
+        (Style::Narrow, Unit::Hour) => load::<NarrowHourRelativeTimeFormatDataV1Marker>,
+        (Style::Narrow, Unit::Day) => load::<NarrowDayRelativeTimeFormatDataV1Marker>,
+        (Style::Narrow, Unit::Week) => load::<NarrowWeekRelativeTimeFormatDataV1Marker>,
+        (Style::Narrow, Unit::Month) => load::<NarrowMonthRelativeTimeFormatDataV1Marker>,
+        (Style::Narrow, Unit::Quarter) => load::<NarrowQuarterRelativeTimeFormatDataV1Marker>,
+        (Style::Narrow, Unit::Year) => load::<NarrowYearRelativeTimeFormatDataV1Marker>,
+    };
+
+    load(&DataLocale::from(locale), context)
+}
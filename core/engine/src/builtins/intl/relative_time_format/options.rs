@@ -0,0 +1,144 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::builtins::options::ParsableOptionType;
+
+/// The length of the internationalized message, as set by the `style` option.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Style {
+    #[default]
+    Long,
+    Short,
+    Narrow,
+}
+
+impl Style {
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Long => "long",
+            Self::Short => "short",
+            Self::Narrow => "narrow",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseStyleError;
+
+impl Display for ParseStyleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("provided string was not `long`, `short` or `narrow`")
+    }
+}
+
+impl FromStr for Style {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "long" => Ok(Self::Long),
+            "short" => Ok(Self::Short),
+            "narrow" => Ok(Self::Narrow),
+            _ => Err(ParseStyleError),
+        }
+    }
+}
+
+impl ParsableOptionType for Style {}
+
+/// Whether to use numeric values in the output, as set by the `numeric` option.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum Numeric {
+    #[default]
+    Always,
+    Auto,
+}
+
+impl Numeric {
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Auto => "auto",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ParseNumericError;
+
+impl Display for ParseNumericError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("provided string was not `always` or `auto`")
+    }
+}
+
+impl FromStr for Numeric {
+    type Err = ParseNumericError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "always" => Ok(Self::Always),
+            "auto" => Ok(Self::Auto),
+            _ => Err(ParseNumericError),
+        }
+    }
+}
+
+impl ParsableOptionType for Numeric {}
+
+/// A relative time unit, as accepted by the `unit` argument of `format` and `formatToParts`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Unit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl Unit {
+    /// Abstract operation [`SingularRelativeTimeUnit ( unit )`][spec].
+    ///
+    /// Parses a unit, accepting both its singular and plural forms.
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-singularrelativetimeunit
+    pub(crate) fn from_singular_or_plural(unit: &str) -> Option<Self> {
+        // 1. If unit is "seconds", return "second".
+        // 2. If unit is "minutes", return "minute".
+        // 3. If unit is "hours", return "hour".
+        // 4. If unit is "days", return "day".
+        // 5. If unit is "weeks", return "week".
+        // 6. If unit is "months", return "month".
+        // 7. If unit is "quarters", return "quarter".
+        // 8. If unit is "years", return "year".
+        // 9. If unit is not one of "second", "minute", "hour", "day", "week", "month", "quarter", or "year", throw a RangeError exception.
+        // 10. Return unit.
+        let unit = match unit {
+            "second" | "seconds" => Self::Second,
+            "minute" | "minutes" => Self::Minute,
+            "hour" | "hours" => Self::Hour,
+            "day" | "days" => Self::Day,
+            "week" | "weeks" => Self::Week,
+            "month" | "months" => Self::Month,
+            "quarter" | "quarters" => Self::Quarter,
+            "year" | "years" => Self::Year,
+            _ => return None,
+        };
+        Some(unit)
+    }
+
+    pub(crate) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Second => "second",
+            Self::Minute => "minute",
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Quarter => "quarter",
+            Self::Year => "year",
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use crate::{js_string, run_test_actions, TestAction};
+
+#[test]
+fn format() {
+    run_test_actions([
+        TestAction::assert_eq(
+            "new Intl.RelativeTimeFormat('de').format(-1, 'day')",
+            js_string!("vor 1 Tag"),
+        ),
+        TestAction::assert_eq(
+            "new Intl.RelativeTimeFormat('de', { numeric: 'auto' }).format(-1, 'day')",
+            js_string!("gestern"),
+        ),
+        TestAction::assert_eq(
+            "new Intl.RelativeTimeFormat('es').format(3, 'month')",
+            js_string!("dentro de 3 meses"),
+        ),
+    ]);
+}
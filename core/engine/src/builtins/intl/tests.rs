@@ -0,0 +1,22 @@
+use crate::{run_test_actions, TestAction};
+
+#[test]
+fn supported_currencies() {
+    run_test_actions([
+        TestAction::run("const currencies = Intl.supportedValuesOf('currency')"),
+        TestAction::assert("currencies.includes('EUR')"),
+        TestAction::assert("currencies.includes('JPY')"),
+        TestAction::assert("!currencies.includes('DEM')"),
+        TestAction::assert("currencies.every((c, i) => i === 0 || currencies[i - 1] < c)"),
+    ]);
+}
+
+#[test]
+fn supported_time_zones() {
+    run_test_actions([
+        TestAction::run("const zones = Intl.supportedValuesOf('timeZone')"),
+        TestAction::assert("zones.includes('Europe/Berlin')"),
+        TestAction::assert("zones.includes('UTC')"),
+        TestAction::assert("!zones.includes('Etc/GMT')"),
+    ]);
+}
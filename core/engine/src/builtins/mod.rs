@@ -279,6 +279,10 @@ impl Realm {
             intl::segmenter::Segments::init(self);
             intl::segmenter::SegmentIterator::init(self);
             intl::PluralRules::init(self);
+            intl::NumberFormat::init(self);
+            intl::RelativeTimeFormat::init(self);
+            intl::DisplayNames::init(self);
+            intl::DurationFormat::init(self);
         }
 
         #[cfg(feature = "temporal")]
@@ -290,24 +290,46 @@ impl Number {
     ///
     /// The `toLocaleString()` method returns a string with a language-sensitive representation of this number.
     ///
-    /// Note that while this technically conforms to the Ecma standard, it does no actual
-    /// internationalization logic.
+    /// Note that without the `intl` feature this technically conforms to the Ecma standard, but
+    /// does no actual internationalization logic.
     ///
     /// More information:
     ///  - [ECMAScript reference][spec]
+    ///  - [ECMA-402 reference][spec-402]
     ///  - [MDN documentation][mdn]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-number.prototype.tolocalestring
+    /// [spec-402]: https://tc39.es/ecma402/#sup-number.prototype.tolocalestring
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Number/toLocaleString
     #[allow(clippy::wrong_self_convention)]
     pub(crate) fn to_locale_string(
         this: &JsValue,
-        _: &[JsValue],
-        _: &mut Context,
+        #[allow(unused_variables)] args: &[JsValue],
+        #[allow(unused_variables)] context: &mut Context,
     ) -> JsResult<JsValue> {
+        // 1. Let x be ? thisNumberValue(this value).
         let this_num = Self::this_number_value(this)?;
-        let this_str_num = this_num.to_string();
-        Ok(JsValue::new(js_string!(this_str_num)))
+
+        #[cfg(feature = "intl")]
+        {
+            use crate::builtins::intl::number_format::{IntlMathematicalValue, NumberFormat};
+
+            // 2. Let numberFormat be ? Construct(%NumberFormat%, « locales, options »).
+            let number_format =
+                NumberFormat::new(args.get_or_undefined(0), args.get_or_undefined(1), context)?;
+
+            // 3. Return FormatNumeric(numberFormat, ! ToIntlMathematicalValue(x)).
+            Ok(
+                js_string!(number_format.format_to_string(IntlMathematicalValue::from(this_num)))
+                    .into(),
+            )
+        }
+
+        #[cfg(not(feature = "intl"))]
+        {
+            let this_str_num = this_num.to_string();
+            Ok(JsValue::new(js_string!(this_str_num)))
+        }
     }
 
     /// `flt_str_to_exp` - used in `to_precision`
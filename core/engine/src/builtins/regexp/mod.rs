@@ -259,6 +259,18 @@ impl BuiltInConstructor for RegExp {
 }
 
 impl RegExp {
+    /// Gets the source text this regular expression was created with.
+    #[must_use]
+    pub const fn original_source(&self) -> &JsString {
+        &self.original_source
+    }
+
+    /// Gets the flags this regular expression was created with.
+    #[must_use]
+    pub const fn original_flags(&self) -> &JsString {
+        &self.original_flags
+    }
+
     /// `7.2.8 IsRegExp ( argument )`
     ///
     /// This modified to return the object if it's `true`, [`None`] otherwise.
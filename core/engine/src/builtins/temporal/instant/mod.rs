@@ -94,6 +94,7 @@ impl IntrinsicObject for Instant {
             .method(Self::since, js_string!("since"), 2)
             .method(Self::round, js_string!("round"), 1)
             .method(Self::equals, js_string!("equals"), 1)
+            .method(Self::to_locale_string, js_string!("toLocaleString"), 0)
             .method(Self::to_zoned_date_time, js_string!("toZonedDateTime"), 1)
             .method(
                 Self::to_zoned_date_time_iso,
@@ -440,6 +441,50 @@ impl Instant {
         Ok(true.into())
     }
 
+    /// 8.3.13 `Temporal.Instant.prototype.toLocaleString ( [ locales [ , options ] ] )`
+    #[allow(unused_variables)]
+    pub(crate) fn to_locale_string(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let instant be the this value.
+        // 2. Perform ? RequireInternalSlot(instant, [[InitializedTemporalInstant]]).
+        let instant = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message("the this object must be an instant object.")
+            })?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let ms = (instant.nanoseconds.to_f64() / 1e6).floor() as i64;
+
+        #[cfg(feature = "intl")]
+        {
+            use crate::builtins::intl::date_time_format::{DateTimeFormat, DateTimeReqs};
+
+            // 3. Let dateFormat be ? CreateDateTimeFormat(%DateTimeFormat%, locales, options, any, all).
+            let date_format = DateTimeFormat::new(
+                args.get_or_undefined(0),
+                args.get_or_undefined(1),
+                &DateTimeReqs::AnyAll,
+                &DateTimeReqs::AnyAll,
+                context,
+            )?;
+
+            // 4. Return ? FormatDateTime(dateFormat, instant).
+            date_format.format_date_time(ms, context).map(JsValue::from)
+        }
+
+        #[cfg(not(feature = "intl"))]
+        {
+            let instant = chrono::NaiveDateTime::from_timestamp_millis(ms)
+                .ok_or_else(|| JsNativeError::range().with_message("invalid instant"))?;
+            Ok(js_string!(instant.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()).into())
+        }
+    }
+
     /// 8.3.17 `Temporal.Instant.prototype.toZonedDateTime ( item )`
     pub(crate) fn to_zoned_date_time(
         _: &JsValue,
@@ -186,6 +186,7 @@ impl IntrinsicObject for PlainDate {
             .method(Self::until, js_string!("until"), 2)
             .method(Self::since, js_string!("since"), 2)
             .method(Self::equals, js_string!("equals"), 1)
+            .method(Self::to_locale_string, js_string!("toLocaleString"), 0)
             .build();
     }
 
@@ -384,6 +385,51 @@ impl PlainDate {
             .with_message("not yet implemented.")
             .into())
     }
+
+    /// 3.3.31 `Temporal.PlainDate.prototype.toLocaleString ( [ locales [ , options ] ] )`
+    fn to_locale_string(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        // 1. Let temporalDate be the this value.
+        // 2. Perform ? RequireInternalSlot(temporalDate, [[InitializedTemporalDate]]).
+        let date = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message("the this object must be a PlainDate object.")
+            })?;
+
+        let (year, month, day) = (date.inner.year(), date.inner.month(), date.inner.day());
+
+        #[cfg(feature = "intl")]
+        {
+            use crate::builtins::intl::date_time_format::{DateTimeFormat, DateTimeReqs};
+
+            // 3. Let dateFormat be ? CreateDateTimeFormat(%DateTimeFormat%, locales, options, date, date).
+            let date_format = DateTimeFormat::new(
+                args.get_or_undefined(0),
+                args.get_or_undefined(1),
+                &DateTimeReqs::Date,
+                &DateTimeReqs::Date,
+                context,
+            )?;
+
+            // 4. Return ? FormatDateTime(dateFormat, temporalDate).
+            let local = chrono::NaiveDate::from_ymd_opt(year, month.into(), day.into())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .ok_or_else(|| JsNativeError::range().with_message("invalid ISO date"))?;
+            date_format
+                .format_local_date_time(&local, context)
+                .map(JsValue::from)
+        }
+
+        #[cfg(not(feature = "intl"))]
+        {
+            Ok(js_string!(format!("{year:04}-{month:02}-{day:02}")).into())
+        }
+    }
 }
 
 // -- `PlainDate` Abstract Operations --
@@ -99,7 +99,8 @@ impl TypedArray {
     }
 
     /// Get the integer indexed object's typed array kind.
-    pub(crate) const fn kind(&self) -> TypedArrayKind {
+    #[must_use]
+    pub const fn kind(&self) -> TypedArrayKind {
         self.kind
     }
 
@@ -331,17 +331,28 @@ pub(crate) enum ContentType {
 
 /// List of all typed array kinds.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub(crate) enum TypedArrayKind {
+pub enum TypedArrayKind {
+    /// The `Int8Array` kind.
     Int8,
+    /// The `Uint8Array` kind.
     Uint8,
+    /// The `Uint8ClampedArray` kind.
     Uint8Clamped,
+    /// The `Int16Array` kind.
     Int16,
+    /// The `Uint16Array` kind.
     Uint16,
+    /// The `Int32Array` kind.
     Int32,
+    /// The `Uint32Array` kind.
     Uint32,
+    /// The `BigInt64Array` kind.
     BigInt64,
+    /// The `BigUint64Array` kind.
     BigUint64,
+    /// The `Float32Array` kind.
     Float32,
+    /// The `Float64Array` kind.
     Float64,
 }
 
@@ -381,9 +392,8 @@ impl TypedArrayKind {
     }
 
     /// Gets the standard constructor accessor of this `TypedArrayKind`.
-    pub(crate) const fn standard_constructor(
-        self,
-    ) -> fn(&StandardConstructors) -> &StandardConstructor {
+    #[must_use]
+    pub const fn standard_constructor(self) -> fn(&StandardConstructors) -> &StandardConstructor {
         match self {
             TypedArrayKind::Int8 => StandardConstructors::typed_int8_array,
             TypedArrayKind::Uint8 => StandardConstructors::typed_uint8_array,
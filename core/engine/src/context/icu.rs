@@ -7,6 +7,8 @@ use icu_provider::{
     AnyProvider, AsDeserializingBufferProvider, AsDowncastingAnyProvider, BufferProvider,
     DataError, DataProvider, DataRequest, DataResponse, KeyedDataMarker, MaybeSendSync,
 };
+use icu_provider_adapters::fallback::LocaleFallbackProvider;
+use icu_provider_blob::BlobDataProvider;
 use serde::Deserialize;
 use thiserror::Error;
 use yoke::{trait_hack::YokeTraitHack, Yokeable};
@@ -32,6 +34,9 @@ pub enum IcuError {
     /// Failed to create the case mapping tools.
     #[error("could not construct the case mapping tools")]
     CaseMap(#[from] DataError),
+    /// Failed to load the data blob.
+    #[error("could not load the ICU data blob")]
+    Blob(#[source] DataError),
 }
 
 /// Custom [`DataProvider`] for `Intl` that caches some utilities.
@@ -91,6 +96,20 @@ impl IntlProvider {
         })
     }
 
+    /// Creates a new [`IntlProvider`] from a postcard data blob, such as the ones generated
+    /// by the `boa_datagen` tool.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blob is not a valid postcard blob, or if any of the tools
+    /// required cannot be constructed.
+    pub(crate) fn try_new_with_blob(blob: Box<[u8]>) -> Result<IntlProvider, IcuError> {
+        let provider = BlobDataProvider::try_new_from_blob(blob).map_err(IcuError::Blob)?;
+        let provider = LocaleFallbackProvider::try_new_with_buffer_provider(provider)
+            .map_err(IcuError::Blob)?;
+        Self::try_new_with_buffer_provider(provider)
+    }
+
     /// Creates a new [`IntlProvider`] from an [`AnyProvider`].
     ///
     /// # Errors
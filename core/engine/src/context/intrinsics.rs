@@ -168,6 +168,14 @@ pub struct StandardConstructors {
     segmenter: StandardConstructor,
     #[cfg(feature = "intl")]
     plural_rules: StandardConstructor,
+    #[cfg(feature = "intl")]
+    number_format: StandardConstructor,
+    #[cfg(feature = "intl")]
+    relative_time_format: StandardConstructor,
+    #[cfg(feature = "intl")]
+    display_names: StandardConstructor,
+    #[cfg(feature = "intl")]
+    duration_format: StandardConstructor,
     #[cfg(feature = "temporal")]
     instant: StandardConstructor,
     #[cfg(feature = "temporal")]
@@ -258,6 +266,14 @@ impl Default for StandardConstructors {
             segmenter: StandardConstructor::default(),
             #[cfg(feature = "intl")]
             plural_rules: StandardConstructor::default(),
+            #[cfg(feature = "intl")]
+            number_format: StandardConstructor::default(),
+            #[cfg(feature = "intl")]
+            relative_time_format: StandardConstructor::default(),
+            #[cfg(feature = "intl")]
+            display_names: StandardConstructor::default(),
+            #[cfg(feature = "intl")]
+            duration_format: StandardConstructor::default(),
             #[cfg(feature = "temporal")]
             instant: StandardConstructor::default(),
             #[cfg(feature = "temporal")]
@@ -876,6 +892,58 @@ impl StandardConstructors {
         &self.plural_rules
     }
 
+    /// Returns the `Intl.NumberFormat` constructor.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.numberformat
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "intl")]
+    pub const fn number_format(&self) -> &StandardConstructor {
+        &self.number_format
+    }
+
+    /// Returns the `Intl.RelativeTimeFormat` constructor.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl-relativetimeformat-constructor
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "intl")]
+    pub const fn relative_time_format(&self) -> &StandardConstructor {
+        &self.relative_time_format
+    }
+
+    /// Returns the `Intl.DisplayNames` constructor.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl-displaynames-constructor
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "intl")]
+    pub const fn display_names(&self) -> &StandardConstructor {
+        &self.display_names
+    }
+
+    /// Returns the `Intl.DurationFormat` constructor.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/proposal-intl-duration-format/#sec-intl-durationformat-constructor
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "intl")]
+    pub const fn duration_format(&self) -> &StandardConstructor {
+        &self.duration_format
+    }
+
     /// Returns the `Temporal.Instant` constructor.
     ///
     /// More information:
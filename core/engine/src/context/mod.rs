@@ -22,7 +22,7 @@ use crate::{
     class::{Class, ClassBuilder},
     job::{JobQueue, NativeJob, SimpleJobQueue},
     js_string,
-    module::{IdleModuleLoader, ModuleLoader, SimpleModuleLoader},
+    module::{IdleModuleLoader, ModuleLoader, Referrer, SimpleModuleLoader},
     native_function::NativeFunction,
     object::{shape::RootShape, FunctionObjectBuilder, JsObject},
     optimizer::{Optimizer, OptimizerOptions, OptimizerStatistics},
@@ -473,6 +473,27 @@ impl Context {
         self.strict = strict;
     }
 
+    /// Gets the referrer of the running code: its active script or module, or the current realm
+    /// if there is none.
+    ///
+    /// This is the referrer of the dynamic imports of the running code, which hosts can also use
+    /// to resolve the specifiers passed to their functions, e.g. the scripts of workers.
+    #[must_use]
+    pub fn active_referrer(&self) -> Referrer {
+        self.get_active_script_or_module()
+            .map_or_else(|| Referrer::Realm(self.realm().clone()), Into::into)
+    }
+
+    /// Increment and get the parser identifier.
+    ///
+    /// Every [`Parser`][boa_parser::Parser] whose code is evaluated by the context must have a
+    /// unique identifier, which is used to cache the objects of its tagged templates.
+    #[inline]
+    pub fn next_parser_identifier(&mut self) -> u32 {
+        self.parser_identifier += 1;
+        self.parser_identifier
+    }
+
     /// Enqueues a [`NativeJob`] on the [`JobQueue`].
     #[inline]
     pub fn enqueue_job(&mut self, job: NativeJob) {
@@ -600,12 +621,6 @@ impl Context {
         std::mem::swap(&mut self.vm.realm, realm);
     }
 
-    /// Increment and get the parser identifier.
-    pub(crate) fn next_parser_identifier(&mut self) -> u32 {
-        self.parser_identifier += 1;
-        self.parser_identifier
-    }
-
     /// `CanDeclareGlobalFunction ( N )`
     ///
     /// More information:
@@ -896,7 +911,7 @@ impl std::fmt::Debug for ContextBuilder {
 
 impl ContextBuilder {
     /// Creates a new [`ContextBuilder`] with a default empty [`Interner`]
-    /// and a default `BoaProvider` if the `intl` feature is enabled.
+    /// and a default `BoaProvider` if the `intl_bundled` feature is enabled.
     #[must_use]
     pub fn new() -> Self {
         Self::default()
@@ -959,6 +974,38 @@ impl ContextBuilder {
         Ok(self)
     }
 
+    /// Provides an ICU4X postcard data blob to the [`Context`].
+    ///
+    /// This allows loading the `Intl` data at runtime instead of bundling it into the binary.
+    /// The blob can be generated using the `boa_datagen` tool, which also supports slicing the
+    /// data to a subset of locales and services.
+    ///
+    /// ```no_run
+    /// # use boa_engine::context::ContextBuilder;
+    /// let blob = std::fs::read("icudata.postcard").unwrap();
+    /// let context = ContextBuilder::new()
+    ///     .icu_blob(blob.into_boxed_slice())
+    ///     .unwrap()
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// This function is only available if the `intl` feature is enabled.
+    ///
+    /// # Errors
+    ///
+    /// This returns `Err` if the blob is not a valid postcard blob, or if it doesn't have the
+    /// required locale information to construct both a [`LocaleCanonicalizer`] and a
+    /// [`LocaleExpander`].
+    ///
+    /// [`LocaleCanonicalizer`]: icu_locid_transform::LocaleCanonicalizer
+    /// [`LocaleExpander`]: icu_locid_transform::LocaleExpander
+    #[cfg(feature = "intl")]
+    pub fn icu_blob(mut self, blob: Box<[u8]>) -> Result<Self, IcuError> {
+        self.icu = Some(icu::IntlProvider::try_new_with_blob(blob)?);
+        Ok(self)
+    }
+
     /// Initializes the [`HostHooks`] for the context.
     ///
     /// [`Host Hooks`]: https://tc39.es/ecma262/#sec-host-hooks-summary
@@ -1015,6 +1062,20 @@ impl ContextBuilder {
     // TODO: try to use a custom error here, since most of the `JsError` APIs
     // require having a `Context` in the first place.
     pub fn build(self) -> JsResult<Context> {
+        #[cfg(feature = "intl")]
+        let intl_provider = match self.icu {
+            Some(icu) => icu,
+            #[cfg(feature = "intl_bundled")]
+            None => icu::IntlProvider::try_new_with_buffer_provider(boa_icu_provider::buffer())
+                .expect("Failed to initialize default icu data."),
+            #[cfg(not(feature = "intl_bundled"))]
+            None => {
+                return Err(JsNativeError::typ()
+                    .with_message("an ICU data provider must be set to build a context with `Intl`")
+                    .into());
+            }
+        };
+
         if self.can_block {
             if CANNOT_BLOCK_COUNTER.get() > 0 {
                 return Err(JsNativeError::typ()
@@ -1050,10 +1111,7 @@ impl ContextBuilder {
             vm,
             strict: false,
             #[cfg(feature = "intl")]
-            intl_provider: self.icu.unwrap_or_else(|| {
-                icu::IntlProvider::try_new_with_buffer_provider(boa_icu_provider::buffer())
-                    .expect("Failed to initialize default icu data.")
-            }),
+            intl_provider,
             #[cfg(feature = "fuzz")]
             instructions_remaining: self.instructions_remaining,
             kept_alive: Vec::new(),
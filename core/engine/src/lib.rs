@@ -41,7 +41,10 @@
 //!
 //!  - **serde** - Enables serialization and deserialization of the AST (Abstract Syntax Tree).
 //!  - **profiler** - Enables profiling with measureme (this is mostly internal).
-//!  - **intl** - Enables `boa`'s [ECMA-402 Internationalization API][ecma-402] (`Intl` object)
+//!  - **intl** - Enables `boa`'s [ECMA-402 Internationalization API][ecma-402] (`Intl` object).
+//!    The ICU4X data must be provided when building the [`Context`].
+//!  - **`intl_bundled`** - Enables **intl** and bundles a default ICU4X data provider into the
+//!    binary.
 //!
 //! [ecma-402]: https://tc39.es/ecma402
 //! [examples]: https://github.com/boa-dev/boa/tree/main/boa_examples
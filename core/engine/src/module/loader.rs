@@ -1,17 +1,25 @@
-use std::path::{Path, PathBuf};
+use std::{
+    fmt::Write,
+    path::{Component, Path, PathBuf},
+};
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use boa_gc::GcRefCell;
 use boa_parser::Source;
 
 use crate::script::Script;
 use crate::{
-    js_string, object::JsObject, realm::Realm, vm::ActiveRunnable, Context, JsError, JsNativeError,
-    JsResult, JsString,
+    builtins::promise::PromiseState,
+    js_string,
+    native_function::NativeFunction,
+    object::{FunctionObjectBuilder, JsObject, JsPromise},
+    realm::Realm,
+    vm::ActiveRunnable,
+    Context, JsArgs, JsError, JsNativeError, JsResult, JsString,
 };
 
-use super::Module;
+use super::{parallel, vfs::normalize, FileSystemSource, Module, ModuleSource};
 
 /// The referrer from which a load request of a module originates.
 #[derive(Debug, Clone)]
@@ -24,6 +32,18 @@ pub enum Referrer {
     Script(Script),
 }
 
+impl Referrer {
+    /// Gets the path of the file the referrer was parsed from, if any.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::Module(module) => module.path(),
+            Self::Realm(_) => None,
+            Self::Script(script) => script.path(),
+        }
+    }
+}
+
 impl From<ActiveRunnable> for Referrer {
     fn from(value: ActiveRunnable) -> Self {
         match value {
@@ -104,6 +124,30 @@ pub trait ModuleLoader {
     fn init_import_meta(&self, _import_meta: &JsObject, _module: &Module, _context: &mut Context) {}
 }
 
+/// A [`ModuleLoader`] that can parse the modules imported statically by a module ahead of
+/// loading them.
+///
+/// The imports are resolved by the caller, which allows a loader wrapping another loader to
+/// preload the modules of a graph through its own resolution of the specifiers.
+pub trait PreloadModuleLoader: ModuleLoader {
+    /// Parses the module at `path` and the modules it imports statically, directly or
+    /// transitively, returning the module at `path`.
+    ///
+    /// `resolve` maps a specifier imported by the module at a path to the path of the imported
+    /// module, or to `None` if the imported module must not be preloaded, which leaves it to be
+    /// loaded normally.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module at `path` cannot be read, or if it cannot be parsed.
+    fn preload_with(
+        &self,
+        path: &Path,
+        resolve: &mut dyn FnMut(&Path, &str, &mut Context) -> Option<PathBuf>,
+        context: &mut Context,
+    ) -> JsResult<Module>;
+}
+
 /// A module loader that throws when trying to load any modules.
 ///
 /// Useful to disable the module system on platforms that don't have a filesystem, for example.
@@ -127,21 +171,60 @@ impl ModuleLoader for IdleModuleLoader {
     }
 }
 
-/// A simple module loader that loads modules relative to a root path.
+/// A simple module loader that loads modules from a [`ModuleSource`], which is the file system
+/// by default.
+///
+/// Relative specifiers (starting with `./` or `../`) are resolved against the directory of the
+/// importing module or script, and any other specifier is resolved against the root path.
+/// Relative paths recorded on modules and scripts are also relative to the root path, and
+/// importers without a path resolve relative specifiers against the root path itself.
+///
+/// The loaded modules are cached by their canonical path, which is shared by the type methods
+/// [`SimpleModuleLoader::insert`] and [`SimpleModuleLoader::get`], and by the utility methods
+/// of [`ModuleLoader`], whose specifiers are resolved against the root path.
 ///
-/// # Note
+/// The loaded modules get the `import.meta.url` property, with the `file:` URL of the module,
+/// and the `import.meta.resolve` function, which resolves specifiers relative to the module.
 ///
-/// This loader only works by using the type methods [`SimpleModuleLoader::insert`] and
-/// [`SimpleModuleLoader::get`]. The utility methods on [`ModuleLoader`] don't work at the moment,
-/// but we'll unify both APIs in the future.
+/// Modules can be replaced by new versions of themselves with [`SimpleModuleLoader::reload`],
+/// which also reloads the modules importing them.
+///
+/// Loading the modules from another source, e.g. from memory, only needs a root path inside of
+/// the source:
+///
+/// ```
+/// use boa_engine::{
+///     builtins::promise::PromiseState,
+///     context::ContextBuilder,
+///     module::{InMemorySource, SimpleModuleLoader},
+///     Context, Module, Source,
+/// };
+/// use std::{path::Path, rc::Rc};
+///
+/// let source = InMemorySource::new()
+///     .with_file("/app/main.js", "export { value } from './lib/value.js';")
+///     .with_file("/app/lib/value.js", "export const value = 42;");
+/// let loader = Rc::new(SimpleModuleLoader::with_source(source, "/app"));
+/// let context = &mut ContextBuilder::new()
+///     .module_loader(loader.clone())
+///     .build()?;
+///
+/// let module = loader.load(Path::new("main.js"), context)?;
+/// let promise = module.load_link_evaluate(context);
+/// context.run_jobs();
+/// assert!(matches!(promise.state(), PromiseState::Fulfilled(_)));
+/// # Ok::<(), boa_engine::JsError>(())
+/// ```
 #[derive(Debug)]
-pub struct SimpleModuleLoader {
+pub struct SimpleModuleLoader<S = FileSystemSource> {
     root: PathBuf,
+    source: S,
     module_map: GcRefCell<FxHashMap<PathBuf, Module>>,
 }
 
 impl SimpleModuleLoader {
-    /// Creates a new `SimpleModuleLoader` from a root module path.
+    /// Creates a new `SimpleModuleLoader` from a root module path, loading the modules from the
+    /// file system.
     pub fn new<P: AsRef<Path>>(root: P) -> JsResult<Self> {
         if cfg!(target_family = "wasm") {
             return Err(JsNativeError::typ()
@@ -156,62 +239,447 @@ impl SimpleModuleLoader {
         })?;
         Ok(Self {
             root: absolute,
+            source: FileSystemSource,
             module_map: GcRefCell::default(),
         })
     }
+}
+
+impl<S: ModuleSource> SimpleModuleLoader<S> {
+    /// Creates a new `SimpleModuleLoader` loading the modules from `source`, with a root module
+    /// path inside of the source.
+    ///
+    /// Relative root paths are relative to the root `/` of the source.
+    pub fn with_source<P: AsRef<Path>>(source: S, root: P) -> Self {
+        let root = source.canonicalize(&Path::new("/").join(root));
+        Self {
+            root,
+            source,
+            module_map: GcRefCell::default(),
+        }
+    }
+
+    /// Gets the source of the modules.
+    #[inline]
+    pub const fn source(&self) -> &S {
+        &self.source
+    }
 
     /// Inserts a new module onto the module map.
     #[inline]
     pub fn insert(&self, path: PathBuf, module: Module) {
-        self.module_map.borrow_mut().insert(path, module);
+        self.module_map
+            .borrow_mut()
+            .insert(self.source.canonicalize(&self.root.join(path)), module);
     }
 
     /// Gets a module from its original path.
     #[inline]
     pub fn get(&self, path: &Path) -> Option<Module> {
-        self.module_map.borrow().get(path).cloned()
+        self.module_map
+            .borrow()
+            .get(&self.source.canonicalize(&self.root.join(path)))
+            .cloned()
+    }
+
+    /// Loads and parses the module at `path`, relative to the root path, unless it is already
+    /// in the module map.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module cannot be read from the source, or if it cannot be parsed.
+    pub fn load(&self, path: &Path, context: &mut Context) -> JsResult<Module> {
+        let path = self.source.canonicalize(&self.root.join(path));
+        if let Some(module) = self.module_map.borrow().get(&path) {
+            return Ok(module.clone());
+        }
+        let contents = self.source.read(&path).map_err(|err| {
+            JsNativeError::typ()
+                .with_message(format!("could not open file `{}`", path.display()))
+                .with_cause(JsError::from_opaque(js_string!(err.to_string()).into()))
+        })?;
+        let source = Source::from_reader(contents.as_slice(), Some(&path));
+        let module = Module::parse(source, None, context).map_err(|err| {
+            JsNativeError::syntax()
+                .with_message(format!("could not parse module `{}`", path.display()))
+                .with_cause(err)
+        })?;
+        self.module_map.borrow_mut().insert(path, module.clone());
+        Ok(module)
+    }
+
+    /// Parses the module at `path` and the modules it imports statically, directly or
+    /// transitively, in parallel, and inserts them into the module map, returning the module at
+    /// `path`.
+    ///
+    /// The imports are resolved like the imports of the loaded modules. Loaders wrapping this
+    /// one with their own resolution can preload through it with
+    /// [`PreloadModuleLoader::preload_with`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module at `path` cannot be read from the source, or if it cannot
+    /// be parsed.
+    pub fn preload(&self, path: &Path, context: &mut Context) -> JsResult<Module>
+    where
+        S: Sync,
+    {
+        self.preload_with(
+            path,
+            &mut |referrer, specifier, _| Some(resolve(&self.root, specifier, Some(referrer))),
+            context,
+        )
+    }
+
+    /// Removes the module at `path` from the module map, along with every module of the map
+    /// that imports it, directly or transitively, so that the next imports of those modules load
+    /// them again.
+    ///
+    /// Returns the paths and the previous versions of the removed modules, where every module
+    /// comes after the modules it imports, except in import cycles.
+    pub fn invalidate(&self, path: &Path) -> Vec<(PathBuf, Module)> {
+        let path = self.source.canonicalize(&self.root.join(path));
+        let mut module_map = self.module_map.borrow_mut();
+        let Some(module) = module_map.get(&path).cloned() else {
+            return Vec::new();
+        };
+
+        let mut importers: FxHashMap<Module, Vec<Module>> = FxHashMap::default();
+        for importer in module_map.values() {
+            for dependency in importer.dependencies() {
+                importers
+                    .entry(dependency)
+                    .or_default()
+                    .push(importer.clone());
+            }
+        }
+        let mut invalidated = FxHashSet::default();
+        invalidated.insert(module.clone());
+        let mut pending = vec![module];
+        while let Some(module) = pending.pop() {
+            for importer in importers.get(&module).into_iter().flatten() {
+                if invalidated.insert(importer.clone()) {
+                    pending.push(importer.clone());
+                }
+            }
+        }
+
+        let mut ordered = Vec::with_capacity(invalidated.len());
+        let mut visited = FxHashSet::default();
+        let mut paths: Vec<_> = module_map
+            .iter()
+            .filter(|(_, module)| invalidated.contains(*module))
+            .map(|(path, _)| path.clone())
+            .collect();
+        paths.sort();
+        for path in paths {
+            visit_dependencies_first(&module_map[&path], &invalidated, &mut visited, &mut ordered);
+        }
+        let mut removed = Vec::with_capacity(ordered.len());
+        for module in ordered {
+            module_map.retain(|path, entry| {
+                if *entry == module {
+                    removed.push((path.clone(), module.clone()));
+                    return false;
+                }
+                true
+            });
+        }
+        removed
+    }
+
+    /// Reloads the module at `path` and every module importing it, in the same context.
+    ///
+    /// The modules are [invalidated][Self::invalidate], then new versions of them are loaded
+    /// from the source, linked and evaluated. The new versions import the new versions of the
+    /// invalidated modules, and the existing versions of all the other modules, which are not
+    /// evaluated again. Modules that aren't in the module map, e.g. modules parsed by the host
+    /// and never inserted, keep importing the previous versions.
+    ///
+    /// The `migrate` hook is called for each reloaded module once the new versions are linked,
+    /// before they are evaluated, with the imported modules first. This allows the host to
+    /// dispose the state of the previous version of a module, or to hand it to the new version,
+    /// e.g. through a global or the host defined data of the realm.
+    ///
+    /// If a new version cannot be loaded, linked or evaluated, or if `migrate` fails, the previous
+    /// versions are restored in the module map.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the new version of a module, or the error of `migrate`.
+    pub fn reload<F>(
+        &self,
+        path: &Path,
+        mut migrate: F,
+        context: &mut Context,
+    ) -> JsResult<Vec<ReloadedModule>>
+    where
+        F: FnMut(&ReloadedModule, &mut Context) -> JsResult<()>,
+    {
+        let invalidated = self.invalidate(path);
+        let result = self.reload_invalidated(&invalidated, &mut migrate, context);
+        if result.is_err() {
+            let mut module_map = self.module_map.borrow_mut();
+            for (path, module) in invalidated {
+                module_map.insert(path, module);
+            }
+        }
+        result
+    }
+
+    /// Loads, links and evaluates new versions of the `invalidated` modules.
+    fn reload_invalidated(
+        &self,
+        invalidated: &[(PathBuf, Module)],
+        migrate: &mut dyn FnMut(&ReloadedModule, &mut Context) -> JsResult<()>,
+        context: &mut Context,
+    ) -> JsResult<Vec<ReloadedModule>> {
+        let mut reloaded = Vec::with_capacity(invalidated.len());
+        for (path, previous) in invalidated {
+            reloaded.push(ReloadedModule {
+                path: path.clone(),
+                previous: previous.clone(),
+                current: self.load(path, context)?,
+            });
+        }
+        for module in &reloaded {
+            let promise = module.current.load(context);
+            context.run_jobs();
+            settled(&promise)?;
+            module.current.link(context)?;
+        }
+        for module in &reloaded {
+            migrate(module, context)?;
+        }
+        for module in &reloaded {
+            let promise = module.current.evaluate(context);
+            context.run_jobs();
+            settled(&promise)?;
+        }
+        Ok(reloaded)
+    }
+}
+
+impl<S: ModuleSource + Sync> PreloadModuleLoader for SimpleModuleLoader<S> {
+    /// Parses the module at `path` and the modules it imports statically in parallel, and
+    /// inserts them into the module map.
+    ///
+    /// The modules are read and parsed on a pool of worker threads, one per available CPU, and
+    /// are compiled on the thread of the context. Loading the returned module then finds its
+    /// dependencies in the module map, which avoids parsing them one by one on the thread of the
+    /// context. Modules already in the module map aren't parsed again, and the imported modules
+    /// that cannot be read or parsed are left to be loaded normally, which reports their errors.
+    fn preload_with(
+        &self,
+        path: &Path,
+        resolve: &mut dyn FnMut(&Path, &str, &mut Context) -> Option<PathBuf>,
+        context: &mut Context,
+    ) -> JsResult<Module> {
+        let path = self.source.canonicalize(&self.root.join(path));
+        if let Some(module) = self.module_map.borrow().get(&path) {
+            return Ok(module.clone());
+        }
+        let parsed = parallel::parse_graph(
+            &self.source,
+            &path,
+            |referrer, specifier, context| {
+                let path = self
+                    .source
+                    .canonicalize(&resolve(referrer, specifier, context)?);
+                (!self.module_map.borrow().contains_key(&path)).then_some(path)
+            },
+            context,
+        )?;
+        for parsed in parsed {
+            let path = parsed.path().to_path_buf();
+            let module = Module::from_parsed(parsed, context);
+            self.module_map.borrow_mut().entry(path).or_insert(module);
+        }
+        Ok(self.module_map.borrow()[&path].clone())
+    }
+}
+
+/// A module replaced by a new version of itself by [`SimpleModuleLoader::reload`].
+#[derive(Debug, Clone)]
+pub struct ReloadedModule {
+    path: PathBuf,
+    previous: Module,
+    current: Module,
+}
+
+impl ReloadedModule {
+    /// Gets the canonical path of the module.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the previous version of the module, which is no longer in the module map.
+    #[must_use]
+    pub const fn previous(&self) -> &Module {
+        &self.previous
+    }
+
+    /// Gets the new version of the module.
+    #[must_use]
+    pub const fn current(&self) -> &Module {
+        &self.current
     }
 }
 
-impl ModuleLoader for SimpleModuleLoader {
+impl<S: ModuleSource> ModuleLoader for SimpleModuleLoader<S> {
     fn load_imported_module(
         &self,
-        _referrer: Referrer,
+        referrer: Referrer,
         specifier: JsString,
         finish_load: Box<dyn FnOnce(JsResult<Module>, &mut Context)>,
         context: &mut Context,
     ) {
-        let result = (|| {
-            let path = specifier
-                .to_std_string()
-                .map_err(|err| JsNativeError::typ().with_message(err.to_string()))?;
-            let short_path = Path::new(&path);
-            let path = self.root.join(short_path);
-            let path = path.canonicalize().map_err(|err| {
-                JsNativeError::typ()
-                    .with_message(format!(
-                        "could not canonicalize path `{}`",
-                        short_path.display()
-                    ))
-                    .with_cause(JsError::from_opaque(js_string!(err.to_string()).into()))
-            })?;
-            if let Some(module) = self.get(&path) {
-                return Ok(module);
-            }
-            let source = Source::from_filepath(&path).map_err(|err| {
-                JsNativeError::typ()
-                    .with_message(format!("could not open file `{}`", short_path.display()))
-                    .with_cause(JsError::from_opaque(js_string!(err.to_string()).into()))
-            })?;
-            let module = Module::parse(source, None, context).map_err(|err| {
-                JsNativeError::syntax()
-                    .with_message(format!("could not parse module `{}`", short_path.display()))
-                    .with_cause(err)
-            })?;
-            self.insert(path, module.clone());
-            Ok(module)
-        })();
+        let result = specifier
+            .to_std_string()
+            .map_err(|err| JsNativeError::typ().with_message(err.to_string()).into())
+            .and_then(|specifier| {
+                self.load(&resolve(&self.root, &specifier, referrer.path()), context)
+            });
 
         finish_load(result, context);
     }
+
+    fn register_module(&self, specifier: JsString, module: Module) {
+        self.insert(PathBuf::from(specifier.to_std_string_escaped()), module);
+    }
+
+    fn get_module(&self, specifier: JsString) -> Option<Module> {
+        self.get(Path::new(&specifier.to_std_string_escaped()))
+    }
+
+    fn init_import_meta(&self, import_meta: &JsObject, module: &Module, context: &mut Context) {
+        let path = module
+            .path()
+            .map(|path| self.source.canonicalize(&self.root.join(path)));
+        if let Some(path) = &path {
+            import_meta
+                .create_data_property_or_throw(
+                    js_string!("url"),
+                    js_string!(file_url(path)),
+                    context,
+                )
+                .expect("defining a property of the import.meta object cannot fail");
+        }
+
+        let resolve = FunctionObjectBuilder::new(
+            context.realm(),
+            NativeFunction::from_copy_closure_with_captures(
+                |_, args, (root, referrer), context| {
+                    let specifier = args.get_or_undefined(0).to_string(context)?;
+                    let referrer = referrer.as_ref().map(JsString::to_std_string_escaped);
+                    let path = resolve(
+                        Path::new(&root.to_std_string_escaped()),
+                        &specifier.to_std_string_escaped(),
+                        referrer.as_deref().map(Path::new),
+                    );
+                    Ok(js_string!(file_url(&path)).into())
+                },
+                (
+                    JsString::from(self.root.to_string_lossy().as_ref()),
+                    path.map(|path| JsString::from(path.to_string_lossy().as_ref())),
+                ),
+            ),
+        )
+        .name(js_string!("resolve"))
+        .length(1)
+        .build();
+        import_meta
+            .create_data_property_or_throw(js_string!("resolve"), resolve, context)
+            .expect("defining a property of the import.meta object cannot fail");
+    }
+}
+
+/// Resolves `specifier` as imported from a module or script at `referrer`, returning the
+/// absolute path of the module with its `.` and `..` components resolved, which may not exist.
+///
+/// Relative specifiers are resolved against the directory of `referrer`, or against `root` if
+/// there is no referrer. Other specifiers are resolved against `root`.
+fn resolve(root: &Path, specifier: &str, referrer: Option<&Path>) -> PathBuf {
+    let specifier = Path::new(specifier);
+    let is_relative = matches!(
+        specifier.components().next(),
+        Some(Component::CurDir | Component::ParentDir)
+    );
+    let base = match referrer.map(|referrer| root.join(referrer)) {
+        Some(referrer) if is_relative => referrer
+            .parent()
+            .map_or_else(|| referrer.clone(), Path::to_path_buf),
+        _ => root.to_path_buf(),
+    };
+    normalize(&base.join(specifier))
+}
+
+/// Pushes `module` to `ordered` after the modules of `invalidated` it imports, unless it was
+/// already visited.
+fn visit_dependencies_first(
+    module: &Module,
+    invalidated: &FxHashSet<Module>,
+    visited: &mut FxHashSet<Module>,
+    ordered: &mut Vec<Module>,
+) {
+    if !visited.insert(module.clone()) {
+        return;
+    }
+    for dependency in module.dependencies() {
+        if invalidated.contains(&dependency) {
+            visit_dependencies_first(&dependency, invalidated, visited, ordered);
+        }
+    }
+    ordered.push(module.clone());
+}
+
+/// Returns the error of `promise` if it was rejected, or an error if it is still pending.
+fn settled(promise: &JsPromise) -> JsResult<()> {
+    match promise.state() {
+        PromiseState::Fulfilled(_) => Ok(()),
+        PromiseState::Rejected(err) => Err(JsError::from_opaque(err)),
+        PromiseState::Pending => Err(JsNativeError::error()
+            .with_message("the module did not settle after running the pending jobs")
+            .into()),
+    }
+}
+
+/// Converts the absolute path `path` to a `file:` URL.
+fn file_url(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let mut url = String::from("file://");
+    if !path.starts_with('/') {
+        url.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'\\' if cfg!(windows) => url.push('/'),
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~'
+            | b'/'
+            | b'!'
+            | b'$'
+            | b'&'
+            | b'\''
+            | b'('
+            | b')'
+            | b'*'
+            | b'+'
+            | b','
+            | b';'
+            | b'='
+            | b':'
+            | b'@' => url.push(char::from(byte)),
+            byte => {
+                write!(url, "%{byte:02X}").expect("writing to a string cannot fail");
+            }
+        }
+    }
+    url
 }
@@ -2,7 +2,9 @@
 //!
 //! This module contains the [`Module`] type, which represents an [**Abstract Module Record**][module],
 //! a [`ModuleLoader`] trait for custom module loader implementations, and [`SimpleModuleLoader`],
-//! the default `ModuleLoader` for [`Context`] which can be used for most simple usecases.
+//! the default `ModuleLoader` for [`Context`] which can be used for most simple usecases. The
+//! `SimpleModuleLoader` reads the modules from a [`ModuleSource`], which can be the file system,
+//! a directory, or an in-memory map of files.
 //!
 //! Every module roughly follows the same lifecycle:
 //! - Parse using [`Module::parse`].
@@ -23,17 +25,22 @@
 
 mod loader;
 mod namespace;
+mod parallel;
 mod source;
 mod synthetic;
+mod vfs;
 use boa_parser::source::ReadChar;
 pub use loader::*;
 pub use namespace::ModuleNamespace;
+use parallel::ParsedModule;
 use source::SourceTextModule;
 pub use synthetic::{SyntheticModule, SyntheticModuleInitializer};
+pub use vfs::{DirectorySource, FileSystemSource, InMemorySource, ModuleSource};
 
 use std::cell::{Cell, RefCell};
 use std::collections::HashSet;
 use std::hash::Hash;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 use rustc_hash::FxHashSet;
@@ -66,6 +73,7 @@ impl std::fmt::Debug for Module {
             .field("environment", &self.inner.environment)
             .field("namespace", &self.inner.namespace)
             .field("kind", &self.inner.kind)
+            .field("path", &self.inner.path)
             .finish()
     }
 }
@@ -77,6 +85,7 @@ struct ModuleRepr {
     namespace: GcRefCell<Option<JsObject>>,
     kind: ModuleKind,
     host_defined: HostDefined,
+    path: Option<PathBuf>,
 }
 
 /// The kind of a [`Module`].
@@ -147,10 +156,29 @@ impl Module {
         context: &mut Context,
     ) -> JsResult<Self> {
         let _timer = Profiler::global().start_event("Module parsing", "Main");
+        let path = src.path().map(Path::to_path_buf);
         let mut parser = Parser::new(src);
         parser.set_identifier(context.next_parser_identifier());
         let module = parser.parse_module(context.interner_mut())?;
 
+        Ok(Self::from_ast(module, path, realm, context))
+    }
+
+    /// Creates a new source text module from a module parsed by [`parallel::parse_graph`].
+    fn from_parsed(parsed: ParsedModule, context: &mut Context) -> Self {
+        let path = parsed.path().to_path_buf();
+        let module = parsed.into_ast(context.interner_mut());
+        Self::from_ast(module, Some(path), None, context)
+    }
+
+    /// Creates a new source text module from its AST, whose symbols are in the interner of the
+    /// context.
+    fn from_ast(
+        module: boa_ast::Module,
+        path: Option<PathBuf>,
+        realm: Option<Realm>,
+        context: &mut Context,
+    ) -> Self {
         let inner = Gc::new_cyclic(|weak| {
             let src = SourceTextModule::new(module, weak.clone(), context.interner());
 
@@ -160,10 +188,11 @@ impl Module {
                 namespace: GcRefCell::default(),
                 kind: ModuleKind::SourceText(src),
                 host_defined: HostDefined::default(),
+                path,
             }
         });
 
-        Ok(Self { inner })
+        Self { inner }
     }
 
     /// Abstract operation [`CreateSyntheticModule ( exportNames, evaluationSteps, realm )`][spec].
@@ -190,6 +219,7 @@ impl Module {
                 namespace: GcRefCell::default(),
                 kind: ModuleKind::Synthetic(synth),
                 host_defined: HostDefined::default(),
+                path: None,
             }
         });
 
@@ -212,6 +242,25 @@ impl Module {
         &self.inner.host_defined
     }
 
+    /// Gets the path of the file this `Module` was parsed from, if its source had one.
+    #[inline]
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.inner.path.as_deref()
+    }
+
+    /// Gets the modules imported by this `Module`, statically or through `import()`, which have
+    /// been loaded so far.
+    #[must_use]
+    pub fn dependencies(&self) -> Vec<Module> {
+        match self.kind() {
+            ModuleKind::SourceText(src) => {
+                src.loaded_modules().borrow().values().cloned().collect()
+            }
+            ModuleKind::Synthetic(_) => Vec::new(),
+        }
+    }
+
     /// Gets the kind of this `Module`.
     pub(crate) fn kind(&self) -> &ModuleKind {
         &self.inner.kind
@@ -0,0 +1,233 @@
+//! The parallel parsing of module graphs.
+//!
+//! Parsing a module needs an [`Interner`], which cannot be shared between threads, so the
+//! modules of a graph are parsed by worker threads with their own interners. The parsed modules
+//! are sent to the thread of the context as [`ParsedModule`]s, which carry the strings of their
+//! symbols instead of an interner. Their symbols are interned again in the interner of the
+//! context before compiling them, which is done on the thread of the context.
+
+use std::{
+    convert::Infallible,
+    io,
+    num::NonZeroUsize,
+    ops::ControlFlow,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::{mpsc, Mutex},
+    thread,
+};
+
+use boa_ast::visitor::{Visitor, VisitorMut};
+use boa_interner::{Interner, Sym};
+use boa_parser::{Parser, Source};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{js_string, Context, JsError, JsNativeError, JsResult};
+
+use super::ModuleSource;
+
+/// A module parsed on a worker thread, independently of the interner of any context.
+#[derive(Debug)]
+pub(super) struct ParsedModule {
+    ast: boa_ast::Module,
+    path: PathBuf,
+    symbols: Vec<(Sym, Box<[u16]>)>,
+    requests: Vec<String>,
+}
+
+impl ParsedModule {
+    /// Parses the module at `path` from its `contents`, with the parser `identifier` reserved
+    /// by the context.
+    fn parse(contents: &[u8], path: PathBuf, identifier: u32) -> Result<Self, boa_parser::Error> {
+        let mut interner = Interner::default();
+        let mut parser = Parser::new(Source::from_reader(contents, Some(&path)));
+        parser.set_identifier(identifier);
+        let ast = parser.parse_module(&mut interner)?;
+
+        let mut symbols = FxHashSet::default();
+        SymbolCollector(&mut symbols).visit_module(&ast);
+        let symbols = symbols
+            .into_iter()
+            .map(|sym| (sym, interner.resolve_expect(sym).utf16().into()))
+            .collect();
+        let requests = ast
+            .items()
+            .requests()
+            .iter()
+            .map(|request| interner.resolve_expect(*request).to_string())
+            .collect();
+
+        Ok(Self {
+            ast,
+            path,
+            symbols,
+            requests,
+        })
+    }
+
+    /// Gets the path of the module.
+    pub(super) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Interns the symbols of the module in `interner`, returning its AST with the symbols of
+    /// `interner`.
+    pub(super) fn into_ast(self, interner: &mut Interner) -> boa_ast::Module {
+        let symbols = self
+            .symbols
+            .iter()
+            .map(|(sym, string)| (*sym, interner.get_or_intern(&string[..])))
+            .collect();
+        let mut ast = self.ast;
+        SymbolRemapper(&symbols).visit_module_mut(&mut ast);
+        ast
+    }
+}
+
+/// Collects the symbols of an AST.
+struct SymbolCollector<'set>(&'set mut FxHashSet<Sym>);
+
+impl<'ast> Visitor<'ast> for SymbolCollector<'_> {
+    type BreakTy = Infallible;
+
+    fn visit_sym(&mut self, node: &'ast Sym) -> ControlFlow<Self::BreakTy> {
+        self.0.insert(*node);
+        ControlFlow::Continue(())
+    }
+}
+
+/// Replaces the symbols of an AST by the symbols of another interner.
+struct SymbolRemapper<'map>(&'map FxHashMap<Sym, Sym>);
+
+impl<'ast> VisitorMut<'ast> for SymbolRemapper<'_> {
+    type BreakTy = Infallible;
+
+    fn visit_sym_mut(&mut self, node: &'ast mut Sym) -> ControlFlow<Self::BreakTy> {
+        *node = self.0[node];
+        ControlFlow::Continue(())
+    }
+}
+
+/// The reason a module of a graph could not be parsed.
+enum ParseError {
+    Read(io::Error),
+    Parse(boa_parser::Error),
+    Panic,
+}
+
+/// Parses the module at `entry` and the modules it imports statically, directly or
+/// transitively, on a pool of worker threads.
+///
+/// `resolve` maps the module requests of the module at a path to the path of the imported
+/// module, or to `None` if the imported module shouldn't be parsed, e.g. because it is already
+/// loaded. It is called on the thread of the context. The imported modules that cannot be read or parsed are skipped, leaving their errors
+/// to be reported when they are loaded.
+///
+/// Returns the parsed modules, starting with the entry, which is parsed before any other module.
+pub(super) fn parse_graph<S, F>(
+    source: &S,
+    entry: &Path,
+    mut resolve: F,
+    context: &mut Context,
+) -> JsResult<Vec<ParsedModule>>
+where
+    S: ModuleSource + Sync,
+    F: FnMut(&Path, &str, &mut Context) -> Option<PathBuf>,
+{
+    let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let (job_sender, job_receiver) = mpsc::channel::<(PathBuf, u32)>();
+    let job_receiver = Mutex::new(job_receiver);
+    let (result_sender, result_receiver) = mpsc::channel();
+
+    let (parsed, entry_error) = thread::scope(|scope| {
+        for _ in 0..workers {
+            let result_sender = result_sender.clone();
+            let job_receiver = &job_receiver;
+            scope.spawn(move || loop {
+                let Ok((path, identifier)) = job_receiver
+                    .lock()
+                    .expect("a module parsing worker panicked")
+                    .recv()
+                else {
+                    break;
+                };
+                // A panic of the parser is reported as the result of the job, since the context
+                // thread waits for a result for every job it sends.
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    source
+                        .read(&path)
+                        .map_err(ParseError::Read)
+                        .and_then(|contents| {
+                            ParsedModule::parse(&contents, path.clone(), identifier)
+                                .map_err(ParseError::Parse)
+                        })
+                }))
+                .unwrap_or(Err(ParseError::Panic));
+                if result_sender.send((path, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        // Only the workers hold result senders, so the receiver is disconnected if they all exit
+        // instead of blocking forever.
+        drop(result_sender);
+        // Moving the sender into the scope stops the workers before the scope joins them, even
+        // if this thread panics.
+        let job_sender = job_sender;
+
+        let mut seen = FxHashSet::default();
+        seen.insert(entry.to_path_buf());
+        job_sender
+            .send((entry.to_path_buf(), context.next_parser_identifier()))
+            .expect("the job receiver lives until the end of the scope");
+        let mut pending = 1;
+        let mut parsed = Vec::new();
+        let mut entry_error = None;
+        while pending > 0 {
+            let Ok((path, result)) = result_receiver.recv() else {
+                return Err(JsError::from(JsNativeError::error().with_message(format!(
+                    "the module parsing workers stopped while parsing `{}`",
+                    entry.display()
+                ))));
+            };
+            pending -= 1;
+            match result {
+                Ok(module) => {
+                    for request in &module.requests {
+                        let Some(dependency) = resolve(&module.path, request, context) else {
+                            continue;
+                        };
+                        if seen.insert(dependency.clone()) {
+                            job_sender
+                                .send((dependency, context.next_parser_identifier()))
+                                .expect("the job receiver lives until the end of the scope");
+                            pending += 1;
+                        }
+                    }
+                    parsed.push(module);
+                }
+                Err(err) if path == entry => entry_error = Some(err),
+                Err(_) => {}
+            }
+        }
+        Ok((parsed, entry_error))
+    })?;
+
+    match entry_error {
+        None => Ok(parsed),
+        Some(ParseError::Read(err)) => Err(JsNativeError::typ()
+            .with_message(format!("could not open file `{}`", entry.display()))
+            .with_cause(JsError::from_opaque(js_string!(err.to_string()).into()))
+            .into()),
+        Some(ParseError::Parse(err)) => Err(JsNativeError::syntax()
+            .with_message(format!("could not parse module `{}`", entry.display()))
+            .with_cause(JsError::from(err))
+            .into()),
+        Some(ParseError::Panic) => Err(JsNativeError::error()
+            .with_message(format!(
+                "the parser panicked on module `{}`",
+                entry.display()
+            ))
+            .into()),
+    }
+}
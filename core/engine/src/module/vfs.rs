@@ -0,0 +1,219 @@
+//! The sources the [`SimpleModuleLoader`] reads its modules from.
+//!
+//! A [`ModuleSource`] maps the paths of the modules to their contents, which allows loading the
+//! modules of an application from somewhere other than the file system of the host, e.g. from a
+//! bundle embedded in the application. The paths of the modules are resolved by the loader before
+//! reaching the source, so relative imports work the same way with any source.
+//!
+//! [`SimpleModuleLoader`]: super::SimpleModuleLoader
+
+use std::{
+    fmt::{self, Debug},
+    io,
+    path::{Component, Path, PathBuf},
+    rc::Rc,
+};
+
+use rustc_hash::FxHashMap;
+
+/// A source of module files, read by the [`SimpleModuleLoader`].
+///
+/// The paths passed to a source are absolute, and have been normalized by its
+/// [`ModuleSource::canonicalize`] method.
+///
+/// [`SimpleModuleLoader`]: super::SimpleModuleLoader
+pub trait ModuleSource {
+    /// Reads the contents of the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file doesn't exist, or if it cannot be read.
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    /// Returns the canonical form of the absolute path `path`, which identifies the module at
+    /// `path` in the module map of the loader.
+    ///
+    /// Resolves the `.` and `..` components of `path` by default.
+    fn canonicalize(&self, path: &Path) -> PathBuf {
+        normalize(path)
+    }
+}
+
+impl Debug for dyn ModuleSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("dyn ModuleSource")
+    }
+}
+
+/// A [`ModuleSource`] reading the modules from the file system of the host.
+///
+/// Paths are canonicalized by the file system if they exist, resolving their symbolic links.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileSystemSource;
+
+impl ModuleSource for FileSystemSource {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> PathBuf {
+        path.canonicalize().unwrap_or_else(|_| normalize(path))
+    }
+}
+
+/// A [`ModuleSource`] exposing the files of a directory of the host as a virtual file system,
+/// whose root `/` is the directory.
+///
+/// Modules cannot read the files outside of the directory, since `..` components cannot go up
+/// from the virtual root, and files whose symbolic links lead outside of the directory are
+/// refused.
+#[derive(Debug, Clone)]
+pub struct DirectorySource {
+    dir: PathBuf,
+}
+
+impl DirectorySource {
+    /// Creates a new `DirectorySource` for the directory `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `dir` cannot be canonicalized.
+    pub fn new<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        Ok(Self {
+            dir: dir.as_ref().canonicalize()?,
+        })
+    }
+
+    /// Gets the directory of the source.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl ModuleSource for DirectorySource {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let relative = path
+            .components()
+            .filter(|component| matches!(component, Component::Normal(_)));
+        let file = self
+            .dir
+            .join(relative.collect::<PathBuf>())
+            .canonicalize()?;
+        if !file.starts_with(&self.dir) {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("`{}` is outside of the module directory", path.display()),
+            ));
+        }
+        std::fs::read(file)
+    }
+}
+
+/// A [`ModuleSource`] storing the files of the modules in memory, keyed by their virtual paths.
+///
+/// Relative paths are inserted relative to the virtual root `/`.
+///
+/// ```
+/// use boa_engine::module::InMemorySource;
+///
+/// let source = InMemorySource::new()
+///     .with_file("/main.js", "export { value } from './lib/value.js';")
+///     .with_file("lib/value.js", "export const value = 42;");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InMemorySource {
+    files: FxHashMap<PathBuf, Vec<u8>>,
+}
+
+impl InMemorySource {
+    /// Creates a new, empty `InMemorySource`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the file `path` with the given `contents`, replacing any previous file at `path`.
+    #[must_use]
+    pub fn with_file<P: AsRef<Path>, C: Into<Vec<u8>>>(mut self, path: P, contents: C) -> Self {
+        self.insert(path, contents);
+        self
+    }
+
+    /// Inserts the file `path` with the given `contents`, returning the previous contents of the
+    /// file, if any.
+    pub fn insert<P: AsRef<Path>, C: Into<Vec<u8>>>(
+        &mut self,
+        path: P,
+        contents: C,
+    ) -> Option<Vec<u8>> {
+        let path = normalize(&Path::new("/").join(path));
+        self.files.insert(path, contents.into())
+    }
+
+    /// Gets the contents of the file `path`.
+    #[must_use]
+    pub fn get(&self, path: &Path) -> Option<&[u8]> {
+        self.files
+            .get(&normalize(&Path::new("/").join(path)))
+            .map(Vec::as_slice)
+    }
+
+    /// Returns the number of files of the source.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns `true` if the source has no files.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+impl ModuleSource for InMemorySource {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.get(path).map(<[u8]>::to_vec).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("`{}` does not exist", path.display()),
+            )
+        })
+    }
+}
+
+impl<S: ModuleSource + ?Sized> ModuleSource for Box<S> {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        (**self).read(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> PathBuf {
+        (**self).canonicalize(path)
+    }
+}
+
+impl<S: ModuleSource + ?Sized> ModuleSource for Rc<S> {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        (**self).read(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> PathBuf {
+        (**self).canonicalize(path)
+    }
+}
+
+/// Resolves the `.` and `..` components of `path`, without accessing the file system.
+pub(super) fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
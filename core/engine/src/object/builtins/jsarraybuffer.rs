@@ -3,7 +3,10 @@ use crate::{
     builtins::array_buffer::ArrayBuffer,
     context::intrinsics::StandardConstructors,
     error::JsNativeError,
-    object::{internal_methods::get_prototype_from_constructor, JsObject, JsObjectType},
+    object::{
+        internal_methods::get_prototype_from_constructor, ErasedObject, JsObject, JsObjectType,
+        Ref, RefMut,
+    },
     value::TryFromJs,
     Context, JsResult, JsValue,
 };
@@ -153,6 +156,77 @@ impl JsArrayBuffer {
             .expect("expected a number") as usize
     }
 
+    /// Gets an immutable reference to the bytes of the inner `ArrayBuffer`, or `None` if it
+    /// is detached.
+    ///
+    /// ```
+    /// # use boa_engine::{
+    /// # object::builtins::JsArrayBuffer,
+    /// # Context, JsResult
+    /// # };
+    /// # fn main() -> JsResult<()> {
+    /// # // Initialize context
+    /// # let context = &mut Context::default();
+    /// // Create a buffer from a chunk of data
+    /// let data_block: Vec<u8> = (0..5).collect();
+    /// let array_buffer = JsArrayBuffer::from_byte_block(data_block, context)?;
+    ///
+    /// assert_eq!(array_buffer.data().as_deref(), Some(&[0, 1, 2, 3, 4][..]));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is currently mutably borrowed.
+    #[inline]
+    #[must_use]
+    pub fn data(&self) -> Option<Ref<'_, [u8]>> {
+        Ref::try_map(
+            self.inner
+                .downcast_ref::<ArrayBuffer>()
+                .expect("inner must be an ArrayBuffer"),
+            ArrayBuffer::data,
+        )
+    }
+
+    /// Gets a mutable reference to the bytes of the inner `ArrayBuffer`, or `None` if it is
+    /// detached.
+    ///
+    /// ```
+    /// # use boa_engine::{
+    /// # object::builtins::JsArrayBuffer,
+    /// # Context, JsResult
+    /// # };
+    /// # fn main() -> JsResult<()> {
+    /// # // Initialize context
+    /// # let context = &mut Context::default();
+    /// // Create a blank array buffer of 3 bytes
+    /// let array_buffer = JsArrayBuffer::new(3, context)?;
+    ///
+    /// if let Some(mut data) = array_buffer.data_mut() {
+    ///     data.copy_from_slice(&[1, 2, 3]);
+    /// }
+    ///
+    /// assert_eq!(array_buffer.data().as_deref(), Some(&[1, 2, 3][..]));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer is currently borrowed.
+    #[inline]
+    #[must_use]
+    pub fn data_mut(&self) -> Option<RefMut<'_, ErasedObject, [u8]>> {
+        RefMut::try_map(
+            self.inner
+                .downcast_mut::<ArrayBuffer>()
+                .expect("inner must be an ArrayBuffer"),
+            ArrayBuffer::data_mut,
+        )
+    }
+
     /// Take the inner `ArrayBuffer`'s `array_buffer_data` field and replace it with `None`
     ///
     /// # Note
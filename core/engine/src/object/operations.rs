@@ -343,6 +343,37 @@ impl JsObject {
         Ok(desc.is_some())
     }
 
+    /// Gets the own property descriptor of `key` in the object, without invoking any getter.
+    ///
+    /// This calls the `[[GetOwnProperty]]` internal method of the object.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-getownproperty-p
+    pub fn own_property<K>(
+        &self,
+        key: K,
+        context: &mut Context,
+    ) -> JsResult<Option<PropertyDescriptor>>
+    where
+        K: Into<PropertyKey>,
+    {
+        self.__get_own_property__(&key.into(), &mut InternalMethodContext::new(context))
+    }
+
+    /// Gets all the own property keys of the object, in the order defined by the specification.
+    ///
+    /// This calls the `[[OwnPropertyKeys]]` internal method of the object.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-ownpropertykeys
+    pub fn own_property_keys(&self, context: &mut Context) -> JsResult<Vec<PropertyKey>> {
+        self.__own_property_keys__(context)
+    }
+
     /// `Call ( F, V [ , argumentsList ] )`
     ///
     /// # Panics
@@ -12,9 +12,11 @@ use boa_gc::{Finalize, Gc, GcRefCell, Trace};
 use boa_parser::{source::ReadChar, Parser, Source};
 use boa_profiler::Profiler;
 use rustc_hash::FxHashMap;
+use std::path::{Path, PathBuf};
 
 use crate::{
     bytecompiler::ByteCompiler,
+    environments::EnvironmentStack,
     js_string,
     realm::Realm,
     vm::{ActiveRunnable, CallFrame, CallFrameFlags, CodeBlock},
@@ -35,6 +37,7 @@ impl std::fmt::Debug for Script {
             .field("realm", &self.inner.realm.addr())
             .field("code", &self.inner.source)
             .field("loaded_modules", &self.inner.loaded_modules)
+            .field("path", &self.inner.path)
             .finish()
     }
 }
@@ -47,6 +50,7 @@ struct Inner {
     codeblock: GcRefCell<Option<Gc<CodeBlock>>>,
     loaded_modules: GcRefCell<FxHashMap<JsString, Module>>,
     host_defined: HostDefined,
+    path: Option<PathBuf>,
 }
 
 impl Script {
@@ -64,6 +68,12 @@ impl Script {
         &self.inner.host_defined
     }
 
+    /// Gets the path of the file this script was parsed from, if its source had one.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.inner.path.as_deref()
+    }
+
     /// Gets the loaded modules of this script.
     pub(crate) fn loaded_modules(&self) -> &GcRefCell<FxHashMap<JsString, Module>> {
         &self.inner.loaded_modules
@@ -80,25 +90,42 @@ impl Script {
         context: &mut Context,
     ) -> JsResult<Self> {
         let _timer = Profiler::global().start_event("Script parsing", "Main");
+        let path = src.path().map(Path::to_path_buf);
         let mut parser = Parser::new(src);
         parser.set_identifier(context.next_parser_identifier());
         if context.is_strict() {
             parser.set_strict();
         }
-        let mut code = parser.parse_script(context.interner_mut())?;
+        let code = parser.parse_script(context.interner_mut())?;
+
+        Ok(Self::from_ast(code, realm, path, context))
+    }
+
+    /// Creates a script from already parsed `code`, e.g. code transformed at the AST level.
+    ///
+    /// The parser of `code` must have an identifier set with
+    /// [`Context::next_parser_identifier`], and `path` is the path used to resolve its dynamic
+    /// imports.
+    pub fn from_ast(
+        mut code: boa_ast::Script,
+        realm: Option<Realm>,
+        path: Option<PathBuf>,
+        context: &mut Context,
+    ) -> Self {
         if !context.optimizer_options().is_empty() {
             context.optimize_statement_list(code.statements_mut());
         }
 
-        Ok(Self {
+        Self {
             inner: Gc::new(Inner {
                 realm: realm.unwrap_or_else(|| context.realm().clone()),
                 source: code,
                 codeblock: GcRefCell::default(),
                 loaded_modules: GcRefCell::default(),
                 host_defined: HostDefined::default(),
+                path,
             }),
-        })
+        }
     }
 
     /// Compiles the codeblock of this script.
@@ -192,12 +219,15 @@ impl Script {
     fn prepare_run(&self, context: &mut Context) -> JsResult<()> {
         let codeblock = self.codeblock(context)?;
 
-        let env_fp = context.vm.environments.len() as u32;
+        // Scripts always run in the global environment of their realm, even when they are
+        // evaluated by a native function called from another function.
+        let environments = EnvironmentStack::new(self.inner.realm.environment().clone());
+        let env_fp = environments.len() as u32;
         context.vm.push_frame_with_stack(
             CallFrame::new(
                 codeblock,
                 Some(ActiveRunnable::Script(self.clone())),
-                context.vm.environments.clone(),
+                environments,
                 self.inner.realm.clone(),
             )
             .with_env_fp(env_fp)
@@ -138,6 +138,10 @@ impl StaticJsStrings {
         (LIST_FORMAT, "ListFormat"),
         (LOCALE, "Locale"),
         (PLURAL_RULES, "PluralRules"),
+        (NUMBER_FORMAT, "NumberFormat"),
+        (RELATIVE_TIME_FORMAT, "RelativeTimeFormat"),
+        (DISPLAY_NAMES, "DisplayNames"),
+        (DURATION_FORMAT, "DurationFormat"),
         (SEGMENTER, "Segmenter"),
         (DATE_TIME_FORMAT, "DateTimeFormat"),
         (JSON, "JSON"),
@@ -280,6 +284,10 @@ const RAW_STATICS: &[&[u16]] = &[
     utf16!("ListFormat"),
     utf16!("Locale"),
     utf16!("PluralRules"),
+    utf16!("NumberFormat"),
+    utf16!("RelativeTimeFormat"),
+    utf16!("DisplayNames"),
+    utf16!("DurationFormat"),
     utf16!("Segmenter"),
     utf16!("DateTimeFormat"),
     utf16!("JSON"),
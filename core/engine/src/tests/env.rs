@@ -1,6 +1,9 @@
 use indoc::indoc;
 
-use crate::{js_string, run_test_actions, JsNativeErrorKind, TestAction};
+use crate::{
+    js_string, native_function::NativeFunction, run_test_actions, run_test_actions_with, Context,
+    JsArgs, JsNativeErrorKind, JsValue, Source, TestAction,
+};
 
 #[test]
 // https://github.com/boa-dev/boa/issues/2317
@@ -46,3 +49,97 @@ fn with_env_not_panic() {
         "k is not defined",
     )]);
 }
+
+#[test]
+fn script_evaluated_from_native_function() {
+    let context = &mut Context::default();
+    context
+        .register_global_builtin_callable(
+            js_string!("evalScript"),
+            1,
+            NativeFunction::from_fn_ptr(|_, args, context| {
+                let source = args.get_or_undefined(0).to_string(context)?;
+                let function = context.eval(Source::from_bytes(&source.to_std_string_escaped()))?;
+                function
+                    .as_callable()
+                    .expect("the script must return a function")
+                    .call(&JsValue::undefined(), &[js_string!("arg").into()], context)
+            }),
+        )
+        .unwrap();
+
+    run_test_actions_with(
+        [TestAction::assert_eq(
+            indoc! {r#"
+                (function () {
+                    let before = "before";
+                    const result = evalScript("(function (x) { let y = x; return y; })");
+                    let after = "after";
+                    return [before, result, after].join();
+                })();
+            "#},
+            js_string!("before,arg,after"),
+        )],
+        context,
+    );
+}
+
+#[test]
+fn script_evaluated_from_nested_functions() {
+    let context = &mut Context::default();
+    context
+        .register_global_builtin_callable(
+            js_string!("evalScript"),
+            1,
+            NativeFunction::from_fn_ptr(|_, args, context| {
+                let source = args.get_or_undefined(0).to_string(context)?;
+                context.eval(Source::from_bytes(&source.to_std_string_escaped()))
+            }),
+        )
+        .unwrap();
+
+    run_test_actions_with(
+        [
+            TestAction::run("var name = 'global';"),
+            // The script must resolve its bindings in the global environment, not in the
+            // environments of the functions calling it.
+            TestAction::assert_eq(
+                indoc! {r#"
+                    (function () {
+                        let name = "outer";
+                        return (() => {
+                            let name = "inner";
+                            {
+                                let name = "block";
+                                return [evalScript("name"), name].join();
+                            }
+                        })();
+                    })();
+                "#},
+                js_string!("global,block"),
+            ),
+            // Bindings declared by the script are global, and the environments of the calling
+            // functions are left intact.
+            TestAction::assert_eq(
+                indoc! {r#"
+                    (function () {
+                        let local = 1;
+                        function nested() {
+                            let inner = 2;
+                            evalScript("let declared = 3; var declaredVar = 4;");
+                            const closure = evalScript(
+                                "(function (a) { let b = a; return () => { let c = b; return c; }; })"
+                            );
+                            let after = closure(2)();
+                            return local + inner + after;
+                        }
+                        return [nested(), local].join();
+                    })();
+                "#},
+                js_string!("5,1"),
+            ),
+            TestAction::assert_eq("declared + declaredVar", 7),
+        ],
+        context,
+    );
+}
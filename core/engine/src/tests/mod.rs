@@ -4,6 +4,7 @@ mod control_flow;
 mod env;
 mod function;
 mod iterators;
+mod modules;
 mod operators;
 mod promise;
 mod spread;
@@ -0,0 +1,448 @@
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use indoc::indoc;
+
+use crate::{
+    builtins::promise::PromiseState,
+    js_string,
+    module::{DirectorySource, InMemorySource, ModuleLoader, SimpleModuleLoader},
+    run_test_actions_with, Context, Module, Source, TestAction,
+};
+
+/// A temporary directory of modules, removed when dropped.
+struct ModuleDir(PathBuf);
+
+impl ModuleDir {
+    fn new(name: &str, files: &[(&str, &str)]) -> Self {
+        let path = std::env::temp_dir().join(format!("boa-modules-{name}-{}", std::process::id()));
+        drop(fs::remove_dir_all(&path));
+        fs::create_dir_all(&path).unwrap();
+        for (file, contents) in files {
+            let file = path.join(file);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, contents).unwrap();
+        }
+        Self(path.canonicalize().unwrap())
+    }
+}
+
+impl Drop for ModuleDir {
+    fn drop(&mut self) {
+        drop(fs::remove_dir_all(&self.0));
+    }
+}
+
+#[test]
+fn simple_module_loader_resolves_against_referrer() {
+    let dir = ModuleDir::new(
+        "referrer",
+        &[
+            (
+                "lib/a.js",
+                indoc! {r#"
+                    import { b } from "./b.js";
+                    import { c } from "../c.js";
+                    export const a = b + c;
+                "#},
+            ),
+            ("lib/b.js", "export const b = 1;"),
+            ("c.js", "export const c = 2;"),
+            ("b.js", "export const b = 100;"),
+        ],
+    );
+    let loader = Rc::new(SimpleModuleLoader::new(&dir.0).unwrap());
+    let context = &mut Context::builder()
+        .module_loader(loader.clone())
+        .build()
+        .unwrap();
+
+    let main = dir.0.join("main.js");
+    let source = indoc! {r#"
+        import { a } from "./lib/a.js";
+        import { b } from "lib/b.js";
+        globalThis.result = [a, b];
+        globalThis.url = import.meta.url;
+        globalThis.resolved = import.meta.resolve("./lib/../d.js");
+    "#};
+    let module = Module::parse(
+        Source::from_reader(source.as_bytes(), Some(&main)),
+        None,
+        context,
+    )
+    .unwrap();
+    loader.insert(main.clone(), module.clone());
+    let promise = module.load_link_evaluate(context);
+    context.run_jobs();
+    assert!(matches!(promise.state(), PromiseState::Fulfilled(_)));
+
+    let url = format!("file://{}", main.display());
+    let resolved = format!("file://{}", dir.0.join("d.js").display());
+    run_test_actions_with(
+        [
+            TestAction::assert_eq("result.join()", js_string!("3,1")),
+            TestAction::assert_eq("url", js_string!(url.as_str())),
+            TestAction::assert_eq("resolved", js_string!(resolved.as_str())),
+        ],
+        context,
+    );
+
+    // The utility methods share the module map of the loader.
+    let b = loader.get(&dir.0.join("lib/b.js")).unwrap();
+    assert_eq!(loader.get_module(js_string!("lib/b.js")), Some(b));
+    assert_eq!(loader.get_module(js_string!("./main.js")), Some(module));
+    assert!(loader.get_module(js_string!("b.js")).is_none());
+}
+
+#[test]
+fn simple_module_loader_register_module() {
+    let dir = ModuleDir::new("register", &[]);
+    let loader = Rc::new(SimpleModuleLoader::new(&dir.0).unwrap());
+    let context = &mut Context::builder()
+        .module_loader(loader.clone())
+        .build()
+        .unwrap();
+
+    let virtual_module = Module::parse(
+        Source::from_bytes("export const value = 'virtual';"),
+        None,
+        context,
+    )
+    .unwrap();
+    context
+        .module_loader()
+        .register_module(js_string!("virtual/value.js"), virtual_module);
+
+    let source = indoc! {r#"
+        import { value } from "./virtual/value.js";
+        globalThis.value = value;
+    "#};
+    let module = Module::parse(Source::from_bytes(source), None, context).unwrap();
+    let promise = module.load_link_evaluate(context);
+    context.run_jobs();
+    assert!(matches!(promise.state(), PromiseState::Fulfilled(_)));
+    run_test_actions_with(
+        [TestAction::assert_eq("value", js_string!("virtual"))],
+        context,
+    );
+}
+
+#[test]
+fn simple_module_loader_in_memory_source() {
+    let source = InMemorySource::new()
+        .with_file(
+            "/app/main.js",
+            indoc! {r#"
+                import { a } from "./lib/a.js";
+                import { shared } from "shared.js";
+                globalThis.result = [a, shared];
+                globalThis.url = import.meta.url;
+            "#},
+        )
+        .with_file(
+            "/app/lib/a.js",
+            "import { shared } from '../shared.js'; export const a = shared + 1;",
+        )
+        .with_file("/app/shared.js", "export const shared = 1;")
+        .with_file("/app/broken.js", "export const = ;");
+    let loader = Rc::new(SimpleModuleLoader::with_source(source, "app"));
+    let context = &mut Context::builder()
+        .module_loader(loader.clone())
+        .build()
+        .unwrap();
+
+    let module = loader.load(Path::new("main.js"), context).unwrap();
+    let promise = module.load_link_evaluate(context);
+    context.run_jobs();
+    assert!(matches!(promise.state(), PromiseState::Fulfilled(_)));
+    run_test_actions_with(
+        [
+            TestAction::assert_eq("result.join()", js_string!("2,1")),
+            TestAction::assert_eq("url", js_string!("file:///app/main.js")),
+        ],
+        context,
+    );
+
+    // Modules are shared by their path, however they were imported.
+    assert_eq!(
+        loader.get(Path::new("/app/lib/../shared.js")),
+        loader.get_module(js_string!("shared.js"))
+    );
+    assert!(loader.load(Path::new("missing.js"), context).is_err());
+    assert!(loader.load(Path::new("broken.js"), context).is_err());
+}
+
+#[test]
+fn simple_module_loader_directory_source() {
+    let dir = ModuleDir::new(
+        "directory",
+        &[
+            (
+                "app/main.js",
+                indoc! {r#"
+                    import { value } from "./value.js";
+                    globalThis.value = value;
+                    globalThis.url = import.meta.url;
+                "#},
+            ),
+            ("app/value.js", "export const value = 'inside';"),
+            ("app/escape.js", "import '../../secret.js';"),
+            ("secret.js", "globalThis.leaked = true;"),
+        ],
+    );
+    let source = DirectorySource::new(dir.0.join("app")).unwrap();
+    let loader = Rc::new(SimpleModuleLoader::with_source(source, "/"));
+    let context = &mut Context::builder()
+        .module_loader(loader.clone())
+        .build()
+        .unwrap();
+
+    let module = loader.load(Path::new("main.js"), context).unwrap();
+    let promise = module.load_link_evaluate(context);
+    context.run_jobs();
+    assert!(matches!(promise.state(), PromiseState::Fulfilled(_)));
+
+    // Parent components cannot leave the directory.
+    let escape = loader.load(Path::new("escape.js"), context).unwrap();
+    let promise = escape.load_link_evaluate(context);
+    context.run_jobs();
+    assert!(matches!(promise.state(), PromiseState::Rejected(_)));
+
+    run_test_actions_with(
+        [
+            TestAction::assert_eq("value", js_string!("inside")),
+            TestAction::assert_eq("url", js_string!("file:///main.js")),
+            TestAction::assert_eq("globalThis.leaked", crate::JsValue::undefined()),
+        ],
+        context,
+    );
+}
+
+#[test]
+fn simple_module_loader_reload() {
+    let dir = ModuleDir::new(
+        "reload",
+        &[
+            (
+                "main.js",
+                indoc! {r#"
+                    import { describe } from "./plugin.js";
+                    globalThis.describe = describe;
+                "#},
+            ),
+            (
+                "plugin.js",
+                indoc! {r#"
+                    import { version, state } from "./lib/version.js";
+                    import { util } from "./lib/util.js";
+                    export const describe = () => `${util} v${version} (${state.hits} hits)`;
+                "#},
+            ),
+            (
+                "lib/util.js",
+                "globalThis.utilRuns = (globalThis.utilRuns ?? 0) + 1; export const util = 'util';",
+            ),
+            (
+                "lib/version.js",
+                "export const version = 1; export const state = { hits: 3 };",
+            ),
+        ],
+    );
+    let loader = Rc::new(SimpleModuleLoader::new(&dir.0).unwrap());
+    let context = &mut Context::builder()
+        .module_loader(loader.clone())
+        .build()
+        .unwrap();
+
+    let main = loader.load(Path::new("main.js"), context).unwrap();
+    let promise = main.load_link_evaluate(context);
+    context.run_jobs();
+    assert!(matches!(promise.state(), PromiseState::Fulfilled(_)));
+    run_test_actions_with(
+        [TestAction::assert_eq(
+            "describe()",
+            js_string!("util v1 (3 hits)"),
+        )],
+        context,
+    );
+
+    fs::write(
+        dir.0.join("lib/version.js"),
+        "export const version = 2; export const state = globalThis.migrated;",
+    )
+    .unwrap();
+    let mut migrated = Vec::new();
+    let reloaded = loader
+        .reload(
+            Path::new("lib/version.js"),
+            |module, context| {
+                migrated.push(module.path().to_path_buf());
+                if module.path().ends_with("version.js") {
+                    let state = module
+                        .previous()
+                        .namespace(context)
+                        .get(js_string!("state"), context)?;
+                    context
+                        .global_object()
+                        .set(js_string!("migrated"), state, true, context)?;
+                }
+                Ok(())
+            },
+            context,
+        )
+        .unwrap();
+
+    let expected = ["lib/version.js", "plugin.js", "main.js"].map(|path| dir.0.join(path));
+    assert_eq!(migrated, expected);
+    assert_eq!(
+        reloaded
+            .iter()
+            .map(|module| module.path().to_path_buf())
+            .collect::<Vec<_>>(),
+        expected
+    );
+    assert_eq!(reloaded[2].previous(), &main);
+    assert_eq!(
+        loader.get(Path::new("main.js")).as_ref(),
+        Some(reloaded[2].current())
+    );
+    run_test_actions_with(
+        [
+            TestAction::assert_eq("describe()", js_string!("util v2 (3 hits)")),
+            TestAction::assert_eq("utilRuns", 1),
+        ],
+        context,
+    );
+
+    // Failed reloads keep the previous versions.
+    fs::write(dir.0.join("lib/version.js"), "export const = ;").unwrap();
+    let plugin = loader.get(Path::new("plugin.js"));
+    assert!(loader
+        .reload(Path::new("lib/version.js"), |_, _| Ok(()), context)
+        .is_err());
+    assert_eq!(loader.get(Path::new("plugin.js")), plugin);
+    assert!(loader.get(Path::new("lib/version.js")).is_some());
+    assert!(loader
+        .reload(Path::new("missing.js"), |_, _| Ok(()), context)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn simple_module_loader_preload() {
+    let mut files = vec![
+        (
+            "main.js".to_owned(),
+            indoc! {r#"
+                import { Counter, tag, pattern } from "./lib/syntax.js";
+                import * as leaves from "./lib/leaves.js";
+                import missing from "./lib/missing.js";
+                export { missing };
+            "#}
+            .to_owned(),
+        ),
+        (
+            "app.js".to_owned(),
+            indoc! {r#"
+                import { Counter, tag, pattern, labels } from "./lib/syntax.js";
+                import * as leaves from "./lib/leaves.js";
+                import { "string name" as stringName } from "./lib/syntax.js";
+                const counter = new Counter();
+                counter.increment();
+                globalThis.result = [
+                    counter.count,
+                    counter.hasSecret(),
+                    tag`a${1}b`,
+                    tag`a${1}b` === tag`a${1}b`,
+                    pattern.test("boa"),
+                    labels(),
+                    stringName,
+                    leaves.sum,
+                    import.meta.url.endsWith("/app.js"),
+                ].join();
+            "#}
+            .to_owned(),
+        ),
+        (
+            "lib/syntax.js".to_owned(),
+            indoc! {r#"
+                export class Counter {
+                    #count = 0;
+                    static #instances = 0;
+                    get count() { return this.#count; }
+                    increment() { this.#count++; Counter.#instances++; }
+                    hasSecret() { return #count in this; }
+                }
+                export const tag = (strings, ...values) => strings.raw.join("|") + values.length;
+                export const pattern = /^b[o]a$/i;
+                export function labels() {
+                    let found = 0;
+                    outer: for (const i of [1, 2, 3]) {
+                        for (const j of [1, 2, 3]) {
+                            if (j === 2) continue outer;
+                            if (i === 3) break outer;
+                            found += i * j;
+                        }
+                    }
+                    return found;
+                }
+                const value = "exported as a string";
+                export { value as "string name" };
+                export * from "./leaves.js";
+            "#}
+            .to_owned(),
+        ),
+    ];
+    let mut leaves = String::new();
+    for i in 0..16 {
+        files.push((
+            format!("lib/leaf{i}.js"),
+            format!("export const leaf{i} = {i};"),
+        ));
+        writeln!(leaves, "import {{ leaf{i} }} from './leaf{i}.js';").unwrap();
+    }
+    let sum = (0..16).map(|i| format!("leaf{i}")).collect::<Vec<_>>();
+    writeln!(leaves, "export const sum = {};", sum.join(" + ")).unwrap();
+    files.push(("lib/leaves.js".to_owned(), leaves));
+    let files: Vec<(&str, &str)> = files
+        .iter()
+        .map(|(path, contents)| (path.as_str(), contents.as_str()))
+        .collect();
+    let dir = ModuleDir::new("preload", &files);
+    let loader = Rc::new(SimpleModuleLoader::new(&dir.0).unwrap());
+    let context = &mut Context::builder()
+        .module_loader(loader.clone())
+        .build()
+        .unwrap();
+
+    // Imported modules that cannot be read are left to the normal loading.
+    let main = loader.preload(Path::new("main.js"), context).unwrap();
+    assert!(loader.get(Path::new("lib/leaf15.js")).is_some());
+    assert!(loader.get(Path::new("lib/missing.js")).is_none());
+    let promise = main.load_link_evaluate(context);
+    context.run_jobs();
+    assert!(matches!(promise.state(), PromiseState::Rejected(_)));
+
+    let syntax = loader.get(Path::new("lib/syntax.js"));
+    let app = loader.preload(Path::new("app.js"), context).unwrap();
+    assert_eq!(loader.get(Path::new("lib/syntax.js")), syntax);
+    assert_eq!(loader.preload(Path::new("app.js"), context).unwrap(), app);
+    let promise = app.load_link_evaluate(context);
+    context.run_jobs();
+    assert!(matches!(promise.state(), PromiseState::Fulfilled(_)));
+    run_test_actions_with(
+        [TestAction::assert_eq(
+            "result",
+            js_string!("1,true,a|b1,true,true,3,exported as a string,120,true"),
+        )],
+        context,
+    );
+
+    assert!(loader.preload(Path::new("missing.js"), context).is_err());
+    fs::write(dir.0.join("broken.js"), "export const = ;").unwrap();
+    assert!(loader.preload(Path::new("broken.js"), context).is_err());
+}
@@ -8,9 +8,26 @@
     rustdoc::missing_crate_level_docs
 )]
 
-use std::{error::Error, fs::File};
+use std::{
+    borrow::Cow,
+    collections::BTreeSet,
+    error::Error,
+    fs::File,
+    io::{BufWriter, Cursor},
+    path::PathBuf,
+    str::FromStr,
+    sync::Mutex,
+};
 
-use boa_icu_provider::data_root;
+use boa_icu_provider::{
+    data_root,
+    provider::{
+        CurrencyNamesV1, CurrencyNamesV1Marker, LongUnitsV1Marker, NarrowUnitsV1Marker,
+        NumberPatternsV1, NumberPatternsV1Marker, ShortUnitsV1Marker, SupportedCurrenciesV1,
+        SupportedCurrenciesV1Marker, UnitsV1, KEYS,
+    },
+};
+use icu_compactdecimal::provider::Count;
 use icu_datagen::{all_keys, CoverageLevel, DatagenDriver, DatagenProvider};
 use icu_plurals::provider::{PluralRangesV1, PluralRangesV1Marker};
 use icu_provider::{
@@ -19,68 +36,479 @@ use icu_provider::{
     prelude::*,
 };
 use icu_provider_blob::export::BlobExporter;
+use serde_json::Value;
+use tinystr::TinyAsciiStr;
+use zip::ZipArchive;
 
-/// Hack that associates the `und` locale with an empty plural ranges data.
-/// This enables the default behaviour for all locales without data.
-#[derive(Debug)]
-struct PluralRangesFallbackHack(DatagenProvider);
+/// Reader of the [CLDR JSON][cldr] files used to generate the data of the keys defined by Boa.
+///
+/// [cldr]: https://github.com/unicode-org/cldr-json
+struct Cldr(Mutex<ZipArchive<Cursor<Vec<u8>>>>);
 
-// We definitely don't want to import dependencies just to do `T::default`.
-#[allow(clippy::default_trait_access)]
-impl DynamicDataProvider<AnyMarker> for PluralRangesFallbackHack {
-    fn load_data(
-        &self,
-        key: DataKey,
-        req: DataRequest<'_>,
-    ) -> Result<DataResponse<AnyMarker>, DataError> {
-        if req.locale.is_und() && key.hashed() == PluralRangesV1Marker::KEY.hashed() {
-            let payload = <AnyMarker as UpcastDataPayload<PluralRangesV1Marker>>::upcast(
-                DataPayload::from_owned(PluralRangesV1 {
-                    ranges: Default::default(),
-                }),
-            );
-            Ok(DataResponse {
-                metadata: DataResponseMetadata::default(),
-                payload: Some(payload),
+impl Cldr {
+    /// Opens the archive of the CLDR version tested by `icu_datagen`, downloading it to the
+    /// source cache shared with `icu_datagen` if it isn't there yet.
+    fn new_latest_tested() -> Result<Self, Box<dyn Error>> {
+        let tag = DatagenProvider::LATEST_TESTED_CLDR_TAG;
+        let url = format!(
+            "https://github.com/unicode-org/cldr-json/releases/download/{tag}/cldr-{tag}-json-full.zip"
+        );
+        let path = std::env::var_os("ICU4X_SOURCE_CACHE")
+            .map_or_else(
+                || std::env::temp_dir().join("icu4x-source-cache"),
+                PathBuf::from,
+            )
+            .join(url.trim_start_matches("https://"));
+
+        if !path.exists() {
+            log::info!("Downloading {url}");
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::io::copy(
+                &mut ureq::get(&url).call()?.into_reader(),
+                &mut BufWriter::new(File::create(&path)?),
+            )?;
+        }
+
+        let archive = ZipArchive::new(Cursor::new(std::fs::read(&path)?))?;
+        Ok(Self(Mutex::new(archive)))
+    }
+
+    /// Gets the locales that have data in a CLDR package.
+    fn locales(&self, package: &str) -> Vec<DataLocale> {
+        let prefix = format!("{package}/main/");
+        let archive = self
+            .0
+            .lock()
+            .expect("the CLDR archive should not be poisoned");
+        archive
+            .file_names()
+            .filter_map(|name| name.strip_prefix(&prefix)?.split_once('/'))
+            .map(|(locale, _)| locale)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|locale| match locale {
+                "root" => Some(DataLocale::default()),
+                locale => DataLocale::from_str(locale).ok(),
             })
+            .collect()
+    }
+
+    /// Reads the data of a locale from a file of a CLDR package.
+    fn read(&self, package: &str, locale: &DataLocale, file: &str) -> Result<Value, DataError> {
+        let name = if locale.is_und() {
+            String::from("root")
         } else {
-            self.0.load_data(key, req)
+            locale.to_string()
+        };
+        let mut archive = self
+            .0
+            .lock()
+            .expect("the CLDR archive should not be poisoned");
+        let reader = archive
+            .by_name(&format!("{package}/main/{name}/{file}"))
+            .map_err(|_| DataErrorKind::MissingLocale.into_error())?;
+        let mut json: Value = serde_json::from_reader(reader)
+            .map_err(|e| DataError::custom("Invalid CLDR JSON").with_display_context(&e))?;
+        Ok(json["main"][&name].take())
+    }
+
+    /// Reads a supplemental data file of the CLDR core package.
+    fn read_supplemental(&self, file: &str) -> Result<Value, DataError> {
+        let mut archive = self
+            .0
+            .lock()
+            .expect("the CLDR archive should not be poisoned");
+        let reader = archive
+            .by_name(&format!("cldr-core/supplemental/{file}"))
+            .map_err(|e| DataError::custom("Missing CLDR file").with_display_context(&e))?;
+        let mut json: Value = serde_json::from_reader(reader)
+            .map_err(|e| DataError::custom("Invalid CLDR JSON").with_display_context(&e))?;
+        Ok(json["supplemental"].take())
+    }
+}
+
+/// Gets the string of a field of a CLDR JSON object.
+fn string<'a>(object: &'a Value, field: &str) -> Result<&'a str, DataError> {
+    object[field]
+        .as_str()
+        .ok_or_else(|| DataError::custom("Missing CLDR field").with_display_context(field))
+}
+
+/// Gets the plural category of a CLDR JSON field with the provided prefix, e.g.
+/// `unitPattern-count-one`.
+fn count(field: &str, prefix: &str) -> Option<Count> {
+    Some(match field.strip_prefix(prefix)? {
+        "zero" => Count::Zero,
+        "one" => Count::One,
+        "two" => Count::Two,
+        "few" => Count::Few,
+        "many" => Count::Many,
+        "other" => Count::Other,
+        "1" => Count::Explicit1,
+        _ => return None,
+    })
+}
+
+/// Provider of the data exported by `boa_datagen`.
+///
+/// Serves the keys defined by Boa from the CLDR JSON files, and forwards everything else to
+/// `icu_datagen`, except for the plural ranges of `und`, which are associated with an empty
+/// data. This enables the default behaviour for all locales without plural ranges data.
+struct BoaDatagenProvider {
+    icu4x: DatagenProvider,
+    cldr: Cldr,
+}
+
+impl BoaDatagenProvider {
+    fn number_patterns(&self, locale: &DataLocale) -> Result<NumberPatternsV1<'static>, DataError> {
+        let json = self
+            .cldr
+            .read("cldr-numbers-full", locale, "numbers.json")?;
+        let numbers = &json["numbers"];
+        let nu = string(numbers, "defaultNumberingSystem")?;
+        let system = |name: &str| {
+            let data = &numbers[format!("{name}-numberSystem-{nu}").as_str()];
+            if data.is_null() {
+                &numbers[format!("{name}-numberSystem-latn").as_str()]
+            } else {
+                data
+            }
+        };
+        let symbols = system("symbols");
+        let currency = system("currencyFormats");
+        let owned = |object, field| string(object, field).map(|s| Cow::Owned(s.to_owned()));
+
+        Ok(NumberPatternsV1 {
+            nan: owned(symbols, "nan")?,
+            infinity: owned(symbols, "infinity")?,
+            exponential: owned(symbols, "exponential")?,
+            approximately_sign: owned(symbols, "approximatelySign")?,
+            percent_sign: owned(symbols, "percentSign")?,
+            percent_pattern: owned(system("percentFormats"), "standard")?,
+            currency_pattern: owned(currency, "standard")?,
+            accounting_pattern: owned(currency, "accounting")?,
+            currency_name_patterns: currency
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter_map(|(field, pattern)| {
+                    Some((count(field, "unitPattern-count-")?, pattern.as_str()?))
+                })
+                .collect(),
+        })
+    }
+
+    fn currency_names(&self, locale: &DataLocale) -> Result<CurrencyNamesV1<'static>, DataError> {
+        let json = self
+            .cldr
+            .read("cldr-numbers-full", locale, "currencies.json")?;
+
+        let mut symbols = Vec::new();
+        let mut narrow_symbols = Vec::new();
+        let mut names = Vec::new();
+        let mut plural_names = Vec::new();
+        for (code, currency) in json["numbers"]["currencies"]
+            .as_object()
+            .into_iter()
+            .flatten()
+        {
+            let Ok(code) = TinyAsciiStr::<3>::from_str(code) else {
+                continue;
+            };
+            let code = code.to_unvalidated();
+            for (field, value) in currency.as_object().into_iter().flatten() {
+                let Some(value) = value.as_str() else {
+                    continue;
+                };
+                match field.as_str() {
+                    "symbol" => symbols.push((code, value)),
+                    "symbol-alt-narrow" => narrow_symbols.push((code, value)),
+                    "displayName" => names.push((code, value)),
+                    field => {
+                        if let Some(count) = count(field, "displayName-count-") {
+                            plural_names.push((code, count, value));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(CurrencyNamesV1 {
+            symbols: symbols.into_iter().collect(),
+            narrow_symbols: narrow_symbols.into_iter().collect(),
+            names: names.into_iter().collect(),
+            plural_names: plural_names.into_iter().collect(),
+        })
+    }
+
+    fn supported_currencies(&self) -> Result<SupportedCurrenciesV1<'static>, DataError> {
+        let json = self.cldr.read_supplemental("currencyData.json")?;
+        let codes = json["currencyData"]["region"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .flat_map(|(_, currencies)| currencies.as_array().into_iter().flatten())
+            .flat_map(|currencies| currencies.as_object().into_iter().flatten())
+            // Currencies with an end date are no longer in use in the region.
+            .filter(|(_, currency)| currency["_to"].is_null())
+            .filter_map(|(code, _)| TinyAsciiStr::<3>::from_str(code).ok())
+            .collect::<BTreeSet<_>>();
+
+        Ok(SupportedCurrenciesV1 {
+            codes: codes
+                .into_iter()
+                .map(TinyAsciiStr::to_unvalidated)
+                .collect(),
+        })
+    }
+
+    fn units(&self, locale: &DataLocale, display: &str) -> Result<UnitsV1<'static>, DataError> {
+        let json = self.cldr.read("cldr-units-full", locale, "units.json")?;
+        let units = &json["units"][display];
+
+        let mut patterns = Vec::new();
+        let mut per_unit_patterns = Vec::new();
+        for (key, unit) in units.as_object().into_iter().flatten() {
+            // Unit keys are prefixed by their category, e.g. `length-kilometer`.
+            let Some((_, id)) = key.split_once('-') else {
+                continue;
+            };
+            for (field, pattern) in unit.as_object().into_iter().flatten() {
+                let Some(pattern) = pattern.as_str() else {
+                    continue;
+                };
+                if let Some(count) = count(field, "unitPattern-count-") {
+                    patterns.push((id, count, pattern));
+                } else if field == "perUnitPattern" {
+                    per_unit_patterns.push((id, pattern));
+                }
+            }
         }
+
+        Ok(UnitsV1 {
+            patterns: patterns.into_iter().collect(),
+            per_unit_patterns: per_unit_patterns.into_iter().collect(),
+            compound_pattern: Cow::Owned(string(&units["per"], "compoundUnitPattern")?.to_owned()),
+        })
     }
 }
 
+// We definitely don't want to import dependencies just to do `T::default`.
 #[allow(clippy::default_trait_access)]
-impl DynamicDataProvider<ExportMarker> for PluralRangesFallbackHack {
-    fn load_data(
-        &self,
-        key: DataKey,
-        req: DataRequest<'_>,
-    ) -> Result<DataResponse<ExportMarker>, DataError> {
-        if req.locale.is_und() && key.hashed() == PluralRangesV1Marker::KEY.hashed() {
-            let payload = <ExportMarker as UpcastDataPayload<PluralRangesV1Marker>>::upcast(
-                DataPayload::from_owned(PluralRangesV1 {
-                    ranges: Default::default(),
-                }),
-            );
-            Ok(DataResponse {
+impl<M> DynamicDataProvider<M> for BoaDatagenProvider
+where
+    M: DataMarker
+        + UpcastDataPayload<PluralRangesV1Marker>
+        + UpcastDataPayload<NumberPatternsV1Marker>
+        + UpcastDataPayload<CurrencyNamesV1Marker>
+        + UpcastDataPayload<SupportedCurrenciesV1Marker>
+        + UpcastDataPayload<LongUnitsV1Marker>
+        + UpcastDataPayload<ShortUnitsV1Marker>
+        + UpcastDataPayload<NarrowUnitsV1Marker>,
+    DatagenProvider: DynamicDataProvider<M>,
+{
+    fn load_data(&self, key: DataKey, req: DataRequest<'_>) -> Result<DataResponse<M>, DataError> {
+        fn upcast<M: UpcastDataPayload<P>, P: DataMarker>(data: P::Yokeable) -> DataResponse<M> {
+            DataResponse {
                 metadata: DataResponseMetadata::default(),
-                payload: Some(payload),
-            })
-        } else {
-            self.0.load_data(key, req)
+                payload: Some(M::upcast(DataPayload::from_owned(data))),
+            }
         }
+
+        let locale = req.locale;
+        let response = match key.hashed() {
+            hash if hash == NumberPatternsV1Marker::KEY.hashed() => {
+                upcast::<M, NumberPatternsV1Marker>(self.number_patterns(locale)?)
+            }
+            hash if hash == CurrencyNamesV1Marker::KEY.hashed() => {
+                upcast::<M, CurrencyNamesV1Marker>(self.currency_names(locale)?)
+            }
+            hash if hash == SupportedCurrenciesV1Marker::KEY.hashed() => {
+                upcast::<M, SupportedCurrenciesV1Marker>(self.supported_currencies()?)
+            }
+            hash if hash == LongUnitsV1Marker::KEY.hashed() => {
+                upcast::<M, LongUnitsV1Marker>(self.units(locale, "long")?)
+            }
+            hash if hash == ShortUnitsV1Marker::KEY.hashed() => {
+                upcast::<M, ShortUnitsV1Marker>(self.units(locale, "short")?)
+            }
+            hash if hash == NarrowUnitsV1Marker::KEY.hashed() => {
+                upcast::<M, NarrowUnitsV1Marker>(self.units(locale, "narrow")?)
+            }
+            hash if hash == PluralRangesV1Marker::KEY.hashed() && locale.is_und() => {
+                upcast::<M, PluralRangesV1Marker>(PluralRangesV1 {
+                    ranges: Default::default(),
+                })
+            }
+            _ => return self.icu4x.load_data(key, req),
+        };
+        Ok(response)
     }
 }
 
-impl IterableDynamicDataProvider<ExportMarker> for PluralRangesFallbackHack {
+impl IterableDynamicDataProvider<ExportMarker> for BoaDatagenProvider {
     fn supported_locales_for_key(&self, key: DataKey) -> Result<Vec<DataLocale>, DataError> {
-        if key.hashed() == PluralRangesV1Marker::KEY.hashed() {
-            let mut locales = self.0.supported_locales_for_key(key)?;
+        if key.hashed() == SupportedCurrenciesV1Marker::KEY.hashed() {
+            Ok(vec![DataLocale::default()])
+        } else if key.path().get().starts_with("boa/units/") {
+            Ok(self.cldr.locales("cldr-units-full"))
+        } else if key.path().get().starts_with("boa/") {
+            Ok(self.cldr.locales("cldr-numbers-full"))
+        } else if key.hashed() == PluralRangesV1Marker::KEY.hashed() {
+            let mut locales = self.icu4x.supported_locales_for_key(key)?;
             locales.push(DataLocale::default());
             Ok(locales)
         } else {
-            self.0.supported_locales_for_key(key)
+            self.icu4x.supported_locales_for_key(key)
+        }
+    }
+}
+
+/// Data key prefixes required by each `Intl` service.
+///
+/// The `base` service contains the data required to build a `Context`, and is always included.
+const SERVICES: &[(&str, &[&str])] = &[
+    (
+        "base",
+        &[
+            "locid_transform/",
+            "fallback/",
+            "normalizer/",
+            "props/casemap",
+            "boa/currencies/supported",
+        ],
+    ),
+    ("collator", &["collator/"]),
+    (
+        "datetimeformat",
+        &[
+            "datetime/",
+            "calendar/",
+            "time_zone/",
+            "decimal/",
+            "plurals/",
+        ],
+    ),
+    ("displaynames", &["displaynames/", "boa/currencies"]),
+    (
+        "durationformat",
+        &["decimal/", "boa/numbers", "plurals/", "list/"],
+    ),
+    ("listformat", &["list/"]),
+    (
+        "locale",
+        &[
+            "collator/meta",
+            "decimal/",
+            "datetime/timelengths",
+            "datetime/week_data",
+            "calendar/",
+            "time_zone/",
+        ],
+    ),
+    (
+        "numberformat",
+        &[
+            "decimal/",
+            "compactdecimal/",
+            "boa/numbers",
+            "boa/currencies",
+            "boa/units/",
+            "plurals/",
+        ],
+    ),
+    ("pluralrules", &["plurals/"]),
+    (
+        "relativetimeformat",
+        &["relativetime/", "decimal/", "boa/numbers", "plurals/"],
+    ),
+    ("segmenter", &["segmenter/"]),
+];
+
+/// Options of the data generator, parsed from the command line arguments.
+struct Options {
+    locales: Option<Vec<DataLocale>>,
+    services: Option<Vec<String>>,
+    out: PathBuf,
+}
+
+impl Options {
+    const USAGE: &'static str =
+        "usage: boa_datagen [--locales <list>] [--services <list>] [--out <path>]
+
+    --locales <list>     Comma separated list of locales to include [default: modern CLDR locales]
+    --services <list>    Comma separated list of `Intl` services to include [default: all]
+    --out <path>         Path of the generated blob [default: data/icudata.postcard]";
+
+    fn parse() -> Result<Self, Box<dyn Error>> {
+        let mut options = Self {
+            locales: None,
+            services: None,
+            out: data_root().join("icudata.postcard"),
+        };
+
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for `{arg}`\n\n{}", Self::USAGE))
+            };
+            match arg.as_str() {
+                "--locales" => {
+                    options.locales = Some(
+                        value()?
+                            .split(',')
+                            .map(DataLocale::from_str)
+                            .collect::<Result<_, _>>()?,
+                    );
+                }
+                "--services" => {
+                    let services = value()?;
+                    let services = services
+                        .split(',')
+                        .map(|service| {
+                            let service = service.to_ascii_lowercase();
+                            if SERVICES.iter().any(|(name, _)| *name == service) {
+                                Ok(service)
+                            } else {
+                                Err(format!("unknown service `{service}`"))
+                            }
+                        })
+                        .collect::<Result<_, _>>()?;
+                    options.services = Some(services);
+                }
+                "--out" => options.out = PathBuf::from(value()?),
+                "-h" | "--help" => {
+                    println!("{}", Self::USAGE);
+                    std::process::exit(0);
+                }
+                _ => return Err(format!("unknown argument `{arg}`\n\n{}", Self::USAGE).into()),
+            }
         }
+
+        Ok(options)
+    }
+
+    /// Gets the set of data keys required by the selected services.
+    fn keys(&self) -> Vec<DataKey> {
+        let keys = all_keys().into_iter().chain(KEYS.iter().copied());
+        let Some(services) = &self.services else {
+            return keys.collect();
+        };
+
+        let prefixes = SERVICES
+            .iter()
+            .filter(|(name, _)| *name == "base" || services.iter().any(|s| s == name))
+            .flat_map(|(_, prefixes)| prefixes.iter())
+            .collect::<Vec<_>>();
+
+        keys.filter(|key| {
+            let path = key.path().get();
+            prefixes.iter().any(|prefix| path.starts_with(*prefix))
+        })
+        .collect()
     }
 }
 
@@ -90,17 +518,33 @@ fn main() -> Result<(), Box<dyn Error>> {
         .with_level(log::LevelFilter::Info)
         .init()?;
 
-    let provider = DatagenProvider::new_latest_tested();
+    let options = Options::parse()?;
+
+    let provider = BoaDatagenProvider {
+        icu4x: DatagenProvider::new_latest_tested(),
+        cldr: Cldr::new_latest_tested()?,
+    };
+
+    let locales = if let Some(locales) = &options.locales {
+        locales
+            .iter()
+            .map(DataLocale::get_langid)
+            .collect::<Vec<_>>()
+    } else {
+        provider
+            .icu4x
+            .locales_for_coverage_levels([CoverageLevel::Modern])?
+            .into_iter()
+            .collect()
+    };
 
     DatagenDriver::new()
-        .with_keys(all_keys())
-        .with_locales(provider.locales_for_coverage_levels([CoverageLevel::Modern])?)
+        .with_keys(options.keys())
+        .with_locales(locales)
         .with_additional_collations([String::from("search*")])
         .export(
-            &PluralRangesFallbackHack(provider),
-            BlobExporter::new_with_sink(Box::new(File::create(
-                data_root().join("icudata.postcard"),
-            )?)),
+            &provider,
+            BlobExporter::new_with_sink(Box::new(File::create(&options.out)?)),
         )?;
 
     Ok(())
@@ -22,6 +22,10 @@
 #![cfg_attr(not(feature = "bin"), no_std)]
 #![allow(unused_crate_dependencies)]
 
+extern crate alloc;
+
+pub mod provider;
+
 /// Gets the path to the directory where the generated data is stored.
 #[cfg(feature = "bin")]
 #[must_use]
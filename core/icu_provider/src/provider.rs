@@ -0,0 +1,146 @@
+//! Data structs of the locale data used by Boa that is not provided by ICU4X yet.
+//!
+//! The data is generated by `boa_datagen` from the [CLDR JSON][cldr] files, and is exported
+//! along with the data of ICU4X, so it can be loaded from the same data provider.
+//!
+//! [cldr]: https://github.com/unicode-org/cldr-json
+
+// Provider structs must be stable.
+#![allow(clippy::exhaustive_structs)]
+// The lifetimes of the provider structs are only used by their `serde` borrows.
+#![allow(single_use_lifetimes)]
+
+use alloc::borrow::Cow;
+use icu_compactdecimal::provider::Count;
+use icu_provider::prelude::*;
+use tinystr::UnvalidatedTinyAsciiStr;
+use zerovec::{ZeroMap, ZeroMap2d, ZeroVec};
+
+/// The data keys defined by Boa.
+#[cfg(feature = "datagen")]
+pub const KEYS: &[DataKey] = &[
+    NumberPatternsV1Marker::KEY,
+    CurrencyNamesV1Marker::KEY,
+    SupportedCurrenciesV1Marker::KEY,
+    LongUnitsV1Marker::KEY,
+    ShortUnitsV1Marker::KEY,
+    NarrowUnitsV1Marker::KEY,
+];
+
+/// The symbols and patterns used to format numbers in a locale, in addition to the ones of
+/// [`DecimalSymbolsV1`][symbols].
+///
+/// The patterns use the syntax of [CLDR number patterns][patterns], with an optional negative
+/// subpattern after a `;`.
+///
+/// [symbols]: https://docs.rs/icu_decimal/latest/icu_decimal/provider/struct.DecimalSymbolsV1.html
+/// [patterns]: https://www.unicode.org/reports/tr35/tr35-numbers.html#Number_Format_Patterns
+#[icu_provider::data_struct(marker(NumberPatternsV1Marker, "boa/numbers@1"))]
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[cfg_attr(
+    feature = "datagen",
+    derive(serde::Serialize, databake::Bake),
+    databake(path = boa_icu_provider::provider)
+)]
+#[yoke(prove_covariance_manually)]
+pub struct NumberPatternsV1<'data> {
+    /// The string used to display a value that is not a number, e.g. `NaN`.
+    #[serde(borrow)]
+    pub nan: Cow<'data, str>,
+    /// The string used to display an infinite value, e.g. `∞`.
+    #[serde(borrow)]
+    pub infinity: Cow<'data, str>,
+    /// The separator of the exponent in the scientific notation, e.g. `E`.
+    #[serde(borrow)]
+    pub exponential: Cow<'data, str>,
+    /// The sign displayed before approximate values, e.g. `~`.
+    #[serde(borrow)]
+    pub approximately_sign: Cow<'data, str>,
+    /// The sign displayed by percentages, e.g. `%`.
+    #[serde(borrow)]
+    pub percent_sign: Cow<'data, str>,
+    /// The pattern used to display percentages, e.g. `#,##0%`.
+    #[serde(borrow)]
+    pub percent_pattern: Cow<'data, str>,
+    /// The pattern used to display currency amounts, e.g. `¤#,##0.00`.
+    #[serde(borrow)]
+    pub currency_pattern: Cow<'data, str>,
+    /// The pattern used to display currency amounts in accounting, e.g. `¤#,##0.00;(¤#,##0.00)`.
+    #[serde(borrow)]
+    pub accounting_pattern: Cow<'data, str>,
+    /// The patterns used to combine a number (`{0}`) with the name of a currency (`{1}`),
+    /// keyed by plural category.
+    #[serde(borrow)]
+    pub currency_name_patterns: ZeroMap<'data, Count, str>,
+}
+
+/// The display names and symbols of the currencies in a locale, keyed by ISO 4217 code.
+#[icu_provider::data_struct(marker(CurrencyNamesV1Marker, "boa/currencies@1"))]
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[cfg_attr(
+    feature = "datagen",
+    derive(serde::Serialize, databake::Bake),
+    databake(path = boa_icu_provider::provider)
+)]
+#[yoke(prove_covariance_manually)]
+pub struct CurrencyNamesV1<'data> {
+    /// The symbols of the currencies, e.g. `US$`.
+    #[serde(borrow)]
+    pub symbols: ZeroMap<'data, UnvalidatedTinyAsciiStr<3>, str>,
+    /// The narrow symbols of the currencies, e.g. `$`.
+    #[serde(borrow)]
+    pub narrow_symbols: ZeroMap<'data, UnvalidatedTinyAsciiStr<3>, str>,
+    /// The standalone display names of the currencies, e.g. `US Dollar`.
+    #[serde(borrow)]
+    pub names: ZeroMap<'data, UnvalidatedTinyAsciiStr<3>, str>,
+    /// The display names of the currencies used after a number, keyed by plural category,
+    /// e.g. `US dollars`.
+    #[serde(borrow)]
+    pub plural_names: ZeroMap2d<'data, UnvalidatedTinyAsciiStr<3>, Count, str>,
+}
+
+/// The currencies that are in use in at least one region, sorted by ISO 4217 code.
+#[icu_provider::data_struct(marker(
+    SupportedCurrenciesV1Marker,
+    "boa/currencies/supported@1",
+    singleton
+))]
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[cfg_attr(
+    feature = "datagen",
+    derive(serde::Serialize, databake::Bake),
+    databake(path = boa_icu_provider::provider)
+)]
+pub struct SupportedCurrenciesV1<'data> {
+    /// The ISO 4217 codes of the currencies, e.g. `USD`.
+    #[serde(borrow)]
+    pub codes: ZeroVec<'data, UnvalidatedTinyAsciiStr<3>>,
+}
+
+/// The display patterns of the units in a locale for one of the unit displays.
+#[icu_provider::data_struct(
+    marker(LongUnitsV1Marker, "boa/units/long@1"),
+    marker(ShortUnitsV1Marker, "boa/units/short@1"),
+    marker(NarrowUnitsV1Marker, "boa/units/narrow@1")
+)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[cfg_attr(
+    feature = "datagen",
+    derive(serde::Serialize, databake::Bake),
+    databake(path = boa_icu_provider::provider)
+)]
+#[yoke(prove_covariance_manually)]
+pub struct UnitsV1<'data> {
+    /// The patterns used to combine a number (`{0}`) with a unit, keyed by the identifier of
+    /// the unit and the plural category, e.g. `{0} kilometers`.
+    #[serde(borrow)]
+    pub patterns: ZeroMap2d<'data, str, Count, str>,
+    /// The patterns used when a unit is the denominator of a compound unit, keyed by the
+    /// identifier of the unit, e.g. `{0} per kilometer`.
+    #[serde(borrow)]
+    pub per_unit_patterns: ZeroMap<'data, str, str>,
+    /// The pattern used to combine the numerator (`{0}`) and the denominator (`{1}`) of a
+    /// compound unit, e.g. `{0} per {1}`.
+    #[serde(borrow)]
+    pub compound_pattern: Cow<'data, str>,
+}
@@ -119,6 +119,14 @@ impl<'path, R: Read> Source<'path, UTF8Input<R>> {
     }
 }
 
+impl<'path, R> Source<'path, R> {
+    /// Gets the path of the file containing the source code, if any.
+    #[must_use]
+    pub const fn path(&self) -> Option<&'path Path> {
+        self.path
+    }
+}
+
 /// This trait is used to abstract over the different types of input readers.
 pub trait ReadChar {
     /// Retrieves the next unicode code point. Returns `None` if the end of the input is reached.
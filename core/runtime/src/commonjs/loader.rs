@@ -0,0 +1,143 @@
+//! A module loader importing CommonJS modules into ECMAScript modules.
+
+use super::{resolve, CommonJs};
+use boa_engine::{
+    js_string,
+    module::{ModuleLoader, PreloadModuleLoader, Referrer},
+    object::JsObject,
+    Context, JsError, JsNativeError, JsResult, JsString, Module,
+};
+use boa_gc::GcRefCell;
+use rustc_hash::FxHashMap;
+use std::path::{Path, PathBuf};
+
+/// A [`ModuleLoader`] that loads CommonJS modules as synthetic modules, and delegates all the
+/// other modules to an inner loader.
+///
+/// Specifiers are resolved with the resolution algorithm of `require` against the directory of
+/// the importing module, or against `root` if the importer has no path, so bare specifiers can
+/// import the packages of the `node_modules` directories. The module is loaded
+/// as a CommonJS module if it is a `.cjs` file, or a `.js` file whose nearest `package.json`
+/// doesn't declare a `"type": "module"`. Its `module.exports` is the default export of the
+/// synthetic module, and its own properties are the named exports.
+///
+/// ```no_run
+/// use boa_engine::{context::ContextBuilder, module::SimpleModuleLoader};
+/// use boa_runtime::CommonJsModuleLoader;
+/// use std::rc::Rc;
+///
+/// let loader = CommonJsModuleLoader::new(SimpleModuleLoader::new("scripts")?, "scripts")?;
+/// let context = ContextBuilder::new()
+///     .module_loader(Rc::new(loader))
+///     .build()?;
+/// # Ok::<(), boa_engine::JsError>(())
+/// ```
+#[derive(Debug)]
+pub struct CommonJsModuleLoader<L> {
+    inner: L,
+    root: PathBuf,
+    modules: GcRefCell<FxHashMap<PathBuf, Module>>,
+}
+
+impl<L: ModuleLoader> CommonJsModuleLoader<L> {
+    /// Creates a new `CommonJsModuleLoader` resolving specifiers against `root`, and
+    /// delegating the ECMAScript modules to `inner`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `root` cannot be canonicalized.
+    pub fn new<P: AsRef<Path>>(inner: L, root: P) -> JsResult<Self> {
+        let root = root.as_ref();
+        let absolute = root.canonicalize().map_err(|e| {
+            JsNativeError::typ()
+                .with_message(format!("could not set module root `{}`", root.display()))
+                .with_cause(JsError::from_opaque(js_string!(e.to_string()).into()))
+        })?;
+        Ok(Self {
+            inner,
+            root: absolute,
+            modules: GcRefCell::default(),
+        })
+    }
+
+    /// Gets the loader of the ECMAScript modules.
+    pub const fn inner(&self) -> &L {
+        &self.inner
+    }
+
+    /// Returns the synthetic module of the CommonJS module `specifier` imported by `referrer`,
+    /// or `None` if it isn't a CommonJS module.
+    fn load_commonjs(
+        &self,
+        referrer: &Referrer,
+        specifier: &JsString,
+        context: &mut Context,
+    ) -> JsResult<Option<Module>> {
+        let dir = referrer
+            .path()
+            .and_then(|path| self.root.join(path).parent().map(Path::to_path_buf))
+            .unwrap_or_else(|| self.root.clone());
+        let Ok(path) = resolve::resolve(&specifier.to_std_string_escaped(), &dir, context) else {
+            return Ok(None);
+        };
+        if let Some(module) = self.modules.borrow().get(&path) {
+            return Ok(Some(module.clone()));
+        }
+        if !resolve::is_commonjs(&path, context)? {
+            return Ok(None);
+        }
+        let module = CommonJs::synthetic_module(&path, context)?;
+        self.modules.borrow_mut().insert(path, module.clone());
+        Ok(Some(module))
+    }
+}
+
+impl<L: ModuleLoader> ModuleLoader for CommonJsModuleLoader<L> {
+    fn load_imported_module(
+        &self,
+        referrer: Referrer,
+        specifier: JsString,
+        finish_load: Box<dyn FnOnce(JsResult<Module>, &mut Context)>,
+        context: &mut Context,
+    ) {
+        match self.load_commonjs(&referrer, &specifier, context) {
+            Ok(Some(module)) => finish_load(Ok(module), context),
+            Ok(None) => self
+                .inner
+                .load_imported_module(referrer, specifier, finish_load, context),
+            Err(e) => finish_load(Err(e), context),
+        }
+    }
+
+    fn register_module(&self, specifier: JsString, module: Module) {
+        self.inner.register_module(specifier, module);
+    }
+
+    fn get_module(&self, specifier: JsString) -> Option<Module> {
+        self.inner.get_module(specifier)
+    }
+
+    fn init_import_meta(&self, import_meta: &JsObject, module: &Module, context: &mut Context) {
+        self.inner.init_import_meta(import_meta, module, context);
+    }
+}
+
+impl<L: PreloadModuleLoader> PreloadModuleLoader for CommonJsModuleLoader<L> {
+    /// Preloads the module at `path` with the inner loader, skipping the imported CommonJS
+    /// modules, which are evaluated as synthetic modules when they are loaded.
+    fn preload_with(
+        &self,
+        path: &Path,
+        resolve: &mut dyn FnMut(&Path, &str, &mut Context) -> Option<PathBuf>,
+        context: &mut Context,
+    ) -> JsResult<Module> {
+        self.inner.preload_with(
+            path,
+            &mut |referrer, specifier, context| {
+                let path = resolve(referrer, specifier, context)?;
+                (!resolve::is_commonjs(&path, context).unwrap_or(true)).then_some(path)
+            },
+            context,
+        )
+    }
+}
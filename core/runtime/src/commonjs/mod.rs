@@ -0,0 +1,295 @@
+//! Boa's implementation of CommonJS modules: the `require` function, the `module` and
+//! `exports` objects, and the `__filename` and `__dirname` variables.
+//!
+//! Each CommonJS module is wrapped in a function taking those five arguments, which is called
+//! the first time the module is required. The `module` objects are cached by their canonical
+//! path in `require.cache`, which is shared by all the modules of a realm. Removing a module
+//! from the cache makes the next `require` load it again.
+//!
+//! Circular dependencies follow the semantics of Node.js: requiring a module that is still
+//! being loaded returns its `exports` object as it is at that point.
+//!
+//! ECMAScript modules can also import CommonJS modules through the
+//! [`CommonJsModuleLoader`], which wraps their `module.exports` in a synthetic module.
+//!
+//! More information:
+//!  - [Node.js documentation][node]
+//!
+//! [node]: https://nodejs.org/api/modules.html
+
+#[cfg(test)]
+mod tests;
+
+mod loader;
+mod resolve;
+
+#[doc(inline)]
+pub use loader::CommonJsModuleLoader;
+
+pub(crate) use resolve::read_package_json;
+
+use crate::Permissions;
+use boa_engine::{
+    js_string,
+    module::{Module, SyntheticModuleInitializer},
+    native_function::NativeFunction,
+    object::{FunctionObjectBuilder, JsObject},
+    property::{Attribute, PropertyKey},
+    Context, JsArgs, JsData, JsError, JsNativeError, JsResult, JsString, JsValue, Source,
+};
+use boa_gc::{Finalize, Trace};
+use std::path::{Path, PathBuf};
+
+/// The cache of the CommonJS modules of a realm, stored in its host defined data.
+#[derive(Trace, Finalize, JsData)]
+struct RequireCache(JsObject);
+
+/// The CommonJS module system.
+#[derive(Debug, Clone, Copy)]
+pub struct CommonJs;
+
+impl CommonJs {
+    /// Registers a global `require` function in the context, which resolves relative
+    /// specifiers against the directory `base`.
+    ///
+    /// # Errors
+    ///
+    /// This will return an error if `base` cannot be canonicalized, or if the function cannot
+    /// be defined in the global object.
+    pub fn register<P: AsRef<Path>>(base: P, context: &mut Context) -> JsResult<()> {
+        let base = base.as_ref();
+        let base = base.canonicalize().map_err(|e| {
+            JsNativeError::typ()
+                .with_message(format!(
+                    "could not set the require base `{}`",
+                    base.display()
+                ))
+                .with_cause(JsError::from_opaque(js_string!(e.to_string()).into()))
+        })?;
+        let require = Self::require_function(&base, context);
+        context.register_global_property(js_string!("require"), require, Attribute::all())
+    }
+
+    /// Requires the module `specifier` from the directory `dir`, returning its
+    /// `module.exports`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module cannot be found, or if loading it throws.
+    pub fn require(specifier: &str, dir: &Path, context: &mut Context) -> JsResult<JsValue> {
+        let path = resolve::resolve(specifier, dir, context)?;
+        Self::require_path(&path, context)
+    }
+
+    /// Creates a synthetic module importing the CommonJS module at `path`, which exports its
+    /// `module.exports` as the default export, along with each of its own properties.
+    ///
+    /// The CommonJS module is evaluated when the synthetic module is created, since its named
+    /// exports are only known once it has run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module cannot be found, or if loading it throws.
+    pub fn synthetic_module(path: &Path, context: &mut Context) -> JsResult<Module> {
+        let path = path.canonicalize().map_err(|e| {
+            JsNativeError::error()
+                .with_message(format!("cannot find module '{}'", path.display()))
+                .with_cause(JsError::from_opaque(js_string!(e.to_string()).into()))
+        })?;
+        let exports = Self::require_path(&path, context)?;
+
+        let mut names = vec![js_string!("default")];
+        if let Some(object) = exports.as_object() {
+            for key in object.own_property_keys(context)? {
+                if let PropertyKey::String(name) = key {
+                    if name != js_string!("default") {
+                        names.push(name);
+                    }
+                }
+            }
+        }
+
+        let initializer = SyntheticModuleInitializer::from_copy_closure_with_captures(
+            |module, (exports, names), context| {
+                module.set_export(&js_string!("default"), exports.clone())?;
+                if let Some(object) = exports.as_object() {
+                    for name in names.iter().skip(1) {
+                        module.set_export(name, object.get(name.clone(), context)?)?;
+                    }
+                }
+                Ok(())
+            },
+            (exports, names.clone()),
+        );
+        Ok(Module::synthetic(&names, initializer, None, context))
+    }
+
+    /// Loads the module at the canonical path `path`, unless it is already cached, returning
+    /// its `module.exports`.
+    fn require_path(path: &Path, context: &mut Context) -> JsResult<JsValue> {
+        let cache = Self::cache(context);
+        let key = path_to_string(path);
+        if let Some(module) = cache.get(key.clone(), context)?.as_object() {
+            return module.get(js_string!("exports"), context);
+        }
+
+        let module = Self::create_module(path, context)?;
+        cache.create_data_property_or_throw(key.clone(), module.clone(), context)?;
+        if let Err(e) = Self::load(&module, path, context) {
+            cache.delete_property_or_throw(key, context)?;
+            return Err(e);
+        }
+        module.set(js_string!("loaded"), true, true, context)?;
+        module.get(js_string!("exports"), context)
+    }
+
+    /// Returns the `require.cache` object of the realm, creating it if needed.
+    fn cache(context: &mut Context) -> JsObject {
+        if let Some(cache) = context.realm().host_defined().get::<RequireCache>() {
+            return cache.0.clone();
+        }
+        let cache = JsObject::with_null_proto();
+        context
+            .realm()
+            .host_defined_mut()
+            .insert(RequireCache(cache.clone()));
+        cache
+    }
+
+    /// Creates the `module` object of the module at `path`.
+    fn create_module(path: &Path, context: &mut Context) -> JsResult<JsObject> {
+        let dir = path.parent().unwrap_or(path);
+        let filename = path_to_string(path);
+        let module = JsObject::with_object_proto(context.intrinsics());
+        let exports = JsObject::with_object_proto(context.intrinsics());
+        let require = Self::require_function(dir, context);
+        let properties: [(JsString, JsValue); 6] = [
+            (js_string!("id"), filename.clone().into()),
+            (js_string!("filename"), filename.into()),
+            (js_string!("path"), path_to_string(dir).into()),
+            (js_string!("exports"), exports.into()),
+            (js_string!("loaded"), false.into()),
+            (js_string!("require"), require.into()),
+        ];
+        for (key, value) in properties {
+            module.create_data_property_or_throw(key, value, context)?;
+        }
+        Ok(module)
+    }
+
+    /// Runs the module at `path`, filling its `module` object.
+    fn load(module: &JsObject, path: &Path, context: &mut Context) -> JsResult<()> {
+        Permissions::check_read(path, context)?;
+        let source = std::fs::read_to_string(path).map_err(|e| {
+            JsNativeError::error()
+                .with_message(format!("could not read module `{}`", path.display()))
+                .with_cause(JsError::from_opaque(js_string!(e.to_string()).into()))
+        })?;
+
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            let exports = parse_json(&source, context)?;
+            module.set(js_string!("exports"), exports, true, context)?;
+            return Ok(());
+        }
+
+        // A hashbang line is only allowed at the start of a script, so it is removed from the
+        // body of the wrapper, keeping the line break to keep the line numbers.
+        let source = match source.strip_prefix("#!") {
+            Some(rest) => rest.find('\n').map_or("", |end| &rest[end..]),
+            None => &source,
+        };
+
+        // The wrapper starts on the first line of the module, to keep its line numbers.
+        let wrapped =
+            format!("(function (exports, require, module, __filename, __dirname) {{ {source}\n}})");
+        let function = context.eval(Source::from_reader(wrapped.as_bytes(), Some(path)))?;
+        let function = function.as_callable().ok_or_else(|| {
+            JsNativeError::syntax()
+                .with_message(format!("could not wrap module `{}`", path.display()))
+        })?;
+        let exports = module.get(js_string!("exports"), context)?;
+        let args = [
+            exports.clone(),
+            module.get(js_string!("require"), context)?,
+            module.clone().into(),
+            module.get(js_string!("filename"), context)?,
+            module.get(js_string!("path"), context)?,
+        ];
+        function.call(&exports, &args, context)?;
+        Ok(())
+    }
+
+    /// Creates a `require` function resolving specifiers against the directory `dir`, with its
+    /// `resolve` method and its `cache` property.
+    fn require_function(dir: &Path, context: &mut Context) -> JsObject {
+        let dir = path_to_string(dir);
+        let resolve = FunctionObjectBuilder::new(
+            context.realm(),
+            NativeFunction::from_copy_closure_with_captures(
+                |_, args, dir, context| {
+                    let specifier = args.get_or_undefined(0).to_string(context)?;
+                    let path = resolve::resolve(
+                        &specifier.to_std_string_escaped(),
+                        &string_to_path(dir),
+                        context,
+                    )?;
+                    Ok(path_to_string(&path).into())
+                },
+                dir.clone(),
+            ),
+        )
+        .name(js_string!("resolve"))
+        .length(1)
+        .build();
+
+        let require = FunctionObjectBuilder::new(
+            context.realm(),
+            NativeFunction::from_copy_closure_with_captures(
+                |_, args, dir, context| {
+                    let specifier = args.get_or_undefined(0).to_string(context)?;
+                    Self::require(
+                        &specifier.to_std_string_escaped(),
+                        &string_to_path(dir),
+                        context,
+                    )
+                },
+                dir,
+            ),
+        )
+        .name(js_string!("require"))
+        .length(1)
+        .build();
+
+        let cache = Self::cache(context);
+        require
+            .create_data_property_or_throw(js_string!("resolve"), resolve, context)
+            .expect("defining a property of a new function cannot fail");
+        require
+            .create_data_property_or_throw(js_string!("cache"), cache, context)
+            .expect("defining a property of a new function cannot fail");
+        require.into()
+    }
+}
+
+/// Parses `source` with `JSON.parse`.
+fn parse_json(source: &str, context: &mut Context) -> JsResult<JsValue> {
+    let json = context.intrinsics().objects().json();
+    let parse = json.get(js_string!("parse"), context)?;
+    let parse = parse
+        .as_callable()
+        .ok_or_else(|| JsNativeError::typ().with_message("JSON.parse is not a function"))?;
+    parse.call(&json.into(), &[JsString::from(source).into()], context)
+}
+
+/// Converts a path to a `JsString`.
+fn path_to_string(path: &Path) -> JsString {
+    JsString::from(path.to_string_lossy().as_ref())
+}
+
+/// Converts a `JsString` created by [`path_to_string`] back to a path.
+fn string_to_path(path: &JsString) -> PathBuf {
+    PathBuf::from(path.to_std_string_escaped())
+}
@@ -0,0 +1,143 @@
+//! The resolution of the specifiers passed to `require`, following the algorithm of Node.js.
+//!
+//! More information:
+//!  - [Node.js documentation][node]
+//!
+//! [node]: https://nodejs.org/api/modules.html#all-together
+
+use crate::Permissions;
+use boa_engine::{js_string, object::JsObject, Context, JsNativeError, JsResult};
+use std::path::{Component, Path, PathBuf};
+
+/// The extensions tried, in order, when a specifier doesn't name an existing file.
+const EXTENSIONS: [&str; 2] = ["js", "json"];
+
+/// Resolves `specifier` as required by a module in the directory `dir`, returning the
+/// canonical path of the file to load.
+pub(super) fn resolve(specifier: &str, dir: &Path, context: &mut Context) -> JsResult<PathBuf> {
+    let found = if is_path(specifier) {
+        let path = dir.join(specifier);
+        match load_as_file(&path) {
+            Some(file) => Some(file),
+            None => load_as_directory(&path, context)?,
+        }
+    } else {
+        load_node_modules(specifier, dir, context)?
+    };
+
+    found
+        .and_then(|path| path.canonicalize().ok())
+        .ok_or_else(|| {
+            JsNativeError::error()
+                .with_message(format!(
+                    "cannot find module '{specifier}' from '{}'",
+                    dir.display()
+                ))
+                .into()
+        })
+}
+
+/// Returns `true` if the module at `path` is a CommonJS module: a `.cjs` file, or a `.js` file
+/// whose nearest `package.json` doesn't declare a `"type": "module"`.
+///
+/// `.js` files outside of any package are not CommonJS modules, which keeps the scripts of an
+/// application loaded as ECMAScript modules by default.
+pub(super) fn is_commonjs(path: &Path, context: &mut Context) -> JsResult<bool> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("cjs") => Ok(true),
+        Some("js") => {
+            for dir in path.ancestors().skip(1) {
+                if let Some(package) = read_package_json(dir, context)? {
+                    let kind = package.get(js_string!("type"), context)?;
+                    return Ok(kind.as_string() != Some(&js_string!("module")));
+                }
+            }
+            Ok(false)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Returns `true` if `specifier` is a relative or absolute path, instead of the name of a
+/// package.
+fn is_path(specifier: &str) -> bool {
+    let path = Path::new(specifier);
+    path.is_absolute()
+        || matches!(
+            path.components().next(),
+            Some(Component::CurDir | Component::ParentDir)
+        )
+}
+
+/// `LOAD_AS_FILE(X)`: loads `path` itself, or `path` with one of the known extensions.
+fn load_as_file(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+    let name = path.file_name()?;
+    EXTENSIONS.iter().find_map(|extension| {
+        let mut name = name.to_os_string();
+        name.push(".");
+        name.push(extension);
+        let file = path.with_file_name(name);
+        file.is_file().then_some(file)
+    })
+}
+
+/// `LOAD_INDEX(X)`: loads the `index` file of the directory `path`.
+fn load_index(path: &Path) -> Option<PathBuf> {
+    EXTENSIONS.iter().find_map(|extension| {
+        let file = path.join(format!("index.{extension}"));
+        file.is_file().then_some(file)
+    })
+}
+
+/// `LOAD_AS_DIRECTORY(X)`: loads the `main` file of the package at `path`, or its `index` file.
+fn load_as_directory(path: &Path, context: &mut Context) -> JsResult<Option<PathBuf>> {
+    if let Some(package) = read_package_json(path, context)? {
+        let main = package.get(js_string!("main"), context)?;
+        if let Some(main) = main.as_string().filter(|main| !main.is_empty()) {
+            let main = path.join(main.to_std_string_escaped());
+            if let Some(file) = load_as_file(&main).or_else(|| load_index(&main)) {
+                return Ok(Some(file));
+            }
+        }
+    }
+    Ok(load_index(path))
+}
+
+/// `LOAD_NODE_MODULES(X, START)`: loads the package `name` from the `node_modules` directories
+/// of `dir` and of its ancestors.
+fn load_node_modules(name: &str, dir: &Path, context: &mut Context) -> JsResult<Option<PathBuf>> {
+    for dir in dir.ancestors() {
+        if dir.file_name().is_some_and(|name| name == "node_modules") {
+            continue;
+        }
+        let path = dir.join("node_modules").join(name);
+        if let Some(file) = load_as_file(&path) {
+            return Ok(Some(file));
+        }
+        if let Some(file) = load_as_directory(&path, context)? {
+            return Ok(Some(file));
+        }
+    }
+    Ok(None)
+}
+
+/// Reads and parses the `package.json` file of the directory `dir`, if it exists and can be read
+/// by the context.
+pub(crate) fn read_package_json(dir: &Path, context: &mut Context) -> JsResult<Option<JsObject>> {
+    let path = dir.join("package.json");
+    if Permissions::check_read(&path, context).is_err() {
+        return Ok(None);
+    }
+    let Ok(source) = std::fs::read_to_string(&path) else {
+        return Ok(None);
+    };
+    let package = super::parse_json(&source, context).map_err(|e| {
+        JsNativeError::syntax()
+            .with_message(format!("could not parse `{}`", path.display()))
+            .with_cause(e)
+    })?;
+    Ok(package.as_object().cloned())
+}
@@ -0,0 +1,215 @@
+use super::{CommonJs, CommonJsModuleLoader};
+use crate::{
+    test::{root, run_test_actions, run_test_actions_with, TempDir, TestAction},
+    Permissions,
+};
+use boa_engine::{
+    builtins::promise::PromiseState, context::ContextBuilder, js_string,
+    module::SimpleModuleLoader, Context, Module, Source,
+};
+use indoc::indoc;
+use std::rc::Rc;
+
+/// Registers the global `require` function against the path defined by
+/// [`TempDir::define_root`].
+fn register_require(context: &mut Context) {
+    let root = root(context);
+    CommonJs::register(&root, context).expect("failed to register require");
+}
+
+#[test]
+fn require() {
+    let dir = TempDir::new("commonjs-require");
+    dir.write(
+        "lib/math.js",
+        indoc! {r#"
+            exports.add = (a, b) => a + b;
+            exports.filename = __filename;
+            exports.dirname = __dirname;
+            exports.isThis = this === exports;
+        "#},
+    )
+    .write(
+        "lib/index.js",
+        "module.exports = require('./math').add(1, 2);",
+    )
+    .write("lib/data.json", r#"{ "answer": 42 }"#)
+    .write("bin.js", "#!/usr/bin/env node\nmodule.exports = 'bin';")
+    .write(
+        "counter.js",
+        "module.exports = { count: (globalThis.loads = (globalThis.loads ?? 0) + 1) };",
+    );
+    let math = dir.path().join("lib").join("math.js");
+
+    run_test_actions([
+        dir.define_root(),
+        TestAction::inspect_context(register_require),
+        TestAction::run("const math = require('./lib/math.js');"),
+        TestAction::assert_eq("math.add(2, 3)", 5),
+        TestAction::assert_eq("math.isThis", true),
+        TestAction::assert_eq("math === require('./lib/math')", true),
+        TestAction::assert_eq("require('./lib')", 3),
+        TestAction::assert_eq("require('./lib/data').answer", 42),
+        TestAction::assert_eq("require('./bin')", js_string!("bin")),
+        TestAction::assert_eq("require('./counter').count", 1),
+        TestAction::assert_eq("require('./counter').count", 1),
+        TestAction::run("delete require.cache[require.resolve('./counter')];"),
+        TestAction::assert_eq("require('./counter').count", 2),
+        TestAction::assert_eq(
+            "try { require('./missing') } catch (e) { e.message.startsWith(\"cannot find module './missing'\") }",
+            true,
+        ),
+        TestAction::assert_eq("math.filename", js_string!(math.to_string_lossy().as_ref())),
+        TestAction::assert_eq(
+            "math.dirname",
+            js_string!(math.parent().unwrap().to_string_lossy().as_ref()),
+        ),
+        TestAction::assert("require.cache[require.resolve('./lib/math')].loaded"),
+    ]);
+}
+
+#[test]
+fn circular_dependencies() {
+    let dir = TempDir::new("commonjs-circular");
+    dir.write(
+        "a.js",
+        indoc! {r#"
+            exports.loaded = false;
+            const b = require("./b");
+            exports.fromB = b.seenFromA;
+            exports.loaded = true;
+        "#},
+    )
+    .write(
+        "b.js",
+        indoc! {r#"
+            const a = require("./a");
+            exports.seenFromA = a.loaded;
+        "#},
+    )
+    .write(
+        "throws.js",
+        "globalThis.attempts = (globalThis.attempts ?? 0) + 1; throw new Error('boom');",
+    );
+
+    run_test_actions([
+        dir.define_root(),
+        TestAction::inspect_context(register_require),
+        TestAction::run("const a = require('./a');"),
+        TestAction::assert_eq("a.loaded", true),
+        TestAction::assert_eq("a.fromB", false),
+        TestAction::assert_eq("require('./b').seenFromA", false),
+        // Modules that throw are removed from the cache.
+        TestAction::assert_eq(
+            "try { require('./throws') } catch (e) { e.message }",
+            js_string!("boom"),
+        ),
+        TestAction::assert_eq("try { require('./throws') } catch (e) { attempts }", 2),
+    ]);
+}
+
+#[test]
+fn node_modules() {
+    let dir = TempDir::new("commonjs-node-modules");
+    dir.write(
+        "node_modules/greet/package.json",
+        r#"{ "main": "lib/greet" }"#,
+    )
+    .write(
+        "node_modules/greet/lib/greet.js",
+        "module.exports = (name) => `hello ${name}`;",
+    )
+    .write("node_modules/plain/index.js", "module.exports = 'plain';")
+    .write(
+        "app/node_modules/plain/index.js",
+        "module.exports = 'nested';",
+    )
+    .write(
+        "app/main.js",
+        "module.exports = [require('greet')('boa'), require('plain')];",
+    );
+
+    run_test_actions([
+        dir.define_root(),
+        TestAction::inspect_context(register_require),
+        TestAction::assert_eq("require('greet')('world')", js_string!("hello world")),
+        TestAction::assert_eq("require('plain')", js_string!("plain")),
+        TestAction::assert_eq(
+            "require('./app/main.js').join()",
+            js_string!("hello boa,nested"),
+        ),
+        TestAction::assert_eq(
+            "try { require('missing') } catch (e) { e instanceof Error }",
+            true,
+        ),
+    ]);
+}
+
+#[test]
+fn import_commonjs() {
+    let dir = TempDir::new("commonjs-import");
+    dir.write(
+        "lib.cjs",
+        "module.exports = { add: (a, b) => a + b, name: 'lib' };",
+    )
+    .write("node_modules/pkg/package.json", r#"{ "main": "main.js" }"#)
+    .write("node_modules/pkg/main.js", "exports.value = 7;")
+    .write("esm/package.json", r#"{ "type": "module" }"#)
+    .write(
+        "esm/esm.js",
+        "import lib from '../lib.cjs'; export const value = lib.name + '-esm';",
+    );
+
+    let loader =
+        CommonJsModuleLoader::new(SimpleModuleLoader::new(dir.path()).unwrap(), dir.path())
+            .unwrap();
+    let context = &mut ContextBuilder::new()
+        .module_loader(Rc::new(loader))
+        .build()
+        .unwrap();
+
+    run_test_actions_with(
+        [
+            TestAction::assert_context(|context| {
+                let source = indoc! {r#"
+                    import lib, { add } from "./lib.cjs";
+                    import { value } from "pkg";
+                    import { value as esm } from "./esm/esm.js";
+                    globalThis.result = [add(1, 2), lib.name, value, esm].join();
+                "#};
+                let module = Module::parse(Source::from_bytes(source), None, context).unwrap();
+                let promise = module.load_link_evaluate(context);
+                context.run_jobs();
+                matches!(promise.state(), PromiseState::Fulfilled(_))
+            }),
+            TestAction::assert_eq("result", js_string!("3,lib,7,lib-esm")),
+        ],
+        context,
+    );
+}
+
+#[test]
+fn permissions() {
+    let dir = TempDir::new("commonjs-permissions");
+    dir.write("allowed/index.js", "module.exports = require('../denied');")
+        .write("allowed/main.js", "module.exports = 'main';")
+        .write("denied.js", "module.exports = 'denied';");
+    run_test_actions([
+        dir.define_root(),
+        TestAction::inspect_context(|context| {
+            register_require(context);
+            Permissions::none()
+                .allow_read(root(context).join("allowed"))
+                .register(context);
+        }),
+        TestAction::assert_eq("require('./allowed/main')", js_string!("main")),
+        TestAction::assert_eq(
+            "try { require('./allowed') } catch (e) { e.message.startsWith('PermissionDenied: requires read access') }",
+            true,
+        ),
+        TestAction::assert_eq(
+            "try { require('./denied') } catch (e) { e.message.startsWith('PermissionDenied') }",
+            true,
+        ),
+    ]);
+}
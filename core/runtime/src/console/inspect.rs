@@ -0,0 +1,213 @@
+//! Depth-limited, cycle-safe rendering of JavaScript values, used by the `console` methods to
+//! apply the "generic JavaScript object formatting" of the [specification][spec].
+//!
+//! [spec]: https://console.spec.whatwg.org/#generic-javascript-object-formatting
+
+use boa_engine::{
+    builtins::{date::Date, object::OrdinaryObject, regexp::RegExp},
+    js_string,
+    object::JsObject,
+    property::{PropertyDescriptor, PropertyKey},
+    Context, JsResult, JsString, JsValue,
+};
+
+/// The number of nested objects that are expanded by default when inspecting a value.
+pub(super) const DEFAULT_DEPTH: usize = 2;
+
+/// Renders `value`, expanding nested objects up to `depth` levels deep.
+///
+/// Objects that are already being rendered higher up in the same branch are printed as
+/// `[Circular]`, and objects nested deeper than `depth` are printed as `[Object]` or `[Array]`.
+/// Accessor properties are never invoked.
+pub(super) fn inspect(value: &JsValue, depth: usize, context: &mut Context) -> JsResult<String> {
+    Inspector {
+        depth,
+        seen: Vec::new(),
+    }
+    .inspect(value, 0, context)
+}
+
+/// Returns the enumerable own property keys of `object`, in property order.
+pub(super) fn enumerable_own_keys(
+    object: &JsObject,
+    context: &mut Context,
+) -> JsResult<Vec<PropertyKey>> {
+    let mut keys = Vec::new();
+    for key in object.own_property_keys(context)? {
+        if object
+            .own_property(key.clone(), context)?
+            .and_then(|desc| desc.enumerable())
+            .unwrap_or_default()
+        {
+            keys.push(key);
+        }
+    }
+    Ok(keys)
+}
+
+/// Returns `true` if `object` is expanded by the inspector instead of being printed with its
+/// builtin representation.
+pub(super) fn is_expandable(object: &JsObject) -> bool {
+    object.is_array() || object.is::<OrdinaryObject>()
+}
+
+/// The state of a single inspection.
+struct Inspector {
+    depth: usize,
+    seen: Vec<JsObject>,
+}
+
+impl Inspector {
+    /// Renders `value`, which is nested `level` objects deep.
+    fn inspect(
+        &mut self,
+        value: &JsValue,
+        level: usize,
+        context: &mut Context,
+    ) -> JsResult<String> {
+        let Some(object) = value.as_object() else {
+            return Ok(value.display().to_string());
+        };
+
+        if object.is_callable() {
+            return Self::function(object, context);
+        }
+        if object.is::<Date>() || object.is::<RegExp>() {
+            return Ok(value.to_string(context)?.to_std_string_escaped());
+        }
+        if !is_expandable(object) {
+            return Ok(value.display().to_string());
+        }
+
+        if self.seen.iter().any(|seen| JsObject::equals(seen, object)) {
+            return Ok("[Circular]".to_string());
+        }
+
+        let is_array = object.is_array();
+        if level > self.depth {
+            return Ok(if is_array { "[Array]" } else { "[Object]" }.to_string());
+        }
+
+        self.seen.push(object.clone());
+        let entries = if is_array {
+            self.array_entries(object, level, context)
+        } else {
+            self.object_entries(object, level, context)
+        };
+        self.seen.pop();
+        let entries = entries?;
+
+        Ok(match (is_array, entries.is_empty()) {
+            (true, true) => "[]".to_string(),
+            (true, false) => format!("[ {} ]", entries.join(", ")),
+            (false, true) => "{}".to_string(),
+            (false, false) => format!("{{ {} }}", entries.join(", ")),
+        })
+    }
+
+    /// Renders a callable object as `[Function: name]`.
+    fn function(object: &JsObject, context: &mut Context) -> JsResult<String> {
+        let name = object
+            .own_property(js_string!("name"), context)?
+            .as_ref()
+            .and_then(PropertyDescriptor::value)
+            .and_then(JsValue::as_string)
+            .map(JsString::to_std_string_escaped)
+            .unwrap_or_default();
+
+        Ok(if name.is_empty() {
+            "[Function (anonymous)]".to_string()
+        } else {
+            format!("[Function: {name}]")
+        })
+    }
+
+    /// Renders the elements of an array, marking holes as `<empty>`.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn array_entries(
+        &mut self,
+        object: &JsObject,
+        level: usize,
+        context: &mut Context,
+    ) -> JsResult<Vec<String>> {
+        let len = object
+            .own_property(js_string!("length"), context)?
+            .as_ref()
+            .and_then(PropertyDescriptor::value)
+            .and_then(JsValue::as_number)
+            .unwrap_or_default() as u32;
+
+        let mut entries = Vec::with_capacity(len as usize);
+        for index in 0..len {
+            let entry = match object.own_property(index, context)? {
+                Some(desc) => self.property(&desc, level, context)?,
+                None => "<empty>".to_string(),
+            };
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+
+    /// Renders the enumerable own properties of an object as `key: value` pairs.
+    fn object_entries(
+        &mut self,
+        object: &JsObject,
+        level: usize,
+        context: &mut Context,
+    ) -> JsResult<Vec<String>> {
+        let mut entries = Vec::new();
+        for key in enumerable_own_keys(object, context)? {
+            let Some(desc) = object.own_property(key.clone(), context)? else {
+                continue;
+            };
+            let value = self.property(&desc, level, context)?;
+            entries.push(format!("{}: {value}", format_key(&key)));
+        }
+        Ok(entries)
+    }
+
+    /// Renders the value of a property, without invoking its accessors.
+    fn property(
+        &mut self,
+        desc: &PropertyDescriptor,
+        level: usize,
+        context: &mut Context,
+    ) -> JsResult<String> {
+        if let Some(value) = desc.value() {
+            return self.inspect(value, level + 1, context);
+        }
+
+        let getter = desc.get().is_some_and(|get| !get.is_undefined());
+        let setter = desc.set().is_some_and(|set| !set.is_undefined());
+        Ok(match (getter, setter) {
+            (true, true) => "[Getter/Setter]",
+            (true, false) => "[Getter]",
+            (false, true) => "[Setter]",
+            (false, false) => "undefined",
+        }
+        .to_string())
+    }
+}
+
+/// Renders a property key, quoting it if it is not a valid identifier.
+fn format_key(key: &PropertyKey) -> String {
+    match key {
+        PropertyKey::String(string) => {
+            let string = string.to_std_string_escaped();
+            let mut chars = string.chars();
+            let is_identifier = chars
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+                && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+            if is_identifier {
+                string
+            } else {
+                format!("{string:?}")
+            }
+        }
+        PropertyKey::Symbol(symbol) => {
+            format!("[{}]", symbol.descriptive_string().to_std_string_escaped())
+        }
+        PropertyKey::Index(index) => index.get().to_string(),
+    }
+}
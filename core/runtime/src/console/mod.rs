@@ -14,10 +14,14 @@
 #[cfg(test)]
 mod tests;
 
+mod inspect;
+
+use self::inspect::{enumerable_own_keys, inspect, is_expandable, DEFAULT_DEPTH};
 use boa_engine::{
     js_string,
     native_function::NativeFunction,
-    object::{JsObject, ObjectInitializer},
+    object::{builtins::JsArray, JsObject, ObjectInitializer},
+    property::PropertyKey,
     string::utf16,
     value::{JsValue, Numeric},
     Context, JsArgs, JsData, JsResult, JsString,
@@ -25,43 +29,108 @@ use boa_engine::{
 use boa_gc::{Finalize, Trace};
 // use boa_profiler::Profiler;
 use rustc_hash::FxHashMap;
-use std::{cell::RefCell, rc::Rc, time::SystemTime};
-
-/// This represents the different types of log messages.
-#[derive(Debug)]
-enum LogMessage {
-    Log(String),
-    Info(String),
-    Warn(String),
-    Error(String),
+use std::{cell::RefCell, fmt, rc::Rc, time::SystemTime};
+
+/// The log level of a message emitted by the `console` methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogLevel {
+    /// Messages emitted by `console.debug`.
+    Debug,
+    /// Messages emitted by `console.log`, `console.table` and `console.timeLog`.
+    Log,
+    /// Messages emitted by `console.info`, `console.count`, `console.group`, `console.dir` and
+    /// `console.timeEnd`.
+    Info,
+    /// Messages emitted by `console.warn` and the warnings of the counting and timing methods.
+    Warn,
+    /// Messages emitted by `console.error` and failed `console.assert` calls.
+    Error,
+    /// Messages emitted by `console.trace`.
+    Trace,
 }
 
-/// Helper function for logging messages.
-fn logger(msg: LogMessage, console_state: &Console) {
-    let indent = 2 * console_state.groups.len();
+/// A message emitted by one of the `console` methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogMessage {
+    /// The log level of the message.
+    pub level: LogLevel,
+    /// The formatted message.
+    pub message: String,
+    /// The number of groups the message is nested in.
+    pub indent: usize,
+    /// The names of the functions in the call stack at the time the message was emitted,
+    /// starting with the innermost one.
+    pub stack: Vec<String>,
+}
 
-    match msg {
-        LogMessage::Error(msg) => {
-            eprintln!("{msg:>indent$}");
+/// The backend of the `console` object, which receives all the messages emitted by its methods.
+///
+/// Implement this trait to route the output of `console` to the logging facilities of an
+/// application, and register it using [`Console::init_with_logger`].
+pub trait ConsoleLogger {
+    /// Logs a single message.
+    fn log(&self, message: LogMessage);
+
+    /// Clears the output, if possible.
+    ///
+    /// This is called by `console.clear()` and does nothing by default.
+    fn clear(&self) {}
+
+    /// Adds a marker with the provided label to the timeline of the application, if any.
+    ///
+    /// This is called by `console.timeStamp()` and does nothing by default.
+    fn time_stamp(&self, label: &str) {
+        let _ = label;
+    }
+}
+
+/// The default [`ConsoleLogger`], which prints errors to the standard error and every other
+/// message to the standard output.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultLogger;
+
+impl ConsoleLogger for DefaultLogger {
+    fn log(&self, message: LogMessage) {
+        let indent = " ".repeat(2 * message.indent);
+        let mut lines: Vec<String> = message
+            .message
+            .lines()
+            .map(|line| format!("{indent}{line}"))
+            .collect();
+        if message.level == LogLevel::Trace {
+            lines.extend(
+                message
+                    .stack
+                    .iter()
+                    .map(|frame| format!("{indent}    at {frame}")),
+            );
         }
-        LogMessage::Log(msg) | LogMessage::Info(msg) | LogMessage::Warn(msg) => {
-            println!("{msg:>indent$}");
+        let output = lines.join("\n");
+
+        match message.level {
+            LogLevel::Error => eprintln!("{output}"),
+            _ => println!("{output}"),
         }
     }
 }
 
+/// Renders a single argument of a `console` method, printing strings verbatim.
+fn format_arg(value: &JsValue, context: &mut Context) -> JsResult<String> {
+    match value {
+        JsValue::String(string) => Ok(string.to_std_string_escaped()),
+        value => inspect(value, DEFAULT_DEPTH, context),
+    }
+}
+
 /// This represents the `console` formatter.
 fn formatter(data: &[JsValue], context: &mut Context) -> JsResult<String> {
     match data {
         [] => Ok(String::new()),
-        [val] => Ok(val.to_string(context)?.to_std_string_escaped()),
-        data => {
+        [val] => format_arg(val, context),
+        [JsValue::String(target), ..] => {
             let mut formatted = String::new();
             let mut arg_index = 1;
-            let target = data
-                .get_or_undefined(0)
-                .to_string(context)?
-                .to_std_string_escaped();
+            let target = target.to_std_string_escaped();
             let mut chars = target.chars();
             while let Some(c) = chars.next() {
                 if c == '%' {
@@ -69,36 +138,45 @@ fn formatter(data: &[JsValue], context: &mut Context) -> JsResult<String> {
                     match fmt {
                         /* integer */
                         'd' | 'i' => {
-                            let arg = match data.get_or_undefined(arg_index).to_numeric(context)? {
-                                Numeric::Number(r) => (r.floor() + 0.0).to_string(),
-                                Numeric::BigInt(int) => int.to_string(),
+                            let arg = match data.get_or_undefined(arg_index) {
+                                JsValue::Symbol(_) => f64::NAN.to_string(),
+                                arg => match arg.to_numeric(context)? {
+                                    Numeric::Number(r) => (r.floor() + 0.0).to_string(),
+                                    Numeric::BigInt(int) => int.to_string(),
+                                },
                             };
                             formatted.push_str(&arg);
                             arg_index += 1;
                         }
                         /* float */
                         'f' => {
-                            let arg = data.get_or_undefined(arg_index).to_number(context)?;
+                            let arg = match data.get_or_undefined(arg_index) {
+                                JsValue::Symbol(_) => f64::NAN,
+                                arg => arg.to_number(context)?,
+                            };
                             formatted.push_str(&format!("{arg:.6}"));
                             arg_index += 1;
                         }
-                        /* object, FIXME: how to render this properly? */
+                        /* object */
                         'o' | 'O' => {
                             let arg = data.get_or_undefined(arg_index);
-                            formatted.push_str(&arg.display().to_string());
+                            formatted.push_str(&inspect(arg, DEFAULT_DEPTH, context)?);
                             arg_index += 1;
                         }
                         /* string */
                         's' => {
-                            let arg = data
-                                .get_or_undefined(arg_index)
-                                .to_string(context)?
-                                .to_std_string_escaped();
-                            formatted.push_str(&arg);
+                            let arg = match data.get_or_undefined(arg_index) {
+                                JsValue::Symbol(symbol) => symbol.descriptive_string(),
+                                arg => arg.to_string(context)?,
+                            };
+                            formatted.push_str(&arg.to_std_string_escaped());
+                            arg_index += 1;
+                        }
+                        /* CSS styling, which cannot be applied to plain text */
+                        'c' => {
                             arg_index += 1;
                         }
                         '%' => formatted.push('%'),
-                        /* TODO: %c is not implemented */
                         c => {
                             formatted.push('%');
                             formatted.push(c);
@@ -111,37 +189,119 @@ fn formatter(data: &[JsValue], context: &mut Context) -> JsResult<String> {
 
             /* unformatted data */
             for rest in data.iter().skip(arg_index) {
-                formatted.push_str(&format!(
-                    " {}",
-                    rest.to_string(context)?.to_std_string_escaped()
-                ));
+                formatted.push(' ');
+                formatted.push_str(&format_arg(rest, context)?);
             }
 
             Ok(formatted)
         }
+        data => {
+            let mut formatted = Vec::with_capacity(data.len());
+            for value in data {
+                formatted.push(format_arg(value, context)?);
+            }
+            Ok(formatted.join(" "))
+        }
     }
 }
 
+/// Renders a table with box-drawing characters.
+fn render_table(header: &[String], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = header
+        .iter()
+        .enumerate()
+        .map(|(i, title)| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .chain(std::iter::once(title))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let separator = |left: &str, middle: &str, right: &str| {
+        let segments: Vec<String> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+        format!("{left}{}{right}", segments.join(middle))
+    };
+    let line = |cells: &[String]| {
+        let segments: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                let cell = cells.get(i).map_or("", String::as_str);
+                let padding = w - cell.chars().count();
+                format!(" {cell}{} ", " ".repeat(padding))
+            })
+            .collect();
+        format!("│{}│", segments.join("│"))
+    };
+
+    let mut table = vec![
+        separator("┌", "┬", "┐"),
+        line(header),
+        separator("├", "┼", "┤"),
+    ];
+    table.extend(rows.iter().map(|row| line(row)));
+    table.push(separator("└", "┴", "┘"));
+    table.join("\n")
+}
+
 /// This is the internal console object state.
-#[derive(Debug, Default, Trace, Finalize, JsData)]
+#[derive(Trace, Finalize, JsData)]
 pub struct Console {
     count_map: FxHashMap<JsString, u32>,
     timer_map: FxHashMap<JsString, u128>,
     groups: Vec<String>,
+    #[unsafe_ignore_trace]
+    logger: Rc<dyn ConsoleLogger>,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self {
+            count_map: FxHashMap::default(),
+            timer_map: FxHashMap::default(),
+            groups: Vec::new(),
+            logger: Rc::new(DefaultLogger),
+        }
+    }
+}
+
+impl fmt::Debug for Console {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Console")
+            .field("count_map", &self.count_map)
+            .field("timer_map", &self.timer_map)
+            .field("groups", &self.groups)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Console {
     /// Name of the built-in `console` property.
     pub const NAME: &'static str = "console";
 
-    /// Initializes the `console` built-in object.
-    #[allow(clippy::too_many_lines)]
+    /// Initializes the `console` built-in object, printing its messages with the
+    /// [`DefaultLogger`].
     pub fn init(context: &mut Context) -> JsObject {
+        Self::init_with_logger(DefaultLogger, context)
+    }
+
+    /// Initializes the `console` built-in object, sending its messages to `logger`.
+    ///
+    /// `logger` must not store any garbage collected values, since it won't be traced.
+    #[allow(clippy::too_many_lines)]
+    pub fn init_with_logger<L>(logger: L, context: &mut Context) -> JsObject
+    where
+        L: ConsoleLogger + 'static,
+    {
         fn console_method(
             f: fn(&JsValue, &[JsValue], &Console, &mut Context) -> JsResult<JsValue>,
             state: Rc<RefCell<Console>>,
         ) -> NativeFunction {
-            // SAFETY: `Console` doesn't contain types that need tracing.
+            // SAFETY: `Console` doesn't contain types that need tracing, and the logger is
+            // required to not store any garbage collected values.
             unsafe {
                 NativeFunction::from_closure(move |this, args, context| {
                     f(this, args, &state.borrow(), context)
@@ -152,7 +312,8 @@ impl Console {
             f: fn(&JsValue, &[JsValue], &mut Console, &mut Context) -> JsResult<JsValue>,
             state: Rc<RefCell<Console>>,
         ) -> NativeFunction {
-            // SAFETY: `Console` doesn't contain types that need tracing.
+            // SAFETY: `Console` doesn't contain types that need tracing, and the logger is
+            // required to not store any garbage collected values.
             unsafe {
                 NativeFunction::from_closure(move |this, args, context| {
                     f(this, args, &mut state.borrow_mut(), context)
@@ -161,7 +322,12 @@ impl Console {
         }
         // let _timer = Profiler::global().start_event(std::any::type_name::<Self>(), "init");
 
-        let state = Rc::new(RefCell::new(Self::default()));
+        let state = Rc::new(RefCell::new(Self {
+            count_map: FxHashMap::default(),
+            timer_map: FxHashMap::default(),
+            groups: Vec::new(),
+            logger: Rc::new(logger),
+        }));
 
         ObjectInitializer::with_native_data(Self::default(), context)
             .function(
@@ -249,10 +415,39 @@ impl Console {
                 js_string!("dir"),
                 0,
             )
-            .function(console_method(Self::dir, state), js_string!("dirxml"), 0)
+            .function(
+                console_method(Self::dirxml, state.clone()),
+                js_string!("dirxml"),
+                0,
+            )
+            .function(
+                console_method(Self::table, state.clone()),
+                js_string!("table"),
+                0,
+            )
+            .function(
+                console_method(Self::time_stamp, state),
+                js_string!("timeStamp"),
+                0,
+            )
             .build()
     }
 
+    /// Sends a message to the logger, capturing the current call stack.
+    fn emit(&self, level: LogLevel, message: String, context: &Context) {
+        let stack = context
+            .stack_trace()
+            .map(|frame| frame.code_block().name().to_std_string_escaped())
+            .collect();
+
+        self.logger.log(LogMessage {
+            level,
+            message,
+            indent: self.groups.len(),
+            stack,
+        });
+    }
+
     /// `console.assert(condition, ...data)`
     ///
     /// Prints a JavaScript value to the standard error if first argument evaluates to `false` or there
@@ -285,7 +480,7 @@ impl Console {
                 args[0] = JsValue::new(concat);
             }
 
-            logger(LogMessage::Error(formatter(&args, context)?), console);
+            console.emit(LogLevel::Error, formatter(&args, context)?, context);
         }
 
         Ok(JsValue::undefined())
@@ -304,6 +499,7 @@ impl Console {
     #[allow(clippy::unnecessary_wraps)]
     fn clear(_: &JsValue, _: &[JsValue], console: &mut Self, _: &mut Context) -> JsResult<JsValue> {
         console.groups.clear();
+        console.logger.clear();
         Ok(JsValue::undefined())
     }
 
@@ -323,7 +519,7 @@ impl Console {
         console: &Self,
         context: &mut Context,
     ) -> JsResult<JsValue> {
-        logger(LogMessage::Log(formatter(args, context)?), console);
+        console.emit(LogLevel::Debug, formatter(args, context)?, context);
         Ok(JsValue::undefined())
     }
 
@@ -343,7 +539,7 @@ impl Console {
         console: &Self,
         context: &mut Context,
     ) -> JsResult<JsValue> {
-        logger(LogMessage::Error(formatter(args, context)?), console);
+        console.emit(LogLevel::Error, formatter(args, context)?, context);
         Ok(JsValue::undefined())
     }
 
@@ -363,7 +559,7 @@ impl Console {
         console: &Self,
         context: &mut Context,
     ) -> JsResult<JsValue> {
-        logger(LogMessage::Info(formatter(args, context)?), console);
+        console.emit(LogLevel::Info, formatter(args, context)?, context);
         Ok(JsValue::undefined())
     }
 
@@ -383,7 +579,7 @@ impl Console {
         console: &Self,
         context: &mut Context,
     ) -> JsResult<JsValue> {
-        logger(LogMessage::Log(formatter(args, context)?), console);
+        console.emit(LogLevel::Log, formatter(args, context)?, context);
         Ok(JsValue::undefined())
     }
 
@@ -403,19 +599,7 @@ impl Console {
         console: &Self,
         context: &mut Context,
     ) -> JsResult<JsValue> {
-        if !args.is_empty() {
-            logger(LogMessage::Log(formatter(args, context)?), console);
-        }
-
-        let stack_trace_dump = context
-            .stack_trace()
-            .map(|frame| frame.code_block().name())
-            .collect::<Vec<_>>()
-            .into_iter()
-            .map(JsString::to_std_string_escaped)
-            .collect::<Vec<_>>()
-            .join("\n");
-        logger(LogMessage::Log(stack_trace_dump), console);
+        console.emit(LogLevel::Trace, formatter(args, context)?, context);
 
         Ok(JsValue::undefined())
     }
@@ -436,7 +620,7 @@ impl Console {
         console: &Self,
         context: &mut Context,
     ) -> JsResult<JsValue> {
-        logger(LogMessage::Warn(formatter(args, context)?), console);
+        console.emit(LogLevel::Warn, formatter(args, context)?, context);
         Ok(JsValue::undefined())
     }
 
@@ -464,8 +648,9 @@ impl Console {
         let msg = format!("count {}:", label.to_std_string_escaped());
         let c = console.count_map.entry(label).or_insert(0);
         *c += 1;
+        let c = *c;
 
-        logger(LogMessage::Info(format!("{msg} {c}")), console);
+        console.emit(LogLevel::Info, format!("{msg} {c}"), context);
         Ok(JsValue::undefined())
     }
 
@@ -492,9 +677,10 @@ impl Console {
 
         console.count_map.remove(&label);
 
-        logger(
-            LogMessage::Warn(format!("countReset {}", label.to_std_string_escaped())),
-            console,
+        console.emit(
+            LogLevel::Warn,
+            format!("countReset {}", label.to_std_string_escaped()),
+            context,
         );
 
         Ok(JsValue::undefined())
@@ -530,12 +716,10 @@ impl Console {
         };
 
         if console.timer_map.get(&label).is_some() {
-            logger(
-                LogMessage::Warn(format!(
-                    "Timer '{}' already exist",
-                    label.to_std_string_escaped()
-                )),
-                console,
+            console.emit(
+                LogLevel::Warn,
+                format!("Timer '{}' already exist", label.to_std_string_escaped()),
+                context,
             );
         } else {
             let time = Self::system_time_in_ms();
@@ -568,12 +752,10 @@ impl Console {
 
         console.timer_map.get(&label).map_or_else(
             || {
-                logger(
-                    LogMessage::Warn(format!(
-                        "Timer '{}' doesn't exist",
-                        label.to_std_string_escaped()
-                    )),
-                    console,
+                console.emit(
+                    LogLevel::Warn,
+                    format!("Timer '{}' doesn't exist", label.to_std_string_escaped()),
+                    context,
                 );
             },
             |t| {
@@ -582,7 +764,7 @@ impl Console {
                 for msg in args.iter().skip(1) {
                     concat = concat + " " + &msg.display().to_string();
                 }
-                logger(LogMessage::Log(concat), console);
+                console.emit(LogLevel::Log, concat, context);
             },
         );
 
@@ -612,23 +794,22 @@ impl Console {
 
         console.timer_map.remove(&label).map_or_else(
             || {
-                logger(
-                    LogMessage::Warn(format!(
-                        "Timer '{}' doesn't exist",
-                        label.to_std_string_escaped()
-                    )),
-                    console,
+                console.emit(
+                    LogLevel::Warn,
+                    format!("Timer '{}' doesn't exist", label.to_std_string_escaped()),
+                    context,
                 );
             },
             |t| {
                 let time = Self::system_time_in_ms();
-                logger(
-                    LogMessage::Info(format!(
+                console.emit(
+                    LogLevel::Info,
+                    format!(
                         "{}: {} ms - timer removed",
                         label.to_std_string_escaped(),
                         time - t
-                    )),
-                    console,
+                    ),
+                    context,
                 );
             },
         );
@@ -654,7 +835,7 @@ impl Console {
     ) -> JsResult<JsValue> {
         let group_label = formatter(args, context)?;
 
-        logger(LogMessage::Info(format!("group: {group_label}")), console);
+        console.emit(LogLevel::Info, format!("group: {group_label}"), context);
         console.groups.push(group_label);
 
         Ok(JsValue::undefined())
@@ -684,7 +865,7 @@ impl Console {
 
     /// `console.dir(item, options)`
     ///
-    /// Prints info about item
+    /// Prints info about item, expanding nested objects up to `options.depth` levels deep.
     ///
     /// More information:
     ///  - [MDN documentation][mdn]
@@ -692,12 +873,175 @@ impl Console {
     ///
     /// [spec]: https://console.spec.whatwg.org/#dir
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/dir
-    #[allow(clippy::unnecessary_wraps)]
-    fn dir(_: &JsValue, args: &[JsValue], console: &Self, _: &mut Context) -> JsResult<JsValue> {
-        logger(
-            LogMessage::Info(args.get_or_undefined(0).display_obj(true)),
-            console,
-        );
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn dir(
+        _: &JsValue,
+        args: &[JsValue],
+        console: &Self,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let depth = match args.get_or_undefined(1).as_object() {
+            Some(options) => match options.get(js_string!("depth"), context)? {
+                JsValue::Undefined => DEFAULT_DEPTH,
+                JsValue::Null => usize::MAX,
+                // Float to integer casts saturate, which maps `Infinity` to `usize::MAX` and
+                // `NaN` and negative depths to 0.
+                depth => depth.to_number(context)? as usize,
+            },
+            None => DEFAULT_DEPTH,
+        };
+
+        let message = inspect(args.get_or_undefined(0), depth, context)?;
+        console.emit(LogLevel::Info, message, context);
+        Ok(JsValue::undefined())
+    }
+
+    /// `console.dirxml(...data)`
+    ///
+    /// Prints info about every item of data.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `console` specification][spec]
+    ///
+    /// [spec]: https://console.spec.whatwg.org/#dirxml
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/dirxml
+    fn dirxml(
+        _: &JsValue,
+        args: &[JsValue],
+        console: &Self,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let mut items = Vec::with_capacity(args.len());
+        for item in args {
+            items.push(inspect(item, DEFAULT_DEPTH, context)?);
+        }
+
+        console.emit(LogLevel::Info, items.join(" "), context);
+        Ok(JsValue::undefined())
+    }
+
+    /// `console.table(tabularData, properties)`
+    ///
+    /// Prints the properties of tabularData as a table, with a row per property and a column per
+    /// property of the rows, optionally restricted to the given properties. If tabularData
+    /// cannot be printed as a table, it is logged instead.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `console` specification][spec]
+    ///
+    /// [spec]: https://console.spec.whatwg.org/#table
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/table
+    fn table(
+        this: &JsValue,
+        args: &[JsValue],
+        console: &Self,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let Some(data) = args
+            .first()
+            .and_then(JsValue::as_object)
+            .filter(|data| is_expandable(data))
+            .cloned()
+        else {
+            return Self::log(this, args, console, context);
+        };
+
+        let filter = match args.get_or_undefined(1).as_object() {
+            Some(properties) if properties.is_array() => {
+                let properties = JsArray::from_object(properties.clone())?;
+                let mut filter = Vec::new();
+                for i in 0..properties.length(context)? {
+                    let property = properties.get(i, context)?.to_property_key(context)?;
+                    filter.push(property);
+                }
+                Some(filter)
+            }
+            _ => None,
+        };
+
+        let mut columns: Vec<PropertyKey> = Vec::new();
+        let mut has_values = false;
+        let mut rows = Vec::new();
+        for index in enumerable_own_keys(&data, context)? {
+            if matches!(index, PropertyKey::Symbol(_)) {
+                continue;
+            }
+
+            let value = data.get(index.clone(), context)?;
+            let mut cells = Vec::new();
+            let mut row_value = None;
+            match value.as_object() {
+                Some(row) if is_expandable(row) => {
+                    for key in enumerable_own_keys(row, context)? {
+                        if matches!(key, PropertyKey::Symbol(_)) {
+                            continue;
+                        }
+                        let cell = inspect(&row.get(key.clone(), context)?, 0, context)?;
+                        if !columns.contains(&key) {
+                            columns.push(key.clone());
+                        }
+                        cells.push((key, cell));
+                    }
+                }
+                _ => {
+                    has_values = true;
+                    row_value = Some(inspect(&value, 0, context)?);
+                }
+            }
+            rows.push((index, cells, row_value));
+        }
+
+        let columns = filter.unwrap_or(columns);
+        let mut header = vec!["(index)".to_string()];
+        header.extend(columns.iter().map(ToString::to_string));
+        if has_values {
+            header.push("Values".to_string());
+        }
+
+        let rows: Vec<Vec<String>> = rows
+            .into_iter()
+            .map(|(index, cells, row_value)| {
+                let mut line = vec![index.to_string()];
+                line.extend(columns.iter().map(|column| {
+                    cells
+                        .iter()
+                        .find(|(key, _)| key == column)
+                        .map(|(_, cell)| cell.clone())
+                        .unwrap_or_default()
+                }));
+                if has_values {
+                    line.push(row_value.unwrap_or_default());
+                }
+                line
+            })
+            .collect();
+
+        console.emit(LogLevel::Log, render_table(&header, &rows), context);
+        Ok(JsValue::undefined())
+    }
+
+    /// `console.timeStamp(label)`
+    ///
+    /// Adds a marker with the given label to the timeline of the logger.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/timeStamp
+    fn time_stamp(
+        _: &JsValue,
+        args: &[JsValue],
+        console: &Self,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let label = match args.first() {
+            Some(value) => value.to_string(context)?,
+            None => "default".into(),
+        };
+
+        console.logger.time_stamp(&label.to_std_string_escaped());
         Ok(JsValue::undefined())
     }
 }
@@ -1,7 +1,8 @@
-use super::{formatter, Console};
+use super::{formatter, Console, ConsoleLogger, LogLevel, LogMessage};
 use crate::test::{run_test_actions, run_test_actions_with, TestAction};
 use boa_engine::{js_string, property::Attribute, Context, JsValue};
 use indoc::indoc;
+use std::{cell::RefCell, rc::Rc};
 
 #[test]
 fn formatter_no_args_is_empty_string() {
@@ -110,3 +111,166 @@ fn console_log_cyclic() {
     );
     // Should not stack overflow
 }
+
+/// A logger that records every message it receives.
+#[derive(Debug, Clone, Default)]
+struct RecordingLogger {
+    messages: Rc<RefCell<Vec<LogMessage>>>,
+    time_stamps: Rc<RefCell<Vec<String>>>,
+}
+
+impl ConsoleLogger for RecordingLogger {
+    fn log(&self, message: LogMessage) {
+        self.messages.borrow_mut().push(message);
+    }
+
+    fn time_stamp(&self, label: &str) {
+        self.time_stamps.borrow_mut().push(label.to_string());
+    }
+}
+
+/// Runs `source` with a console backed by a [`RecordingLogger`], returning the logger.
+fn record(source: &'static str) -> RecordingLogger {
+    let logger = RecordingLogger::default();
+    let mut context = Context::default();
+    let console = Console::init_with_logger(logger.clone(), &mut context);
+    context
+        .register_global_property(js_string!(Console::NAME), console, Attribute::all())
+        .unwrap();
+
+    run_test_actions_with([TestAction::run(source)], &mut context);
+    logger
+}
+
+/// Returns the formatted messages received by `logger`.
+fn messages(logger: &RecordingLogger) -> Vec<String> {
+    logger
+        .messages
+        .borrow()
+        .iter()
+        .map(|message| message.message.clone())
+        .collect()
+}
+
+#[test]
+fn logger_receives_levels_and_group_indent() {
+    let logger = record(indoc! {r#"
+        console.debug("a");
+        console.group("outer");
+        console.warn("b");
+        console.groupEnd();
+        console.error("c");
+    "#});
+
+    let levels: Vec<_> = logger
+        .messages
+        .borrow()
+        .iter()
+        .map(|message| (message.level, message.indent))
+        .collect();
+    assert_eq!(
+        levels,
+        [
+            (LogLevel::Debug, 0),
+            (LogLevel::Info, 0),
+            (LogLevel::Warn, 1),
+            (LogLevel::Error, 0),
+        ]
+    );
+}
+
+#[test]
+fn logger_receives_stack() {
+    let logger = record(indoc! {r#"
+        function inner() { console.trace("here"); }
+        function outer() { inner(); }
+        outer();
+    "#});
+
+    let messages = logger.messages.borrow();
+    assert_eq!(messages[0].level, LogLevel::Trace);
+    assert_eq!(messages[0].message, "here");
+    assert_eq!(messages[0].stack[..2], ["inner", "outer"]);
+}
+
+#[test]
+fn log_inspects_objects() {
+    let logger = record(indoc! {r#"
+        console.log({ a: 1, b: "x", c: [1, { d: { e: {} } }], f() {} }, [,1]);
+        const cyclic = { name: "self" };
+        cyclic.self = cyclic;
+        console.log("%o!", cyclic);
+        console.dir({ a: { b: { c: {} } } }, { depth: 0 });
+        console.log({ get a() { throw new Error(); }, "b-c": 1, [Symbol("s")]: 2 });
+    "#});
+
+    assert_eq!(
+        messages(&logger),
+        [
+            r#"{ a: 1, b: "x", c: [ 1, { d: [Object] } ], f: [Function: f] } [ <empty>, 1 ]"#,
+            r#"{ name: "self", self: [Circular] }!"#,
+            "{ a: [Object] }",
+            r#"{ a: [Getter], "b-c": 1, [Symbol(s)]: 2 }"#,
+        ]
+    );
+}
+
+#[test]
+fn formatter_css_consumes_argument() {
+    run_test_actions([TestAction::inspect_context(|ctx| {
+        assert_eq!(
+            formatter(
+                &[
+                    JsValue::new(js_string!("%cstyled%c text")),
+                    JsValue::new(js_string!("color: red")),
+                    JsValue::new(js_string!("")),
+                    JsValue::new(1),
+                ],
+                ctx
+            )
+            .unwrap(),
+            "styled text 1"
+        );
+    })]);
+}
+
+#[test]
+fn console_table() {
+    let logger = record(indoc! {r#"
+        console.table([{ a: 1, b: "x" }, { a: 2, c: true }, 3]);
+        console.table({ row: { a: 1, b: 2 } }, ["b"]);
+        console.table(42);
+    "#});
+
+    assert_eq!(
+        messages(&logger),
+        [
+            indoc! {r#"
+                ┌─────────┬───┬─────┬──────┬────────┐
+                │ (index) │ a │ b   │ c    │ Values │
+                ├─────────┼───┼─────┼──────┼────────┤
+                │ 0       │ 1 │ "x" │      │        │
+                │ 1       │ 2 │     │ true │        │
+                │ 2       │   │     │      │ 3      │
+                └─────────┴───┴─────┴──────┴────────┘"#},
+            indoc! {r#"
+                ┌─────────┬───┐
+                │ (index) │ b │
+                ├─────────┼───┤
+                │ row     │ 2 │
+                └─────────┴───┘"#},
+            "42",
+        ]
+    );
+}
+
+#[test]
+fn console_time_stamp() {
+    let logger = record(indoc! {r#"
+        console.timeStamp("start");
+        console.timeStamp();
+    "#});
+
+    assert_eq!(*logger.time_stamps.borrow(), ["start", "default"]);
+    assert!(logger.messages.borrow().is_empty());
+}
@@ -0,0 +1,266 @@
+//! Boa's implementation of a subset of the W3C Web Cryptography API: the `crypto` object with
+//! its `getRandomValues` and `randomUUID` methods, and the `digest` method of `crypto.subtle`.
+//!
+//! The random bytes are read from a [`RandomSource`] implemented by the host, which makes it
+//! possible to use the [`SeededRandom`] source to get reproducible values in tests.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [W3C `Web Cryptography API` specification][spec]
+//!
+//! [spec]: https://w3c.github.io/webcrypto/
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Web_Crypto_API
+
+#[cfg(test)]
+mod tests;
+
+mod random;
+
+#[doc(inline)]
+pub use random::{OsRandom, RandomSource, SeededRandom};
+
+use crate::{events::create_error, util::buffer_source_bytes};
+use boa_engine::{
+    js_string,
+    native_function::NativeFunction,
+    object::{
+        builtins::{JsArrayBuffer, JsFloat32Array, JsFloat64Array, JsPromise, JsTypedArray},
+        JsObject, ObjectInitializer,
+    },
+    property::Attribute,
+    Context, JsArgs, JsError, JsNativeError, JsResult, JsString, JsSymbol, JsValue,
+};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::{fmt::Write, rc::Rc};
+
+/// The maximum number of bytes `crypto.getRandomValues` can fill at once.
+const MAX_RANDOM_BYTES: usize = 65536;
+
+/// The `crypto` global object.
+#[derive(Debug, Clone, Copy)]
+pub struct Crypto;
+
+impl Crypto {
+    /// The name of the `crypto` object.
+    pub const NAME: &'static str = "crypto";
+
+    /// Registers the `crypto` object in the global object of the context, reading its random
+    /// bytes from `source`.
+    ///
+    /// # Errors
+    ///
+    /// This will return an error if the `crypto` object cannot be defined in the global object.
+    pub fn register<R: RandomSource + 'static>(source: R, context: &mut Context) -> JsResult<()> {
+        let crypto = Self::init(source, context);
+        context.register_global_property(js_string!(Self::NAME), crypto, Attribute::all())
+    }
+
+    /// Initializes the `crypto` object, reading its random bytes from `source`.
+    pub fn init<R: RandomSource + 'static>(source: R, context: &mut Context) -> JsObject {
+        let source: Rc<dyn RandomSource> = Rc::new(source);
+
+        // SAFETY: `RandomSource`s don't need to be traced, since they cannot contain any garbage
+        // collected values.
+        let get_random_values = unsafe {
+            let source = source.clone();
+            NativeFunction::from_closure(move |_, args, context| {
+                Self::get_random_values(args, source.as_ref(), context)
+            })
+        };
+        // SAFETY: Same as above.
+        let random_uuid = unsafe {
+            NativeFunction::from_closure(move |_, _, _| Self::random_uuid(source.as_ref()))
+        };
+
+        let subtle = ObjectInitializer::new(context)
+            .function(
+                NativeFunction::from_fn_ptr(Self::digest),
+                js_string!("digest"),
+                2,
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("SubtleCrypto"),
+                Attribute::CONFIGURABLE,
+            )
+            .build();
+
+        ObjectInitializer::new(context)
+            .function(get_random_values, js_string!("getRandomValues"), 1)
+            .function(random_uuid, js_string!("randomUUID"), 0)
+            .property(
+                js_string!("subtle"),
+                subtle,
+                Attribute::ENUMERABLE | Attribute::CONFIGURABLE,
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("Crypto"),
+                Attribute::CONFIGURABLE,
+            )
+            .build()
+    }
+
+    /// `crypto.getRandomValues(typedArray)`
+    ///
+    /// Fills an integer typed array with random values, returning the same array. At most 65536
+    /// bytes can be filled at once.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [W3C `Web Cryptography API` specification][spec]
+    ///
+    /// [spec]: https://w3c.github.io/webcrypto/#Crypto-method-getRandomValues
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Crypto/getRandomValues
+    fn get_random_values(
+        args: &[JsValue],
+        source: &dyn RandomSource,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let value = args.get_or_undefined(0);
+        let (object, array) = value
+            .as_object()
+            .and_then(|object| {
+                let array = JsTypedArray::from_object(object.clone()).ok()?;
+                Some((object.clone(), array))
+            })
+            .ok_or_else(|| JsNativeError::typ().with_message("the argument is not a TypedArray"))?;
+
+        if JsFloat32Array::from_object(object.clone()).is_ok()
+            || JsFloat64Array::from_object(object).is_ok()
+        {
+            let error = create_error(
+                js_string!("TypeMismatchError"),
+                "the TypedArray must be an integer array",
+                context,
+            )?;
+            return Err(JsError::from_opaque(error));
+        }
+
+        let offset = array.byte_offset(context)?;
+        let length = array.byte_length(context)?;
+        if length > MAX_RANDOM_BYTES {
+            let error = create_error(
+                js_string!("QuotaExceededError"),
+                &format!("the TypedArray is larger than {MAX_RANDOM_BYTES} bytes"),
+                context,
+            )?;
+            return Err(JsError::from_opaque(error));
+        }
+
+        let buffer = array
+            .buffer(context)?
+            .as_object()
+            .and_then(|buffer| JsArrayBuffer::from_object(buffer.clone()).ok())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("cannot fill a TypedArray backed by a SharedArrayBuffer")
+            })?;
+        if let Some(mut data) = buffer.data_mut() {
+            source.fill(&mut data[offset..offset + length])?;
+        }
+
+        Ok(value.clone())
+    }
+
+    /// `crypto.randomUUID()`
+    ///
+    /// Returns a random version 4 UUID, as defined by [RFC 4122][rfc].
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [W3C `Web Cryptography API` specification][spec]
+    ///
+    /// [rfc]: https://www.rfc-editor.org/rfc/rfc4122#section-4.4
+    /// [spec]: https://w3c.github.io/webcrypto/#Crypto-method-randomUUID
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Crypto/randomUUID
+    fn random_uuid(source: &dyn RandomSource) -> JsResult<JsValue> {
+        let mut bytes = [0; 16];
+        source.fill(&mut bytes)?;
+        bytes[6] = (bytes[6] & 0x0F) | 0x40;
+        bytes[8] = (bytes[8] & 0x3F) | 0x80;
+
+        let mut uuid = String::with_capacity(36);
+        for (i, byte) in bytes.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                uuid.push('-');
+            }
+            let _ = write!(uuid, "{byte:02x}");
+        }
+        Ok(JsString::from(uuid).into())
+    }
+
+    /// `crypto.subtle.digest(algorithm, data)`
+    ///
+    /// Returns a promise fulfilled with an `ArrayBuffer` containing the digest of `data`, using
+    /// `SHA-1`, `SHA-256`, `SHA-384` or `SHA-512`.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [W3C `Web Cryptography API` specification][spec]
+    ///
+    /// [spec]: https://w3c.github.io/webcrypto/#SubtleCrypto-method-digest
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/SubtleCrypto/digest
+    #[allow(clippy::unnecessary_wraps)]
+    fn digest(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let digest =
+            DigestAlgorithm::from_value(args.get_or_undefined(0), context).and_then(|algorithm| {
+                let data = buffer_source_bytes(args.get_or_undefined(1), context)?;
+                JsArrayBuffer::from_byte_block(algorithm.digest(&data), context)
+            });
+        let promise = match digest {
+            Ok(digest) => JsPromise::resolve(digest, context),
+            Err(e) => JsPromise::reject(e, context),
+        };
+        Ok(promise.into())
+    }
+}
+
+/// The hash functions supported by `crypto.subtle.digest`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DigestAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    /// [Normalizes][spec] an algorithm identifier, which is either the name of the algorithm or
+    /// an object with a `name` property. Names are compared ignoring ASCII case.
+    ///
+    /// [spec]: https://w3c.github.io/webcrypto/#algorithm-normalization-normalize-an-algorithm
+    fn from_value(value: &JsValue, context: &mut Context) -> JsResult<Self> {
+        let name = if let Some(object) = value.as_object() {
+            object.get(js_string!("name"), context)?
+        } else {
+            value.clone()
+        };
+        let name = name.to_string(context)?.to_std_string_escaped();
+        match name.to_ascii_uppercase().as_str() {
+            "SHA-1" => Ok(Self::Sha1),
+            "SHA-256" => Ok(Self::Sha256),
+            "SHA-384" => Ok(Self::Sha384),
+            "SHA-512" => Ok(Self::Sha512),
+            _ => {
+                let error = create_error(
+                    js_string!("NotSupportedError"),
+                    &format!("the algorithm '{name}' is not supported"),
+                    context,
+                )?;
+                Err(JsError::from_opaque(error))
+            }
+        }
+    }
+
+    /// Computes the digest of `data`.
+    fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha1 => Sha1::digest(data).to_vec(),
+            Self::Sha256 => Sha256::digest(data).to_vec(),
+            Self::Sha384 => Sha384::digest(data).to_vec(),
+            Self::Sha512 => Sha512::digest(data).to_vec(),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! The sources of random bytes of the `crypto` object.
+
+use boa_engine::{JsNativeError, JsResult};
+use std::cell::Cell;
+
+/// A source of random bytes, used by `crypto.getRandomValues` and `crypto.randomUUID`.
+///
+/// Hosts can implement this trait to control where the random bytes come from, e.g. to make
+/// them deterministic in tests with a [`SeededRandom`]. The [`OsRandom`] source should be used
+/// otherwise.
+///
+/// Sources are never traced by the garbage collector, so they must not store any garbage
+/// collected values (e.g. `JsObject`s).
+pub trait RandomSource {
+    /// Fills `bytes` with random bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no random bytes are available, which is thrown to the script.
+    fn fill(&self, bytes: &mut [u8]) -> JsResult<()>;
+}
+
+/// A [`RandomSource`] reading cryptographically secure random bytes from the operating system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsRandom;
+
+impl RandomSource for OsRandom {
+    fn fill(&self, bytes: &mut [u8]) -> JsResult<()> {
+        getrandom::getrandom(bytes).map_err(|e| {
+            JsNativeError::error()
+                .with_message(format!("could not get random bytes: {e}"))
+                .into()
+        })
+    }
+}
+
+/// A deterministic [`RandomSource`], which yields the same bytes for the same seed.
+///
+/// The bytes are **not** cryptographically secure, so this source must only be used to make
+/// tests reproducible.
+#[derive(Debug, Clone, Default)]
+pub struct SeededRandom {
+    state: Cell<u64>,
+}
+
+impl SeededRandom {
+    /// Creates a new `SeededRandom` source starting from `seed`.
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            state: Cell::new(seed),
+        }
+    }
+
+    /// Returns the next value of the [SplitMix64][splitmix] generator.
+    ///
+    /// [splitmix]: https://prng.di.unimi.it/splitmix64.c
+    fn next(&self) -> u64 {
+        let state = self.state.get().wrapping_add(0x9E37_79B9_7F4A_7C15);
+        self.state.set(state);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl RandomSource for SeededRandom {
+    fn fill(&self, bytes: &mut [u8]) -> JsResult<()> {
+        for chunk in bytes.chunks_mut(8) {
+            let value = self.next().to_le_bytes();
+            chunk.copy_from_slice(&value[..chunk.len()]);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+use super::{Crypto, RandomSource, SeededRandom};
+use crate::test::{run_test_actions, TestAction};
+use boa_engine::{js_string, Context, JsNativeError, JsResult, JsString};
+use indoc::indoc;
+
+/// A [`RandomSource`] that never has any random bytes available.
+struct Exhausted;
+
+impl RandomSource for Exhausted {
+    fn fill(&self, _: &mut [u8]) -> JsResult<()> {
+        Err(JsNativeError::error().with_message("no entropy").into())
+    }
+}
+
+#[test]
+fn get_random_values() {
+    run_test_actions([
+        TestAction::inspect_context(|context| {
+            Crypto::register(SeededRandom::new(42), context).expect("failed to register crypto");
+        }),
+        TestAction::run(indoc! {r#"
+            const bytes = new Uint8Array(16);
+            const result = crypto.getRandomValues(bytes);
+            const words = crypto.getRandomValues(new Uint32Array(new ArrayBuffer(16), 4, 2));
+        "#}),
+        TestAction::assert_eq("result === bytes", true),
+        TestAction::assert_eq("bytes.some((b) => b !== 0)", true),
+        TestAction::assert_eq("words.every((w) => w !== 0)", true),
+        TestAction::assert_eq("new Uint32Array(words.buffer)[0]", 0),
+        TestAction::assert_eq(
+            "crypto.getRandomValues(new BigInt64Array(2)) instanceof BigInt64Array",
+            true,
+        ),
+        TestAction::assert_eq(
+            "try { crypto.getRandomValues(new Float64Array(1)) } catch (e) { e.name }",
+            js_string!("TypeMismatchError"),
+        ),
+        TestAction::assert_eq(
+            "try { crypto.getRandomValues(new Uint8Array(65537)) } catch (e) { e.name }",
+            js_string!("QuotaExceededError"),
+        ),
+        TestAction::assert_eq(
+            "crypto.getRandomValues(new Uint8Array(65536)).length",
+            65536,
+        ),
+        TestAction::assert_eq(
+            "try { crypto.getRandomValues([1, 2]) } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+        TestAction::assert_eq(
+            "Object.prototype.toString.call(crypto)",
+            js_string!("[object Crypto]"),
+        ),
+    ]);
+}
+
+#[test]
+fn seeded_random_is_deterministic() {
+    run_test_actions([
+        TestAction::inspect_context(|context| {
+            for (name, seed) in [("a", 7), ("b", 7), ("c", 8)] {
+                let crypto = Crypto::init(SeededRandom::new(seed), context);
+                context
+                    .global_object()
+                    .set(JsString::from(name), crypto, false, context)
+                    .unwrap();
+            }
+        }),
+        TestAction::run(indoc! {r#"
+            const values = (crypto) =>
+                crypto.getRandomValues(new Uint8Array(12)).join() + crypto.randomUUID();
+        "#}),
+        TestAction::assert("values(a) === values(b)"),
+        TestAction::assert("values(a) !== values(c)"),
+    ]);
+}
+
+#[test]
+fn random_uuid() {
+    run_test_actions([
+        TestAction::inspect_context(|context| {
+            Crypto::register(SeededRandom::new(1), context).expect("failed to register crypto");
+        }),
+        TestAction::run("const uuid = crypto.randomUUID();"),
+        TestAction::assert_eq(
+            "/^[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$/.test(uuid)",
+            true,
+        ),
+        TestAction::assert_eq("crypto.randomUUID() !== uuid", true),
+    ]);
+
+    run_test_actions([
+        TestAction::inspect_context(|context| {
+            Crypto::register(Exhausted, context).expect("failed to register crypto");
+        }),
+        TestAction::assert_eq(
+            "try { crypto.randomUUID() } catch (e) { e.message }",
+            js_string!("no entropy"),
+        ),
+    ]);
+}
+
+#[test]
+fn digest() {
+    run_test_actions([
+        TestAction::inspect_context(|context| {
+            Crypto::register(SeededRandom::new(0), context).expect("failed to register crypto");
+        }),
+        TestAction::run(indoc! {r#"
+            const hex = (buffer) => Array.from(new Uint8Array(buffer))
+                .map((b) => b.toString(16).padStart(2, "0"))
+                .join("");
+            const data = new TextEncoder().encode("abc");
+            const results = {};
+            crypto.subtle.digest("SHA-256", data).then((d) => { results.sha256 = hex(d); });
+            crypto.subtle.digest({ name: "sha-1" }, data.buffer)
+                .then((d) => { results.sha1 = hex(d); });
+            crypto.subtle.digest("SHA-384", new DataView(data.buffer))
+                .then((d) => { results.sha384 = d.byteLength; });
+            crypto.subtle.digest("SHA-512", new Uint8Array())
+                .then((d) => { results.sha512 = d.byteLength; });
+            crypto.subtle.digest("MD5", data).catch((e) => { results.md5 = e.name; });
+            crypto.subtle.digest("SHA-256", "abc").catch((e) => { results.string = e.name; });
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq(
+            "results.sha256",
+            js_string!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+        ),
+        TestAction::assert_eq(
+            "results.sha1",
+            js_string!("a9993e364706816aba3e25717850c26c9cd0d89d"),
+        ),
+        TestAction::assert_eq("results.sha384", 48),
+        TestAction::assert_eq("results.sha512", 64),
+        TestAction::assert_eq("results.md5", js_string!("NotSupportedError")),
+        TestAction::assert_eq("results.string", js_string!("TypeError")),
+        TestAction::assert_eq(
+            "Object.prototype.toString.call(crypto.subtle)",
+            js_string!("[object SubtleCrypto]"),
+        ),
+    ]);
+}
@@ -0,0 +1,490 @@
+//! Boa's implementation of an event loop, separating the macrotasks (e.g. timers) from the
+//! microtasks (e.g. promise jobs).
+//!
+//! More information:
+//!  - [HTML specification][spec]
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/webappapis.html#event-loops
+
+use boa_engine::{
+    builtins::promise::PromiseState,
+    job::{FutureJob, JobQueue, NativeJob},
+    object::builtins::{JsFunction, JsPromise},
+    Context, JsData, JsError, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, VecDeque},
+    fmt::Debug,
+    rc::Rc,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    time::{Duration, Instant},
+};
+
+/// The clock used by the event loop to schedule its timers.
+#[derive(Debug)]
+enum Clock {
+    /// Wall-clock time, measured from the creation of the event loop.
+    Real(Instant),
+    /// Virtual time, which only advances when the event loop is waiting for a timer.
+    Virtual(Cell<Duration>),
+}
+
+/// The smallest delay between two runs of an interval.
+pub(crate) const MIN_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The nesting level of the timers after which their delays are clamped to
+/// [`MIN_NESTED_DELAY`].
+const MAX_UNCLAMPED_NESTING: u32 = 5;
+
+/// The smallest delay of the timers scheduled by deeply nested timers.
+const MIN_NESTED_DELAY: Duration = Duration::from_millis(4);
+
+/// A timer scheduled by `setTimeout` or `setInterval`.
+#[derive(Clone)]
+pub(crate) struct Timer {
+    pub(crate) callback: JsFunction,
+    pub(crate) args: Vec<JsValue>,
+    pub(crate) interval: Option<Duration>,
+}
+
+/// A function reporting the uncaught errors of an event loop, set by
+/// [`EventLoop::set_error_reporter`].
+type ErrorReporter = dyn Fn(&JsError, &mut Context);
+
+/// The event loop of a context, stored in the host defined data of its realm to report the
+/// uncaught errors of the code run outside of the tasks of the event loop, e.g. event listeners.
+#[derive(Trace, Finalize, JsData)]
+struct EventLoopHost {
+    // SAFETY: See the garbage collection section of `EventLoop`.
+    #[unsafe_ignore_trace]
+    event_loop: Rc<EventLoop>,
+}
+
+/// A task sent to an event loop from another thread, e.g. a message posted by a worker.
+pub(crate) type RemoteTask = Box<dyn FnOnce(&EventLoop, &mut Context) + Send>;
+
+/// A handle to send [`RemoteTask`]s to an event loop from any thread.
+#[derive(Debug, Clone)]
+pub(crate) struct RemoteSender(Sender<RemoteTask>);
+
+impl RemoteSender {
+    /// Creates a channel of remote tasks, whose receiver is given to
+    /// [`EventLoop::with_remote_channel`].
+    pub(crate) fn channel() -> (Self, Receiver<RemoteTask>) {
+        let (sender, receiver) = mpsc::channel();
+        (Self(sender), receiver)
+    }
+
+    /// Sends `task` to the event loop, returning `false` if the event loop was dropped.
+    pub(crate) fn send(&self, task: RemoteTask) -> bool {
+        self.0.send(task).is_ok()
+    }
+}
+
+/// An event loop [`JobQueue`] that keeps macrotasks and microtasks in separate queues.
+///
+/// Every time a macrotask (e.g. a timer callback) finishes, the event loop performs a microtask
+/// checkpoint, running all the pending microtasks (e.g. promise jobs) before the next macrotask.
+/// [`JobQueue::run_jobs`] runs the loop until both queues are empty and no worker can send any more
+/// tasks, so it blocks for as long as a timer is pending (forever with `setInterval`) or a worker is
+/// running. Use [`EventLoop::run_until_settled`] or [`EventLoop::advance`] to only run part of it.
+///
+/// By default, timers are scheduled using the system time, blocking the current thread until the
+/// next timer is due. An event loop created with [`EventLoop::with_virtual_time`] instead skips
+/// directly to the next timer, which is useful to write deterministic tests.
+///
+/// Other threads (e.g. workers) can also send tasks to the event loop, which are run as soon as
+/// the event loop is idle or waiting for a timer.
+///
+/// Uncaught errors thrown by a task are reported to the standard error, and don't stop the event
+/// loop. They can be reported elsewhere with [`EventLoop::set_error_reporter`].
+///
+/// # Garbage collection
+///
+/// `EventLoop` doesn't implement [`Trace`], so the garbage collected values of its tasks (e.g. the
+/// callbacks of the timers) are never unrooted, and stay alive as long as their task is queued.
+/// The event loop can therefore be stored in traced data with `#[unsafe_ignore_trace]`, which is
+/// how the runtime keeps it in the host defined data of a realm.
+pub struct EventLoop {
+    microtasks: RefCell<VecDeque<NativeJob>>,
+    /// The scheduled timers, with their nesting level.
+    timers: RefCell<BTreeMap<(Duration, u32), (Timer, u32)>>,
+    next_timer_id: Cell<u32>,
+    /// The nesting level of the timer whose callback is running, or `0` outside of timers.
+    timer_nesting: Cell<u32>,
+    clock: Clock,
+    remote_sender: RemoteSender,
+    remote_tasks: Receiver<RemoteTask>,
+    /// The number of remote sources (e.g. running workers) that can still send tasks, which keep
+    /// [`JobQueue::run_jobs`] waiting for them.
+    remote_sources: Cell<usize>,
+    /// Whether the event loop was shut down, discarding all its tasks.
+    shut_down: Cell<bool>,
+    error_reporter: RefCell<Option<Rc<ErrorReporter>>>,
+}
+
+impl Debug for EventLoop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventLoop")
+            .field("clock", &self.clock)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for EventLoop {
+    fn default() -> Self {
+        Self::with_clock(Clock::Real(Instant::now()))
+    }
+}
+
+impl EventLoop {
+    /// Creates a new `EventLoop` that schedules its timers using the system time.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `EventLoop` that schedules its timers using virtual time.
+    ///
+    /// Instead of waiting for a timer to be due, the event loop advances its clock directly to the
+    /// time of the next timer.
+    #[must_use]
+    pub fn with_virtual_time() -> Self {
+        Self::with_clock(Clock::Virtual(Cell::new(Duration::ZERO)))
+    }
+
+    /// Creates a new `EventLoop` that schedules its timers using the system time, receiving its
+    /// remote tasks from a channel created by [`RemoteSender::channel`].
+    pub(crate) fn with_remote_channel(
+        (remote_sender, remote_tasks): (RemoteSender, Receiver<RemoteTask>),
+    ) -> Self {
+        Self {
+            microtasks: RefCell::default(),
+            timers: RefCell::default(),
+            next_timer_id: Cell::new(1),
+            timer_nesting: Cell::new(0),
+            clock: Clock::Real(Instant::now()),
+            remote_sender,
+            remote_tasks,
+            remote_sources: Cell::new(0),
+            shut_down: Cell::new(false),
+            error_reporter: RefCell::default(),
+        }
+    }
+
+    fn with_clock(clock: Clock) -> Self {
+        Self {
+            clock,
+            ..Self::with_remote_channel(RemoteSender::channel())
+        }
+    }
+
+    /// Gets the time elapsed since the creation of the event loop.
+    #[must_use]
+    pub fn now(&self) -> Duration {
+        match &self.clock {
+            Clock::Real(start) => start.elapsed(),
+            Clock::Virtual(now) => now.get(),
+        }
+    }
+
+    /// Returns `true` if there are no pending microtasks or timers in the event loop.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.microtasks.borrow().is_empty() && self.timers.borrow().is_empty()
+    }
+
+    /// Runs all the tasks that are scheduled to run in the next `duration`, then advances the clock
+    /// of the event loop by `duration`.
+    ///
+    /// Using the system time, this blocks the current thread for `duration`.
+    pub fn advance(&self, duration: Duration, context: &mut Context) {
+        self.run_until(Some(self.now() + duration), || false, context);
+    }
+
+    /// Runs the event loop until `promise` is settled, or until there are no more tasks that
+    /// could settle it.
+    ///
+    /// Unlike [`JobQueue::run_jobs`], this returns as soon as `promise` is settled, leaving the
+    /// remaining tasks (e.g. the timers scheduled by the code awaiting `promise`) in the queues.
+    pub fn run_until_settled(&self, promise: &JsPromise, context: &mut Context) {
+        self.run_until(
+            None,
+            || !matches!(promise.state(), PromiseState::Pending),
+            context,
+        );
+    }
+
+    /// Sets the function called with the uncaught errors thrown by the tasks of the event loop
+    /// (e.g. timer callbacks) and by the event listeners of the context, which are printed to the
+    /// standard error by default.
+    ///
+    /// ```
+    /// use boa_engine::{context::ContextBuilder, Source};
+    /// use boa_runtime::{EventLoop, Timers};
+    /// use std::{cell::RefCell, rc::Rc};
+    ///
+    /// let event_loop = Rc::new(EventLoop::with_virtual_time());
+    /// let errors = Rc::new(RefCell::new(Vec::new()));
+    /// let reported = errors.clone();
+    /// event_loop.set_error_reporter(move |error, _| {
+    ///     reported.borrow_mut().push(error.to_string());
+    /// });
+    ///
+    /// let mut context = ContextBuilder::new()
+    ///     .job_queue(event_loop.clone())
+    ///     .build()?;
+    /// Timers::register(&event_loop, &mut context)?;
+    /// context.eval(Source::from_bytes("setTimeout(() => { throw 'oops'; });"))?;
+    /// context.run_jobs();
+    ///
+    /// assert_eq!(*errors.borrow(), ["\"oops\""]);
+    /// # Ok::<(), boa_engine::JsError>(())
+    /// ```
+    pub fn set_error_reporter<F>(&self, reporter: F)
+    where
+        F: Fn(&JsError, &mut Context) + 'static,
+    {
+        *self.error_reporter.borrow_mut() = Some(Rc::new(reporter));
+    }
+
+    /// Reports an uncaught `error` with the reporter set by [`EventLoop::set_error_reporter`],
+    /// or to the standard error if there is none.
+    pub fn report_error(&self, error: &JsError, context: &mut Context) {
+        let reporter = self.error_reporter.borrow().clone();
+        match reporter {
+            Some(reporter) => reporter(error, context),
+            None => eprintln!("Uncaught {error}"),
+        }
+    }
+
+    /// Stores `event_loop` in the context, to report the uncaught errors of the code run outside
+    /// of its tasks with [`EventLoop::report_uncaught`].
+    pub(crate) fn register_host(event_loop: &Rc<Self>, context: &mut Context) {
+        context.realm().host_defined_mut().insert(EventLoopHost {
+            event_loop: event_loop.clone(),
+        });
+    }
+
+    /// Gets the event loop registered in the context by [`EventLoop::register_host`].
+    pub(crate) fn of(context: &Context) -> Option<Rc<Self>> {
+        context
+            .realm()
+            .host_defined()
+            .get::<EventLoopHost>()
+            .map(|host| host.event_loop.clone())
+    }
+
+    /// Reports an uncaught `error` with the event loop registered in the context by
+    /// [`EventLoop::register_host`], or to the standard error if there is none.
+    pub(crate) fn report_uncaught(error: &JsError, context: &mut Context) {
+        match Self::of(context) {
+            Some(event_loop) => event_loop.report_error(error, context),
+            None => eprintln!("Uncaught {error}"),
+        }
+    }
+
+    /// Schedules a new timer after `delay`, returning its identifier.
+    ///
+    /// Timers scheduled by deeply nested timers are delayed by at least 4 milliseconds, as
+    /// required by the [timer initialization steps][spec], which keeps chains of timers with a
+    /// delay of `0` from stopping the clock of the event loop.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#timer-initialisation-steps
+    pub(crate) fn add_timer(&self, timer: Timer, delay: Duration) -> u32 {
+        let id = self.next_timer_id.get();
+        self.next_timer_id.set(id.wrapping_add(1).max(1));
+        self.insert_timer(timer, delay, self.timer_nesting.get() + 1, id);
+        id
+    }
+
+    /// Inserts the timer `id` with the given nesting level, to run after `delay`.
+    fn insert_timer(&self, timer: Timer, delay: Duration, nesting: u32, id: u32) {
+        let delay = if nesting > MAX_UNCLAMPED_NESTING {
+            delay.max(MIN_NESTED_DELAY)
+        } else {
+            delay
+        };
+        self.timers
+            .borrow_mut()
+            .insert((self.now() + delay, id), (timer, nesting));
+    }
+
+    /// Cancels the timer with identifier `id`, if it exists.
+    pub(crate) fn remove_timer(&self, id: u32) {
+        self.timers
+            .borrow_mut()
+            .retain(|&(_, timer_id), _| timer_id != id);
+    }
+
+    /// Gets a handle to send tasks to this event loop from other threads.
+    pub(crate) fn remote_sender(&self) -> RemoteSender {
+        self.remote_sender.clone()
+    }
+
+    /// Registers a remote source, which keeps [`JobQueue::run_jobs`] waiting for remote tasks
+    /// until it is released by [`EventLoop::release_remote`].
+    pub(crate) fn retain_remote(&self) {
+        self.remote_sources.set(self.remote_sources.get() + 1);
+    }
+
+    /// Releases a remote source registered by [`EventLoop::retain_remote`].
+    pub(crate) fn release_remote(&self) {
+        self.remote_sources
+            .set(self.remote_sources.get().saturating_sub(1));
+    }
+
+    /// Shuts down the event loop, discarding all its pending tasks. The tasks enqueued afterwards
+    /// are never run.
+    pub(crate) fn shutdown(&self) {
+        self.shut_down.set(true);
+        self.microtasks.borrow_mut().clear();
+        self.timers.borrow_mut().clear();
+        self.remote_sources.set(0);
+    }
+
+    /// Returns `true` if the event loop was shut down by [`EventLoop::shutdown`].
+    pub(crate) fn is_shut_down(&self) -> bool {
+        self.shut_down.get()
+    }
+
+    /// Blocks until a remote task is received, then runs it along with all the tasks it
+    /// schedules.
+    pub(crate) fn run_next_remote_task(&self, context: &mut Context) {
+        if let Ok(task) = self.remote_tasks.recv() {
+            self.run_remote_task(task, context);
+            self.run_until(None, || false, context);
+        }
+    }
+
+    /// Runs a remote task, unless the event loop was shut down.
+    fn run_remote_task(&self, task: RemoteTask, context: &mut Context) {
+        if !self.shut_down.get() {
+            task(self, context);
+        }
+    }
+
+    /// Blocks until the clock reaches `time`, returning `false` if a remote task was run in the
+    /// meantime.
+    fn wait_until(&self, time: Duration, context: &mut Context) -> bool {
+        match &self.clock {
+            Clock::Real(start) => {
+                if let Some(remaining) = time.checked_sub(start.elapsed()) {
+                    match self.remote_tasks.recv_timeout(remaining) {
+                        Ok(task) => {
+                            self.run_remote_task(task, context);
+                            return false;
+                        }
+                        Err(RecvTimeoutError::Timeout) => {}
+                        // Unreachable, since the event loop holds a sender.
+                        Err(RecvTimeoutError::Disconnected) => std::thread::sleep(remaining),
+                    }
+                }
+            }
+            Clock::Virtual(now) => {
+                if time > now.get() {
+                    now.set(time);
+                }
+            }
+        }
+        true
+    }
+
+    /// Runs all the pending microtasks, including the ones enqueued while running them.
+    fn run_microtasks(&self, context: &mut Context) {
+        loop {
+            if self.shut_down.get() {
+                return;
+            }
+            let job = self.microtasks.borrow_mut().pop_front();
+            let Some(job) = job else {
+                return;
+            };
+            if let Err(e) = job.call(context) {
+                self.report_error(&e, context);
+            }
+        }
+    }
+
+    /// Runs the event loop until there are no more tasks due before `deadline`, or until `done`
+    /// returns `true` after a microtask checkpoint.
+    fn run_until<F>(&self, deadline: Option<Duration>, done: F, context: &mut Context)
+    where
+        F: Fn() -> bool,
+    {
+        loop {
+            self.run_microtasks(context);
+            if self.shut_down.get() || done() {
+                return;
+            }
+            if let Ok(task) = self.remote_tasks.try_recv() {
+                self.run_remote_task(task, context);
+                continue;
+            }
+
+            let next = self.timers.borrow().keys().next().copied();
+            let Some(key @ (due, id)) =
+                next.filter(|&(due, _)| deadline.map_or(true, |deadline| due <= deadline))
+            else {
+                match deadline {
+                    Some(deadline) if !self.wait_until(deadline, context) => continue,
+                    None if self.remote_sources.get() > 0 => {
+                        if let Ok(task) = self.remote_tasks.recv() {
+                            self.run_remote_task(task, context);
+                            continue;
+                        }
+                    }
+                    Some(_) | None => {}
+                }
+                break;
+            };
+
+            if !self.wait_until(due, context) {
+                continue;
+            }
+
+            let Some((timer, nesting)) = self.timers.borrow_mut().remove(&key) else {
+                continue;
+            };
+
+            // Reschedule the interval before running the callback, so that the callback is able
+            // to cancel it. The interval is never `0`, otherwise it would run forever without
+            // advancing the clock.
+            if let Some(interval) = timer.interval {
+                self.insert_timer(timer.clone(), interval.max(MIN_INTERVAL), nesting + 1, id);
+            }
+
+            self.timer_nesting.set(nesting);
+            let result = timer
+                .callback
+                .call(&JsValue::undefined(), &timer.args, context);
+            self.timer_nesting.set(0);
+            if let Err(e) = result {
+                self.report_error(&e, context);
+            }
+        }
+    }
+}
+
+impl JobQueue for EventLoop {
+    fn enqueue_promise_job(&self, job: NativeJob, _: &mut Context) {
+        if !self.shut_down.get() {
+            self.microtasks.borrow_mut().push_back(job);
+        }
+    }
+
+    /// Runs the event loop until there are no more microtasks, timers or running workers.
+    ///
+    /// This blocks the current thread until the last timer is due and every worker has exited, and
+    /// never returns while an interval is scheduled.
+    fn run_jobs(&self, context: &mut Context) {
+        self.run_until(None, || false, context);
+    }
+
+    fn enqueue_future_job(&self, future: FutureJob, context: &mut Context) {
+        let job = pollster::block_on(future);
+        self.enqueue_promise_job(job, context);
+    }
+}
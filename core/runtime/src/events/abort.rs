@@ -0,0 +1,534 @@
+//! Boa's implementation of the `AbortController` and `AbortSignal` objects.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `DOM` specification][spec]
+//!
+//! [spec]: https://dom.spec.whatwg.org/#aborting-ongoing-activities
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/AbortController
+
+use super::{event::Event, target::EventTarget};
+use crate::{
+    event_loop::{EventLoop, Timer},
+    util::{add_getter, iterable_to_list},
+};
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::{builtins::JsFunction, JsObject},
+    property::Attribute,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsString, JsSymbol, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::time::Duration;
+
+/// Creates an `Error` object with the given name, used in place of a `DOMException`.
+pub(crate) fn create_error(
+    name: JsString,
+    message: &str,
+    context: &mut Context,
+) -> JsResult<JsValue> {
+    let error = JsNativeError::error()
+        .with_message(message)
+        .to_opaque(context);
+    error.set(js_string!("name"), name, false, context)?;
+    Ok(error.into())
+}
+
+/// The `AbortSignal` class, an `EventTarget` that is notified when an operation is aborted.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `DOM` specification][spec]
+///
+/// [spec]: https://dom.spec.whatwg.org/#interface-AbortSignal
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal
+#[derive(Debug, Clone, Default, Trace, Finalize, JsData)]
+pub struct AbortSignal {
+    target: EventTarget,
+    /// The abort reason, which is `None` while the signal is not aborted.
+    reason: Option<JsValue>,
+    /// The functions to call when the signal is aborted.
+    algorithms: Vec<JsFunction>,
+    /// Whether the signal was created by `AbortSignal.any`.
+    dependent: bool,
+    /// The signals a dependent signal follows.
+    sources: Vec<JsObject>,
+    /// The dependent signals following this signal.
+    dependents: Vec<JsObject>,
+}
+
+impl AbortSignal {
+    /// Creates the data of a signal without listeners.
+    fn new(reason: Option<JsValue>, dependent: bool, sources: Vec<JsObject>) -> Self {
+        Self {
+            target: EventTarget::default(),
+            reason,
+            algorithms: Vec::new(),
+            dependent,
+            sources,
+            dependents: Vec::new(),
+        }
+    }
+
+    /// Returns the `EventTarget` data of the signal.
+    pub(super) fn target_mut(&mut self) -> &mut EventTarget {
+        &mut self.target
+    }
+
+    /// Creates a new `AbortSignal` object that is not aborted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `AbortSignal` class is not registered in the context.
+    pub fn create(context: &mut Context) -> JsResult<JsObject> {
+        Self::from_data(Self::default(), context)
+    }
+
+    /// Returns `true` if `signal` is an aborted `AbortSignal`.
+    #[must_use]
+    pub fn is_aborted(signal: &JsObject) -> bool {
+        signal
+            .downcast_ref::<Self>()
+            .is_some_and(|signal| signal.reason.is_some())
+    }
+
+    /// Returns the abort reason of `signal`, which is `None` if it is not an aborted
+    /// `AbortSignal`.
+    #[must_use]
+    pub fn reason(signal: &JsObject) -> Option<JsValue> {
+        signal
+            .downcast_ref::<Self>()
+            .and_then(|signal| signal.reason.clone())
+    }
+
+    /// Adds a function to call when `signal` is aborted, before the `abort` event is dispatched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `signal` is not an `AbortSignal`.
+    pub fn add_algorithm(signal: &JsObject, algorithm: JsFunction) {
+        let mut signal = signal
+            .downcast_mut::<Self>()
+            .expect("must be an AbortSignal");
+        if signal.reason.is_none() {
+            signal.algorithms.push(algorithm);
+        }
+    }
+
+    /// [Signals abort][spec] on `signal` with `reason`, or with an `AbortError` if `reason` is
+    /// `undefined`.
+    ///
+    /// This does nothing if the signal is already aborted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the default reason or the `abort` event cannot be created.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `signal` is not an `AbortSignal`.
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#abortsignal-signal-abort
+    pub fn abort(signal: &JsObject, reason: JsValue, context: &mut Context) -> JsResult<()> {
+        if Self::is_aborted(signal) {
+            return Ok(());
+        }
+        let reason = if reason.is_undefined() {
+            create_error(
+                js_string!("AbortError"),
+                "This operation was aborted",
+                context,
+            )?
+        } else {
+            reason
+        };
+
+        // The reason of the dependent signals is set before running any abort steps.
+        let mut aborted = vec![signal.clone()];
+        let dependents = {
+            let mut data = signal
+                .downcast_mut::<Self>()
+                .expect("must be an AbortSignal");
+            data.reason = Some(reason.clone());
+            std::mem::take(&mut data.dependents)
+        };
+        for dependent in dependents {
+            let mut data = dependent
+                .downcast_mut::<Self>()
+                .expect("must be an AbortSignal");
+            if data.reason.is_none() {
+                data.reason = Some(reason.clone());
+                drop(data);
+                aborted.push(dependent);
+            }
+        }
+
+        for signal in aborted {
+            Self::run_abort_steps(&signal, context)?;
+        }
+        Ok(())
+    }
+
+    /// Runs the abort algorithms of an aborted signal and dispatches its `abort` event.
+    fn run_abort_steps(signal: &JsObject, context: &mut Context) -> JsResult<()> {
+        let algorithms = std::mem::take(
+            &mut signal
+                .downcast_mut::<Self>()
+                .expect("must be an AbortSignal")
+                .algorithms,
+        );
+        for algorithm in algorithms {
+            if let Err(e) = algorithm.call(&JsValue::undefined(), &[], context) {
+                EventLoop::report_uncaught(&e, context);
+            }
+        }
+        let event = Event::create_trusted(js_string!("abort"), context)?;
+        EventTarget::dispatch(signal, &event, context)?;
+        Ok(())
+    }
+
+    /// Creates a signal following all of `signals`, which is aborted as soon as one of them is.
+    fn create_dependent(signals: &[JsObject], context: &mut Context) -> JsResult<JsObject> {
+        if let Some(aborted) = signals.iter().find(|signal| Self::is_aborted(signal)) {
+            let reason = aborted
+                .downcast_ref::<Self>()
+                .expect("must be an AbortSignal")
+                .reason
+                .clone();
+            return Self::from_data(Self::new(reason, false, Vec::new()), context);
+        }
+
+        let mut sources: Vec<JsObject> = Vec::new();
+        for signal in signals {
+            let data = signal
+                .downcast_ref::<Self>()
+                .expect("must be an AbortSignal");
+            // Dependent signals are never aborted directly, so they follow their sources instead.
+            let signal_sources = if data.dependent {
+                data.sources.clone()
+            } else {
+                vec![signal.clone()]
+            };
+            for source in signal_sources {
+                if !sources.contains(&source) {
+                    sources.push(source);
+                }
+            }
+        }
+
+        let dependent = Self::from_data(Self::new(None, true, sources.clone()), context)?;
+        for source in sources {
+            source
+                .downcast_mut::<Self>()
+                .expect("must be an AbortSignal")
+                .dependents
+                .push(dependent.clone());
+        }
+        Ok(dependent)
+    }
+
+    /// Creates the `AbortSignal.timeout` function, which schedules its timers on `event_loop`.
+    pub(super) fn timeout_function(event_loop: std::rc::Rc<EventLoop>) -> NativeFunction {
+        // SAFETY: `EventLoop` doesn't need to be traced, since all the objects it contains are
+        // rooted.
+        unsafe {
+            NativeFunction::from_closure(move |_, args, context| {
+                Self::timeout(args, &event_loop, context)
+            })
+        }
+    }
+
+    /// `AbortSignal.timeout(milliseconds)`
+    ///
+    /// Returns a signal that is aborted with a `TimeoutError` after `milliseconds`.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-abortsignal-timeout
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal/timeout_static
+    fn timeout(
+        args: &[JsValue],
+        event_loop: &EventLoop,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let milliseconds = args.get_or_undefined(0).to_number(context)?;
+        if !milliseconds.is_finite() || milliseconds < 0.0 {
+            return Err(JsNativeError::typ()
+                .with_message("the timeout must be a non-negative finite number")
+                .into());
+        }
+        let signal = Self::create(context)?;
+        let callback = NativeFunction::from_copy_closure_with_captures(
+            |_, _, signal, context| {
+                let reason = create_error(
+                    js_string!("TimeoutError"),
+                    "The operation timed out",
+                    context,
+                )?;
+                Self::abort(signal, reason, context)?;
+                Ok(JsValue::undefined())
+            },
+            signal.clone(),
+        )
+        .to_js_function(context.realm());
+        let timer = Timer {
+            callback,
+            args: Vec::new(),
+            interval: None,
+        };
+        event_loop.add_timer(
+            timer,
+            Duration::from_secs_f64(milliseconds.trunc() / 1000.0),
+        );
+        Ok(signal.into())
+    }
+
+    /// `AbortSignal.abort(reason)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-abortsignal-abort
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal/abort_static
+    fn abort_static(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let signal = Self::create(context)?;
+        Self::abort(&signal, args.get_or_undefined(0).clone(), context)?;
+        Ok(signal.into())
+    }
+
+    /// `AbortSignal.any(signals)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-abortsignal-any
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal/any_static
+    fn any(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let signals = iterable_to_list(
+            args.get_or_undefined(0),
+            None,
+            |signal, _| {
+                signal
+                    .as_object()
+                    .filter(|signal| signal.is::<Self>())
+                    .cloned()
+                    .ok_or_else(|| {
+                        JsNativeError::typ()
+                            .with_message("AbortSignal.any: every item must be an AbortSignal")
+                            .into()
+                    })
+            },
+            context,
+        )?;
+        Ok(Self::create_dependent(&signals, context)?.into())
+    }
+
+    /// `get AbortSignal.prototype.aborted`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-abortsignal-aborted
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal/aborted
+    fn get_aborted(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::is_aborted(Self::this_signal(this)?).into())
+    }
+
+    /// `get AbortSignal.prototype.reason`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-abortsignal-reason
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal/reason
+    fn get_reason(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let signal = Self::this_signal(this)?;
+        let signal = signal.downcast_ref::<Self>().expect("checked above");
+        Ok(signal.reason.clone().unwrap_or_default())
+    }
+
+    /// `AbortSignal.prototype.throwIfAborted()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-abortsignal-throwifaborted
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal/throwIfAborted
+    fn throw_if_aborted(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let signal = Self::this_signal(this)?;
+        let signal = signal.downcast_ref::<Self>().expect("checked above");
+        match &signal.reason {
+            Some(reason) => Err(boa_engine::JsError::from_opaque(reason.clone())),
+            None => Ok(JsValue::undefined()),
+        }
+    }
+
+    /// `get AbortSignal.prototype.onabort`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-abortsignal-onabort
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal/abort_event
+    fn get_onabort(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let signal = Self::this_signal(this)?;
+        Ok(EventTarget::event_handler(signal, &js_string!("abort")))
+    }
+
+    /// `set AbortSignal.prototype.onabort`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-abortsignal-onabort
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/AbortSignal/abort_event
+    fn set_onabort(this: &JsValue, args: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let signal = Self::this_signal(this)?;
+        EventTarget::set_event_handler(signal, &js_string!("abort"), args.get_or_undefined(0));
+        Ok(JsValue::undefined())
+    }
+
+    /// Checks that `this` is an `AbortSignal` object, returning it.
+    fn this_signal(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not an AbortSignal object")
+                    .into()
+            })
+    }
+}
+
+impl Class for AbortSignal {
+    const NAME: &'static str = "AbortSignal";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        let realm = class.context().realm().clone();
+        let get_onabort = NativeFunction::from_fn_ptr(Self::get_onabort).to_js_function(&realm);
+        let set_onabort = NativeFunction::from_fn_ptr(Self::set_onabort).to_js_function(&realm);
+
+        add_getter(class, js_string!("aborted"), Self::get_aborted);
+        add_getter(class, js_string!("reason"), Self::get_reason);
+        class
+            .accessor(
+                js_string!("onabort"),
+                Some(get_onabort),
+                Some(set_onabort),
+                Attribute::CONFIGURABLE | Attribute::ENUMERABLE,
+            )
+            .method(
+                js_string!("throwIfAborted"),
+                0,
+                NativeFunction::from_fn_ptr(Self::throw_if_aborted),
+            )
+            .static_method(
+                js_string!("abort"),
+                0,
+                NativeFunction::from_fn_ptr(Self::abort_static),
+            )
+            .static_method(js_string!("any"), 1, NativeFunction::from_fn_ptr(Self::any))
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("AbortSignal"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<Self> {
+        Err(JsNativeError::typ()
+            .with_message("AbortSignal cannot be constructed directly")
+            .into())
+    }
+}
+
+/// The `AbortController` class, which aborts its `AbortSignal` on request.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `DOM` specification][spec]
+///
+/// [spec]: https://dom.spec.whatwg.org/#interface-abortcontroller
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/AbortController
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct AbortController {
+    signal: JsObject,
+}
+
+impl AbortController {
+    /// Returns the `AbortSignal` of `this`.
+    fn this_signal(this: &JsValue) -> JsResult<JsObject> {
+        this.as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .map(|controller| controller.signal.clone())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not an AbortController object")
+                    .into()
+            })
+    }
+
+    /// `get AbortController.prototype.signal`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-abortcontroller-signal
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/AbortController/signal
+    fn get_signal(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::this_signal(this)?.into())
+    }
+
+    /// `AbortController.prototype.abort(reason)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-abortcontroller-abort
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/AbortController/abort
+    fn abort(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let signal = Self::this_signal(this)?;
+        AbortSignal::abort(&signal, args.get_or_undefined(0).clone(), context)?;
+        Ok(JsValue::undefined())
+    }
+}
+
+impl Class for AbortController {
+    const NAME: &'static str = "AbortController";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(class, js_string!("signal"), Self::get_signal);
+        class
+            .method(
+                js_string!("abort"),
+                0,
+                NativeFunction::from_fn_ptr(Self::abort),
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("AbortController"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        Ok(Self {
+            signal: AbortSignal::create(context)?,
+        })
+    }
+}
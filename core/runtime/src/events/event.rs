@@ -0,0 +1,600 @@
+//! Boa's implementation of the `Event` and `CustomEvent` objects.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `DOM` specification][spec]
+//!
+//! [spec]: https://dom.spec.whatwg.org/#interface-event
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event
+
+use crate::util::add_getter;
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::{builtins::JsArray, ErasedObject, JsObject, RefMut},
+    property::Attribute,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsString, JsValue,
+};
+use boa_gc::{Finalize, GcRefMut, Trace};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The `eventPhase` of an event that is not being dispatched.
+const NONE: u16 = 0;
+/// The `eventPhase` of an event that is being dispatched to its target.
+const AT_TARGET: u16 = 2;
+
+/// The `Event` class, which represents an event dispatched to an `EventTarget`.
+///
+/// The native classes inheriting from `Event` store an `Event` in their data, which is retrieved
+/// by [`Event::from_object`].
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Event {
+    kind: JsString,
+    bubbles: bool,
+    cancelable: bool,
+    composed: bool,
+    /// The "canceled flag", set by `preventDefault`.
+    canceled: bool,
+    stop_propagation: bool,
+    stop_immediate_propagation: bool,
+    dispatching: bool,
+    in_passive_listener: bool,
+    trusted: bool,
+    phase: u16,
+    time_stamp: f64,
+    target: Option<JsObject>,
+    current_target: Option<JsObject>,
+}
+
+impl Event {
+    /// Creates a new, untrusted `Event` of type `event_type`.
+    #[must_use]
+    pub fn new(event_type: JsString, bubbles: bool, cancelable: bool) -> Self {
+        let time_stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0.0, |time| time.as_secs_f64() * 1000.0);
+        Self {
+            kind: event_type,
+            bubbles,
+            cancelable,
+            composed: false,
+            canceled: false,
+            stop_propagation: false,
+            stop_immediate_propagation: false,
+            dispatching: false,
+            in_passive_listener: false,
+            trusted: false,
+            phase: NONE,
+            time_stamp,
+            target: None,
+            current_target: None,
+        }
+    }
+
+    /// Creates a new, trusted `Event` object of type `event_type`, as if it was created by the
+    /// host instead of a script.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `Event` class is not registered in the context.
+    pub fn create_trusted(event_type: JsString, context: &mut Context) -> JsResult<JsObject> {
+        let mut event = Self::new(event_type, false, false);
+        event.trusted = true;
+        Self::from_data(event, context)
+    }
+
+    /// Gets a mutable reference to the `Event` data of an object, if it is an `Event` or an
+    /// instance of one of its native subclasses.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object is currently borrowed.
+    #[must_use]
+    pub fn from_object(object: &JsObject) -> Option<RefMut<'_, ErasedObject, Self>> {
+        if let Some(event) = object.downcast_mut::<Self>() {
+            return Some(event);
+        }
+        object
+            .downcast_mut::<CustomEvent>()
+            .map(|event| GcRefMut::map(event, |event| &mut event.event))
+    }
+
+    /// Returns the type of the event.
+    #[must_use]
+    pub const fn event_type(&self) -> &JsString {
+        &self.kind
+    }
+
+    /// Returns `true` if `preventDefault` was called on the event.
+    #[must_use]
+    pub const fn default_prevented(&self) -> bool {
+        self.canceled
+    }
+
+    /// Reads the options shared by `Event` and its subclasses from the `eventInitDict` argument.
+    fn from_init(event_type: JsString, init: &JsValue, context: &mut Context) -> JsResult<Self> {
+        let mut event = Self::new(event_type, false, false);
+        if let Some(init) = init.as_object() {
+            event.bubbles = init.get(js_string!("bubbles"), context)?.to_boolean();
+            event.cancelable = init.get(js_string!("cancelable"), context)?.to_boolean();
+            event.composed = init.get(js_string!("composed"), context)?.to_boolean();
+        } else if !init.is_null_or_undefined() {
+            return Err(JsNativeError::typ()
+                .with_message("the event options must be an object")
+                .into());
+        }
+        Ok(event)
+    }
+
+    /// Sets the "canceled flag" of the event, if it is cancelable.
+    fn cancel(&mut self) {
+        if self.cancelable && !self.in_passive_listener {
+            self.canceled = true;
+        }
+    }
+
+    /// Gets the `Event` data of `this`.
+    fn this_event(this: &JsValue) -> JsResult<RefMut<'_, ErasedObject, Self>> {
+        this.as_object().and_then(Self::from_object).ok_or_else(|| {
+            JsNativeError::typ()
+                .with_message("'this' is not an Event object")
+                .into()
+        })
+    }
+
+    /// `get Event.prototype.type`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-type
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/type
+    fn get_type(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::this_event(this)?.kind.clone().into())
+    }
+
+    /// `get Event.prototype.target`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-target
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/target
+    fn get_target(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let event = Self::this_event(this)?;
+        Ok(event.target.clone().map_or_else(JsValue::null, Into::into))
+    }
+
+    /// `get Event.prototype.currentTarget`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-currenttarget
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/currentTarget
+    fn get_current_target(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let event = Self::this_event(this)?;
+        Ok(event
+            .current_target
+            .clone()
+            .map_or_else(JsValue::null, Into::into))
+    }
+
+    /// `Event.prototype.composedPath()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-composedpath
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/composedPath
+    fn composed_path(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let current_target = Self::this_event(this)?.current_target.clone();
+        let path = current_target.into_iter().map(Into::into);
+        Ok(JsArray::from_iter(path, context).into())
+    }
+
+    /// `get Event.prototype.eventPhase`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-eventphase
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/eventPhase
+    fn get_event_phase(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::this_event(this)?.phase.into())
+    }
+
+    /// `Event.prototype.stopPropagation()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-stoppropagation
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/stopPropagation
+    fn stop_propagation(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Self::this_event(this)?.stop_propagation = true;
+        Ok(JsValue::undefined())
+    }
+
+    /// `get Event.prototype.cancelBubble`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-cancelbubble
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/cancelBubble
+    fn get_cancel_bubble(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::this_event(this)?.stop_propagation.into())
+    }
+
+    /// `set Event.prototype.cancelBubble`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-cancelbubble
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/cancelBubble
+    fn set_cancel_bubble(this: &JsValue, args: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let mut event = Self::this_event(this)?;
+        if args.get_or_undefined(0).to_boolean() {
+            event.stop_propagation = true;
+        }
+        Ok(JsValue::undefined())
+    }
+
+    /// `Event.prototype.stopImmediatePropagation()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-stopimmediatepropagation
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/stopImmediatePropagation
+    fn stop_immediate_propagation(
+        this: &JsValue,
+        _: &[JsValue],
+        _: &mut Context,
+    ) -> JsResult<JsValue> {
+        let mut event = Self::this_event(this)?;
+        event.stop_propagation = true;
+        event.stop_immediate_propagation = true;
+        Ok(JsValue::undefined())
+    }
+
+    /// `get Event.prototype.bubbles`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-bubbles
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/bubbles
+    fn get_bubbles(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::this_event(this)?.bubbles.into())
+    }
+
+    /// `get Event.prototype.cancelable`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-cancelable
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/cancelable
+    fn get_cancelable(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::this_event(this)?.cancelable.into())
+    }
+
+    /// `get Event.prototype.returnValue`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-returnvalue
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/returnValue
+    fn get_return_value(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok((!Self::this_event(this)?.canceled).into())
+    }
+
+    /// `set Event.prototype.returnValue`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-returnvalue
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/returnValue
+    fn set_return_value(this: &JsValue, args: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let mut event = Self::this_event(this)?;
+        if !args.get_or_undefined(0).to_boolean() {
+            event.cancel();
+        }
+        Ok(JsValue::undefined())
+    }
+
+    /// `Event.prototype.preventDefault()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-preventdefault
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/preventDefault
+    fn prevent_default(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Self::this_event(this)?.cancel();
+        Ok(JsValue::undefined())
+    }
+
+    /// `get Event.prototype.defaultPrevented`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-defaultprevented
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/defaultPrevented
+    fn get_default_prevented(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::this_event(this)?.canceled.into())
+    }
+
+    /// `get Event.prototype.composed`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-composed
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/composed
+    fn get_composed(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::this_event(this)?.composed.into())
+    }
+
+    /// `get Event.prototype.isTrusted`
+    ///
+    /// Returns `true` if the event was dispatched by the host, instead of by `dispatchEvent`.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-istrusted
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/isTrusted
+    fn get_is_trusted(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::this_event(this)?.trusted.into())
+    }
+
+    /// `get Event.prototype.timeStamp`
+    ///
+    /// Returns the creation time of the event, in milliseconds since the Unix epoch.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-event-timestamp
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event/timeStamp
+    fn get_time_stamp(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::this_event(this)?.time_stamp.into())
+    }
+}
+
+/// Adds the members of `Event` to the prototype of a class.
+fn add_event_members(class: &mut ClassBuilder<'_>) {
+    let realm = class.context().realm().clone();
+    let cancel_bubble = (
+        NativeFunction::from_fn_ptr(Event::get_cancel_bubble).to_js_function(&realm),
+        NativeFunction::from_fn_ptr(Event::set_cancel_bubble).to_js_function(&realm),
+    );
+    let return_value = (
+        NativeFunction::from_fn_ptr(Event::get_return_value).to_js_function(&realm),
+        NativeFunction::from_fn_ptr(Event::set_return_value).to_js_function(&realm),
+    );
+
+    add_getter(class, js_string!("type"), Event::get_type);
+    add_getter(class, js_string!("target"), Event::get_target);
+    add_getter(class, js_string!("srcElement"), Event::get_target);
+    add_getter(
+        class,
+        js_string!("currentTarget"),
+        Event::get_current_target,
+    );
+    add_getter(class, js_string!("eventPhase"), Event::get_event_phase);
+    add_getter(class, js_string!("bubbles"), Event::get_bubbles);
+    add_getter(class, js_string!("cancelable"), Event::get_cancelable);
+    add_getter(
+        class,
+        js_string!("defaultPrevented"),
+        Event::get_default_prevented,
+    );
+    add_getter(class, js_string!("composed"), Event::get_composed);
+    add_getter(class, js_string!("isTrusted"), Event::get_is_trusted);
+    add_getter(class, js_string!("timeStamp"), Event::get_time_stamp);
+    class
+        .accessor(
+            js_string!("cancelBubble"),
+            Some(cancel_bubble.0),
+            Some(cancel_bubble.1),
+            Attribute::CONFIGURABLE,
+        )
+        .accessor(
+            js_string!("returnValue"),
+            Some(return_value.0),
+            Some(return_value.1),
+            Attribute::CONFIGURABLE,
+        )
+        .method(
+            js_string!("composedPath"),
+            0,
+            NativeFunction::from_fn_ptr(Event::composed_path),
+        )
+        .method(
+            js_string!("stopPropagation"),
+            0,
+            NativeFunction::from_fn_ptr(Event::stop_propagation),
+        )
+        .method(
+            js_string!("stopImmediatePropagation"),
+            0,
+            NativeFunction::from_fn_ptr(Event::stop_immediate_propagation),
+        )
+        .method(
+            js_string!("preventDefault"),
+            0,
+            NativeFunction::from_fn_ptr(Event::prevent_default),
+        );
+}
+
+/// Gets the required `type` argument of the `Event` constructors.
+fn event_type_arg(args: &[JsValue], context: &mut Context) -> JsResult<JsString> {
+    let Some(event_type) = args.first() else {
+        return Err(JsNativeError::typ()
+            .with_message("the event type is required")
+            .into());
+    };
+    event_type.to_string(context)
+}
+
+impl Class for Event {
+    const NAME: &'static str = "Event";
+    const LENGTH: usize = 1;
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        let attribute = Attribute::ENUMERABLE;
+        for (name, value) in [
+            (js_string!("NONE"), 0),
+            (js_string!("CAPTURING_PHASE"), 1),
+            (js_string!("AT_TARGET"), 2),
+            (js_string!("BUBBLING_PHASE"), 3),
+        ] {
+            class
+                .static_property(name.clone(), value, attribute)
+                .property(name, value, attribute);
+        }
+        add_event_members(class);
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        let event_type = event_type_arg(args, context)?;
+        Self::from_init(event_type, args.get_or_undefined(1), context)
+    }
+}
+
+/// The `CustomEvent` class, an `Event` carrying arbitrary data in its `detail` property.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `DOM` specification][spec]
+///
+/// [spec]: https://dom.spec.whatwg.org/#interface-customevent
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/CustomEvent
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct CustomEvent {
+    event: Event,
+    detail: JsValue,
+}
+
+impl CustomEvent {
+    /// `get CustomEvent.prototype.detail`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-customevent-detail
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/CustomEvent/detail
+    fn get_detail(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let event = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message("'this' is not a CustomEvent object")
+            })?;
+        Ok(event.detail.clone())
+    }
+}
+
+impl Class for CustomEvent {
+    const NAME: &'static str = "CustomEvent";
+    const LENGTH: usize = 1;
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(class, js_string!("detail"), Self::get_detail);
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        let event_type = event_type_arg(args, context)?;
+        let init = args.get_or_undefined(1);
+        let event = Event::from_init(event_type, init, context)?;
+        let detail = match init.as_object() {
+            Some(init) => init.get(js_string!("detail"), context)?,
+            None => JsValue::null(),
+        };
+        Ok(Self { event, detail })
+    }
+}
+
+/// Starts dispatching `event` to `target`, returning its type.
+pub(super) fn start_dispatch(event: &JsObject, target: &JsObject) -> JsResult<JsString> {
+    let mut event = Event::from_object(event)
+        .ok_or_else(|| JsNativeError::typ().with_message("the argument must be an Event"))?;
+    if event.dispatching {
+        return Err(JsNativeError::typ()
+            .with_message("InvalidStateError: the event is already being dispatched")
+            .into());
+    }
+    event.dispatching = true;
+    event.target = Some(target.clone());
+    event.current_target = Some(target.clone());
+    event.phase = AT_TARGET;
+    Ok(event.kind.clone())
+}
+
+/// Finishes dispatching `event`, returning `false` if it was canceled.
+pub(super) fn finish_dispatch(event: &JsObject) -> bool {
+    let mut event = Event::from_object(event).expect("must be an Event");
+    event.dispatching = false;
+    event.current_target = None;
+    event.phase = NONE;
+    event.stop_propagation = false;
+    event.stop_immediate_propagation = false;
+    !event.canceled
+}
+
+/// Returns `true` if `stopImmediatePropagation` was called on `event`.
+pub(super) fn immediate_propagation_stopped(event: &JsObject) -> bool {
+    Event::from_object(event)
+        .expect("must be an Event")
+        .stop_immediate_propagation
+}
+
+/// Sets whether `event` is being handled by a passive listener, which cannot cancel it.
+pub(super) fn set_in_passive_listener(event: &JsObject, passive: bool) {
+    Event::from_object(event)
+        .expect("must be an Event")
+        .in_passive_listener = passive;
+}
+
+/// Sets whether `event` was created by the host.
+pub(super) fn set_trusted(event: &JsObject, trusted: bool) {
+    Event::from_object(event).expect("must be an Event").trusted = trusted;
+}
+
+/// Cancels `event`, if it is cancelable.
+pub(super) fn prevent_default(event: &JsObject) {
+    Event::from_object(event)
+        .expect("must be an Event")
+        .cancel();
+}
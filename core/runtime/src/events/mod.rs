@@ -0,0 +1,104 @@
+//! Boa's implementation of the DOM events API: the `EventTarget`, `Event` and `CustomEvent`
+//! classes, and the `AbortController` and `AbortSignal` classes built on top of them.
+//!
+//! All the classes can be subclassed from JavaScript, e.g. `class Emitter extends EventTarget {}`.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `DOM` specification][spec]
+//!
+//! [spec]: https://dom.spec.whatwg.org/#events
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Event
+
+#[cfg(test)]
+mod tests;
+
+mod abort;
+mod event;
+mod target;
+
+#[doc(inline)]
+pub use abort::{AbortController, AbortSignal};
+#[doc(inline)]
+pub use event::{CustomEvent, Event};
+#[doc(inline)]
+pub use target::EventTarget;
+
+pub(crate) use abort::create_error;
+
+use crate::EventLoop;
+use boa_engine::{
+    class::Class, context::intrinsics::StandardConstructor, js_string,
+    property::PropertyDescriptor, Context, JsNativeError, JsResult,
+};
+use std::rc::Rc;
+
+/// The `EventTarget`, `Event`, `CustomEvent`, `AbortController` and `AbortSignal` classes.
+#[derive(Debug, Clone, Copy)]
+pub struct Events;
+
+impl Events {
+    /// Registers the event classes in the global object of the context, scheduling the timers
+    /// of `AbortSignal.timeout` on `event_loop`.
+    ///
+    /// The uncaught errors thrown by event listeners are reported by
+    /// [`EventLoop::report_error`].
+    ///
+    /// `event_loop` should also be the [`JobQueue`] of the context, otherwise the timeouts will
+    /// never abort their signals.
+    ///
+    /// [`JobQueue`]: boa_engine::job::JobQueue
+    ///
+    /// # Errors
+    ///
+    /// This will return an error if any of the classes cannot be defined in the global object.
+    pub fn register(event_loop: &Rc<EventLoop>, context: &mut Context) -> JsResult<()> {
+        context.register_global_class::<EventTarget>()?;
+        context.register_global_class::<Event>()?;
+        context.register_global_class::<CustomEvent>()?;
+        context.register_global_class::<AbortSignal>()?;
+        context.register_global_class::<AbortController>()?;
+
+        inherit::<CustomEvent, Event>(context)?;
+        inherit::<AbortSignal, EventTarget>(context)?;
+
+        // Reports the uncaught errors of the event listeners through the event loop.
+        EventLoop::register_host(event_loop, context);
+
+        let signal = registered_class::<AbortSignal>(context)?.constructor();
+        let timeout =
+            AbortSignal::timeout_function(event_loop.clone()).to_js_function(context.realm());
+        signal.define_property_or_throw(
+            js_string!("timeout"),
+            PropertyDescriptor::builder()
+                .value(timeout)
+                .writable(true)
+                .enumerable(false)
+                .configurable(true),
+            context,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Makes the registered class `C` inherit from the registered class `P`, linking both their
+/// prototypes and their constructors.
+pub(crate) fn inherit<C: Class, P: Class>(context: &Context) -> JsResult<()> {
+    let child = registered_class::<C>(context)?;
+    let parent = registered_class::<P>(context)?;
+    child.prototype().set_prototype(Some(parent.prototype()));
+    child
+        .constructor()
+        .set_prototype(Some(parent.constructor()));
+    Ok(())
+}
+
+/// Gets the constructor and prototype of the registered class `C`.
+fn registered_class<C: Class>(context: &Context) -> JsResult<StandardConstructor> {
+    context.get_global_class::<C>().ok_or_else(|| {
+        JsNativeError::typ()
+            .with_message(format!("the class {} is not registered", C::NAME))
+            .into()
+    })
+}
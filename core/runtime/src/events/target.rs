@@ -0,0 +1,419 @@
+//! Boa's implementation of the `EventTarget` object.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `DOM` specification][spec]
+//!
+//! [spec]: https://dom.spec.whatwg.org/#interface-eventtarget
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/EventTarget
+
+use super::{
+    abort::AbortSignal,
+    event::{self, Event},
+};
+use crate::{EventLoop, Worker};
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::{ErasedObject, JsObject, RefMut},
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsString, JsValue,
+};
+use boa_gc::{Finalize, GcRefMut, Trace};
+
+/// The callback of an event listener.
+#[derive(Debug, Clone, Trace, Finalize)]
+enum Callback {
+    /// A function, or an object with a `handleEvent` method.
+    Listener(JsObject),
+    /// The event handler of the event type, e.g. `onabort` for `abort` events.
+    ///
+    /// The listener stays at the position where the event handler was first set, but calls its
+    /// current value.
+    Handler,
+}
+
+/// An event listener registered on an `EventTarget`.
+#[derive(Debug, Clone, Trace, Finalize)]
+struct Listener {
+    /// The identifier of the listener, used to check if it's still registered while dispatching
+    /// an event.
+    id: u64,
+    event_type: JsString,
+    callback: Callback,
+    capture: bool,
+    passive: bool,
+    once: bool,
+}
+
+/// The `EventTarget` class, an object that can receive events and have listeners for them.
+///
+/// The native classes inheriting from `EventTarget` store an `EventTarget` in their data, which
+/// is retrieved by [`EventTarget::from_object`].
+#[derive(Debug, Clone, Default, Trace, Finalize, JsData)]
+pub struct EventTarget {
+    listeners: Vec<Listener>,
+    /// The current values of the event handlers, by event type.
+    handlers: Vec<(JsString, JsObject)>,
+    next_id: u64,
+}
+
+impl EventTarget {
+    /// Gets a mutable reference to the `EventTarget` data of an object, if it is an
+    /// `EventTarget` or an instance of one of its native subclasses.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object is currently borrowed.
+    #[must_use]
+    pub fn from_object(object: &JsObject) -> Option<RefMut<'_, ErasedObject, Self>> {
+        if let Some(target) = object.downcast_mut::<Self>() {
+            return Some(target);
+        }
+        if let Some(signal) = object.downcast_mut::<AbortSignal>() {
+            return Some(GcRefMut::map(signal, AbortSignal::target_mut));
+        }
+        object
+            .downcast_mut::<Worker>()
+            .map(|worker| GcRefMut::map(worker, Worker::target_mut))
+    }
+
+    /// Adds a listener, returning its identifier, unless an equal listener is already registered.
+    fn add_listener(
+        &mut self,
+        event_type: JsString,
+        callback: Callback,
+        capture: bool,
+        passive: bool,
+        once: bool,
+    ) -> Option<u64> {
+        let exists = self.listeners.iter().any(|listener| {
+            listener.event_type == event_type
+                && listener.capture == capture
+                && match (&listener.callback, &callback) {
+                    (Callback::Listener(a), Callback::Listener(b)) => a == b,
+                    _ => false,
+                }
+        });
+        if exists {
+            return None;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.listeners.push(Listener {
+            id,
+            event_type,
+            callback,
+            capture,
+            passive,
+            once,
+        });
+        Some(id)
+    }
+
+    /// Gets the value of the event handler for `event_type` of `object`, e.g. `onabort` for
+    /// `abort` events.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `object` is not an `EventTarget`.
+    #[must_use]
+    pub fn event_handler(object: &JsObject, event_type: &JsString) -> JsValue {
+        let target = Self::from_object(object).expect("must be an EventTarget");
+        target
+            .handlers
+            .iter()
+            .find(|(t, _)| t == event_type)
+            .map_or_else(JsValue::null, |(_, handler)| handler.clone().into())
+    }
+
+    /// Sets the event handler for `event_type` of `object`.
+    ///
+    /// Values that aren't callable remove the event handler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `object` is not an `EventTarget`.
+    pub fn set_event_handler(object: &JsObject, event_type: &JsString, value: &JsValue) {
+        let mut target = Self::from_object(object).expect("must be an EventTarget");
+        target.handlers.retain(|(t, _)| t != event_type);
+        match value.as_callable() {
+            Some(handler) => {
+                target.handlers.push((event_type.clone(), handler.clone()));
+                let registered = target.listeners.iter().any(|listener| {
+                    listener.event_type == *event_type
+                        && matches!(listener.callback, Callback::Handler)
+                });
+                if !registered {
+                    target.add_listener(event_type.clone(), Callback::Handler, false, false, false);
+                }
+            }
+            None => target.listeners.retain(|listener| {
+                listener.event_type != *event_type
+                    || !matches!(listener.callback, Callback::Handler)
+            }),
+        }
+    }
+
+    /// [Dispatches][spec] `event` to `target`, returning `false` if the event was canceled.
+    ///
+    /// Errors thrown by the listeners are reported to the standard error, and don't stop the
+    /// dispatch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `event` is not an `Event`, or if it is already being dispatched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target` is not an `EventTarget`.
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#concept-event-dispatch
+    pub fn dispatch(target: &JsObject, event: &JsObject, context: &mut Context) -> JsResult<bool> {
+        let event_type = event::start_dispatch(event, target)?;
+        let listeners = Self::from_object(target)
+            .expect("must be an EventTarget")
+            .listeners
+            .iter()
+            .filter(|listener| listener.event_type == event_type)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        // Capturing listeners are invoked before the non-capturing ones.
+        let ordered = listeners
+            .iter()
+            .filter(|listener| listener.capture)
+            .chain(listeners.iter().filter(|listener| !listener.capture));
+        for listener in ordered {
+            if event::immediate_propagation_stopped(event) {
+                break;
+            }
+            {
+                let mut target = Self::from_object(target).expect("must be an EventTarget");
+                let Some(index) = target.listeners.iter().position(|l| l.id == listener.id) else {
+                    continue;
+                };
+                if listener.once {
+                    target.listeners.remove(index);
+                }
+            }
+            event::set_in_passive_listener(event, listener.passive);
+            if let Err(e) = Self::invoke(target, event, &event_type, &listener.callback, context) {
+                EventLoop::report_uncaught(&e, context);
+            }
+            event::set_in_passive_listener(event, false);
+        }
+
+        Ok(event::finish_dispatch(event))
+    }
+
+    /// Calls the callback of a listener with `event`.
+    fn invoke(
+        target: &JsObject,
+        event: &JsObject,
+        event_type: &JsString,
+        callback: &Callback,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let args = [event.clone().into()];
+        match callback {
+            Callback::Listener(listener) => {
+                if let Some(function) = JsValue::from(listener.clone()).as_callable() {
+                    function.call(&target.clone().into(), &args, context)?;
+                    return Ok(());
+                }
+                let handle_event = listener.get(js_string!("handleEvent"), context)?;
+                let handle_event = handle_event.as_callable().ok_or_else(|| {
+                    JsNativeError::typ()
+                        .with_message("the event listener doesn't have a handleEvent method")
+                })?;
+                handle_event.call(&listener.clone().into(), &args, context)?;
+            }
+            Callback::Handler => {
+                let handler = Self::event_handler(target, event_type);
+                let Some(handler) = handler.as_callable() else {
+                    return Ok(());
+                };
+                let result = handler.call(&target.clone().into(), &args, context)?;
+                if result == JsValue::from(false) {
+                    event::prevent_default(event);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// `EventTarget.prototype.addEventListener(type, callback, options)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-eventtarget-addeventlistener
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/addEventListener
+    fn add_event_listener(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let object = Self::this_target(this)?;
+        let event_type = args.get_or_undefined(0).to_string(context)?;
+        let callback = match args.get_or_undefined(1) {
+            JsValue::Object(callback) => Some(callback.clone()),
+            JsValue::Null | JsValue::Undefined => None,
+            _ => {
+                return Err(JsNativeError::typ()
+                    .with_message("the event listener must be an object")
+                    .into())
+            }
+        };
+
+        let (capture, once, passive, signal) = match args.get_or_undefined(2) {
+            JsValue::Object(options) => {
+                let capture = options.get(js_string!("capture"), context)?.to_boolean();
+                let once = options.get(js_string!("once"), context)?.to_boolean();
+                let passive = options.get(js_string!("passive"), context)?.to_boolean();
+                let signal = options.get(js_string!("signal"), context)?;
+                let signal = if signal.is_undefined() {
+                    None
+                } else {
+                    let signal = signal
+                        .as_object()
+                        .filter(|signal| signal.is::<AbortSignal>())
+                        .ok_or_else(|| {
+                            JsNativeError::typ()
+                                .with_message("the signal option must be an AbortSignal")
+                        })?;
+                    Some(signal.clone())
+                };
+                (capture, once, passive, signal)
+            }
+            options => (options.to_boolean(), false, false, None),
+        };
+
+        let Some(callback) = callback else {
+            return Ok(JsValue::undefined());
+        };
+        if signal.as_ref().is_some_and(AbortSignal::is_aborted) {
+            return Ok(JsValue::undefined());
+        }
+
+        let id = Self::from_object(object)
+            .expect("checked above")
+            .add_listener(
+                event_type,
+                Callback::Listener(callback),
+                capture,
+                passive,
+                once,
+            );
+        if let (Some(signal), Some(id)) = (signal, id) {
+            let remove = NativeFunction::from_copy_closure_with_captures(
+                |_, _, (target, id), _| {
+                    let mut target = Self::from_object(target).expect("must be an EventTarget");
+                    target.listeners.retain(|listener| listener.id != *id);
+                    Ok(JsValue::undefined())
+                },
+                (object.clone(), id),
+            )
+            .to_js_function(context.realm());
+            AbortSignal::add_algorithm(&signal, remove);
+        }
+        Ok(JsValue::undefined())
+    }
+
+    /// `EventTarget.prototype.removeEventListener(type, callback, options)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-eventtarget-removeeventlistener
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/removeEventListener
+    fn remove_event_listener(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let object = Self::this_target(this)?;
+        let event_type = args.get_or_undefined(0).to_string(context)?;
+        let Some(callback) = args.get_or_undefined(1).as_object().cloned() else {
+            return Ok(JsValue::undefined());
+        };
+        let capture = match args.get_or_undefined(2) {
+            JsValue::Object(options) => options.get(js_string!("capture"), context)?.to_boolean(),
+            options => options.to_boolean(),
+        };
+        Self::from_object(object)
+            .expect("checked above")
+            .listeners
+            .retain(|listener| {
+                !(listener.event_type == event_type
+                    && listener.capture == capture
+                    && matches!(&listener.callback, Callback::Listener(c) if *c == callback))
+            });
+        Ok(JsValue::undefined())
+    }
+
+    /// `EventTarget.prototype.dispatchEvent(event)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `DOM` specification][spec]
+    ///
+    /// [spec]: https://dom.spec.whatwg.org/#dom-eventtarget-dispatchevent
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/dispatchEvent
+    fn dispatch_event(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let object = Self::this_target(this)?;
+        let event = args
+            .get_or_undefined(0)
+            .as_object()
+            .filter(|event| Event::from_object(event).is_some())
+            .ok_or_else(|| JsNativeError::typ().with_message("the argument must be an Event"))?;
+        event::set_trusted(event, false);
+        Ok(Self::dispatch(object, event, context)?.into())
+    }
+
+    /// Checks that `this` is an `EventTarget`, returning it.
+    fn this_target(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| Self::from_object(object).is_some())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not an EventTarget object")
+                    .into()
+            })
+    }
+}
+
+impl Class for EventTarget {
+    const NAME: &'static str = "EventTarget";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        class
+            .method(
+                js_string!("addEventListener"),
+                2,
+                NativeFunction::from_fn_ptr(Self::add_event_listener),
+            )
+            .method(
+                js_string!("removeEventListener"),
+                2,
+                NativeFunction::from_fn_ptr(Self::remove_event_listener),
+            )
+            .method(
+                js_string!("dispatchEvent"),
+                1,
+                NativeFunction::from_fn_ptr(Self::dispatch_event),
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<Self> {
+        Ok(Self::default())
+    }
+}
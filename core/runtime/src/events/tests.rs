@@ -0,0 +1,212 @@
+use crate::{
+    test::{run_test_actions, TestAction},
+    EventLoop,
+};
+use boa_engine::{js_string, object::builtins::JsArray, Context};
+use indoc::indoc;
+use std::time::Duration;
+
+#[test]
+fn dispatch_event() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const target = new EventTarget();
+            const log = [];
+            const listener = (e) => log.push("bubble:" + e.eventPhase);
+            target.addEventListener("ping", listener);
+            target.addEventListener("ping", listener);
+            target.addEventListener("ping", () => log.push("capture"), { capture: true });
+            target.addEventListener("ping", () => log.push("once"), { once: true });
+            target.addEventListener("ping", { handleEvent(e) { log.push(e.type); } });
+            const event = new Event("ping", { cancelable: true });
+            target.dispatchEvent(event);
+            target.dispatchEvent(new Event("ping"));
+            target.removeEventListener("ping", listener);
+            target.dispatchEvent(new Event("ping"));
+        "#}),
+        TestAction::assert_eq(
+            "log.join()",
+            js_string!("capture,bubble:2,once,ping,capture,bubble:2,ping,capture,ping"),
+        ),
+        TestAction::assert_eq("event.target === target", true),
+        TestAction::assert_eq("event.currentTarget", boa_engine::JsValue::null()),
+        TestAction::assert_eq("event.eventPhase === Event.NONE", true),
+        TestAction::assert_eq("event.isTrusted", false),
+        TestAction::assert_eq("typeof event.timeStamp", js_string!("number")),
+    ]);
+}
+
+#[test]
+fn cancel_and_stop_propagation() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const target = new EventTarget();
+            const log = [];
+            target.addEventListener("a", (e) => { e.preventDefault(); log.push(1); });
+            target.addEventListener("a", (e) => { e.stopImmediatePropagation(); log.push(2); });
+            target.addEventListener("a", () => log.push(3));
+            target.addEventListener("b", (e) => e.preventDefault(), { passive: true });
+            target.addEventListener("c", () => { throw new Error("ignored"); });
+            target.addEventListener("c", () => log.push(4));
+        "#}),
+        TestAction::assert_eq(
+            "target.dispatchEvent(new Event('a', { cancelable: true }))",
+            false,
+        ),
+        TestAction::assert_eq("target.dispatchEvent(new Event('a'))", true),
+        TestAction::assert_eq(
+            "target.dispatchEvent(new Event('b', { cancelable: true }))",
+            true,
+        ),
+        TestAction::assert_eq("target.dispatchEvent(new Event('c'))", true),
+        TestAction::assert_eq("log.join()", js_string!("1,2,1,2,4")),
+        TestAction::assert_eq(
+            "try { new Event() } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+        TestAction::assert_eq(
+            "try { target.dispatchEvent({}) } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+    ]);
+}
+
+#[test]
+fn subclasses() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            class Emitter extends EventTarget {
+                emit(type, detail) {
+                    return this.dispatchEvent(new CustomEvent(type, { detail }));
+                }
+            }
+            class PingEvent extends Event {
+                constructor() { super("ping"); this.extra = 42; }
+            }
+            const emitter = new Emitter();
+            const received = [];
+            emitter.addEventListener("data", (e) => received.push(e.detail));
+            emitter.addEventListener("ping", (e) => received.push(e.extra));
+            emitter.emit("data", "hello");
+            emitter.dispatchEvent(new PingEvent());
+        "#}),
+        TestAction::assert_eq("received.join()", js_string!("hello,42")),
+        TestAction::assert_eq("emitter instanceof EventTarget", true),
+        TestAction::assert_eq("new CustomEvent('x') instanceof Event", true),
+        TestAction::assert_eq("new CustomEvent('x').detail", boa_engine::JsValue::null()),
+        TestAction::assert_eq("Object.getPrototypeOf(CustomEvent) === Event", true),
+        TestAction::assert_eq("new AbortController().signal instanceof EventTarget", true),
+    ]);
+}
+
+#[test]
+fn abort_controller() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const controller = new AbortController();
+            const { signal } = controller;
+            const target = new EventTarget();
+            const log = [];
+            signal.onabort = (e) => log.push("onabort:" + e.isTrusted);
+            signal.addEventListener("abort", () => log.push("listener"));
+            target.addEventListener("x", () => log.push("x"), { signal });
+            target.dispatchEvent(new Event("x"));
+            controller.abort();
+            controller.abort("ignored");
+            target.dispatchEvent(new Event("x"));
+        "#}),
+        TestAction::assert_eq("log.join()", js_string!("x,onabort:true,listener")),
+        TestAction::assert_eq("signal.aborted", true),
+        TestAction::assert_eq("signal.reason.name", js_string!("AbortError")),
+        TestAction::assert_eq(
+            "try { signal.throwIfAborted() } catch (e) { e === signal.reason }",
+            true,
+        ),
+        TestAction::assert_eq("AbortSignal.abort('why').reason", js_string!("why")),
+        TestAction::assert_eq(
+            "try { new AbortSignal() } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+    ]);
+}
+
+#[test]
+fn abort_signal_any() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const a = new AbortController();
+            const b = new AbortController();
+            const any = AbortSignal.any([a.signal, b.signal]);
+            const nested = AbortSignal.any([any]);
+            const log = [];
+            any.addEventListener("abort", () => log.push("any"));
+            nested.addEventListener("abort", () => log.push("nested"));
+            b.abort("b");
+            a.abort("a");
+        "#}),
+        TestAction::assert_eq("log.join()", js_string!("any,nested")),
+        TestAction::assert_eq("any.reason + nested.reason", js_string!("bb")),
+        TestAction::assert_eq("AbortSignal.any([AbortSignal.abort(1)]).reason", 1),
+        TestAction::assert_eq(
+            "try { AbortSignal.any([{}]) } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+    ]);
+}
+
+#[test]
+fn abort_signal_timeout() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const signal = AbortSignal.timeout(50);
+            let reason;
+            signal.addEventListener("abort", () => { reason = signal.reason.name; });
+        "#}),
+        TestAction::assert_eq("signal.aborted", false),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("signal.aborted", true),
+        TestAction::assert_eq("reason", js_string!("TimeoutError")),
+        TestAction::assert_context(|context| {
+            EventLoop::of(context)
+                .is_some_and(|event_loop| event_loop.now() == Duration::from_millis(50))
+        }),
+    ]);
+}
+
+#[test]
+fn report_uncaught_errors() {
+    run_test_actions([
+        TestAction::run("var errors = [];"),
+        TestAction::inspect_context(|context| {
+            let event_loop =
+                EventLoop::of(context).expect("the test context runs on an event loop");
+            event_loop.set_error_reporter(|error, context| {
+                let errors = context
+                    .global_object()
+                    .get(js_string!("errors"), context)
+                    .expect("the errors are a global property");
+                let errors = errors
+                    .as_object()
+                    .and_then(|errors| JsArray::from_object(errors.clone()).ok())
+                    .expect("the errors are an array");
+                errors
+                    .push(js_string!(error.to_string()), context)
+                    .expect("the error can be recorded");
+            });
+        }),
+        TestAction::run(indoc! {r#"
+            const target = new EventTarget();
+            target.addEventListener("ping", () => { throw new Error("listener"); });
+            target.dispatchEvent(new Event("ping"));
+            const controller = new AbortController();
+            controller.signal.onabort = () => { throw new Error("onabort"); };
+            controller.abort();
+            setTimeout(() => { throw new Error("timer"); });
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq(
+            "errors.join()",
+            js_string!("Error: listener,Error: onabort,Error: timer"),
+        ),
+    ]);
+}
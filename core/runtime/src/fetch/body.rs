@@ -0,0 +1,266 @@
+//! Boa's implementation of the `Body` mixin, shared by `Request` and `Response`.
+//!
+//! More information:
+//!  - [WHATWG `Fetch` specification][spec]
+//!
+//! [spec]: https://fetch.spec.whatwg.org/#body-mixin
+
+use crate::{
+    util::{add_getter, buffer_source_bytes, to_usv_string},
+    UrlSearchParams,
+};
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::{
+        builtins::{JsArrayBuffer, JsPromise, JsUint8Array},
+        JsObject,
+    },
+    Context, JsNativeError, JsResult, JsString, JsValue, NativeObject,
+};
+
+/// The body of a request or a response.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Body {
+    /// The bytes of the body, or `None` for a null body.
+    bytes: Option<Vec<u8>>,
+    /// Whether the body has already been read.
+    used: bool,
+}
+
+impl Body {
+    /// Creates a new, unused body.
+    pub(super) const fn new(bytes: Option<Vec<u8>>) -> Self {
+        Self { bytes, used: false }
+    }
+
+    /// Returns `true` if the body is null.
+    pub(super) const fn is_null(&self) -> bool {
+        self.bytes.is_none()
+    }
+
+    /// Returns `true` if the body has already been read.
+    pub(super) const fn is_used(&self) -> bool {
+        self.used
+    }
+
+    /// Returns a copy of the body, failing if it has already been read.
+    pub(super) fn try_clone(&self) -> JsResult<Self> {
+        if self.used {
+            return Err(used_error().into());
+        }
+        Ok(self.clone())
+    }
+
+    /// Reads the bytes of the body, marking it as used if it isn't null.
+    pub(super) fn take(&mut self) -> JsResult<Option<Vec<u8>>> {
+        if self.used {
+            return Err(used_error().into());
+        }
+        self.used = self.bytes.is_some();
+        Ok(self.bytes.take())
+    }
+}
+
+/// Creates the error thrown when a body is read twice.
+fn used_error() -> JsNativeError {
+    JsNativeError::typ().with_message("the body has already been read")
+}
+
+/// Extracts the bytes and the default `Content-Type` of a `BodyInit` value.
+///
+/// Returns `None` if `value` is `undefined` or `null`.
+pub(super) fn extract(
+    value: &JsValue,
+    context: &mut Context,
+) -> JsResult<Option<(Vec<u8>, Option<&'static str>)>> {
+    if value.is_null_or_undefined() {
+        return Ok(None);
+    }
+    if let Some(object) = value.as_object() {
+        if object.is::<UrlSearchParams>() {
+            let bytes = UrlSearchParams::serialize(object).into_bytes();
+            return Ok(Some((
+                bytes,
+                Some("application/x-www-form-urlencoded;charset=UTF-8"),
+            )));
+        }
+        if let Ok(bytes) = buffer_source_bytes(value, context) {
+            return Ok(Some((bytes, None)));
+        }
+    }
+    let text = to_usv_string(value, context)?;
+    Ok(Some((text.into_bytes(), Some("text/plain;charset=UTF-8"))))
+}
+
+/// An object implementing the `Body` mixin.
+pub(super) trait HasBody: Class + NativeObject {
+    /// Gets a reference to the body of the object.
+    fn body(&self) -> &Body;
+
+    /// Gets a mutable reference to the body of the object.
+    fn body_mut(&mut self) -> &mut Body;
+}
+
+/// Checks that `this` is an object of the class `T`, returning it.
+fn this_object<T: HasBody>(this: &JsValue) -> JsResult<&JsObject> {
+    this.as_object()
+        .filter(|object| object.is::<T>())
+        .ok_or_else(|| {
+            JsNativeError::typ()
+                .with_message(format!("'this' is not a {} object", T::NAME))
+                .into()
+        })
+}
+
+/// The format a body is read into.
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    ArrayBuffer,
+    Bytes,
+    Json,
+    Text,
+}
+
+/// Reads the body of `this` as `format`, returning a promise for the result.
+fn consume<T: HasBody>(this: &JsValue, format: Format, context: &mut Context) -> JsResult<JsValue> {
+    let bytes = this_object::<T>(this)?
+        .downcast_mut::<T>()
+        .expect("checked above")
+        .body_mut()
+        .take();
+    let result = bytes.and_then(|bytes| {
+        let bytes = bytes.unwrap_or_default();
+        match format {
+            Format::ArrayBuffer => Ok(JsArrayBuffer::from_byte_block(bytes, context)?.into()),
+            Format::Bytes => {
+                let buffer = JsArrayBuffer::from_byte_block(bytes, context)?;
+                Ok(JsUint8Array::from_array_buffer(buffer, context)?.into())
+            }
+            Format::Json => call_json(js_string!("parse"), &[decode_utf8(&bytes).into()], context),
+            Format::Text => Ok(decode_utf8(&bytes).into()),
+        }
+    });
+    let promise = match result {
+        Ok(value) => JsPromise::resolve(value, context),
+        Err(e) => JsPromise::reject(e, context),
+    };
+    Ok(promise.into())
+}
+
+/// Calls the method `name` of the intrinsic `JSON` object.
+pub(super) fn call_json(
+    name: JsString,
+    args: &[JsValue],
+    context: &mut Context,
+) -> JsResult<JsValue> {
+    let json = context.intrinsics().objects().json();
+    let method = json.get(name, context)?;
+    let method = method
+        .as_callable()
+        .ok_or_else(|| JsNativeError::typ().with_message("the JSON method is not a function"))?;
+    method.call(&json.into(), args, context)
+}
+
+/// Decodes UTF-8 bytes into a string, skipping the byte order mark and replacing invalid
+/// sequences with U+FFFD.
+fn decode_utf8(bytes: &[u8]) -> JsString {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    JsString::from(String::from_utf8_lossy(bytes).as_ref())
+}
+
+/// `get Body.prototype.bodyUsed`
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Fetch` specification][spec]
+///
+/// [spec]: https://fetch.spec.whatwg.org/#dom-body-bodyused
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/bodyUsed
+fn get_body_used<T: HasBody>(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+    let used = this_object::<T>(this)?
+        .downcast_ref::<T>()
+        .expect("checked above")
+        .body()
+        .is_used();
+    Ok(used.into())
+}
+
+/// `Body.prototype.arrayBuffer()`
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Fetch` specification][spec]
+///
+/// [spec]: https://fetch.spec.whatwg.org/#dom-body-arraybuffer
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/arrayBuffer
+fn array_buffer<T: HasBody>(
+    this: &JsValue,
+    _: &[JsValue],
+    context: &mut Context,
+) -> JsResult<JsValue> {
+    consume::<T>(this, Format::ArrayBuffer, context)
+}
+
+/// `Body.prototype.bytes()`
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Fetch` specification][spec]
+///
+/// [spec]: https://fetch.spec.whatwg.org/#dom-body-bytes
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/bytes
+fn bytes<T: HasBody>(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    consume::<T>(this, Format::Bytes, context)
+}
+
+/// `Body.prototype.json()`
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Fetch` specification][spec]
+///
+/// [spec]: https://fetch.spec.whatwg.org/#dom-body-json
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/json
+fn json<T: HasBody>(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    consume::<T>(this, Format::Json, context)
+}
+
+/// `Body.prototype.text()`
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Fetch` specification][spec]
+///
+/// [spec]: https://fetch.spec.whatwg.org/#dom-body-text
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/text
+fn text<T: HasBody>(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    consume::<T>(this, Format::Text, context)
+}
+
+/// Adds the members of the `Body` mixin to the prototype of the class `T`.
+pub(super) fn add_body_members<T: HasBody>(class: &mut ClassBuilder<'_>) {
+    add_getter(class, js_string!("bodyUsed"), get_body_used::<T>);
+    class
+        .method(
+            js_string!("arrayBuffer"),
+            0,
+            NativeFunction::from_fn_ptr(array_buffer::<T>),
+        )
+        .method(
+            js_string!("bytes"),
+            0,
+            NativeFunction::from_fn_ptr(bytes::<T>),
+        )
+        .method(
+            js_string!("json"),
+            0,
+            NativeFunction::from_fn_ptr(json::<T>),
+        )
+        .method(
+            js_string!("text"),
+            0,
+            NativeFunction::from_fn_ptr(text::<T>),
+        );
+}
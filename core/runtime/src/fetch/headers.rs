@@ -0,0 +1,449 @@
+//! Boa's implementation of the `Headers` object.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `Fetch` specification][spec]
+//!
+//! [spec]: https://fetch.spec.whatwg.org/#headers-class
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Headers
+
+use crate::{url::pairs_to_list, util::to_usv_string};
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::{builtins::JsArray, JsObject},
+    property::Attribute,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsString, JsSymbol, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+
+/// The kind of values returned by the iterators of `Headers`.
+#[derive(Debug, Clone, Copy)]
+enum IterationKind {
+    Key,
+    Value,
+    KeyAndValue,
+}
+
+/// Returns `true` if `name` is a valid header name, i.e. a non-empty HTTP token.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+/// Validates and normalizes a header name and value, lowercasing the name and removing the
+/// leading and trailing HTTP whitespace of the value.
+pub(super) fn normalize(name: &str, value: &str) -> JsResult<(String, String)> {
+    if !is_valid_name(name) {
+        return Err(JsNativeError::typ()
+            .with_message(format!("'{name}' is not a valid header name"))
+            .into());
+    }
+    let value = value.trim_matches(|c| matches!(c, ' ' | '\t' | '\n' | '\r'));
+    if value
+        .chars()
+        .any(|c| matches!(c, '\0' | '\n' | '\r') || u32::from(c) > 0xFF)
+    {
+        return Err(JsNativeError::typ()
+            .with_message(format!(
+                "'{value}' is not a valid value for header '{name}'"
+            ))
+            .into());
+    }
+    Ok((name.to_ascii_lowercase(), value.to_owned()))
+}
+
+/// Gets a header name from the first argument of a method, lowercased.
+fn name_arg(args: &[JsValue], context: &mut Context) -> JsResult<String> {
+    let name = to_usv_string(args.get_or_undefined(0), context)?;
+    if !is_valid_name(&name) {
+        return Err(JsNativeError::typ()
+            .with_message(format!("'{name}' is not a valid header name"))
+            .into());
+    }
+    Ok(name.to_ascii_lowercase())
+}
+
+/// The `Headers` class, which represents the list of headers of a request or a response.
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct Headers {
+    /// The headers, with lowercase names, in insertion order.
+    #[unsafe_ignore_trace]
+    list: Vec<(String, String)>,
+    /// Whether the headers can be modified by scripts, which isn't the case for the headers of a
+    /// response returned by `fetch`.
+    immutable: bool,
+}
+
+impl Headers {
+    /// Creates a new `Headers` object containing `list`.
+    pub(super) fn create(
+        list: Vec<(String, String)>,
+        immutable: bool,
+        context: &mut Context,
+    ) -> JsResult<JsObject> {
+        Self::from_data(Self { list, immutable }, context)
+    }
+
+    /// Converts a `HeadersInit` value (a `Headers` object, a sequence of pairs or a record) into a
+    /// list of normalized headers.
+    pub(super) fn list_from_init(
+        init: &JsValue,
+        context: &mut Context,
+    ) -> JsResult<Vec<(String, String)>> {
+        let Some(object) = init.as_object() else {
+            if init.is_undefined() {
+                return Ok(Vec::new());
+            }
+            return Err(JsNativeError::typ()
+                .with_message("the headers must be an object")
+                .into());
+        };
+        if let Some(headers) = object.downcast_ref::<Self>() {
+            return Ok(headers.list.clone());
+        }
+        pairs_to_list(object, context)?
+            .iter()
+            .map(|(name, value)| normalize(name, value))
+            .collect()
+    }
+
+    /// Returns a copy of the headers of a `Headers` object.
+    pub(super) fn list(object: &JsObject) -> Vec<(String, String)> {
+        object
+            .downcast_ref::<Self>()
+            .expect("must be a Headers object")
+            .list
+            .clone()
+    }
+
+    /// Gets the value of the header `name` of a `Headers` object, combining all its values.
+    pub(super) fn get_value(object: &JsObject, name: &str) -> Option<String> {
+        let headers = object
+            .downcast_ref::<Self>()
+            .expect("must be a Headers object");
+        let values = headers
+            .list
+            .iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+            .collect::<Vec<_>>();
+        (!values.is_empty()).then(|| values.join(", "))
+    }
+
+    /// Returns the headers sorted by name, with the values of headers with the same name
+    /// combined, except for `set-cookie`.
+    fn sort_and_combine(object: &JsObject) -> Vec<(String, String)> {
+        let mut list = Self::list(object);
+        list.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut combined: Vec<(String, String)> = Vec::with_capacity(list.len());
+        for (name, value) in list {
+            match combined.last_mut() {
+                Some((previous, combined_value)) if *previous == name && name != "set-cookie" => {
+                    combined_value.push_str(", ");
+                    combined_value.push_str(&value);
+                }
+                _ => combined.push((name, value)),
+            }
+        }
+        combined
+    }
+
+    /// Modifies the headers of `this` with `f`, failing if they are immutable.
+    fn update(this: &JsValue, f: impl FnOnce(&mut Vec<(String, String)>)) -> JsResult<()> {
+        let object = Self::this_headers(this)?;
+        let mut headers = object.downcast_mut::<Self>().expect("checked above");
+        if headers.immutable {
+            return Err(JsNativeError::typ()
+                .with_message("the headers are immutable")
+                .into());
+        }
+        f(&mut headers.list);
+        Ok(())
+    }
+
+    /// `Headers.prototype.append(name, value)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-headers-append
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Headers/append
+    fn append(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = to_usv_string(args.get_or_undefined(0), context)?;
+        let value = to_usv_string(args.get_or_undefined(1), context)?;
+        let header = normalize(&name, &value)?;
+        Self::update(this, |list| list.push(header))?;
+        Ok(JsValue::undefined())
+    }
+
+    /// `Headers.prototype.delete(name)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-headers-delete
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Headers/delete
+    fn delete(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = name_arg(args, context)?;
+        Self::update(this, |list| list.retain(|(n, _)| *n != name))?;
+        Ok(JsValue::undefined())
+    }
+
+    /// `Headers.prototype.get(name)`
+    ///
+    /// Returns the values of all the headers named `name` joined by `", "`, or `null` if there
+    /// is none.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-headers-get
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Headers/get
+    fn get(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_headers(this)?;
+        let name = name_arg(args, context)?;
+        Ok(Self::get_value(object, &name)
+            .map_or_else(JsValue::null, |value| JsString::from(value).into()))
+    }
+
+    /// `Headers.prototype.getSetCookie()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-headers-getsetcookie
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Headers/getSetCookie
+    fn get_set_cookie(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_headers(this)?;
+        let values = Self::list(object)
+            .into_iter()
+            .filter(|(n, _)| n == "set-cookie")
+            .map(|(_, v)| JsString::from(v).into());
+        Ok(JsArray::from_iter(values, context).into())
+    }
+
+    /// `Headers.prototype.has(name)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-headers-has
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Headers/has
+    fn has(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_headers(this)?;
+        let name = name_arg(args, context)?;
+        Ok(Self::list(object).iter().any(|(n, _)| *n == name).into())
+    }
+
+    /// `Headers.prototype.set(name, value)`
+    ///
+    /// Replaces all the headers named `name` by a single header with value `value`.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-headers-set
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Headers/set
+    fn set(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let name = to_usv_string(args.get_or_undefined(0), context)?;
+        let value = to_usv_string(args.get_or_undefined(1), context)?;
+        let (name, value) = normalize(&name, &value)?;
+        Self::update(this, |list| {
+            let mut found = false;
+            list.retain_mut(|(n, v)| {
+                if *n != name {
+                    return true;
+                }
+                if found {
+                    return false;
+                }
+                found = true;
+                v.clone_from(&value);
+                true
+            });
+            if !found {
+                list.push((name, value));
+            }
+        })?;
+        Ok(JsValue::undefined())
+    }
+
+    /// `Headers.prototype.forEach(callbackFn, thisArg)`
+    ///
+    /// Calls `callbackFn` with the value, the name and the object of each header, sorted by name.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://webidl.spec.whatwg.org/#es-forEach
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Headers/forEach
+    fn for_each(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_headers(this)?;
+        let callback = args
+            .get_or_undefined(0)
+            .as_callable()
+            .ok_or_else(|| JsNativeError::typ().with_message("the callback must be a function"))?;
+        let this_arg = args.get_or_undefined(1);
+
+        // The list is read again on each step, so that the callback observes its own changes.
+        let mut index = 0;
+        while let Some((name, value)) = Self::sort_and_combine(object).into_iter().nth(index) {
+            callback.call(
+                this_arg,
+                &[
+                    JsString::from(value).into(),
+                    JsString::from(name).into(),
+                    this.clone(),
+                ],
+                context,
+            )?;
+            index += 1;
+        }
+        Ok(JsValue::undefined())
+    }
+
+    /// Returns an iterator over a sorted snapshot of the headers of `this`.
+    fn iterator(this: &JsValue, kind: IterationKind, context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_headers(this)?;
+        let values = Self::sort_and_combine(object)
+            .into_iter()
+            .map(|(name, value)| match kind {
+                IterationKind::Key => JsString::from(name).into(),
+                IterationKind::Value => JsString::from(value).into(),
+                IterationKind::KeyAndValue => JsArray::from_iter(
+                    [JsString::from(name).into(), JsString::from(value).into()],
+                    context,
+                )
+                .into(),
+            })
+            .collect::<Vec<JsValue>>();
+        let array = JsArray::from_iter(values, context);
+        let values = context.intrinsics().objects().array_prototype_values();
+        values.call(&array.into(), &[], context)
+    }
+
+    /// `Headers.prototype.entries()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#headers-class
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Headers/entries
+    fn entries(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::iterator(this, IterationKind::KeyAndValue, context)
+    }
+
+    /// `Headers.prototype.keys()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#headers-class
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Headers/keys
+    fn keys(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::iterator(this, IterationKind::Key, context)
+    }
+
+    /// `Headers.prototype.values()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#headers-class
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Headers/values
+    fn values(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::iterator(this, IterationKind::Value, context)
+    }
+
+    /// Checks that `this` is a `Headers` object, returning it.
+    fn this_headers(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a Headers object")
+                    .into()
+            })
+    }
+}
+
+impl Class for Headers {
+    const NAME: &'static str = "Headers";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        let realm = class.context().realm().clone();
+        let entries = NativeFunction::from_fn_ptr(Self::entries).to_js_function(&realm);
+        class
+            .method(
+                js_string!("append"),
+                2,
+                NativeFunction::from_fn_ptr(Self::append),
+            )
+            .method(
+                js_string!("delete"),
+                1,
+                NativeFunction::from_fn_ptr(Self::delete),
+            )
+            .method(js_string!("get"), 1, NativeFunction::from_fn_ptr(Self::get))
+            .method(
+                js_string!("getSetCookie"),
+                0,
+                NativeFunction::from_fn_ptr(Self::get_set_cookie),
+            )
+            .method(js_string!("has"), 1, NativeFunction::from_fn_ptr(Self::has))
+            .method(js_string!("set"), 2, NativeFunction::from_fn_ptr(Self::set))
+            .method(
+                js_string!("forEach"),
+                1,
+                NativeFunction::from_fn_ptr(Self::for_each),
+            )
+            .property(
+                js_string!("entries"),
+                entries.clone(),
+                Attribute::WRITABLE | Attribute::CONFIGURABLE | Attribute::ENUMERABLE,
+            )
+            .method(
+                js_string!("keys"),
+                0,
+                NativeFunction::from_fn_ptr(Self::keys),
+            )
+            .method(
+                js_string!("values"),
+                0,
+                NativeFunction::from_fn_ptr(Self::values),
+            )
+            .property(
+                JsSymbol::iterator(),
+                entries,
+                Attribute::WRITABLE | Attribute::CONFIGURABLE,
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("Headers"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        let list = Self::list_from_init(args.get_or_undefined(0), context)?;
+        Ok(Self {
+            list,
+            immutable: false,
+        })
+    }
+}
@@ -0,0 +1,127 @@
+//! Boa's implementation of the WHATWG Fetch API: the `fetch` function and the `Headers`,
+//! `Request` and `Response` classes.
+//!
+//! The actual I/O is delegated to a [`FetchProvider`] implemented by the host, which makes it
+//! possible to route requests through any transport, including the [`InMemoryFetchProvider`],
+//! which answers requests by calling Rust closures.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `Fetch` specification][spec]
+//!
+//! [spec]: https://fetch.spec.whatwg.org/
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Fetch_API
+
+#[cfg(test)]
+mod tests;
+
+mod body;
+mod headers;
+mod provider;
+mod request;
+mod response;
+
+#[doc(inline)]
+pub use headers::Headers;
+#[doc(inline)]
+pub use provider::{
+    FetchFuture, FetchProvider, FetchRequest, FetchResponse, InMemoryFetchProvider,
+};
+#[doc(inline)]
+pub use request::Request;
+#[doc(inline)]
+pub use response::Response;
+
+use crate::Permissions;
+use boa_engine::{
+    class::Class, job::NativeJob, js_string, native_function::NativeFunction,
+    object::builtins::JsPromise, Context, JsArgs, JsResult, JsValue,
+};
+use std::rc::Rc;
+
+/// The `fetch` global function.
+#[derive(Debug, Clone, Copy)]
+pub struct Fetch;
+
+impl Fetch {
+    /// The name of the `fetch` function.
+    pub const NAME: &'static str = "fetch";
+
+    /// Registers the `fetch` function and the `Headers`, `Request` and `Response` classes in the
+    /// global object of the context, performing the requests with `provider`.
+    ///
+    /// # Errors
+    ///
+    /// This will return an error if any of the globals cannot be defined in the global object.
+    pub fn register<P: FetchProvider + 'static>(
+        provider: P,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        context.register_global_class::<Headers>()?;
+        context.register_global_class::<Request>()?;
+        context.register_global_class::<Response>()?;
+
+        let provider: Rc<dyn FetchProvider> = Rc::new(provider);
+        // SAFETY: `FetchProvider`s don't need to be traced, since they cannot contain any garbage
+        // collected values.
+        let fetch = unsafe {
+            NativeFunction::from_closure(move |_, args, context| {
+                Ok(Self::fetch(args, &provider, context))
+            })
+        };
+        context.register_global_builtin_callable(js_string!(Self::NAME), 1, fetch)
+    }
+
+    /// `fetch(input, init)`
+    ///
+    /// Sends a request to the [`FetchProvider`], returning a promise that is fulfilled with a
+    /// `Response` object, or rejected if the request couldn't be made.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#fetch-method
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/fetch
+    fn fetch(args: &[JsValue], provider: &Rc<dyn FetchProvider>, context: &mut Context) -> JsValue {
+        let request =
+            Request::construct(args.get_or_undefined(0), args.get_or_undefined(1), context)
+                .and_then(|request| Request::from_data(request, context))
+                .and_then(|request| Request::to_fetch_request(&request))
+                .and_then(|request| {
+                    Permissions::check_net(&request.url, context)?;
+                    Ok(request)
+                });
+        let request = match request {
+            Ok(request) => request,
+            Err(e) => return JsPromise::reject(e, context).into(),
+        };
+
+        let (promise, resolvers) = JsPromise::new_pending(context);
+        let url = request.url.clone();
+        let response = provider.fetch(request);
+        let future = async move {
+            let response = response.await;
+            NativeJob::new(move |context| {
+                match response
+                    .and_then(|response| Response::from_fetch_response(response, url, context))
+                {
+                    Ok(response) => {
+                        resolvers
+                            .resolve
+                            .call(&JsValue::undefined(), &[response.into()], context)
+                    }
+                    Err(e) => {
+                        let e = e.to_opaque(context);
+                        resolvers.reject.call(&JsValue::undefined(), &[e], context)
+                    }
+                }
+            })
+        };
+        context
+            .job_queue()
+            .enqueue_future_job(Box::pin(future), context);
+
+        promise.into()
+    }
+}
@@ -0,0 +1,187 @@
+//! The host interface used by `fetch` to perform requests.
+
+use boa_engine::JsResult;
+use std::{fmt::Debug, future::Future, pin::Pin};
+
+/// The [`Future`] returned by [`FetchProvider::fetch`].
+pub type FetchFuture = Pin<Box<dyn Future<Output = JsResult<FetchResponse>> + 'static>>;
+
+/// A request made by `fetch`, passed to the [`FetchProvider`] of the context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchRequest {
+    /// The normalized method of the request, e.g. `GET`.
+    pub method: String,
+    /// The URL of the request.
+    pub url: url::Url,
+    /// The headers of the request, with lowercase names.
+    pub headers: Vec<(String, String)>,
+    /// The body of the request, if any.
+    pub body: Option<Vec<u8>>,
+}
+
+impl FetchRequest {
+    /// Gets the value of the first header named `name`, ignoring case.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Decodes the body of the request as UTF-8, replacing invalid sequences with U+FFFD.
+    #[must_use]
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(self.body.as_deref().unwrap_or_default()).into_owned()
+    }
+}
+
+/// A response returned by a [`FetchProvider`], which `fetch` resolves to a `Response` object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchResponse {
+    /// The status code of the response.
+    pub status: u16,
+    /// The status message of the response, e.g. `OK`.
+    pub status_text: String,
+    /// The headers of the response.
+    pub headers: Vec<(String, String)>,
+    /// The body of the response.
+    pub body: Vec<u8>,
+}
+
+impl FetchResponse {
+    /// Creates a new `FetchResponse` with status `status` and an empty body.
+    #[must_use]
+    pub const fn new(status: u16) -> Self {
+        Self {
+            status,
+            status_text: String::new(),
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Sets the status message of the response.
+    #[must_use]
+    pub fn with_status_text(mut self, status_text: impl Into<String>) -> Self {
+        self.status_text = status_text.into();
+        self
+    }
+
+    /// Appends a header to the response.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the body of the response.
+    #[must_use]
+    pub fn with_body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+}
+
+/// The host hook that performs the I/O of `fetch`.
+///
+/// Boa doesn't do any networking by itself; instead, every request made by `fetch` is converted
+/// into a [`FetchRequest`] and passed to the provider registered by [`Fetch::register`]. The
+/// returned future is enqueued into the [`JobQueue`] of the context with
+/// [`JobQueue::enqueue_future_job`], and its result settles the promise returned by `fetch`.
+/// Errors reject the promise with the thrown value, and should usually be a `TypeError` for
+/// network errors.
+///
+/// Providers are never traced by the garbage collector, so they must not store any garbage
+/// collected values (e.g. `JsObject`s).
+///
+/// [`Fetch::register`]: crate::Fetch::register
+/// [`JobQueue`]: boa_engine::job::JobQueue
+/// [`JobQueue::enqueue_future_job`]: boa_engine::job::JobQueue::enqueue_future_job
+pub trait FetchProvider {
+    /// Performs `request`, returning a future that resolves to its response.
+    fn fetch(&self, request: FetchRequest) -> FetchFuture;
+}
+
+/// A handler of an [`InMemoryFetchProvider`] route.
+type Handler = Box<dyn Fn(&FetchRequest) -> JsResult<FetchResponse>>;
+
+/// A [`FetchProvider`] that answers requests by calling Rust handlers, without doing any I/O.
+///
+/// Requests are routed by method and URL, ignoring the query and the fragment of the URL.
+/// Requests that don't match any route get a `404 Not Found` response.
+///
+/// ```
+/// use boa_engine::Context;
+/// use boa_runtime::{Fetch, FetchResponse, InMemoryFetchProvider};
+///
+/// let provider = InMemoryFetchProvider::new().route("GET", "https://api.test/hello", |_| {
+///     Ok(FetchResponse::new(200).with_body("Hello!"))
+/// });
+///
+/// let mut context = Context::default();
+/// Fetch::register(provider, &mut context).unwrap();
+/// ```
+#[derive(Default)]
+pub struct InMemoryFetchProvider {
+    routes: Vec<(String, url::Url, Handler)>,
+}
+
+impl Debug for InMemoryFetchProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(
+                self.routes
+                    .iter()
+                    .map(|(method, url, _)| format!("{method} {url}")),
+            )
+            .finish()
+    }
+}
+
+impl InMemoryFetchProvider {
+    /// Creates a new `InMemoryFetchProvider` without any routes.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a route that answers the requests with method `method` to `url` by calling `handler`.
+    ///
+    /// `method` is compared ignoring case. If several routes match a request, the first one added
+    /// is used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `url` is not a valid absolute URL.
+    #[must_use]
+    pub fn route<F>(mut self, method: &str, url: &str, handler: F) -> Self
+    where
+        F: Fn(&FetchRequest) -> JsResult<FetchResponse> + 'static,
+    {
+        let url = url::Url::parse(url).expect("the URL of a route must be a valid absolute URL");
+        self.routes
+            .push((method.to_ascii_uppercase(), url, Box::new(handler)));
+        self
+    }
+
+    /// Finds the route matching `request` and calls its handler.
+    fn handle(&self, request: &FetchRequest) -> JsResult<FetchResponse> {
+        let path = |url: &url::Url| url[..url::Position::AfterPath].to_owned();
+        let request_path = path(&request.url);
+        let route = self.routes.iter().find(|(method, url, _)| {
+            method.eq_ignore_ascii_case(&request.method) && path(url) == request_path
+        });
+        match route {
+            Some((_, _, handler)) => handler(request),
+            None => Ok(FetchResponse::new(404).with_status_text("Not Found")),
+        }
+    }
+}
+
+impl FetchProvider for InMemoryFetchProvider {
+    fn fetch(&self, request: FetchRequest) -> FetchFuture {
+        let response = self.handle(&request);
+        Box::pin(async move { response })
+    }
+}
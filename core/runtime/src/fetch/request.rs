@@ -0,0 +1,293 @@
+//! Boa's implementation of the `Request` object.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `Fetch` specification][spec]
+//!
+//! [spec]: https://fetch.spec.whatwg.org/#request-class
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Request
+
+use super::{
+    body::{self, Body, HasBody},
+    headers::Headers,
+    FetchRequest,
+};
+use crate::util::{add_getter, to_usv_string};
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::JsObject,
+    property::Attribute,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsString, JsSymbol, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+
+/// Parses the URL of a request, which must be absolute and can't contain credentials.
+fn parse_request_url(input: &JsValue, context: &mut Context) -> JsResult<url::Url> {
+    let input = to_usv_string(input, context)?;
+    let url = url::Url::parse(&input).map_err(|_| {
+        JsNativeError::typ().with_message(format!("'{input}' is not a valid absolute URL"))
+    })?;
+    if !url.username().is_empty() || url.password().is_some() {
+        return Err(JsNativeError::typ()
+            .with_message("the URL of a request cannot contain credentials")
+            .into());
+    }
+    Ok(url)
+}
+
+/// Validates and normalizes the method of a request, uppercasing the standard methods.
+fn normalize_method(method: &str) -> JsResult<String> {
+    let is_token = !method.is_empty()
+        && method
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b));
+    if !is_token {
+        return Err(JsNativeError::typ()
+            .with_message(format!("'{method}' is not a valid HTTP method"))
+            .into());
+    }
+    let upper = method.to_ascii_uppercase();
+    match upper.as_str() {
+        "CONNECT" | "TRACE" | "TRACK" => Err(JsNativeError::typ()
+            .with_message(format!("'{method}' HTTP method is unsupported"))
+            .into()),
+        "DELETE" | "GET" | "HEAD" | "OPTIONS" | "POST" | "PUT" => Ok(upper),
+        _ => Ok(method.to_owned()),
+    }
+}
+
+/// The `Request` class, which represents a request made by `fetch`.
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct Request {
+    method: String,
+    #[unsafe_ignore_trace]
+    url: url::Url,
+    /// The `Headers` object of the request.
+    headers: JsObject,
+    #[unsafe_ignore_trace]
+    body: Body,
+}
+
+impl HasBody for Request {
+    fn body(&self) -> &Body {
+        &self.body
+    }
+
+    fn body_mut(&mut self) -> &mut Body {
+        &mut self.body
+    }
+}
+
+impl Request {
+    /// Returns the method of the request.
+    #[must_use]
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Returns the URL of the request.
+    #[must_use]
+    pub const fn url(&self) -> &url::Url {
+        &self.url
+    }
+
+    /// Creates the data of a new `Request` from the `input` and `init` arguments of the `Request`
+    /// constructor or of `fetch`.
+    pub(super) fn construct(
+        input: &JsValue,
+        init: &JsValue,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        let input_request = input.as_object().filter(|object| object.is::<Self>());
+        let (url, mut method, mut headers, input_body) = match input_request {
+            Some(request) => {
+                let request = request.downcast_ref::<Self>().expect("checked above");
+                (
+                    request.url.clone(),
+                    request.method.clone(),
+                    Headers::list(&request.headers),
+                    Some(request.body.clone()),
+                )
+            }
+            None => (
+                parse_request_url(input, context)?,
+                String::from("GET"),
+                Vec::new(),
+                None,
+            ),
+        };
+
+        let mut init_body = None;
+        if let Some(init) = init.as_object() {
+            let body = init.get(js_string!("body"), context)?;
+            if !body.is_undefined() {
+                init_body = Some(body::extract(&body, context)?);
+            }
+            let headers_init = init.get(js_string!("headers"), context)?;
+            if !headers_init.is_undefined() {
+                headers = Headers::list_from_init(&headers_init, context)?;
+            }
+            let method_init = init.get(js_string!("method"), context)?;
+            if !method_init.is_undefined() {
+                method = normalize_method(&to_usv_string(&method_init, context)?)?;
+            }
+        } else if !init.is_null_or_undefined() {
+            return Err(JsNativeError::typ()
+                .with_message("the request options must be an object")
+                .into());
+        }
+
+        let inherits_body = init_body.is_none();
+        let body = match init_body {
+            Some(Some((bytes, content_type))) => {
+                if let Some(content_type) = content_type {
+                    if !headers.iter().any(|(name, _)| name == "content-type") {
+                        headers.push(("content-type".to_owned(), content_type.to_owned()));
+                    }
+                }
+                Body::new(Some(bytes))
+            }
+            Some(None) => Body::default(),
+            None => input_body.map_or_else(|| Ok(Body::default()), |body| body.try_clone())?,
+        };
+
+        if !body.is_null() && matches!(method.as_str(), "GET" | "HEAD") {
+            return Err(JsNativeError::typ()
+                .with_message(format!("a {method} request cannot have a body"))
+                .into());
+        }
+
+        // Using the body of another request consumes it.
+        if let Some(request) = input_request.filter(|_| inherits_body && !body.is_null()) {
+            request
+                .downcast_mut::<Self>()
+                .expect("checked above")
+                .body
+                .take()?;
+        }
+
+        Ok(Self {
+            method,
+            url,
+            headers: Headers::create(headers, false, context)?,
+            body,
+        })
+    }
+
+    /// Converts a `Request` object into the request passed to the [`FetchProvider`], consuming
+    /// its body.
+    ///
+    /// [`FetchProvider`]: super::FetchProvider
+    pub(super) fn to_fetch_request(object: &JsObject) -> JsResult<FetchRequest> {
+        let mut request = object.downcast_mut::<Self>().expect("must be a Request");
+        let body = request.body.take()?;
+        Ok(FetchRequest {
+            method: request.method.clone(),
+            url: request.url.clone(),
+            headers: Headers::list(&request.headers),
+            body,
+        })
+    }
+
+    /// `get Request.prototype.method`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-request-method
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Request/method
+    fn get_method(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_request(this)?;
+        let request = object.downcast_ref::<Self>().expect("checked above");
+        Ok(JsString::from(request.method.as_str()).into())
+    }
+
+    /// `get Request.prototype.url`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-request-url
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Request/url
+    fn get_url(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_request(this)?;
+        let request = object.downcast_ref::<Self>().expect("checked above");
+        Ok(JsString::from(request.url.as_str()).into())
+    }
+
+    /// `get Request.prototype.headers`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-request-headers
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Request/headers
+    fn get_headers(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_request(this)?;
+        let request = object.downcast_ref::<Self>().expect("checked above");
+        Ok(request.headers.clone().into())
+    }
+
+    /// `Request.prototype.clone()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-request-clone
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Request/clone
+    fn clone_request(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_request(this)?;
+        let mut clone = object
+            .downcast_ref::<Self>()
+            .expect("checked above")
+            .clone();
+        clone.body = clone.body.try_clone()?;
+        clone.headers = Headers::create(Headers::list(&clone.headers), false, context)?;
+        Ok(Self::from_data(clone, context)?.into())
+    }
+
+    /// Checks that `this` is a `Request` object, returning it.
+    fn this_request(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a Request object")
+                    .into()
+            })
+    }
+}
+
+impl Class for Request {
+    const NAME: &'static str = "Request";
+    const LENGTH: usize = 1;
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(class, js_string!("method"), Self::get_method);
+        add_getter(class, js_string!("url"), Self::get_url);
+        add_getter(class, js_string!("headers"), Self::get_headers);
+        body::add_body_members::<Self>(class);
+        class
+            .method(
+                js_string!("clone"),
+                0,
+                NativeFunction::from_fn_ptr(Self::clone_request),
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("Request"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        Self::construct(args.get_or_undefined(0), args.get_or_undefined(1), context)
+    }
+}
@@ -0,0 +1,457 @@
+//! Boa's implementation of the `Response` object.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `Fetch` specification][spec]
+//!
+//! [spec]: https://fetch.spec.whatwg.org/#response-class
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response
+
+use super::{
+    body::{self, call_json, Body, HasBody},
+    headers::{self, Headers},
+    FetchResponse,
+};
+use crate::util::{add_getter, to_usv_string};
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::JsObject,
+    property::Attribute,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsString, JsSymbol, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+
+/// The type of a response, returned by `Response.prototype.type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResponseType {
+    /// A response received from the [`FetchProvider`](super::FetchProvider).
+    Basic,
+    /// A response created by a script.
+    Default,
+    /// A network error, created by `Response.error()`.
+    Error,
+}
+
+impl ResponseType {
+    /// Gets the name of the type.
+    fn name(self) -> JsString {
+        match self {
+            Self::Basic => js_string!("basic"),
+            Self::Default => js_string!("default"),
+            Self::Error => js_string!("error"),
+        }
+    }
+}
+
+/// Returns `true` if a response with status `status` must have a null body.
+const fn is_null_body_status(status: u16) -> bool {
+    matches!(status, 101 | 103 | 204 | 205 | 304)
+}
+
+/// The `Response` class, which represents the response to a request.
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct Response {
+    #[unsafe_ignore_trace]
+    kind: ResponseType,
+    status: u16,
+    status_text: String,
+    #[unsafe_ignore_trace]
+    url: Option<url::Url>,
+    /// The `Headers` object of the response.
+    headers: JsObject,
+    #[unsafe_ignore_trace]
+    body: Body,
+}
+
+impl HasBody for Response {
+    fn body(&self) -> &Body {
+        &self.body
+    }
+
+    fn body_mut(&mut self) -> &mut Body {
+        &mut self.body
+    }
+}
+
+impl Response {
+    /// Returns the status code of the response.
+    #[must_use]
+    pub const fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Creates a `Response` object from a response of the [`FetchProvider`] to a request to
+    /// `url`.
+    ///
+    /// [`FetchProvider`]: super::FetchProvider
+    pub(super) fn from_fetch_response(
+        response: FetchResponse,
+        url: url::Url,
+        context: &mut Context,
+    ) -> JsResult<JsObject> {
+        if !(200..=599).contains(&response.status) {
+            return Err(JsNativeError::typ()
+                .with_message(format!(
+                    "network error: invalid response status {}",
+                    response.status
+                ))
+                .into());
+        }
+        let headers = response
+            .headers
+            .iter()
+            .map(|(name, value)| headers::normalize(name, value))
+            .collect::<JsResult<_>>()?;
+        let body = (!is_null_body_status(response.status)).then_some(response.body);
+        let response = Self {
+            kind: ResponseType::Basic,
+            status: response.status,
+            status_text: response.status_text,
+            url: Some(url),
+            headers: Headers::create(headers, true, context)?,
+            body: Body::new(body),
+        };
+        Self::from_data(response, context)
+    }
+
+    /// Creates the data of a `Response` from the `body` and `init` arguments of the `Response`
+    /// constructor, with the body and its content type already extracted.
+    fn construct(
+        body: Option<(Vec<u8>, Option<&'static str>)>,
+        init: &JsValue,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        let mut status = 200;
+        let mut status_text = String::new();
+        let mut headers = Vec::new();
+        if let Some(init) = init.as_object() {
+            let headers_init = init.get(js_string!("headers"), context)?;
+            headers = Headers::list_from_init(&headers_init, context)?;
+            let status_init = init.get(js_string!("status"), context)?;
+            if !status_init.is_undefined() {
+                status = status_init.to_uint16(context)?;
+            }
+            let status_text_init = init.get(js_string!("statusText"), context)?;
+            if !status_text_init.is_undefined() {
+                status_text = to_usv_string(&status_text_init, context)?;
+            }
+        } else if !init.is_null_or_undefined() {
+            return Err(JsNativeError::typ()
+                .with_message("the response options must be an object")
+                .into());
+        }
+
+        if !(200..=599).contains(&status) {
+            return Err(JsNativeError::range()
+                .with_message(format!("{status} is not a valid response status"))
+                .into());
+        }
+        if status_text
+            .chars()
+            .any(|c| matches!(c, '\r' | '\n') || u32::from(c) > 0xFF)
+        {
+            return Err(JsNativeError::typ()
+                .with_message("the status message contains invalid characters")
+                .into());
+        }
+
+        let body = match body {
+            Some(_) if is_null_body_status(status) => {
+                return Err(JsNativeError::typ()
+                    .with_message(format!(
+                        "a response with status {status} cannot have a body"
+                    ))
+                    .into());
+            }
+            Some((bytes, content_type)) => {
+                if let Some(content_type) = content_type {
+                    if !headers.iter().any(|(name, _)| name == "content-type") {
+                        headers.push(("content-type".to_owned(), content_type.to_owned()));
+                    }
+                }
+                Body::new(Some(bytes))
+            }
+            None => Body::default(),
+        };
+
+        Ok(Self {
+            kind: ResponseType::Default,
+            status,
+            status_text,
+            url: None,
+            headers: Headers::create(headers, false, context)?,
+            body,
+        })
+    }
+
+    /// `Response.error()`
+    ///
+    /// Returns a new response representing a network error.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-response-error
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/error_static
+    fn error(_: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let response = Self {
+            kind: ResponseType::Error,
+            status: 0,
+            status_text: String::new(),
+            url: None,
+            headers: Headers::create(Vec::new(), true, context)?,
+            body: Body::default(),
+        };
+        Ok(Self::from_data(response, context)?.into())
+    }
+
+    /// `Response.json(data, init)`
+    ///
+    /// Returns a new response whose body is the JSON serialization of `data`.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-response-json
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/json_static
+    fn json(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let json = call_json(
+            js_string!("stringify"),
+            &[args.get_or_undefined(0).clone()],
+            context,
+        )?;
+        let Some(json) = json.as_string() else {
+            return Err(JsNativeError::typ()
+                .with_message("the data cannot be serialized to JSON")
+                .into());
+        };
+        let bytes = json.to_std_string_escaped().into_bytes();
+        let response = Self::construct(
+            Some((bytes, Some("application/json"))),
+            args.get_or_undefined(1),
+            context,
+        )?;
+        Ok(Self::from_data(response, context)?.into())
+    }
+
+    /// `Response.redirect(url, status = 302)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-response-redirect
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/redirect_static
+    fn redirect(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let input = to_usv_string(args.get_or_undefined(0), context)?;
+        let url = url::Url::parse(&input).map_err(|_| {
+            JsNativeError::typ().with_message(format!("'{input}' is not a valid absolute URL"))
+        })?;
+        let status = match args.get_or_undefined(1) {
+            JsValue::Undefined => 302,
+            status => status.to_uint16(context)?,
+        };
+        if !matches!(status, 301 | 302 | 303 | 307 | 308) {
+            return Err(JsNativeError::range()
+                .with_message(format!("{status} is not a valid redirect status"))
+                .into());
+        }
+        let headers = vec![("location".to_owned(), url.to_string())];
+        let response = Self {
+            kind: ResponseType::Default,
+            status,
+            status_text: String::new(),
+            url: None,
+            headers: Headers::create(headers, true, context)?,
+            body: Body::default(),
+        };
+        Ok(Self::from_data(response, context)?.into())
+    }
+
+    /// `get Response.prototype.type`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-response-type
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/type
+    fn get_type(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_response(this)?;
+        let response = object.downcast_ref::<Self>().expect("checked above");
+        Ok(response.kind.name().into())
+    }
+
+    /// `get Response.prototype.url`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-response-url
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/url
+    fn get_url(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_response(this)?;
+        let response = object.downcast_ref::<Self>().expect("checked above");
+        let url = response
+            .url
+            .as_ref()
+            .map_or("", |url| &url[..url::Position::AfterQuery]);
+        Ok(JsString::from(url).into())
+    }
+
+    /// `get Response.prototype.redirected`
+    ///
+    /// Always `false`, since redirects are handled by the [`FetchProvider`](super::FetchProvider).
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-response-redirected
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/redirected
+    fn get_redirected(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Self::this_response(this)?;
+        Ok(false.into())
+    }
+
+    /// `get Response.prototype.status`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-response-status
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/status
+    fn get_status(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_response(this)?;
+        let response = object.downcast_ref::<Self>().expect("checked above");
+        Ok(response.status.into())
+    }
+
+    /// `get Response.prototype.ok`
+    ///
+    /// Returns `true` if the status is in the range 200 to 299.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-response-ok
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/ok
+    fn get_ok(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_response(this)?;
+        let response = object.downcast_ref::<Self>().expect("checked above");
+        Ok((200..=299).contains(&response.status).into())
+    }
+
+    /// `get Response.prototype.statusText`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-response-statustext
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/statusText
+    fn get_status_text(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_response(this)?;
+        let response = object.downcast_ref::<Self>().expect("checked above");
+        Ok(JsString::from(response.status_text.as_str()).into())
+    }
+
+    /// `get Response.prototype.headers`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-response-headers
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/headers
+    fn get_headers(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_response(this)?;
+        let response = object.downcast_ref::<Self>().expect("checked above");
+        Ok(response.headers.clone().into())
+    }
+
+    /// `Response.prototype.clone()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Fetch` specification][spec]
+    ///
+    /// [spec]: https://fetch.spec.whatwg.org/#dom-response-clone
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Response/clone
+    fn clone_response(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_response(this)?;
+        let mut clone = object
+            .downcast_ref::<Self>()
+            .expect("checked above")
+            .clone();
+        let immutable = clone.kind != ResponseType::Default;
+        clone.body = clone.body.try_clone()?;
+        clone.headers = Headers::create(Headers::list(&clone.headers), immutable, context)?;
+        Ok(Self::from_data(clone, context)?.into())
+    }
+
+    /// Checks that `this` is a `Response` object, returning it.
+    fn this_response(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a Response object")
+                    .into()
+            })
+    }
+}
+
+impl Class for Response {
+    const NAME: &'static str = "Response";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(class, js_string!("type"), Self::get_type);
+        add_getter(class, js_string!("url"), Self::get_url);
+        add_getter(class, js_string!("redirected"), Self::get_redirected);
+        add_getter(class, js_string!("status"), Self::get_status);
+        add_getter(class, js_string!("ok"), Self::get_ok);
+        add_getter(class, js_string!("statusText"), Self::get_status_text);
+        add_getter(class, js_string!("headers"), Self::get_headers);
+        body::add_body_members::<Self>(class);
+        class
+            .static_method(
+                js_string!("error"),
+                0,
+                NativeFunction::from_fn_ptr(Self::error),
+            )
+            .static_method(
+                js_string!("json"),
+                1,
+                NativeFunction::from_fn_ptr(Self::json),
+            )
+            .static_method(
+                js_string!("redirect"),
+                1,
+                NativeFunction::from_fn_ptr(Self::redirect),
+            )
+            .method(
+                js_string!("clone"),
+                0,
+                NativeFunction::from_fn_ptr(Self::clone_response),
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("Response"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        let body = body::extract(args.get_or_undefined(0), context)?;
+        Self::construct(body, args.get_or_undefined(1), context)
+    }
+}
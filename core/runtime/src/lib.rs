@@ -51,15 +51,74 @@
     clippy::let_unit_value
 )]
 
+mod commonjs;
 mod console;
+mod crypto;
+mod event_loop;
+mod events;
+mod fetch;
+mod modules;
+mod permissions;
+mod streams;
+mod structured_clone;
+mod text;
+mod timers;
+mod url;
+mod util;
+mod workers;
 
 #[doc(inline)]
-pub use console::Console;
+pub use commonjs::{CommonJs, CommonJsModuleLoader};
+#[doc(inline)]
+pub use console::{Console, ConsoleLogger, DefaultLogger, LogLevel, LogMessage};
+#[doc(inline)]
+pub use crypto::{Crypto, OsRandom, RandomSource, SeededRandom};
+#[doc(inline)]
+pub use event_loop::EventLoop;
+#[doc(inline)]
+pub use events::{AbortController, AbortSignal, CustomEvent, Event, EventTarget, Events};
+#[doc(inline)]
+pub use fetch::{
+    Fetch, FetchFuture, FetchProvider, FetchRequest, FetchResponse, Headers, InMemoryFetchProvider,
+    Request, Response,
+};
+#[doc(inline)]
+pub use modules::{ArchiveSource, ImportMap, NodeModuleLoader};
+#[doc(inline)]
+pub use permissions::Permissions;
+#[doc(inline)]
+pub use streams::{
+    ByteLengthQueuingStrategy, ByteStream, CountQueuingStrategy, ReadableStream,
+    ReadableStreamDefaultController, ReadableStreamDefaultReader, Streams, TransformStream,
+    TransformStreamDefaultController, WritableStream, WritableStreamDefaultController,
+    WritableStreamDefaultWriter,
+};
+#[doc(inline)]
+pub use structured_clone::{SerializedValue, StructuredClone};
+#[doc(inline)]
+pub use text::{TextDecoder, TextEncoder};
+#[doc(inline)]
+pub use timers::Timers;
+#[doc(inline)]
+pub use url::{Url, UrlSearchParams};
+#[doc(inline)]
+pub use workers::{Worker, WorkerConfig, WorkerEvent, WorkerHandle, Workers};
 
 #[cfg(test)]
 pub(crate) mod test {
-    use boa_engine::{builtins, Context, JsResult, JsValue, Source};
-    use std::borrow::Cow;
+    use crate::{
+        EventLoop, Events, Streams, StructuredClone, TextDecoder, TextEncoder, Timers, Url,
+        UrlSearchParams,
+    };
+    use boa_engine::{
+        builtins, context::ContextBuilder, js_string, Context, JsResult, JsValue, Source,
+    };
+    use std::{
+        borrow::Cow,
+        fs,
+        path::{Path, PathBuf},
+        rc::Rc,
+    };
 
     /// A test action executed in a test function.
     #[allow(missing_debug_implementations)]
@@ -73,6 +132,9 @@ pub(crate) mod test {
         Run {
             source: Cow<'static, str>,
         },
+        RunFile {
+            path: PathBuf,
+        },
         InspectContext {
             op: fn(&mut Context),
         },
@@ -109,18 +171,71 @@ pub(crate) mod test {
             })
         }
 
+        /// Runs the script at `path`, panicking if the execution throws.
+        pub(crate) fn run_file(path: impl Into<PathBuf>) -> Self {
+            Self(Inner::RunFile { path: path.into() })
+        }
+
+        /// Evaluates `source`, panicking if the result is not equal to `expected`.
+        pub(crate) fn assert_eq(
+            source: impl Into<Cow<'static, str>>,
+            expected: impl Into<JsValue>,
+        ) -> Self {
+            Self(Inner::AssertEq {
+                source: source.into(),
+                expected: expected.into(),
+            })
+        }
+
+        /// Evaluates `source`, panicking if the result is not `true`.
+        pub(crate) fn assert(source: impl Into<Cow<'static, str>>) -> Self {
+            Self(Inner::Assert {
+                source: source.into(),
+            })
+        }
+
         /// Executes `op` with the currently active context.
         ///
         /// Useful to make custom assertions that must be done from Rust code.
         pub(crate) fn inspect_context(op: fn(&mut Context)) -> Self {
             Self(Inner::InspectContext { op })
         }
+
+        /// Asserts that calling `op` with the currently active context returns `true`.
+        pub(crate) fn assert_context(op: fn(&mut Context) -> bool) -> Self {
+            Self(Inner::AssertContext { op })
+        }
     }
 
-    /// Executes a list of test actions on a new, default context.
+    /// Executes a list of test actions on a new context with the APIs of the runtime that don't
+    /// need to be configured by the host: events, timers, `URL`, the encoding classes,
+    /// `structuredClone` and streams.
+    ///
+    /// The context runs on an [`EventLoop`] using virtual time, which can be retrieved with
+    /// [`EventLoop::of`].
     #[track_caller]
     pub(crate) fn run_test_actions(actions: impl IntoIterator<Item = TestAction>) {
-        let context = &mut Context::default();
+        let event_loop = Rc::new(EventLoop::with_virtual_time());
+        let context = &mut ContextBuilder::new()
+            .job_queue(event_loop.clone())
+            .build()
+            .expect("failed to build the context");
+        Events::register(&event_loop, context).expect("failed to register the event classes");
+        Timers::register(&event_loop, context).expect("failed to register the timers");
+        context
+            .register_global_class::<Url>()
+            .expect("failed to register the URL class");
+        context
+            .register_global_class::<UrlSearchParams>()
+            .expect("failed to register the URLSearchParams class");
+        context
+            .register_global_class::<TextEncoder>()
+            .expect("failed to register the TextEncoder class");
+        context
+            .register_global_class::<TextDecoder>()
+            .expect("failed to register the TextDecoder class");
+        StructuredClone::register(context).expect("failed to register structuredClone");
+        Streams::register(context).expect("failed to register the stream classes");
         run_test_actions_with(actions, context);
     }
 
@@ -176,6 +291,12 @@ pub(crate) mod test {
                         panic!("{}\nUncaught {e}", fmt_test(&source, i));
                     }
                 }
+                Inner::RunFile { path } => {
+                    let source = Source::from_filepath(&path).expect("failed to open the script");
+                    if let Err(e) = context.eval(source) {
+                        panic!("\n\nTest case {i}: `{}`\nUncaught {e}", path.display());
+                    }
+                }
                 Inner::InspectContext { op } => {
                     op(context);
                 }
@@ -262,4 +383,52 @@ pub(crate) mod test {
             }
         }
     }
+
+    /// A temporary directory, removed when dropped.
+    pub(crate) struct TempDir(PathBuf);
+
+    impl TempDir {
+        /// Creates an empty temporary directory, unique to `name` and to the test process.
+        pub(crate) fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("boa-{name}-{}", std::process::id()));
+            drop(fs::remove_dir_all(&path));
+            fs::create_dir_all(&path).unwrap();
+            Self(path.canonicalize().unwrap())
+        }
+
+        /// Writes `contents` to the file at the relative path `file`, creating its directories.
+        pub(crate) fn write(&self, file: &str, contents: &str) -> &Self {
+            let path = self.0.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+            self
+        }
+
+        /// Gets the canonical path of the directory.
+        pub(crate) fn path(&self) -> &Path {
+            &self.0
+        }
+
+        /// Creates an action defining the global `root` of the test context as the path of the
+        /// directory, which can then be read from Rust code with [`root`].
+        pub(crate) fn define_root(&self) -> TestAction {
+            TestAction::run(format!("var root = {:?};", self.0.to_string_lossy()))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            drop(fs::remove_dir_all(&self.0));
+        }
+    }
+
+    /// Gets the path defined by [`TempDir::define_root`].
+    pub(crate) fn root(context: &mut Context) -> PathBuf {
+        let root = context
+            .global_object()
+            .get(js_string!("root"), context)
+            .and_then(|root| root.to_string(context))
+            .expect("the root is a global property");
+        PathBuf::from(root.to_std_string_escaped())
+    }
 }
@@ -0,0 +1,139 @@
+//! A module source reading the modules of an application from a zip or tar archive.
+
+use boa_engine::module::{InMemorySource, ModuleSource};
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+};
+
+/// A [`ModuleSource`] with the files of a zip or tar archive, which allows shipping all the
+/// modules of an application in one bundle file.
+///
+/// The files of the archive are read into memory when the source is created, and are exposed
+/// under the virtual root `/`, so the file `lib/util.js` of the archive is the module
+/// `/lib/util.js`. Directories, links and entries leaving the root of the archive are skipped.
+///
+/// Reading zip and tar archives needs the `zip` and `tar` features, which are enabled by
+/// default.
+///
+/// ```no_run
+/// use boa_engine::{context::ContextBuilder, module::SimpleModuleLoader};
+/// use boa_runtime::ArchiveSource;
+/// use std::rc::Rc;
+///
+/// let source = ArchiveSource::open("app.zip").expect("could not open the bundle");
+/// let loader = Rc::new(SimpleModuleLoader::with_source(source, "/"));
+/// let context = ContextBuilder::new()
+///     .module_loader(loader.clone())
+///     .build()?;
+/// # Ok::<(), boa_engine::JsError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveSource {
+    files: InMemorySource,
+}
+
+impl ArchiveSource {
+    /// Reads the zip or tar archive at `path`, depending on its extension: `.zip` for zip
+    /// archives and `.tar` for tar archives.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive cannot be read, or if its format isn't supported.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let reader = BufReader::new(File::open(path)?);
+        match path.extension().and_then(|extension| extension.to_str()) {
+            #[cfg(feature = "zip")]
+            Some("zip") => Self::from_zip(reader),
+            #[cfg(feature = "tar")]
+            Some("tar") => Self::from_tar(reader),
+            _ => {
+                drop(reader);
+                Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("unsupported archive `{}`", path.display()),
+                ))
+            }
+        }
+    }
+
+    /// Reads a zip archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive or one of its files cannot be read.
+    #[cfg(feature = "zip")]
+    pub fn from_zip<R: io::Read + io::Seek>(reader: R) -> io::Result<Self> {
+        let mut archive = zip::ZipArchive::new(reader)?;
+        let mut source = Self::default();
+        for index in 0..archive.len() {
+            let mut file = archive.by_index(index)?;
+            if !file.is_file() {
+                continue;
+            }
+            let Some(path) = file.enclosed_name().map(Path::to_path_buf) else {
+                continue;
+            };
+            let mut contents = Vec::new();
+            io::Read::read_to_end(&mut file, &mut contents)?;
+            source.files.insert(path, contents);
+        }
+        Ok(source)
+    }
+
+    /// Reads a tar archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive or one of its files cannot be read.
+    #[cfg(feature = "tar")]
+    pub fn from_tar<R: io::Read>(reader: R) -> io::Result<Self> {
+        let mut archive = tar::Archive::new(reader);
+        let mut source = Self::default();
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let Some(path) = enclosed_path(&entry.path()?) else {
+                continue;
+            };
+            let mut contents = Vec::new();
+            io::Read::read_to_end(&mut entry, &mut contents)?;
+            source.files.insert(path, contents);
+        }
+        Ok(source)
+    }
+
+    /// Gets the files of the archive.
+    #[must_use]
+    pub const fn files(&self) -> &InMemorySource {
+        &self.files
+    }
+}
+
+impl ModuleSource for ArchiveSource {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.files.read(path)
+    }
+}
+
+/// Returns the relative path of an archive entry at `path`, or `None` if the entry would leave
+/// the root of the archive.
+#[cfg(feature = "tar")]
+fn enclosed_path(path: &Path) -> Option<std::path::PathBuf> {
+    use std::path::{Component, PathBuf};
+
+    let mut enclosed = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => enclosed.push(name),
+            Component::CurDir => {}
+            Component::ParentDir if enclosed.pop() => {}
+            _ => return None,
+        }
+    }
+    Some(enclosed)
+}
@@ -0,0 +1,248 @@
+//! Boa's implementation of WHATWG import maps.
+//!
+//! More information:
+//!  - [HTML specification][spec]
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/webappapis.html#import-maps
+
+use boa_engine::{JsError, JsNativeError, JsResult};
+use serde_json::Value;
+use std::path::Path;
+use url::Url;
+
+/// A specifier map, sorted by descending specifier keys so that longer prefixes match first.
+///
+/// A `None` address is an entry that was invalid, which blocks the specifiers it matches.
+type SpecifierMap = Vec<(String, Option<Url>)>;
+
+/// An [import map][spec], which remaps the module specifiers imported by modules.
+///
+/// ```
+/// use boa_runtime::ImportMap;
+/// use url::Url;
+///
+/// let base = Url::parse("file:///app/import-map.json").unwrap();
+/// let import_map = ImportMap::parse(
+///     r#"{
+///         "imports": { "lodash": "./vendor/lodash/index.js", "lib/": "./src/lib/" },
+///         "scopes": { "./vendor/": { "lodash": "./vendor/lodash-old/index.js" } }
+///     }"#,
+///     &base,
+/// )?;
+///
+/// let main = Url::parse("file:///app/main.js").unwrap();
+/// assert_eq!(
+///     import_map.resolve("lib/math.js", &main)?.unwrap().as_str(),
+///     "file:///app/src/lib/math.js"
+/// );
+/// assert!(import_map.resolve("./other.js", &main)?.is_none());
+/// # Ok::<(), boa_engine::JsError>(())
+/// ```
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/webappapis.html#import-maps
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportMap {
+    imports: SpecifierMap,
+    scopes: Vec<(String, SpecifierMap)>,
+}
+
+impl ImportMap {
+    /// [`parse an import map string`][spec], resolving its addresses and scopes against the URL
+    /// `base` of the import map.
+    ///
+    /// The invalid entries of the specifier maps are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is not valid JSON, or if it isn't an object whose `imports` and
+    /// `scopes` properties are objects.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/webappapis.html#parse-an-import-map-string
+    pub fn parse(json: &str, base: &Url) -> JsResult<Self> {
+        let parsed: Value = serde_json::from_str(json).map_err(|e| {
+            JsNativeError::syntax().with_message(format!("invalid import map: {e}"))
+        })?;
+        let Value::Object(parsed) = parsed else {
+            return Err(invalid("the top-level value needs to be a JSON object"));
+        };
+
+        let imports = match parsed.get("imports") {
+            None => SpecifierMap::new(),
+            Some(Value::Object(imports)) => sort_and_normalize_specifier_map(imports, base),
+            Some(_) => {
+                return Err(invalid(
+                    "the \"imports\" top-level key needs to be an object",
+                ))
+            }
+        };
+
+        let mut scopes = Vec::new();
+        match parsed.get("scopes") {
+            None => {}
+            Some(Value::Object(map)) => {
+                for (prefix, imports) in map {
+                    let Value::Object(imports) = imports else {
+                        return Err(invalid(&format!(
+                            "the value of the scope \"{prefix}\" needs to be an object"
+                        )));
+                    };
+                    // Scopes whose prefix cannot be parsed are ignored.
+                    if let Ok(prefix) = base.join(prefix) {
+                        scopes.push((
+                            prefix.to_string(),
+                            sort_and_normalize_specifier_map(imports, base),
+                        ));
+                    }
+                }
+            }
+            Some(_) => {
+                return Err(invalid(
+                    "the \"scopes\" top-level key needs to be an object",
+                ))
+            }
+        }
+        scopes.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        Ok(Self { imports, scopes })
+    }
+
+    /// Reads and parses the import map file at `path`, resolving its addresses and scopes
+    /// against the location of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or if it isn't a valid import map.
+    pub fn load<P: AsRef<Path>>(path: P) -> JsResult<Self> {
+        let path = path.as_ref();
+        let read_error = |message: String| {
+            JsNativeError::typ().with_message(format!(
+                "could not read the import map `{}`: {message}",
+                path.display()
+            ))
+        };
+        let json = std::fs::read_to_string(path).map_err(|e| read_error(e.to_string()))?;
+        let base = path
+            .canonicalize()
+            .ok()
+            .and_then(|path| Url::from_file_path(path).ok())
+            .ok_or_else(|| read_error("invalid path".to_owned()))?;
+        Self::parse(&json, &base)
+    }
+
+    /// [`resolve a module specifier`][spec] imported by the module at `base`, returning `None`
+    /// if the import map doesn't remap it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `specifier` is blocked by an invalid entry of the import map, or if
+    /// it would resolve outside of the address of a prefix entry.
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/webappapis.html#resolve-a-module-specifier
+    pub fn resolve(&self, specifier: &str, base: &Url) -> JsResult<Option<Url>> {
+        let as_url = resolve_url_like_specifier(specifier, base);
+        let normalized = as_url.as_ref().map_or(specifier, Url::as_str);
+
+        for (prefix, imports) in &self.scopes {
+            if prefix == base.as_str()
+                || (prefix.ends_with('/') && base.as_str().starts_with(prefix))
+            {
+                if let Some(url) = resolve_imports_match(normalized, as_url.as_ref(), imports)? {
+                    return Ok(Some(url));
+                }
+            }
+        }
+        resolve_imports_match(normalized, as_url.as_ref(), &self.imports)
+    }
+}
+
+/// [`sort and normalize a specifier map`][spec].
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/webappapis.html#sorting-and-normalizing-a-module-specifier-map
+fn sort_and_normalize_specifier_map(
+    map: &serde_json::Map<String, Value>,
+    base: &Url,
+) -> SpecifierMap {
+    let mut normalized = SpecifierMap::new();
+    for (key, value) in map {
+        if key.is_empty() {
+            continue;
+        }
+        let key = resolve_url_like_specifier(key, base).map_or_else(|| key.clone(), String::from);
+        let address = value
+            .as_str()
+            .and_then(|address| resolve_url_like_specifier(address, base))
+            .filter(|address| !key.ends_with('/') || address.as_str().ends_with('/'));
+        normalized.push((key, address));
+    }
+    normalized.sort_by(|(a, _), (b, _)| b.cmp(a));
+    normalized
+}
+
+/// [`resolve an imports match`][spec], returning `None` if no entry of `imports` matches.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/webappapis.html#resolving-an-imports-match
+fn resolve_imports_match(
+    normalized: &str,
+    as_url: Option<&Url>,
+    imports: &SpecifierMap,
+) -> JsResult<Option<Url>> {
+    for (key, address) in imports {
+        if key == normalized {
+            return address.clone().map(Some).ok_or_else(|| blocked(normalized));
+        }
+        if key.ends_with('/')
+            && normalized.starts_with(key.as_str())
+            && as_url.map_or(true, is_special)
+        {
+            let address = address.as_ref().ok_or_else(|| blocked(normalized))?;
+            let after_prefix = &normalized[key.len()..];
+            let url = address
+                .join(after_prefix)
+                .ok()
+                .filter(|url| url.as_str().starts_with(address.as_str()))
+                .ok_or_else(|| {
+                    JsNativeError::typ().with_message(format!(
+                        "the specifier \"{normalized}\" backtracks above its prefix \"{key}\""
+                    ))
+                })?;
+            return Ok(Some(url));
+        }
+    }
+    Ok(None)
+}
+
+/// [`resolve a URL-like module specifier`][spec], returning `None` for bare specifiers.
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/webappapis.html#resolving-a-url-like-module-specifier
+fn resolve_url_like_specifier(specifier: &str, base: &Url) -> Option<Url> {
+    if specifier.starts_with('/') || specifier.starts_with("./") || specifier.starts_with("../") {
+        return base.join(specifier).ok();
+    }
+    Url::parse(specifier).ok()
+}
+
+/// Returns `true` if `url` has a [special scheme][spec].
+///
+/// [spec]: https://url.spec.whatwg.org/#special-scheme
+fn is_special(url: &Url) -> bool {
+    matches!(
+        url.scheme(),
+        "ftp" | "file" | "http" | "https" | "ws" | "wss"
+    )
+}
+
+/// Creates the error of an import map with an invalid structure.
+fn invalid(message: &str) -> JsError {
+    JsNativeError::typ()
+        .with_message(format!("invalid import map: {message}"))
+        .into()
+}
+
+/// Creates the error of a specifier blocked by an invalid entry of the import map.
+fn blocked(specifier: &str) -> JsError {
+    JsNativeError::typ()
+        .with_message(format!(
+            "the import map blocks the resolution of \"{specifier}\""
+        ))
+        .into()
+}
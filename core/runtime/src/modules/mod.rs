@@ -0,0 +1,302 @@
+//! Boa's resolution of the bare module specifiers of ECMAScript modules, through import maps and
+//! through the packages of the `node_modules` directories.
+//!
+//! The [`NodeModuleLoader`] resolves the specifiers of the imports to absolute paths, and
+//! delegates the loading of the modules to an inner loader, e.g. a [`SimpleModuleLoader`]. The
+//! [`ArchiveSource`] allows a `SimpleModuleLoader` to load the modules from a zip or tar archive.
+//!
+//! More information:
+//!  - [Node.js documentation][node]
+//!  - [HTML specification of import maps][spec]
+//!
+//! [node]: https://nodejs.org/api/esm.html#resolution-algorithm
+//! [spec]: https://html.spec.whatwg.org/multipage/webappapis.html#import-maps
+//! [`SimpleModuleLoader`]: boa_engine::module::SimpleModuleLoader
+
+#[cfg(test)]
+mod tests;
+
+mod archive;
+mod import_map;
+mod resolve;
+
+#[doc(inline)]
+pub use archive::ArchiveSource;
+#[doc(inline)]
+pub use import_map::ImportMap;
+
+use crate::Permissions;
+use boa_engine::{
+    js_string,
+    module::{ModuleLoader, PreloadModuleLoader, Referrer},
+    object::JsObject,
+    Context, JsError, JsNativeError, JsResult, JsString, Module,
+};
+use std::path::{Component, Path, PathBuf};
+use url::Url;
+
+/// A [`ModuleLoader`] that resolves the bare specifiers of the imports like Node.js, and
+/// delegates the loading of the resolved modules to an inner loader.
+///
+/// The specifiers are resolved, in order:
+///  - through the [`ImportMap`] of the loader, if any;
+///  - through the `imports` of the nearest `package.json` for `#` specifiers;
+///  - through the packages of the `node_modules` directories for bare specifiers, following
+///    their `exports` with the conditions of the loader, or their `main` file.
+///
+/// Relative specifiers not remapped by the import map are passed unchanged to the inner loader,
+/// and all the other specifiers are passed as absolute paths. The directory of the importing
+/// module is used for the resolution, or `root` if the importer has no path.
+///
+/// Every imported module, static or dynamic, needs the read access to its resolved path from
+/// the [`Permissions`] of the context, if any.
+///
+/// ```no_run
+/// use boa_engine::{context::ContextBuilder, module::SimpleModuleLoader};
+/// use boa_runtime::NodeModuleLoader;
+/// use std::rc::Rc;
+///
+/// let loader = NodeModuleLoader::new(SimpleModuleLoader::new("app")?, "app")?
+///     .with_conditions(["import", "browser"]);
+/// let context = ContextBuilder::new()
+///     .module_loader(Rc::new(loader))
+///     .build()?;
+/// # Ok::<(), boa_engine::JsError>(())
+/// ```
+#[derive(Debug)]
+pub struct NodeModuleLoader<L> {
+    inner: L,
+    root: PathBuf,
+    import_map: Option<ImportMap>,
+    conditions: Vec<String>,
+}
+
+impl<L: ModuleLoader> NodeModuleLoader<L> {
+    /// Creates a new `NodeModuleLoader` resolving the specifiers of importers without a path
+    /// against `root`, and delegating the loading of the modules to `inner`.
+    ///
+    /// The loader has no import map, and matches the `import` and `default` conditions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `root` cannot be canonicalized.
+    pub fn new<P: AsRef<Path>>(inner: L, root: P) -> JsResult<Self> {
+        let root = root.as_ref();
+        let absolute = root.canonicalize().map_err(|e| {
+            JsNativeError::typ()
+                .with_message(format!("could not set module root `{}`", root.display()))
+                .with_cause(JsError::from_opaque(js_string!(e.to_string()).into()))
+        })?;
+        Ok(Self {
+            inner,
+            root: absolute,
+            import_map: None,
+            conditions: vec!["import".to_owned()],
+        })
+    }
+
+    /// Sets the import map remapping the specifiers before any other resolution.
+    #[must_use]
+    pub fn with_import_map(mut self, import_map: ImportMap) -> Self {
+        self.import_map = Some(import_map);
+        self
+    }
+
+    /// Sets the conditions matched by the `exports` and `imports` of the packages, in addition
+    /// to the `default` condition.
+    #[must_use]
+    pub fn with_conditions<I, S>(mut self, conditions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.conditions = conditions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Gets the loader of the resolved modules.
+    pub const fn inner(&self) -> &L {
+        &self.inner
+    }
+
+    /// Gets the directory the specifiers imported by the module or script at `referrer` are
+    /// resolved against.
+    fn base_dir(&self, referrer: Option<&Path>) -> PathBuf {
+        referrer
+            .map(|referrer| self.root.join(referrer))
+            .and_then(|referrer| referrer.parent().map(Path::to_path_buf))
+            .unwrap_or_else(|| self.root.clone())
+    }
+
+    /// Resolves `specifier` imported by the module or script at `referrer`, returning `None` if
+    /// it must be passed unchanged to the inner loader.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the import map blocks `specifier`, or if it cannot be resolved.
+    pub fn resolve(
+        &self,
+        specifier: &str,
+        referrer: Option<&Path>,
+        context: &mut Context,
+    ) -> JsResult<Option<PathBuf>> {
+        let dir = self.base_dir(referrer);
+        let referrer = referrer.map(|referrer| self.root.join(referrer));
+
+        if let Some(import_map) = &self.import_map {
+            let base = match &referrer {
+                Some(referrer) => Url::from_file_path(referrer).ok(),
+                None => Url::from_directory_path(&self.root).ok(),
+            };
+            if let Some(url) = base
+                .map(|base| import_map.resolve(specifier, &base))
+                .transpose()?
+                .flatten()
+            {
+                return file_url_to_path(&url).map(Some);
+            }
+        }
+
+        if specifier.starts_with('#') {
+            return resolve::package_imports_resolve(specifier, &dir, &self.conditions, context)
+                .map(Some);
+        }
+        if is_path(specifier) {
+            return Ok(None);
+        }
+        if let Ok(url) = Url::parse(specifier) {
+            return file_url_to_path(&url).map(Some);
+        }
+        resolve::package_resolve(specifier, &dir, &self.conditions, context).map(Some)
+    }
+
+    /// Resolves `specifier` imported by the module or script at `referrer` to the path of the
+    /// imported module, including the specifiers passed unchanged to the inner loader.
+    fn resolve_path(
+        &self,
+        specifier: &str,
+        referrer: Option<&Path>,
+        context: &mut Context,
+    ) -> JsResult<PathBuf> {
+        Ok(self
+            .resolve(specifier, referrer, context)?
+            .unwrap_or_else(|| self.base_dir(referrer).join(specifier)))
+    }
+}
+
+impl<L: PreloadModuleLoader> NodeModuleLoader<L> {
+    /// Parses the module at `path` and the modules it imports statically, directly or
+    /// transitively, ahead of loading them, returning the module at `path`.
+    ///
+    /// The imports are resolved like the imports of the loaded modules, through the import map
+    /// and the packages. Relative paths are relative to `root`. The imported modules that cannot
+    /// be resolved are left to be loaded normally, which reports their errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [`Permissions`] of the context don't allow reading the module at
+    /// `path`, or if it cannot be read or parsed.
+    pub fn preload(&self, path: &Path, context: &mut Context) -> JsResult<Module> {
+        self.preload_with(
+            path,
+            &mut |referrer, specifier, context| {
+                self.resolve_path(specifier, Some(referrer), context).ok()
+            },
+            context,
+        )
+    }
+}
+
+impl<L: ModuleLoader> ModuleLoader for NodeModuleLoader<L> {
+    fn load_imported_module(
+        &self,
+        referrer: Referrer,
+        specifier: JsString,
+        finish_load: Box<dyn FnOnce(JsResult<Module>, &mut Context)>,
+        context: &mut Context,
+    ) {
+        let resolved = self
+            .resolve(&specifier.to_std_string_escaped(), referrer.path(), context)
+            .and_then(|path| {
+                let checked = path.clone().unwrap_or_else(|| {
+                    self.base_dir(referrer.path())
+                        .join(specifier.to_std_string_escaped())
+                });
+                Permissions::check_read(&checked, context)?;
+                Ok(path)
+            });
+        match resolved {
+            Ok(Some(path)) => self.inner.load_imported_module(
+                referrer,
+                JsString::from(path.to_string_lossy().as_ref()),
+                finish_load,
+                context,
+            ),
+            Ok(None) => self
+                .inner
+                .load_imported_module(referrer, specifier, finish_load, context),
+            Err(e) => finish_load(Err(e), context),
+        }
+    }
+
+    fn register_module(&self, specifier: JsString, module: Module) {
+        self.inner.register_module(specifier, module);
+    }
+
+    fn get_module(&self, specifier: JsString) -> Option<Module> {
+        self.inner.get_module(specifier)
+    }
+
+    fn init_import_meta(&self, import_meta: &JsObject, module: &Module, context: &mut Context) {
+        self.inner.init_import_meta(import_meta, module, context);
+    }
+}
+
+impl<L: PreloadModuleLoader> PreloadModuleLoader for NodeModuleLoader<L> {
+    /// Preloads the module at `path` with the inner loader, skipping the imported modules that
+    /// the [`Permissions`] of the context don't allow reading, which makes their loading fail.
+    fn preload_with(
+        &self,
+        path: &Path,
+        resolve: &mut dyn FnMut(&Path, &str, &mut Context) -> Option<PathBuf>,
+        context: &mut Context,
+    ) -> JsResult<Module> {
+        let path = self.root.join(path);
+        Permissions::check_read(&path, context)?;
+        self.inner.preload_with(
+            &path,
+            &mut |referrer, specifier, context| {
+                let path = resolve(referrer, specifier, context)?;
+                Permissions::check_read(&path, context).ok()?;
+                Some(path)
+            },
+            context,
+        )
+    }
+}
+
+/// Returns `true` if `specifier` is a relative or absolute path.
+fn is_path(specifier: &str) -> bool {
+    let path = Path::new(specifier);
+    path.is_absolute()
+        || matches!(
+            path.components().next(),
+            Some(Component::CurDir | Component::ParentDir)
+        )
+}
+
+/// Converts a `file:` URL to a path.
+fn file_url_to_path(url: &Url) -> JsResult<PathBuf> {
+    if url.scheme() != "file" {
+        return Err(JsNativeError::typ()
+            .with_message(format!(
+                "cannot load the module '{url}': unsupported URL scheme"
+            ))
+            .into());
+    }
+    url.to_file_path().map_err(|()| {
+        JsNativeError::typ()
+            .with_message(format!("cannot load the module '{url}': invalid file URL"))
+            .into()
+    })
+}
@@ -0,0 +1,386 @@
+//! The resolution of the bare specifiers of ECMAScript modules, following the algorithm of
+//! Node.js.
+//!
+//! More information:
+//!  - [Node.js documentation][node]
+//!
+//! [node]: https://nodejs.org/api/esm.html#resolution-algorithm-specification
+
+use crate::commonjs::read_package_json;
+use boa_engine::{
+    js_string,
+    object::{builtins::JsArray, JsObject},
+    property::PropertyKey,
+    Context, JsError, JsNativeError, JsResult, JsValue,
+};
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// `PACKAGE_RESOLVE(packageSpecifier, parentURL)`: resolves the bare specifier `specifier`
+/// imported from a module in the directory `dir`.
+pub(super) fn package_resolve(
+    specifier: &str,
+    dir: &Path,
+    conditions: &[String],
+    context: &mut Context,
+) -> JsResult<PathBuf> {
+    let (name, subpath) = parse_package_name(specifier).ok_or_else(|| {
+        JsNativeError::typ().with_message(format!("invalid module specifier '{specifier}'"))
+    })?;
+
+    if let Some((scope, package)) = lookup_package_scope(dir, context)? {
+        let exports = package.get(js_string!("exports"), context)?;
+        let own_name = package.get(js_string!("name"), context)?;
+        if own_name.as_string() == Some(&js_string!(name)) && !exports.is_null_or_undefined() {
+            return package_exports_resolve(&scope, &subpath, &exports, conditions, context);
+        }
+    }
+
+    for dir in dir.ancestors() {
+        if dir.file_name().is_some_and(|name| name == "node_modules") {
+            continue;
+        }
+        let package_dir = dir.join("node_modules").join(name);
+        if !package_dir.is_dir() {
+            continue;
+        }
+        let package = read_package_json(&package_dir, context)?;
+        if let Some(package) = &package {
+            let exports = package.get(js_string!("exports"), context)?;
+            if !exports.is_null_or_undefined() {
+                return package_exports_resolve(
+                    &package_dir,
+                    &subpath,
+                    &exports,
+                    conditions,
+                    context,
+                );
+            }
+        }
+        if subpath == "." {
+            return legacy_main_resolve(&package_dir, package.as_ref(), context);
+        }
+        return Ok(package_dir.join(subpath));
+    }
+
+    Err(JsNativeError::typ()
+        .with_message(format!(
+            "cannot find package '{name}' imported from '{}'",
+            dir.display()
+        ))
+        .into())
+}
+
+/// `PACKAGE_IMPORTS_RESOLVE(specifier, parentURL, conditions)`: resolves the `#` specifier
+/// `specifier` through the `imports` of the package containing the directory `dir`.
+pub(super) fn package_imports_resolve(
+    specifier: &str,
+    dir: &Path,
+    conditions: &[String],
+    context: &mut Context,
+) -> JsResult<PathBuf> {
+    if specifier == "#" || specifier.starts_with("#/") {
+        return Err(JsNativeError::typ()
+            .with_message(format!("invalid module specifier '{specifier}'"))
+            .into());
+    }
+    if let Some((scope, package)) = lookup_package_scope(dir, context)? {
+        let imports = package.get(js_string!("imports"), context)?;
+        if let Some(imports) = imports.as_object().filter(|imports| !imports.is_array()) {
+            if let Some(path) = package_imports_exports_resolve(
+                specifier, imports, &scope, true, conditions, context,
+            )? {
+                return Ok(path);
+            }
+        }
+    }
+    Err(JsNativeError::typ()
+        .with_message(format!(
+            "package import specifier '{specifier}' is not defined in the package of '{}'",
+            dir.display()
+        ))
+        .into())
+}
+
+/// Splits a bare specifier into its package name and its subpath, which starts with `.`.
+fn parse_package_name(specifier: &str) -> Option<(&str, String)> {
+    let end = if specifier.starts_with('@') {
+        let scope_end = specifier.find('/')?;
+        specifier[scope_end + 1..]
+            .find('/')
+            .map_or(specifier.len(), |end| scope_end + 1 + end)
+    } else {
+        specifier.find('/').unwrap_or(specifier.len())
+    };
+    let name = &specifier[..end];
+    if name.is_empty() || name.starts_with('.') || name.contains(['\\', '%']) {
+        return None;
+    }
+    Some((name, format!(".{}", &specifier[end..])))
+}
+
+/// `LOOKUP_PACKAGE_SCOPE(url)`: finds the nearest package containing the directory `dir`,
+/// returning its directory and its `package.json`.
+fn lookup_package_scope(
+    dir: &Path,
+    context: &mut Context,
+) -> JsResult<Option<(PathBuf, JsObject)>> {
+    for dir in dir.ancestors() {
+        if dir.file_name().is_some_and(|name| name == "node_modules") {
+            return Ok(None);
+        }
+        if let Some(package) = read_package_json(dir, context)? {
+            return Ok(Some((dir.to_path_buf(), package)));
+        }
+    }
+    Ok(None)
+}
+
+/// `PACKAGE_EXPORTS_RESOLVE(packageURL, subpath, exports, conditions)`.
+fn package_exports_resolve(
+    package: &Path,
+    subpath: &str,
+    exports: &JsValue,
+    conditions: &[String],
+    context: &mut Context,
+) -> JsResult<PathBuf> {
+    let object = exports.as_object().filter(|exports| !exports.is_array());
+    let keys = match object {
+        Some(object) => string_keys(object, context)?,
+        None => Vec::new(),
+    };
+    let subpath_keys = keys.iter().filter(|key| key.starts_with('.')).count();
+    if subpath_keys != 0 && subpath_keys != keys.len() {
+        return Err(JsNativeError::typ()
+            .with_message(format!(
+                "invalid package configuration in '{}': the \"exports\" cannot mix subpaths and conditions",
+                package.display()
+            ))
+            .into());
+    }
+
+    let resolved = match object {
+        Some(object) if subpath_keys != 0 => {
+            if subpath == "." {
+                let main = object.get(js_string!("."), context)?;
+                package_target_resolve(package, &main, None, false, conditions, context)?
+            } else {
+                package_imports_exports_resolve(
+                    subpath, object, package, false, conditions, context,
+                )?
+            }
+        }
+        _ if subpath == "." => {
+            package_target_resolve(package, exports, None, false, conditions, context)?
+        }
+        _ => None,
+    };
+
+    resolved.ok_or_else(|| {
+        JsNativeError::typ()
+            .with_message(format!(
+                "package subpath '{subpath}' is not exported by '{}'",
+                package.display()
+            ))
+            .into()
+    })
+}
+
+/// `PACKAGE_IMPORTS_EXPORTS_RESOLVE(matchKey, matchObj, packageURL, isImports, conditions)`:
+/// resolves `key` through the subpaths or the patterns of `map`.
+fn package_imports_exports_resolve(
+    key: &str,
+    map: &JsObject,
+    package: &Path,
+    is_imports: bool,
+    conditions: &[String],
+    context: &mut Context,
+) -> JsResult<Option<PathBuf>> {
+    let keys = string_keys(map, context)?;
+    if !key.contains('*') && keys.iter().any(|candidate| candidate == key) {
+        let target = map.get(js_string!(key), context)?;
+        return package_target_resolve(package, &target, None, is_imports, conditions, context);
+    }
+
+    let mut patterns: Vec<(&str, &str, &String)> = keys
+        .iter()
+        .filter(|candidate| candidate.matches('*').count() == 1)
+        .filter_map(|candidate| {
+            let (base, trailer) = candidate.split_once('*')?;
+            Some((base, trailer, candidate))
+        })
+        .collect();
+    // `PATTERN_KEY_COMPARE`: the patterns with the longest base, then the longest ones, first.
+    patterns.sort_by(|(a_base, _, a), (b_base, _, b)| {
+        b_base.len().cmp(&a_base.len()).then(b.len().cmp(&a.len()))
+    });
+
+    for (base, trailer, pattern) in patterns {
+        if key.starts_with(base)
+            && key != base
+            && (trailer.is_empty() || (key.ends_with(trailer) && key.len() >= pattern.len()))
+        {
+            let target = map.get(js_string!(pattern.as_str()), context)?;
+            let pattern_match = &key[base.len()..key.len() - trailer.len()];
+            return package_target_resolve(
+                package,
+                &target,
+                Some(pattern_match),
+                is_imports,
+                conditions,
+                context,
+            );
+        }
+    }
+    Ok(None)
+}
+
+/// `PACKAGE_TARGET_RESOLVE(packageURL, target, patternMatch, isImports, conditions)`: resolves
+/// a target of the `exports` or `imports` of a package, returning `None` if no target matches
+/// the conditions.
+fn package_target_resolve(
+    package: &Path,
+    target: &JsValue,
+    pattern_match: Option<&str>,
+    is_imports: bool,
+    conditions: &[String],
+    context: &mut Context,
+) -> JsResult<Option<PathBuf>> {
+    if let Some(target) = target.as_string() {
+        let target = target.to_std_string_escaped();
+        let substituted = pattern_match.map_or_else(
+            || target.clone(),
+            |pattern_match| target.replace('*', pattern_match),
+        );
+        if !target.starts_with("./") {
+            if is_imports
+                && !target.starts_with("../")
+                && !target.starts_with('/')
+                && Url::parse(&target).is_err()
+            {
+                return package_resolve(&substituted, package, conditions, context).map(Some);
+            }
+            return Err(invalid_target(&target, package));
+        }
+        if has_invalid_segment(&target[2..]) || pattern_match.is_some_and(has_invalid_segment) {
+            return Err(invalid_target(&target, package));
+        }
+        return Ok(Some(package.join(&substituted[2..])));
+    }
+
+    if let Some(object) = target.as_object() {
+        if object.is_array() {
+            let fallbacks = JsArray::from_object(object.clone())?;
+            let mut last_error = None;
+            for index in 0..fallbacks.length(context)? {
+                let fallback = fallbacks.get(index, context)?;
+                match package_target_resolve(
+                    package,
+                    &fallback,
+                    pattern_match,
+                    is_imports,
+                    conditions,
+                    context,
+                ) {
+                    Ok(Some(path)) => return Ok(Some(path)),
+                    Ok(None) => last_error = None,
+                    Err(e) => last_error = Some(e),
+                }
+            }
+            return last_error.map_or(Ok(None), Err);
+        }
+
+        for condition in string_keys(object, context)? {
+            if condition == "default" || conditions.contains(&condition) {
+                let value = object.get(js_string!(condition.as_str()), context)?;
+                let resolved = package_target_resolve(
+                    package,
+                    &value,
+                    pattern_match,
+                    is_imports,
+                    conditions,
+                    context,
+                )?;
+                if resolved.is_some() {
+                    return Ok(resolved);
+                }
+            }
+        }
+        return Ok(None);
+    }
+
+    if target.is_null() {
+        return Ok(None);
+    }
+    Err(invalid_target(&target.display().to_string(), package))
+}
+
+/// `LEGACY_MAIN_RESOLVE(packageURL, pjson)`: resolves the `main` file of a package without
+/// `exports`.
+fn legacy_main_resolve(
+    package_dir: &Path,
+    package: Option<&JsObject>,
+    context: &mut Context,
+) -> JsResult<PathBuf> {
+    if let Some(package) = package {
+        let main = package.get(js_string!("main"), context)?;
+        if let Some(main) = main.as_string().filter(|main| !main.is_empty()) {
+            let main = package_dir.join(main.to_std_string_escaped());
+            let mut candidates = vec![main.clone()];
+            for extension in ["js", "json"] {
+                let mut name = main.clone().into_os_string();
+                name.push(".");
+                name.push(extension);
+                candidates.push(PathBuf::from(name));
+            }
+            candidates.push(main.join("index.js"));
+            candidates.push(main.join("index.json"));
+            if let Some(file) = candidates.into_iter().find(|file| file.is_file()) {
+                return Ok(file);
+            }
+        }
+    }
+    ["index.js", "index.json"]
+        .into_iter()
+        .map(|index| package_dir.join(index))
+        .find(|file| file.is_file())
+        .ok_or_else(|| {
+            JsNativeError::typ()
+                .with_message(format!(
+                    "cannot find the main module of the package '{}'",
+                    package_dir.display()
+                ))
+                .into()
+        })
+}
+
+/// Returns the own string keys of `object`, in property order.
+fn string_keys(object: &JsObject, context: &mut Context) -> JsResult<Vec<String>> {
+    Ok(object
+        .own_property_keys(context)?
+        .into_iter()
+        .filter_map(|key| match key {
+            PropertyKey::String(key) => Some(key.to_std_string_escaped()),
+            PropertyKey::Index(index) => Some(index.get().to_string()),
+            PropertyKey::Symbol(_) => None,
+        })
+        .collect())
+}
+
+/// Returns `true` if `path` has a `.`, `..` or `node_modules` segment, which aren't allowed in
+/// package targets.
+fn has_invalid_segment(path: &str) -> bool {
+    path.split(['/', '\\']).any(|segment| {
+        segment == "." || segment == ".." || segment.eq_ignore_ascii_case("node_modules")
+    })
+}
+
+/// Creates the error of an invalid target of the `exports` or `imports` of a package.
+fn invalid_target(target: &str, package: &Path) -> JsError {
+    JsNativeError::typ()
+        .with_message(format!(
+            "invalid package target '{target}' in '{}'",
+            package.join("package.json").display()
+        ))
+        .into()
+}
@@ -0,0 +1,388 @@
+#[cfg(all(feature = "zip", feature = "tar"))]
+use super::ArchiveSource;
+use super::{ImportMap, NodeModuleLoader};
+use crate::{
+    test::{root, run_test_actions_with, TempDir, TestAction},
+    CommonJsModuleLoader, Permissions,
+};
+use boa_engine::{context::ContextBuilder, js_string, module::SimpleModuleLoader, Context};
+use indoc::indoc;
+use std::{path::Path, rc::Rc};
+use url::Url;
+
+/// Creates a `NodeModuleLoader` loading the modules of `root`, including CommonJS modules.
+fn node_loader(root: &Path) -> NodeModuleLoader<CommonJsModuleLoader<SimpleModuleLoader>> {
+    NodeModuleLoader::new(
+        CommonJsModuleLoader::new(SimpleModuleLoader::new(root).unwrap(), root).unwrap(),
+        root,
+    )
+    .unwrap()
+}
+
+/// Creates an action dynamically importing the module at `specifier`, setting the global
+/// `imported` to `true` once it is evaluated, or to the message of its error.
+fn import(specifier: &str) -> TestAction {
+    TestAction::run(format!(
+        "var imported; import({specifier:?}).then(
+            () => {{ imported = true; }},
+            (e) => {{ imported = e.message; }},
+        );"
+    ))
+}
+
+#[test]
+fn packages() {
+    let dir = TempDir::new("modules-packages");
+    dir.write(
+        "node_modules/lodash-es/package.json",
+        r#"{ "type": "module", "exports": { ".": "./lodash.js", "./*": "./*.js" } }"#,
+    )
+    .write(
+        "node_modules/lodash-es/lodash.js",
+        "export { add } from './add.js'; export default 'lodash';",
+    )
+    .write(
+        "node_modules/lodash-es/add.js",
+        "export const add = (a, b) => a + b;",
+    )
+    .write(
+        "node_modules/cond/package.json",
+        indoc! {r#"
+            {
+                "exports": {
+                    ".": { "node": "./node.js", "import": "./import.js", "default": "./default.js" },
+                    "./feature": { "require": "./feature.cjs", "default": "./feature.mjs" },
+                    "./lib/*.js": "./src/*.mjs",
+                    "./internal/*": null
+                }
+            }
+        "#},
+    )
+    .write("node_modules/cond/import.js", "exports.kind = 'import';")
+    .write("node_modules/cond/feature.mjs", "export const feature = 'feature';")
+    .write("node_modules/cond/src/thing.mjs", "export const lib = 'lib';")
+    .write(
+        "node_modules/@scope/legacy/package.json",
+        r#"{ "type": "module", "main": "./lib/main" }"#,
+    )
+    .write(
+        "node_modules/@scope/legacy/lib/main.js",
+        "export const legacy = 'legacy';",
+    )
+    .write(
+        "app/package.json",
+        indoc! {r##"
+            {
+                "name": "app",
+                "type": "module",
+                "imports": { "#utils/*": "./src/utils/*.js", "#dep": "lodash-es" },
+                "exports": { "./self": "./src/self.js" }
+            }
+        "##},
+    )
+    .write("app/src/utils/greet.js", "export const greet = 'hi';")
+    .write("app/src/self.js", "export const self = 'self';")
+    .write(
+        "app/main.js",
+        indoc! {r##"
+            import lodash, { add } from "lodash-es";
+            import { add as add2 } from "lodash-es/add";
+            import { kind } from "cond";
+            import { feature } from "cond/feature";
+            import { lib } from "cond/lib/thing.js";
+            import { legacy } from "@scope/legacy";
+            import { greet } from "#utils/greet";
+            import { add as add3 } from "#dep";
+            import { self } from "app/self";
+            globalThis.result = [
+                lodash, add(1, 2), add2(2, 3), kind, feature, lib, legacy, greet, add3(3, 4), self,
+            ];
+        "##},
+    );
+    let context = &mut ContextBuilder::new()
+        .module_loader(Rc::new(node_loader(dir.path())))
+        .build()
+        .unwrap();
+
+    run_test_actions_with(
+        [
+            import("./app/main.js"),
+            TestAction::inspect_context(Context::run_jobs),
+            TestAction::assert_eq("imported", true),
+            TestAction::assert_eq(
+                "result.join()",
+                js_string!("lodash,3,5,import,feature,lib,legacy,hi,7,self"),
+            ),
+            TestAction::run(indoc! {r#"
+                const errors = {};
+                import("cond/internal/secret.js").catch((e) => { errors.internal = e.message; });
+                import("cond/missing").catch((e) => { errors.subpath = e.message; });
+                import("missing-package").catch((e) => { errors.package = e.message; });
+            "#}),
+            TestAction::inspect_context(Context::run_jobs),
+            TestAction::assert_eq(
+                "errors.internal.startsWith(\"package subpath './internal/secret.js' is not exported\")",
+                true,
+            ),
+            TestAction::assert_eq(
+                "errors.subpath.startsWith(\"package subpath './missing' is not exported\")",
+                true,
+            ),
+            TestAction::assert_eq(
+                "errors.package.startsWith(\"cannot find package 'missing-package'\")",
+                true,
+            ),
+        ],
+        context,
+    );
+}
+
+#[test]
+fn import_maps() {
+    let dir = TempDir::new("modules-import-maps");
+    dir.write("vendor/a.js", "export default 'a';")
+        .write("vendor/old-a.js", "export default 'old';")
+        .write("vendor/uses-a.js", "import a from 'a'; export default a;")
+        .write("src/lib/math.js", "export const two = 2;")
+        .write(
+            "main.js",
+            indoc! {r#"
+                import a from "a";
+                import viaVendor from "./vendor/uses-a.js";
+                import { two } from "lib/math.js";
+                globalThis.result = [a, viaVendor, two];
+            "#},
+        );
+    let base = Url::from_file_path(dir.path().join("import-map.json")).unwrap();
+    let import_map = ImportMap::parse(
+        indoc! {r#"
+            {
+                "imports": { "a": "./vendor/a.js", "lib/": "./src/lib/", "blocked": 42 },
+                "scopes": { "./vendor/": { "a": "./vendor/old-a.js" } }
+            }
+        "#},
+        &base,
+    )
+    .unwrap();
+    let context = &mut ContextBuilder::new()
+        .module_loader(Rc::new(node_loader(dir.path()).with_import_map(import_map)))
+        .build()
+        .unwrap();
+
+    run_test_actions_with(
+        [
+            import("./main.js"),
+            TestAction::inspect_context(Context::run_jobs),
+            TestAction::assert_eq("imported", true),
+            TestAction::assert_eq("result.join()", js_string!("a,old,2")),
+            TestAction::run(indoc! {r#"
+                const errors = {};
+                import("blocked").catch((e) => { errors.blocked = e.message; });
+                import("lib/../secret.js").catch((e) => { errors.backtracking = e.message; });
+            "#}),
+            TestAction::inspect_context(Context::run_jobs),
+            TestAction::assert_eq(
+                "errors.blocked",
+                js_string!("the import map blocks the resolution of \"blocked\""),
+            ),
+            TestAction::assert_eq("errors.backtracking.includes('backtracks')", true),
+        ],
+        context,
+    );
+}
+
+#[test]
+fn permissions() {
+    let dir = TempDir::new("modules-permissions");
+    dir.write("app/lib.js", "export default 'lib';")
+        .write(
+            "app/imports-secret.js",
+            "import secret from '../secret.js';",
+        )
+        .write("secret.js", "export default 'secret';")
+        .write(
+            "app/main.js",
+            indoc! {r#"
+                import lib from "./lib.js";
+                globalThis.result = lib;
+            "#},
+        );
+    let context = &mut ContextBuilder::new()
+        .module_loader(Rc::new(node_loader(dir.path())))
+        .build()
+        .unwrap();
+    Permissions::none()
+        .allow_read(dir.path().join("app"))
+        .register(context);
+
+    run_test_actions_with(
+        [
+            import("./app/main.js"),
+            TestAction::inspect_context(Context::run_jobs),
+            TestAction::assert_eq("imported", true),
+            TestAction::assert_eq("result", js_string!("lib")),
+            TestAction::run(indoc! {r#"
+                const errors = {};
+                import("./secret.js").catch((e) => { errors.dynamic = e.message; });
+                import("./app/imports-secret.js").catch((e) => { errors.static = e.message; });
+            "#}),
+            TestAction::inspect_context(Context::run_jobs),
+            TestAction::assert_eq(
+                "errors.dynamic.startsWith('PermissionDenied: requires read access')",
+                true,
+            ),
+            TestAction::assert_eq(
+                "errors.static.startsWith('PermissionDenied: requires read access')",
+                true,
+            ),
+        ],
+        context,
+    );
+}
+
+#[test]
+fn preload() {
+    let dir = TempDir::new("modules-preload");
+    dir.write(
+        "app/main.js",
+        "import { value } from 'value'; import '../secret.js'; globalThis.result = value;",
+    )
+    .write("app/lib/value.js", "export const value = 42;")
+    .write("secret.js", "export default 'secret';");
+    let app = dir.path().join("app");
+    let base = Url::from_directory_path(&app).unwrap();
+    let import_map =
+        ImportMap::parse(r#"{ "imports": { "value": "./lib/value.js" } }"#, &base).unwrap();
+    let loader = Rc::new(node_loader(&app).with_import_map(import_map));
+    let context = &mut ContextBuilder::new()
+        .module_loader(loader.clone())
+        .build()
+        .unwrap();
+    Permissions::none().allow_read(&app).register(context);
+
+    let main = loader.preload(Path::new("main.js"), context).unwrap();
+    let error = loader
+        .preload(&dir.path().join("secret.js"), context)
+        .expect_err("the preload of `secret.js` must be denied");
+    let evaluation = main.load_link_evaluate(context);
+    let global = context.global_object();
+    global
+        .set(js_string!("evaluation"), evaluation, false, context)
+        .unwrap();
+    global
+        .set(
+            js_string!("preloadError"),
+            error.to_opaque(context),
+            false,
+            context,
+        )
+        .unwrap();
+
+    run_test_actions_with(
+        [
+            dir.define_root(),
+            TestAction::assert_context(|context| {
+                let value = root(context).join("app/lib/value.js");
+                let value = js_string!(value.to_string_lossy().as_ref());
+                context.module_loader().get_module(value).is_some()
+            }),
+            TestAction::assert_context(|context| {
+                let secret = root(context).join("secret.js");
+                let secret = js_string!(secret.to_string_lossy().as_ref());
+                context.module_loader().get_module(secret).is_none()
+            }),
+            TestAction::assert("preloadError.message.startsWith('PermissionDenied')"),
+            TestAction::run(indoc! {r#"
+                let error;
+                evaluation.catch((e) => { error = e.message; });
+            "#}),
+            TestAction::inspect_context(Context::run_jobs),
+            TestAction::assert("error.startsWith('PermissionDenied: requires read access')"),
+        ],
+        context,
+    );
+}
+
+#[test]
+fn invalid_import_maps() {
+    let base = Url::parse("file:///app/import-map.json").unwrap();
+    assert!(ImportMap::parse("not json", &base).is_err());
+    assert!(ImportMap::parse("[]", &base).is_err());
+    assert!(ImportMap::parse(r#"{ "imports": [] }"#, &base).is_err());
+    assert!(ImportMap::parse(r#"{ "scopes": { "/": [] } }"#, &base).is_err());
+    assert_eq!(ImportMap::parse("{}", &base).unwrap(), ImportMap::default());
+}
+
+/// The files of the bundle of the `archives` test.
+#[cfg(all(feature = "zip", feature = "tar"))]
+const BUNDLE: [(&str, &str); 3] = [
+    (
+        "app/main.js",
+        "import { value } from './lib/value.js'; globalThis.result = value;",
+    ),
+    (
+        "app/lib/value.js",
+        "import { base } from '../../shared/base.js'; export const value = base + 1;",
+    ),
+    ("shared/base.js", "export const base = 41;"),
+];
+
+/// Asserts that the `/app/main.js` module of an archive evaluates its `result` to 42.
+#[cfg(all(feature = "zip", feature = "tar"))]
+fn assert_bundle(source: ArchiveSource) {
+    let context = &mut ContextBuilder::new()
+        .module_loader(Rc::new(SimpleModuleLoader::with_source(source, "/app")))
+        .build()
+        .unwrap();
+    run_test_actions_with(
+        [
+            import("./main.js"),
+            TestAction::inspect_context(Context::run_jobs),
+            TestAction::assert_eq("imported", true),
+            TestAction::assert_eq("result", 42),
+        ],
+        context,
+    );
+}
+
+#[test]
+#[cfg(all(feature = "zip", feature = "tar"))]
+fn archives() {
+    use std::io::{Cursor, Write};
+
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    zip.add_directory("app/", zip::write::FileOptions::default())
+        .unwrap();
+    for (path, contents) in BUNDLE {
+        zip.start_file(path, zip::write::FileOptions::default())
+            .unwrap();
+        zip.write_all(contents.as_bytes()).unwrap();
+    }
+    let zip = zip.finish().unwrap().into_inner();
+    let source = ArchiveSource::from_zip(Cursor::new(zip.as_slice())).unwrap();
+    assert_eq!(source.files().len(), 3);
+    assert_bundle(source);
+
+    let mut tar = tar::Builder::new(Vec::new());
+    for (path, contents) in BUNDLE {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        tar.append_data(&mut header, format!("./{path}"), contents.as_bytes())
+            .unwrap();
+    }
+    let tar = tar.into_inner().unwrap();
+    let source = ArchiveSource::from_tar(tar.as_slice()).unwrap();
+    assert_eq!(
+        source.files().get(Path::new("/shared/base.js")),
+        Some(BUNDLE[2].1.as_bytes())
+    );
+    assert_bundle(source);
+
+    let dir = TempDir::new("modules-archives");
+    std::fs::write(dir.path().join("bundle.zip"), &zip).unwrap();
+    std::fs::write(dir.path().join("bundle.tar"), &tar).unwrap();
+    assert_bundle(ArchiveSource::open(dir.path().join("bundle.zip")).unwrap());
+    assert_bundle(ArchiveSource::open(dir.path().join("bundle.tar")).unwrap());
+    assert!(ArchiveSource::open(dir.path().join("bundle.rar")).is_err());
+}
@@ -0,0 +1,347 @@
+//! Boa's capability-based permission system, which restricts the I/O that the runtime APIs can
+//! perform on behalf of the scripts of a context.
+//!
+//! Each context can register a [`Permissions`] set, checked by the runtime APIs before doing any
+//! I/O: `require` and the workers read files, and `fetch` connects to hosts. Requests that aren't
+//! granted fail with a `PermissionDenied` error.
+//!
+//! A context without a registered permission set has every permission, which keeps embedders
+//! that only run trusted scripts working unchanged. Contexts running untrusted scripts should
+//! register a set built from [`Permissions::none`], granting only what the scripts need.
+//!
+//! The model follows the permissions of Deno.
+//!
+//! More information:
+//!  - [Deno documentation][deno]
+//!
+//! [deno]: https://docs.deno.com/runtime/fundamentals/security/
+
+#[cfg(test)]
+mod tests;
+
+use boa_engine::{Context, JsData, JsNativeError, JsResult};
+use boa_gc::{Finalize, Trace};
+use std::{
+    fmt::Display,
+    path::{Component, Path, PathBuf},
+};
+use url::Url;
+
+/// The values granted for one kind of permission.
+#[derive(Debug, Clone, PartialEq, Eq, Trace, Finalize)]
+enum Grant<T: Trace + 'static> {
+    /// Every value is granted.
+    All,
+    /// Only the listed values are granted.
+    Only(Vec<T>),
+}
+
+impl<T: Trace + 'static> Grant<T> {
+    /// Grants `value`, unless every value is already granted.
+    fn add(&mut self, value: T) {
+        if let Self::Only(values) = self {
+            values.push(value);
+        }
+    }
+
+    /// Returns `true` if `granted` returns `true` for any of the granted values.
+    fn allows(&self, granted: impl Fn(&T) -> bool) -> bool {
+        match self {
+            Self::All => true,
+            Self::Only(values) => values.iter().any(granted),
+        }
+    }
+}
+
+/// A host that can be connected to, on any port or only on `port`.
+#[derive(Debug, Clone, PartialEq, Eq, Trace, Finalize)]
+struct NetGrant {
+    host: String,
+    port: Option<u16>,
+}
+
+impl NetGrant {
+    /// Parses a `host` or `host:port` grant, where IPv6 hosts are enclosed in brackets.
+    fn parse(grant: &str) -> Self {
+        let grant = grant.to_ascii_lowercase();
+        if let Some((host, port)) = grant.rsplit_once(':') {
+            let bracketed = host.starts_with('[') && host.ends_with(']');
+            if let (true, Ok(port)) = (bracketed || !host.contains(':'), port.parse()) {
+                return Self {
+                    host: host.to_owned(),
+                    port: Some(port),
+                };
+            }
+        }
+        Self {
+            host: grant,
+            port: None,
+        }
+    }
+}
+
+/// The set of permissions of a context.
+///
+/// The read and write permissions are granted for paths, which also grant every path inside
+/// them. The network permissions are granted for hosts, optionally restricted to one port with
+/// `host:port`. The environment permissions are granted for variable names.
+///
+/// ```
+/// use boa_engine::Context;
+/// use boa_runtime::Permissions;
+///
+/// let context = &mut Context::default();
+/// Permissions::none()
+///     .allow_read("scripts")
+///     .allow_net("example.com")
+///     .allow_net("localhost:8080")
+///     .register(context);
+///
+/// assert!(Permissions::check_net(&"https://example.com/".parse().unwrap(), context).is_ok());
+/// assert!(Permissions::check_net(&"http://localhost:3000/".parse().unwrap(), context).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Trace, Finalize, JsData)]
+pub struct Permissions {
+    read: Grant<PathBuf>,
+    write: Grant<PathBuf>,
+    net: Grant<NetGrant>,
+    env: Grant<String>,
+}
+
+impl Default for Permissions {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl Permissions {
+    /// Creates a permission set that grants nothing.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self {
+            read: Grant::Only(Vec::new()),
+            write: Grant::Only(Vec::new()),
+            net: Grant::Only(Vec::new()),
+            env: Grant::Only(Vec::new()),
+        }
+    }
+
+    /// Creates a permission set that grants everything.
+    #[must_use]
+    pub const fn all() -> Self {
+        Self {
+            read: Grant::All,
+            write: Grant::All,
+            net: Grant::All,
+            env: Grant::All,
+        }
+    }
+
+    /// Grants reading the files inside `path`.
+    #[must_use]
+    pub fn allow_read<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.read.add(normalize(path.as_ref()));
+        self
+    }
+
+    /// Grants reading any file.
+    #[must_use]
+    pub fn allow_all_read(mut self) -> Self {
+        self.read = Grant::All;
+        self
+    }
+
+    /// Grants writing the files inside `path`.
+    #[must_use]
+    pub fn allow_write<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.write.add(normalize(path.as_ref()));
+        self
+    }
+
+    /// Grants writing any file.
+    #[must_use]
+    pub fn allow_all_write(mut self) -> Self {
+        self.write = Grant::All;
+        self
+    }
+
+    /// Grants connecting to `host`, or only to one of its ports if `host` is `host:port`.
+    #[must_use]
+    pub fn allow_net(mut self, host: &str) -> Self {
+        self.net.add(NetGrant::parse(host));
+        self
+    }
+
+    /// Grants connecting to any host.
+    #[must_use]
+    pub fn allow_all_net(mut self) -> Self {
+        self.net = Grant::All;
+        self
+    }
+
+    /// Grants accessing the environment variable `name`.
+    #[must_use]
+    pub fn allow_env(mut self, name: &str) -> Self {
+        self.env.add(name.to_owned());
+        self
+    }
+
+    /// Grants accessing any environment variable.
+    #[must_use]
+    pub fn allow_all_env(mut self) -> Self {
+        self.env = Grant::All;
+        self
+    }
+
+    /// Sets the permission set of the context, replacing its previous set.
+    pub fn register(self, context: &mut Context) {
+        context.realm().host_defined_mut().insert(self);
+    }
+
+    /// Gets a copy of the permission set of the context, or `None` if the context has every
+    /// permission.
+    #[must_use]
+    pub fn of(context: &Context) -> Option<Self> {
+        context.realm().host_defined().get::<Self>().cloned()
+    }
+
+    /// Returns `true` if reading the file at `path` is granted.
+    #[must_use]
+    pub fn can_read(&self, path: &Path) -> bool {
+        let path = normalize(path);
+        self.read.allows(|granted| path.starts_with(granted))
+    }
+
+    /// Returns `true` if writing the file at `path` is granted.
+    #[must_use]
+    pub fn can_write(&self, path: &Path) -> bool {
+        let path = normalize(path);
+        self.write.allows(|granted| path.starts_with(granted))
+    }
+
+    /// Returns `true` if connecting to `port` of `host` is granted.
+    #[must_use]
+    pub fn can_connect(&self, host: &str, port: Option<u16>) -> bool {
+        let host = host.to_ascii_lowercase();
+        self.net.allows(|granted| {
+            granted.host == host && (granted.port.is_none() || granted.port == port)
+        })
+    }
+
+    /// Returns `true` if accessing the environment variable `name` is granted.
+    #[must_use]
+    pub fn can_access_env(&self, name: &str) -> bool {
+        self.env.allows(|granted| granted == name)
+    }
+
+    /// Checks that the context can read the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PermissionDenied` error if reading `path` isn't granted.
+    pub fn check_read(path: &Path, context: &Context) -> JsResult<()> {
+        Self::check(
+            context,
+            |permissions| permissions.can_read(path),
+            || format!("read access to `{}`", path.display()),
+        )
+    }
+
+    /// Checks that the context can write the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PermissionDenied` error if writing `path` isn't granted.
+    pub fn check_write(path: &Path, context: &Context) -> JsResult<()> {
+        Self::check(
+            context,
+            |permissions| permissions.can_write(path),
+            || format!("write access to `{}`", path.display()),
+        )
+    }
+
+    /// Checks that the context can access `url`: connecting to its host for network URLs, or
+    /// reading its file for `file:` URLs. Other URLs, e.g. `data:` URLs, don't need any
+    /// permission.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PermissionDenied` error if accessing `url` isn't granted.
+    pub fn check_net(url: &Url, context: &Context) -> JsResult<()> {
+        if url.scheme() == "file" {
+            return match url.to_file_path() {
+                Ok(path) => Self::check_read(&path, context),
+                Err(()) => Err(permission_denied(format!("read access to `{url}`")).into()),
+            };
+        }
+        let Some(host) = url.host_str() else {
+            return Ok(());
+        };
+        let port = url.port_or_known_default();
+        Self::check(
+            context,
+            |permissions| permissions.can_connect(host, port),
+            || match port {
+                Some(port) => format!("net access to `{host}:{port}`"),
+                None => format!("net access to `{host}`"),
+            },
+        )
+    }
+
+    /// Checks that the context can access the environment variable `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PermissionDenied` error if accessing `name` isn't granted.
+    pub fn check_env(name: &str, context: &Context) -> JsResult<()> {
+        Self::check(
+            context,
+            |permissions| permissions.can_access_env(name),
+            || format!("env access to `{name}`"),
+        )
+    }
+
+    /// Checks a permission of the context with `granted`, describing the access with
+    /// `access` if it isn't granted.
+    fn check<D: Display>(
+        context: &Context,
+        granted: impl FnOnce(&Self) -> bool,
+        access: impl FnOnce() -> D,
+    ) -> JsResult<()> {
+        match context.realm().host_defined().get::<Self>() {
+            Some(permissions) if !granted(permissions) => Err(permission_denied(access()).into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Creates a `PermissionDenied` error for a required `access`.
+fn permission_denied(access: impl Display) -> JsNativeError {
+    JsNativeError::error().with_message(format!("PermissionDenied: requires {access}"))
+}
+
+/// Makes `path` absolute and resolves its `.` and `..` components, along with the symbolic
+/// links of its existing ancestors, so that paths can be compared by their components.
+///
+/// The symbolic links are resolved before the `..` components following them, which go to the
+/// parent of the target of the link, like the file system does. The components that don't
+/// exist are resolved lexically.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    let absolute = std::env::current_dir().unwrap_or_default().join(path);
+    for component in absolute.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            component => {
+                normalized.push(component);
+                if let Ok(canonical) = normalized.canonicalize() {
+                    normalized = canonical;
+                }
+            }
+        }
+    }
+    normalized
+}
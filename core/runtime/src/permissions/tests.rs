@@ -0,0 +1,109 @@
+use super::Permissions;
+use crate::test::{run_test_actions_with, TempDir, TestAction};
+use boa_engine::{js_string, Context};
+use std::path::Path;
+
+#[test]
+fn paths() {
+    let permissions = Permissions::none()
+        .allow_read("scripts")
+        .allow_write("/tmp/boa/out");
+
+    assert!(permissions.can_read(Path::new("scripts")));
+    assert!(permissions.can_read(Path::new("scripts/lib/main.js")));
+    assert!(permissions.can_read(Path::new("./other/../scripts/main.js")));
+    assert!(!permissions.can_read(Path::new("scripts/../secrets.txt")));
+    assert!(!permissions.can_read(Path::new("scripts-old/main.js")));
+    assert!(!permissions.can_write(Path::new("scripts/main.js")));
+    assert!(permissions.can_write(Path::new("/tmp/boa/out/result.txt")));
+    assert!(!permissions.can_write(Path::new("/tmp/boa/output.txt")));
+
+    let all = Permissions::none().allow_all_read();
+    assert!(all.can_read(Path::new("/etc/hosts")));
+    assert!(!all.can_write(Path::new("/etc/hosts")));
+}
+
+#[cfg(unix)]
+#[test]
+fn symlinks() {
+    let dir = TempDir::new("permissions-symlinks");
+    dir.write("allowed/main.js", "")
+        .write("allowed/secret.txt", "")
+        .write("private/nested/data.txt", "")
+        .write("private/secret.txt", "");
+    std::os::unix::fs::symlink(
+        dir.path().join("private/nested"),
+        dir.path().join("allowed/link"),
+    )
+    .unwrap();
+    let permissions = Permissions::none().allow_read(dir.path().join("allowed"));
+
+    assert!(permissions.can_read(&dir.path().join("allowed/main.js")));
+    assert!(!permissions.can_read(&dir.path().join("allowed/link/data.txt")));
+    // `..` goes to the parent of the target of the link, not back to `allowed`.
+    assert!(!permissions.can_read(&dir.path().join("allowed/link/../secret.txt")));
+    assert!(!permissions.can_read(&dir.path().join("allowed/link/../missing/../secret.txt")));
+    assert!(permissions.can_read(&dir.path().join("allowed/missing/../secret.txt")));
+}
+
+#[test]
+fn hosts() {
+    let permissions = Permissions::none()
+        .allow_net("Example.com")
+        .allow_net("localhost:8080")
+        .allow_net("[::1]:3000");
+
+    assert!(permissions.can_connect("example.com", Some(443)));
+    assert!(permissions.can_connect("EXAMPLE.COM", Some(80)));
+    assert!(!permissions.can_connect("api.example.com", Some(443)));
+    assert!(permissions.can_connect("localhost", Some(8080)));
+    assert!(!permissions.can_connect("localhost", Some(3000)));
+    assert!(permissions.can_connect("[::1]", Some(3000)));
+    assert!(!permissions.can_connect("[::1]", Some(8080)));
+    assert!(Permissions::all().can_connect("anything.test", None));
+}
+
+#[test]
+fn env() {
+    let permissions = Permissions::none().allow_env("HOME");
+    assert!(permissions.can_access_env("HOME"));
+    assert!(!permissions.can_access_env("PATH"));
+    assert!(permissions.allow_all_env().can_access_env("PATH"));
+}
+
+#[test]
+fn checks() {
+    let context = &mut Context::default();
+    let url = "https://example.com/".parse().unwrap();
+
+    // Contexts without a permission set have every permission.
+    assert!(Permissions::of(context).is_none());
+    assert!(Permissions::check_net(&url, context).is_ok());
+    assert!(Permissions::check_env("HOME", context).is_ok());
+
+    Permissions::none()
+        .allow_net("example.com")
+        .register(context);
+    assert!(Permissions::check_net(&url, context).is_ok());
+    assert!(Permissions::check_net(&"data:text/plain,boa".parse().unwrap(), context).is_ok());
+    assert!(Permissions::check_read(Path::new("scripts"), context).is_err());
+    assert!(Permissions::check_write(Path::new("scripts"), context).is_err());
+
+    let error = Permissions::check_env("HOME", context)
+        .unwrap_err()
+        .to_opaque(context);
+    context
+        .global_object()
+        .set(js_string!("error"), error, true, context)
+        .unwrap();
+    run_test_actions_with(
+        [
+            TestAction::assert_eq("error instanceof Error", true),
+            TestAction::assert_eq(
+                "error.message",
+                js_string!("PermissionDenied: requires env access to `HOME`"),
+            ),
+        ],
+        context,
+    );
+}
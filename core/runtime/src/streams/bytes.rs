@@ -0,0 +1,269 @@
+//! Adapters between Rust streams of bytes and `ReadableStream` objects.
+
+use super::{
+    promise::{upon, Algorithm, Deferred},
+    readable::{ReadableStream, ReadableStreamDefaultController, ReadableStreamDefaultReader},
+    strategy::QueuingStrategy,
+};
+use crate::util::buffer_source_bytes;
+use boa_engine::{
+    job::NativeJob,
+    js_string,
+    native_function::NativeFunction,
+    object::{builtins::JsUint8Array, JsObject},
+    Context, JsArgs, JsError, JsNativeError, JsResult, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use futures_core::Stream;
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    future::poll_fn,
+    pin::Pin,
+    rc::{Rc, Weak},
+    task::{Context as TaskContext, Poll, Waker},
+};
+
+/// A boxed Rust stream of bytes.
+type BoxedStream = Pin<Box<dyn Stream<Item = Vec<u8>>>>;
+
+/// The underlying source of a `ReadableStream` created from a Rust stream.
+#[derive(Clone, Trace, Finalize)]
+struct RustSource {
+    /// The Rust stream, which is `None` while a chunk is being polled or once the stream is
+    /// canceled.
+    // SAFETY: Rust streams cannot contain any garbage collected values.
+    #[unsafe_ignore_trace]
+    stream: Rc<RefCell<Option<BoxedStream>>>,
+}
+
+impl ReadableStream {
+    /// Creates a `ReadableStream` object reading its chunks from a Rust stream of bytes.
+    ///
+    /// Each item of `stream` is enqueued as a `Uint8Array`. The stream is only polled when
+    /// JavaScript code reads from the `ReadableStream`, so large payloads are never buffered
+    /// entirely. Canceling the `ReadableStream` drops `stream`.
+    ///
+    /// The items are polled in future jobs, so the [`JobQueue`] of the context must be able to
+    /// run futures.
+    ///
+    /// [`JobQueue`]: boa_engine::job::JobQueue
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream classes are not registered in the context.
+    pub fn from_byte_stream<S>(stream: S, context: &mut Context) -> JsResult<JsObject>
+    where
+        S: Stream<Item = Vec<u8>> + 'static,
+    {
+        let source = RustSource {
+            stream: Rc::new(RefCell::new(Some(Box::pin(stream)))),
+        };
+        let pull = Algorithm::native(
+            NativeFunction::from_copy_closure_with_captures(
+                |_, args, source, context| {
+                    let Some(controller) = args.get_or_undefined(0).as_object().cloned() else {
+                        return Ok(JsValue::undefined());
+                    };
+                    Ok(Self::pull_byte_stream(source, controller, context))
+                },
+                source.clone(),
+            ),
+            context,
+        );
+        let cancel = Algorithm::native(
+            NativeFunction::from_copy_closure_with_captures(
+                |_, _, source, _| {
+                    source.stream.borrow_mut().take();
+                    Ok(JsValue::undefined())
+                },
+                source,
+            ),
+            context,
+        );
+        let strategy = QueuingStrategy {
+            high_water_mark: 0.0,
+            size: None,
+        };
+        Self::create(None, Some(pull), Some(cancel), strategy, context)
+    }
+
+    /// Polls the next chunk of a Rust stream, enqueueing it in a future job.
+    fn pull_byte_stream(
+        source: &RustSource,
+        controller: JsObject,
+        context: &mut Context,
+    ) -> JsValue {
+        let Some(mut stream) = source.stream.borrow_mut().take() else {
+            return JsValue::undefined();
+        };
+        let pulled = Deferred::new(context);
+        let promise = pulled.promise();
+        let shared = source.stream.clone();
+        let future = async move {
+            let chunk = poll_fn(|cx| stream.as_mut().poll_next(cx)).await;
+            NativeJob::new(move |context| {
+                if ReadableStreamDefaultController::can_close_or_enqueue(&controller) {
+                    *shared.borrow_mut() = Some(stream);
+                    match chunk {
+                        Some(chunk) => {
+                            let chunk = JsUint8Array::from_iter(chunk, context)?;
+                            ReadableStreamDefaultController::enqueue(
+                                &controller,
+                                chunk.into(),
+                                context,
+                            )?;
+                        }
+                        None => ReadableStreamDefaultController::close(&controller, context),
+                    }
+                }
+                pulled.resolve(JsValue::undefined(), context);
+                Ok(JsValue::undefined())
+            })
+        };
+        context
+            .job_queue()
+            .enqueue_future_job(Box::pin(future), context);
+        promise.into()
+    }
+
+    /// Locks a `ReadableStream` object, returning a Rust stream of its chunks.
+    ///
+    /// The chunks must be `ArrayBuffer`s, typed arrays or `DataView`s. The `ReadableStream` is
+    /// read while the context runs its jobs, and the chunks are buffered until the returned
+    /// stream is polled. Dropping the returned stream cancels the `ReadableStream`.
+    ///
+    /// An error is yielded if the `ReadableStream` errors or if one of its chunks is not a
+    /// buffer, after which the returned stream ends.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `stream` is not a `ReadableStream` or if it is locked.
+    pub fn into_byte_stream(stream: &JsObject, context: &mut Context) -> JsResult<ByteStream> {
+        if !stream.is::<Self>() {
+            return Err(JsNativeError::typ()
+                .with_message("the object is not a ReadableStream")
+                .into());
+        }
+        let reader = ReadableStreamDefaultReader::acquire(stream, context)?;
+        let state = Rc::new(RefCell::new(ByteStreamState::default()));
+        let sink = RustSink {
+            stream: stream.clone(),
+            reader,
+            state: Rc::downgrade(&state),
+        };
+        RustSink::read(&sink, context);
+        Ok(ByteStream { state })
+    }
+}
+
+/// The state shared between a [`ByteStream`] and the reader of its `ReadableStream`.
+#[derive(Debug, Default)]
+struct ByteStreamState {
+    chunks: VecDeque<JsResult<Vec<u8>>>,
+    done: bool,
+    waker: Option<Waker>,
+}
+
+impl ByteStreamState {
+    /// Adds an item to the stream, waking the task polling it.
+    fn push(&mut self, item: JsResult<Vec<u8>>) {
+        self.chunks.push_back(item);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Ends the stream, waking the task polling it.
+    fn finish(&mut self) {
+        self.done = true;
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Reads the chunks of a `ReadableStream` into a [`ByteStream`].
+#[derive(Clone, Trace, Finalize)]
+struct RustSink {
+    stream: JsObject,
+    reader: JsObject,
+    /// The state of the `ByteStream`, which is dropped with it.
+    // SAFETY: The errors of the state are rooted, so they don't need to be traced.
+    #[unsafe_ignore_trace]
+    state: Weak<RefCell<ByteStreamState>>,
+}
+
+impl RustSink {
+    /// Reads the next chunk of the stream.
+    fn read(sink: &Self, context: &mut Context) {
+        let read = ReadableStreamDefaultReader::read(&sink.reader, context);
+        upon(
+            &read,
+            sink,
+            Some(|result, sink, context| {
+                // The `ByteStream` was dropped, so nobody is interested in the chunks anymore.
+                let Some(state) = sink.state.upgrade() else {
+                    ReadableStreamDefaultReader::release(&sink.reader, context);
+                    ReadableStream::cancel(&sink.stream, JsValue::undefined(), context);
+                    return Ok(JsValue::undefined());
+                };
+                let result = result.as_object().expect("must be an iterator result");
+                if result.get(js_string!("done"), context)?.to_boolean() {
+                    ReadableStreamDefaultReader::release(&sink.reader, context);
+                    state.borrow_mut().finish();
+                    return Ok(JsValue::undefined());
+                }
+                let chunk = result.get(js_string!("value"), context)?;
+                match buffer_source_bytes(&chunk, context) {
+                    Ok(bytes) => {
+                        state.borrow_mut().push(Ok(bytes));
+                        Self::read(sink, context);
+                    }
+                    Err(e) => {
+                        let reason = e.to_opaque(context);
+                        ReadableStreamDefaultReader::release(&sink.reader, context);
+                        ReadableStream::cancel(&sink.stream, reason, context);
+                        let mut state = state.borrow_mut();
+                        state.push(Err(e));
+                        state.finish();
+                    }
+                }
+                Ok(JsValue::undefined())
+            }),
+            Some(|reason, sink, context| {
+                ReadableStreamDefaultReader::release(&sink.reader, context);
+                if let Some(state) = sink.state.upgrade() {
+                    let mut state = state.borrow_mut();
+                    state.push(Err(JsError::from_opaque(reason.clone())));
+                    state.finish();
+                }
+                Ok(JsValue::undefined())
+            }),
+            context,
+        );
+    }
+}
+
+/// A Rust [`Stream`] of the chunks of a `ReadableStream` object, created by
+/// [`ReadableStream::into_byte_stream`].
+#[derive(Debug)]
+pub struct ByteStream {
+    state: Rc<RefCell<ByteStreamState>>,
+}
+
+impl Stream for ByteStream {
+    type Item = JsResult<Vec<u8>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.state.borrow_mut();
+        if let Some(item) = state.chunks.pop_front() {
+            return Poll::Ready(Some(item));
+        }
+        if state.done {
+            return Poll::Ready(None);
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
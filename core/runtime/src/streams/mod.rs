@@ -0,0 +1,88 @@
+//! Boa's implementation of the WHATWG Streams API: the `ReadableStream`, `WritableStream` and
+//! `TransformStream` classes, their default readers, writers and controllers, and the
+//! `CountQueuingStrategy` and `ByteLengthQueuingStrategy` classes.
+//!
+//! Readable byte streams and BYOB readers are not supported.
+//!
+//! [`ReadableStream::from_byte_stream`] and [`ReadableStream::into_byte_stream`] convert between
+//! Rust [`Stream`]s of bytes and `ReadableStream` objects, which makes it possible to process
+//! large payloads incrementally.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `Streams` specification][spec]
+//!
+//! [`Stream`]: futures_core::Stream
+//! [spec]: https://streams.spec.whatwg.org/
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Streams_API
+
+#[cfg(test)]
+mod tests;
+
+mod bytes;
+mod pipe;
+mod promise;
+mod readable;
+mod strategy;
+mod transform;
+mod writable;
+
+#[doc(inline)]
+pub use bytes::ByteStream;
+#[doc(inline)]
+pub use readable::{ReadableStream, ReadableStreamDefaultController, ReadableStreamDefaultReader};
+#[doc(inline)]
+pub use strategy::{ByteLengthQueuingStrategy, CountQueuingStrategy};
+#[doc(inline)]
+pub use transform::{TransformStream, TransformStreamDefaultController};
+#[doc(inline)]
+pub use writable::{WritableStream, WritableStreamDefaultController, WritableStreamDefaultWriter};
+
+use boa_engine::{class::Class, object::JsObject, Context, JsNativeError, JsResult};
+use readable::AsyncIteratorPrototype;
+
+/// The stream classes.
+#[derive(Debug, Clone, Copy)]
+pub struct Streams;
+
+impl Streams {
+    /// Registers the stream classes in the global object of the context.
+    ///
+    /// The `signal` of the controllers of writable streams is only available if the
+    /// [`AbortSignal`] class is registered, e.g. by [`Events::register`].
+    ///
+    /// [`AbortSignal`]: crate::AbortSignal
+    /// [`Events::register`]: crate::Events::register
+    ///
+    /// # Errors
+    ///
+    /// This will return an error if any of the classes cannot be defined in the global object.
+    pub fn register(context: &mut Context) -> JsResult<()> {
+        context.register_global_class::<ReadableStream>()?;
+        context.register_global_class::<ReadableStreamDefaultController>()?;
+        context.register_global_class::<ReadableStreamDefaultReader>()?;
+        context.register_global_class::<WritableStream>()?;
+        context.register_global_class::<WritableStreamDefaultController>()?;
+        context.register_global_class::<WritableStreamDefaultWriter>()?;
+        context.register_global_class::<TransformStream>()?;
+        context.register_global_class::<TransformStreamDefaultController>()?;
+        context.register_global_class::<CountQueuingStrategy>()?;
+        context.register_global_class::<ByteLengthQueuingStrategy>()?;
+
+        let prototype = AsyncIteratorPrototype::create(context);
+        context.realm().host_defined_mut().insert(prototype);
+        Ok(())
+    }
+}
+
+/// Creates an instance of the registered class `C` without calling its constructor, which is
+/// used to create the objects whose constructors cannot be called from JavaScript.
+fn create_instance<C: Class>(data: C, context: &mut Context) -> JsResult<JsObject> {
+    let prototype = context
+        .get_global_class::<C>()
+        .ok_or_else(|| {
+            JsNativeError::typ().with_message(format!("the class {} is not registered", C::NAME))
+        })?
+        .prototype();
+    Ok(JsObject::from_proto_and_data(prototype, data))
+}
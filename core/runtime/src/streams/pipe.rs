@@ -0,0 +1,449 @@
+//! Boa's implementation of the piping of a `ReadableStream` to a `WritableStream`.
+//!
+//! More information:
+//!  - [WHATWG `Streams` specification][spec]
+//!
+//! [spec]: https://streams.spec.whatwg.org/#readable-stream-pipe-to
+
+use super::{
+    promise::{check_dictionary, get_bool, upon, Deferred},
+    readable::{ReadableState, ReadableStream, ReadableStreamDefaultReader},
+    writable::{WritableState, WritableStream, WritableStreamDefaultWriter},
+};
+use crate::AbortSignal;
+use boa_engine::{
+    js_string,
+    native_function::NativeFunction,
+    object::{builtins::JsPromise, ErasedObject, JsObject, RefMut},
+    Context, JsData, JsNativeError, JsResult, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+
+/// The options of `pipeTo` and `pipeThrough`.
+#[derive(Debug, Clone, Default, Trace, Finalize)]
+pub(super) struct PipeOptions {
+    prevent_abort: bool,
+    prevent_cancel: bool,
+    prevent_close: bool,
+    signal: Option<JsObject>,
+}
+
+impl PipeOptions {
+    /// Converts a `StreamPipeOptions` dictionary.
+    pub(super) fn from_value(options: &JsValue, context: &mut Context) -> JsResult<Self> {
+        check_dictionary(options, "pipe options")?;
+        let prevent_abort = get_bool(options, js_string!("preventAbort"), context)?;
+        let prevent_cancel = get_bool(options, js_string!("preventCancel"), context)?;
+        let prevent_close = get_bool(options, js_string!("preventClose"), context)?;
+        let signal = match options.as_object() {
+            Some(options) => options.get(js_string!("signal"), context)?,
+            None => JsValue::undefined(),
+        };
+        let signal = if signal.is_undefined() {
+            None
+        } else {
+            Some(
+                signal
+                    .as_object()
+                    .filter(|signal| signal.is::<AbortSignal>())
+                    .cloned()
+                    .ok_or_else(|| {
+                        JsNativeError::typ().with_message("the signal must be an AbortSignal")
+                    })?,
+            )
+        };
+        Ok(Self {
+            prevent_abort,
+            prevent_cancel,
+            prevent_close,
+            signal,
+        })
+    }
+}
+
+/// The action performed when a pipe shuts down, before releasing the streams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    /// Aborts the destination with the error.
+    AbortDestination,
+    /// Cancels the source with the error.
+    CancelSource,
+    /// Aborts the destination and cancels the source with the error.
+    AbortAndCancel,
+    /// Closes the destination.
+    CloseDestination,
+}
+
+/// The state of a pipe, shared by all of its promise reactions.
+#[derive(Debug, Trace, Finalize, JsData)]
+struct Pipe {
+    source: JsObject,
+    destination: JsObject,
+    reader: JsObject,
+    writer: JsObject,
+    options: PipeOptions,
+    shutting_down: bool,
+    #[unsafe_ignore_trace]
+    action: Option<Action>,
+    /// The error the pipe shuts down with, if any.
+    error: Option<JsValue>,
+    /// The promise settled once the pending read has passed its chunk to the writer.
+    current_read: Option<JsPromise>,
+    /// The promise of the last write, which is waited for before shutting down.
+    current_write: Option<JsPromise>,
+    promise: Deferred,
+}
+
+/// [Pipes][spec] `source` to `destination`, returning a promise settled when the pipe shuts
+/// down.
+///
+/// Both streams must be unlocked.
+///
+/// [spec]: https://streams.spec.whatwg.org/#readable-stream-pipe-to
+pub(super) fn pipe_to(
+    source: &JsObject,
+    destination: &JsObject,
+    options: &PipeOptions,
+    context: &mut Context,
+) -> JsResult<JsPromise> {
+    let reader = ReadableStreamDefaultReader::acquire(source, context)?;
+    let writer = WritableStreamDefaultWriter::acquire(destination, context)?;
+    ReadableStream::disturb(source);
+    let promise = Deferred::new(context);
+    let result = promise.promise();
+    let pipe = JsObject::from_proto_and_data(
+        None,
+        Pipe {
+            source: source.clone(),
+            destination: destination.clone(),
+            reader: reader.clone(),
+            writer: writer.clone(),
+            options: options.clone(),
+            shutting_down: false,
+            action: None,
+            error: None,
+            current_read: None,
+            current_write: None,
+            promise,
+        },
+    );
+
+    if let Some(signal) = &options.signal {
+        if let Some(reason) = AbortSignal::reason(signal) {
+            abort(&pipe, reason, context);
+            return Ok(result);
+        }
+        let on_abort = NativeFunction::from_copy_closure_with_captures(
+            |_, _, (pipe, signal), context| {
+                let reason = AbortSignal::reason(signal).unwrap_or_default();
+                abort(pipe, reason, context);
+                Ok(JsValue::undefined())
+            },
+            (pipe.clone(), signal.clone()),
+        )
+        .to_js_function(context.realm());
+        AbortSignal::add_algorithm(signal, on_abort);
+    }
+
+    // Errors and closings must be propagated forward and backward.
+    if ReadableStream::state(source) == ReadableState::Errored {
+        source_errored(&pipe, ReadableStream::stored_error(source), context);
+    }
+    if WritableStream::state(destination) == WritableState::Errored {
+        destination_errored(&pipe, WritableStream::stored_error(destination), context);
+    }
+    if ReadableStream::state(source) == ReadableState::Closed {
+        source_closed(&pipe, context);
+    }
+    if WritableStream::close_queued_or_in_flight(destination)
+        || WritableStream::state(destination) == WritableState::Closed
+    {
+        destination_closed(&pipe, context);
+    }
+    upon(
+        &ReadableStreamDefaultReader::closed(&reader),
+        &pipe,
+        Some(|_, pipe, context| {
+            source_closed(pipe, context);
+            Ok(JsValue::undefined())
+        }),
+        Some(|reason, pipe, context| {
+            source_errored(pipe, reason.clone(), context);
+            Ok(JsValue::undefined())
+        }),
+        context,
+    );
+    upon(
+        &WritableStreamDefaultWriter::closed(&writer),
+        &pipe,
+        None,
+        Some(|reason, pipe, context| {
+            destination_errored(pipe, reason.clone(), context);
+            Ok(JsValue::undefined())
+        }),
+        context,
+    );
+
+    step(&pipe, context);
+    Ok(result)
+}
+
+/// Borrows the state of a pipe.
+fn data(pipe: &JsObject) -> RefMut<'_, ErasedObject, Pipe> {
+    pipe.downcast_mut::<Pipe>().expect("must be a pipe")
+}
+
+/// Waits for the destination to be ready, then reads a chunk from the source and writes it.
+fn step(pipe: &JsObject, context: &mut Context) {
+    let writer = {
+        let data = data(pipe);
+        if data.shutting_down {
+            return;
+        }
+        data.writer.clone()
+    };
+    upon(
+        &WritableStreamDefaultWriter::ready(&writer),
+        pipe,
+        Some(|_, pipe, context| {
+            let reader = {
+                let data = data(pipe);
+                if data.shutting_down {
+                    return Ok(JsValue::undefined());
+                }
+                data.reader.clone()
+            };
+            let read = upon(
+                &ReadableStreamDefaultReader::read(&reader, context),
+                pipe,
+                Some(|result, pipe, context| {
+                    data(pipe).current_read = None;
+                    let result = result.as_object().expect("must be an iterator result");
+                    if result.get(js_string!("done"), context)?.to_boolean() {
+                        return Ok(JsValue::undefined());
+                    }
+                    let chunk = result.get(js_string!("value"), context)?;
+                    let writer = data(pipe).writer.clone();
+                    let write = WritableStreamDefaultWriter::write(&writer, chunk, context);
+                    // Errors are handled when the destination becomes errored.
+                    let write = upon(
+                        &write,
+                        &(),
+                        None,
+                        Some(|_, (), _| Ok(JsValue::undefined())),
+                        context,
+                    );
+                    data(pipe).current_write = Some(write);
+                    step(pipe, context);
+                    Ok(JsValue::undefined())
+                }),
+                Some(|_, pipe, _| {
+                    data(pipe).current_read = None;
+                    Ok(JsValue::undefined())
+                }),
+                context,
+            );
+            data(pipe).current_read = Some(read);
+            Ok(JsValue::undefined())
+        }),
+        Some(|_, _, _| Ok(JsValue::undefined())),
+        context,
+    );
+}
+
+/// Handles the source becoming errored.
+fn source_errored(pipe: &JsObject, error: JsValue, context: &mut Context) {
+    let action = (!data(pipe).options.prevent_abort).then_some(Action::AbortDestination);
+    shutdown(pipe, action, Some(error), context);
+}
+
+/// Handles the destination becoming errored.
+fn destination_errored(pipe: &JsObject, error: JsValue, context: &mut Context) {
+    let action = (!data(pipe).options.prevent_cancel).then_some(Action::CancelSource);
+    shutdown(pipe, action, Some(error), context);
+}
+
+/// Handles the source becoming closed.
+fn source_closed(pipe: &JsObject, context: &mut Context) {
+    let action = (!data(pipe).options.prevent_close).then_some(Action::CloseDestination);
+    shutdown(pipe, action, None, context);
+}
+
+/// Handles the destination becoming closing or closed.
+fn destination_closed(pipe: &JsObject, context: &mut Context) {
+    let error = JsNativeError::typ()
+        .with_message("the destination stream was closed")
+        .to_opaque(context);
+    let action = (!data(pipe).options.prevent_cancel).then_some(Action::CancelSource);
+    shutdown(pipe, action, Some(error.into()), context);
+}
+
+/// Handles the abort signal of the pipe being aborted.
+fn abort(pipe: &JsObject, reason: JsValue, context: &mut Context) {
+    let action = {
+        let data = data(pipe);
+        match (data.options.prevent_abort, data.options.prevent_cancel) {
+            (false, false) => Some(Action::AbortAndCancel),
+            (false, true) => Some(Action::AbortDestination),
+            (true, false) => Some(Action::CancelSource),
+            (true, true) => None,
+        }
+    };
+    shutdown(pipe, action, Some(reason), context);
+}
+
+/// Shuts down the pipe, waiting for the pending writes before performing `action`.
+fn shutdown(
+    pipe: &JsObject,
+    action: Option<Action>,
+    error: Option<JsValue>,
+    context: &mut Context,
+) {
+    let destination = {
+        let mut data = data(pipe);
+        if data.shutting_down {
+            return;
+        }
+        data.shutting_down = true;
+        data.action = action;
+        data.error = error;
+        data.destination.clone()
+    };
+    if WritableStream::state(&destination) == WritableState::Writable
+        && !WritableStream::close_queued_or_in_flight(&destination)
+    {
+        wait_for_writes(pipe, context);
+    } else {
+        perform_action(pipe, context);
+    }
+}
+
+/// Waits for the last write of the pipe, including the writes started while waiting.
+///
+/// The chunk of a pending read is also written if the source was closed or errored, since the
+/// read will settle soon.
+fn wait_for_writes(pipe: &JsObject, context: &mut Context) {
+    let (read, write, source) = {
+        let data = data(pipe);
+        (
+            data.current_read.clone(),
+            data.current_write.clone(),
+            data.source.clone(),
+        )
+    };
+    if let Some(read) = read.filter(|_| ReadableStream::state(&source) != ReadableState::Readable) {
+        upon(
+            &read,
+            pipe,
+            Some(|_, pipe, context| {
+                wait_for_writes(pipe, context);
+                Ok(JsValue::undefined())
+            }),
+            None,
+            context,
+        );
+        return;
+    }
+    let Some(write) = write else {
+        perform_action(pipe, context);
+        return;
+    };
+    upon(
+        &write,
+        &(pipe.clone(), write.clone()),
+        Some(|_, (pipe, write), context| {
+            let current = data(pipe).current_write.clone();
+            if current.is_some_and(|current| *current != **write) {
+                wait_for_writes(pipe, context);
+            } else {
+                perform_action(pipe, context);
+            }
+            Ok(JsValue::undefined())
+        }),
+        None,
+        context,
+    );
+}
+
+/// Performs the shutdown action of the pipe, then finalizes it.
+fn perform_action(pipe: &JsObject, context: &mut Context) {
+    let (action, error, source, destination, writer) = {
+        let data = data(pipe);
+        (
+            data.action,
+            data.error.clone().unwrap_or_default(),
+            data.source.clone(),
+            data.destination.clone(),
+            data.writer.clone(),
+        )
+    };
+    let abort_destination = |context: &mut Context| {
+        if WritableStream::state(&destination) == WritableState::Writable {
+            WritableStream::abort(&destination, error.clone(), context)
+        } else {
+            JsPromise::resolve(JsValue::undefined(), context)
+        }
+    };
+    let cancel_source = |context: &mut Context| {
+        if ReadableStream::state(&source) == ReadableState::Readable {
+            ReadableStream::cancel(&source, error.clone(), context)
+        } else {
+            JsPromise::resolve(JsValue::undefined(), context)
+        }
+    };
+    let promise = match action {
+        None => {
+            finalize(pipe, context);
+            return;
+        }
+        Some(Action::AbortDestination) => abort_destination(context),
+        Some(Action::CancelSource) => cancel_source(context),
+        Some(Action::AbortAndCancel) => {
+            let abort = abort_destination(context);
+            let cancel = cancel_source(context);
+            upon(
+                &abort,
+                &cancel,
+                Some(|_, cancel, _| Ok(cancel.clone().into())),
+                None,
+                context,
+            )
+        }
+        Some(Action::CloseDestination) => {
+            WritableStreamDefaultWriter::close_with_error_propagation(&writer, context)
+        }
+    };
+    upon(
+        &promise,
+        pipe,
+        Some(|_, pipe, context| {
+            finalize(pipe, context);
+            Ok(JsValue::undefined())
+        }),
+        Some(|reason, pipe, context| {
+            data(pipe).error = Some(reason.clone());
+            finalize(pipe, context);
+            Ok(JsValue::undefined())
+        }),
+        context,
+    );
+}
+
+/// Releases the streams of the pipe and settles its promise.
+fn finalize(pipe: &JsObject, context: &mut Context) {
+    let (reader, writer, error, promise) = {
+        let data = data(pipe);
+        (
+            data.reader.clone(),
+            data.writer.clone(),
+            data.error.clone(),
+            data.promise.clone(),
+        )
+    };
+    WritableStreamDefaultWriter::release(&writer, context);
+    ReadableStreamDefaultReader::release(&reader, context);
+    match error {
+        Some(error) => promise.reject(error, context),
+        None => promise.resolve(JsValue::undefined(), context),
+    }
+}
@@ -0,0 +1,209 @@
+//! Promise utilities shared by the stream classes.
+
+use boa_engine::{
+    native_function::NativeFunction,
+    object::{
+        builtins::{JsFunction, JsPromise},
+        JsObject,
+    },
+    Context, JsArgs, JsError, JsNativeError, JsResult, JsString, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+
+/// A promise together with the functions settling it.
+///
+/// Settling an already settled promise does nothing, like in the specification.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub(super) struct Deferred {
+    promise: JsPromise,
+    resolve: JsFunction,
+    reject: JsFunction,
+}
+
+impl Deferred {
+    /// Creates a new pending promise.
+    pub(super) fn new(context: &mut Context) -> Self {
+        let (promise, resolvers) = JsPromise::new_pending(context);
+        Self {
+            promise,
+            resolve: resolvers.resolve,
+            reject: resolvers.reject,
+        }
+    }
+
+    /// Creates a promise already resolved with `value`.
+    pub(super) fn resolved(value: JsValue, context: &mut Context) -> Self {
+        let deferred = Self::new(context);
+        deferred.resolve(value, context);
+        deferred
+    }
+
+    /// Creates a promise already rejected with `reason`.
+    pub(super) fn rejected(reason: JsValue, context: &mut Context) -> Self {
+        let deferred = Self::new(context);
+        deferred.reject(reason, context);
+        deferred
+    }
+
+    /// Returns the promise.
+    pub(super) fn promise(&self) -> JsPromise {
+        self.promise.clone()
+    }
+
+    /// Returns `true` if the promise is still pending.
+    pub(super) fn is_pending(&self) -> bool {
+        matches!(
+            self.promise.state(),
+            boa_engine::builtins::promise::PromiseState::Pending
+        )
+    }
+
+    /// Resolves the promise with `value`.
+    pub(super) fn resolve(&self, value: JsValue, context: &mut Context) {
+        self.resolve
+            .call(&JsValue::undefined(), &[value], context)
+            .expect("resolving functions cannot throw");
+    }
+
+    /// Rejects the promise with `reason`.
+    pub(super) fn reject(&self, reason: JsValue, context: &mut Context) {
+        self.reject
+            .call(&JsValue::undefined(), &[reason], context)
+            .expect("resolving functions cannot throw");
+    }
+}
+
+/// A reaction to the settlement of a promise.
+pub(super) type Reaction<T> = fn(&JsValue, &T, &mut Context) -> JsResult<JsValue>;
+
+/// Reacts to the fulfillment or rejection of `promise`, returning the promise resolved with the
+/// result of the reaction.
+///
+/// Missing reactions pass the value or the reason through.
+pub(super) fn upon<T: Trace + Clone + 'static>(
+    promise: &JsPromise,
+    captures: &T,
+    on_fulfilled: Option<Reaction<T>>,
+    on_rejected: Option<Reaction<T>>,
+    context: &mut Context,
+) -> JsPromise {
+    let realm = context.realm().clone();
+    let reaction = |reaction: Reaction<T>| {
+        NativeFunction::from_copy_closure_with_captures(
+            move |_, args, captures, context| reaction(args.get_or_undefined(0), captures, context),
+            captures.clone(),
+        )
+        .to_js_function(&realm)
+    };
+    promise.then(
+        on_fulfilled.map(reaction),
+        on_rejected.map(reaction),
+        context,
+    )
+}
+
+/// Converts the result of an operation into a promise.
+pub(super) fn to_promise(result: JsResult<JsValue>, context: &mut Context) -> JsPromise {
+    match result {
+        Ok(value) => JsPromise::resolve(value, context),
+        Err(e) => JsPromise::reject(e, context),
+    }
+}
+
+/// Returns a promise rejected with a new `TypeError`.
+pub(super) fn rejected_type_error(message: &'static str, context: &mut Context) -> JsPromise {
+    JsPromise::reject(JsNativeError::typ().with_message(message), context)
+}
+
+/// Converts a rejection reason into an error that can be thrown.
+pub(super) fn throw(reason: &JsValue) -> JsError {
+    JsError::from_opaque(reason.clone())
+}
+
+/// An algorithm of an underlying source, sink or transformer: a function called with a fixed
+/// `this` value.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub(super) struct Algorithm {
+    this: JsValue,
+    function: JsObject,
+}
+
+impl Algorithm {
+    /// Gets the method `name` of `object`, which is `undefined` if it doesn't exist.
+    ///
+    /// Throws a `TypeError` if the method is not callable.
+    pub(super) fn from_method(
+        object: &JsValue,
+        name: &JsString,
+        context: &mut Context,
+    ) -> JsResult<Option<Self>> {
+        let Some(object) = object.as_object() else {
+            return Ok(None);
+        };
+        let method = object.get(name.clone(), context)?;
+        if method.is_null_or_undefined() {
+            return Ok(None);
+        }
+        let function = method.as_callable().cloned().ok_or_else(|| {
+            JsNativeError::typ().with_message(format!(
+                "the '{}' member must be a function",
+                name.to_std_string_escaped()
+            ))
+        })?;
+        Ok(Some(Self {
+            this: object.clone().into(),
+            function,
+        }))
+    }
+
+    /// Creates an algorithm from a native function.
+    pub(super) fn native(function: NativeFunction, context: &mut Context) -> Self {
+        Self {
+            this: JsValue::undefined(),
+            function: function.to_js_function(context.realm()).into(),
+        }
+    }
+
+    /// Calls the algorithm.
+    pub(super) fn call(&self, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        self.function.call(&self.this, args, context)
+    }
+
+    /// Calls an optional algorithm, returning a promise for its result.
+    ///
+    /// Missing algorithms return a promise resolved with `undefined`, and errors thrown by the
+    /// algorithm reject the returned promise.
+    pub(super) fn call_promise(
+        algorithm: Option<&Self>,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsPromise {
+        let result = algorithm.map_or(Ok(JsValue::undefined()), |algorithm| {
+            algorithm.call(args, context)
+        });
+        to_promise(result, context)
+    }
+}
+
+/// Gets the boolean member `name` of an optional dictionary.
+pub(super) fn get_bool(
+    dictionary: &JsValue,
+    name: JsString,
+    context: &mut Context,
+) -> JsResult<bool> {
+    match dictionary.as_object() {
+        Some(dictionary) => Ok(dictionary.get(name, context)?.to_boolean()),
+        None => Ok(false),
+    }
+}
+
+/// Checks that an optional dictionary argument is an object, `undefined` or `null`.
+pub(super) fn check_dictionary(value: &JsValue, name: &str) -> JsResult<()> {
+    if value.is_object() || value.is_null_or_undefined() {
+        Ok(())
+    } else {
+        Err(JsNativeError::typ()
+            .with_message(format!("the {name} must be an object"))
+            .into())
+    }
+}
@@ -0,0 +1,1258 @@
+//! Boa's implementation of the `ReadableStream` object, and of its default reader and
+//! controller.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `Streams` specification][spec]
+//!
+//! [spec]: https://streams.spec.whatwg.org/#rs-model
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStream
+
+use super::{
+    create_instance, pipe,
+    promise::{check_dictionary, get_bool, rejected_type_error, throw, upon, Algorithm, Deferred},
+    strategy::{QueuingStrategy, SizedQueue},
+};
+use crate::util::add_getter;
+use boa_engine::{
+    builtins::iterable::create_iter_result_object,
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::{builtins::JsPromise, ErasedObject, JsObject, ObjectInitializer, RefMut},
+    property::Attribute,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsSymbol, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::collections::VecDeque;
+
+/// The state of a `ReadableStream`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ReadableState {
+    Readable,
+    Closed,
+    Errored,
+}
+
+/// Creates a new `TypeError` as a JavaScript value.
+fn type_error(message: &'static str, context: &mut Context) -> JsValue {
+    JsNativeError::typ()
+        .with_message(message)
+        .to_opaque(context)
+        .into()
+}
+
+/// The `ReadableStream` class, a source of data that can be read in chunks.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Streams` specification][spec]
+///
+/// [spec]: https://streams.spec.whatwg.org/#rs-class
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStream
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct ReadableStream {
+    #[unsafe_ignore_trace]
+    state: ReadableState,
+    stored_error: JsValue,
+    disturbed: bool,
+    reader: Option<JsObject>,
+    controller: Option<JsObject>,
+}
+
+impl Default for ReadableStream {
+    fn default() -> Self {
+        Self {
+            state: ReadableState::Readable,
+            stored_error: JsValue::undefined(),
+            disturbed: false,
+            reader: None,
+            controller: None,
+        }
+    }
+}
+
+impl ReadableStream {
+    /// Borrows the data of a `ReadableStream` object.
+    fn data(stream: &JsObject) -> RefMut<'_, ErasedObject, Self> {
+        stream
+            .downcast_mut::<Self>()
+            .expect("must be a ReadableStream")
+    }
+
+    /// Creates a `ReadableStream` object, whose controller must be set up afterwards.
+    pub(super) fn new_object(context: &mut Context) -> JsResult<JsObject> {
+        create_instance(Self::default(), context)
+    }
+
+    /// Creates a `ReadableStream` with a default controller.
+    pub(super) fn create(
+        start: Option<Algorithm>,
+        pull: Option<Algorithm>,
+        cancel: Option<Algorithm>,
+        strategy: QueuingStrategy,
+        context: &mut Context,
+    ) -> JsResult<JsObject> {
+        let stream = Self::new_object(context)?;
+        ReadableStreamDefaultController::setup(&stream, start, pull, cancel, strategy, context)?;
+        Ok(stream)
+    }
+
+    /// Returns `true` if the stream has a reader.
+    pub(super) fn is_locked(stream: &JsObject) -> bool {
+        Self::data(stream).reader.is_some()
+    }
+
+    /// Returns the state of the stream.
+    pub(super) fn state(stream: &JsObject) -> ReadableState {
+        Self::data(stream).state
+    }
+
+    /// Returns the error of an errored stream.
+    pub(super) fn stored_error(stream: &JsObject) -> JsValue {
+        Self::data(stream).stored_error.clone()
+    }
+
+    /// Returns the controller of the stream.
+    pub(super) fn controller(stream: &JsObject) -> JsObject {
+        Self::data(stream)
+            .controller
+            .clone()
+            .expect("the controller must be set up")
+    }
+
+    /// Marks the stream as read from.
+    pub(super) fn disturb(stream: &JsObject) {
+        Self::data(stream).disturbed = true;
+    }
+
+    /// Returns the number of pending read requests of the reader of the stream.
+    fn num_read_requests(stream: &JsObject) -> usize {
+        let reader = Self::data(stream).reader.clone();
+        reader.map_or(0, |reader| {
+            ReadableStreamDefaultReader::data(&reader)
+                .read_requests
+                .len()
+        })
+    }
+
+    /// Resolves the first pending read request of the reader of the stream.
+    fn fulfill_read_request(stream: &JsObject, chunk: JsValue, done: bool, context: &mut Context) {
+        let reader = Self::data(stream).reader.clone().expect("must be locked");
+        let request = ReadableStreamDefaultReader::data(&reader)
+            .read_requests
+            .pop_front()
+            .expect("must have a read request");
+        let result = create_iter_result_object(chunk, done, context);
+        request.resolve(result, context);
+    }
+
+    /// [Closes][spec] the stream, resolving its pending read requests.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#readable-stream-close
+    fn close(stream: &JsObject, context: &mut Context) {
+        let reader = {
+            let mut data = Self::data(stream);
+            data.state = ReadableState::Closed;
+            data.reader.clone()
+        };
+        let Some(reader) = reader else {
+            return;
+        };
+        let (closed, requests) = {
+            let mut reader = ReadableStreamDefaultReader::data(&reader);
+            (
+                reader.closed.clone(),
+                std::mem::take(&mut reader.read_requests),
+            )
+        };
+        if let Some(closed) = closed {
+            closed.resolve(JsValue::undefined(), context);
+        }
+        for request in requests {
+            let result = create_iter_result_object(JsValue::undefined(), true, context);
+            request.resolve(result, context);
+        }
+    }
+
+    /// [Errors][spec] the stream, rejecting its pending read requests.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#readable-stream-error
+    fn error(stream: &JsObject, error: &JsValue, context: &mut Context) {
+        let reader = {
+            let mut data = Self::data(stream);
+            data.state = ReadableState::Errored;
+            data.stored_error = error.clone();
+            data.reader.clone()
+        };
+        let Some(reader) = reader else {
+            return;
+        };
+        let (closed, requests) = {
+            let mut reader = ReadableStreamDefaultReader::data(&reader);
+            (
+                reader.closed.clone(),
+                std::mem::take(&mut reader.read_requests),
+            )
+        };
+        if let Some(closed) = closed {
+            closed.reject(error.clone(), context);
+        }
+        for request in requests {
+            request.reject(error.clone(), context);
+        }
+    }
+
+    /// [Cancels][spec] the stream, returning a promise resolved when the underlying source was
+    /// canceled.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#readable-stream-cancel
+    pub(super) fn cancel(stream: &JsObject, reason: JsValue, context: &mut Context) -> JsPromise {
+        Self::disturb(stream);
+        match Self::state(stream) {
+            ReadableState::Closed => return JsPromise::resolve(JsValue::undefined(), context),
+            ReadableState::Errored => {
+                return JsPromise::reject(throw(&Self::stored_error(stream)), context)
+            }
+            ReadableState::Readable => {}
+        }
+        Self::close(stream, context);
+        let controller = Self::controller(stream);
+        let source_cancel =
+            ReadableStreamDefaultController::cancel_steps(&controller, reason, context);
+        upon(
+            &source_cancel,
+            &(),
+            Some(|_, (), _| Ok(JsValue::undefined())),
+            None,
+            context,
+        )
+    }
+
+    /// Checks that `this` is a `ReadableStream` object, returning it.
+    fn this_stream(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a ReadableStream object")
+                    .into()
+            })
+    }
+
+    /// `get ReadableStream.prototype.locked`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#rs-locked
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStream/locked
+    fn get_locked(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::is_locked(Self::this_stream(this)?).into())
+    }
+
+    /// `ReadableStream.prototype.cancel(reason)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#rs-cancel
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStream/cancel
+    #[allow(clippy::unnecessary_wraps)]
+    fn cancel_method(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let stream = match Self::this_stream(this) {
+            Ok(stream) => stream,
+            Err(e) => return Ok(JsPromise::reject(e, context).into()),
+        };
+        if Self::is_locked(stream) {
+            return Ok(rejected_type_error("cannot cancel a locked stream", context).into());
+        }
+        Ok(Self::cancel(stream, args.get_or_undefined(0).clone(), context).into())
+    }
+
+    /// `ReadableStream.prototype.getReader(options)`
+    ///
+    /// Only default readers are supported, since byte streams are not implemented.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#rs-get-reader
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStream/getReader
+    fn get_reader(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let stream = Self::this_stream(this)?;
+        let options = args.get_or_undefined(0);
+        check_dictionary(options, "reader options")?;
+        if let Some(options) = options.as_object() {
+            let mode = options.get(js_string!("mode"), context)?;
+            if !mode.is_undefined() {
+                let mode = mode.to_string(context)?;
+                let message = if mode == js_string!("byob") {
+                    "BYOB readers are not supported, since byte streams are not implemented"
+                } else {
+                    "the reader mode must be 'byob'"
+                };
+                return Err(JsNativeError::typ().with_message(message).into());
+            }
+        }
+        Ok(ReadableStreamDefaultReader::acquire(stream, context)?.into())
+    }
+
+    /// `ReadableStream.prototype.pipeThrough(transform, options)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#rs-pipe-through
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStream/pipeThrough
+    fn pipe_through(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let stream = Self::this_stream(this)?;
+        let transform = args.get_or_undefined(0).as_object().ok_or_else(|| {
+            JsNativeError::typ()
+                .with_message("the transform must be an object with writable and readable streams")
+        })?;
+        let readable = transform.get(js_string!("readable"), context)?;
+        let readable = readable
+            .as_object()
+            .filter(|readable| readable.is::<Self>())
+            .cloned()
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message("the readable member must be a ReadableStream")
+            })?;
+        let writable = transform.get(js_string!("writable"), context)?;
+        let writable = writable
+            .as_object()
+            .filter(|writable| writable.is::<super::WritableStream>())
+            .cloned()
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message("the writable member must be a WritableStream")
+            })?;
+        let options = pipe::PipeOptions::from_value(args.get_or_undefined(1), context)?;
+        if Self::is_locked(stream) {
+            return Err(JsNativeError::typ()
+                .with_message("cannot pipe a locked stream")
+                .into());
+        }
+        if super::WritableStream::is_locked(&writable) {
+            return Err(JsNativeError::typ()
+                .with_message("cannot pipe to a locked stream")
+                .into());
+        }
+        pipe::pipe_to(stream, &writable, &options, context)?;
+        Ok(readable.into())
+    }
+
+    /// `ReadableStream.prototype.pipeTo(destination, options)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#rs-pipe-to
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStream/pipeTo
+    #[allow(clippy::unnecessary_wraps)]
+    fn pipe_to(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let result = (|| {
+            let stream = Self::this_stream(this)?;
+            let destination = args
+                .get_or_undefined(0)
+                .as_object()
+                .filter(|destination| destination.is::<super::WritableStream>())
+                .ok_or_else(|| {
+                    JsNativeError::typ().with_message("the destination must be a WritableStream")
+                })?;
+            let options = pipe::PipeOptions::from_value(args.get_or_undefined(1), context)?;
+            if Self::is_locked(stream) {
+                return Err(JsNativeError::typ()
+                    .with_message("cannot pipe a locked stream")
+                    .into());
+            }
+            if super::WritableStream::is_locked(destination) {
+                return Err(JsNativeError::typ()
+                    .with_message("cannot pipe to a locked stream")
+                    .into());
+            }
+            pipe::pipe_to(stream, destination, &options, context)
+        })();
+        Ok(result
+            .unwrap_or_else(|e| JsPromise::reject(e, context))
+            .into())
+    }
+
+    /// `ReadableStream.prototype.values(options)` and `ReadableStream.prototype[@@asyncIterator](options)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#rs-asynciterator
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStream#async_iteration
+    fn values(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let stream = Self::this_stream(this)?;
+        let options = args.get_or_undefined(0);
+        check_dictionary(options, "iterator options")?;
+        let prevent_cancel = get_bool(options, js_string!("preventCancel"), context)?;
+        let prototype = context
+            .realm()
+            .host_defined()
+            .get::<AsyncIteratorPrototype>()
+            .map(|prototype| prototype.0.clone())
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message("the stream iterators are not registered")
+            })?;
+        let reader = ReadableStreamDefaultReader::acquire(stream, context)?;
+        let iterator = JsObject::from_proto_and_data(
+            prototype,
+            ReadableStreamAsyncIterator {
+                reader,
+                prevent_cancel,
+                finished: false,
+            },
+        );
+        Ok(iterator.into())
+    }
+}
+
+impl Class for ReadableStream {
+    const NAME: &'static str = "ReadableStream";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        let realm = class.context().realm().clone();
+        let values = NativeFunction::from_fn_ptr(Self::values).to_js_function(&realm);
+
+        add_getter(class, js_string!("locked"), Self::get_locked);
+        class
+            .method(
+                js_string!("cancel"),
+                0,
+                NativeFunction::from_fn_ptr(Self::cancel_method),
+            )
+            .method(
+                js_string!("getReader"),
+                0,
+                NativeFunction::from_fn_ptr(Self::get_reader),
+            )
+            .method(
+                js_string!("pipeThrough"),
+                1,
+                NativeFunction::from_fn_ptr(Self::pipe_through),
+            )
+            .method(
+                js_string!("pipeTo"),
+                1,
+                NativeFunction::from_fn_ptr(Self::pipe_to),
+            )
+            .property(
+                js_string!("values"),
+                values.clone(),
+                Attribute::WRITABLE | Attribute::CONFIGURABLE,
+            )
+            .property(
+                JsSymbol::async_iterator(),
+                values,
+                Attribute::WRITABLE | Attribute::CONFIGURABLE,
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("ReadableStream"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<Self> {
+        Ok(Self::default())
+    }
+
+    fn object_constructor(
+        instance: &JsObject,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let source = args.get_or_undefined(0);
+        check_dictionary(source, "underlying source")?;
+        let cancel = Algorithm::from_method(source, &js_string!("cancel"), context)?;
+        let pull = Algorithm::from_method(source, &js_string!("pull"), context)?;
+        let start = Algorithm::from_method(source, &js_string!("start"), context)?;
+        if let Some(source) = source.as_object() {
+            let kind = source.get(js_string!("type"), context)?;
+            if !kind.is_undefined() {
+                let message = if kind.to_string(context)? == js_string!("bytes") {
+                    "readable byte streams are not supported"
+                } else {
+                    "the type of the underlying source must be 'bytes'"
+                };
+                return Err(JsNativeError::typ().with_message(message).into());
+            }
+        }
+        let strategy = QueuingStrategy::extract(args.get_or_undefined(1), 1.0, context)?;
+        ReadableStreamDefaultController::setup(instance, start, pull, cancel, strategy, context)?;
+        Ok(())
+    }
+}
+
+/// The `ReadableStreamDefaultController` class, which controls the state and the queue of a
+/// `ReadableStream`.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Streams` specification][spec]
+///
+/// [spec]: https://streams.spec.whatwg.org/#rs-default-controller-class
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStreamDefaultController
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ReadableStreamDefaultController {
+    stream: JsObject,
+    queue: SizedQueue<JsValue>,
+    strategy: QueuingStrategy,
+    started: bool,
+    close_requested: bool,
+    pull_again: bool,
+    pulling: bool,
+    pull: Option<Algorithm>,
+    cancel: Option<Algorithm>,
+}
+
+impl ReadableStreamDefaultController {
+    /// Borrows the data of a `ReadableStreamDefaultController` object.
+    fn data(controller: &JsObject) -> RefMut<'_, ErasedObject, Self> {
+        controller
+            .downcast_mut::<Self>()
+            .expect("must be a ReadableStreamDefaultController")
+    }
+
+    /// [Sets up][spec] a new controller for `stream`.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#set-up-readable-stream-default-controller
+    fn setup(
+        stream: &JsObject,
+        start: Option<Algorithm>,
+        pull: Option<Algorithm>,
+        cancel: Option<Algorithm>,
+        strategy: QueuingStrategy,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let controller = create_instance(
+            Self {
+                stream: stream.clone(),
+                queue: SizedQueue::default(),
+                strategy,
+                started: false,
+                close_requested: false,
+                pull_again: false,
+                pulling: false,
+                pull,
+                cancel,
+            },
+            context,
+        )?;
+        ReadableStream::data(stream).controller = Some(controller.clone());
+
+        let start_result = match start {
+            Some(start) => start.call(&[controller.clone().into()], context)?,
+            None => JsValue::undefined(),
+        };
+        let start_promise = JsPromise::resolve(start_result, context);
+        upon(
+            &start_promise,
+            &controller,
+            Some(|_, controller, context| {
+                Self::data(controller).started = true;
+                Self::call_pull_if_needed(controller, context);
+                Ok(JsValue::undefined())
+            }),
+            Some(|reason, controller, context| {
+                Self::error(controller, reason, context);
+                Ok(JsValue::undefined())
+            }),
+            context,
+        );
+        Ok(())
+    }
+
+    /// Calls the pull algorithm of the underlying source if the queue needs more chunks.
+    fn call_pull_if_needed(controller: &JsObject, context: &mut Context) {
+        if !Self::should_call_pull(controller) {
+            return;
+        }
+        let pull = {
+            let mut data = Self::data(controller);
+            if data.pulling {
+                data.pull_again = true;
+                return;
+            }
+            data.pulling = true;
+            data.pull.clone()
+        };
+        let pull_promise =
+            Algorithm::call_promise(pull.as_ref(), &[controller.clone().into()], context);
+        upon(
+            &pull_promise,
+            controller,
+            Some(|_, controller, context| {
+                let pull_again = {
+                    let mut data = Self::data(controller);
+                    data.pulling = false;
+                    std::mem::take(&mut data.pull_again)
+                };
+                if pull_again {
+                    Self::call_pull_if_needed(controller, context);
+                }
+                Ok(JsValue::undefined())
+            }),
+            Some(|reason, controller, context| {
+                Self::error(controller, reason, context);
+                Ok(JsValue::undefined())
+            }),
+            context,
+        );
+    }
+
+    /// Returns `true` if the pull algorithm should be called.
+    fn should_call_pull(controller: &JsObject) -> bool {
+        if !Self::can_close_or_enqueue(controller) {
+            return false;
+        }
+        let stream = {
+            let data = Self::data(controller);
+            if !data.started {
+                return false;
+            }
+            data.stream.clone()
+        };
+        if ReadableStream::is_locked(&stream) && ReadableStream::num_read_requests(&stream) > 0 {
+            return true;
+        }
+        Self::desired_size(controller).is_some_and(|size| size > 0.0)
+    }
+
+    /// Returns `true` if the queue is full, i.e. if the pull algorithm shouldn't be called.
+    pub(super) fn has_backpressure(controller: &JsObject) -> bool {
+        !Self::should_call_pull(controller)
+    }
+
+    /// Returns `true` if chunks can still be enqueued in the stream.
+    pub(super) fn can_close_or_enqueue(controller: &JsObject) -> bool {
+        let data = Self::data(controller);
+        !data.close_requested && ReadableStream::state(&data.stream) == ReadableState::Readable
+    }
+
+    /// Returns the desired size to fill the queue of the stream, which is `None` if the stream
+    /// is errored.
+    pub(super) fn desired_size(controller: &JsObject) -> Option<f64> {
+        let data = Self::data(controller);
+        match ReadableStream::state(&data.stream) {
+            ReadableState::Errored => None,
+            ReadableState::Closed => Some(0.0),
+            ReadableState::Readable => {
+                Some(data.strategy.high_water_mark - data.queue.total_size())
+            }
+        }
+    }
+
+    /// [Enqueues][spec] `chunk` in the stream, which must be able to accept chunks.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#readable-stream-default-controller-enqueue
+    pub(super) fn enqueue(
+        controller: &JsObject,
+        chunk: JsValue,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        if !Self::can_close_or_enqueue(controller) {
+            return Ok(());
+        }
+        let stream = Self::data(controller).stream.clone();
+        if ReadableStream::is_locked(&stream) && ReadableStream::num_read_requests(&stream) > 0 {
+            ReadableStream::fulfill_read_request(&stream, chunk, false, context);
+        } else {
+            let strategy = Self::data(controller).strategy.clone();
+            let size = match strategy.size(&chunk, context) {
+                Ok(size) => size,
+                Err(e) => {
+                    let reason = e.to_opaque(context);
+                    Self::error(controller, &reason, context);
+                    return Err(throw(&reason));
+                }
+            };
+            Self::data(controller).queue.enqueue(chunk, size);
+        }
+        Self::call_pull_if_needed(controller, context);
+        Ok(())
+    }
+
+    /// [Closes][spec] the stream once all of its queued chunks have been read.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#readable-stream-default-controller-close
+    pub(super) fn close(controller: &JsObject, context: &mut Context) {
+        if !Self::can_close_or_enqueue(controller) {
+            return;
+        }
+        let stream = {
+            let mut data = Self::data(controller);
+            data.close_requested = true;
+            if !data.queue.is_empty() {
+                return;
+            }
+            data.stream.clone()
+        };
+        Self::clear_algorithms(controller);
+        ReadableStream::close(&stream, context);
+    }
+
+    /// [Errors][spec] the stream, discarding its queued chunks.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#readable-stream-default-controller-error
+    pub(super) fn error(controller: &JsObject, error: &JsValue, context: &mut Context) {
+        let stream = Self::data(controller).stream.clone();
+        if ReadableStream::state(&stream) != ReadableState::Readable {
+            return;
+        }
+        Self::data(controller).queue.reset();
+        Self::clear_algorithms(controller);
+        ReadableStream::error(&stream, error, context);
+    }
+
+    /// Drops the algorithms of the underlying source, which won't be called anymore.
+    fn clear_algorithms(controller: &JsObject) {
+        let mut data = Self::data(controller);
+        data.pull = None;
+        data.cancel = None;
+        data.strategy.size = None;
+    }
+
+    /// Cancels the underlying source, discarding the queued chunks.
+    fn cancel_steps(controller: &JsObject, reason: JsValue, context: &mut Context) -> JsPromise {
+        let cancel = {
+            let mut data = Self::data(controller);
+            data.queue.reset();
+            data.cancel.clone()
+        };
+        let result = Algorithm::call_promise(cancel.as_ref(), &[reason], context);
+        Self::clear_algorithms(controller);
+        result
+    }
+
+    /// Reads a chunk from the queue, or waits for one to be enqueued.
+    fn pull_steps(controller: &JsObject, request: Deferred, context: &mut Context) {
+        let (chunk, stream, closing) = {
+            let mut data = Self::data(controller);
+            let chunk = data.queue.dequeue();
+            let closing = data.close_requested && data.queue.is_empty();
+            (chunk, data.stream.clone(), closing)
+        };
+        let Some(chunk) = chunk else {
+            let reader = ReadableStream::data(&stream)
+                .reader
+                .clone()
+                .expect("must be locked");
+            ReadableStreamDefaultReader::data(&reader)
+                .read_requests
+                .push_back(request);
+            Self::call_pull_if_needed(controller, context);
+            return;
+        };
+        if closing {
+            Self::clear_algorithms(controller);
+            ReadableStream::close(&stream, context);
+        } else {
+            Self::call_pull_if_needed(controller, context);
+        }
+        let result = create_iter_result_object(chunk, false, context);
+        request.resolve(result, context);
+    }
+
+    /// Checks that `this` is a `ReadableStreamDefaultController` object, returning it.
+    fn this_controller(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a ReadableStreamDefaultController object")
+                    .into()
+            })
+    }
+
+    /// `get ReadableStreamDefaultController.prototype.desiredSize`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#rs-default-controller-desired-size
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStreamDefaultController/desiredSize
+    fn get_desired_size(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let controller = Self::this_controller(this)?;
+        Ok(Self::desired_size(controller).map_or_else(JsValue::null, Into::into))
+    }
+
+    /// `ReadableStreamDefaultController.prototype.close()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#rs-default-controller-close
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStreamDefaultController/close
+    fn close_method(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let controller = Self::this_controller(this)?;
+        if !Self::can_close_or_enqueue(controller) {
+            return Err(JsNativeError::typ()
+                .with_message("the stream is already closing or not readable")
+                .into());
+        }
+        Self::close(controller, context);
+        Ok(JsValue::undefined())
+    }
+
+    /// `ReadableStreamDefaultController.prototype.enqueue(chunk)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#rs-default-controller-enqueue
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStreamDefaultController/enqueue
+    fn enqueue_method(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let controller = Self::this_controller(this)?;
+        if !Self::can_close_or_enqueue(controller) {
+            return Err(JsNativeError::typ()
+                .with_message("the stream is already closing or not readable")
+                .into());
+        }
+        Self::enqueue(controller, args.get_or_undefined(0).clone(), context)?;
+        Ok(JsValue::undefined())
+    }
+
+    /// `ReadableStreamDefaultController.prototype.error(e)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#rs-default-controller-error
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStreamDefaultController/error
+    fn error_method(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let controller = Self::this_controller(this)?;
+        Self::error(controller, args.get_or_undefined(0), context);
+        Ok(JsValue::undefined())
+    }
+}
+
+impl Class for ReadableStreamDefaultController {
+    const NAME: &'static str = "ReadableStreamDefaultController";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(class, js_string!("desiredSize"), Self::get_desired_size);
+        class
+            .method(
+                js_string!("close"),
+                0,
+                NativeFunction::from_fn_ptr(Self::close_method),
+            )
+            .method(
+                js_string!("enqueue"),
+                0,
+                NativeFunction::from_fn_ptr(Self::enqueue_method),
+            )
+            .method(
+                js_string!("error"),
+                0,
+                NativeFunction::from_fn_ptr(Self::error_method),
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("ReadableStreamDefaultController"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<Self> {
+        Err(JsNativeError::typ()
+            .with_message("ReadableStreamDefaultController cannot be constructed directly")
+            .into())
+    }
+}
+
+/// The `ReadableStreamDefaultReader` class, which reads the chunks of a `ReadableStream`.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Streams` specification][spec]
+///
+/// [spec]: https://streams.spec.whatwg.org/#default-reader-class
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStreamDefaultReader
+#[derive(Debug, Clone, Default, Trace, Finalize, JsData)]
+pub struct ReadableStreamDefaultReader {
+    stream: Option<JsObject>,
+    closed: Option<Deferred>,
+    read_requests: VecDeque<Deferred>,
+}
+
+impl ReadableStreamDefaultReader {
+    /// Borrows the data of a `ReadableStreamDefaultReader` object.
+    fn data(reader: &JsObject) -> RefMut<'_, ErasedObject, Self> {
+        reader
+            .downcast_mut::<Self>()
+            .expect("must be a ReadableStreamDefaultReader")
+    }
+
+    /// Creates a reader locking `stream`.
+    pub(super) fn acquire(stream: &JsObject, context: &mut Context) -> JsResult<JsObject> {
+        let reader = create_instance(Self::default(), context)?;
+        Self::setup(&reader, stream, context)?;
+        Ok(reader)
+    }
+
+    /// Locks `stream` to `reader`.
+    fn setup(reader: &JsObject, stream: &JsObject, context: &mut Context) -> JsResult<()> {
+        if ReadableStream::is_locked(stream) {
+            return Err(JsNativeError::typ()
+                .with_message("the stream is already locked to a reader")
+                .into());
+        }
+        let closed = match ReadableStream::state(stream) {
+            ReadableState::Readable => Deferred::new(context),
+            ReadableState::Closed => Deferred::resolved(JsValue::undefined(), context),
+            ReadableState::Errored => {
+                Deferred::rejected(ReadableStream::stored_error(stream), context)
+            }
+        };
+        let mut data = Self::data(reader);
+        data.stream = Some(stream.clone());
+        data.closed = Some(closed);
+        ReadableStream::data(stream).reader = Some(reader.clone());
+        Ok(())
+    }
+
+    /// Returns the promise resolved when the stream is closed, or rejected when it errors or
+    /// when the reader is released.
+    pub(super) fn closed(reader: &JsObject) -> JsPromise {
+        Self::data(reader)
+            .closed
+            .as_ref()
+            .expect("the reader must be set up")
+            .promise()
+    }
+
+    /// Reads the next chunk of the stream, returning a promise for an iterator result.
+    pub(super) fn read(reader: &JsObject, context: &mut Context) -> JsPromise {
+        let Some(stream) = Self::data(reader).stream.clone() else {
+            return rejected_type_error("the reader was released", context);
+        };
+        let request = Deferred::new(context);
+        let promise = request.promise();
+        ReadableStream::disturb(&stream);
+        match ReadableStream::state(&stream) {
+            ReadableState::Closed => {
+                let result = create_iter_result_object(JsValue::undefined(), true, context);
+                request.resolve(result, context);
+            }
+            ReadableState::Errored => {
+                request.reject(ReadableStream::stored_error(&stream), context);
+            }
+            ReadableState::Readable => {
+                let controller = ReadableStream::controller(&stream);
+                ReadableStreamDefaultController::pull_steps(&controller, request, context);
+            }
+        }
+        promise
+    }
+
+    /// Releases the lock of the reader on its stream, rejecting its pending read requests.
+    pub(super) fn release(reader: &JsObject, context: &mut Context) {
+        let Some(stream) = Self::data(reader).stream.clone() else {
+            return;
+        };
+        let error = type_error("the reader was released", context);
+        let readable = ReadableStream::state(&stream) == ReadableState::Readable;
+        let (closed, requests) = {
+            let mut data = Self::data(reader);
+            data.stream = None;
+            (data.closed.clone(), std::mem::take(&mut data.read_requests))
+        };
+        ReadableStream::data(&stream).reader = None;
+        match closed {
+            Some(closed) if readable => closed.reject(error.clone(), context),
+            _ => Self::data(reader).closed = Some(Deferred::rejected(error.clone(), context)),
+        }
+        for request in requests {
+            request.reject(error.clone(), context);
+        }
+    }
+
+    /// Checks that `this` is a `ReadableStreamDefaultReader` object, returning it.
+    fn this_reader(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a ReadableStreamDefaultReader object")
+                    .into()
+            })
+    }
+
+    /// `get ReadableStreamDefaultReader.prototype.closed`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#generic-reader-closed
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStreamDefaultReader/closed
+    #[allow(clippy::unnecessary_wraps)]
+    fn get_closed(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        match Self::this_reader(this) {
+            Ok(reader) => Ok(Self::closed(reader).into()),
+            Err(e) => Ok(JsPromise::reject(e, context).into()),
+        }
+    }
+
+    /// `ReadableStreamDefaultReader.prototype.cancel(reason)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#generic-reader-cancel
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStreamDefaultReader/cancel
+    #[allow(clippy::unnecessary_wraps)]
+    fn cancel(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let reader = match Self::this_reader(this) {
+            Ok(reader) => reader,
+            Err(e) => return Ok(JsPromise::reject(e, context).into()),
+        };
+        let Some(stream) = Self::data(reader).stream.clone() else {
+            return Ok(rejected_type_error("the reader was released", context).into());
+        };
+        Ok(ReadableStream::cancel(&stream, args.get_or_undefined(0).clone(), context).into())
+    }
+
+    /// `ReadableStreamDefaultReader.prototype.read()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#default-reader-read
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStreamDefaultReader/read
+    #[allow(clippy::unnecessary_wraps)]
+    fn read_method(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        match Self::this_reader(this) {
+            Ok(reader) => Ok(Self::read(reader, context).into()),
+            Err(e) => Ok(JsPromise::reject(e, context).into()),
+        }
+    }
+
+    /// `ReadableStreamDefaultReader.prototype.releaseLock()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#default-reader-release-lock
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ReadableStreamDefaultReader/releaseLock
+    fn release_lock(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::release(Self::this_reader(this)?, context);
+        Ok(JsValue::undefined())
+    }
+}
+
+impl Class for ReadableStreamDefaultReader {
+    const NAME: &'static str = "ReadableStreamDefaultReader";
+    const LENGTH: usize = 1;
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(class, js_string!("closed"), Self::get_closed);
+        class
+            .method(
+                js_string!("cancel"),
+                0,
+                NativeFunction::from_fn_ptr(Self::cancel),
+            )
+            .method(
+                js_string!("read"),
+                0,
+                NativeFunction::from_fn_ptr(Self::read_method),
+            )
+            .method(
+                js_string!("releaseLock"),
+                0,
+                NativeFunction::from_fn_ptr(Self::release_lock),
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("ReadableStreamDefaultReader"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<Self> {
+        Ok(Self::default())
+    }
+
+    fn object_constructor(
+        instance: &JsObject,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let stream = args
+            .get_or_undefined(0)
+            .as_object()
+            .filter(|stream| stream.is::<ReadableStream>())
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message("the argument must be a ReadableStream")
+            })?;
+        Self::setup(instance, stream, context)
+    }
+}
+
+/// The prototype of the async iterators of readable streams, stored in the host defined data of
+/// the realm.
+#[derive(Debug, Trace, Finalize, JsData)]
+pub(super) struct AsyncIteratorPrototype(JsObject);
+
+impl AsyncIteratorPrototype {
+    /// Creates the prototype of the async iterators of readable streams.
+    pub(super) fn create(context: &mut Context) -> Self {
+        let parent = context
+            .intrinsics()
+            .objects()
+            .iterator_prototypes()
+            .async_iterator();
+        let prototype = ObjectInitializer::new(context)
+            .function(
+                NativeFunction::from_fn_ptr(ReadableStreamAsyncIterator::next),
+                js_string!("next"),
+                0,
+            )
+            .function(
+                NativeFunction::from_fn_ptr(ReadableStreamAsyncIterator::return_method),
+                js_string!("return"),
+                1,
+            )
+            .build();
+        prototype.set_prototype(Some(parent));
+        Self(prototype)
+    }
+}
+
+/// An async iterator over the chunks of a `ReadableStream`.
+///
+/// More information:
+///  - [WHATWG `Streams` specification][spec]
+///
+/// [spec]: https://streams.spec.whatwg.org/#rs-asynciterator
+#[derive(Debug, Trace, Finalize, JsData)]
+struct ReadableStreamAsyncIterator {
+    reader: JsObject,
+    prevent_cancel: bool,
+    finished: bool,
+}
+
+impl ReadableStreamAsyncIterator {
+    /// Checks that `this` is a stream async iterator, returning it.
+    fn this_iterator(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a ReadableStream async iterator")
+                    .into()
+            })
+    }
+
+    /// Stops the iteration, releasing the lock on the stream.
+    fn finish(iterator: &JsObject, context: &mut Context) -> Option<JsObject> {
+        let reader = {
+            let mut data = iterator
+                .downcast_mut::<Self>()
+                .expect("must be an iterator");
+            if data.finished {
+                return None;
+            }
+            data.finished = true;
+            data.reader.clone()
+        };
+        let stream = ReadableStreamDefaultReader::data(&reader).stream.clone();
+        ReadableStreamDefaultReader::release(&reader, context);
+        stream
+    }
+
+    /// `next()`
+    #[allow(clippy::unnecessary_wraps)]
+    fn next(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let iterator = match Self::this_iterator(this) {
+            Ok(iterator) => iterator,
+            Err(e) => return Ok(JsPromise::reject(e, context).into()),
+        };
+        let (reader, finished) = {
+            let data = iterator.downcast_ref::<Self>().expect("checked above");
+            (data.reader.clone(), data.finished)
+        };
+        if finished {
+            let result = create_iter_result_object(JsValue::undefined(), true, context);
+            return Ok(JsPromise::resolve(result, context).into());
+        }
+        let read = ReadableStreamDefaultReader::read(&reader, context);
+        let next = upon(
+            &read,
+            iterator,
+            Some(|result, iterator, context| {
+                let done = result
+                    .as_object()
+                    .expect("must be an iterator result")
+                    .get(js_string!("done"), context)?
+                    .to_boolean();
+                if done {
+                    Self::finish(iterator, context);
+                }
+                Ok(result.clone())
+            }),
+            Some(|reason, iterator, context| {
+                Self::finish(iterator, context);
+                Err(throw(reason))
+            }),
+            context,
+        );
+        Ok(next.into())
+    }
+
+    /// `return(value)`
+    #[allow(clippy::unnecessary_wraps)]
+    fn return_method(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let iterator = match Self::this_iterator(this) {
+            Ok(iterator) => iterator,
+            Err(e) => return Ok(JsPromise::reject(e, context).into()),
+        };
+        let value = args.get_or_undefined(0).clone();
+        let prevent_cancel = iterator
+            .downcast_ref::<Self>()
+            .expect("checked above")
+            .prevent_cancel;
+        let stream = Self::finish(iterator, context);
+        let result = create_iter_result_object(value.clone(), true, context);
+        let Some(stream) = stream.filter(|_| !prevent_cancel) else {
+            return Ok(JsPromise::resolve(result, context).into());
+        };
+        let cancel = ReadableStream::cancel(&stream, value, context);
+        Ok(upon(
+            &cancel,
+            &result,
+            Some(|_, result, _| Ok(result.clone())),
+            None,
+            context,
+        )
+        .into())
+    }
+}
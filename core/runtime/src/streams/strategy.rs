@@ -0,0 +1,323 @@
+//! Boa's implementation of the queuing strategies of streams, and of the `CountQueuingStrategy`
+//! and `ByteLengthQueuingStrategy` objects.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `Streams` specification][spec]
+//!
+//! [spec]: https://streams.spec.whatwg.org/#qs
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Streams_API/Concepts#internal_queues_and_queuing_strategies
+
+use crate::util::add_getter;
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::JsObject,
+    property::Attribute,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsSymbol, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::collections::VecDeque;
+
+/// The queuing strategy of a stream, extracted from the `strategy` argument of its constructor.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub(super) struct QueuingStrategy {
+    pub(super) high_water_mark: f64,
+    /// The function computing the size of each chunk, which is `1` if there's no function.
+    pub(super) size: Option<JsObject>,
+}
+
+impl QueuingStrategy {
+    /// Extracts the queuing strategy from a `QueuingStrategy` dictionary, using
+    /// `default_high_water_mark` if it doesn't have a `highWaterMark` member.
+    pub(super) fn extract(
+        strategy: &JsValue,
+        default_high_water_mark: f64,
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        let Some(strategy) = strategy.as_object() else {
+            if strategy.is_null_or_undefined() {
+                return Ok(Self {
+                    high_water_mark: default_high_water_mark,
+                    size: None,
+                });
+            }
+            return Err(JsNativeError::typ()
+                .with_message("the queuing strategy must be an object")
+                .into());
+        };
+
+        let high_water_mark = strategy.get(js_string!("highWaterMark"), context)?;
+        let high_water_mark = if high_water_mark.is_undefined() {
+            default_high_water_mark
+        } else {
+            let high_water_mark = high_water_mark.to_number(context)?;
+            if high_water_mark.is_nan() || high_water_mark < 0.0 {
+                return Err(JsNativeError::range()
+                    .with_message("the high water mark must be a non-negative number")
+                    .into());
+            }
+            high_water_mark
+        };
+
+        let size = strategy.get(js_string!("size"), context)?;
+        let size = if size.is_undefined() {
+            None
+        } else {
+            Some(size.as_callable().cloned().ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("the size of a queuing strategy must be a function")
+            })?)
+        };
+
+        Ok(Self {
+            high_water_mark,
+            size,
+        })
+    }
+
+    /// Computes the size of `chunk`.
+    pub(super) fn size(&self, chunk: &JsValue, context: &mut Context) -> JsResult<f64> {
+        let Some(size) = &self.size else {
+            return Ok(1.0);
+        };
+        let size = size
+            .call(&JsValue::undefined(), std::slice::from_ref(chunk), context)?
+            .to_number(context)?;
+        if !size.is_finite() || size < 0.0 {
+            return Err(JsNativeError::range()
+                .with_message("the size of a chunk must be a non-negative finite number")
+                .into());
+        }
+        Ok(size)
+    }
+}
+
+/// A queue of values with sizes, used by the stream controllers.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub(super) struct SizedQueue<T: Trace + 'static> {
+    sizes: VecDeque<f64>,
+    values: VecDeque<T>,
+    total_size: f64,
+}
+
+impl<T: Trace + 'static> Default for SizedQueue<T> {
+    fn default() -> Self {
+        Self {
+            sizes: VecDeque::new(),
+            values: VecDeque::new(),
+            total_size: 0.0,
+        }
+    }
+}
+
+impl<T: Trace + 'static> SizedQueue<T> {
+    /// Returns `true` if the queue is empty.
+    pub(super) fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the sum of the sizes of the values in the queue.
+    pub(super) const fn total_size(&self) -> f64 {
+        self.total_size
+    }
+
+    /// Adds a value at the end of the queue.
+    pub(super) fn enqueue(&mut self, value: T, size: f64) {
+        self.values.push_back(value);
+        self.sizes.push_back(size);
+        self.total_size += size;
+    }
+
+    /// Removes the value at the front of the queue.
+    pub(super) fn dequeue(&mut self) -> Option<T> {
+        let value = self.values.pop_front()?;
+        let size = self.sizes.pop_front().unwrap_or_default();
+        // Rounding errors could make the total size slightly negative.
+        self.total_size = (self.total_size - size).max(0.0);
+        Some(value)
+    }
+
+    /// Returns the value at the front of the queue.
+    pub(super) fn peek(&self) -> Option<&T> {
+        self.values.front()
+    }
+
+    /// Removes all the values of the queue.
+    pub(super) fn reset(&mut self) {
+        self.values.clear();
+        self.sizes.clear();
+        self.total_size = 0.0;
+    }
+}
+
+/// Gets the required `highWaterMark` member of the argument of the queuing strategy
+/// constructors.
+fn high_water_mark_arg(args: &[JsValue], context: &mut Context) -> JsResult<f64> {
+    let high_water_mark = match args.get_or_undefined(0).as_object() {
+        Some(init) => init.get(js_string!("highWaterMark"), context)?,
+        None => JsValue::undefined(),
+    };
+    if high_water_mark.is_undefined() {
+        return Err(JsNativeError::typ()
+            .with_message("the highWaterMark member is required")
+            .into());
+    }
+    high_water_mark.to_number(context)
+}
+
+/// The `CountQueuingStrategy` class, which counts the chunks of a stream.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Streams` specification][spec]
+///
+/// [spec]: https://streams.spec.whatwg.org/#cqs-class
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/CountQueuingStrategy
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct CountQueuingStrategy {
+    high_water_mark: f64,
+}
+
+impl CountQueuingStrategy {
+    /// `get CountQueuingStrategy.prototype.highWaterMark`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#cqs-high-water-mark
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/CountQueuingStrategy/highWaterMark
+    fn get_high_water_mark(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let strategy = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message("'this' is not a CountQueuingStrategy object")
+            })?;
+        Ok(strategy.high_water_mark.into())
+    }
+
+    /// `CountQueuingStrategy.prototype.size(chunk)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#cqs-size
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/CountQueuingStrategy/size
+    #[allow(clippy::unnecessary_wraps)]
+    fn size(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(1.into())
+    }
+}
+
+impl Class for CountQueuingStrategy {
+    const NAME: &'static str = "CountQueuingStrategy";
+    const LENGTH: usize = 1;
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(
+            class,
+            js_string!("highWaterMark"),
+            Self::get_high_water_mark,
+        );
+        class
+            .method(
+                js_string!("size"),
+                0,
+                NativeFunction::from_fn_ptr(Self::size),
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("CountQueuingStrategy"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        Ok(Self {
+            high_water_mark: high_water_mark_arg(args, context)?,
+        })
+    }
+}
+
+/// The `ByteLengthQueuingStrategy` class, which counts the bytes of the chunks of a stream.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Streams` specification][spec]
+///
+/// [spec]: https://streams.spec.whatwg.org/#blqs-class
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ByteLengthQueuingStrategy
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct ByteLengthQueuingStrategy {
+    high_water_mark: f64,
+}
+
+impl ByteLengthQueuingStrategy {
+    /// `get ByteLengthQueuingStrategy.prototype.highWaterMark`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#blqs-high-water-mark
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ByteLengthQueuingStrategy/highWaterMark
+    fn get_high_water_mark(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let strategy = this
+            .as_object()
+            .and_then(JsObject::downcast_ref::<Self>)
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a ByteLengthQueuingStrategy object")
+            })?;
+        Ok(strategy.high_water_mark.into())
+    }
+
+    /// `ByteLengthQueuingStrategy.prototype.size(chunk)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#blqs-size
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/ByteLengthQueuingStrategy/size
+    fn size(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let chunk = args.get_or_undefined(0).to_object(context)?;
+        chunk.get(js_string!("byteLength"), context)
+    }
+}
+
+impl Class for ByteLengthQueuingStrategy {
+    const NAME: &'static str = "ByteLengthQueuingStrategy";
+    const LENGTH: usize = 1;
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(
+            class,
+            js_string!("highWaterMark"),
+            Self::get_high_water_mark,
+        );
+        class
+            .method(
+                js_string!("size"),
+                1,
+                NativeFunction::from_fn_ptr(Self::size),
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("ByteLengthQueuingStrategy"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        Ok(Self {
+            high_water_mark: high_water_mark_arg(args, context)?,
+        })
+    }
+}
@@ -0,0 +1,454 @@
+use super::ReadableStream;
+use crate::test::{run_test_actions, TestAction};
+use boa_engine::{js_string, Context, JsResult, JsString};
+use futures_core::Stream;
+use indoc::indoc;
+use std::{
+    collections::VecDeque,
+    future::poll_fn,
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+};
+
+/// A Rust stream yielding a fixed list of chunks.
+struct Chunks(VecDeque<Vec<u8>>);
+
+impl Stream for Chunks {
+    type Item = Vec<u8>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut TaskContext<'_>) -> Poll<Option<Vec<u8>>> {
+        Poll::Ready(self.0.pop_front())
+    }
+}
+
+#[test]
+fn readable_stream() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const log = [];
+            const stream = new ReadableStream({
+                start(controller) {
+                    controller.enqueue("a");
+                    controller.enqueue("b");
+                    log.push("desired:" + controller.desiredSize);
+                },
+                pull(controller) {
+                    log.push("pull");
+                    controller.enqueue("c");
+                    controller.close();
+                },
+            }, { highWaterMark: 2 });
+            const reader = stream.getReader();
+            const chunks = [];
+            (async () => {
+                while (true) {
+                    const { value, done } = await reader.read();
+                    if (done) break;
+                    chunks.push(value);
+                }
+                await reader.closed;
+                log.push("closed");
+            })();
+        "#}),
+        TestAction::assert_eq("stream.locked", true),
+        TestAction::assert_eq(
+            "try { stream.getReader() } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("chunks.join()", js_string!("a,b,c")),
+        TestAction::assert_eq("log.join()", js_string!("desired:0,pull,closed")),
+        TestAction::run("reader.releaseLock()"),
+        TestAction::assert_eq("stream.locked", false),
+        TestAction::assert_eq(
+            "try { new ReadableStream({ type: 'bytes' }) } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+        TestAction::assert_eq(
+            "try { new ReadableStreamDefaultController() } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+    ]);
+}
+
+#[test]
+fn readable_stream_error_and_cancel() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const results = {};
+            let errorController;
+            const errored = new ReadableStream({
+                start(controller) { errorController = controller; },
+            });
+            errored.getReader().read().catch((e) => { results.read = e; });
+            errorController.error("boom");
+
+            const canceled = new ReadableStream({
+                start(controller) { controller.enqueue(1); },
+                cancel(reason) { results.cancel = reason; },
+            });
+            canceled.cancel("enough").then((v) => { results.canceled = v; });
+
+            const throwing = new ReadableStream({
+                pull() { throw new Error("pull failed"); },
+            });
+            throwing.getReader().closed.catch((e) => { results.closed = e.message; });
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("results.read", js_string!("boom")),
+        TestAction::assert_eq("results.cancel", js_string!("enough")),
+        TestAction::assert_eq("results.canceled", boa_engine::JsValue::undefined()),
+        TestAction::assert_eq("results.closed", js_string!("pull failed")),
+    ]);
+}
+
+#[test]
+fn async_iteration() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            let i = 0;
+            const stream = new ReadableStream({
+                pull(controller) {
+                    if (i < 5) {
+                        controller.enqueue(i++);
+                    } else {
+                        controller.close();
+                    }
+                },
+            });
+            const values = [];
+            (async () => {
+                for await (const value of stream) {
+                    values.push(value);
+                }
+            })();
+
+            let canceled = false;
+            const infinite = new ReadableStream({
+                pull(controller) { controller.enqueue("x"); },
+                cancel() { canceled = true; },
+            });
+            let count = 0;
+            (async () => {
+                for await (const value of infinite.values()) {
+                    if (++count === 3) break;
+                }
+            })();
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("values.join()", js_string!("0,1,2,3,4")),
+        TestAction::assert_eq("stream.locked", false),
+        TestAction::assert_eq("count", 3),
+        TestAction::assert_eq("canceled", true),
+        TestAction::assert_eq("infinite.locked", false),
+    ]);
+}
+
+#[test]
+fn writable_stream() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const log = [];
+            const written = [];
+            let resolveWrite;
+            const stream = new WritableStream({
+                write(chunk, controller) {
+                    log.push("write:" + chunk);
+                    return new Promise((resolve) => { resolveWrite = () => {
+                        written.push(chunk);
+                        resolve();
+                    }; });
+                },
+                close() { log.push("close"); },
+            }, new CountQueuingStrategy({ highWaterMark: 2 }));
+            const writer = stream.getWriter();
+            log.push("desired:" + writer.desiredSize);
+            writer.write("a");
+            writer.write("b");
+            log.push("desired:" + writer.desiredSize);
+            writer.ready.then(() => log.push("ready"));
+            const closed = writer.close();
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq(
+            "log.join()",
+            js_string!("desired:2,desired:0,write:a,ready"),
+        ),
+        TestAction::run("resolveWrite()"),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::run("resolveWrite()"),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq(
+            "log.join()",
+            js_string!("desired:2,desired:0,write:a,ready,write:b,close"),
+        ),
+        TestAction::assert_eq("written.join()", js_string!("a,b")),
+        TestAction::assert_eq("writer.desiredSize", 0),
+        TestAction::run(indoc! {r#"
+            const results = {};
+            writer.write("c").catch((e) => { results.write = e.name; });
+            writer.releaseLock();
+            writer.closed.catch((e) => { results.closed = e.name; });
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("results.write", js_string!("TypeError")),
+        TestAction::assert_eq("results.closed", js_string!("TypeError")),
+    ]);
+}
+
+#[test]
+fn writable_stream_abort() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const results = {};
+            let signal;
+            const stream = new WritableStream({
+                start(controller) { signal = controller.signal; },
+                write() { return new Promise(() => {}); },
+                abort(reason) { results.abort = reason; },
+            });
+            const writer = stream.getWriter();
+            writer.write(1).catch((e) => { results.write = e; });
+            writer.write(2).catch((e) => { results.queued = e; });
+            writer.abort("stop").then(() => { results.aborted = true; });
+            writer.closed.catch((e) => { results.closed = e; });
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("signal.aborted", true),
+        TestAction::assert_eq("signal.reason", js_string!("stop")),
+        TestAction::assert_eq("results.queued", js_string!("stop")),
+        TestAction::assert_eq("results.write", js_string!("stop")),
+        TestAction::assert_eq("results.abort", js_string!("stop")),
+        TestAction::assert_eq("results.aborted", true),
+        TestAction::assert_eq("results.closed", js_string!("stop")),
+        TestAction::run(indoc! {r#"
+            const failing = new WritableStream({
+                write() { throw new Error("disk full"); },
+            });
+            const failingWriter = failing.getWriter();
+            failingWriter.write("x").catch((e) => { results.failed = e.message; });
+            failingWriter.ready.catch((e) => { results.ready = e.message; });
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("results.failed", js_string!("disk full")),
+        TestAction::assert_eq("results.ready", js_string!("disk full")),
+        TestAction::assert_eq(
+            "try { new WritableStream({ type: 'bytes' }) } catch (e) { e.name }",
+            js_string!("RangeError"),
+        ),
+    ]);
+}
+
+#[test]
+fn transform_stream() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const upper = new TransformStream({
+                transform(chunk, controller) {
+                    controller.enqueue(chunk.toUpperCase());
+                },
+                flush(controller) {
+                    controller.enqueue("!");
+                },
+            });
+            const source = new ReadableStream({
+                start(controller) {
+                    for (const word of ["hello", " ", "world"]) {
+                        controller.enqueue(word);
+                    }
+                    controller.close();
+                },
+            });
+            let text = "";
+            (async () => {
+                for await (const chunk of source.pipeThrough(upper)) {
+                    text += chunk;
+                }
+            })();
+
+            const identity = new TransformStream();
+            const identityWriter = identity.writable.getWriter();
+            identityWriter.write(1);
+            identityWriter.write(2);
+            identityWriter.close();
+            const identityChunks = [];
+            (async () => {
+                for await (const chunk of identity.readable) {
+                    identityChunks.push(chunk);
+                }
+            })();
+
+            const results = {};
+            const failing = new TransformStream({
+                transform() { throw new Error("bad chunk"); },
+            });
+            failing.writable.getWriter().write(1).catch((e) => { results.write = e.message; });
+            failing.readable.getReader().read().catch((e) => { results.read = e.message; });
+
+            const terminated = new TransformStream({
+                start(controller) { controller.terminate(); },
+            });
+            terminated.readable.getReader().read().then((r) => { results.done = r.done; });
+            terminated.writable.getWriter().closed.catch((e) => { results.closed = e.name; });
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("text", js_string!("HELLO WORLD!")),
+        TestAction::assert_eq("identityChunks.join()", js_string!("1,2")),
+        TestAction::assert_eq("results.write", js_string!("bad chunk")),
+        TestAction::assert_eq("results.read", js_string!("bad chunk")),
+        TestAction::assert_eq("results.done", true),
+        TestAction::assert_eq("results.closed", js_string!("TypeError")),
+        TestAction::assert_eq(
+            "try { new TransformStream({ readableType: 'bytes' }) } catch (e) { e.name }",
+            js_string!("RangeError"),
+        ),
+    ]);
+}
+
+#[test]
+fn pipe_to() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const results = {};
+            const written = [];
+            const source = new ReadableStream({
+                start(controller) {
+                    controller.enqueue(1);
+                    controller.enqueue(2);
+                    controller.enqueue(3);
+                    controller.close();
+                },
+            });
+            const destination = new WritableStream({
+                write(chunk) { written.push(chunk); },
+                close() { results.closed = true; },
+            }, { highWaterMark: 1 });
+            source.pipeTo(destination).then(() => { results.piped = true; });
+
+            const erroring = new ReadableStream({
+                start(controller) {
+                    controller.enqueue("x");
+                    controller.error("source failed");
+                },
+            });
+            const aborted = new WritableStream({
+                abort(reason) { results.abort = reason; },
+            });
+            erroring.pipeTo(aborted).catch((e) => { results.pipeError = e; });
+
+            const controller = new AbortController();
+            let canceled;
+            const idle = new ReadableStream({ cancel(reason) { canceled = reason; } });
+            const sink = new WritableStream();
+            idle.pipeTo(sink, { signal: controller.signal })
+                .catch((e) => { results.signal = e; });
+            controller.abort("user");
+
+            const kept = new WritableStream();
+            new ReadableStream({ start(c) { c.close(); } })
+                .pipeTo(kept, { preventClose: true })
+                .then(() => { results.kept = kept.locked; });
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("written.join()", js_string!("1,2,3")),
+        TestAction::assert_eq("results.closed", true),
+        TestAction::assert_eq("results.piped", true),
+        TestAction::assert_eq("source.locked || destination.locked", false),
+        TestAction::assert_eq("results.abort", js_string!("source failed")),
+        TestAction::assert_eq("results.pipeError", js_string!("source failed")),
+        TestAction::assert_eq("results.signal", js_string!("user")),
+        TestAction::assert_eq("canceled", js_string!("user")),
+        TestAction::assert_eq("results.kept", false),
+        TestAction::assert_eq(
+            "source.pipeTo({}).catch((e) => { results.invalid = e.name; }); 0",
+            0,
+        ),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("results.invalid", js_string!("TypeError")),
+    ]);
+}
+
+#[test]
+fn queuing_strategies() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const count = new CountQueuingStrategy({ highWaterMark: 3 });
+            const bytes = new ByteLengthQueuingStrategy({ highWaterMark: 16 });
+            let controller;
+            new ReadableStream({ start(c) { controller = c; } }, bytes);
+            controller.enqueue(new Uint8Array(10));
+        "#}),
+        TestAction::assert_eq("count.highWaterMark", 3),
+        TestAction::assert_eq("count.size('anything')", 1),
+        TestAction::assert_eq("bytes.size(new ArrayBuffer(7))", 7),
+        TestAction::assert_eq("controller.desiredSize", 6),
+        TestAction::assert_eq(
+            "Object.prototype.toString.call(count)",
+            js_string!("[object CountQueuingStrategy]"),
+        ),
+        TestAction::assert_eq(
+            "try { new CountQueuingStrategy() } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+        TestAction::assert_eq(
+            "try { new ReadableStream({}, { highWaterMark: -1 }) } catch (e) { e.name }",
+            js_string!("RangeError"),
+        ),
+        TestAction::assert_eq(
+            indoc! {r#"
+                try {
+                    new ReadableStream({ start(c) { c.enqueue(1); } }, { size: () => NaN });
+                } catch (e) { e.name }
+            "#},
+            js_string!("RangeError"),
+        ),
+    ]);
+}
+
+/// Reads the global `ReadableStream` named `name` through [`ReadableStream::into_byte_stream`].
+fn read_bytes(name: &str, context: &mut Context) -> JsResult<Vec<u8>> {
+    let stream = context.global_object().get(JsString::from(name), context)?;
+    let stream = stream
+        .as_object()
+        .expect("must be a ReadableStream")
+        .clone();
+    let mut bytes = ReadableStream::into_byte_stream(&stream, context)?;
+    context.run_jobs();
+
+    pollster::block_on(async {
+        let mut output = Vec::new();
+        while let Some(chunk) = poll_fn(|cx| Pin::new(&mut bytes).poll_next(cx)).await {
+            output.extend(chunk?);
+        }
+        Ok(output)
+    })
+}
+
+#[test]
+fn byte_stream_adapters() {
+    run_test_actions([
+        TestAction::inspect_context(|context| {
+            let chunks = Chunks(VecDeque::from([b"hello ".to_vec(), b"world".to_vec()]));
+            let stream = ReadableStream::from_byte_stream(chunks, context).unwrap();
+            context
+                .global_object()
+                .set(js_string!("source"), stream, false, context)
+                .unwrap();
+        }),
+        TestAction::run(indoc! {r#"
+            const decoder = new TextDecoder();
+            const encoder = new TextEncoder();
+            var upper = source.pipeThrough(new TransformStream({
+                transform(chunk, controller) {
+                    const text = decoder.decode(chunk).toUpperCase();
+                    controller.enqueue(encoder.encode(text));
+                },
+            }));
+            var invalid = new ReadableStream({ start(c) { c.enqueue("text"); } });
+        "#}),
+        TestAction::assert_context(|context| {
+            read_bytes("upper", context).is_ok_and(|output| output == b"HELLO WORLD")
+        }),
+        TestAction::assert_context(|context| read_bytes("invalid", context).is_err()),
+    ]);
+}
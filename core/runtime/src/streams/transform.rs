@@ -0,0 +1,614 @@
+//! Boa's implementation of the `TransformStream` object, and of its default controller.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `Streams` specification][spec]
+//!
+//! [spec]: https://streams.spec.whatwg.org/#ts-model
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TransformStream
+
+use super::{
+    create_instance,
+    promise::{check_dictionary, throw, to_promise, upon, Algorithm, Deferred},
+    readable::{ReadableState, ReadableStream, ReadableStreamDefaultController},
+    strategy::QueuingStrategy,
+    writable::{WritableState, WritableStream, WritableStreamDefaultController},
+};
+use crate::util::add_getter;
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::{builtins::JsPromise, ErasedObject, JsObject, RefMut},
+    property::Attribute,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsSymbol, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+
+/// The `TransformStream` class, a pair of a `WritableStream` and a `ReadableStream` where the
+/// chunks written to the first are transformed and read from the second.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Streams` specification][spec]
+///
+/// [spec]: https://streams.spec.whatwg.org/#ts-class
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TransformStream
+#[derive(Debug, Clone, Default, Trace, Finalize, JsData)]
+pub struct TransformStream {
+    readable: Option<JsObject>,
+    writable: Option<JsObject>,
+    backpressure: bool,
+    /// The promise resolved when the backpressure changes.
+    backpressure_change: Option<Deferred>,
+    controller: Option<JsObject>,
+}
+
+impl TransformStream {
+    /// Borrows the data of a `TransformStream` object.
+    fn data(stream: &JsObject) -> RefMut<'_, ErasedObject, Self> {
+        stream
+            .downcast_mut::<Self>()
+            .expect("must be a TransformStream")
+    }
+
+    /// Returns the readable side of the stream.
+    fn readable(stream: &JsObject) -> JsObject {
+        Self::data(stream)
+            .readable
+            .clone()
+            .expect("the stream must be initialized")
+    }
+
+    /// Returns the writable side of the stream.
+    fn writable(stream: &JsObject) -> JsObject {
+        Self::data(stream)
+            .writable
+            .clone()
+            .expect("the stream must be initialized")
+    }
+
+    /// Returns the controller of the stream.
+    fn controller(stream: &JsObject) -> JsObject {
+        Self::data(stream)
+            .controller
+            .clone()
+            .expect("the controller must be set up")
+    }
+
+    /// [Initializes][spec] the readable and writable sides of the stream, which are started
+    /// when `start` settles.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#initialize-transform-stream
+    fn initialize(
+        stream: &JsObject,
+        start: &JsPromise,
+        writable_strategy: QueuingStrategy,
+        readable_strategy: QueuingStrategy,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let start = Algorithm::native(
+            NativeFunction::from_copy_closure_with_captures(
+                |_, _, start, _| Ok(start.clone().into()),
+                start.clone(),
+            ),
+            context,
+        );
+        let write = Algorithm::native(
+            NativeFunction::from_copy_closure_with_captures(
+                |_, args, stream, context| {
+                    Ok(Self::sink_write(stream, args.get_or_undefined(0).clone(), context).into())
+                },
+                stream.clone(),
+            ),
+            context,
+        );
+        let close = Algorithm::native(
+            NativeFunction::from_copy_closure_with_captures(
+                |_, _, stream, context| Ok(Self::sink_close(stream, context).into()),
+                stream.clone(),
+            ),
+            context,
+        );
+        let abort = Algorithm::native(
+            NativeFunction::from_copy_closure_with_captures(
+                |_, args, stream, context| {
+                    Self::error(stream, args.get_or_undefined(0), context);
+                    Ok(JsPromise::resolve(JsValue::undefined(), context).into())
+                },
+                stream.clone(),
+            ),
+            context,
+        );
+        let writable = WritableStream::create(
+            Some(start.clone()),
+            Some(write),
+            Some(close),
+            Some(abort),
+            writable_strategy,
+            context,
+        )?;
+
+        let pull = Algorithm::native(
+            NativeFunction::from_copy_closure_with_captures(
+                |_, _, stream, context| Ok(Self::source_pull(stream, context).into()),
+                stream.clone(),
+            ),
+            context,
+        );
+        let cancel = Algorithm::native(
+            NativeFunction::from_copy_closure_with_captures(
+                |_, args, stream, context| {
+                    Self::error_writable_and_unblock_write(
+                        stream,
+                        args.get_or_undefined(0).clone(),
+                        context,
+                    );
+                    Ok(JsPromise::resolve(JsValue::undefined(), context).into())
+                },
+                stream.clone(),
+            ),
+            context,
+        );
+        let readable = ReadableStream::create(
+            Some(start),
+            Some(pull),
+            Some(cancel),
+            readable_strategy,
+            context,
+        )?;
+
+        {
+            let mut data = Self::data(stream);
+            data.readable = Some(readable);
+            data.writable = Some(writable);
+        }
+        Self::set_backpressure(stream, true, context);
+        Ok(())
+    }
+
+    /// Errors both sides of the stream.
+    fn error(stream: &JsObject, error: &JsValue, context: &mut Context) {
+        let readable = Self::readable(stream);
+        ReadableStreamDefaultController::error(
+            &ReadableStream::controller(&readable),
+            error,
+            context,
+        );
+        Self::error_writable_and_unblock_write(stream, error.clone(), context);
+    }
+
+    /// Errors the writable side of the stream, resolving any pending write waiting for the
+    /// backpressure to be relieved.
+    fn error_writable_and_unblock_write(stream: &JsObject, error: JsValue, context: &mut Context) {
+        let (writable, backpressure) = {
+            let data = Self::data(stream);
+            (
+                data.writable
+                    .clone()
+                    .expect("the stream must be initialized"),
+                data.backpressure,
+            )
+        };
+        WritableStreamDefaultController::error_if_needed(
+            &WritableStream::controller(&writable),
+            error,
+            context,
+        );
+        if backpressure {
+            Self::set_backpressure(stream, false, context);
+        }
+    }
+
+    /// Sets the backpressure of the stream, resolving the promise of the previous backpressure.
+    fn set_backpressure(stream: &JsObject, backpressure: bool, context: &mut Context) {
+        let previous = Self::data(stream).backpressure_change.take();
+        if let Some(previous) = previous {
+            previous.resolve(JsValue::undefined(), context);
+        }
+        let mut data = Self::data(stream);
+        data.backpressure_change = Some(Deferred::new(context));
+        data.backpressure = backpressure;
+    }
+
+    /// The write algorithm of the writable side, which waits for the backpressure to be
+    /// relieved before transforming `chunk`.
+    fn sink_write(stream: &JsObject, chunk: JsValue, context: &mut Context) -> JsPromise {
+        let (backpressure, change, controller) = {
+            let data = Self::data(stream);
+            (
+                data.backpressure,
+                data.backpressure_change.clone(),
+                data.controller
+                    .clone()
+                    .expect("the controller must be set up"),
+            )
+        };
+        let Some(change) = change.filter(|_| backpressure) else {
+            return TransformStreamDefaultController::perform_transform(
+                &controller,
+                chunk,
+                context,
+            );
+        };
+        upon(
+            &change.promise(),
+            &(stream.clone(), chunk),
+            Some(|_, (stream, chunk), context| {
+                let writable = Self::writable(stream);
+                if WritableStream::state(&writable) == WritableState::Erroring {
+                    return Err(throw(&WritableStream::stored_error(&writable)));
+                }
+                let controller = Self::controller(stream);
+                Ok(TransformStreamDefaultController::perform_transform(
+                    &controller,
+                    chunk.clone(),
+                    context,
+                )
+                .into())
+            }),
+            None,
+            context,
+        )
+    }
+
+    /// The close algorithm of the writable side, which flushes the transformer and closes the
+    /// readable side.
+    fn sink_close(stream: &JsObject, context: &mut Context) -> JsPromise {
+        let flush = TransformStreamDefaultController::data(&Self::controller(stream))
+            .flush
+            .clone();
+        let flush =
+            Algorithm::call_promise(flush.as_ref(), &[Self::controller(stream).into()], context);
+        upon(
+            &flush,
+            stream,
+            Some(|_, stream, context| {
+                let readable = Self::readable(stream);
+                if ReadableStream::state(&readable) == ReadableState::Errored {
+                    return Err(throw(&ReadableStream::stored_error(&readable)));
+                }
+                ReadableStreamDefaultController::close(
+                    &ReadableStream::controller(&readable),
+                    context,
+                );
+                Ok(JsValue::undefined())
+            }),
+            Some(|reason, stream, context| {
+                Self::error(stream, reason, context);
+                Err(throw(&ReadableStream::stored_error(&Self::readable(
+                    stream,
+                ))))
+            }),
+            context,
+        )
+    }
+
+    /// The pull algorithm of the readable side, which relieves the backpressure.
+    fn source_pull(stream: &JsObject, context: &mut Context) -> JsPromise {
+        Self::set_backpressure(stream, false, context);
+        Self::data(stream)
+            .backpressure_change
+            .as_ref()
+            .expect("the backpressure was just set")
+            .promise()
+    }
+
+    /// Checks that `this` is a `TransformStream` object, returning it.
+    fn this_stream(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a TransformStream object")
+                    .into()
+            })
+    }
+
+    /// `get TransformStream.prototype.readable`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#ts-readable
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TransformStream/readable
+    fn get_readable(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::readable(Self::this_stream(this)?).into())
+    }
+
+    /// `get TransformStream.prototype.writable`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#ts-writable
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TransformStream/writable
+    fn get_writable(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::writable(Self::this_stream(this)?).into())
+    }
+}
+
+impl Class for TransformStream {
+    const NAME: &'static str = "TransformStream";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(class, js_string!("readable"), Self::get_readable);
+        add_getter(class, js_string!("writable"), Self::get_writable);
+        class.property(
+            JsSymbol::to_string_tag(),
+            js_string!("TransformStream"),
+            Attribute::CONFIGURABLE,
+        );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<Self> {
+        Ok(Self::default())
+    }
+
+    fn object_constructor(
+        instance: &JsObject,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let transformer = args.get_or_undefined(0);
+        check_dictionary(transformer, "transformer")?;
+        let flush = Algorithm::from_method(transformer, &js_string!("flush"), context)?;
+        if let Some(object) = transformer.as_object() {
+            if !object
+                .get(js_string!("readableType"), context)?
+                .is_undefined()
+            {
+                return Err(JsNativeError::range()
+                    .with_message("the readableType of the transformer must be undefined")
+                    .into());
+            }
+        }
+        let start = Algorithm::from_method(transformer, &js_string!("start"), context)?;
+        let transform = Algorithm::from_method(transformer, &js_string!("transform"), context)?;
+        if let Some(object) = transformer.as_object() {
+            if !object
+                .get(js_string!("writableType"), context)?
+                .is_undefined()
+            {
+                return Err(JsNativeError::range()
+                    .with_message("the writableType of the transformer must be undefined")
+                    .into());
+            }
+        }
+        let writable_strategy = QueuingStrategy::extract(args.get_or_undefined(1), 1.0, context)?;
+        let readable_strategy = QueuingStrategy::extract(args.get_or_undefined(2), 0.0, context)?;
+
+        let start_promise = Deferred::new(context);
+        Self::initialize(
+            instance,
+            &start_promise.promise(),
+            writable_strategy,
+            readable_strategy,
+            context,
+        )?;
+        let controller = create_instance(
+            TransformStreamDefaultController {
+                stream: instance.clone(),
+                transform,
+                flush,
+            },
+            context,
+        )?;
+        Self::data(instance).controller = Some(controller.clone());
+
+        let start_result = match start {
+            Some(start) => start.call(&[controller.into()], context)?,
+            None => JsValue::undefined(),
+        };
+        start_promise.resolve(start_result, context);
+        Ok(())
+    }
+}
+
+/// The `TransformStreamDefaultController` class, which enqueues the transformed chunks in the
+/// readable side of a `TransformStream`.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Streams` specification][spec]
+///
+/// [spec]: https://streams.spec.whatwg.org/#ts-default-controller-class
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TransformStreamDefaultController
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct TransformStreamDefaultController {
+    stream: JsObject,
+    /// The transform algorithm, which enqueues the chunks unchanged if there's no algorithm.
+    transform: Option<Algorithm>,
+    flush: Option<Algorithm>,
+}
+
+impl TransformStreamDefaultController {
+    /// Borrows the data of a `TransformStreamDefaultController` object.
+    fn data(controller: &JsObject) -> RefMut<'_, ErasedObject, Self> {
+        controller
+            .downcast_mut::<Self>()
+            .expect("must be a TransformStreamDefaultController")
+    }
+
+    /// Returns the controller of the readable side of the stream.
+    fn readable_controller(controller: &JsObject) -> JsObject {
+        let stream = Self::data(controller).stream.clone();
+        ReadableStream::controller(&TransformStream::readable(&stream))
+    }
+
+    /// [Enqueues][spec] `chunk` in the readable side of the stream.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#transform-stream-default-controller-enqueue
+    fn enqueue(controller: &JsObject, chunk: JsValue, context: &mut Context) -> JsResult<()> {
+        let stream = Self::data(controller).stream.clone();
+        let readable_controller = Self::readable_controller(controller);
+        if !ReadableStreamDefaultController::can_close_or_enqueue(&readable_controller) {
+            return Err(JsNativeError::typ()
+                .with_message("the readable side is not readable")
+                .into());
+        }
+        if let Err(e) =
+            ReadableStreamDefaultController::enqueue(&readable_controller, chunk, context)
+        {
+            let error = e.to_opaque(context);
+            TransformStream::error_writable_and_unblock_write(&stream, error, context);
+            let readable = TransformStream::readable(&stream);
+            return Err(throw(&ReadableStream::stored_error(&readable)));
+        }
+        let backpressure = ReadableStreamDefaultController::has_backpressure(&readable_controller);
+        if backpressure != TransformStream::data(&stream).backpressure {
+            TransformStream::set_backpressure(&stream, true, context);
+        }
+        Ok(())
+    }
+
+    /// [Terminates][spec] the stream, closing its readable side and erroring its writable side.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#transform-stream-default-controller-terminate
+    fn terminate(controller: &JsObject, context: &mut Context) {
+        let stream = Self::data(controller).stream.clone();
+        ReadableStreamDefaultController::close(&Self::readable_controller(controller), context);
+        let error = JsNativeError::typ()
+            .with_message("the stream was terminated")
+            .to_opaque(context);
+        TransformStream::error_writable_and_unblock_write(&stream, error.into(), context);
+    }
+
+    /// Transforms `chunk`, erroring the stream if the transform algorithm fails.
+    fn perform_transform(
+        controller: &JsObject,
+        chunk: JsValue,
+        context: &mut Context,
+    ) -> JsPromise {
+        let transform = Self::data(controller).transform.clone();
+        let result = match transform {
+            Some(transform) => transform.call(&[chunk, controller.clone().into()], context),
+            None => Self::enqueue(controller, chunk, context).map(|()| JsValue::undefined()),
+        };
+        let transform = to_promise(result, context);
+        let stream = Self::data(controller).stream.clone();
+        upon(
+            &transform,
+            &stream,
+            Some(|_, _, _| Ok(JsValue::undefined())),
+            Some(|reason, stream, context| {
+                TransformStream::error(stream, reason, context);
+                Err(throw(reason))
+            }),
+            context,
+        )
+    }
+
+    /// Checks that `this` is a `TransformStreamDefaultController` object, returning it.
+    fn this_controller(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a TransformStreamDefaultController object")
+                    .into()
+            })
+    }
+
+    /// `get TransformStreamDefaultController.prototype.desiredSize`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#ts-default-controller-desired-size
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TransformStreamDefaultController/desiredSize
+    fn get_desired_size(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let controller = Self::this_controller(this)?;
+        let readable_controller = Self::readable_controller(controller);
+        Ok(
+            ReadableStreamDefaultController::desired_size(&readable_controller)
+                .map_or_else(JsValue::null, Into::into),
+        )
+    }
+
+    /// `TransformStreamDefaultController.prototype.enqueue(chunk)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#ts-default-controller-enqueue
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TransformStreamDefaultController/enqueue
+    fn enqueue_method(
+        this: &JsValue,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let controller = Self::this_controller(this)?;
+        Self::enqueue(controller, args.get_or_undefined(0).clone(), context)?;
+        Ok(JsValue::undefined())
+    }
+
+    /// `TransformStreamDefaultController.prototype.error(reason)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#ts-default-controller-error
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TransformStreamDefaultController/error
+    fn error_method(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let controller = Self::this_controller(this)?;
+        let stream = Self::data(controller).stream.clone();
+        TransformStream::error(&stream, args.get_or_undefined(0), context);
+        Ok(JsValue::undefined())
+    }
+
+    /// `TransformStreamDefaultController.prototype.terminate()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#ts-default-controller-terminate
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TransformStreamDefaultController/terminate
+    fn terminate_method(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::terminate(Self::this_controller(this)?, context);
+        Ok(JsValue::undefined())
+    }
+}
+
+impl Class for TransformStreamDefaultController {
+    const NAME: &'static str = "TransformStreamDefaultController";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(class, js_string!("desiredSize"), Self::get_desired_size);
+        class
+            .method(
+                js_string!("enqueue"),
+                0,
+                NativeFunction::from_fn_ptr(Self::enqueue_method),
+            )
+            .method(
+                js_string!("error"),
+                0,
+                NativeFunction::from_fn_ptr(Self::error_method),
+            )
+            .method(
+                js_string!("terminate"),
+                0,
+                NativeFunction::from_fn_ptr(Self::terminate_method),
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("TransformStreamDefaultController"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<Self> {
+        Err(JsNativeError::typ()
+            .with_message("TransformStreamDefaultController cannot be constructed directly")
+            .into())
+    }
+}
@@ -0,0 +1,1243 @@
+//! Boa's implementation of the `WritableStream` object, and of its default writer and
+//! controller.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `Streams` specification][spec]
+//!
+//! [spec]: https://streams.spec.whatwg.org/#ws-model
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStream
+
+use super::{
+    create_instance,
+    promise::{check_dictionary, rejected_type_error, throw, upon, Algorithm, Deferred},
+    strategy::{QueuingStrategy, SizedQueue},
+};
+use crate::{util::add_getter, AbortSignal};
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::{builtins::JsPromise, ErasedObject, JsObject, RefMut},
+    property::Attribute,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsSymbol, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::collections::VecDeque;
+
+/// The state of a `WritableStream`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum WritableState {
+    Writable,
+    Closed,
+    Erroring,
+    Errored,
+}
+
+/// A pending call to [`WritableStream::abort`], waiting for the in-flight operations to finish.
+#[derive(Debug, Clone, Trace, Finalize)]
+struct PendingAbortRequest {
+    promise: Deferred,
+    reason: JsValue,
+    was_already_erroring: bool,
+}
+
+/// The `WritableStream` class, a destination for data that is written in chunks.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Streams` specification][spec]
+///
+/// [spec]: https://streams.spec.whatwg.org/#ws-class
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStream
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct WritableStream {
+    #[unsafe_ignore_trace]
+    state: WritableState,
+    stored_error: JsValue,
+    writer: Option<JsObject>,
+    controller: Option<JsObject>,
+    in_flight_write_request: Option<Deferred>,
+    close_request: Option<Deferred>,
+    in_flight_close_request: Option<Deferred>,
+    pending_abort_request: Option<PendingAbortRequest>,
+    write_requests: VecDeque<Deferred>,
+    backpressure: bool,
+}
+
+impl Default for WritableStream {
+    fn default() -> Self {
+        Self {
+            state: WritableState::Writable,
+            stored_error: JsValue::undefined(),
+            writer: None,
+            controller: None,
+            in_flight_write_request: None,
+            close_request: None,
+            in_flight_close_request: None,
+            pending_abort_request: None,
+            write_requests: VecDeque::new(),
+            backpressure: false,
+        }
+    }
+}
+
+impl WritableStream {
+    /// Borrows the data of a `WritableStream` object.
+    fn data(stream: &JsObject) -> RefMut<'_, ErasedObject, Self> {
+        stream
+            .downcast_mut::<Self>()
+            .expect("must be a WritableStream")
+    }
+
+    /// Creates a `WritableStream` with a default controller.
+    pub(super) fn create(
+        start: Option<Algorithm>,
+        write: Option<Algorithm>,
+        close: Option<Algorithm>,
+        abort: Option<Algorithm>,
+        strategy: QueuingStrategy,
+        context: &mut Context,
+    ) -> JsResult<JsObject> {
+        let stream = create_instance(Self::default(), context)?;
+        WritableStreamDefaultController::setup(
+            &stream, start, write, close, abort, strategy, context,
+        )?;
+        Ok(stream)
+    }
+
+    /// Returns `true` if the stream has a writer.
+    pub(super) fn is_locked(stream: &JsObject) -> bool {
+        Self::data(stream).writer.is_some()
+    }
+
+    /// Returns the state of the stream.
+    pub(super) fn state(stream: &JsObject) -> WritableState {
+        Self::data(stream).state
+    }
+
+    /// Returns the error of an erroring or errored stream.
+    pub(super) fn stored_error(stream: &JsObject) -> JsValue {
+        Self::data(stream).stored_error.clone()
+    }
+
+    /// Returns the controller of the stream.
+    pub(super) fn controller(stream: &JsObject) -> JsObject {
+        Self::data(stream)
+            .controller
+            .clone()
+            .expect("the controller must be set up")
+    }
+
+    /// Returns `true` if the stream was closed by its writer, even if the sink was not closed
+    /// yet.
+    pub(super) fn close_queued_or_in_flight(stream: &JsObject) -> bool {
+        let data = Self::data(stream);
+        data.close_request.is_some() || data.in_flight_close_request.is_some()
+    }
+
+    /// Returns `true` if the sink is currently writing a chunk or closing.
+    fn has_operation_marked_in_flight(stream: &JsObject) -> bool {
+        let data = Self::data(stream);
+        data.in_flight_write_request.is_some() || data.in_flight_close_request.is_some()
+    }
+
+    /// [Aborts][spec] the stream, returning a promise resolved when the sink was aborted.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#writable-stream-abort
+    pub(super) fn abort(stream: &JsObject, reason: JsValue, context: &mut Context) -> JsPromise {
+        if matches!(
+            Self::state(stream),
+            WritableState::Closed | WritableState::Errored
+        ) {
+            return JsPromise::resolve(JsValue::undefined(), context);
+        }
+        let signal = WritableStreamDefaultController::data(&Self::controller(stream))
+            .signal
+            .clone();
+        if let Some(signal) = signal {
+            if let Err(e) = AbortSignal::abort(&signal, reason.clone(), context) {
+                return JsPromise::reject(e, context);
+            }
+        }
+
+        // Aborting the signal could have run code erroring or closing the stream.
+        let state = Self::state(stream);
+        if matches!(state, WritableState::Closed | WritableState::Errored) {
+            return JsPromise::resolve(JsValue::undefined(), context);
+        }
+        if let Some(request) = &Self::data(stream).pending_abort_request {
+            return request.promise.promise();
+        }
+        let was_already_erroring = state == WritableState::Erroring;
+        let reason = if was_already_erroring {
+            JsValue::undefined()
+        } else {
+            reason
+        };
+        let deferred = Deferred::new(context);
+        let promise = deferred.promise();
+        Self::data(stream).pending_abort_request = Some(PendingAbortRequest {
+            promise: deferred,
+            reason: reason.clone(),
+            was_already_erroring,
+        });
+        if !was_already_erroring {
+            Self::start_erroring(stream, reason, context);
+        }
+        promise
+    }
+
+    /// [Closes][spec] the stream once all of its queued chunks have been written.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#writable-stream-close
+    pub(super) fn close(stream: &JsObject, context: &mut Context) -> JsPromise {
+        if matches!(
+            Self::state(stream),
+            WritableState::Closed | WritableState::Errored
+        ) {
+            return rejected_type_error("the stream is already closed or errored", context);
+        }
+        let deferred = Deferred::new(context);
+        let promise = deferred.promise();
+        let (writer, ready) = {
+            let mut data = Self::data(stream);
+            data.close_request = Some(deferred);
+            let ready = data.backpressure && data.state == WritableState::Writable;
+            (data.writer.clone(), ready)
+        };
+        if let Some(writer) = writer.filter(|_| ready) {
+            WritableStreamDefaultWriter::ready_deferred(&writer)
+                .resolve(JsValue::undefined(), context);
+        }
+        WritableStreamDefaultController::close(&Self::controller(stream), context);
+        promise
+    }
+
+    /// Handles an error of the sink.
+    fn deal_with_rejection(stream: &JsObject, error: JsValue, context: &mut Context) {
+        if Self::state(stream) == WritableState::Writable {
+            Self::start_erroring(stream, error, context);
+            return;
+        }
+        Self::finish_erroring(stream, context);
+    }
+
+    /// Starts erroring the stream, which will be errored once the in-flight operations finish.
+    fn start_erroring(stream: &JsObject, reason: JsValue, context: &mut Context) {
+        let (controller, writer) = {
+            let mut data = Self::data(stream);
+            data.state = WritableState::Erroring;
+            data.stored_error = reason.clone();
+            (
+                data.controller
+                    .clone()
+                    .expect("the controller must be set up"),
+                data.writer.clone(),
+            )
+        };
+        if let Some(writer) = writer {
+            WritableStreamDefaultWriter::ensure_ready_rejected(&writer, reason, context);
+        }
+        let started = WritableStreamDefaultController::data(&controller).started;
+        if !Self::has_operation_marked_in_flight(stream) && started {
+            Self::finish_erroring(stream, context);
+        }
+    }
+
+    /// Errors the stream, rejecting its pending write requests and running the pending abort.
+    fn finish_erroring(stream: &JsObject, context: &mut Context) {
+        let (stored_error, requests, abort_request, controller) = {
+            let mut data = Self::data(stream);
+            data.state = WritableState::Errored;
+            (
+                data.stored_error.clone(),
+                std::mem::take(&mut data.write_requests),
+                data.pending_abort_request.take(),
+                data.controller
+                    .clone()
+                    .expect("the controller must be set up"),
+            )
+        };
+        WritableStreamDefaultController::data(&controller)
+            .queue
+            .reset();
+        for request in requests {
+            request.reject(stored_error.clone(), context);
+        }
+
+        let Some(abort_request) = abort_request else {
+            Self::reject_close_and_closed_promise_if_needed(stream, context);
+            return;
+        };
+        if abort_request.was_already_erroring {
+            abort_request.promise.reject(stored_error, context);
+            Self::reject_close_and_closed_promise_if_needed(stream, context);
+            return;
+        }
+        let sink_abort = WritableStreamDefaultController::abort_steps(
+            &controller,
+            abort_request.reason.clone(),
+            context,
+        );
+        upon(
+            &sink_abort,
+            &(stream.clone(), abort_request.promise.clone()),
+            Some(|_, (stream, promise), context| {
+                promise.resolve(JsValue::undefined(), context);
+                Self::reject_close_and_closed_promise_if_needed(stream, context);
+                Ok(JsValue::undefined())
+            }),
+            Some(|reason, (stream, promise), context| {
+                promise.reject(reason.clone(), context);
+                Self::reject_close_and_closed_promise_if_needed(stream, context);
+                Ok(JsValue::undefined())
+            }),
+            context,
+        );
+    }
+
+    /// Marks the close request as successful, closing the stream.
+    fn finish_in_flight_close(stream: &JsObject, context: &mut Context) {
+        let (request, abort_request, writer) = {
+            let mut data = Self::data(stream);
+            let request = data.in_flight_close_request.take();
+            let abort_request = if data.state == WritableState::Erroring {
+                data.stored_error = JsValue::undefined();
+                data.pending_abort_request.take()
+            } else {
+                None
+            };
+            data.state = WritableState::Closed;
+            (request, abort_request, data.writer.clone())
+        };
+        if let Some(request) = request {
+            request.resolve(JsValue::undefined(), context);
+        }
+        if let Some(abort_request) = abort_request {
+            abort_request.promise.resolve(JsValue::undefined(), context);
+        }
+        if let Some(writer) = writer {
+            WritableStreamDefaultWriter::closed_deferred(&writer)
+                .resolve(JsValue::undefined(), context);
+        }
+    }
+
+    /// Marks the close request as failed with `error`, erroring the stream.
+    fn finish_in_flight_close_with_error(stream: &JsObject, error: JsValue, context: &mut Context) {
+        let (request, abort_request) = {
+            let mut data = Self::data(stream);
+            (
+                data.in_flight_close_request.take(),
+                data.pending_abort_request.take(),
+            )
+        };
+        if let Some(request) = request {
+            request.reject(error.clone(), context);
+        }
+        if let Some(abort_request) = abort_request {
+            abort_request.promise.reject(error.clone(), context);
+        }
+        Self::deal_with_rejection(stream, error, context);
+    }
+
+    /// Marks the in-flight write request as successful.
+    fn finish_in_flight_write(stream: &JsObject, context: &mut Context) {
+        let request = Self::data(stream).in_flight_write_request.take();
+        if let Some(request) = request {
+            request.resolve(JsValue::undefined(), context);
+        }
+    }
+
+    /// Marks the in-flight write request as failed with `error`, erroring the stream.
+    fn finish_in_flight_write_with_error(stream: &JsObject, error: JsValue, context: &mut Context) {
+        let request = Self::data(stream).in_flight_write_request.take();
+        if let Some(request) = request {
+            request.reject(error.clone(), context);
+        }
+        Self::deal_with_rejection(stream, error, context);
+    }
+
+    /// Rejects the close request and the `closed` promise of the writer of an errored stream.
+    fn reject_close_and_closed_promise_if_needed(stream: &JsObject, context: &mut Context) {
+        let (stored_error, request, writer) = {
+            let mut data = Self::data(stream);
+            (
+                data.stored_error.clone(),
+                data.close_request.take(),
+                data.writer.clone(),
+            )
+        };
+        if let Some(request) = request {
+            request.reject(stored_error.clone(), context);
+        }
+        if let Some(writer) = writer {
+            WritableStreamDefaultWriter::closed_deferred(&writer).reject(stored_error, context);
+        }
+    }
+
+    /// Updates the backpressure of the stream, replacing the `ready` promise of its writer.
+    fn update_backpressure(stream: &JsObject, backpressure: bool, context: &mut Context) {
+        let (writer, changed) = {
+            let data = Self::data(stream);
+            (data.writer.clone(), data.backpressure != backpressure)
+        };
+        if let Some(writer) = writer.filter(|_| changed) {
+            if backpressure {
+                WritableStreamDefaultWriter::data(&writer).ready = Some(Deferred::new(context));
+            } else {
+                WritableStreamDefaultWriter::ready_deferred(&writer)
+                    .resolve(JsValue::undefined(), context);
+            }
+        }
+        Self::data(stream).backpressure = backpressure;
+    }
+
+    /// Checks that `this` is a `WritableStream` object, returning it.
+    fn this_stream(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a WritableStream object")
+                    .into()
+            })
+    }
+
+    /// `get WritableStream.prototype.locked`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#ws-locked
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStream/locked
+    fn get_locked(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Ok(Self::is_locked(Self::this_stream(this)?).into())
+    }
+
+    /// `WritableStream.prototype.abort(reason)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#ws-abort
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStream/abort
+    #[allow(clippy::unnecessary_wraps)]
+    fn abort_method(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let stream = match Self::this_stream(this) {
+            Ok(stream) => stream,
+            Err(e) => return Ok(JsPromise::reject(e, context).into()),
+        };
+        if Self::is_locked(stream) {
+            return Ok(rejected_type_error("cannot abort a locked stream", context).into());
+        }
+        Ok(Self::abort(stream, args.get_or_undefined(0).clone(), context).into())
+    }
+
+    /// `WritableStream.prototype.close()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#ws-close
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStream/close
+    #[allow(clippy::unnecessary_wraps)]
+    fn close_method(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let stream = match Self::this_stream(this) {
+            Ok(stream) => stream,
+            Err(e) => return Ok(JsPromise::reject(e, context).into()),
+        };
+        if Self::is_locked(stream) {
+            return Ok(rejected_type_error("cannot close a locked stream", context).into());
+        }
+        if Self::close_queued_or_in_flight(stream) {
+            return Ok(rejected_type_error("the stream is already closing", context).into());
+        }
+        Ok(Self::close(stream, context).into())
+    }
+
+    /// `WritableStream.prototype.getWriter()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#ws-get-writer
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStream/getWriter
+    fn get_writer(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let stream = Self::this_stream(this)?;
+        Ok(WritableStreamDefaultWriter::acquire(stream, context)?.into())
+    }
+}
+
+impl Class for WritableStream {
+    const NAME: &'static str = "WritableStream";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(class, js_string!("locked"), Self::get_locked);
+        class
+            .method(
+                js_string!("abort"),
+                0,
+                NativeFunction::from_fn_ptr(Self::abort_method),
+            )
+            .method(
+                js_string!("close"),
+                0,
+                NativeFunction::from_fn_ptr(Self::close_method),
+            )
+            .method(
+                js_string!("getWriter"),
+                0,
+                NativeFunction::from_fn_ptr(Self::get_writer),
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("WritableStream"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<Self> {
+        Ok(Self::default())
+    }
+
+    fn object_constructor(
+        instance: &JsObject,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let sink = args.get_or_undefined(0);
+        check_dictionary(sink, "underlying sink")?;
+        let abort = Algorithm::from_method(sink, &js_string!("abort"), context)?;
+        let close = Algorithm::from_method(sink, &js_string!("close"), context)?;
+        let start = Algorithm::from_method(sink, &js_string!("start"), context)?;
+        if let Some(sink) = sink.as_object() {
+            if !sink.get(js_string!("type"), context)?.is_undefined() {
+                return Err(JsNativeError::range()
+                    .with_message("the type of the underlying sink must be undefined")
+                    .into());
+            }
+        }
+        let write = Algorithm::from_method(sink, &js_string!("write"), context)?;
+        let strategy = QueuingStrategy::extract(args.get_or_undefined(1), 1.0, context)?;
+        WritableStreamDefaultController::setup(
+            instance, start, write, close, abort, strategy, context,
+        )
+    }
+}
+
+/// The `WritableStreamDefaultController` class, which controls the state and the queue of a
+/// `WritableStream`.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Streams` specification][spec]
+///
+/// [spec]: https://streams.spec.whatwg.org/#ws-default-controller-class
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStreamDefaultController
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct WritableStreamDefaultController {
+    stream: JsObject,
+    /// The queued chunks, where `None` marks the close request.
+    queue: SizedQueue<Option<JsValue>>,
+    strategy: QueuingStrategy,
+    started: bool,
+    write: Option<Algorithm>,
+    close: Option<Algorithm>,
+    abort: Option<Algorithm>,
+    /// The `AbortSignal` aborted when the stream is aborted, which is `None` if the `AbortSignal`
+    /// class is not registered.
+    signal: Option<JsObject>,
+}
+
+impl WritableStreamDefaultController {
+    /// Borrows the data of a `WritableStreamDefaultController` object.
+    fn data(controller: &JsObject) -> RefMut<'_, ErasedObject, Self> {
+        controller
+            .downcast_mut::<Self>()
+            .expect("must be a WritableStreamDefaultController")
+    }
+
+    /// [Sets up][spec] a new controller for `stream`.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#set-up-writable-stream-default-controller
+    fn setup(
+        stream: &JsObject,
+        start: Option<Algorithm>,
+        write: Option<Algorithm>,
+        close: Option<Algorithm>,
+        abort: Option<Algorithm>,
+        strategy: QueuingStrategy,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let signal = AbortSignal::create(context).ok();
+        let controller = create_instance(
+            Self {
+                stream: stream.clone(),
+                queue: SizedQueue::default(),
+                strategy,
+                started: false,
+                write,
+                close,
+                abort,
+                signal,
+            },
+            context,
+        )?;
+        WritableStream::data(stream).controller = Some(controller.clone());
+        let backpressure = Self::backpressure(&controller);
+        WritableStream::update_backpressure(stream, backpressure, context);
+
+        let start_result = match start {
+            Some(start) => start.call(&[controller.clone().into()], context)?,
+            None => JsValue::undefined(),
+        };
+        let start_promise = JsPromise::resolve(start_result, context);
+        upon(
+            &start_promise,
+            &controller,
+            Some(|_, controller, context| {
+                Self::data(controller).started = true;
+                Self::advance_queue_if_needed(controller, context);
+                Ok(JsValue::undefined())
+            }),
+            Some(|reason, controller, context| {
+                let stream = {
+                    let mut data = Self::data(controller);
+                    data.started = true;
+                    data.stream.clone()
+                };
+                WritableStream::deal_with_rejection(&stream, reason.clone(), context);
+                Ok(JsValue::undefined())
+            }),
+            context,
+        );
+        Ok(())
+    }
+
+    /// Returns `true` if the queue is full.
+    fn backpressure(controller: &JsObject) -> bool {
+        Self::desired_size(controller) <= 0.0
+    }
+
+    /// Returns the desired size to fill the queue of the stream.
+    pub(super) fn desired_size(controller: &JsObject) -> f64 {
+        let data = Self::data(controller);
+        data.strategy.high_water_mark - data.queue.total_size()
+    }
+
+    /// Computes the size of `chunk`, erroring the stream if the size is invalid.
+    fn chunk_size(controller: &JsObject, chunk: &JsValue, context: &mut Context) -> f64 {
+        let strategy = Self::data(controller).strategy.clone();
+        match strategy.size(chunk, context) {
+            Ok(size) => size,
+            Err(e) => {
+                let error = e.to_opaque(context);
+                Self::error_if_needed(controller, error, context);
+                1.0
+            }
+        }
+    }
+
+    /// Queues the close request, which is processed after all the queued chunks.
+    fn close(controller: &JsObject, context: &mut Context) {
+        Self::data(controller).queue.enqueue(None, 0.0);
+        Self::advance_queue_if_needed(controller, context);
+    }
+
+    /// Queues `chunk` to be written by the sink.
+    fn write(controller: &JsObject, chunk: JsValue, size: f64, context: &mut Context) {
+        let stream = {
+            let mut data = Self::data(controller);
+            data.queue.enqueue(Some(chunk), size);
+            data.stream.clone()
+        };
+        if !WritableStream::close_queued_or_in_flight(&stream)
+            && WritableStream::state(&stream) == WritableState::Writable
+        {
+            let backpressure = Self::backpressure(controller);
+            WritableStream::update_backpressure(&stream, backpressure, context);
+        }
+        Self::advance_queue_if_needed(controller, context);
+    }
+
+    /// Passes the next queued chunk or close request to the sink, if the sink is not busy.
+    fn advance_queue_if_needed(controller: &JsObject, context: &mut Context) {
+        let (stream, next) = {
+            let data = Self::data(controller);
+            if !data.started {
+                return;
+            }
+            (data.stream.clone(), data.queue.peek().cloned())
+        };
+        if WritableStream::data(&stream)
+            .in_flight_write_request
+            .is_some()
+        {
+            return;
+        }
+        if WritableStream::state(&stream) == WritableState::Erroring {
+            WritableStream::finish_erroring(&stream, context);
+            return;
+        }
+        match next {
+            None => {}
+            Some(None) => Self::process_close(controller, &stream, context),
+            Some(Some(chunk)) => Self::process_write(controller, &stream, chunk, context),
+        }
+    }
+
+    /// Errors the stream if it is still writable.
+    pub(super) fn error_if_needed(controller: &JsObject, error: JsValue, context: &mut Context) {
+        let stream = Self::data(controller).stream.clone();
+        if WritableStream::state(&stream) == WritableState::Writable {
+            Self::error(controller, error, context);
+        }
+    }
+
+    /// Calls the close algorithm of the sink.
+    fn process_close(controller: &JsObject, stream: &JsObject, context: &mut Context) {
+        {
+            let mut data = WritableStream::data(stream);
+            data.in_flight_close_request = data.close_request.take();
+        }
+        let close = {
+            let mut data = Self::data(controller);
+            data.queue.dequeue();
+            data.close.clone()
+        };
+        let sink_close = Algorithm::call_promise(close.as_ref(), &[], context);
+        Self::clear_algorithms(controller);
+        upon(
+            &sink_close,
+            stream,
+            Some(|_, stream, context| {
+                WritableStream::finish_in_flight_close(stream, context);
+                Ok(JsValue::undefined())
+            }),
+            Some(|reason, stream, context| {
+                WritableStream::finish_in_flight_close_with_error(stream, reason.clone(), context);
+                Ok(JsValue::undefined())
+            }),
+            context,
+        );
+    }
+
+    /// Calls the write algorithm of the sink with `chunk`.
+    fn process_write(
+        controller: &JsObject,
+        stream: &JsObject,
+        chunk: JsValue,
+        context: &mut Context,
+    ) {
+        {
+            let mut data = WritableStream::data(stream);
+            data.in_flight_write_request = data.write_requests.pop_front();
+        }
+        let write = Self::data(controller).write.clone();
+        let sink_write =
+            Algorithm::call_promise(write.as_ref(), &[chunk, controller.clone().into()], context);
+        upon(
+            &sink_write,
+            controller,
+            Some(|_, controller, context| {
+                let stream = Self::data(controller).stream.clone();
+                WritableStream::finish_in_flight_write(&stream, context);
+                Self::data(controller).queue.dequeue();
+                if !WritableStream::close_queued_or_in_flight(&stream)
+                    && WritableStream::state(&stream) == WritableState::Writable
+                {
+                    let backpressure = Self::backpressure(controller);
+                    WritableStream::update_backpressure(&stream, backpressure, context);
+                }
+                Self::advance_queue_if_needed(controller, context);
+                Ok(JsValue::undefined())
+            }),
+            Some(|reason, controller, context| {
+                let stream = Self::data(controller).stream.clone();
+                if WritableStream::state(&stream) == WritableState::Writable {
+                    Self::clear_algorithms(controller);
+                }
+                WritableStream::finish_in_flight_write_with_error(&stream, reason.clone(), context);
+                Ok(JsValue::undefined())
+            }),
+            context,
+        );
+    }
+
+    /// Drops the algorithms of the underlying sink, which won't be called anymore.
+    fn clear_algorithms(controller: &JsObject) {
+        let mut data = Self::data(controller);
+        data.write = None;
+        data.close = None;
+        data.abort = None;
+        data.strategy.size = None;
+    }
+
+    /// Calls the abort algorithm of the sink.
+    fn abort_steps(controller: &JsObject, reason: JsValue, context: &mut Context) -> JsPromise {
+        let abort = Self::data(controller).abort.clone();
+        let result = Algorithm::call_promise(abort.as_ref(), &[reason], context);
+        Self::clear_algorithms(controller);
+        result
+    }
+
+    /// [Errors][spec] the stream, which must be writable.
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#writable-stream-default-controller-error
+    fn error(controller: &JsObject, error: JsValue, context: &mut Context) {
+        let stream = Self::data(controller).stream.clone();
+        Self::clear_algorithms(controller);
+        WritableStream::start_erroring(&stream, error, context);
+    }
+
+    /// Checks that `this` is a `WritableStreamDefaultController` object, returning it.
+    fn this_controller(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a WritableStreamDefaultController object")
+                    .into()
+            })
+    }
+
+    /// `get WritableStreamDefaultController.prototype.signal`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#ws-default-controller-signal
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStreamDefaultController/signal
+    fn get_signal(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let controller = Self::this_controller(this)?;
+        Ok(Self::data(controller)
+            .signal
+            .clone()
+            .map_or_else(JsValue::undefined, Into::into))
+    }
+
+    /// `WritableStreamDefaultController.prototype.error(e)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#ws-default-controller-error
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStreamDefaultController/error
+    fn error_method(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let controller = Self::this_controller(this)?;
+        Self::error_if_needed(controller, args.get_or_undefined(0).clone(), context);
+        Ok(JsValue::undefined())
+    }
+}
+
+impl Class for WritableStreamDefaultController {
+    const NAME: &'static str = "WritableStreamDefaultController";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(class, js_string!("signal"), Self::get_signal);
+        class
+            .method(
+                js_string!("error"),
+                0,
+                NativeFunction::from_fn_ptr(Self::error_method),
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("WritableStreamDefaultController"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<Self> {
+        Err(JsNativeError::typ()
+            .with_message("WritableStreamDefaultController cannot be constructed directly")
+            .into())
+    }
+}
+
+/// The `WritableStreamDefaultWriter` class, which writes chunks to a `WritableStream`.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `Streams` specification][spec]
+///
+/// [spec]: https://streams.spec.whatwg.org/#default-writer-class
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStreamDefaultWriter
+#[derive(Debug, Clone, Default, Trace, Finalize, JsData)]
+pub struct WritableStreamDefaultWriter {
+    stream: Option<JsObject>,
+    ready: Option<Deferred>,
+    closed: Option<Deferred>,
+}
+
+impl WritableStreamDefaultWriter {
+    /// Borrows the data of a `WritableStreamDefaultWriter` object.
+    fn data(writer: &JsObject) -> RefMut<'_, ErasedObject, Self> {
+        writer
+            .downcast_mut::<Self>()
+            .expect("must be a WritableStreamDefaultWriter")
+    }
+
+    /// Returns the `ready` promise and its resolving functions.
+    fn ready_deferred(writer: &JsObject) -> Deferred {
+        Self::data(writer)
+            .ready
+            .clone()
+            .expect("the writer must be set up")
+    }
+
+    /// Returns the `closed` promise and its resolving functions.
+    fn closed_deferred(writer: &JsObject) -> Deferred {
+        Self::data(writer)
+            .closed
+            .clone()
+            .expect("the writer must be set up")
+    }
+
+    /// Creates a writer locking `stream`.
+    pub(super) fn acquire(stream: &JsObject, context: &mut Context) -> JsResult<JsObject> {
+        let writer = create_instance(Self::default(), context)?;
+        Self::setup(&writer, stream, context)?;
+        Ok(writer)
+    }
+
+    /// Locks `stream` to `writer`.
+    fn setup(writer: &JsObject, stream: &JsObject, context: &mut Context) -> JsResult<()> {
+        if WritableStream::is_locked(stream) {
+            return Err(JsNativeError::typ()
+                .with_message("the stream is already locked to a writer")
+                .into());
+        }
+        let stored_error = WritableStream::stored_error(stream);
+        let (ready, closed) = match WritableStream::state(stream) {
+            WritableState::Writable => {
+                let ready = if !WritableStream::close_queued_or_in_flight(stream)
+                    && WritableStream::data(stream).backpressure
+                {
+                    Deferred::new(context)
+                } else {
+                    Deferred::resolved(JsValue::undefined(), context)
+                };
+                (ready, Deferred::new(context))
+            }
+            WritableState::Erroring => (
+                Deferred::rejected(stored_error, context),
+                Deferred::new(context),
+            ),
+            WritableState::Closed => (
+                Deferred::resolved(JsValue::undefined(), context),
+                Deferred::resolved(JsValue::undefined(), context),
+            ),
+            WritableState::Errored => (
+                Deferred::rejected(stored_error.clone(), context),
+                Deferred::rejected(stored_error, context),
+            ),
+        };
+        let mut data = Self::data(writer);
+        data.stream = Some(stream.clone());
+        data.ready = Some(ready);
+        data.closed = Some(closed);
+        WritableStream::data(stream).writer = Some(writer.clone());
+        Ok(())
+    }
+
+    /// Rejects the `ready` promise, replacing it if it is already settled.
+    fn ensure_ready_rejected(writer: &JsObject, error: JsValue, context: &mut Context) {
+        let ready = Self::ready_deferred(writer);
+        if ready.is_pending() {
+            ready.reject(error, context);
+        } else {
+            Self::data(writer).ready = Some(Deferred::rejected(error, context));
+        }
+    }
+
+    /// Rejects the `closed` promise, replacing it if it is already settled.
+    fn ensure_closed_rejected(writer: &JsObject, error: JsValue, context: &mut Context) {
+        let closed = Self::closed_deferred(writer);
+        if closed.is_pending() {
+            closed.reject(error, context);
+        } else {
+            Self::data(writer).closed = Some(Deferred::rejected(error, context));
+        }
+    }
+
+    /// Returns the promise resolved when the stream is closed, or rejected when it errors or
+    /// when the writer is released.
+    pub(super) fn closed(writer: &JsObject) -> JsPromise {
+        Self::closed_deferred(writer).promise()
+    }
+
+    /// Returns the promise resolved when the stream has no backpressure.
+    pub(super) fn ready(writer: &JsObject) -> JsPromise {
+        Self::ready_deferred(writer).promise()
+    }
+
+    /// Writes `chunk` to the stream, returning a promise resolved once the sink has written it.
+    pub(super) fn write(writer: &JsObject, chunk: JsValue, context: &mut Context) -> JsPromise {
+        let Some(stream) = Self::data(writer).stream.clone() else {
+            return rejected_type_error("the writer was released", context);
+        };
+        let controller = WritableStream::controller(&stream);
+        let size = WritableStreamDefaultController::chunk_size(&controller, &chunk, context);
+        // Computing the size could have released the writer.
+        if Self::data(writer).stream.as_ref() != Some(&stream) {
+            return rejected_type_error("the writer was released", context);
+        }
+        let state = WritableStream::state(&stream);
+        if state == WritableState::Errored {
+            return JsPromise::reject(throw(&WritableStream::stored_error(&stream)), context);
+        }
+        if WritableStream::close_queued_or_in_flight(&stream) || state == WritableState::Closed {
+            return rejected_type_error("the stream is closing or closed", context);
+        }
+        if state == WritableState::Erroring {
+            return JsPromise::reject(throw(&WritableStream::stored_error(&stream)), context);
+        }
+        let request = Deferred::new(context);
+        let promise = request.promise();
+        WritableStream::data(&stream)
+            .write_requests
+            .push_back(request);
+        WritableStreamDefaultController::write(&controller, chunk, size, context);
+        promise
+    }
+
+    /// Closes the stream, resolving if it is already closed and rejecting if it is errored.
+    pub(super) fn close_with_error_propagation(
+        writer: &JsObject,
+        context: &mut Context,
+    ) -> JsPromise {
+        let Some(stream) = Self::data(writer).stream.clone() else {
+            return rejected_type_error("the writer was released", context);
+        };
+        let state = WritableStream::state(&stream);
+        if WritableStream::close_queued_or_in_flight(&stream) || state == WritableState::Closed {
+            return JsPromise::resolve(JsValue::undefined(), context);
+        }
+        if state == WritableState::Errored {
+            return JsPromise::reject(throw(&WritableStream::stored_error(&stream)), context);
+        }
+        WritableStream::close(&stream, context)
+    }
+
+    /// Releases the lock of the writer on its stream, rejecting its promises.
+    pub(super) fn release(writer: &JsObject, context: &mut Context) {
+        let Some(stream) = Self::data(writer).stream.clone() else {
+            return;
+        };
+        let error = JsValue::from(
+            JsNativeError::typ()
+                .with_message("the writer was released")
+                .to_opaque(context),
+        );
+        Self::ensure_ready_rejected(writer, error.clone(), context);
+        Self::ensure_closed_rejected(writer, error, context);
+        WritableStream::data(&stream).writer = None;
+        Self::data(writer).stream = None;
+    }
+
+    /// Checks that `this` is a `WritableStreamDefaultWriter` object, returning it.
+    fn this_writer(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a WritableStreamDefaultWriter object")
+                    .into()
+            })
+    }
+
+    /// `get WritableStreamDefaultWriter.prototype.closed`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#default-writer-closed
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStreamDefaultWriter/closed
+    #[allow(clippy::unnecessary_wraps)]
+    fn get_closed(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        match Self::this_writer(this) {
+            Ok(writer) => Ok(Self::closed(writer).into()),
+            Err(e) => Ok(JsPromise::reject(e, context).into()),
+        }
+    }
+
+    /// `get WritableStreamDefaultWriter.prototype.desiredSize`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#default-writer-desired-size
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStreamDefaultWriter/desiredSize
+    fn get_desired_size(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let writer = Self::this_writer(this)?;
+        let stream = Self::data(writer)
+            .stream
+            .clone()
+            .ok_or_else(|| JsNativeError::typ().with_message("the writer was released"))?;
+        match WritableStream::state(&stream) {
+            WritableState::Errored | WritableState::Erroring => Ok(JsValue::null()),
+            WritableState::Closed => Ok(0.into()),
+            WritableState::Writable => Ok(WritableStreamDefaultController::desired_size(
+                &WritableStream::controller(&stream),
+            )
+            .into()),
+        }
+    }
+
+    /// `get WritableStreamDefaultWriter.prototype.ready`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#default-writer-ready
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStreamDefaultWriter/ready
+    #[allow(clippy::unnecessary_wraps)]
+    fn get_ready(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        match Self::this_writer(this) {
+            Ok(writer) => Ok(Self::ready(writer).into()),
+            Err(e) => Ok(JsPromise::reject(e, context).into()),
+        }
+    }
+
+    /// `WritableStreamDefaultWriter.prototype.abort(reason)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#default-writer-abort
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStreamDefaultWriter/abort
+    #[allow(clippy::unnecessary_wraps)]
+    fn abort(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let writer = match Self::this_writer(this) {
+            Ok(writer) => writer,
+            Err(e) => return Ok(JsPromise::reject(e, context).into()),
+        };
+        let Some(stream) = Self::data(writer).stream.clone() else {
+            return Ok(rejected_type_error("the writer was released", context).into());
+        };
+        Ok(WritableStream::abort(&stream, args.get_or_undefined(0).clone(), context).into())
+    }
+
+    /// `WritableStreamDefaultWriter.prototype.close()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#default-writer-close
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStreamDefaultWriter/close
+    #[allow(clippy::unnecessary_wraps)]
+    fn close(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let writer = match Self::this_writer(this) {
+            Ok(writer) => writer,
+            Err(e) => return Ok(JsPromise::reject(e, context).into()),
+        };
+        let Some(stream) = Self::data(writer).stream.clone() else {
+            return Ok(rejected_type_error("the writer was released", context).into());
+        };
+        if WritableStream::close_queued_or_in_flight(&stream) {
+            return Ok(rejected_type_error("the stream is already closing", context).into());
+        }
+        Ok(WritableStream::close(&stream, context).into())
+    }
+
+    /// `WritableStreamDefaultWriter.prototype.releaseLock()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#default-writer-release-lock
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStreamDefaultWriter/releaseLock
+    fn release_lock(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::release(Self::this_writer(this)?, context);
+        Ok(JsValue::undefined())
+    }
+
+    /// `WritableStreamDefaultWriter.prototype.write(chunk)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Streams` specification][spec]
+    ///
+    /// [spec]: https://streams.spec.whatwg.org/#default-writer-write
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/WritableStreamDefaultWriter/write
+    #[allow(clippy::unnecessary_wraps)]
+    fn write_method(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        match Self::this_writer(this) {
+            Ok(writer) => Ok(Self::write(writer, args.get_or_undefined(0).clone(), context).into()),
+            Err(e) => Ok(JsPromise::reject(e, context).into()),
+        }
+    }
+}
+
+impl Class for WritableStreamDefaultWriter {
+    const NAME: &'static str = "WritableStreamDefaultWriter";
+    const LENGTH: usize = 1;
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(class, js_string!("closed"), Self::get_closed);
+        add_getter(class, js_string!("desiredSize"), Self::get_desired_size);
+        add_getter(class, js_string!("ready"), Self::get_ready);
+        class
+            .method(
+                js_string!("abort"),
+                0,
+                NativeFunction::from_fn_ptr(Self::abort),
+            )
+            .method(
+                js_string!("close"),
+                0,
+                NativeFunction::from_fn_ptr(Self::close),
+            )
+            .method(
+                js_string!("releaseLock"),
+                0,
+                NativeFunction::from_fn_ptr(Self::release_lock),
+            )
+            .method(
+                js_string!("write"),
+                0,
+                NativeFunction::from_fn_ptr(Self::write_method),
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("WritableStreamDefaultWriter"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<Self> {
+        Ok(Self::default())
+    }
+
+    fn object_constructor(
+        instance: &JsObject,
+        args: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let stream = args
+            .get_or_undefined(0)
+            .as_object()
+            .filter(|stream| stream.is::<WritableStream>())
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message("the argument must be a WritableStream")
+            })?;
+        Self::setup(instance, stream, context)
+    }
+}
@@ -0,0 +1,773 @@
+//! Boa's implementation of the `structuredClone` global function and of the structured
+//! serialization algorithms of the HTML specification.
+//!
+//! Values are serialized into [`SerializedValue`]s, a self-contained byte format that can be
+//! stored, or sent to another [`Context`] and deserialized there.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [HTML specification][spec]
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/structured-data.html
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/structuredClone
+
+#[cfg(test)]
+mod tests;
+
+use crate::util::iterable_to_list;
+use boa_engine::{
+    builtins::{
+        error::ErrorObject,
+        map::ordered_map::OrderedMap,
+        regexp::RegExp,
+        set::ordered_set::OrderedSet,
+        typed_array::{TypedArray, TypedArrayKind},
+    },
+    js_string,
+    native_function::NativeFunction,
+    object::{
+        builtins::{JsArray, JsArrayBuffer, JsDataView, JsDate, JsMap, JsSet},
+        JsObject,
+    },
+    property::PropertyKey,
+    Context, JsArgs, JsBigInt, JsNativeError, JsResult, JsString, JsValue,
+};
+use rustc_hash::FxHashMap;
+
+/// The version of the serialization format, stored in the first byte of every
+/// [`SerializedValue`].
+const FORMAT_VERSION: u8 = 1;
+
+/// The tags identifying each kind of serialized value.
+mod tag {
+    pub(super) const UNDEFINED: u8 = 0;
+    pub(super) const NULL: u8 = 1;
+    pub(super) const FALSE: u8 = 2;
+    pub(super) const TRUE: u8 = 3;
+    pub(super) const NUMBER: u8 = 4;
+    pub(super) const BIGINT: u8 = 5;
+    pub(super) const STRING: u8 = 6;
+    /// A reference to an object that was already serialized, by its index in the order of
+    /// serialization.
+    pub(super) const REFERENCE: u8 = 7;
+    pub(super) const BOOLEAN_OBJECT: u8 = 8;
+    pub(super) const NUMBER_OBJECT: u8 = 9;
+    pub(super) const BIGINT_OBJECT: u8 = 10;
+    pub(super) const STRING_OBJECT: u8 = 11;
+    pub(super) const DATE: u8 = 12;
+    pub(super) const REGEXP: u8 = 13;
+    pub(super) const ARRAY_BUFFER: u8 = 14;
+    pub(super) const TYPED_ARRAY: u8 = 15;
+    pub(super) const DATA_VIEW: u8 = 16;
+    pub(super) const MAP: u8 = 17;
+    pub(super) const SET: u8 = 18;
+    pub(super) const ERROR: u8 = 19;
+    pub(super) const ARRAY: u8 = 20;
+    pub(super) const OBJECT: u8 = 21;
+}
+
+/// The typed array kinds, in the order of their serialized index.
+const TYPED_ARRAY_KINDS: [TypedArrayKind; 11] = [
+    TypedArrayKind::Int8,
+    TypedArrayKind::Uint8,
+    TypedArrayKind::Uint8Clamped,
+    TypedArrayKind::Int16,
+    TypedArrayKind::Uint16,
+    TypedArrayKind::Int32,
+    TypedArrayKind::Uint32,
+    TypedArrayKind::BigInt64,
+    TypedArrayKind::BigUint64,
+    TypedArrayKind::Float32,
+    TypedArrayKind::Float64,
+];
+
+/// Creates a native error of a given type.
+type ErrorConstructor = fn() -> JsNativeError;
+
+/// The error types that keep their type when serialized, by name. Any other error is deserialized
+/// as an `Error`.
+const ERROR_TYPES: [(&str, ErrorConstructor); 7] = [
+    ("Error", JsNativeError::error),
+    ("EvalError", JsNativeError::eval),
+    ("RangeError", JsNativeError::range),
+    ("ReferenceError", JsNativeError::reference),
+    ("SyntaxError", JsNativeError::syntax),
+    ("TypeError", JsNativeError::typ),
+    ("URIError", JsNativeError::uri),
+];
+
+/// Creates the error thrown when a value cannot be serialized.
+fn data_clone_error(message: &str) -> JsNativeError {
+    JsNativeError::typ().with_message(format!("DataCloneError: {message}"))
+}
+
+/// A JavaScript value graph serialized with the [structured serialization][spec] algorithm.
+///
+/// The serialized value doesn't reference any object of the [`Context`] it was serialized from,
+/// so it can be converted to bytes, stored, or deserialized into a different `Context`.
+///
+/// Supported values are primitives other than symbols, plain objects, arrays, `Boolean`,
+/// `Number`, `BigInt` and `String` objects, `Date`, `RegExp`, `Map`, `Set`, `ArrayBuffer`,
+/// typed arrays, `DataView` and errors. Cyclic and shared references are preserved.
+///
+/// # Example
+///
+/// ```
+/// use boa_engine::{js_string, Context, JsValue, Source};
+/// use boa_runtime::SerializedValue;
+///
+/// let source = &mut Context::default();
+/// let value = source
+///     .eval(Source::from_bytes("const a = { name: 'boa' }; a.self = a; a"))
+///     .unwrap();
+/// let bytes = SerializedValue::serialize(&value, &[], source)
+///     .unwrap()
+///     .into_bytes();
+///
+/// let target = &mut Context::default();
+/// let value = SerializedValue::from_bytes(bytes).deserialize(target).unwrap();
+/// let object = value.as_object().unwrap();
+///
+/// assert_eq!(
+///     object.get(js_string!("name"), target).unwrap(),
+///     js_string!("boa").into()
+/// );
+/// assert_eq!(
+///     object.get(js_string!("self"), target).unwrap(),
+///     JsValue::from(object.clone())
+/// );
+/// ```
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#structuredserializeinternal
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SerializedValue {
+    bytes: Vec<u8>,
+}
+
+impl SerializedValue {
+    /// Serializes `value`, transferring the `ArrayBuffer`s in `transfer`.
+    ///
+    /// Transferred buffers are detached from `context` once the serialization succeeds, and their
+    /// contents are moved into the serialized value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` contains a value that cannot be serialized, if an item of
+    /// `transfer` is not a detachable `ArrayBuffer` or is repeated, or if a getter of `value`
+    /// throws.
+    pub fn serialize(
+        value: &JsValue,
+        transfer: &[JsObject],
+        context: &mut Context,
+    ) -> JsResult<Self> {
+        let mut buffers = Vec::with_capacity(transfer.len());
+        for (i, object) in transfer.iter().enumerate() {
+            let buffer = JsArrayBuffer::from_object(object.clone())
+                .map_err(|_| data_clone_error("only ArrayBuffers can be transferred"))?;
+            if buffer.data().is_none() {
+                return Err(data_clone_error("cannot transfer a detached ArrayBuffer").into());
+            }
+            if transfer[..i].contains(object) {
+                return Err(data_clone_error(
+                    "an ArrayBuffer cannot be transferred more than once",
+                )
+                .into());
+            }
+            buffers.push(buffer);
+        }
+
+        let mut serializer = Serializer {
+            bytes: vec![FORMAT_VERSION],
+            memory: FxHashMap::default(),
+        };
+        serializer.write_value(value, context)?;
+
+        for buffer in buffers {
+            buffer.detach(&JsValue::undefined())?;
+        }
+
+        Ok(Self {
+            bytes: serializer.bytes,
+        })
+    }
+
+    /// Deserializes this value into `context`, creating new objects in its current realm.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes of this value are not in the serialization format, which can
+    /// only happen for values created with [`SerializedValue::from_bytes`].
+    pub fn deserialize(&self, context: &mut Context) -> JsResult<JsValue> {
+        let mut deserializer = Deserializer {
+            bytes: &self.bytes,
+            position: 0,
+            memory: Vec::new(),
+        };
+        if deserializer.read_u8()? != FORMAT_VERSION {
+            return Err(Deserializer::invalid());
+        }
+        let value = deserializer.read_value(context)?;
+        if deserializer.position != self.bytes.len() {
+            return Err(Deserializer::invalid());
+        }
+        Ok(value)
+    }
+
+    /// Creates a serialized value from bytes returned by [`SerializedValue::into_bytes`] or
+    /// [`SerializedValue::as_bytes`].
+    ///
+    /// The bytes are validated by [`SerializedValue::deserialize`].
+    #[must_use]
+    pub const fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns the bytes of this serialized value.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Converts this serialized value into its bytes.
+    #[must_use]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// The state of a serialization.
+struct Serializer {
+    bytes: Vec<u8>,
+    /// The index of each object serialized so far.
+    memory: FxHashMap<JsObject, u32>,
+}
+
+impl Serializer {
+    fn write_u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_f64(&mut self, value: f64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Writes a length or a count, which must fit in a `u32`.
+    fn write_len(&mut self, len: usize) -> JsResult<()> {
+        let len = u32::try_from(len).map_err(|_| data_clone_error("the value is too large"))?;
+        self.write_u32(len);
+        Ok(())
+    }
+
+    fn write_string(&mut self, string: &JsString) -> JsResult<()> {
+        self.write_len(string.len())?;
+        for unit in string.iter() {
+            self.bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        Ok(())
+    }
+
+    fn write_bigint(&mut self, bigint: &JsBigInt) -> JsResult<()> {
+        let digits = bigint.to_string_radix(16);
+        self.write_len(digits.len())?;
+        self.bytes.extend_from_slice(digits.as_bytes());
+        Ok(())
+    }
+
+    fn write_value(&mut self, value: &JsValue, context: &mut Context) -> JsResult<()> {
+        match value {
+            JsValue::Undefined => self.write_u8(tag::UNDEFINED),
+            JsValue::Null => self.write_u8(tag::NULL),
+            JsValue::Boolean(false) => self.write_u8(tag::FALSE),
+            JsValue::Boolean(true) => self.write_u8(tag::TRUE),
+            JsValue::Integer(number) => {
+                self.write_u8(tag::NUMBER);
+                self.write_f64(f64::from(*number));
+            }
+            JsValue::Rational(number) => {
+                self.write_u8(tag::NUMBER);
+                self.write_f64(*number);
+            }
+            JsValue::BigInt(bigint) => {
+                self.write_u8(tag::BIGINT);
+                self.write_bigint(bigint)?;
+            }
+            JsValue::String(string) => {
+                self.write_u8(tag::STRING);
+                self.write_string(string)?;
+            }
+            JsValue::Symbol(_) => return Err(data_clone_error("symbols cannot be cloned").into()),
+            JsValue::Object(object) => self.write_object(object, context)?,
+        }
+        Ok(())
+    }
+
+    fn write_object(&mut self, object: &JsObject, context: &mut Context) -> JsResult<()> {
+        if let Some(&index) = self.memory.get(object) {
+            self.write_u8(tag::REFERENCE);
+            self.write_u32(index);
+            return Ok(());
+        }
+        let index = u32::try_from(self.memory.len())
+            .map_err(|_| data_clone_error("the value has too many objects"))?;
+        self.memory.insert(object.clone(), index);
+
+        if let Some(boolean) = object.downcast_ref::<bool>().as_deref().copied() {
+            self.write_u8(tag::BOOLEAN_OBJECT);
+            self.write_u8(u8::from(boolean));
+        } else if let Some(number) = object.downcast_ref::<f64>().as_deref().copied() {
+            self.write_u8(tag::NUMBER_OBJECT);
+            self.write_f64(number);
+        } else if let Some(bigint) = object.downcast_ref::<JsBigInt>().as_deref().cloned() {
+            self.write_u8(tag::BIGINT_OBJECT);
+            self.write_bigint(&bigint)?;
+        } else if let Some(string) = object.downcast_ref::<JsString>().as_deref().cloned() {
+            self.write_u8(tag::STRING_OBJECT);
+            self.write_string(&string)?;
+        } else if let Ok(date) = JsDate::from_object(object.clone()) {
+            let time = date.get_time(context)?.as_number().unwrap_or(f64::NAN);
+            self.write_u8(tag::DATE);
+            self.write_f64(time);
+        } else if let Some(regexp) = object.downcast_ref::<RegExp>().as_deref().cloned() {
+            self.write_u8(tag::REGEXP);
+            self.write_string(regexp.original_source())?;
+            self.write_string(regexp.original_flags())?;
+        } else if let Ok(buffer) = JsArrayBuffer::from_object(object.clone()) {
+            let data = buffer
+                .data()
+                .ok_or_else(|| data_clone_error("cannot clone a detached ArrayBuffer"))?
+                .to_vec();
+            self.write_u8(tag::ARRAY_BUFFER);
+            self.write_u64(data.len() as u64);
+            self.bytes.extend_from_slice(&data);
+        } else if let Some(array) = object.downcast_ref::<TypedArray>().as_deref().cloned() {
+            let kind = (0..)
+                .zip(TYPED_ARRAY_KINDS)
+                .find_map(|(i, kind)| (kind == array.kind()).then_some(i))
+                .expect("all typed array kinds must be listed");
+            self.write_u8(tag::TYPED_ARRAY);
+            self.write_u8(kind);
+            self.write_value(&array.viewed_array_buffer().clone().into(), context)?;
+            self.write_u64(array.byte_offset());
+            self.write_u64(array.array_length());
+        } else if let Ok(view) = JsDataView::from_object(object.clone()) {
+            let buffer = view.buffer(context)?;
+            let offset = view.byte_offset(context)?;
+            let length = view.byte_length(context)?;
+            self.write_u8(tag::DATA_VIEW);
+            self.write_value(&buffer, context)?;
+            self.write_u64(offset);
+            self.write_u64(length);
+        } else if object.is::<OrderedMap<JsValue>>() {
+            let entries = object
+                .downcast_ref::<OrderedMap<JsValue>>()
+                .expect("checked above")
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<Vec<_>>();
+            self.write_u8(tag::MAP);
+            self.write_len(entries.len())?;
+            for (key, value) in &entries {
+                self.write_value(key, context)?;
+                self.write_value(value, context)?;
+            }
+        } else if object.is::<OrderedSet>() {
+            let values = object
+                .downcast_ref::<OrderedSet>()
+                .expect("checked above")
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>();
+            self.write_u8(tag::SET);
+            self.write_len(values.len())?;
+            for value in &values {
+                self.write_value(value, context)?;
+            }
+        } else if object.is::<ErrorObject>() {
+            self.write_error(object, context)?;
+        } else if object.is_array() {
+            let length = object
+                .get(js_string!("length"), context)?
+                .to_length(context)?;
+            self.write_u8(tag::ARRAY);
+            self.write_u32(u32::try_from(length).expect("array lengths must fit in a u32"));
+            self.write_properties(object, context)?;
+        } else if object.is_ordinary() {
+            self.write_u8(tag::OBJECT);
+            self.write_properties(object, context)?;
+        } else {
+            return Err(data_clone_error("the object cannot be cloned").into());
+        }
+        Ok(())
+    }
+
+    /// Writes the name and message of an error object.
+    fn write_error(&mut self, object: &JsObject, context: &mut Context) -> JsResult<()> {
+        let name = object.get(js_string!("name"), context)?;
+        let name = if name.is_undefined() {
+            0
+        } else {
+            let name = name.to_string(context)?.to_std_string_escaped();
+            (0..)
+                .zip(ERROR_TYPES)
+                .find_map(|(i, (n, _))| (n == name).then_some(i))
+                .unwrap_or(0)
+        };
+
+        let message = match object.own_property(js_string!("message"), context)? {
+            Some(desc) if desc.is_data_descriptor() => Some(
+                object
+                    .get(js_string!("message"), context)?
+                    .to_string(context)?,
+            ),
+            _ => None,
+        };
+
+        self.write_u8(tag::ERROR);
+        self.write_u8(name);
+        if let Some(message) = message {
+            self.write_u8(1);
+            self.write_string(&message)?;
+        } else {
+            self.write_u8(0);
+        }
+        Ok(())
+    }
+
+    /// Writes the enumerable own string-keyed properties of an object.
+    fn write_properties(&mut self, object: &JsObject, context: &mut Context) -> JsResult<()> {
+        let mut keys = Vec::new();
+        for key in object.own_property_keys(context)? {
+            let key = match key {
+                PropertyKey::String(key) => key,
+                PropertyKey::Index(index) => JsString::from(index.get().to_string()),
+                PropertyKey::Symbol(_) => continue,
+            };
+            if object
+                .own_property(key.clone(), context)?
+                .is_some_and(|desc| desc.expect_enumerable())
+            {
+                keys.push(key);
+            }
+        }
+
+        self.write_len(keys.len())?;
+        for key in keys {
+            let value = object.get(key.clone(), context)?;
+            self.write_string(&key)?;
+            self.write_value(&value, context)?;
+        }
+        Ok(())
+    }
+}
+
+/// The state of a deserialization.
+struct Deserializer<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    /// The objects deserialized so far, by index. The entry of an object is `None` until it's
+    /// created.
+    memory: Vec<Option<JsObject>>,
+}
+
+impl Deserializer<'_> {
+    /// Creates the error returned for malformed data.
+    fn invalid() -> boa_engine::JsError {
+        JsNativeError::typ()
+            .with_message("the serialized value is malformed")
+            .into()
+    }
+
+    fn read_bytes(&mut self, len: usize) -> JsResult<&[u8]> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(Self::invalid)?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> JsResult<[u8; N]> {
+        Ok(self
+            .read_bytes(N)?
+            .try_into()
+            .expect("the slice must have the requested length"))
+    }
+
+    fn read_u8(&mut self) -> JsResult<u8> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    fn read_u32(&mut self) -> JsResult<u32> {
+        self.read_array().map(u32::from_le_bytes)
+    }
+
+    fn read_u64(&mut self) -> JsResult<u64> {
+        self.read_array().map(u64::from_le_bytes)
+    }
+
+    fn read_f64(&mut self) -> JsResult<f64> {
+        self.read_array().map(f64::from_le_bytes)
+    }
+
+    fn read_usize(&mut self) -> JsResult<usize> {
+        usize::try_from(self.read_u64()?).map_err(|_| Self::invalid())
+    }
+
+    fn read_string(&mut self) -> JsResult<JsString> {
+        let len = self.read_u32()? as usize;
+        let bytes = self.read_bytes(len.checked_mul(2).ok_or_else(Self::invalid)?)?;
+        let units = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect::<Vec<_>>();
+        Ok(JsString::from(units))
+    }
+
+    fn read_bigint(&mut self) -> JsResult<JsBigInt> {
+        let len = self.read_u32()? as usize;
+        let digits = std::str::from_utf8(self.read_bytes(len)?).map_err(|_| Self::invalid())?;
+        JsBigInt::from_string_radix(digits, 16).ok_or_else(Self::invalid)
+    }
+
+    /// Reserves the index of the next object.
+    fn reserve(&mut self) -> usize {
+        self.memory.push(None);
+        self.memory.len() - 1
+    }
+
+    /// Stores the object at a reserved index, returning it as a value.
+    fn store(&mut self, index: usize, object: JsObject) -> JsValue {
+        self.memory[index] = Some(object.clone());
+        object.into()
+    }
+
+    fn read_value(&mut self, context: &mut Context) -> JsResult<JsValue> {
+        let value = match self.read_u8()? {
+            tag::UNDEFINED => JsValue::undefined(),
+            tag::NULL => JsValue::null(),
+            tag::FALSE => false.into(),
+            tag::TRUE => true.into(),
+            tag::NUMBER => self.read_f64()?.into(),
+            tag::BIGINT => self.read_bigint()?.into(),
+            tag::STRING => self.read_string()?.into(),
+            tag::REFERENCE => {
+                let index = self.read_u32()? as usize;
+                self.memory
+                    .get(index)
+                    .cloned()
+                    .flatten()
+                    .ok_or_else(Self::invalid)?
+                    .into()
+            }
+            tag => return self.read_object(tag, context),
+        };
+        Ok(value)
+    }
+
+    fn read_object(&mut self, tag: u8, context: &mut Context) -> JsResult<JsValue> {
+        let index = self.reserve();
+        let object = match tag {
+            tag::BOOLEAN_OBJECT => JsValue::from(self.read_u8()? != 0).to_object(context)?,
+            tag::NUMBER_OBJECT => JsValue::from(self.read_f64()?).to_object(context)?,
+            tag::BIGINT_OBJECT => JsValue::from(self.read_bigint()?).to_object(context)?,
+            tag::STRING_OBJECT => JsValue::from(self.read_string()?).to_object(context)?,
+            tag::DATE => {
+                let date = JsDate::new(context);
+                date.set_time(self.read_f64()?, context)?;
+                date.into()
+            }
+            tag::REGEXP => {
+                let source = self.read_string()?;
+                let flags = self.read_string()?;
+                context
+                    .intrinsics()
+                    .constructors()
+                    .regexp()
+                    .constructor()
+                    .construct(&[source.into(), flags.into()], None, context)
+                    .map_err(|_| Self::invalid())?
+            }
+            tag::ARRAY_BUFFER => {
+                let len = self.read_usize()?;
+                let data = self.read_bytes(len)?.to_vec();
+                JsArrayBuffer::from_byte_block(data, context)?.into()
+            }
+            tag::TYPED_ARRAY | tag::DATA_VIEW => self.read_view(tag, context)?,
+            tag::MAP => {
+                let map = JsMap::new(context);
+                self.memory[index] = Some(map.clone().into());
+                for _ in 0..self.read_u32()? {
+                    let key = self.read_value(context)?;
+                    let value = self.read_value(context)?;
+                    map.set(key, value, context)?;
+                }
+                map.into()
+            }
+            tag::SET => {
+                let set = JsSet::new(context);
+                self.memory[index] = Some(set.clone().into());
+                for _ in 0..self.read_u32()? {
+                    let value = self.read_value(context)?;
+                    set.add(value, context)?;
+                }
+                set.into()
+            }
+            tag::ERROR => self.read_error(context)?,
+            tag::ARRAY => {
+                let length = self.read_u32()?;
+                let array = JsArray::new(context);
+                array.set(js_string!("length"), length, true, context)?;
+                self.memory[index] = Some(array.clone().into());
+                self.read_properties(&array, context)?;
+                array.into()
+            }
+            tag::OBJECT => {
+                let object = JsObject::with_object_proto(context.intrinsics());
+                self.memory[index] = Some(object.clone());
+                self.read_properties(&object, context)?;
+                object
+            }
+            _ => return Err(Self::invalid()),
+        };
+        Ok(self.store(index, object))
+    }
+
+    /// Reads a typed array or a `DataView`, identified by `tag`.
+    fn read_view(&mut self, tag: u8, context: &mut Context) -> JsResult<JsObject> {
+        let constructor = if tag == tag::TYPED_ARRAY {
+            let kind = *TYPED_ARRAY_KINDS
+                .get(usize::from(self.read_u8()?))
+                .ok_or_else(Self::invalid)?;
+            kind.standard_constructor()(context.intrinsics().constructors()).constructor()
+        } else {
+            context
+                .intrinsics()
+                .constructors()
+                .data_view()
+                .constructor()
+        };
+        let buffer = self.read_value(context)?;
+        if !buffer.as_object().is_some_and(JsObject::is_buffer) {
+            return Err(Self::invalid());
+        }
+        let offset = self.read_u64()?;
+        let length = self.read_u64()?;
+        constructor
+            .construct(&[buffer, offset.into(), length.into()], None, context)
+            .map_err(|_| Self::invalid())
+    }
+
+    /// Reads the type and message of an error.
+    fn read_error(&mut self, context: &mut Context) -> JsResult<JsObject> {
+        let (_, error) = ERROR_TYPES
+            .get(usize::from(self.read_u8()?))
+            .ok_or_else(Self::invalid)?;
+        match self.read_u8()? {
+            0 => {
+                let error = error().to_opaque(context);
+                error.delete_property_or_throw(js_string!("message"), context)?;
+                Ok(error)
+            }
+            1 => {
+                let message = self.read_string()?;
+                Ok(error()
+                    .with_message(message.to_std_string_escaped())
+                    .to_opaque(context))
+            }
+            _ => Err(Self::invalid()),
+        }
+    }
+
+    /// Reads the properties of an object, defining them on `object`.
+    fn read_properties(&mut self, object: &JsObject, context: &mut Context) -> JsResult<()> {
+        for _ in 0..self.read_u32()? {
+            let key = self.read_string()?;
+            let value = self.read_value(context)?;
+            object.create_data_property_or_throw(key, value, context)?;
+        }
+        Ok(())
+    }
+}
+
+/// The `structuredClone` global function.
+#[derive(Debug, Clone, Copy)]
+pub struct StructuredClone;
+
+impl StructuredClone {
+    /// The binding name of the function.
+    pub const NAME: &'static str = "structuredClone";
+
+    /// Registers `structuredClone` as a global function of the context.
+    ///
+    /// # Errors
+    ///
+    /// This will return an error if the function cannot be defined in the global object.
+    pub fn register(context: &mut Context) -> JsResult<()> {
+        context.register_global_builtin_callable(
+            js_string!(Self::NAME),
+            1,
+            NativeFunction::from_fn_ptr(Self::structured_clone),
+        )
+    }
+
+    /// `structuredClone(value, options = {})`
+    ///
+    /// Returns a deep clone of value, transferring the `ArrayBuffer`s of `options.transfer`.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#dom-structuredclone
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/structuredClone
+    fn structured_clone(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let transfer = transfer_list(args.get_or_undefined(1), context)?;
+        SerializedValue::serialize(args.get_or_undefined(0), &transfer, context)?
+            .deserialize(context)
+    }
+}
+
+/// Gets the objects to transfer from the `transfer` member of a `StructuredSerializeOptions`
+/// dictionary.
+pub(crate) fn transfer_list(options: &JsValue, context: &mut Context) -> JsResult<Vec<JsObject>> {
+    match options {
+        JsValue::Undefined | JsValue::Null => Ok(Vec::new()),
+        JsValue::Object(options) => {
+            let transfer = options.get(js_string!("transfer"), context)?;
+            if transfer.is_undefined() {
+                Ok(Vec::new())
+            } else {
+                object_list(&transfer, context)
+            }
+        }
+        _ => Err(JsNativeError::typ()
+            .with_message("the structuredClone options must be an object")
+            .into()),
+    }
+}
+
+/// Converts an iterable of objects, e.g. a transfer list, into a list.
+pub(crate) fn object_list(iterable: &JsValue, context: &mut Context) -> JsResult<Vec<JsObject>> {
+    iterable_to_list(
+        iterable,
+        None,
+        |item, _| {
+            item.as_object().cloned().ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("the transfer list can only contain objects")
+                    .into()
+            })
+        },
+        context,
+    )
+}
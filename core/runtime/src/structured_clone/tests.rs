@@ -0,0 +1,200 @@
+use super::SerializedValue;
+use crate::test::{run_test_actions, TestAction};
+use boa_engine::{js_string, property::Attribute, Context, JsString, JsValue, Source};
+use indoc::indoc;
+
+#[test]
+fn clone_primitives_and_wrappers() {
+    run_test_actions([
+        TestAction::assert_eq("structuredClone(undefined)", JsValue::undefined()),
+        TestAction::assert_eq("structuredClone(null)", JsValue::null()),
+        TestAction::assert_eq(
+            "structuredClone(-0) === 0 && 1 / structuredClone(-0)",
+            f64::NEG_INFINITY,
+        ),
+        TestAction::assert_eq("Number.isNaN(structuredClone(NaN))", true),
+        TestAction::assert_eq(
+            "structuredClone(-12345678901234567890n) === -12345678901234567890n",
+            true,
+        ),
+        TestAction::assert_eq(
+            "structuredClone('a\\uD800b')",
+            JsString::from(&[0x61, 0xD800, 0x62][..]),
+        ),
+        TestAction::assert_eq(
+            indoc! {r#"
+                const wrappers = structuredClone([new Boolean(false), new Number(4), Object(5n), new String("hi")]);
+                wrappers.map((w) => typeof w === "object" && w.valueOf().toString()).join()
+            "#},
+            js_string!("false,4,5,hi"),
+        ),
+        TestAction::assert_eq(
+            "try { structuredClone(Symbol()) } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+        TestAction::assert_eq(
+            "try { structuredClone({ f() {} }) } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+    ]);
+}
+
+#[test]
+fn clone_objects_and_collections() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            class Point { constructor() { this.x = 1; } }
+            const shared = { n: 1 };
+            const original = {
+                array: [1, , shared],
+                date: new Date(1e12),
+                regexp: /a+b/giu,
+                map: new Map([[shared, "value"], ["key", shared]]),
+                set: new Set([shared, 2]),
+                point: new Point(),
+                get getter() { return "read"; },
+                [Symbol()]: "skipped",
+            };
+            Object.defineProperty(original, "hidden", { value: 1, enumerable: false });
+            original.self = original;
+            const clone = structuredClone(original);
+        "#}),
+        TestAction::assert_eq("clone === original", false),
+        TestAction::assert_eq("clone.self === clone", true),
+        TestAction::assert_eq(
+            "Object.keys(clone).join()",
+            js_string!("array,date,regexp,map,set,point,getter,self"),
+        ),
+        TestAction::assert_eq("clone.array.length", 3),
+        TestAction::assert_eq("1 in clone.array", false),
+        TestAction::assert_eq("clone.array[2] === clone.map.get('key')", true),
+        TestAction::assert_eq("clone.set.has(clone.array[2])", true),
+        TestAction::assert_eq("clone.map.get(clone.array[2])", js_string!("value")),
+        TestAction::assert_eq("clone.date instanceof Date && clone.date.getTime()", 1e12),
+        TestAction::assert_eq("String(clone.regexp)", js_string!("/a+b/giu")),
+        TestAction::assert_eq("clone.regexp.lastIndex", 0),
+        TestAction::assert_eq(
+            "Object.getPrototypeOf(clone.point) === Object.prototype",
+            true,
+        ),
+        TestAction::assert_eq("clone.getter", js_string!("read")),
+    ]);
+}
+
+#[test]
+fn clone_errors() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const range = structuredClone(new RangeError("out of range"));
+            const custom = new TypeError("custom");
+            custom.name = "CustomError";
+            const other = structuredClone(custom);
+            const empty = structuredClone(new SyntaxError());
+        "#}),
+        TestAction::assert_eq("range instanceof RangeError", true),
+        TestAction::assert_eq("range.message", js_string!("out of range")),
+        TestAction::assert_eq("Object.keys(range).length", 0),
+        TestAction::assert_eq("Object.getPrototypeOf(other) === Error.prototype", true),
+        TestAction::assert_eq("other.message", js_string!("custom")),
+        TestAction::assert_eq("empty instanceof SyntaxError", true),
+        TestAction::assert_eq("Object.hasOwn(empty, 'message')", false),
+    ]);
+}
+
+#[test]
+fn clone_buffers_and_transfer() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const buffer = new ArrayBuffer(8);
+            const bytes = new Uint8Array(buffer);
+            bytes.set([1, 2, 3, 4, 5, 6, 7, 8]);
+            const original = {
+                bytes,
+                view: new DataView(buffer, 2, 4),
+                floats: new Float32Array(buffer, 4, 1),
+            };
+            const copy = structuredClone(original);
+        "#}),
+        TestAction::assert_eq("copy.bytes.buffer === copy.view.buffer", true),
+        TestAction::assert_eq("copy.bytes.buffer === buffer", false),
+        TestAction::assert_eq(
+            "copy.view.byteOffset + ',' + copy.view.byteLength",
+            js_string!("2,4"),
+        ),
+        TestAction::assert_eq(
+            "copy.floats instanceof Float32Array && copy.floats.length",
+            1,
+        ),
+        TestAction::assert_eq("copy.view.getUint8(0)", 3),
+        TestAction::run("copy.bytes[0] = 42;"),
+        TestAction::assert_eq("bytes[0]", 1),
+        TestAction::run("const moved = structuredClone(bytes, { transfer: [buffer] });"),
+        TestAction::assert_eq("buffer.byteLength", 0),
+        TestAction::assert_eq("moved.join()", js_string!("1,2,3,4,5,6,7,8")),
+        TestAction::assert_eq(
+            "try { structuredClone(bytes) } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+        TestAction::assert_eq(
+            indoc! {r#"
+                const twice = new ArrayBuffer(1);
+                try { structuredClone(null, { transfer: [twice, twice] }) } catch (e) { e.name }
+            "#},
+            js_string!("TypeError"),
+        ),
+        TestAction::assert_eq("twice.byteLength", 1),
+        TestAction::assert_eq(
+            "try { structuredClone(null, { transfer: [{}] }) } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+    ]);
+}
+
+/// Serializes a cyclic value from another context.
+fn serialized_state() -> Vec<u8> {
+    let source = &mut Context::default();
+    let value = source
+        .eval(Source::from_bytes(indoc! {r#"
+            const state = { list: [1, "two", 3n], map: new Map([["k", new Uint16Array([7])]]) };
+            state.list.push(state);
+            state
+        "#}))
+        .expect("the state should be created");
+    SerializedValue::serialize(&value, &[], source)
+        .expect("the state should be serializable")
+        .into_bytes()
+}
+
+#[test]
+fn serialize_into_another_context() {
+    run_test_actions([
+        TestAction::inspect_context(|context| {
+            let state = SerializedValue::from_bytes(serialized_state())
+                .deserialize(context)
+                .expect("the state should be deserialized");
+            context
+                .register_global_property(js_string!("state"), state, Attribute::all())
+                .expect("the state should be defined");
+        }),
+        TestAction::assert_eq("state.list[3] === state", true),
+        TestAction::assert_eq("state.list.slice(0, 3).join()", js_string!("1,two,3")),
+        TestAction::assert_eq("state.map.get('k') instanceof Uint16Array", true),
+        TestAction::assert_eq("state.map.get('k')[0]", 7),
+        // Malformed data is rejected instead of panicking.
+        TestAction::assert_context(|context| {
+            let bytes = serialized_state();
+            (0..bytes.len()).all(|len| {
+                SerializedValue::from_bytes(bytes[..len].to_vec())
+                    .deserialize(context)
+                    .is_err()
+            })
+        }),
+        TestAction::assert_context(|context| {
+            let mut trailing = serialized_state();
+            trailing.push(0);
+            SerializedValue::from_bytes(trailing)
+                .deserialize(context)
+                .is_err()
+        }),
+    ]);
+}
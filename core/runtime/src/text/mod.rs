@@ -0,0 +1,493 @@
+//! Boa's implementation of the `TextEncoder` and `TextDecoder` objects of the WHATWG Encoding
+//! API.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `Encoding` specification][spec]
+//!
+//! [spec]: https://encoding.spec.whatwg.org/
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Encoding_API
+
+#[cfg(test)]
+mod tests;
+
+use crate::util::{add_getter, buffer_source_bytes};
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::{
+        builtins::{JsArrayBuffer, JsUint8Array},
+        JsObject, ObjectInitializer,
+    },
+    property::Attribute,
+    string::CodePoint,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsString, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+
+/// The byte order mark, as a UTF-16 code unit.
+const BOM: u16 = 0xFEFF;
+
+/// The replacement character, emitted in place of invalid sequences.
+const REPLACEMENT: u16 = 0xFFFD;
+
+/// The `TextEncoder` class, which encodes strings into UTF-8 bytes.
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct TextEncoder;
+
+impl TextEncoder {
+    /// `get TextEncoder.prototype.encoding`
+    ///
+    /// Returns `"utf-8"`, the only encoding supported by `TextEncoder`.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Encoding` specification][spec]
+    ///
+    /// [spec]: https://encoding.spec.whatwg.org/#dom-textencoder-encoding
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TextEncoder/encoding
+    fn get_encoding(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        Self::this_encoder(this)?;
+        Ok(js_string!("utf-8").into())
+    }
+
+    /// `TextEncoder.prototype.encode(input = "")`
+    ///
+    /// Encodes input as UTF-8, returning the bytes in a new `Uint8Array`.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Encoding` specification][spec]
+    ///
+    /// [spec]: https://encoding.spec.whatwg.org/#dom-textencoder-encode
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TextEncoder/encode
+    fn encode(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::this_encoder(this)?;
+
+        let input = match args.get_or_undefined(0) {
+            JsValue::Undefined => js_string!(),
+            input => input.to_string(context)?,
+        };
+
+        let mut bytes = Vec::with_capacity(input.len());
+        let mut buf = [0; 4];
+        for code_point in input.code_points() {
+            bytes.extend_from_slice(to_scalar(code_point).encode_utf8(&mut buf).as_bytes());
+        }
+
+        Ok(JsUint8Array::from_iter(bytes, context)?.into())
+    }
+
+    /// `TextEncoder.prototype.encodeInto(source, destination)`
+    ///
+    /// Encodes source as UTF-8 into the destination `Uint8Array`, stopping before the first
+    /// character that doesn't fit. Returns an object with the number of UTF-16 code units `read`
+    /// from source and the number of bytes `written` into destination.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Encoding` specification][spec]
+    ///
+    /// [spec]: https://encoding.spec.whatwg.org/#dom-textencoder-encodeinto
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TextEncoder/encodeInto
+    fn encode_into(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::this_encoder(this)?;
+
+        let source = args.get_or_undefined(0).to_string(context)?;
+        let destination = args
+            .get_or_undefined(1)
+            .as_object()
+            .and_then(|object| JsUint8Array::from_object(object.clone()).ok())
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message("the destination is not a Uint8Array")
+            })?;
+
+        let offset = destination.byte_offset(context)?;
+        let length = destination.byte_length(context)?;
+        let buffer = destination
+            .buffer(context)?
+            .as_object()
+            .and_then(|buffer| JsArrayBuffer::from_object(buffer.clone()).ok())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("cannot encode into a Uint8Array backed by a SharedArrayBuffer")
+            })?;
+
+        let mut read = 0;
+        let mut written = 0;
+        if let Some(mut data) = buffer.data_mut() {
+            let destination = &mut data[offset..offset + length];
+            let mut buf = [0; 4];
+            for code_point in source.code_points() {
+                let c = to_scalar(code_point);
+                let bytes = c.encode_utf8(&mut buf).as_bytes();
+                let Some(slot) = destination.get_mut(written..written + bytes.len()) else {
+                    break;
+                };
+                slot.copy_from_slice(bytes);
+                read += code_point.code_unit_count();
+                written += bytes.len();
+            }
+        }
+
+        let result = ObjectInitializer::new(context)
+            .property(js_string!("read"), read, Attribute::all())
+            .property(js_string!("written"), written, Attribute::all())
+            .build();
+        Ok(result.into())
+    }
+
+    /// Checks that `this` is a `TextEncoder` object.
+    fn this_encoder(this: &JsValue) -> JsResult<()> {
+        if this.as_object().is_some_and(JsObject::is::<Self>) {
+            Ok(())
+        } else {
+            Err(JsNativeError::typ()
+                .with_message("'this' is not a TextEncoder object")
+                .into())
+        }
+    }
+}
+
+impl Class for TextEncoder {
+    const NAME: &'static str = "TextEncoder";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(class, js_string!("encoding"), Self::get_encoding);
+        class
+            .method(
+                js_string!("encode"),
+                0,
+                NativeFunction::from_fn_ptr(Self::encode),
+            )
+            .method(
+                js_string!("encodeInto"),
+                2,
+                NativeFunction::from_fn_ptr(Self::encode_into),
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<Self> {
+        Ok(Self)
+    }
+}
+
+/// Converts a code point into a Unicode scalar value, replacing unpaired surrogates by U+FFFD.
+fn to_scalar(code_point: CodePoint) -> char {
+    match code_point {
+        CodePoint::Unicode(c) => c,
+        CodePoint::UnpairedSurrogate(_) => char::REPLACEMENT_CHARACTER,
+    }
+}
+
+/// The encodings supported by `TextDecoder`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+}
+
+impl Encoding {
+    /// Gets the encoding for a label, as defined by the [specification][spec].
+    ///
+    /// [spec]: https://encoding.spec.whatwg.org/#concept-encoding-get
+    fn from_label(label: &str) -> Option<Self> {
+        let label = label
+            .trim_matches(|c| matches!(c, '\t' | '\n' | '\x0C' | '\r' | ' '))
+            .to_ascii_lowercase();
+        match label.as_str() {
+            "unicode-1-1-utf-8" | "unicode11utf8" | "unicode20utf8" | "utf-8" | "utf8"
+            | "x-unicode20utf8" => Some(Self::Utf8),
+            "csunicode" | "iso-10646-ucs-2" | "ucs-2" | "unicode" | "unicodefeff" | "utf-16"
+            | "utf-16le" => Some(Self::Utf16Le),
+            "unicodefffe" | "utf-16be" => Some(Self::Utf16Be),
+            _ => None,
+        }
+    }
+
+    /// The name of the encoding.
+    fn name(self) -> JsString {
+        match self {
+            Self::Utf8 => js_string!("utf-8"),
+            Self::Utf16Le => js_string!("utf-16le"),
+            Self::Utf16Be => js_string!("utf-16be"),
+        }
+    }
+}
+
+/// The `TextDecoder` class, which decodes UTF-8 and UTF-16 bytes into strings.
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct TextDecoder {
+    #[unsafe_ignore_trace]
+    encoding: Encoding,
+    fatal: bool,
+    ignore_bom: bool,
+    /// Whether any output was produced since the start of the stream.
+    bom_seen: bool,
+    /// The bytes of an incomplete sequence at the end of the last streamed chunk.
+    pending: Vec<u8>,
+}
+
+impl TextDecoder {
+    /// `get TextDecoder.prototype.encoding`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Encoding` specification][spec]
+    ///
+    /// [spec]: https://encoding.spec.whatwg.org/#dom-textdecoder-encoding
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/encoding
+    fn get_encoding(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let decoder = Self::this_decoder(this)?;
+        let encoding = decoder
+            .downcast_ref::<Self>()
+            .expect("checked above")
+            .encoding;
+        Ok(encoding.name().into())
+    }
+
+    /// `get TextDecoder.prototype.fatal`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Encoding` specification][spec]
+    ///
+    /// [spec]: https://encoding.spec.whatwg.org/#dom-textdecoder-fatal
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/fatal
+    fn get_fatal(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let decoder = Self::this_decoder(this)?;
+        let fatal = decoder.downcast_ref::<Self>().expect("checked above").fatal;
+        Ok(fatal.into())
+    }
+
+    /// `get TextDecoder.prototype.ignoreBOM`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Encoding` specification][spec]
+    ///
+    /// [spec]: https://encoding.spec.whatwg.org/#dom-textdecoder-ignorebom
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/ignoreBOM
+    fn get_ignore_bom(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let decoder = Self::this_decoder(this)?;
+        let ignore_bom = decoder
+            .downcast_ref::<Self>()
+            .expect("checked above")
+            .ignore_bom;
+        Ok(ignore_bom.into())
+    }
+
+    /// `TextDecoder.prototype.decode(input, options = {})`
+    ///
+    /// Decodes input into a string. If `options.stream` is `true`, an incomplete sequence at the
+    /// end of input is kept and prepended to the input of the next call.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `Encoding` specification][spec]
+    ///
+    /// [spec]: https://encoding.spec.whatwg.org/#dom-textdecoder-decode
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/TextDecoder/decode
+    fn decode(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let decoder = Self::this_decoder(this)?;
+
+        let input = match args.get_or_undefined(0) {
+            JsValue::Undefined => Vec::new(),
+            input => buffer_source_bytes(input, context)?,
+        };
+        let stream = match args.get_or_undefined(1) {
+            JsValue::Undefined | JsValue::Null => false,
+            JsValue::Object(options) => options.get(js_string!("stream"), context)?.to_boolean(),
+            _ => {
+                return Err(JsNativeError::typ()
+                    .with_message("the decode options must be an object")
+                    .into())
+            }
+        };
+
+        let mut decoder = decoder.downcast_mut::<Self>().expect("checked above");
+        let mut bytes = std::mem::take(&mut decoder.pending);
+        bytes.extend_from_slice(&input);
+
+        let result = match decoder.encoding {
+            Encoding::Utf8 => decoder.decode_utf8(&bytes, stream),
+            Encoding::Utf16Le => decoder.decode_utf16(&bytes, u16::from_le_bytes, stream),
+            Encoding::Utf16Be => decoder.decode_utf16(&bytes, u16::from_be_bytes, stream),
+        };
+
+        let Some(mut output) = result else {
+            decoder.pending.clear();
+            decoder.bom_seen = false;
+            return Err(JsNativeError::typ()
+                .with_message(format!(
+                    "the encoded data is not valid {}",
+                    decoder.encoding.name().to_std_string_escaped()
+                ))
+                .into());
+        };
+
+        if !decoder.bom_seen && !output.is_empty() {
+            decoder.bom_seen = true;
+            if !decoder.ignore_bom && output[0] == BOM {
+                output.remove(0);
+            }
+        }
+        if !stream {
+            decoder.pending.clear();
+            decoder.bom_seen = false;
+        }
+
+        Ok(JsString::from(output).into())
+    }
+
+    /// Decodes UTF-8 bytes into UTF-16 code units, returning `None` if an invalid sequence is
+    /// found and the decoder is fatal.
+    fn decode_utf8(&mut self, mut bytes: &[u8], stream: bool) -> Option<Vec<u16>> {
+        let mut output = Vec::with_capacity(bytes.len());
+        loop {
+            match std::str::from_utf8(bytes) {
+                Ok(valid) => {
+                    output.extend(valid.encode_utf16());
+                    return Some(output);
+                }
+                Err(error) => {
+                    let (valid, rest) = bytes.split_at(error.valid_up_to());
+                    output.extend(
+                        std::str::from_utf8(valid)
+                            .expect("prefix should be valid")
+                            .encode_utf16(),
+                    );
+
+                    if let Some(len) = error.error_len() {
+                        if self.fatal {
+                            return None;
+                        }
+                        output.push(REPLACEMENT);
+                        bytes = &rest[len..];
+                    } else {
+                        // The input ends in the middle of a sequence.
+                        if stream {
+                            self.pending = rest.to_vec();
+                        } else if self.fatal {
+                            return None;
+                        } else {
+                            output.push(REPLACEMENT);
+                        }
+                        return Some(output);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Decodes UTF-16 bytes into code units, returning `None` if an invalid sequence is found and
+    /// the decoder is fatal.
+    fn decode_utf16(
+        &mut self,
+        bytes: &[u8],
+        from_bytes: fn([u8; 2]) -> u16,
+        stream: bool,
+    ) -> Option<Vec<u16>> {
+        let mut end = bytes.len() - bytes.len() % 2;
+
+        // A trailing lead surrogate could be completed by the next chunk.
+        if stream
+            && end >= 2
+            && (0xD800..0xDC00).contains(&from_bytes([bytes[end - 2], bytes[end - 1]]))
+        {
+            end -= 2;
+        }
+        let (complete, rest) = bytes.split_at(end);
+        let units = complete
+            .chunks_exact(2)
+            .map(|pair| from_bytes([pair[0], pair[1]]));
+
+        let mut output = Vec::with_capacity(complete.len() / 2);
+        let mut invalid = false;
+        for unit in char::decode_utf16(units) {
+            if let Ok(c) = unit {
+                let mut buf = [0; 2];
+                output.extend_from_slice(c.encode_utf16(&mut buf));
+            } else {
+                invalid = true;
+                output.push(REPLACEMENT);
+            }
+        }
+
+        if stream {
+            self.pending = rest.to_vec();
+        } else if !rest.is_empty() {
+            invalid = true;
+            output.push(REPLACEMENT);
+        }
+
+        if invalid && self.fatal {
+            return None;
+        }
+        Some(output)
+    }
+
+    /// Checks that `this` is a `TextDecoder` object, returning it.
+    fn this_decoder(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a TextDecoder object")
+                    .into()
+            })
+    }
+}
+
+impl Class for TextDecoder {
+    const NAME: &'static str = "TextDecoder";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        add_getter(class, js_string!("encoding"), Self::get_encoding);
+        add_getter(class, js_string!("fatal"), Self::get_fatal);
+        add_getter(class, js_string!("ignoreBOM"), Self::get_ignore_bom);
+        class.method(
+            js_string!("decode"),
+            0,
+            NativeFunction::from_fn_ptr(Self::decode),
+        );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        let encoding = match args.get_or_undefined(0) {
+            JsValue::Undefined => Encoding::Utf8,
+            label => {
+                let label = label.to_string(context)?.to_std_string_escaped();
+                Encoding::from_label(&label).ok_or_else(|| {
+                    JsNativeError::range()
+                        .with_message(format!("the encoding label `{label}` is not supported"))
+                })?
+            }
+        };
+
+        let (fatal, ignore_bom) = match args.get_or_undefined(1) {
+            JsValue::Undefined | JsValue::Null => (false, false),
+            JsValue::Object(options) => (
+                options.get(js_string!("fatal"), context)?.to_boolean(),
+                options.get(js_string!("ignoreBOM"), context)?.to_boolean(),
+            ),
+            _ => {
+                return Err(JsNativeError::typ()
+                    .with_message("the TextDecoder options must be an object")
+                    .into())
+            }
+        };
+
+        Ok(Self {
+            encoding,
+            fatal,
+            ignore_bom,
+            bom_seen: false,
+            pending: Vec::new(),
+        })
+    }
+}
@@ -0,0 +1,123 @@
+use crate::test::{run_test_actions, TestAction};
+use boa_engine::js_string;
+use indoc::indoc;
+
+#[test]
+fn encoder_encode() {
+    run_test_actions([
+        TestAction::run("const encoder = new TextEncoder();"),
+        TestAction::assert_eq("encoder.encoding", js_string!("utf-8")),
+        TestAction::assert_eq("encoder.encode().length", 0),
+        TestAction::assert_eq(
+            "encoder.encode('a€😀').join()",
+            js_string!("97,226,130,172,240,159,152,128"),
+        ),
+        // Lone surrogates are replaced by U+FFFD.
+        TestAction::assert_eq(
+            "encoder.encode('\\uD800x').join()",
+            js_string!("239,191,189,120"),
+        ),
+    ]);
+}
+
+#[test]
+fn encoder_encode_into() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const buffer = new Uint8Array(8);
+            const view = buffer.subarray(2);
+            const { read, written } = new TextEncoder().encodeInto("ab😀c", view);
+        "#}),
+        TestAction::assert_eq("read", 4),
+        TestAction::assert_eq("written", 6),
+        TestAction::assert_eq("buffer.join()", js_string!("0,0,97,98,240,159,152,128")),
+        TestAction::assert_eq(
+            indoc! {r#"
+                try {
+                    new TextEncoder().encodeInto("a", new Uint16Array(1));
+                } catch (e) {
+                    e.name
+                }
+            "#},
+            js_string!("TypeError"),
+        ),
+    ]);
+}
+
+#[test]
+fn decoder_labels_and_options() {
+    run_test_actions([
+        TestAction::assert_eq("new TextDecoder().encoding", js_string!("utf-8")),
+        TestAction::assert_eq(
+            "new TextDecoder(' UTF-16 ').encoding",
+            js_string!("utf-16le"),
+        ),
+        TestAction::assert_eq(
+            "new TextDecoder('unicodeFFFE').encoding",
+            js_string!("utf-16be"),
+        ),
+        TestAction::assert_eq("new TextDecoder('utf8', { fatal: true }).fatal", true),
+        TestAction::assert_eq("new TextDecoder('utf8', { ignoreBOM: 1 }).ignoreBOM", true),
+        TestAction::assert_eq(
+            "try { new TextDecoder('latin2') } catch (e) { e.name }",
+            js_string!("RangeError"),
+        ),
+    ]);
+}
+
+#[test]
+fn decoder_decode() {
+    run_test_actions([
+        TestAction::run("const bytes = new Uint8Array([0xEF, 0xBB, 0xBF, 97, 0xE2, 0x82, 0xAC]);"),
+        TestAction::assert_eq("new TextDecoder().decode(bytes)", js_string!("a€")),
+        TestAction::assert_eq(
+            "new TextDecoder('utf-8', { ignoreBOM: true }).decode(bytes.buffer)",
+            js_string!("\u{FEFF}a€"),
+        ),
+        TestAction::assert_eq(
+            "new TextDecoder().decode(new DataView(bytes.buffer, 3, 1))",
+            js_string!("a"),
+        ),
+        TestAction::assert_eq(
+            "new TextDecoder().decode(new Uint8Array([97, 0xFF, 0xE2, 0x82]))",
+            js_string!("a\u{FFFD}\u{FFFD}"),
+        ),
+        TestAction::assert_eq(
+            indoc! {r#"
+                try {
+                    new TextDecoder("utf-8", { fatal: true }).decode(new Uint8Array([0xFF]));
+                } catch (e) {
+                    e.name
+                }
+            "#},
+            js_string!("TypeError"),
+        ),
+        TestAction::assert_eq(
+            "new TextDecoder('utf-16le').decode(new Uint8Array([0xFF, 0xFE, 0x3D, 0xD8, 0x00, 0xDE, 0x61]))",
+            js_string!("😀\u{FFFD}"),
+        ),
+        TestAction::assert_eq(
+            "new TextDecoder('utf-16be').decode(new Uint8Array([0, 97, 0xD8, 0]))",
+            js_string!("a\u{FFFD}"),
+        ),
+    ]);
+}
+
+#[test]
+fn decoder_streaming() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            const utf8 = new TextDecoder();
+            const utf16 = new TextDecoder("utf-16be");
+            const chunks = [
+                utf8.decode(new Uint8Array([0xEF, 0xBB]), { stream: true }),
+                utf8.decode(new Uint8Array([0xBF, 0xF0, 0x9F]), { stream: true }),
+                utf8.decode(new Uint8Array([0x98, 0x80, 0xE2]), { stream: true }),
+                utf8.decode(),
+                utf16.decode(new Uint8Array([0xD8, 0x3D, 0xDE]), { stream: true }),
+                utf16.decode(new Uint8Array([0x00])),
+            ];
+        "#}),
+        TestAction::assert_eq("chunks.join('|')", js_string!("||😀|\u{FFFD}||😀")),
+    ]);
+}
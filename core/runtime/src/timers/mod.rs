@@ -0,0 +1,198 @@
+//! Boa's implementation of the timer functions and `queueMicrotask` of the `WindowOrWorkerGlobalScope`
+//! Web API mixin.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [HTML specification][spec]
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#timers
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/setTimeout
+
+#[cfg(test)]
+mod tests;
+
+use crate::event_loop::{EventLoop, Timer, MIN_INTERVAL};
+use boa_engine::{
+    job::NativeJob, js_string, native_function::NativeFunction, object::builtins::JsFunction,
+    Context, JsArgs, JsNativeError, JsResult, JsValue,
+};
+use std::{rc::Rc, time::Duration};
+
+/// The largest delay accepted by the timer functions, in milliseconds.
+const MAX_DELAY: f64 = i32::MAX as f64;
+
+/// The `setTimeout`, `setInterval`, `clearTimeout`, `clearInterval` and `queueMicrotask` global
+/// functions.
+#[derive(Debug, Clone, Copy)]
+pub struct Timers;
+
+impl Timers {
+    /// Registers the timer functions as global functions of the context, scheduling their
+    /// callbacks on `event_loop`.
+    ///
+    /// `event_loop` should also be the [`JobQueue`] of the context, otherwise the timers will never
+    /// run.
+    ///
+    /// [`JobQueue`]: boa_engine::job::JobQueue
+    ///
+    /// # Errors
+    ///
+    /// This will return an error if any of the functions cannot be defined in the global object.
+    pub fn register(event_loop: &Rc<EventLoop>, context: &mut Context) -> JsResult<()> {
+        fn timer_method(
+            f: fn(&[JsValue], &EventLoop, &mut Context) -> JsResult<JsValue>,
+            event_loop: Rc<EventLoop>,
+        ) -> NativeFunction {
+            // SAFETY: `EventLoop` doesn't need to be traced, since all the objects it contains are
+            // rooted.
+            unsafe {
+                NativeFunction::from_closure(move |_, args, context| f(args, &event_loop, context))
+            }
+        }
+
+        context.register_global_builtin_callable(
+            js_string!("setTimeout"),
+            1,
+            timer_method(Self::set_timeout, event_loop.clone()),
+        )?;
+        context.register_global_builtin_callable(
+            js_string!("setInterval"),
+            1,
+            timer_method(Self::set_interval, event_loop.clone()),
+        )?;
+        context.register_global_builtin_callable(
+            js_string!("clearTimeout"),
+            0,
+            timer_method(Self::clear_timer, event_loop.clone()),
+        )?;
+        context.register_global_builtin_callable(
+            js_string!("clearInterval"),
+            0,
+            timer_method(Self::clear_timer, event_loop.clone()),
+        )?;
+        context.register_global_builtin_callable(
+            js_string!("queueMicrotask"),
+            1,
+            NativeFunction::from_fn_ptr(Self::queue_microtask),
+        )?;
+
+        Ok(())
+    }
+
+    /// `setTimeout(handler, timeout = 0, ...arguments)`
+    ///
+    /// Schedules `handler` to be called with `arguments` after `timeout` milliseconds, returning
+    /// the identifier of the timer.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#dom-settimeout
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/setTimeout
+    fn set_timeout(
+        args: &[JsValue],
+        event_loop: &EventLoop,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        Self::schedule(args, false, event_loop, context)
+    }
+
+    /// `setInterval(handler, timeout = 0, ...arguments)`
+    ///
+    /// Schedules `handler` to be called with `arguments` every `timeout` milliseconds, returning
+    /// the identifier of the timer.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#dom-setinterval
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/setInterval
+    fn set_interval(
+        args: &[JsValue],
+        event_loop: &EventLoop,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        Self::schedule(args, true, event_loop, context)
+    }
+
+    /// `clearTimeout(id = 0)` and `clearInterval(id = 0)`
+    ///
+    /// Cancels the timer with identifier `id`. Both functions share the same pool of
+    /// identifiers, so they can be used interchangeably.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#dom-cleartimeout
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/clearTimeout
+    fn clear_timer(
+        args: &[JsValue],
+        event_loop: &EventLoop,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let id = args.get_or_undefined(0).to_u32(context)?;
+        event_loop.remove_timer(id);
+        Ok(JsValue::undefined())
+    }
+
+    /// `queueMicrotask(callback)`
+    ///
+    /// Queues `callback` to be called on the next microtask checkpoint.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/timers-and-user-prompts.html#dom-queuemicrotask
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/queueMicrotask
+    fn queue_microtask(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let callback = callable_argument(args, "queueMicrotask")?;
+        context.enqueue_job(NativeJob::new(move |context| {
+            callback.call(&JsValue::undefined(), &[], context)
+        }));
+        Ok(JsValue::undefined())
+    }
+
+    /// Schedules a new timer from the arguments of `setTimeout` or `setInterval`.
+    fn schedule(
+        args: &[JsValue],
+        repeat: bool,
+        event_loop: &EventLoop,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let callback = callable_argument(args, if repeat { "setInterval" } else { "setTimeout" })?;
+
+        // Non-finite or negative delays are treated as `0`.
+        let delay = args.get_or_undefined(1).to_number(context)?;
+        let delay = if delay.is_finite() {
+            delay.clamp(0.0, MAX_DELAY)
+        } else {
+            0.0
+        };
+        let delay = Duration::from_secs_f64(delay / 1000.0);
+
+        let timer = Timer {
+            callback,
+            args: args.get(2..).unwrap_or_default().to_vec(),
+            interval: repeat.then_some(delay.max(MIN_INTERVAL)),
+        };
+
+        Ok(event_loop.add_timer(timer, delay).into())
+    }
+}
+
+/// Gets the first argument of `method` as a function, throwing a `TypeError` if it isn't callable.
+fn callable_argument(args: &[JsValue], method: &str) -> JsResult<JsFunction> {
+    args.get_or_undefined(0)
+        .as_callable()
+        .cloned()
+        .and_then(JsFunction::from_object)
+        .ok_or_else(|| {
+            JsNativeError::typ()
+                .with_message(format!("{method}: the callback must be a function"))
+                .into()
+        })
+}
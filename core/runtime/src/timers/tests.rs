@@ -0,0 +1,162 @@
+use crate::{
+    test::{run_test_actions, TestAction},
+    EventLoop,
+};
+use boa_engine::{js_string, object::builtins::JsPromise, Context};
+use indoc::indoc;
+use std::{rc::Rc, time::Duration};
+
+fn event_loop(context: &Context) -> Rc<EventLoop> {
+    EventLoop::of(context).expect("the test context runs on an event loop")
+}
+
+#[test]
+fn timers_run_in_order_after_microtasks() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            let log = [];
+            setTimeout(() => log.push("b"), 20);
+            setTimeout((x, y) => log.push(x + y), 10, "a", "!");
+            setTimeout(() => log.push("c"), 20);
+            Promise.resolve().then(() => log.push("promise"));
+            queueMicrotask(() => log.push("microtask"));
+            log.push("sync");
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("log.join()", js_string!("sync,promise,microtask,a!,b,c")),
+        TestAction::assert_context(|context| {
+            event_loop(context).now() == Duration::from_millis(20)
+        }),
+        TestAction::assert_context(|context| event_loop(context).is_empty()),
+    ]);
+}
+
+#[test]
+fn microtasks_run_between_timers() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            let log = [];
+            setTimeout(() => {
+                log.push("first");
+                Promise.resolve().then(() => log.push("first promise"));
+            });
+            setTimeout(() => log.push("second"));
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("log.join()", js_string!("first,first promise,second")),
+    ]);
+}
+
+#[test]
+fn clear_timers() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            let ticks = 0;
+            let timeout = setTimeout(() => { throw new Error("unreachable") }, 5);
+            clearTimeout(timeout);
+            let interval = setInterval(() => {
+                ticks += 1;
+                if (ticks === 3) {
+                    clearInterval(interval);
+                }
+            }, 10);
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("ticks", 3),
+        TestAction::assert_context(|context| {
+            event_loop(context).now() == Duration::from_millis(30)
+        }),
+    ]);
+}
+
+#[test]
+fn await_timer() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            let done = false;
+            (async () => {
+                await new Promise((resolve) => setTimeout(resolve, 1000));
+                done = true;
+            })();
+        "#}),
+        TestAction::assert_eq("done", false),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("done", true),
+    ]);
+}
+
+#[test]
+fn advance_virtual_time() {
+    run_test_actions([
+        TestAction::run("let fired = false; setTimeout(() => { fired = true; }, 100);"),
+        TestAction::inspect_context(|context| {
+            event_loop(context).advance(Duration::from_millis(50), context);
+        }),
+        TestAction::assert_context(|context| {
+            event_loop(context).now() == Duration::from_millis(50)
+        }),
+        TestAction::assert_eq("fired", false),
+        TestAction::inspect_context(|context| {
+            event_loop(context).advance(Duration::from_millis(50), context);
+        }),
+        TestAction::assert_eq("fired", true),
+        TestAction::assert_context(|context| event_loop(context).is_empty()),
+    ]);
+}
+
+#[test]
+fn run_until_settled() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            let late = false;
+            setTimeout(() => { late = true; }, 200);
+            let result;
+            var promise = new Promise((resolve) => setTimeout(() => resolve(42), 100));
+            promise.then((value) => { result = value; });
+        "#}),
+        TestAction::inspect_context(|context| {
+            let promise = context
+                .global_object()
+                .get(js_string!("promise"), context)
+                .expect("the promise is a global property");
+            let promise = promise
+                .as_object()
+                .and_then(|promise| JsPromise::from_object(promise.clone()).ok())
+                .expect("the global property is a promise");
+            event_loop(context).run_until_settled(&promise, context);
+        }),
+        TestAction::assert_eq("result", 42),
+        TestAction::assert_eq("late", false),
+        TestAction::assert_context(|context| {
+            event_loop(context).now() == Duration::from_millis(100)
+        }),
+        TestAction::assert_context(|context| !event_loop(context).is_empty()),
+    ]);
+}
+
+#[test]
+fn zero_delays_advance_virtual_time() {
+    run_test_actions([
+        TestAction::run(indoc! {r#"
+            let intervals = 0;
+            setInterval(() => { intervals++; }, 0);
+            let timeouts = 0;
+            (function chain() {
+                setTimeout(() => {
+                    timeouts++;
+                    chain();
+                }, 0);
+            })();
+        "#}),
+        // Intervals run at least 1 ms apart, and deeply nested timers at least 4 ms apart, so the
+        // clock keeps advancing.
+        TestAction::inspect_context(|context| {
+            event_loop(context).advance(Duration::from_millis(10), context);
+        }),
+        TestAction::assert_context(|context| {
+            event_loop(context).now() == Duration::from_millis(10)
+        }),
+        TestAction::assert_eq("intervals", 6),
+        TestAction::assert_eq("timeouts", 7),
+    ]);
+}
@@ -0,0 +1,371 @@
+//! Boa's implementation of the `URL` and `URLSearchParams` objects of the WHATWG URL API.
+//!
+//! Parsing and serialization are delegated to the [`url`] crate, which implements the
+//! [URL Standard][spec].
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `URL` specification][spec]
+//!
+//! [spec]: https://url.spec.whatwg.org/
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URL_API
+
+#[cfg(test)]
+mod tests;
+
+mod search_params;
+
+#[doc(inline)]
+pub use search_params::UrlSearchParams;
+
+pub(crate) use search_params::pairs_to_list;
+
+use crate::util::to_usv_string;
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::JsObject,
+    property::Attribute,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsString, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+
+use url::quirks;
+
+/// Parses `input` against the optional `base` URL, as defined by the [API URL parser][spec].
+///
+/// [spec]: https://url.spec.whatwg.org/#api-url-parser
+fn parse_url(input: &str, base: Option<&str>) -> Option<url::Url> {
+    let base = match base {
+        Some(base) => Some(url::Url::parse(base).ok()?),
+        None => None,
+    };
+    url::Url::options()
+        .base_url(base.as_ref())
+        .parse(input)
+        .ok()
+}
+
+/// Gets the arguments of the `URL` constructor, `URL.canParse` and `URL.parse` as strings.
+fn url_and_base(args: &[JsValue], context: &mut Context) -> JsResult<(String, Option<String>)> {
+    let input = to_usv_string(args.get_or_undefined(0), context)?;
+    let base = match args.get_or_undefined(1) {
+        JsValue::Undefined => None,
+        base => Some(to_usv_string(base, context)?),
+    };
+    Ok((input, base))
+}
+
+/// The `URL` class, which represents a parsed URL.
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct Url {
+    #[unsafe_ignore_trace]
+    inner: url::Url,
+    /// The `URLSearchParams` object returned by `searchParams`, created on first access.
+    search_params: Option<JsObject>,
+}
+
+impl Url {
+    /// Returns the parsed URL.
+    #[must_use]
+    pub const fn inner(&self) -> &url::Url {
+        &self.inner
+    }
+
+    /// `URL.canParse(url, base)`
+    ///
+    /// Returns `true` if `url` can be parsed against `base`.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-url-canparse
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URL/canParse_static
+    fn can_parse(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let (input, base) = url_and_base(args, context)?;
+        Ok(parse_url(&input, base.as_deref()).is_some().into())
+    }
+
+    /// `URL.parse(url, base)`
+    ///
+    /// Returns a new `URL` object, or `null` if `url` cannot be parsed against `base`.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-url-parse
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URL/parse_static
+    fn parse(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let (input, base) = url_and_base(args, context)?;
+        let Some(inner) = parse_url(&input, base.as_deref()) else {
+            return Ok(JsValue::null());
+        };
+        let url = Self::from_data(
+            Self {
+                inner,
+                search_params: None,
+            },
+            context,
+        )?;
+        Ok(url.into())
+    }
+
+    /// `get URL.prototype.origin`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-url-origin
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URL/origin
+    fn get_origin(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_url(this)?;
+        let url = object.downcast_ref::<Self>().expect("checked above");
+        Ok(JsString::from(quirks::origin(&url.inner)).into())
+    }
+
+    /// `get URL.prototype.searchParams`
+    ///
+    /// Returns the `URLSearchParams` object for the query of this URL. The object is always the
+    /// same, and updating it updates the URL.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-url-searchparams
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URL/searchParams
+    fn get_search_params(
+        this: &JsValue,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let object = Self::this_url(this)?;
+        let search_params = object
+            .downcast_ref::<Self>()
+            .expect("checked above")
+            .search_params
+            .clone();
+        if let Some(search_params) = search_params {
+            return Ok(search_params.into());
+        }
+
+        let search_params = UrlSearchParams::from_data(UrlSearchParams::for_url(object), context)?;
+        object
+            .downcast_mut::<Self>()
+            .expect("checked above")
+            .search_params = Some(search_params.clone());
+        Ok(search_params.into())
+    }
+
+    /// `URL.prototype.toString()` and `URL.prototype.toJSON()`
+    ///
+    /// Returns the serialization of this URL, the same as `href`.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-url-href
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URL/toString
+    fn to_string(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_url(this)?;
+        let url = object.downcast_ref::<Self>().expect("checked above");
+        Ok(JsString::from(url.inner.as_str()).into())
+    }
+
+    /// `set URL.prototype.href`
+    ///
+    /// Unlike the other setters, this throws a `TypeError` if the new value cannot be parsed.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-url-href
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URL/href
+    fn set_href(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_url(this)?;
+        let href = to_usv_string(args.get_or_undefined(0), context)?;
+        let mut url = object.downcast_mut::<Self>().expect("checked above");
+        quirks::set_href(&mut url.inner, &href).map_err(|error| {
+            JsNativeError::typ().with_message(format!("invalid URL `{href}`: {error}"))
+        })?;
+        Ok(JsValue::undefined())
+    }
+
+    /// Checks that `this` is a `URL` object, returning it.
+    fn this_url(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a URL object")
+                    .into()
+            })
+    }
+}
+
+/// Adds an accessor named `name` to the prototype of the `URL` class, reading the component with
+/// `get` and updating it with `set`.
+fn add_component(
+    class: &mut ClassBuilder<'_>,
+    name: JsString,
+    get: fn(&url::Url) -> &str,
+    set: fn(&mut url::Url, &str),
+) {
+    let realm = class.context().realm().clone();
+    let getter = NativeFunction::from_copy_closure(move |this, _, _| {
+        let object = Url::this_url(this)?;
+        let url = object.downcast_ref::<Url>().expect("checked above");
+        Ok(JsString::from(get(&url.inner)).into())
+    })
+    .to_js_function(&realm);
+    let setter = NativeFunction::from_copy_closure(move |this, args, context| {
+        let object = Url::this_url(this)?;
+        let value = to_usv_string(args.get_or_undefined(0), context)?;
+        set(
+            &mut object.downcast_mut::<Url>().expect("checked above").inner,
+            &value,
+        );
+        Ok(JsValue::undefined())
+    })
+    .to_js_function(&realm);
+    class.accessor(
+        name,
+        Some(getter),
+        Some(setter),
+        Attribute::CONFIGURABLE | Attribute::ENUMERABLE,
+    );
+}
+
+impl Class for Url {
+    const NAME: &'static str = "URL";
+    const LENGTH: usize = 1;
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        let realm = class.context().realm().clone();
+        let href = NativeFunction::from_fn_ptr(Self::to_string).to_js_function(&realm);
+        let set_href = NativeFunction::from_fn_ptr(Self::set_href).to_js_function(&realm);
+        class.accessor(
+            js_string!("href"),
+            Some(href),
+            Some(set_href),
+            Attribute::CONFIGURABLE | Attribute::ENUMERABLE,
+        );
+
+        let origin = NativeFunction::from_fn_ptr(Self::get_origin).to_js_function(&realm);
+        class.accessor(
+            js_string!("origin"),
+            Some(origin),
+            None,
+            Attribute::CONFIGURABLE | Attribute::ENUMERABLE,
+        );
+
+        // Failures of the component setters are ignored, as required by the specification.
+        add_component(
+            class,
+            js_string!("protocol"),
+            quirks::protocol,
+            |url, value| {
+                let _ = quirks::set_protocol(url, value);
+            },
+        );
+        add_component(
+            class,
+            js_string!("username"),
+            quirks::username,
+            |url, value| {
+                let _ = quirks::set_username(url, value);
+            },
+        );
+        add_component(
+            class,
+            js_string!("password"),
+            quirks::password,
+            |url, value| {
+                let _ = quirks::set_password(url, value);
+            },
+        );
+        add_component(class, js_string!("host"), quirks::host, |url, value| {
+            let _ = quirks::set_host(url, value);
+        });
+        add_component(
+            class,
+            js_string!("hostname"),
+            quirks::hostname,
+            |url, value| {
+                let _ = quirks::set_hostname(url, value);
+            },
+        );
+        add_component(class, js_string!("port"), quirks::port, |url, value| {
+            // The `url` crate removes the port if the value doesn't start with a digit, but
+            // the specification only does that for the empty string.
+            if value.is_empty() || value.starts_with(|c: char| c.is_ascii_digit()) {
+                let _ = quirks::set_port(url, value);
+            }
+        });
+        add_component(
+            class,
+            js_string!("pathname"),
+            quirks::pathname,
+            quirks::set_pathname,
+        );
+        add_component(
+            class,
+            js_string!("search"),
+            quirks::search,
+            quirks::set_search,
+        );
+        add_component(class, js_string!("hash"), quirks::hash, quirks::set_hash);
+
+        let search_params =
+            NativeFunction::from_fn_ptr(Self::get_search_params).to_js_function(&realm);
+        class.accessor(
+            js_string!("searchParams"),
+            Some(search_params),
+            None,
+            Attribute::CONFIGURABLE | Attribute::ENUMERABLE,
+        );
+
+        class
+            .method(
+                js_string!("toString"),
+                0,
+                NativeFunction::from_fn_ptr(Self::to_string),
+            )
+            .method(
+                js_string!("toJSON"),
+                0,
+                NativeFunction::from_fn_ptr(Self::to_string),
+            )
+            .static_method(
+                js_string!("canParse"),
+                1,
+                NativeFunction::from_fn_ptr(Self::can_parse),
+            )
+            .static_method(
+                js_string!("parse"),
+                1,
+                NativeFunction::from_fn_ptr(Self::parse),
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        let (input, base) = url_and_base(args, context)?;
+        let inner = parse_url(&input, base.as_deref()).ok_or_else(|| {
+            JsNativeError::typ().with_message(match &base {
+                Some(base) => format!("invalid URL `{input}` with base `{base}`"),
+                None => format!("invalid URL `{input}`"),
+            })
+        })?;
+        Ok(Self {
+            inner,
+            search_params: None,
+        })
+    }
+}
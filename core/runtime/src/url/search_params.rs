@@ -0,0 +1,549 @@
+//! Boa's implementation of the `URLSearchParams` object.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [WHATWG `URL` specification][spec]
+//!
+//! [spec]: https://url.spec.whatwg.org/#interface-urlsearchparams
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams
+
+use super::Url;
+use crate::util::{iterable_to_list, to_usv_string};
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::{builtins::JsArray, JsObject},
+    property::{Attribute, PropertyKey},
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsString, JsSymbol, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use url::form_urlencoded;
+
+/// The kind of values returned by the iterators of `URLSearchParams`.
+#[derive(Debug, Clone, Copy)]
+enum IterationKind {
+    Key,
+    Value,
+    KeyAndValue,
+}
+
+/// The `URLSearchParams` class, which represents a list of name-value pairs in the
+/// `application/x-www-form-urlencoded` format.
+#[derive(Debug, Clone, Trace, Finalize, JsData)]
+pub struct UrlSearchParams {
+    list: Vec<(String, String)>,
+    /// The `URL` object whose query is represented by this object, if any.
+    ///
+    /// If present, `list` is unused and the pairs are read from and written into the query of the
+    /// URL instead.
+    url: Option<JsObject>,
+}
+
+impl UrlSearchParams {
+    /// Creates the `URLSearchParams` data for the query of the `URL` object `url`.
+    pub(super) fn for_url(url: &JsObject) -> Self {
+        Self {
+            list: Vec::new(),
+            url: Some(url.clone()),
+        }
+    }
+
+    /// Returns a copy of the name-value pairs of a `URLSearchParams` object.
+    fn list(object: &JsObject) -> Vec<(String, String)> {
+        let params = object
+            .downcast_ref::<Self>()
+            .expect("must be a URLSearchParams");
+        match &params.url {
+            Some(url) => {
+                let url = url.downcast_ref::<Url>().expect("must be a URL");
+                parse(url.inner.query().unwrap_or_default())
+            }
+            None => params.list.clone(),
+        }
+    }
+
+    /// Updates the name-value pairs of a `URLSearchParams` object with `f`, serializing them
+    /// back into the query of its URL if there is one.
+    fn update(object: &JsObject, f: impl FnOnce(&mut Vec<(String, String)>)) {
+        let url = object
+            .downcast_ref::<Self>()
+            .expect("must be a URLSearchParams")
+            .url
+            .clone();
+        let Some(url) = url else {
+            f(&mut object
+                .downcast_mut::<Self>()
+                .expect("must be a URLSearchParams")
+                .list);
+            return;
+        };
+
+        let mut url = url.downcast_mut::<Url>().expect("must be a URL");
+        let mut list = parse(url.inner.query().unwrap_or_default());
+        f(&mut list);
+        if list.is_empty() {
+            url.inner.set_query(None);
+        } else {
+            url.inner.set_query(Some(&serialize(&list)));
+        }
+    }
+
+    /// Serializes the name-value pairs of a `URLSearchParams` object in the
+    /// `application/x-www-form-urlencoded` format.
+    pub(crate) fn serialize(object: &JsObject) -> String {
+        serialize(&Self::list(object))
+    }
+
+    /// `get URLSearchParams.prototype.size`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-urlsearchparams-size
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams/size
+    fn get_size(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_params(this)?;
+        Ok(Self::list(object).len().into())
+    }
+
+    /// `URLSearchParams.prototype.append(name, value)`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-urlsearchparams-append
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams/append
+    fn append(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_params(this)?;
+        let name = to_usv_string(args.get_or_undefined(0), context)?;
+        let value = to_usv_string(args.get_or_undefined(1), context)?;
+        Self::update(object, |list| list.push((name, value)));
+        Ok(JsValue::undefined())
+    }
+
+    /// `URLSearchParams.prototype.delete(name, value)`
+    ///
+    /// Removes all the pairs with the given name and, if present, value.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-urlsearchparams-delete
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams/delete
+    fn delete(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_params(this)?;
+        let (name, value) = name_and_value(args, context)?;
+        Self::update(object, |list| {
+            list.retain(|(n, v)| !(*n == name && value.as_ref().map_or(true, |value| v == value)));
+        });
+        Ok(JsValue::undefined())
+    }
+
+    /// `URLSearchParams.prototype.get(name)`
+    ///
+    /// Returns the value of the first pair with the given name, or `null` if there is none.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-urlsearchparams-get
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams/get
+    fn get(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_params(this)?;
+        let name = to_usv_string(args.get_or_undefined(0), context)?;
+        Ok(Self::list(object)
+            .into_iter()
+            .find(|(n, _)| *n == name)
+            .map_or_else(JsValue::null, |(_, v)| JsString::from(v).into()))
+    }
+
+    /// `URLSearchParams.prototype.getAll(name)`
+    ///
+    /// Returns the values of all the pairs with the given name.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-urlsearchparams-getall
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams/getAll
+    fn get_all(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_params(this)?;
+        let name = to_usv_string(args.get_or_undefined(0), context)?;
+        let values = Self::list(object)
+            .into_iter()
+            .filter(|(n, _)| *n == name)
+            .map(|(_, v)| JsString::from(v).into());
+        Ok(JsArray::from_iter(values, context).into())
+    }
+
+    /// `URLSearchParams.prototype.has(name, value)`
+    ///
+    /// Returns `true` if there is a pair with the given name and, if present, value.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-urlsearchparams-has
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams/has
+    fn has(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_params(this)?;
+        let (name, value) = name_and_value(args, context)?;
+        let found = Self::list(object)
+            .iter()
+            .any(|(n, v)| *n == name && value.as_ref().map_or(true, |value| v == value));
+        Ok(found.into())
+    }
+
+    /// `URLSearchParams.prototype.set(name, value)`
+    ///
+    /// Sets the value of the first pair with the given name, removing all the others, or appends
+    /// a new pair if there is none.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-urlsearchparams-set
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams/set
+    fn set(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_params(this)?;
+        let name = to_usv_string(args.get_or_undefined(0), context)?;
+        let value = to_usv_string(args.get_or_undefined(1), context)?;
+        Self::update(object, |list| {
+            let mut found = false;
+            list.retain_mut(|(n, v)| {
+                if *n != name {
+                    return true;
+                }
+                if found {
+                    return false;
+                }
+                found = true;
+                v.clone_from(&value);
+                true
+            });
+            if !found {
+                list.push((name, value));
+            }
+        });
+        Ok(JsValue::undefined())
+    }
+
+    /// `URLSearchParams.prototype.sort()`
+    ///
+    /// Sorts the pairs by name, comparing code units and preserving the relative order of pairs
+    /// with the same name.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#dom-urlsearchparams-sort
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams/sort
+    fn sort(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_params(this)?;
+        Self::update(object, |list| {
+            list.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+        });
+        Ok(JsValue::undefined())
+    }
+
+    /// `URLSearchParams.prototype.toString()`
+    ///
+    /// Serializes the pairs in the `application/x-www-form-urlencoded` format.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#urlsearchparams-stringification-behavior
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams/toString
+    fn to_string(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_params(this)?;
+        Ok(JsString::from(Self::serialize(object)).into())
+    }
+
+    /// `URLSearchParams.prototype.forEach(callbackFn, thisArg)`
+    ///
+    /// Calls `callbackFn` with the value, the name and the object of each pair.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://webidl.spec.whatwg.org/#es-forEach
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams/forEach
+    fn for_each(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_params(this)?;
+        let callback = args
+            .get_or_undefined(0)
+            .as_callable()
+            .ok_or_else(|| JsNativeError::typ().with_message("the callback must be a function"))?;
+        let this_arg = args.get_or_undefined(1);
+
+        // The list is read again on each step, so that the callback observes its own changes.
+        let mut index = 0;
+        while let Some((name, value)) = Self::list(object).into_iter().nth(index) {
+            callback.call(
+                this_arg,
+                &[
+                    JsString::from(value).into(),
+                    JsString::from(name).into(),
+                    this.clone(),
+                ],
+                context,
+            )?;
+            index += 1;
+        }
+        Ok(JsValue::undefined())
+    }
+
+    /// Returns an iterator over a snapshot of the pairs of `this`.
+    fn iterator(this: &JsValue, kind: IterationKind, context: &mut Context) -> JsResult<JsValue> {
+        let object = Self::this_params(this)?;
+        let values = Self::list(object)
+            .into_iter()
+            .map(|(name, value)| match kind {
+                IterationKind::Key => JsString::from(name).into(),
+                IterationKind::Value => JsString::from(value).into(),
+                IterationKind::KeyAndValue => JsArray::from_iter(
+                    [JsString::from(name).into(), JsString::from(value).into()],
+                    context,
+                )
+                .into(),
+            })
+            .collect::<Vec<JsValue>>();
+        let array = JsArray::from_iter(values, context);
+        let values = context.intrinsics().objects().array_prototype_values();
+        values.call(&array.into(), &[], context)
+    }
+
+    /// `URLSearchParams.prototype.entries()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#interface-urlsearchparams
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams/entries
+    fn entries(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::iterator(this, IterationKind::KeyAndValue, context)
+    }
+
+    /// `URLSearchParams.prototype.keys()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#interface-urlsearchparams
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams/keys
+    fn keys(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::iterator(this, IterationKind::Key, context)
+    }
+
+    /// `URLSearchParams.prototype.values()`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [WHATWG `URL` specification][spec]
+    ///
+    /// [spec]: https://url.spec.whatwg.org/#interface-urlsearchparams
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams/values
+    fn values(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        Self::iterator(this, IterationKind::Value, context)
+    }
+
+    /// Checks that `this` is a `URLSearchParams` object, returning it.
+    fn this_params(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a URLSearchParams object")
+                    .into()
+            })
+    }
+}
+
+/// Parses an `application/x-www-form-urlencoded` string into a list of name-value pairs.
+fn parse(input: &str) -> Vec<(String, String)> {
+    form_urlencoded::parse(input.as_bytes())
+        .into_owned()
+        .collect()
+}
+
+/// Serializes a list of name-value pairs in the `application/x-www-form-urlencoded` format.
+fn serialize(list: &[(String, String)]) -> String {
+    form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(list)
+        .finish()
+}
+
+/// Gets the `name` and optional `value` arguments of `delete` and `has`.
+fn name_and_value(args: &[JsValue], context: &mut Context) -> JsResult<(String, Option<String>)> {
+    let name = to_usv_string(args.get_or_undefined(0), context)?;
+    let value = match args.get_or_undefined(1) {
+        JsValue::Undefined => None,
+        value => Some(to_usv_string(value, context)?),
+    };
+    Ok((name, value))
+}
+
+/// Converts a sequence of name-value pairs, iterated with `method`, into a list.
+fn sequence_to_list(
+    init: &JsValue,
+    method: JsObject,
+    context: &mut Context,
+) -> JsResult<Vec<(String, String)>> {
+    iterable_to_list(
+        init,
+        Some(method),
+        |pair, context| {
+            let pair = iterable_to_list(
+                &pair,
+                None,
+                |value, context| to_usv_string(&value, context),
+                context,
+            )?;
+            let [name, value] = <[String; 2]>::try_from(pair).map_err(|_| {
+                JsNativeError::typ()
+                    .with_message("each name-value pair must have exactly two items")
+            })?;
+            Ok((name, value))
+        },
+        context,
+    )
+}
+
+/// Converts a record of names to values, the enumerable own string properties of `object`, into
+/// a list.
+fn record_to_list(object: &JsObject, context: &mut Context) -> JsResult<Vec<(String, String)>> {
+    let mut list = Vec::new();
+    for key in object.own_property_keys(context)? {
+        if matches!(key, PropertyKey::Symbol(_)) {
+            continue;
+        }
+        let enumerable = object
+            .own_property(key.clone(), context)?
+            .is_some_and(|desc| desc.expect_enumerable());
+        if enumerable {
+            let value = object.get(key.clone(), context)?;
+            list.push((key.to_string(), to_usv_string(&value, context)?));
+        }
+    }
+    Ok(list)
+}
+
+/// Converts an object that is either a sequence of name-value pairs or a record of names to
+/// values into a list.
+pub(crate) fn pairs_to_list(
+    object: &JsObject,
+    context: &mut Context,
+) -> JsResult<Vec<(String, String)>> {
+    let method = object.get(JsSymbol::iterator(), context)?;
+    if method.is_null_or_undefined() {
+        return record_to_list(object, context);
+    }
+    let method = method.as_callable().cloned().ok_or_else(|| {
+        JsNativeError::typ().with_message("the iterator method must be a function")
+    })?;
+    sequence_to_list(&object.clone().into(), method, context)
+}
+
+impl Class for UrlSearchParams {
+    const NAME: &'static str = "URLSearchParams";
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        let realm = class.context().realm().clone();
+        let size = NativeFunction::from_fn_ptr(Self::get_size).to_js_function(&realm);
+        let entries = NativeFunction::from_fn_ptr(Self::entries).to_js_function(&realm);
+        class
+            .accessor(
+                js_string!("size"),
+                Some(size),
+                None,
+                Attribute::CONFIGURABLE | Attribute::ENUMERABLE,
+            )
+            .method(
+                js_string!("append"),
+                2,
+                NativeFunction::from_fn_ptr(Self::append),
+            )
+            .method(
+                js_string!("delete"),
+                1,
+                NativeFunction::from_fn_ptr(Self::delete),
+            )
+            .method(js_string!("get"), 1, NativeFunction::from_fn_ptr(Self::get))
+            .method(
+                js_string!("getAll"),
+                1,
+                NativeFunction::from_fn_ptr(Self::get_all),
+            )
+            .method(js_string!("has"), 1, NativeFunction::from_fn_ptr(Self::has))
+            .method(js_string!("set"), 2, NativeFunction::from_fn_ptr(Self::set))
+            .method(
+                js_string!("sort"),
+                0,
+                NativeFunction::from_fn_ptr(Self::sort),
+            )
+            .method(
+                js_string!("toString"),
+                0,
+                NativeFunction::from_fn_ptr(Self::to_string),
+            )
+            .method(
+                js_string!("forEach"),
+                1,
+                NativeFunction::from_fn_ptr(Self::for_each),
+            )
+            .property(
+                js_string!("entries"),
+                entries.clone(),
+                Attribute::WRITABLE | Attribute::CONFIGURABLE | Attribute::ENUMERABLE,
+            )
+            .method(
+                js_string!("keys"),
+                0,
+                NativeFunction::from_fn_ptr(Self::keys),
+            )
+            .method(
+                js_string!("values"),
+                0,
+                NativeFunction::from_fn_ptr(Self::values),
+            )
+            .property(
+                JsSymbol::iterator(),
+                entries,
+                Attribute::WRITABLE | Attribute::CONFIGURABLE,
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("URLSearchParams"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        let init = args.get_or_undefined(0);
+        let list = match init {
+            JsValue::Undefined => Vec::new(),
+            JsValue::Object(object) => pairs_to_list(object, context)?,
+            init => {
+                let init = to_usv_string(init, context)?;
+                parse(init.strip_prefix('?').unwrap_or(&init))
+            }
+        };
+
+        Ok(Self { list, url: None })
+    }
+}
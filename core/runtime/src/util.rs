@@ -0,0 +1,155 @@
+//! Helpers shared by the Web APIs of the runtime, mostly conversions of the Web IDL
+//! specification.
+//!
+//! More information:
+//!  - [Web IDL specification][spec]
+//!
+//! [spec]: https://webidl.spec.whatwg.org/
+
+use boa_engine::{
+    class::ClassBuilder,
+    js_string,
+    native_function::NativeFunction,
+    object::{
+        builtins::{JsArrayBuffer, JsDataView, JsTypedArray},
+        JsObject,
+    },
+    property::Attribute,
+    Context, JsError, JsNativeError, JsResult, JsString, JsSymbol, JsValue,
+};
+
+/// Adds a getter named `name` to the prototype of a class.
+pub(crate) fn add_getter(
+    class: &mut ClassBuilder<'_>,
+    name: JsString,
+    getter: fn(&JsValue, &[JsValue], &mut Context) -> JsResult<JsValue>,
+) {
+    let realm = class.context().realm().clone();
+    let getter = NativeFunction::from_fn_ptr(getter).to_js_function(&realm);
+    class.accessor(name, Some(getter), None, Attribute::CONFIGURABLE);
+}
+
+/// Converts a value into a Rust string, following the conversion to a `USVString` of Web IDL.
+///
+/// Unpaired surrogates are replaced by U+FFFD.
+pub(crate) fn to_usv_string(value: &JsValue, context: &mut Context) -> JsResult<String> {
+    Ok(String::from_utf16_lossy(&value.to_string(context)?))
+}
+
+/// Collects the values of `iterable` into a list, iterating it with `method` if given, or with
+/// its `@@iterator` method otherwise, following the conversion to a `sequence` of Web IDL.
+///
+/// Each value is passed through `convert` as soon as it is produced. If a conversion fails,
+/// the iterator is closed before the error is returned.
+///
+/// More information:
+///  - [Web IDL specification][spec]
+///
+/// [spec]: https://webidl.spec.whatwg.org/#create-sequence-from-iterable
+pub(crate) fn iterable_to_list<T>(
+    iterable: &JsValue,
+    method: Option<JsObject>,
+    mut convert: impl FnMut(JsValue, &mut Context) -> JsResult<T>,
+    context: &mut Context,
+) -> JsResult<Vec<T>> {
+    let method = match method {
+        Some(method) => method,
+        None => iterable
+            .to_object(context)?
+            .get(JsSymbol::iterator(), context)?
+            .as_callable()
+            .cloned()
+            .ok_or_else(|| {
+                JsNativeError::typ().with_message(format!(
+                    "value with type `{}` is not iterable",
+                    iterable.type_of()
+                ))
+            })?,
+    };
+    let iterator = method.call(iterable, &[], context)?;
+    let iterator = iterator
+        .as_object()
+        .ok_or_else(|| JsNativeError::typ().with_message("returned iterator is not an object"))?;
+    let next = iterator.get(js_string!("next"), context)?;
+    let next = next
+        .as_callable()
+        .ok_or_else(|| JsNativeError::typ().with_message("the next method is not callable"))?;
+
+    let mut values = Vec::new();
+    loop {
+        let result = next.call(&iterator.clone().into(), &[], context)?;
+        let result = result.as_object().ok_or_else(|| {
+            JsNativeError::typ().with_message("the iterator result is not an object")
+        })?;
+        if result.get(js_string!("done"), context)?.to_boolean() {
+            return Ok(values);
+        }
+        let value = result.get(js_string!("value"), context)?;
+        match convert(value, context) {
+            Ok(value) => values.push(value),
+            Err(error) => return Err(close_iterator(iterator, error, context)),
+        }
+    }
+}
+
+/// Closes `iterator` after an abrupt completion with `error`, as defined by the
+/// [`IteratorClose`][spec] abstract operation.
+///
+/// The original error always takes precedence over any error thrown while closing.
+///
+/// [spec]: https://tc39.es/ecma262/#sec-iteratorclose
+fn close_iterator(iterator: &JsObject, error: JsError, context: &mut Context) -> JsError {
+    if let Some(close) = iterator
+        .get(js_string!("return"), context)
+        .ok()
+        .and_then(|close| close.as_callable().cloned())
+    {
+        // Step 5 of `IteratorClose` returns the throw completion regardless of the outcome of
+        // calling `return`.
+        close.call(&iterator.clone().into(), &[], context).ok();
+    }
+    error
+}
+
+/// Copies the bytes viewed by a `BufferSource` (an `ArrayBuffer`, a typed array or a
+/// `DataView`).
+///
+/// Shared and detached buffers are viewed as empty.
+pub(crate) fn buffer_source_bytes(value: &JsValue, context: &mut Context) -> JsResult<Vec<u8>> {
+    let invalid = || {
+        JsNativeError::typ()
+            .with_message("the provided value is not an ArrayBuffer, a TypedArray or a DataView")
+    };
+
+    let object = value.as_object().ok_or_else(invalid)?;
+    let (buffer, offset, length) = if let Ok(buffer) = JsArrayBuffer::from_object(object.clone()) {
+        let length = buffer.byte_length(context);
+        (JsValue::from(buffer), 0, length)
+    } else if let Ok(array) = JsTypedArray::from_object(object.clone()) {
+        (
+            array.buffer(context)?,
+            array.byte_offset(context)?,
+            array.byte_length(context)?,
+        )
+    } else if let Ok(view) = JsDataView::from_object(object.clone()) {
+        (
+            view.buffer(context)?,
+            usize::try_from(view.byte_offset(context)?).map_err(|_| invalid())?,
+            usize::try_from(view.byte_length(context)?).map_err(|_| invalid())?,
+        )
+    } else {
+        return Err(invalid().into());
+    };
+
+    let Some(buffer) = buffer
+        .as_object()
+        .and_then(|buffer| JsArrayBuffer::from_object(buffer.clone()).ok())
+    else {
+        return Ok(Vec::new());
+    };
+    let bytes = buffer
+        .data()
+        .and_then(|data| data.get(offset..offset + length).map(<[u8]>::to_vec))
+        .unwrap_or_default();
+    Ok(bytes)
+}
@@ -0,0 +1,297 @@
+//! The threads running the workers, and the handles to control them from Rust.
+
+use super::{scope, WorkerConfig};
+use crate::{
+    event_loop::{RemoteSender, RemoteTask},
+    EventLoop, Events, Permissions, SerializedValue,
+};
+use boa_engine::{context::ContextBuilder, job::JobQueue, Source};
+use std::{
+    io,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex, PoisonError,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// An event sent by a worker to its owner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkerEvent {
+    /// A message posted by the worker with `postMessage`.
+    Message(SerializedValue),
+    /// An uncaught error of the worker, thrown by its script or by its `onmessage` handler.
+    Error(String),
+}
+
+/// Where a worker sends its events.
+pub(super) trait Outbox: Send {
+    /// Sends `event` to the owner of the worker.
+    fn send(&self, event: WorkerEvent);
+
+    /// Notifies the owner of the worker that the worker has exited. No events are sent
+    /// afterwards.
+    fn exit(&self);
+}
+
+/// The outbox of a [`WorkerHandle`], which is a channel closed when the worker exits.
+struct ChannelOutbox(Mutex<Option<Sender<WorkerEvent>>>);
+
+impl Outbox for ChannelOutbox {
+    fn send(&self, event: WorkerEvent) {
+        if let Some(sender) = &*self.0.lock().unwrap_or_else(PoisonError::into_inner) {
+            // The owner doesn't listen to the worker anymore if the receiver was dropped.
+            drop(sender.send(event));
+        }
+    }
+
+    fn exit(&self) {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).take();
+    }
+}
+
+/// Notifies the owner of a worker when the worker exits, even if it panics.
+struct ExitGuard(Rc<dyn Outbox>);
+
+impl Drop for ExitGuard {
+    fn drop(&mut self) {
+        self.0.exit();
+    }
+}
+
+/// Loads the source code of a worker script, on the thread of the worker.
+pub(super) type ScriptLoader = Box<dyn FnOnce() -> Result<String, String> + Send>;
+
+/// The thread of a running worker.
+///
+/// Dropping a `WorkerThread` terminates the worker.
+#[derive(Debug)]
+pub(super) struct WorkerThread {
+    sender: RemoteSender,
+    terminated: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WorkerThread {
+    /// Spawns a new worker thread, running the script loaded by `script` in a new context
+    /// initialized by `config` and restricted by `permissions`, and sending the events of the
+    /// worker to `outbox`.
+    pub(super) fn spawn(
+        script: ScriptLoader,
+        config: &WorkerConfig,
+        permissions: Option<Permissions>,
+        outbox: Box<dyn Outbox>,
+    ) -> io::Result<Self> {
+        let channel = RemoteSender::channel();
+        let sender = channel.0.clone();
+        let terminated = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let terminated = terminated.clone();
+            let config = config.clone();
+            thread::Builder::new()
+                .name("boa-worker".to_owned())
+                .spawn(move || {
+                    run(
+                        script,
+                        &config,
+                        permissions,
+                        channel,
+                        &terminated,
+                        &outbox.into(),
+                    );
+                })?
+        };
+        Ok(Self {
+            sender,
+            terminated,
+            thread: Some(thread),
+        })
+    }
+
+    /// Sends a message to the `onmessage` handler of the worker.
+    ///
+    /// The message is dropped if the worker has exited.
+    pub(super) fn post_message(&self, message: SerializedValue) {
+        let terminated = self.terminated.clone();
+        self.send(Box::new(move |_, context| {
+            if !terminated.load(Ordering::Acquire) {
+                scope::dispatch_message(&message, context);
+            }
+        }));
+    }
+
+    /// Terminates the worker, discarding its pending tasks.
+    ///
+    /// A worker that is running a task is terminated once the task finishes.
+    pub(super) fn terminate(&self) {
+        self.terminated.store(true, Ordering::Release);
+        self.send(Box::new(|event_loop, _| event_loop.shutdown()));
+    }
+
+    /// Blocks until the worker thread exits.
+    pub(super) fn join(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            // Panics of the worker are already reported by the default panic hook.
+            drop(thread.join());
+        }
+    }
+
+    /// Sends a task to the event loop of the worker, which is dropped if the worker has exited.
+    fn send(&self, task: RemoteTask) {
+        self.sender.send(task);
+    }
+}
+
+impl Drop for WorkerThread {
+    fn drop(&mut self) {
+        self.terminate();
+    }
+}
+
+/// Runs a worker on the current thread, until it closes itself, is terminated, or has no more
+/// tasks and no `onmessage` handler.
+fn run(
+    script: ScriptLoader,
+    config: &WorkerConfig,
+    permissions: Option<Permissions>,
+    channel: (RemoteSender, Receiver<RemoteTask>),
+    terminated: &AtomicBool,
+    outbox: &Rc<dyn Outbox>,
+) {
+    let _guard = ExitGuard(outbox.clone());
+    let event_loop = Rc::new(EventLoop::with_remote_channel(channel));
+    let context = &mut match ContextBuilder::new().job_queue(event_loop.clone()).build() {
+        Ok(context) => context,
+        Err(e) => return outbox.send(WorkerEvent::Error(e.to_string())),
+    };
+    if let Some(permissions) = permissions {
+        permissions.register(context);
+    }
+    let scope = Events::register(&event_loop, context)
+        .and_then(|()| scope::register(&event_loop, outbox.clone(), context));
+    if let Err(e) = scope {
+        return outbox.send(WorkerEvent::Error(e.to_string()));
+    }
+    (config.init)(context, &event_loop);
+
+    match script() {
+        Ok(source) => {
+            if let Err(e) = context.eval(Source::from_bytes(&source)) {
+                outbox.send(WorkerEvent::Error(e.to_string()));
+            }
+        }
+        Err(e) => return outbox.send(WorkerEvent::Error(e)),
+    }
+
+    loop {
+        event_loop.run_jobs(context);
+        if terminated.load(Ordering::Acquire)
+            || event_loop.is_shut_down()
+            || !scope::is_listening(context)
+        {
+            break;
+        }
+        event_loop.run_next_remote_task(context);
+    }
+}
+
+/// A worker spawned from Rust, exchanging [`SerializedValue`]s with its owner.
+///
+/// The worker runs until its script closes it with `close()`, until it is terminated, or until
+/// it has no more tasks to run and no `onmessage` handler. Dropping the handle terminates the
+/// worker.
+///
+/// ```
+/// use boa_engine::{js_string, Context, JsValue};
+/// use boa_runtime::{SerializedValue, WorkerConfig, WorkerEvent, WorkerHandle};
+///
+/// let worker = WorkerHandle::spawn(
+///     "onmessage = (e) => postMessage(e.data * 2);",
+///     &WorkerConfig::new(),
+/// )
+/// .unwrap();
+///
+/// let context = &mut Context::default();
+/// let message = SerializedValue::serialize(&JsValue::from(21), &[], context).unwrap();
+/// worker.post_message(message);
+///
+/// let Some(WorkerEvent::Message(reply)) = worker.recv() else {
+///     unreachable!("the worker replies with a message");
+/// };
+/// assert_eq!(reply.deserialize(context).unwrap(), JsValue::from(42));
+/// ```
+#[derive(Debug)]
+pub struct WorkerHandle {
+    thread: WorkerThread,
+    events: Receiver<WorkerEvent>,
+}
+
+impl WorkerHandle {
+    /// Spawns a new worker running the script `source`, in a new context initialized by the
+    /// initializer of `config` and restricted by its permissions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the thread of the worker cannot be spawned.
+    pub fn spawn(source: impl Into<String>, config: &WorkerConfig) -> io::Result<Self> {
+        let source = source.into();
+        let (sender, events) = mpsc::channel();
+        let outbox = ChannelOutbox(Mutex::new(Some(sender)));
+        let thread = WorkerThread::spawn(
+            Box::new(move || Ok(source)),
+            config,
+            config.permissions.clone(),
+            Box::new(outbox),
+        )?;
+        Ok(Self { thread, events })
+    }
+
+    /// Posts a message to the worker, which is received by its `onmessage` handler.
+    ///
+    /// The message is dropped if the worker has exited.
+    pub fn post_message(&self, message: SerializedValue) {
+        self.thread.post_message(message);
+    }
+
+    /// Blocks until the worker sends an event, returning `None` once the worker has exited and
+    /// all its events were received.
+    #[must_use]
+    pub fn recv(&self) -> Option<WorkerEvent> {
+        self.events.recv().ok()
+    }
+
+    /// Returns the next event sent by the worker, without blocking.
+    ///
+    /// Returns `None` if there is no pending event.
+    #[must_use]
+    pub fn try_recv(&self) -> Option<WorkerEvent> {
+        self.events.try_recv().ok()
+    }
+
+    /// Blocks until the worker sends an event or until `timeout` elapses.
+    ///
+    /// Returns `None` if the timeout elapsed, or if the worker has exited and all its events were
+    /// received.
+    #[must_use]
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<WorkerEvent> {
+        self.events.recv_timeout(timeout).ok()
+    }
+
+    /// Terminates the worker, discarding its pending tasks.
+    ///
+    /// A worker that is running a task is terminated once the task finishes, since running
+    /// scripts cannot be interrupted.
+    pub fn terminate(&self) {
+        self.thread.terminate();
+    }
+
+    /// Blocks until the worker exits, returning the events it sent that were not received yet.
+    #[must_use]
+    pub fn join(mut self) -> Vec<WorkerEvent> {
+        self.thread.join();
+        self.events.try_iter().collect()
+    }
+}
@@ -0,0 +1,235 @@
+//! Boa's implementation of dedicated workers: the `Worker` class, and the global scope of the
+//! scripts it runs.
+//!
+//! Each worker runs its own [`Context`] on a separate OS thread. Since garbage collected values
+//! cannot leave the thread that created them, the messages exchanged with `postMessage` are
+//! [`SerializedValue`]s, which are deserialized into the receiving context.
+//!
+//! Workers can also be spawned from Rust with [`WorkerHandle::spawn`], which exchanges messages
+//! with the worker without an owning context.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [HTML specification][spec]
+//!
+//! [`SerializedValue`]: crate::SerializedValue
+//! [spec]: https://html.spec.whatwg.org/multipage/workers.html
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API
+
+#[cfg(test)]
+mod tests;
+
+mod handle;
+mod scope;
+mod worker;
+
+#[doc(inline)]
+pub use handle::{WorkerEvent, WorkerHandle};
+#[doc(inline)]
+pub use worker::Worker;
+
+use crate::{
+    events::inherit,
+    structured_clone::{object_list, transfer_list},
+    Event, EventLoop, EventTarget, Permissions, SerializedValue,
+};
+use boa_engine::{
+    js_string,
+    module::{FileSystemSource, ModuleSource},
+    object::JsObject,
+    property::PropertyDescriptor,
+    Context, JsArgs, JsResult, JsString, JsValue,
+};
+use std::{fmt::Debug, rc::Rc, sync::Arc};
+
+/// Loads the source code of the worker script at a specifier.
+type Loader = dyn Fn(&str) -> Result<String, String> + Send + Sync;
+
+/// Initializes the context of a worker.
+type Initializer = dyn Fn(&mut Context, &Rc<EventLoop>) + Send + Sync;
+
+/// The configuration of the workers: how their scripts are loaded, and how their contexts are
+/// initialized.
+///
+/// The `Worker` class resolves the URL of a script against the URL of the script or module
+/// calling it, and reads the resolved path from a [`ModuleSource`], the file system of the host
+/// by default. A loader set with [`WorkerConfig::with_loader`] receives the specifier instead.
+///
+/// The context of a worker always has the event classes and the worker global scope (`self`,
+/// `postMessage`, `onmessage` and `close`). The initializer can add other globals, e.g. the
+/// `console` object or the timer functions, using the event loop of the worker.
+///
+/// ```
+/// use boa_runtime::{Timers, WorkerConfig};
+///
+/// let config = WorkerConfig::new()
+///     .with_loader(|specifier| match specifier {
+///         "echo.js" => Ok("onmessage = (e) => postMessage(e.data);".to_owned()),
+///         _ => Err(format!("cannot find the worker script '{specifier}'")),
+///     })
+///     .with_init(|context, event_loop| {
+///         Timers::register(event_loop, context).expect("the timer functions shouldn't exist");
+///     });
+/// ```
+#[derive(Clone)]
+pub struct WorkerConfig {
+    /// The loader of the worker scripts, which are read from `source` if it is `None`.
+    loader: Option<Arc<Loader>>,
+    source: Arc<dyn ModuleSource + Send + Sync>,
+    init: Arc<Initializer>,
+    permissions: Option<Permissions>,
+}
+
+impl Debug for WorkerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerConfig").finish_non_exhaustive()
+    }
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            loader: None,
+            source: Arc::new(FileSystemSource),
+            init: Arc::new(|_, _| {}),
+            permissions: None,
+        }
+    }
+}
+
+impl WorkerConfig {
+    /// Creates a new `WorkerConfig`, which reads the worker scripts from the file system and
+    /// doesn't add any global to the contexts of the workers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the function that loads the source code of the worker scripts, called on the thread
+    /// of the worker with the specifier passed to the `Worker` constructor.
+    ///
+    /// The returned error is reported as an `error` event of the worker. By default, the scripts
+    /// are read from the [`ModuleSource`] of the workers, which requires the read permission of
+    /// their path.
+    #[must_use]
+    pub fn with_loader<F>(mut self, loader: F) -> Self
+    where
+        F: Fn(&str) -> Result<String, String> + Send + Sync + 'static,
+    {
+        self.loader = Some(Arc::new(loader));
+        self
+    }
+
+    /// Sets the [`ModuleSource`] the worker scripts are read from, which is the file system of
+    /// the host by default, e.g. to read them from the same virtual file system as the modules.
+    #[must_use]
+    pub fn with_source<S>(mut self, source: S) -> Self
+    where
+        S: ModuleSource + Send + Sync + 'static,
+    {
+        self.source = Arc::new(source);
+        self
+    }
+
+    /// Sets the permissions of the workers spawned by [`WorkerHandle::spawn`].
+    ///
+    /// The workers spawned by the `Worker` class always have the permissions of the context
+    /// spawning them.
+    #[must_use]
+    pub fn with_permissions(mut self, permissions: Permissions) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    /// Sets the function that initializes the context of each worker, called on the thread of
+    /// the worker before running its script.
+    #[must_use]
+    pub fn with_init<F>(mut self, init: F) -> Self
+    where
+        F: Fn(&mut Context, &Rc<EventLoop>) + Send + Sync + 'static,
+    {
+        self.init = Arc::new(init);
+        self
+    }
+}
+
+/// The `Worker` class.
+#[derive(Debug, Clone, Copy)]
+pub struct Workers;
+
+impl Workers {
+    /// Registers the `Worker` class in the global object of the context, spawning the workers
+    /// with `config` and receiving their messages on `event_loop`.
+    ///
+    /// The event classes must already be registered, e.g. by [`Events::register`], and
+    /// `event_loop` should be the [`JobQueue`] of the context. The event loop keeps running while
+    /// any worker is running.
+    ///
+    /// [`Events::register`]: crate::Events::register
+    /// [`JobQueue`]: boa_engine::job::JobQueue
+    ///
+    /// # Errors
+    ///
+    /// This will return an error if the class cannot be defined in the global object, or if the
+    /// event classes are not registered.
+    pub fn register(
+        event_loop: &Rc<EventLoop>,
+        config: WorkerConfig,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        context.register_global_class::<Worker>()?;
+        inherit::<Worker, EventTarget>(context)?;
+        context
+            .realm()
+            .host_defined_mut()
+            .insert(worker::WorkerHost::new(event_loop.clone(), config));
+        Ok(())
+    }
+}
+
+/// Creates a trusted `message` event carrying `data`.
+fn message_event(data: JsValue, context: &mut Context) -> JsResult<JsObject> {
+    event_with(js_string!("message"), js_string!("data"), data, context)
+}
+
+/// Creates a trusted `error` event for an uncaught error of a worker.
+fn error_event(message: &str, context: &mut Context) -> JsResult<JsObject> {
+    let message = JsString::from(message);
+    event_with(
+        js_string!("error"),
+        js_string!("message"),
+        message.into(),
+        context,
+    )
+}
+
+/// Creates a trusted event of type `event_type` with a read-only `key` property.
+fn event_with(
+    event_type: JsString,
+    key: JsString,
+    value: JsValue,
+    context: &mut Context,
+) -> JsResult<JsObject> {
+    let event = Event::create_trusted(event_type, context)?;
+    event.define_property_or_throw(
+        key,
+        PropertyDescriptor::builder()
+            .value(value)
+            .writable(false)
+            .enumerable(true)
+            .configurable(true),
+        context,
+    )?;
+    Ok(event)
+}
+
+/// Serializes the arguments of a `postMessage` method, which are the message and either a
+/// transfer list or a `StructuredSerializeOptions` dictionary.
+fn serialize_message(args: &[JsValue], context: &mut Context) -> JsResult<SerializedValue> {
+    let options = args.get_or_undefined(1);
+    let transfer = match options.as_object() {
+        Some(transfer) if transfer.is_array() => object_list(options, context)?,
+        _ => transfer_list(options, context)?,
+    };
+    SerializedValue::serialize(args.get_or_undefined(0), &transfer, context)
+}
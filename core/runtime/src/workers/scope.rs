@@ -0,0 +1,134 @@
+//! The global scope of the scripts run by workers: `self`, `postMessage`, `onmessage` and
+//! `close`.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [HTML specification][spec]
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/workers.html#dedicated-workers-and-the-dedicatedworkerglobalscope-interface
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/DedicatedWorkerGlobalScope
+
+use super::{
+    handle::{Outbox, WorkerEvent},
+    message_event, serialize_message,
+};
+use crate::{EventLoop, SerializedValue};
+use boa_engine::{
+    js_string, native_function::NativeFunction, property::Attribute, Context, JsData,
+    JsNativeError, JsResult, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::rc::Rc;
+
+/// The state of the global scope of a worker, stored in the host defined data of its realm.
+#[derive(Trace, Finalize, JsData)]
+struct WorkerScope {
+    // SAFETY: See the garbage collection section of `EventLoop`.
+    #[unsafe_ignore_trace]
+    event_loop: Rc<EventLoop>,
+    // SAFETY: Outboxes are `Send`, so they cannot contain any garbage collected values.
+    #[unsafe_ignore_trace]
+    outbox: Rc<dyn Outbox>,
+}
+
+/// Defines the global scope of a worker in the context, sending the messages posted by the
+/// worker to `outbox`.
+pub(super) fn register(
+    event_loop: &Rc<EventLoop>,
+    outbox: Rc<dyn Outbox>,
+    context: &mut Context,
+) -> JsResult<()> {
+    let global = context.global_object();
+    context.register_global_property(js_string!("self"), global, Attribute::all())?;
+    context.register_global_property(js_string!("onmessage"), JsValue::null(), Attribute::all())?;
+    context.register_global_builtin_callable(
+        js_string!("postMessage"),
+        1,
+        NativeFunction::from_fn_ptr(post_message),
+    )?;
+    context.register_global_builtin_callable(
+        js_string!("close"),
+        0,
+        NativeFunction::from_fn_ptr(close),
+    )?;
+    context.realm().host_defined_mut().insert(WorkerScope {
+        event_loop: event_loop.clone(),
+        outbox,
+    });
+    Ok(())
+}
+
+/// Returns `true` if the worker has an `onmessage` handler, which keeps it running while it
+/// waits for messages.
+pub(super) fn is_listening(context: &mut Context) -> bool {
+    context
+        .global_object()
+        .get(js_string!("onmessage"), context)
+        .is_ok_and(|handler| handler.is_callable())
+}
+
+/// Calls the `onmessage` handler of the worker with a `message` event carrying `message`.
+///
+/// Uncaught errors are sent to the owner of the worker as [`WorkerEvent::Error`]s.
+pub(super) fn dispatch_message(message: &SerializedValue, context: &mut Context) {
+    let result: JsResult<()> = (|| {
+        let global = context.global_object();
+        let handler = global.get(js_string!("onmessage"), context)?;
+        let Some(handler) = handler.as_callable() else {
+            return Ok(());
+        };
+        let data = message.deserialize(context)?;
+        let event = message_event(data, context)?;
+        handler.call(&global.into(), &[event.into()], context)?;
+        Ok(())
+    })();
+    if let Err(e) = result {
+        let outbox = with_scope(context, |scope| scope.outbox.clone());
+        if let Some(outbox) = outbox {
+            outbox.send(WorkerEvent::Error(e.to_string()));
+        }
+    }
+}
+
+/// Calls `f` with the worker scope of the context, if it exists.
+fn with_scope<R>(context: &Context, f: impl FnOnce(&WorkerScope) -> R) -> Option<R> {
+    context.realm().host_defined().get::<WorkerScope>().map(f)
+}
+
+/// `postMessage(message, transfer)`
+///
+/// Sends a structured clone of `message` to the owner of the worker, transferring the
+/// `ArrayBuffer`s of `transfer`.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [HTML specification][spec]
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/workers.html#dom-dedicatedworkerglobalscope-postmessage
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/DedicatedWorkerGlobalScope/postMessage
+fn post_message(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    let message = serialize_message(args, context)?;
+    let outbox = with_scope(context, |scope| scope.outbox.clone()).ok_or_else(|| {
+        JsNativeError::typ().with_message("postMessage can only be called by a worker")
+    })?;
+    outbox.send(WorkerEvent::Message(message));
+    Ok(JsValue::undefined())
+}
+
+/// `close()`
+///
+/// Closes the worker, discarding its pending tasks. The current task still runs to completion.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [HTML specification][spec]
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/workers.html#dom-dedicatedworkerglobalscope-close
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/DedicatedWorkerGlobalScope/close
+#[allow(clippy::unnecessary_wraps)]
+fn close(_: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+    if let Some(event_loop) = with_scope(context, |scope| scope.event_loop.clone()) {
+        event_loop.shutdown();
+    }
+    Ok(JsValue::undefined())
+}
@@ -0,0 +1,248 @@
+use super::{WorkerConfig, WorkerEvent, WorkerHandle, Workers};
+use crate::{
+    test::{run_test_actions, TempDir, TestAction},
+    EventLoop, Permissions, SerializedValue, Timers,
+};
+use boa_engine::{js_string, property::Attribute, Context, JsValue};
+use indoc::indoc;
+use std::time::Duration;
+
+/// Loads the worker scripts of the tests.
+fn load_script(specifier: &str) -> Result<String, String> {
+    match specifier {
+        "echo.js" => Ok("onmessage = (e) => postMessage(e.data);".to_owned()),
+        "once.js" => Ok(indoc! {r#"
+            onmessage = (e) => {
+                postMessage(e.data + 1);
+                close();
+                postMessage("after close");
+            };
+        "#}
+        .to_owned()),
+        "timer.js" => Ok("setTimeout(() => postMessage('done'), 10);".to_owned()),
+        "throw.js" => Ok("throw new Error('boom');".to_owned()),
+        _ => Err(format!("cannot find the worker script '{specifier}'")),
+    }
+}
+
+/// Registers the `Worker` class on the event loop of the test context.
+fn register_workers(config: WorkerConfig, context: &mut Context) {
+    let event_loop = EventLoop::of(context).expect("the test context runs on an event loop");
+    Workers::register(&event_loop, config, context).expect("failed to register workers");
+}
+
+#[test]
+fn post_message() {
+    run_test_actions([
+        TestAction::inspect_context(|context| {
+            let config =
+                WorkerConfig::new()
+                    .with_loader(load_script)
+                    .with_init(|context, event_loop| {
+                        Timers::register(event_loop, context).expect("failed to register timers");
+                    });
+            register_workers(config, context);
+        }),
+        TestAction::run(indoc! {r#"
+            const worker = new Worker("echo.js");
+            const received = [];
+            worker.onmessage = (e) => {
+                received.push(e.data);
+                if (received.length === 2) {
+                    worker.terminate();
+                }
+            };
+            const buffer = new Uint8Array([1, 2, 3]).buffer;
+            worker.postMessage({ text: "hello", list: [1, 2] });
+            worker.postMessage(buffer, [buffer]);
+        "#}),
+        TestAction::assert_eq("buffer.byteLength", 0),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("received.length", 2),
+        TestAction::assert_eq("received[0].text", js_string!("hello")),
+        TestAction::assert_eq("received[0].list[1]", 2),
+        TestAction::assert_eq("new Uint8Array(received[1]).join()", js_string!("1,2,3")),
+        TestAction::assert_eq(
+            "Object.prototype.toString.call(worker)",
+            js_string!("[object Worker]"),
+        ),
+        TestAction::assert_eq("worker instanceof EventTarget", true),
+    ]);
+}
+
+#[test]
+fn worker_exits() {
+    run_test_actions([
+        TestAction::inspect_context(|context| {
+            let config =
+                WorkerConfig::new()
+                    .with_loader(load_script)
+                    .with_init(|context, event_loop| {
+                        Timers::register(event_loop, context).expect("failed to register timers");
+                    });
+            register_workers(config, context);
+        }),
+        TestAction::run(indoc! {r#"
+            const received = [];
+            const once = new Worker("once.js");
+            once.addEventListener("message", (e) => received.push(e.data));
+            once.postMessage(1);
+            const timer = new Worker("timer.js");
+            timer.onmessage = (e) => received.push(e.data);
+        "#}),
+        // The event loop returns once both workers have exited.
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("received.length", 3),
+        TestAction::assert_eq("received.includes(2)", true),
+        TestAction::assert_eq("received.includes('after close')", true),
+        TestAction::assert_eq("received.includes('done')", true),
+    ]);
+}
+
+#[test]
+fn errors() {
+    run_test_actions([
+        TestAction::inspect_context(|context| {
+            let config =
+                WorkerConfig::new()
+                    .with_loader(load_script)
+                    .with_init(|context, event_loop| {
+                        Timers::register(event_loop, context).expect("failed to register timers");
+                    });
+            register_workers(config, context);
+        }),
+        TestAction::run(indoc! {r#"
+            const errors = [];
+            new Worker("throw.js").onerror = (e) => errors.push(e.message);
+            new Worker("missing.js").addEventListener("error", (e) => errors.push(e.message));
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("errors.length", 2),
+        TestAction::assert_eq("errors.some((e) => e.includes('boom'))", true),
+        TestAction::assert_eq(
+            "errors.includes(\"cannot find the worker script 'missing.js'\")",
+            true,
+        ),
+        TestAction::assert_eq(
+            "try { new Worker('echo.js').postMessage(() => {}) } catch (e) { e.message }",
+            js_string!("DataCloneError: the object cannot be cloned"),
+        ),
+    ]);
+}
+
+#[test]
+fn worker_handle() {
+    const DOUBLE: &str = indoc! {r#"
+        onmessage = (e) => {
+            if (e.data === "throw") {
+                throw new Error("bad message");
+            }
+            postMessage(e.data * 2);
+        };
+    "#};
+
+    run_test_actions([
+        TestAction::assert_context(|context| {
+            let worker = WorkerHandle::spawn(DOUBLE, &WorkerConfig::new()).unwrap();
+            let message = SerializedValue::serialize(&JsValue::from(21), &[], context).unwrap();
+            worker.post_message(message);
+            matches!(
+                worker.recv_timeout(Duration::from_secs(10)),
+                Some(WorkerEvent::Message(reply))
+                    if reply.deserialize(context).unwrap() == JsValue::from(42)
+            )
+        }),
+        TestAction::assert_context(|context| {
+            let worker = WorkerHandle::spawn(DOUBLE, &WorkerConfig::new()).unwrap();
+            let message =
+                SerializedValue::serialize(&js_string!("throw").into(), &[], context).unwrap();
+            worker.post_message(message);
+            matches!(
+                worker.recv_timeout(Duration::from_secs(10)),
+                Some(WorkerEvent::Error(error)) if error.contains("bad message")
+            ) && worker.try_recv().is_none()
+        }),
+        TestAction::assert_context(|_| {
+            let worker = WorkerHandle::spawn(DOUBLE, &WorkerConfig::new()).unwrap();
+            worker.terminate();
+            worker.join().is_empty()
+        }),
+        TestAction::assert_context(|_| {
+            let worker =
+                WorkerHandle::spawn("postMessage(1); postMessage(2);", &WorkerConfig::new())
+                    .unwrap();
+            worker.join().len() == 2
+        }),
+    ]);
+}
+
+#[test]
+fn permissions() {
+    // The workers inherit the permissions of the context spawning them.
+    run_test_actions([
+        TestAction::inspect_context(|context| {
+            let config = WorkerConfig::new()
+                .with_loader(|_| Ok("postMessage(allowed);".to_owned()))
+                .with_init(|context, _| {
+                    let allowed = Permissions::of(context)
+                        .is_some_and(|permissions| permissions.can_connect("allowed.test", None));
+                    context
+                        .register_global_property(js_string!("allowed"), allowed, Attribute::all())
+                        .unwrap();
+                });
+            register_workers(config, context);
+            Permissions::none()
+                .allow_net("allowed.test")
+                .register(context);
+        }),
+        TestAction::run(indoc! {r#"
+            let result;
+            new Worker("allowed.js").onmessage = (e) => {
+                result = e.data;
+            };
+        "#}),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("result", true),
+    ]);
+
+    // Reading the scripts from the file system requires the read permission.
+    run_test_actions([
+        TestAction::inspect_context(|context| {
+            register_workers(WorkerConfig::new(), context);
+            Permissions::none().register(context);
+        }),
+        TestAction::assert(
+            "try { new Worker('worker.js') } catch (e) { e.message.startsWith('PermissionDenied') }",
+        ),
+    ]);
+}
+
+#[test]
+fn script_urls() {
+    // The scripts are resolved against the URL of the script creating the worker.
+    let dir = TempDir::new("workers-script-urls");
+    dir.write(
+        "app/main.js",
+        indoc! {r#"
+            const results = [];
+            new Worker("./lib/worker.js").onmessage = (e) => results.push(e.data);
+        "#},
+    )
+    .write("app/lib/worker.js", "postMessage('relative');")
+    .write("absolute.js", "postMessage('absolute');");
+    let absolute = url::Url::from_file_path(dir.path().join("absolute.js")).unwrap();
+
+    run_test_actions([
+        TestAction::inspect_context(|context| register_workers(WorkerConfig::new(), context)),
+        TestAction::run_file(dir.path().join("app").join("main.js")),
+        TestAction::run(format!(
+            "new Worker('{absolute}').onmessage = (e) => results.push(e.data);"
+        )),
+        TestAction::inspect_context(Context::run_jobs),
+        TestAction::assert_eq("results.sort().join()", js_string!("absolute,relative")),
+        TestAction::assert_eq(
+            "try { new Worker('https://example.com/worker.js') } catch (e) { e.name }",
+            js_string!("TypeError"),
+        ),
+    ]);
+}
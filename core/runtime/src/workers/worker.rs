@@ -0,0 +1,371 @@
+//! Boa's implementation of the `Worker` class.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [HTML specification][spec]
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/workers.html#dedicated-workers-and-the-worker-interface
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Worker
+
+use super::{
+    error_event,
+    handle::{Outbox, ScriptLoader, WorkerEvent, WorkerThread},
+    message_event, serialize_message, WorkerConfig,
+};
+use crate::{event_loop::RemoteSender, EventLoop, EventTarget, Permissions};
+use boa_engine::{
+    class::{Class, ClassBuilder},
+    js_string,
+    native_function::NativeFunction,
+    object::JsObject,
+    property::Attribute,
+    Context, JsArgs, JsData, JsNativeError, JsResult, JsSymbol, JsValue,
+};
+use boa_gc::{Finalize, Trace};
+use std::{path::PathBuf, rc::Rc};
+use url::Url;
+
+/// Resolves the URL `specifier` of a worker script against the URL of the running script or
+/// module, or against the current directory if it has no path, returning the path of the script.
+///
+/// Only `file:` URLs are supported.
+fn resolve_script(specifier: &str, context: &mut Context) -> JsResult<PathBuf> {
+    let referrer = context.active_referrer();
+    let base = std::env::current_dir()
+        .ok()
+        .and_then(|dir| match referrer.path() {
+            Some(path) => Url::from_file_path(dir.join(path)).ok(),
+            None => Url::from_directory_path(dir).ok(),
+        });
+    let invalid =
+        || JsNativeError::syntax().with_message(format!("invalid worker script URL '{specifier}'"));
+    let url = match base {
+        Some(base) => base.join(specifier),
+        None => Url::parse(specifier),
+    }
+    .map_err(|_| invalid())?;
+    if url.scheme() != "file" {
+        return Err(JsNativeError::typ()
+            .with_message(format!(
+                "cannot load the worker script '{specifier}': only file URLs are supported"
+            ))
+            .into());
+    }
+    url.to_file_path().map_err(|()| invalid().into())
+}
+
+/// The workers spawned by a context, stored in the host defined data of its realm.
+#[derive(Trace, Finalize, JsData)]
+pub(super) struct WorkerHost {
+    // SAFETY: See the garbage collection section of `EventLoop`.
+    #[unsafe_ignore_trace]
+    event_loop: Rc<EventLoop>,
+    // SAFETY: The configuration is `Send`, so it cannot contain any garbage collected values.
+    #[unsafe_ignore_trace]
+    config: WorkerConfig,
+    /// The running workers, which are kept alive until they exit or are terminated.
+    workers: Vec<(u32, JsObject)>,
+    next_id: u32,
+}
+
+impl WorkerHost {
+    /// Creates a new `WorkerHost` without workers.
+    pub(super) fn new(event_loop: Rc<EventLoop>, config: WorkerConfig) -> Self {
+        Self {
+            event_loop,
+            config,
+            workers: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Calls `f` with the worker host of the context.
+    fn with<R>(context: &Context, f: impl FnOnce(&mut Self) -> R) -> JsResult<R> {
+        let mut host_defined = context.realm().host_defined_mut();
+        let host = host_defined.get_mut::<Self>().ok_or_else(|| {
+            JsNativeError::typ().with_message("the Worker class is not registered")
+        })?;
+        Ok(f(host))
+    }
+
+    /// Removes the worker with identifier `id` from the running workers, returning it if it was
+    /// still running.
+    fn remove(&mut self, id: u32) -> Option<JsObject> {
+        let index = self.workers.iter().position(|(worker, _)| *worker == id)?;
+        let (_, worker) = self.workers.swap_remove(index);
+        self.event_loop.release_remote();
+        Some(worker)
+    }
+}
+
+/// The outbox of a `Worker` object, which sends the events of the worker to the event loop of
+/// its owner.
+struct ParentOutbox {
+    sender: RemoteSender,
+    id: u32,
+}
+
+impl Outbox for ParentOutbox {
+    fn send(&self, event: WorkerEvent) {
+        let id = self.id;
+        self.sender.send(Box::new(move |event_loop, context| {
+            let worker = WorkerHost::with(context, |host| {
+                host.workers
+                    .iter()
+                    .find(|(worker, _)| *worker == id)
+                    .map(|(_, worker)| worker.clone())
+            });
+            // Events sent after the worker was terminated are dropped.
+            let Ok(Some(worker)) = worker else {
+                return;
+            };
+            let result = match event {
+                WorkerEvent::Message(message) => message
+                    .deserialize(context)
+                    .and_then(|data| message_event(data, context)),
+                WorkerEvent::Error(message) => error_event(&message, context),
+            }
+            .and_then(|event| EventTarget::dispatch(&worker, &event, context));
+            if let Err(e) = result {
+                event_loop.report_error(&e, context);
+            }
+        }));
+    }
+
+    fn exit(&self) {
+        let id = self.id;
+        self.sender.send(Box::new(move |_, context| {
+            // The worker host is always registered in the context owning the worker.
+            drop(WorkerHost::with(context, |host| host.remove(id)));
+        }));
+    }
+}
+
+/// The `Worker` class, an `EventTarget` running a script on a separate thread.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [HTML specification][spec]
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/workers.html#worker
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Worker
+#[derive(Debug, Trace, Finalize, JsData)]
+pub struct Worker {
+    target: EventTarget,
+    id: u32,
+    // SAFETY: Worker threads are `Send`, so they cannot contain any garbage collected values.
+    #[unsafe_ignore_trace]
+    thread: WorkerThread,
+}
+
+impl Worker {
+    /// Returns the `EventTarget` data of the worker.
+    pub(crate) fn target_mut(&mut self) -> &mut EventTarget {
+        &mut self.target
+    }
+
+    /// Checks that `this` is a `Worker` object, returning it.
+    fn this_worker(this: &JsValue) -> JsResult<&JsObject> {
+        this.as_object()
+            .filter(|object| object.is::<Self>())
+            .ok_or_else(|| {
+                JsNativeError::typ()
+                    .with_message("'this' is not a Worker object")
+                    .into()
+            })
+    }
+
+    /// `Worker.prototype.postMessage(message, transfer)`
+    ///
+    /// Sends a structured clone of `message` to the `onmessage` handler of the worker,
+    /// transferring the `ArrayBuffer`s of `transfer`.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/workers.html#dom-worker-postmessage
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Worker/postMessage
+    fn post_message(this: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let worker = Self::this_worker(this)?;
+        let message = serialize_message(args, context)?;
+        let worker = worker.downcast_ref::<Self>().expect("checked above");
+        worker.thread.post_message(message);
+        Ok(JsValue::undefined())
+    }
+
+    /// `Worker.prototype.terminate()`
+    ///
+    /// Terminates the worker, discarding its pending tasks and the events it has not delivered
+    /// yet.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/workers.html#dom-worker-terminate
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Worker/terminate
+    fn terminate(this: &JsValue, _: &[JsValue], context: &mut Context) -> JsResult<JsValue> {
+        let worker = Self::this_worker(this)?;
+        let id = {
+            let worker = worker.downcast_ref::<Self>().expect("checked above");
+            worker.thread.terminate();
+            worker.id
+        };
+        WorkerHost::with(context, |host| host.remove(id))?;
+        Ok(JsValue::undefined())
+    }
+
+    /// `get Worker.prototype.onmessage`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/workers.html#handler-worker-onmessage
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Worker/message_event
+    fn get_onmessage(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let worker = Self::this_worker(this)?;
+        Ok(EventTarget::event_handler(worker, &js_string!("message")))
+    }
+
+    /// `set Worker.prototype.onmessage`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/workers.html#handler-worker-onmessage
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Worker/message_event
+    fn set_onmessage(this: &JsValue, args: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let worker = Self::this_worker(this)?;
+        EventTarget::set_event_handler(worker, &js_string!("message"), args.get_or_undefined(0));
+        Ok(JsValue::undefined())
+    }
+
+    /// `get Worker.prototype.onerror`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/workers.html#handler-abstractworker-onerror
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Worker/error_event
+    fn get_onerror(this: &JsValue, _: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let worker = Self::this_worker(this)?;
+        Ok(EventTarget::event_handler(worker, &js_string!("error")))
+    }
+
+    /// `set Worker.prototype.onerror`
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/workers.html#handler-abstractworker-onerror
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/Worker/error_event
+    fn set_onerror(this: &JsValue, args: &[JsValue], _: &mut Context) -> JsResult<JsValue> {
+        let worker = Self::this_worker(this)?;
+        EventTarget::set_event_handler(worker, &js_string!("error"), args.get_or_undefined(0));
+        Ok(JsValue::undefined())
+    }
+}
+
+impl Class for Worker {
+    const NAME: &'static str = "Worker";
+    const LENGTH: usize = 1;
+
+    fn init(class: &mut ClassBuilder<'_>) -> JsResult<()> {
+        let realm = class.context().realm().clone();
+        let accessor = |f| NativeFunction::from_fn_ptr(f).to_js_function(&realm);
+        let (get_onmessage, set_onmessage) =
+            (accessor(Self::get_onmessage), accessor(Self::set_onmessage));
+        let (get_onerror, set_onerror) = (accessor(Self::get_onerror), accessor(Self::set_onerror));
+
+        class
+            .accessor(
+                js_string!("onmessage"),
+                Some(get_onmessage),
+                Some(set_onmessage),
+                Attribute::CONFIGURABLE | Attribute::ENUMERABLE,
+            )
+            .accessor(
+                js_string!("onerror"),
+                Some(get_onerror),
+                Some(set_onerror),
+                Attribute::CONFIGURABLE | Attribute::ENUMERABLE,
+            )
+            .method(
+                js_string!("postMessage"),
+                1,
+                NativeFunction::from_fn_ptr(Self::post_message),
+            )
+            .method(
+                js_string!("terminate"),
+                0,
+                NativeFunction::from_fn_ptr(Self::terminate),
+            )
+            .property(
+                JsSymbol::to_string_tag(),
+                js_string!("Worker"),
+                Attribute::CONFIGURABLE,
+            );
+        Ok(())
+    }
+
+    fn data_constructor(_: &JsValue, args: &[JsValue], context: &mut Context) -> JsResult<Self> {
+        let specifier = args
+            .get_or_undefined(0)
+            .to_string(context)?
+            .to_std_string_escaped();
+        let (id, config, sender) = WorkerHost::with(context, |host| {
+            let id = host.next_id;
+            host.next_id = host.next_id.wrapping_add(1);
+            (id, host.config.clone(), host.event_loop.remote_sender())
+        })?;
+
+        let script: ScriptLoader = if let Some(loader) = config.loader.clone() {
+            Box::new(move || loader(&specifier))
+        } else {
+            let path = resolve_script(&specifier, context)?;
+            Permissions::check_read(&path, context)?;
+            let source = config.source.clone();
+            Box::new(move || {
+                let bytes = source.read(&path).map_err(|e| e.to_string());
+                bytes
+                    .and_then(|bytes| String::from_utf8(bytes).map_err(|e| e.to_string()))
+                    .map_err(|e| format!("cannot load the worker script '{specifier}': {e}"))
+            })
+        };
+        let thread = WorkerThread::spawn(
+            script,
+            &config,
+            Permissions::of(context),
+            Box::new(ParentOutbox { sender, id }),
+        )
+        .map_err(|e| {
+            JsNativeError::error().with_message(format!("cannot spawn the worker thread: {e}"))
+        })?;
+
+        Ok(Self {
+            target: EventTarget::default(),
+            id,
+            thread,
+        })
+    }
+
+    fn object_constructor(
+        instance: &JsObject,
+        _: &[JsValue],
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let id = instance
+            .downcast_ref::<Self>()
+            .expect("the object was just constructed")
+            .id;
+        WorkerHost::with(context, |host| {
+            host.event_loop.retain_remote();
+            host.workers.push((id, instance.clone()));
+        })
+    }
+}